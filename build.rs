@@ -0,0 +1,24 @@
+fn main() {
+    #[cfg(feature = "capi")]
+    generate_c_header();
+}
+
+/// Generate the C header from the `capi` module into `OUT_DIR`
+///
+/// `tests/capi_tests.rs` checks it against the checked-in `include/engine_2d.h`.
+#[cfg(feature = "capi")]
+fn generate_c_header() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("cargo sets CARGO_MANIFEST_DIR");
+    let out_dir = std::env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("cbindgen.toml is valid");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{}/src/capi.rs", crate_dir))
+        .generate()
+        .expect("the C API header generates")
+        .write_to_file(format!("{}/engine_2d.h", out_dir));
+}
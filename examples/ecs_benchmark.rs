@@ -0,0 +1,120 @@
+//! Headless ECS iteration benchmark
+//!
+//! Run with `cargo run --release --example ecs_benchmark [entities]`.
+//! Times a two-component movement query over the world's per-type columns, and the same
+//! update over a map of per-entity component maps for comparison.
+
+use engine_2d::ecs::{Entity, Scheduler, Stage, Transform, Velocity, World};
+use glam::Vec2;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const FRAMES: u32 = 300;
+const DT: f32 = 1.0 / 60.0;
+
+/// Average and worst time of `frame` over `FRAMES` runs, after a warm-up
+fn measure(mut frame: impl FnMut()) -> (Duration, Duration) {
+    for _ in 0..30 {
+        frame();
+    }
+    let mut worst = Duration::ZERO;
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        let frame_start = Instant::now();
+        frame();
+        worst = worst.max(frame_start.elapsed());
+    }
+    (start.elapsed() / FRAMES, worst)
+}
+
+fn report(label: &str, (average, worst): (Duration, Duration)) {
+    println!(
+        "{:<28} average {:>8.3} ms, worst {:>8.3} ms per frame",
+        label,
+        average.as_secs_f64() * 1000.0,
+        worst.as_secs_f64() * 1000.0
+    );
+}
+
+fn velocity(i: usize) -> Velocity {
+    Velocity(Vec2::new((i % 7) as f32, (i % 5) as f32))
+}
+
+fn main() {
+    let entities: usize = std::env::args()
+        .nth(1)
+        .and_then(|a| a.parse().ok())
+        .unwrap_or(50_000);
+
+    let mut world = World::new();
+    for i in 0..entities {
+        world.spawn((Transform::default(), velocity(i)));
+    }
+    // Entities the query has to skip
+    for _ in 0..entities / 4 {
+        world.spawn((Transform::default(),));
+    }
+
+    report(
+        "world.query",
+        measure(|| {
+            for (_, (transform, velocity)) in world.query::<(&mut Transform, &Velocity)>() {
+                transform.position += velocity.0 * DT;
+            }
+            world.clear_trackers();
+        }),
+    );
+
+    let mut scheduler = Scheduler::new();
+    scheduler.add_query_system::<(&mut Transform, &Velocity), _>(
+        Stage::Physics,
+        "movement",
+        |_, (transform, velocity), dt| transform.position += velocity.0 * dt,
+    );
+    report(
+        "scheduler query system",
+        measure(|| scheduler.run(&mut world, DT).unwrap()),
+    );
+    black_box(&world);
+
+    // The layout columns replace: each entity owns a map of boxed components
+    let mut per_entity: HashMap<Entity, HashMap<TypeId, Box<dyn Any>>> = HashMap::new();
+    let mut allocator = World::new();
+    for i in 0..entities + entities / 4 {
+        let mut components: HashMap<TypeId, Box<dyn Any>> = HashMap::new();
+        components.insert(TypeId::of::<Transform>(), Box::new(Transform::default()));
+        if i < entities {
+            components.insert(TypeId::of::<Velocity>(), Box::new(velocity(i)));
+        }
+        per_entity.insert(allocator.spawn(()), components);
+    }
+    report(
+        "per-entity component maps",
+        measure(|| {
+            for components in per_entity.values_mut() {
+                let Some(velocity) = components
+                    .get(&TypeId::of::<Velocity>())
+                    .and_then(|v| v.downcast_ref::<Velocity>())
+                    .copied()
+                else {
+                    continue;
+                };
+                if let Some(transform) = components
+                    .get_mut(&TypeId::of::<Transform>())
+                    .and_then(|t| t.downcast_mut::<Transform>())
+                {
+                    transform.position += velocity.0 * DT;
+                }
+            }
+        }),
+    );
+    black_box(&per_entity);
+
+    println!(
+        "{} entities with Transform + Velocity, {} with Transform only",
+        entities,
+        entities / 4
+    );
+}
@@ -0,0 +1,61 @@
+//! Headless particle simulation benchmark
+//!
+//! Run with `cargo run --release --example particle_benchmark [particles] [emitters]`.
+//! Reports the average simulation time per frame against the 60 FPS frame budget.
+
+use engine_2d::particles::{EmitterConfig, OverflowPolicy, ParticleSystem};
+use glam::Vec2;
+use std::time::{Duration, Instant};
+
+const FRAMES: u32 = 600;
+const DT: f32 = 1.0 / 60.0;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let particles: usize = args.next().and_then(|a| a.parse().ok()).unwrap_or(100_000);
+    let emitters: usize = args.next().and_then(|a| a.parse().ok()).unwrap_or(8);
+    let per_emitter = particles / emitters.max(1);
+
+    let mut system = ParticleSystem::new();
+    for i in 0..emitters {
+        let config = EmitterConfig::default()
+            .with_position(Vec2::new(i as f32, 0.0))
+            .with_max_particles(per_emitter)
+            // Keep every emitter saturated: spawn more than the cap every frame
+            .with_spawn_rate(per_emitter as f32)
+            .with_lifetime(0.5, 1.5)
+            .with_speed(1.0, 3.0)
+            .with_forces(Vec2::new(0.0, -9.8), 0.1)
+            .with_overflow(OverflowPolicy::Recycle);
+        let id = system.add_emitter(config);
+        system.emitter_mut(id).unwrap().burst(per_emitter);
+    }
+
+    // Warm up the thread pool and caches
+    for _ in 0..30 {
+        system.update(DT);
+    }
+
+    let mut worst = Duration::ZERO;
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        let frame_start = Instant::now();
+        system.update(DT);
+        worst = worst.max(frame_start.elapsed());
+    }
+    let average = start.elapsed() / FRAMES;
+
+    let budget = Duration::from_secs_f32(DT);
+    println!(
+        "{} particles across {} emitters ({} threads)",
+        system.particle_count(),
+        emitters,
+        rayon::current_num_threads()
+    );
+    println!(
+        "average {:.3} ms, worst {:.3} ms per frame ({:.1}% of the 60 FPS budget)",
+        average.as_secs_f64() * 1000.0,
+        worst.as_secs_f64() * 1000.0,
+        average.as_secs_f64() / budget.as_secs_f64() * 100.0
+    );
+}
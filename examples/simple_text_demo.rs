@@ -507,6 +507,9 @@ fn main() {
         // Configure viewport for UI coordinates (0 to 1, 0 to 1)
         viewport: engine_2d::engine::config::ViewportConfig::ui_based(),
         fallback_font_path: DEFAULT_FONT_PATH.to_string(),
+        idle: engine_2d::engine::config::IdleConfig::default(),
+        metrics: engine_2d::engine::config::MetricsConfig::default(),
+        srgb: false,
     };
 
     let animation = Box::new(SimpleTextDemo::new());
@@ -0,0 +1,207 @@
+/*
+ * C API for engine_2d, built with the `capi` feature.
+ *
+ * The host owns the loop: create an engine, then each frame report input, queue draws
+ * and call e2d_engine_frame. Every function accepts a null engine. Functions that can
+ * fail return false (or null) and leave a message for e2d_last_error.
+ *
+ * Inputs are named like the binding files, e.g. "Keyboard:Space", "Mouse:Left" or
+ * "GamepadAxis:LeftStickX". Strings are UTF-8 and NUL-terminated.
+ */
+
+#ifndef ENGINE_2D_H
+#define ENGINE_2D_H
+
+/* Generated by cbindgen from src/capi.rs; do not edit */
+
+#include <stdbool.h>
+#include <stdint.h>
+
+/**
+ * What `e2d_engine_frame` did, mirroring `TickResult`
+ */
+typedef enum {
+    E2D_FRAME_RESULT_RENDERED = 0,
+    /**
+     * No frame was presented (idle mode or headless)
+     */
+    E2D_FRAME_RESULT_SKIPPED = 1,
+    /**
+     * The engine was asked to close; stop calling `e2d_engine_frame`
+     */
+    E2D_FRAME_RESULT_EXIT = 2,
+} E2dFrameResult;
+
+/**
+ * Opaque engine handle owned by the C host
+ */
+typedef struct E2dEngine E2dEngine;
+
+/**
+ * Window settings for `e2d_engine_create`
+ */
+typedef struct {
+    uint32_t width;
+    uint32_t height;
+    /**
+     * Frame rate cap; 0 for uncapped
+     */
+    uint32_t target_fps;
+    bool vsync;
+    bool fullscreen;
+} E2dConfig;
+
+/**
+ * Straight (not premultiplied) RGBA, each 0 to 1
+ */
+typedef struct {
+    float r;
+    float g;
+    float b;
+    float a;
+} E2dColor;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * The default window settings
+ */
+E2dConfig e2d_config_default(void);
+
+/**
+ * The last error on this thread, or null; valid until the next failing call
+ */
+const char *e2d_last_error(void);
+
+/**
+ * Create an engine with a window (headless without the `opengl` feature); null on failure
+ *
+ * # Safety
+ * `title` must be null or a NUL-terminated string. A null `config` uses the defaults.
+ */
+E2dEngine *e2d_engine_create(const E2dConfig *config, const char *title);
+
+/**
+ * Create an engine with no window or GPU; null on failure
+ *
+ * # Safety
+ * `title` must be null or a NUL-terminated string. A null `config` uses the defaults.
+ */
+E2dEngine *e2d_engine_create_headless(const E2dConfig *config, const char *title);
+
+/**
+ * Shut the engine down and free it
+ */
+void e2d_engine_destroy(E2dEngine *engine);
+
+/**
+ * Run one frame, submitting the queued draws; `delta_time` in seconds, or 0 to measure it
+ */
+E2dFrameResult e2d_engine_frame(E2dEngine *engine, float delta_time);
+
+/**
+ * Ask the engine to stop; the next frame returns `E2D_FRAME_RESULT_EXIT`
+ */
+void e2d_engine_quit(E2dEngine *engine);
+
+/**
+ * Seconds of frame time since the engine started
+ */
+float e2d_engine_elapsed_time(const E2dEngine *engine);
+
+/**
+ * Load a texture for `e2d_draw_sprite`; needs a windowed engine
+ *
+ * # Safety
+ * `path` must be null or a NUL-terminated string.
+ */
+bool e2d_texture_load(E2dEngine *engine, const char *path, uint32_t *out_texture);
+
+/**
+ * Load a font under `name` for `e2d_draw_text`; needs a windowed engine
+ *
+ * # Safety
+ * `name` and `path` must be null or NUL-terminated strings.
+ */
+bool e2d_font_load(E2dEngine *engine, const char *name, const char *path, uint32_t size);
+
+/**
+ * Queue a textured sprite for the next frame, in world units
+ */
+void e2d_draw_sprite(E2dEngine *engine,
+                     uint32_t texture,
+                     float x,
+                     float y,
+                     float width,
+                     float height,
+                     E2dColor color);
+
+/**
+ * Queue text in a loaded font for the next frame
+ *
+ * # Safety
+ * `text` and `font` must be null or NUL-terminated strings.
+ */
+bool e2d_draw_text(E2dEngine *engine,
+                   const char *text,
+                   float x,
+                   float y,
+                   const char *font,
+                   E2dColor color);
+
+/**
+ * Register an action bound to one input, written like `Keyboard:Space` or
+ * `GamepadAxis:LeftStickX`; axes make the action analog
+ *
+ * # Safety
+ * `action_id` and `input` must be null or NUL-terminated strings.
+ */
+bool e2d_input_bind_action(E2dEngine *engine, const char *action_id, const char *input);
+
+/**
+ * Report a button or key going down or up; applied on the next frame
+ *
+ * # Safety
+ * `input` must be null or a NUL-terminated string.
+ */
+bool e2d_input_set(E2dEngine *engine, const char *input, bool pressed);
+
+/**
+ * Report an analog value (axis position or delta); applied on the next frame
+ *
+ * # Safety
+ * `input` must be null or a NUL-terminated string.
+ */
+bool e2d_input_set_value(E2dEngine *engine, const char *input, float value);
+
+/**
+ * Check if an action went down on the last frame
+ *
+ * # Safety
+ * `action_id` must be null or a NUL-terminated string.
+ */
+bool e2d_action_pressed(const E2dEngine *engine, const char *action_id);
+
+/**
+ * Check if an action is held down
+ *
+ * # Safety
+ * `action_id` must be null or a NUL-terminated string.
+ */
+bool e2d_action_held(const E2dEngine *engine, const char *action_id);
+
+/**
+ * An analog action's value, or 1/0 for a digital one
+ *
+ * # Safety
+ * `action_id` must be null or a NUL-terminated string.
+ */
+float e2d_action_value(const E2dEngine *engine, const char *action_id);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ENGINE_2D_H */
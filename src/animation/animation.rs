@@ -1,6 +1,8 @@
 #[cfg(feature = "opengl")]
 use crate::engine::window::{WindowEvent, WindowManager};
 #[cfg(feature = "opengl")]
+use crate::render::lighting::LightingRenderer;
+#[cfg(feature = "opengl")]
 use crate::render::simple_text::SimpleTextRenderer;
 #[cfg(feature = "opengl")]
 use crate::render::sprite::SpriteRenderer;
@@ -11,14 +13,17 @@ use crate::render::sprite::SpriteRenderer;
 /// without modifying the engine core. The engine will call update() each frame
 /// with access to the sprite renderer (when available) and elapsed time, allowing you to create
 /// and animate sprites as needed.
+///
+/// New code should implement `engine::Game`, which keeps simulation and drawing in
+/// separate phases; the engine runs an `Animation` through `engine::LegacyAnimation`.
 #[cfg(feature = "opengl")]
 pub trait Animation {
     /// Update the animation
     ///
     /// # Arguments
     /// * `sprite_renderer` - Optional access to sprite renderer for creating/rendering sprites (None in headless mode)
-    /// * `elapsed_time` - Time in seconds since the animation started
-    /// * `delta_time` - Time in seconds since the last frame
+    /// * `elapsed_time` - Time in seconds since the animation started, under the engine's `AnimationTime`
+    /// * `delta_time` - Time in seconds since the last frame, scaled by the engine's `AnimationTime`
     /// * `window_manager` - Optional access to window manager for window operations
     /// * `text_renderer` - Optional access to text renderer for rendering text (None in headless mode)
     fn update(
@@ -50,15 +55,35 @@ pub trait Animation {
         // Default implementation does nothing
         // Animations can override this to control window features
     }
+
+    /// Update lights and occluders (called in the update phase, before `update` and lighting)
+    ///
+    /// # Arguments
+    /// * `lighting` - Mutable reference to the lighting renderer (disabled by default)
+    fn update_lighting(&mut self, _lighting: &mut LightingRenderer) {
+        // Default implementation does nothing
+        // Animations can override this to enable lighting and move lights
+    }
+
+    /// Handle a framebuffer resize (called once per frame after the viewport is updated)
+    ///
+    /// # Arguments
+    /// * `old_size` - Previous framebuffer size in pixels
+    /// * `new_size` - New framebuffer size in pixels
+    fn on_resize(&mut self, _old_size: (u32, u32), _new_size: (u32, u32)) {
+        // Default implementation does nothing
+        // Animations can override this to re-lay-out UI or reallocate render targets
+    }
 }
 
+/// Headless form of the animation trait; see `engine::Game` for new code
 #[cfg(not(feature = "opengl"))]
 pub trait Animation {
     /// Update the animation (headless mode)
     ///
     /// # Arguments
-    /// * `elapsed_time` - Time in seconds since the animation started
-    /// * `delta_time` - Time in seconds since the last frame
+    /// * `elapsed_time` - Time in seconds since the animation started, under the engine's `AnimationTime`
+    /// * `delta_time` - Time in seconds since the last frame, scaled by the engine's `AnimationTime`
     fn update(&mut self, elapsed_time: f32, delta_time: f32);
 
     /// Get the name of the animation (for debugging/logging purposes)
@@ -0,0 +1,196 @@
+use glam::Vec2;
+
+/// One key of an `AnimationCurve`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveKey {
+    pub time: f32,
+    pub value: f32,
+    /// Slope arriving at the key; None to derive a smooth one from the neighbours
+    pub in_tangent: Option<f32>,
+    /// Slope leaving the key; None to derive a smooth one from the neighbours
+    pub out_tangent: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    /// Cubic Hermite segments between keys, sorted by time
+    Hermite(Vec<CurveKey>),
+    /// A cubic Bezier from (0, 0) to (1, 1) with two control points, as in CSS
+    Bezier(Vec2, Vec2),
+}
+
+/// A designer-made curve evaluable at any time, for easing beyond the fixed functions in
+/// `utils::math::interpolation`
+///
+/// Curves convert into an `Easing`, so they can be used wherever tweens and timelines
+/// take one.
+///
+/// ```
+/// # use engine_2d::animation::{AnimationCurve, tween};
+/// # use glam::Vec2;
+/// // Overshoot then settle
+/// let snappy = AnimationCurve::new()
+///     .with_key(0.0, 0.0)
+///     .with_key(0.6, 1.15)
+///     .with_key(1.0, 1.0);
+/// let slide = tween(0.0).to(100.0).over(0.4).ease(snappy);
+/// let css_ease = AnimationCurve::bezier(Vec2::new(0.25, 0.1), Vec2::new(0.25, 1.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationCurve {
+    shape: Shape,
+}
+
+impl Default for AnimationCurve {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationCurve {
+    /// A curve without keys, which evaluates to 0
+    pub fn new() -> Self {
+        Self {
+            shape: Shape::Hermite(Vec::new()),
+        }
+    }
+
+    /// A timing curve through (0, 0) and (1, 1) shaped by two Bezier control points, like
+    /// CSS `cubic-bezier`; control point x values are clamped to 0..1
+    pub fn bezier(p1: Vec2, p2: Vec2) -> Self {
+        let clamp_x = |p: Vec2| Vec2::new(p.x.clamp(0.0, 1.0), p.y);
+        Self {
+            shape: Shape::Bezier(clamp_x(p1), clamp_x(p2)),
+        }
+    }
+
+    /// Add a key with smooth automatic tangents; a key at the same time is replaced
+    ///
+    /// Keys added to a `bezier` curve turn it into a key curve.
+    pub fn with_key(self, time: f32, value: f32) -> Self {
+        self.with_key_tangents(time, value, None, None)
+    }
+
+    /// Add a key with explicit slopes; None derives a smooth one
+    pub fn with_key_tangents(
+        mut self,
+        time: f32,
+        value: f32,
+        in_tangent: Option<f32>,
+        out_tangent: Option<f32>,
+    ) -> Self {
+        self.add_key(CurveKey {
+            time,
+            value,
+            in_tangent,
+            out_tangent,
+        });
+        self
+    }
+
+    pub fn add_key(&mut self, key: CurveKey) {
+        if !matches!(self.shape, Shape::Hermite(_)) {
+            self.shape = Shape::Hermite(Vec::new());
+        }
+        let Shape::Hermite(keys) = &mut self.shape else {
+            return;
+        };
+        match keys.iter().position(|k| k.time >= key.time) {
+            Some(index) if keys[index].time == key.time => keys[index] = key,
+            Some(index) => keys.insert(index, key),
+            None => keys.push(key),
+        }
+    }
+
+    /// Keys of a key curve; empty for a `bezier` curve
+    pub fn keys(&self) -> &[CurveKey] {
+        match &self.shape {
+            Shape::Hermite(keys) => keys,
+            Shape::Bezier(..) => &[],
+        }
+    }
+
+    /// Value at `t`, holding the first and last keys outside their range
+    pub fn evaluate(&self, t: f32) -> f32 {
+        match &self.shape {
+            Shape::Hermite(keys) => evaluate_hermite(keys, t),
+            Shape::Bezier(p1, p2) => evaluate_bezier(*p1, *p2, t),
+        }
+    }
+}
+
+fn evaluate_hermite(keys: &[CurveKey], t: f32) -> f32 {
+    let (Some(first), Some(last)) = (keys.first(), keys.last()) else {
+        return 0.0;
+    };
+    if t <= first.time {
+        return first.value;
+    }
+    if t >= last.time {
+        return last.value;
+    }
+    let index = keys.partition_point(|key| key.time <= t) - 1;
+    let (from, to) = (&keys[index], &keys[index + 1]);
+    let span = to.time - from.time;
+    let s = (t - from.time) / span;
+    let m0 = from
+        .out_tangent
+        .unwrap_or_else(|| auto_tangent(keys, index))
+        * span;
+    let m1 = to
+        .in_tangent
+        .unwrap_or_else(|| auto_tangent(keys, index + 1))
+        * span;
+
+    let (s2, s3) = (s * s, s * s * s);
+    (2.0 * s3 - 3.0 * s2 + 1.0) * from.value
+        + (s3 - 2.0 * s2 + s) * m0
+        + (-2.0 * s3 + 3.0 * s2) * to.value
+        + (s3 - s2) * m1
+}
+
+/// Catmull-Rom slope through the neighbours; flat at the ends so curves ease in and out
+fn auto_tangent(keys: &[CurveKey], index: usize) -> f32 {
+    if index == 0 || index + 1 >= keys.len() {
+        return 0.0;
+    }
+    let (before, after) = (&keys[index - 1], &keys[index + 1]);
+    (after.value - before.value) / (after.time - before.time)
+}
+
+fn evaluate_bezier(p1: Vec2, p2: Vec2, t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    let bezier = |a: f32, b: f32, u: f32| {
+        let v = 1.0 - u;
+        3.0 * v * v * u * a + 3.0 * v * u * u * b + u * u * u
+    };
+    let slope = |a: f32, b: f32, u: f32| {
+        let v = 1.0 - u;
+        3.0 * v * v * a + 6.0 * v * u * (b - a) + 3.0 * u * u * (1.0 - b)
+    };
+
+    // Find the curve parameter whose x is t: Newton steps, then bisection if they stall
+    let mut u = t;
+    for _ in 0..8 {
+        let error = bezier(p1.x, p2.x, u) - t;
+        if error.abs() < 1e-6 {
+            return bezier(p1.y, p2.y, u);
+        }
+        let derivative = slope(p1.x, p2.x, u);
+        if derivative.abs() < 1e-6 {
+            break;
+        }
+        u = (u - error / derivative).clamp(0.0, 1.0);
+    }
+    let (mut low, mut high) = (0.0, 1.0);
+    u = t;
+    for _ in 0..32 {
+        if bezier(p1.x, p2.x, u) < t {
+            low = u;
+        } else {
+            high = u;
+        }
+        u = (low + high) * 0.5;
+    }
+    bezier(p1.y, p2.y, u)
+}
@@ -0,0 +1,228 @@
+use super::sprite_sheet::SpriteSheetAnimation;
+use super::state_machine::AnimationController;
+use super::timeline::{Timeline, TimelinePlayer};
+use crate::ecs::World;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+#[derive(Debug, Clone)]
+enum Clip {
+    SpriteSheet(SpriteSheetAnimation),
+    Timeline(Timeline),
+}
+
+impl Clip {
+    fn load(&self, path: &str) -> Result<Clip, String> {
+        Ok(match self {
+            Clip::SpriteSheet(_) => Clip::SpriteSheet(SpriteSheetAnimation::load(path)?),
+            Clip::Timeline(_) => Clip::Timeline(Timeline::load(path)?),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Asset {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    clip: Clip,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Marks an entity's `SpriteSheetAnimation` or `TimelinePlayer` as playing the library
+/// clip of this name, so `animation_reload_system` updates it when the file changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnimationAsset(pub String);
+
+/// Sprite sheet clips and timelines loaded from files by name
+///
+/// With hot-reload enabled, `poll_hot_reload` re-reads definition files that changed on
+/// disk; a file that fails to load is logged and its previous clip kept. Put the library
+/// in the world as a resource and run `animation_reload_system` to update playing clips
+/// in place.
+///
+/// ```no_run
+/// # use engine_2d::animation::{AnimationAsset, AnimationLibrary};
+/// # use engine_2d::ecs::World;
+/// # let mut world = World::new();
+/// let mut library = AnimationLibrary::new();
+/// library.load_sprite_sheet("run", "assets/hero_run.json")?;
+/// library.set_hot_reload(cfg!(debug_assertions));
+/// let run = library.sprite_sheet("run").unwrap().clone();
+/// world.spawn((run, AnimationAsset("run".to_string())));
+/// world.insert_resource(library);
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnimationLibrary {
+    assets: HashMap<String, Asset>,
+    hot_reload_enabled: bool,
+    hot_reload_interval: Duration,
+    last_hot_reload_check: Instant,
+}
+
+impl Default for AnimationLibrary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationLibrary {
+    pub fn new() -> Self {
+        Self {
+            assets: HashMap::new(),
+            hot_reload_enabled: false,
+            hot_reload_interval: Duration::from_millis(500),
+            last_hot_reload_check: Instant::now(),
+        }
+    }
+
+    /// Load a sprite sheet clip from a JSON file (see `SpriteSheetAnimation::parse`)
+    pub fn load_sprite_sheet(&mut self, name: &str, path: &str) -> Result<(), String> {
+        let clip = Clip::SpriteSheet(SpriteSheetAnimation::load(path)?);
+        self.insert(name, path, clip);
+        Ok(())
+    }
+
+    /// Load a timeline from a JSON file (see `Timeline`)
+    pub fn load_timeline(&mut self, name: &str, path: &str) -> Result<(), String> {
+        let clip = Clip::Timeline(Timeline::load(path)?);
+        self.insert(name, path, clip);
+        Ok(())
+    }
+
+    fn insert(&mut self, name: &str, path: &str, clip: Clip) {
+        let path = PathBuf::from(path);
+        let modified = modified_time(&path);
+        self.assets.insert(
+            name.to_string(),
+            Asset {
+                path,
+                modified,
+                clip,
+            },
+        );
+    }
+
+    pub fn sprite_sheet(&self, name: &str) -> Option<&SpriteSheetAnimation> {
+        match &self.assets.get(name)?.clip {
+            Clip::SpriteSheet(clip) => Some(clip),
+            Clip::Timeline(_) => None,
+        }
+    }
+
+    pub fn timeline(&self, name: &str) -> Option<&Timeline> {
+        match &self.assets.get(name)?.clip {
+            Clip::Timeline(timeline) => Some(timeline),
+            Clip::SpriteSheet(_) => None,
+        }
+    }
+
+    /// Enable or disable hot-reload (development mode)
+    pub fn set_hot_reload(&mut self, enabled: bool) {
+        self.hot_reload_enabled = enabled;
+        if enabled {
+            // Don't pick up edits made while hot-reload was off as a burst of reloads
+            for asset in self.assets.values_mut() {
+                asset.modified = modified_time(&asset.path);
+            }
+        }
+    }
+
+    pub fn is_hot_reload_enabled(&self) -> bool {
+        self.hot_reload_enabled
+    }
+
+    /// Set how often `poll_hot_reload` checks the files on disk
+    pub fn set_hot_reload_interval(&mut self, interval: Duration) {
+        self.hot_reload_interval = interval;
+    }
+
+    /// Reload changed files if hot-reload is enabled and the poll interval has elapsed
+    ///
+    /// Intended to be called once per frame. Returns the names of the reloaded clips.
+    pub fn poll_hot_reload(&mut self) -> Vec<String> {
+        if !self.hot_reload_enabled
+            || self.last_hot_reload_check.elapsed() < self.hot_reload_interval
+        {
+            return Vec::new();
+        }
+        self.last_hot_reload_check = Instant::now();
+        self.reload_changed()
+    }
+
+    /// Re-read every clip whose file changed on disk, returning the names reloaded
+    pub fn reload_changed(&mut self) -> Vec<String> {
+        let mut reloaded = Vec::new();
+        for (name, asset) in self.assets.iter_mut() {
+            let modified = modified_time(&asset.path);
+            if modified == asset.modified {
+                continue;
+            }
+            asset.modified = modified;
+            match asset.clip.load(&asset.path.to_string_lossy()) {
+                Ok(clip) => {
+                    asset.clip = clip;
+                    log::info!("Reloaded animation '{}'", name);
+                    reloaded.push(name.clone());
+                }
+                Err(e) => {
+                    log::error!(
+                        "Failed to reload animation '{}', keeping previous clip: {}",
+                        name,
+                        e
+                    );
+                }
+            }
+        }
+        reloaded
+    }
+
+    /// Update a controller's states named after reloaded sprite sheet clips
+    pub fn reload_controller(&self, controller: &mut AnimationController, reloaded: &[String]) {
+        for name in reloaded {
+            if let Some(definition) = self.sprite_sheet(name) {
+                controller.reload_state(name, definition);
+            }
+        }
+    }
+}
+
+/// Poll the world's `AnimationLibrary` for changed files and update the clips of entities
+/// tagged with their `AnimationAsset` and the `AnimationController` states named after the
+/// reloaded clips, keeping the current frame or time
+pub fn animation_reload_system(world: &mut World, _delta_time: f32) {
+    let Some(mut library) = world.remove_resource::<AnimationLibrary>() else {
+        return;
+    };
+    let reloaded = library.poll_hot_reload();
+    if !reloaded.is_empty() {
+        apply_reloaded(world, &library, &reloaded);
+    }
+    world.insert_resource(library);
+}
+
+fn apply_reloaded(world: &mut World, library: &AnimationLibrary, reloaded: &[String]) {
+    let is_reloaded = |asset: &AnimationAsset| reloaded.contains(&asset.0);
+    for (_, (asset, clip)) in world.query::<(&AnimationAsset, &mut SpriteSheetAnimation)>() {
+        if is_reloaded(asset)
+            && let Some(definition) = library.sprite_sheet(&asset.0)
+        {
+            clip.reload_from(definition);
+        }
+    }
+    for (_, (asset, player)) in world.query::<(&AnimationAsset, &mut TimelinePlayer)>() {
+        if is_reloaded(asset)
+            && let Some(timeline) = library.timeline(&asset.0)
+        {
+            player.set_timeline(timeline.clone());
+        }
+    }
+    for (_, controller) in world.query::<&mut AnimationController>() {
+        library.reload_controller(controller, reloaded);
+    }
+}
@@ -1,7 +1,33 @@
 #[allow(clippy::module_inception)]
 mod animation;
+pub mod curve;
+pub mod library;
+pub mod procedural;
+pub mod skeleton;
+pub mod spine;
+pub mod sprite_sheet;
+pub mod state_machine;
+pub mod time;
+pub mod timeline;
+pub mod tween;
 
 pub use animation::*;
+pub use curve::{AnimationCurve, CurveKey};
+pub use library::{AnimationAsset, AnimationLibrary, animation_reload_system};
+pub use procedural::{Oscillator, SecondaryMotion, Spring, SpringVec2, Waveform};
+pub use skeleton::{
+    Attachment, Bone, BoneTrack, SkeletalClip, Skeleton, SkinnedMesh, SkinnedVertex,
+};
+pub use spine::{SpineAtlas, SpineImport, SpineRegion};
+pub use sprite_sheet::{FrameRegion, LoopMode, SpriteFrame, SpriteSheetAnimation};
+pub use state_machine::{AnimationController, Condition, Transition};
+pub use time::{AnimationTime, animation_delta};
+pub use timeline::{
+    Keyframe, Timeline, TimelinePlayer, TimelineSample, Track, timeline_system,
+};
+pub use tween::{
+    Easing, Repeat, Tween, TweenId, TweenManager, Tweenable, easing_by_name, linear, tween,
+};
 
 #[cfg(test)]
 mod tests {
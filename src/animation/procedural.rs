@@ -0,0 +1,321 @@
+use glam::Vec2;
+use std::f32::consts::TAU;
+use std::ops::{Add, Mul, Sub};
+
+/// Longest step the spring integrates at once, so stiff springs stay stable at low frame rates
+const MAX_SPRING_STEP: f32 = 1.0 / 120.0;
+
+/// A value that chases a target like a weight on a damped spring, for motion that overshoots
+/// and settles without keyframes
+///
+/// Retargeting mid-flight keeps the current velocity, so springs stay smooth when the target
+/// moves every frame (a camera following a player, a button under the cursor).
+///
+/// ```
+/// # use engine_2d::animation::SpringVec2;
+/// # use glam::Vec2;
+/// # let (player_position, delta_time) = (Vec2::new(40.0, 0.0), 0.016);
+/// let mut camera = SpringVec2::new(Vec2::ZERO).with_stiffness(120.0).with_damping(18.0);
+/// // each frame
+/// camera.set_target(player_position);
+/// let position = camera.update(delta_time);
+/// # assert!(position.x > 0.0 && position.x < 40.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring<T> {
+    value: T,
+    velocity: T,
+    target: T,
+    stiffness: f32,
+    damping: f32,
+}
+
+/// A spring in 2D, e.g. for cameras and UI positions
+pub type SpringVec2 = Spring<Vec2>;
+
+impl<T> Spring<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    /// A spring at rest at `value`, with a snappy, barely overshooting default feel
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            velocity: T::default(),
+            target: value,
+            stiffness: 170.0,
+            damping: 26.0,
+        }
+    }
+
+    /// Pull towards the target per unit of distance; higher is faster
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness.max(0.0);
+        self
+    }
+
+    /// Resistance to velocity; lower overshoots and wobbles more
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping.max(0.0);
+        self
+    }
+
+    pub fn stiffness(&self) -> f32 {
+        self.stiffness
+    }
+
+    pub fn damping(&self) -> f32 {
+        self.damping
+    }
+
+    pub fn set_target(&mut self, target: T) {
+        self.target = target;
+    }
+
+    pub fn target(&self) -> T {
+        self.target
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    pub fn velocity(&self) -> T {
+        self.velocity
+    }
+
+    /// Jump to `value` and stop, leaving the target unchanged
+    pub fn set_value(&mut self, value: T) {
+        self.value = value;
+        self.velocity = T::default();
+    }
+
+    /// Kick the spring, e.g. to make a button wobble when clicked
+    pub fn add_impulse(&mut self, velocity: T) {
+        self.velocity = self.velocity + velocity;
+    }
+
+    /// Advance the simulation and return the new value
+    pub fn update(&mut self, delta_time: f32) -> T {
+        let mut remaining = delta_time.max(0.0);
+        while remaining > 0.0 {
+            let step = remaining.min(MAX_SPRING_STEP);
+            let force = (self.target - self.value) * self.stiffness - self.velocity * self.damping;
+            self.velocity = self.velocity + force * step;
+            self.value = self.value + self.velocity * step;
+            remaining -= step;
+        }
+        self.value
+    }
+}
+
+impl Spring<f32> {
+    /// Close enough to the target and slow enough to stop updating
+    pub fn is_settled(&self, epsilon: f32) -> bool {
+        (self.target - self.value).abs() <= epsilon && self.velocity.abs() <= epsilon
+    }
+}
+
+impl Spring<Vec2> {
+    /// Close enough to the target and slow enough to stop updating
+    pub fn is_settled(&self, epsilon: f32) -> bool {
+        self.target.distance(self.value) <= epsilon && self.velocity.length() <= epsilon
+    }
+}
+
+/// Shape of an `Oscillator`'s cycle, each ranging over -1..1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Triangle,
+    Square,
+    /// Rises from -1 to 1, then drops back
+    Sawtooth,
+}
+
+impl Waveform {
+    /// Value at `phase` cycles (the fractional part is used)
+    pub fn sample(self, phase: f32) -> f32 {
+        let phase = phase.rem_euclid(1.0);
+        match self {
+            Waveform::Sine => (phase * TAU).sin(),
+            Waveform::Triangle => 4.0 * ((phase - 0.25).rem_euclid(1.0) - 0.5).abs() - 1.0,
+            Waveform::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Sawtooth => 2.0 * phase - 1.0,
+        }
+    }
+}
+
+/// A repeating wave for bobbing, pulsing and shaking
+///
+/// With a decay the amplitude shrinks over time, so `kick` gives a one-shot wobble such as
+/// screen shake.
+///
+/// ```
+/// # use engine_2d::animation::Oscillator;
+/// # use glam::Vec2;
+/// # let (mut offset, delta_time) = (Vec2::ZERO, 0.016);
+/// let mut bob = Oscillator::new(4.0, 0.5); // 4 pixels, once every 2 seconds
+/// let mut shake = Oscillator::new(0.0, 20.0).with_decay(6.0);
+/// shake.kick(8.0); // on impact
+/// offset.y = bob.update(delta_time) + shake.update(delta_time);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oscillator {
+    amplitude: f32,
+    /// Cycles per second
+    frequency: f32,
+    waveform: Waveform,
+    /// Exponential amplitude falloff per second
+    decay: f32,
+    /// Position in the current cycle, 0..1
+    phase: f32,
+}
+
+impl Oscillator {
+    /// A sine wave of `amplitude` repeating `frequency` times per second
+    pub fn new(amplitude: f32, frequency: f32) -> Self {
+        Self {
+            amplitude,
+            frequency,
+            waveform: Waveform::default(),
+            decay: 0.0,
+            phase: 0.0,
+        }
+    }
+
+    pub fn with_waveform(mut self, waveform: Waveform) -> Self {
+        self.waveform = waveform;
+        self
+    }
+
+    /// Starting point in the cycle, in cycles (0.25 starts a sine at its peak)
+    pub fn with_phase(mut self, phase: f32) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    /// Shrink the amplitude by `rate` e-folds per second; 0 keeps it constant
+    pub fn with_decay(mut self, rate: f32) -> Self {
+        self.decay = rate.max(0.0);
+        self
+    }
+
+    pub fn amplitude(&self) -> f32 {
+        self.amplitude
+    }
+
+    pub fn set_amplitude(&mut self, amplitude: f32) {
+        self.amplitude = amplitude;
+    }
+
+    pub fn frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    pub fn set_frequency(&mut self, frequency: f32) {
+        self.frequency = frequency;
+    }
+
+    /// Restart the wave from the beginning of its cycle at `amplitude`
+    pub fn kick(&mut self, amplitude: f32) {
+        self.amplitude = amplitude;
+        self.phase = 0.0;
+    }
+
+    /// Current offset
+    pub fn value(&self) -> f32 {
+        self.amplitude * self.waveform.sample(self.phase)
+    }
+
+    /// Advance time and return the new offset
+    pub fn update(&mut self, delta_time: f32) -> f32 {
+        self.phase = (self.phase + self.frequency * delta_time).rem_euclid(1.0);
+        if self.decay > 0.0 {
+            self.amplitude *= (-self.decay * delta_time).exp();
+        }
+        self.value()
+    }
+}
+
+/// Follow-through for parts attached to something that moves, like a tail, antenna or hat
+/// brim: the part lags behind its anchor, overshoots when the anchor stops and settles
+///
+/// Chain several, each anchored to the previous one's position, for ropes and tails.
+///
+/// ```
+/// # use engine_2d::animation::SecondaryMotion;
+/// # use glam::Vec2;
+/// # let (head_position, delta_time) = (Vec2::new(100.0, 50.0), 0.016);
+/// let mut antenna = SecondaryMotion::new(Vec2::new(0.0, 12.0));
+/// // each frame
+/// let tip = antenna.update(head_position, delta_time);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecondaryMotion {
+    /// Rest position relative to the anchor
+    offset: Vec2,
+    spring: SpringVec2,
+    initialized: bool,
+}
+
+impl SecondaryMotion {
+    /// A part resting at `offset` from its anchor, with a loose, wobbly default spring
+    pub fn new(offset: Vec2) -> Self {
+        Self {
+            offset,
+            spring: SpringVec2::new(Vec2::ZERO)
+                .with_stiffness(120.0)
+                .with_damping(10.0),
+            initialized: false,
+        }
+    }
+
+    /// Tune how tightly the part follows; see `Spring`
+    pub fn with_spring(mut self, stiffness: f32, damping: f32) -> Self {
+        self.spring = self.spring.with_stiffness(stiffness).with_damping(damping);
+        self
+    }
+
+    pub fn offset(&self) -> Vec2 {
+        self.offset
+    }
+
+    pub fn set_offset(&mut self, offset: Vec2) {
+        self.offset = offset;
+    }
+
+    /// Where the part is
+    pub fn position(&self) -> Vec2 {
+        self.spring.value()
+    }
+
+    /// How far the part trails its rest position
+    pub fn lag(&self) -> Vec2 {
+        self.spring.value() - self.spring.target()
+    }
+
+    /// Snap to the rest position at `anchor`, e.g. after teleporting
+    pub fn reset(&mut self, anchor: Vec2) {
+        self.spring.set_target(anchor + self.offset);
+        self.spring.set_value(anchor + self.offset);
+        self.initialized = true;
+    }
+
+    /// Follow the anchor's new position and return the part's position; the first call
+    /// starts at rest
+    pub fn update(&mut self, anchor: Vec2, delta_time: f32) -> Vec2 {
+        if !self.initialized {
+            self.reset(anchor);
+        }
+        self.spring.set_target(anchor + self.offset);
+        self.spring.update(delta_time)
+    }
+}
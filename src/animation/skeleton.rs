@@ -0,0 +1,417 @@
+use super::sprite_sheet::FrameRegion;
+use super::timeline::Track;
+use super::tween::Tweenable;
+use crate::ecs::Transform;
+#[cfg(feature = "opengl")]
+use crate::render::sprite::Sprite;
+use glam::{Affine2, Vec2};
+
+/// One joint of a skeleton, positioned relative to its parent
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bone {
+    pub name: String,
+    /// Index of the parent bone; parents always come before their children
+    pub parent: Option<usize>,
+    /// Rest transform, relative to the parent
+    pub bind: Transform,
+    /// Current transform, relative to the parent
+    pub pose: Transform,
+}
+
+/// Part of a character drawn on a bone, e.g. a forearm sprite
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    pub name: String,
+    pub bone: usize,
+    /// Placement relative to the bone
+    pub offset: Transform,
+    /// Size of the sprite before bone scaling
+    pub size: Vec2,
+    /// Texture region of the sprite; the whole texture when None
+    pub region: Option<FrameRegion>,
+    /// Draw order among the skeleton's sprites
+    pub layer: i32,
+}
+
+/// A bone hierarchy with sprite attachments, posed by `SkeletalClip`s
+///
+/// ```
+/// # use engine_2d::animation::{SkeletalClip, Skeleton};
+/// # use engine_2d::ecs::Transform;
+/// # use glam::Vec2;
+/// # let (walk, time) = (SkeletalClip::new(), 0.25);
+/// let mut rig = Skeleton::new();
+/// rig.add_bone("hip", None, Transform::from_position(Vec2::new(0.0, 40.0)))?;
+/// rig.add_bone("thigh", Some("hip"), Transform::default())?;
+/// rig.add_attachment("thigh", "leg", Transform::default(), Vec2::new(8.0, 24.0))?;
+/// walk.apply(&mut rig, time);
+/// // where each attachment's sprite goes; `apply_attachments` moves sprites there
+/// let placements = rig.attachment_transforms();
+/// # assert_eq!(placements[0].position, Vec2::new(0.0, 40.0));
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Skeleton {
+    bones: Vec<Bone>,
+    attachments: Vec<Attachment>,
+}
+
+fn affine(transform: &Transform) -> Affine2 {
+    Affine2::from_scale_angle_translation(transform.scale, transform.rotation, transform.position)
+}
+
+fn from_affine(affine: Affine2) -> Transform {
+    let (scale, rotation, position) = affine.to_scale_angle_translation();
+    Transform {
+        position,
+        rotation,
+        scale,
+    }
+}
+
+impl Skeleton {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a bone under `parent` (None for a root) and return its index
+    pub fn add_bone(
+        &mut self,
+        name: &str,
+        parent: Option<&str>,
+        bind: Transform,
+    ) -> Result<usize, String> {
+        if self.bone_index(name).is_some() {
+            return Err(format!("Duplicate bone '{}'", name));
+        }
+        let parent = match parent {
+            Some(parent) => Some(
+                self.bone_index(parent)
+                    .ok_or_else(|| format!("Unknown parent bone '{}'", parent))?,
+            ),
+            None => None,
+        };
+        self.bones.push(Bone {
+            name: name.to_string(),
+            parent,
+            bind,
+            pose: bind,
+        });
+        Ok(self.bones.len() - 1)
+    }
+
+    /// Attach a sprite of `size` to a bone and return its index
+    pub fn add_attachment(
+        &mut self,
+        bone: &str,
+        name: &str,
+        offset: Transform,
+        size: Vec2,
+    ) -> Result<usize, String> {
+        let bone = self
+            .bone_index(bone)
+            .ok_or_else(|| format!("Unknown bone '{}'", bone))?;
+        self.attachments.push(Attachment {
+            name: name.to_string(),
+            bone,
+            offset,
+            size,
+            region: None,
+            layer: 0,
+        });
+        Ok(self.attachments.len() - 1)
+    }
+
+    pub fn bone_index(&self, name: &str) -> Option<usize> {
+        self.bones.iter().position(|bone| bone.name == name)
+    }
+
+    pub fn bones(&self) -> &[Bone] {
+        &self.bones
+    }
+
+    pub fn bone(&self, name: &str) -> Option<&Bone> {
+        self.bones.iter().find(|bone| bone.name == name)
+    }
+
+    /// The bone's current transform relative to its parent
+    pub fn pose_mut(&mut self, name: &str) -> Option<&mut Transform> {
+        self.bones
+            .iter_mut()
+            .find(|bone| bone.name == name)
+            .map(|bone| &mut bone.pose)
+    }
+
+    /// Put every bone back in its bind transform
+    pub fn reset_pose(&mut self) {
+        for bone in &mut self.bones {
+            bone.pose = bone.bind;
+        }
+    }
+
+    pub fn attachments(&self) -> &[Attachment] {
+        &self.attachments
+    }
+
+    pub fn attachments_mut(&mut self) -> &mut [Attachment] {
+        &mut self.attachments
+    }
+
+    /// Skeleton-space matrix of every bone in the current pose, by bone index
+    pub fn world_matrices(&self) -> Vec<Affine2> {
+        self.accumulate(|bone| &bone.pose)
+    }
+
+    /// Skeleton-space matrix of every bone in the bind pose, by bone index
+    pub fn bind_matrices(&self) -> Vec<Affine2> {
+        self.accumulate(|bone| &bone.bind)
+    }
+
+    fn accumulate(&self, local: impl Fn(&Bone) -> &Transform) -> Vec<Affine2> {
+        let mut matrices: Vec<Affine2> = Vec::with_capacity(self.bones.len());
+        for bone in &self.bones {
+            let matrix = affine(local(bone));
+            let matrix = match bone.parent {
+                Some(parent) => matrices[parent] * matrix,
+                None => matrix,
+            };
+            matrices.push(matrix);
+        }
+        matrices
+    }
+
+    /// Skeleton-space transform of a bone in the current pose
+    pub fn world_transform(&self, name: &str) -> Option<Transform> {
+        let index = self.bone_index(name)?;
+        Some(from_affine(self.world_matrices()[index]))
+    }
+
+    /// Skeleton-space transform of every attachment in the current pose, by index
+    pub fn attachment_transforms(&self) -> Vec<Transform> {
+        let matrices = self.world_matrices();
+        self.attachments
+            .iter()
+            .map(|attachment| from_affine(matrices[attachment.bone] * affine(&attachment.offset)))
+            .collect()
+    }
+
+    /// Place one sprite per attachment, in attachment order, at `origin`
+    ///
+    /// Sprites don't rotate, so bone rotation only moves the sprites.
+    #[cfg(feature = "opengl")]
+    pub fn apply_attachments(&self, origin: Vec2, sprites: &mut [Sprite]) {
+        let transforms = self.attachment_transforms();
+        for ((attachment, transform), sprite) in
+            self.attachments.iter().zip(transforms).zip(sprites)
+        {
+            sprite.set_position(origin + transform.position);
+            sprite.set_size(attachment.size * transform.scale);
+            sprite.set_region(attachment.region);
+            sprite.set_layer(attachment.layer);
+        }
+    }
+}
+
+/// Keyframes for one bone's pose; empty tracks leave that part of the pose alone
+#[derive(Debug, Clone, Default)]
+pub struct BoneTrack {
+    pub position: Track<Vec2>,
+    /// Radians
+    pub rotation: Track<f32>,
+    pub scale: Track<Vec2>,
+}
+
+/// A keyframed skeleton animation, e.g. a walk cycle
+#[derive(Debug, Clone, Default)]
+pub struct SkeletalClip {
+    tracks: Vec<(String, BoneTrack)>,
+    /// Wrap times past the end back to the start
+    pub looping: bool,
+}
+
+impl SkeletalClip {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// The keyframes of a bone, created empty on first use
+    pub fn track_mut(&mut self, bone: &str) -> &mut BoneTrack {
+        let index = match self.tracks.iter().position(|(name, _)| name == bone) {
+            Some(index) => index,
+            None => {
+                self.tracks.push((bone.to_string(), BoneTrack::default()));
+                self.tracks.len() - 1
+            }
+        };
+        &mut self.tracks[index].1
+    }
+
+    pub fn track(&self, bone: &str) -> Option<&BoneTrack> {
+        self.tracks
+            .iter()
+            .find(|(name, _)| name == bone)
+            .map(|(_, track)| track)
+    }
+
+    /// Time of the last key on any bone
+    pub fn duration(&self) -> f32 {
+        self.tracks
+            .iter()
+            .map(|(_, track)| {
+                track
+                    .position
+                    .duration()
+                    .max(track.rotation.duration())
+                    .max(track.scale.duration())
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// Pose the skeleton at `time`; tracks for bones it doesn't have are skipped
+    pub fn apply(&self, skeleton: &mut Skeleton, time: f32) {
+        self.apply_weighted(skeleton, time, 1.0);
+    }
+
+    /// Blend the skeleton's current pose towards this clip's pose at `time`, for
+    /// crossfading between clips
+    pub fn apply_weighted(&self, skeleton: &mut Skeleton, time: f32, weight: f32) {
+        let duration = self.duration();
+        let time = if self.looping && duration > 0.0 {
+            time.rem_euclid(duration)
+        } else {
+            time
+        };
+        for (bone, track) in &self.tracks {
+            let Some(pose) = skeleton.pose_mut(bone) else {
+                continue;
+            };
+            if let Some(position) = track.position.sample(time) {
+                pose.position = Vec2::lerp(pose.position, position, weight);
+            }
+            if let Some(rotation) = track.rotation.sample(time) {
+                pose.rotation = f32::lerp(pose.rotation, rotation, weight);
+            }
+            if let Some(scale) = track.scale.sample(time) {
+                pose.scale = Vec2::lerp(pose.scale, scale, weight);
+            }
+        }
+    }
+}
+
+/// A mesh vertex that follows one or more bones
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkinnedVertex {
+    /// Skeleton-space position in the bind pose
+    pub position: Vec2,
+    pub uv: Vec2,
+    /// Bone indices and their influence; normalized when deforming
+    pub weights: Vec<(usize, f32)>,
+}
+
+impl SkinnedVertex {
+    pub fn new(position: Vec2, uv: Vec2) -> Self {
+        Self {
+            position,
+            uv,
+            weights: Vec::new(),
+        }
+    }
+
+    pub fn with_weight(mut self, bone: usize, weight: f32) -> Self {
+        self.weights.push((bone, weight));
+        self
+    }
+}
+
+/// A triangle mesh bent by a skeleton's bones, for smooth joints and squash-and-stretch
+///
+/// `deform` returns the posed vertex positions; draw them as textured triangles with the
+/// vertices' UVs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkinnedMesh {
+    vertices: Vec<SkinnedVertex>,
+    /// Three vertex indices per triangle
+    indices: Vec<u32>,
+}
+
+impl SkinnedMesh {
+    /// Fails if a triangle or weight refers to something the mesh or skeleton lacks
+    pub fn new(
+        vertices: Vec<SkinnedVertex>,
+        indices: Vec<u32>,
+        skeleton: &Skeleton,
+    ) -> Result<Self, String> {
+        if !indices.len().is_multiple_of(3) {
+            return Err(format!(
+                "{} indices don't make whole triangles",
+                indices.len()
+            ));
+        }
+        if let Some(index) = indices
+            .iter()
+            .find(|&&index| index as usize >= vertices.len())
+        {
+            return Err(format!(
+                "Index {} is past the last of {} vertices",
+                index,
+                vertices.len()
+            ));
+        }
+        for (index, vertex) in vertices.iter().enumerate() {
+            if vertex.weights.is_empty() {
+                return Err(format!("Vertex {} has no bone weights", index));
+            }
+            if let Some((bone, _)) = vertex
+                .weights
+                .iter()
+                .find(|(bone, _)| *bone >= skeleton.bones().len())
+            {
+                return Err(format!(
+                    "Vertex {} is weighted to unknown bone {}",
+                    index, bone
+                ));
+            }
+        }
+        Ok(Self { vertices, indices })
+    }
+
+    pub fn vertices(&self) -> &[SkinnedVertex] {
+        &self.vertices
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Skeleton-space vertex positions in the skeleton's current pose
+    pub fn deform(&self, skeleton: &Skeleton) -> Vec<Vec2> {
+        // Each bone's movement away from its bind pose
+        let skinning: Vec<Affine2> = skeleton
+            .world_matrices()
+            .into_iter()
+            .zip(skeleton.bind_matrices())
+            .map(|(posed, bind)| posed * bind.inverse())
+            .collect();
+        self.vertices
+            .iter()
+            .map(|vertex| {
+                let total: f32 = vertex.weights.iter().map(|(_, weight)| weight).sum();
+                if total <= 0.0 {
+                    return vertex.position;
+                }
+                vertex
+                    .weights
+                    .iter()
+                    .map(|(bone, weight)| {
+                        skinning[*bone].transform_point2(vertex.position) * (weight / total)
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,510 @@
+use super::curve::AnimationCurve;
+use super::skeleton::{SkeletalClip, Skeleton, SkinnedMesh, SkinnedVertex};
+use super::sprite_sheet::FrameRegion;
+use super::timeline::Track;
+use super::tween::Easing;
+use crate::ecs::Transform;
+use glam::{Affine2, Vec2};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// One image in a Spine texture atlas
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpineRegion {
+    /// File name of the atlas page (texture) holding the image
+    pub page: String,
+    /// Pixel rectangle on the page, as used by `Sprite::region`
+    pub region: FrameRegion,
+    /// Packed rotated by 90 degrees
+    pub rotated: bool,
+}
+
+/// A Spine `.atlas` file: texture pages and the named images packed into them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpineAtlas {
+    /// Page file names, in file order
+    pub pages: Vec<String>,
+    pub regions: HashMap<String, SpineRegion>,
+}
+
+impl SpineAtlas {
+    /// Load an atlas file exported by Spine
+    pub fn load(path: &str) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read Spine atlas '{}': {}", path, e))?;
+        Self::parse(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Parse the text of an atlas file, in the Spine 3 or Spine 4 layout
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut atlas = SpineAtlas::default();
+        let mut expect_page = true;
+        // The region being read, whose properties follow its name
+        let mut current: Option<(String, SpineRegion)> = None;
+        for (index, line) in source.lines().enumerate() {
+            let error = |message: &str| format!("line {}: {}", index + 1, message);
+            let line = line.trim();
+            if line.is_empty() {
+                expect_page = true;
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                if let Some((name, region)) = current.take() {
+                    atlas.regions.insert(name, region);
+                }
+                if expect_page {
+                    atlas.pages.push(line.to_string());
+                    expect_page = false;
+                } else {
+                    let page = atlas
+                        .pages
+                        .last()
+                        .ok_or_else(|| error("region before a page"))?;
+                    let region = SpineRegion {
+                        page: page.clone(),
+                        region: (0.0, 0.0, 0.0, 0.0),
+                        rotated: false,
+                    };
+                    current = Some((line.to_string(), region));
+                }
+                continue;
+            };
+            // Page properties (size, filter, ...) aren't needed to place regions
+            let Some((_, region)) = &mut current else {
+                continue;
+            };
+            let numbers = || -> Result<Vec<f32>, String> {
+                value
+                    .split(',')
+                    .map(|number| number.trim().parse::<f32>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| error(&format!("invalid numbers in '{}'", line)))
+            };
+            let (x, y, width, height) = &mut region.region;
+            match key.trim() {
+                "xy" => [*x, *y] = pair(numbers()?, &error)?,
+                "size" => [*width, *height] = pair(numbers()?, &error)?,
+                "bounds" => match numbers()?.as_slice() {
+                    [bx, by, bw, bh] => (*x, *y, *width, *height) = (*bx, *by, *bw, *bh),
+                    _ => return Err(error("'bounds' needs four numbers")),
+                },
+                "rotate" => {
+                    region.rotated = matches!(value.trim(), "true" | "90" | "270");
+                }
+                _ => {}
+            }
+        }
+        if let Some((name, region)) = current {
+            atlas.regions.insert(name, region);
+        }
+        Ok(atlas)
+    }
+
+    pub fn region(&self, name: &str) -> Option<&SpineRegion> {
+        self.regions.get(name)
+    }
+}
+
+fn pair(numbers: Vec<f32>, error: &dyn Fn(&str) -> String) -> Result<[f32; 2], String> {
+    match numbers.as_slice() {
+        [a, b] => Ok([*a, *b]),
+        _ => Err(error("expected two numbers")),
+    }
+}
+
+/// A character exported from Spine as JSON, mapped onto `Skeleton` and `SkeletalClip`
+///
+/// Bones, the setup pose's region and mesh attachments, and bone rotate, translate and
+/// scale timelines (with stepped and Bezier curves) are imported. Constraints, shear,
+/// slot colors, deform timelines and skin swapping are not. Coordinates are Spine's:
+/// y points up and rotations are counter-clockwise.
+///
+/// ```no_run
+/// # use engine_2d::animation::{SpineAtlas, SpineImport};
+/// # let time = 0.5;
+/// let atlas = SpineAtlas::load("assets/hero.atlas")?;
+/// let mut hero = SpineImport::load("assets/hero.json", Some(&atlas))?;
+/// hero.clips["walk"].apply(&mut hero.skeleton, time);
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SpineImport {
+    pub skeleton: Skeleton,
+    /// Animations by name
+    pub clips: HashMap<String, SkeletalClip>,
+    /// Mesh attachments by attachment name, deformed with `SkinnedMesh::deform`
+    pub meshes: HashMap<String, SkinnedMesh>,
+}
+
+impl SpineImport {
+    /// Load a Spine JSON export; attachment regions are looked up in `atlas` if given
+    pub fn load(path: &str, atlas: Option<&SpineAtlas>) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read Spine file '{}': {}", path, e))?;
+        Self::parse(&source, atlas).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Parse the text of a Spine JSON export
+    pub fn parse(source: &str, atlas: Option<&SpineAtlas>) -> Result<Self, String> {
+        let file: SpineFile =
+            serde_json::from_str(source).map_err(|e| format!("Invalid Spine JSON: {}", e))?;
+        let mut import = SpineImport::default();
+        import.read_bones(&file)?;
+        import.read_attachments(&file, atlas)?;
+
+        // Spine 4 stores Bezier handles in timeline time and value, Spine 3 in 0..1
+        let version = &file.skeleton.spine;
+        let absolute_curves = !version.starts_with('3') && !version.starts_with('2');
+        for (name, animation) in &file.animations {
+            let clip = import
+                .read_clip(animation, absolute_curves)
+                .map_err(|e| format!("animation '{}': {}", name, e))?;
+            import.clips.insert(name.clone(), clip);
+        }
+        Ok(import)
+    }
+
+    fn read_bones(&mut self, file: &SpineFile) -> Result<(), String> {
+        for bone in &file.bones {
+            let bind = Transform {
+                position: Vec2::new(bone.x, bone.y),
+                rotation: bone.rotation.to_radians(),
+                scale: Vec2::new(bone.scale_x, bone.scale_y),
+            };
+            self.skeleton
+                .add_bone(&bone.name, bone.parent.as_deref(), bind)?;
+        }
+        Ok(())
+    }
+
+    /// Add the attachment each slot shows in the setup pose, drawn in slot order
+    fn read_attachments(
+        &mut self,
+        file: &SpineFile,
+        atlas: Option<&SpineAtlas>,
+    ) -> Result<(), String> {
+        let skin = file.skins.as_ref().and_then(SpineSkins::default_skin);
+        let bind_matrices = self.skeleton.bind_matrices();
+        for (layer, slot) in file.slots.iter().enumerate() {
+            let Some(attachment_name) = slot.attachment.as_deref() else {
+                continue;
+            };
+            let Some(attachment) = skin
+                .and_then(|skin| skin.get(&slot.name))
+                .and_then(|attachments| attachments.get(attachment_name))
+            else {
+                continue;
+            };
+            let path = attachment.path.as_deref().unwrap_or(attachment_name);
+            match attachment.kind.as_str() {
+                "region" => {
+                    let offset = Transform {
+                        position: Vec2::new(attachment.x, attachment.y),
+                        rotation: attachment.rotation.to_radians(),
+                        scale: Vec2::new(attachment.scale_x, attachment.scale_y),
+                    };
+                    let size = Vec2::new(attachment.width, attachment.height);
+                    let index =
+                        self.skeleton
+                            .add_attachment(&slot.bone, attachment_name, offset, size)?;
+                    let attachment = &mut self.skeleton.attachments_mut()[index];
+                    attachment.layer = layer as i32;
+                    attachment.region = atlas
+                        .and_then(|atlas| atlas.region(path))
+                        .map(|region| region.region);
+                }
+                "mesh" => {
+                    let bone_index = self.skeleton.bone_index(&slot.bone).ok_or_else(|| {
+                        format!("slot '{}' has unknown bone '{}'", slot.name, slot.bone)
+                    })?;
+                    let mesh = read_mesh(attachment, bone_index, &bind_matrices, &self.skeleton)
+                        .map_err(|e| format!("mesh '{}': {}", attachment_name, e))?;
+                    self.meshes.insert(attachment_name.to_string(), mesh);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn read_clip(
+        &self,
+        animation: &SpineAnimation,
+        absolute_curves: bool,
+    ) -> Result<SkeletalClip, String> {
+        let mut clip = SkeletalClip::new();
+        for (bone_name, timelines) in &animation.bones {
+            let bind = self
+                .skeleton
+                .bone(bone_name)
+                .ok_or_else(|| format!("unknown bone '{}'", bone_name))?
+                .bind;
+            let track = clip.track_mut(bone_name);
+            if let Some(keys) = &timelines.rotate {
+                track.rotation = read_track(keys, absolute_curves, |key| {
+                    let angle = key.angle.or(key.value).unwrap_or(0.0);
+                    bind.rotation + angle.to_radians()
+                })?;
+            }
+            if let Some(keys) = &timelines.translate {
+                track.position = read_track(keys, absolute_curves, |key| {
+                    bind.position + Vec2::new(key.x.unwrap_or(0.0), key.y.unwrap_or(0.0))
+                })?;
+            }
+            if let Some(keys) = &timelines.scale {
+                track.scale = read_track(keys, absolute_curves, |key| {
+                    bind.scale * Vec2::new(key.x.unwrap_or(1.0), key.y.unwrap_or(1.0))
+                })?;
+            }
+        }
+        Ok(clip)
+    }
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+fn region_kind() -> String {
+    "region".to_string()
+}
+
+/// The parts of a Spine JSON export that are imported
+#[derive(Debug, Deserialize)]
+struct SpineFile {
+    #[serde(default)]
+    skeleton: SpineSkeletonInfo,
+    bones: Vec<SpineBone>,
+    #[serde(default)]
+    slots: Vec<SpineSlot>,
+    skins: Option<SpineSkins>,
+    #[serde(default)]
+    animations: BTreeMap<String, SpineAnimation>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SpineSkeletonInfo {
+    /// Editor version the file was exported from
+    #[serde(default)]
+    spine: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpineBone {
+    name: String,
+    parent: Option<String>,
+    #[serde(default)]
+    x: f32,
+    #[serde(default)]
+    y: f32,
+    #[serde(default)]
+    rotation: f32,
+    #[serde(default = "one")]
+    scale_x: f32,
+    #[serde(default = "one")]
+    scale_y: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpineSlot {
+    name: String,
+    bone: String,
+    attachment: Option<String>,
+}
+
+/// Attachments by slot name, then attachment name
+type SpineSkin = HashMap<String, HashMap<String, SpineAttachment>>;
+
+/// Skins in the Spine 3.8+ array layout or the older object layout
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SpineSkins {
+    List(Vec<SpineNamedSkin>),
+    ByName(HashMap<String, SpineSkin>),
+}
+
+impl SpineSkins {
+    /// Attachments of the skin named "default"
+    fn default_skin(&self) -> Option<&SpineSkin> {
+        match self {
+            SpineSkins::List(skins) => skins
+                .iter()
+                .find(|skin| skin.name == "default")
+                .map(|skin| &skin.attachments),
+            SpineSkins::ByName(skins) => skins.get("default"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SpineNamedSkin {
+    name: String,
+    #[serde(default)]
+    attachments: SpineSkin,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpineAttachment {
+    #[serde(rename = "type", default = "region_kind")]
+    kind: String,
+    path: Option<String>,
+    #[serde(default)]
+    x: f32,
+    #[serde(default)]
+    y: f32,
+    #[serde(default)]
+    rotation: f32,
+    #[serde(default = "one")]
+    scale_x: f32,
+    #[serde(default = "one")]
+    scale_y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    uvs: Option<Vec<f32>>,
+    vertices: Option<Vec<f32>>,
+    triangles: Option<Vec<u32>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpineAnimation {
+    #[serde(default)]
+    bones: BTreeMap<String, SpineBoneTimelines>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpineBoneTimelines {
+    rotate: Option<Vec<SpineKey>>,
+    translate: Option<Vec<SpineKey>>,
+    scale: Option<Vec<SpineKey>>,
+}
+
+/// One timeline key; which value fields are set depends on the timeline and version
+#[derive(Debug, Deserialize)]
+struct SpineKey {
+    #[serde(default)]
+    time: f32,
+    angle: Option<f32>,
+    value: Option<f32>,
+    x: Option<f32>,
+    y: Option<f32>,
+    curve: Option<SpineCurve>,
+    c2: Option<f32>,
+    c3: Option<f32>,
+    c4: Option<f32>,
+}
+
+/// How a key eases into the next one
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SpineCurve {
+    /// "stepped" or "linear"
+    Named(String),
+    /// Spine 3.8 writes the first handle coordinate as a number and the rest as c2..c4
+    FirstHandle(f32),
+    /// Spine 4 writes the Bezier handles as an array
+    Handles(Vec<f32>),
+}
+
+/// Keys of one bone timeline; a key's curve eases the segment after it
+fn read_track<T: super::tween::Tweenable>(
+    keys: &[SpineKey],
+    absolute_curves: bool,
+    value: impl Fn(&SpineKey) -> T,
+) -> Result<Track<T>, String> {
+    let mut track = Track::new();
+    let mut ease = Easing::default();
+    for (index, key) in keys.iter().enumerate() {
+        track.add_key(key.time, value(key), ease.clone());
+        ease = match keys.get(index + 1) {
+            Some(next) => read_curve(key, next, absolute_curves)
+                .map_err(|e| format!("key {}: {}", index, e))?,
+            None => Easing::default(),
+        };
+    }
+    Ok(track)
+}
+
+fn read_curve(key: &SpineKey, next: &SpineKey, absolute: bool) -> Result<Easing, String> {
+    let handles = match &key.curve {
+        None => return Ok(Easing::default()),
+        Some(SpineCurve::Named(curve)) if curve == "stepped" => {
+            return Ok(Easing::new(|t| if t >= 1.0 { 1.0 } else { 0.0 }));
+        }
+        Some(SpineCurve::Named(curve)) if curve == "linear" => return Ok(Easing::default()),
+        Some(SpineCurve::FirstHandle(cx1)) => [
+            *cx1,
+            key.c2.unwrap_or(0.0),
+            key.c3.unwrap_or(1.0),
+            key.c4.unwrap_or(1.0),
+        ],
+        Some(SpineCurve::Handles(values)) if values.len() >= 4 => {
+            [values[0], values[1], values[2], values[3]]
+        }
+        Some(_) => return Err("unsupported curve".to_string()),
+    };
+    let [mut cx1, mut cy1, mut cx2, mut cy2] = handles;
+    if absolute {
+        // Handles are in (time, value) of the first channel; normalize them to 0..1
+        let (t0, t1) = (key.time, next.time);
+        let channel_value = |key: &SpineKey| key.angle.or(key.value).or(key.x);
+        let v0 = channel_value(key).unwrap_or(0.0);
+        let v1 = channel_value(next).unwrap_or(0.0);
+        if t1 <= t0 || v1 == v0 {
+            return Ok(Easing::default());
+        }
+        cx1 = (cx1 - t0) / (t1 - t0);
+        cx2 = (cx2 - t0) / (t1 - t0);
+        cy1 = (cy1 - v0) / (v1 - v0);
+        cy2 = (cy2 - v0) / (v1 - v0);
+    }
+    Ok(AnimationCurve::bezier(Vec2::new(cx1, cy1), Vec2::new(cx2, cy2)).into())
+}
+
+/// A mesh attachment: vertices are either relative to the slot's bone, or weighted lists of
+/// (bone, x, y, weight) relative to each bone
+fn read_mesh(
+    attachment: &SpineAttachment,
+    slot_bone: usize,
+    bind_matrices: &[Affine2],
+    skeleton: &Skeleton,
+) -> Result<SkinnedMesh, String> {
+    let uvs = attachment.uvs.as_deref().ok_or("missing 'uvs'")?;
+    let data = attachment.vertices.as_deref().ok_or("missing 'vertices'")?;
+    let indices = attachment.triangles.clone().ok_or("missing 'triangles'")?;
+
+    let mut vertices = Vec::with_capacity(uvs.len() / 2);
+    if data.len() == uvs.len() {
+        for (point, uv) in data.chunks_exact(2).zip(uvs.chunks_exact(2)) {
+            let position = bind_matrices[slot_bone].transform_point2(Vec2::new(point[0], point[1]));
+            let vertex = SkinnedVertex::new(position, Vec2::new(uv[0], uv[1]));
+            vertices.push(vertex.with_weight(slot_bone, 1.0));
+        }
+    } else {
+        let mut rest = data;
+        for uv in uvs.chunks_exact(2) {
+            let (&count, tail) = rest.split_first().ok_or("weighted vertices end early")?;
+            let count = count as usize;
+            if tail.len() < count * 4 {
+                return Err("weighted vertices end early".to_string());
+            }
+            let mut vertex = SkinnedVertex::new(Vec2::ZERO, Vec2::new(uv[0], uv[1]));
+            for influence in tail[..count * 4].chunks_exact(4) {
+                let bone = influence[0] as usize;
+                let matrix = bind_matrices
+                    .get(bone)
+                    .ok_or_else(|| format!("unknown bone index {}", bone))?;
+                let weight = influence[3];
+                vertex.position +=
+                    matrix.transform_point2(Vec2::new(influence[1], influence[2])) * weight;
+                vertex = vertex.with_weight(bone, weight);
+            }
+            vertices.push(vertex);
+            rest = &tail[count * 4..];
+        }
+    }
+    SkinnedMesh::new(vertices, indices, skeleton)
+}
@@ -0,0 +1,498 @@
+use crate::ecs::Entity;
+use crate::events::AnimationEvent;
+#[cfg(feature = "opengl")]
+use crate::render::sprite::Sprite;
+use serde::Deserialize;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
+
+/// Source rectangle in texture pixels (x, y, width, height), as in `Sprite::region`
+pub type FrameRegion = (f32, f32, f32, f32);
+
+/// One frame of a sprite sheet clip
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteFrame {
+    pub region: FrameRegion,
+    /// Seconds the frame is shown for
+    pub duration: f32,
+}
+
+impl SpriteFrame {
+    pub fn new(region: FrameRegion, duration: f32) -> Self {
+        Self { region, duration }
+    }
+}
+
+/// What happens when playback reaches the last frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoopMode {
+    /// Stop on the last frame
+    Once,
+    /// Start again from the first frame
+    #[default]
+    Loop,
+    /// Play backwards to the first frame, then forwards again
+    PingPong,
+    /// Play from the last frame to the first, then start again from the last
+    Reverse,
+}
+
+fn default_speed() -> f32 {
+    1.0
+}
+
+fn default_grid_size() -> u32 {
+    1
+}
+
+fn default_frame_duration() -> f32 {
+    0.1
+}
+
+/// A clip definition file, as read by `SpriteSheetAnimation::parse`
+#[derive(Debug, Deserialize)]
+struct ClipDefinition {
+    frames: Option<Vec<FrameDefinition>>,
+    grid: Option<GridDefinition>,
+    #[serde(rename = "loop")]
+    loop_mode: Option<LoopMode>,
+    #[serde(default = "default_speed")]
+    speed: f32,
+    #[serde(default)]
+    markers: Vec<MarkerDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FrameDefinition {
+    region: [f32; 4],
+    #[serde(default = "default_frame_duration")]
+    duration: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GridDefinition {
+    #[serde(default = "default_grid_size")]
+    columns: u32,
+    #[serde(default = "default_grid_size")]
+    rows: u32,
+    /// Frames in the grid; all of its cells by default
+    count: Option<usize>,
+    #[serde(default)]
+    frame_width: f32,
+    #[serde(default)]
+    frame_height: f32,
+    #[serde(default = "default_frame_duration")]
+    duration: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct MarkerDefinition {
+    #[serde(default)]
+    frame: usize,
+    name: String,
+}
+
+/// Shared playback callback; `Fn` rather than `FnMut` so clips stay `Clone`
+#[derive(Clone)]
+struct Callback(Arc<dyn Fn() + Send + Sync>);
+
+impl fmt::Debug for Callback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Callback")
+    }
+}
+
+/// Plays a sequence of sprite sheet frames with per-frame durations
+///
+/// ```
+/// # use engine_2d::animation::SpriteSheetAnimation;
+/// # let delta_time = 0.1;
+/// let mut run = SpriteSheetAnimation::from_grid(8, 1, 8, (32.0, 32.0), 0.08)?;
+/// run.play();
+/// // each frame; `update_sprite` also shows the frame on a sprite
+/// run.update(delta_time);
+/// let (x, y, width, height) = run.current_region();
+/// # assert_eq!((x, y, width, height), (32.0, 0.0, 32.0, 32.0));
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpriteSheetAnimation {
+    frames: Vec<SpriteFrame>,
+    loop_mode: LoopMode,
+    speed: f32,
+    current: usize,
+    /// Time spent on the current frame
+    frame_time: f32,
+    /// Playing backwards in `PingPong` mode
+    reversed: bool,
+    playing: bool,
+    finished: bool,
+    /// Named events on frame indices
+    markers: Vec<(usize, String)>,
+    /// Markers reached but not yet taken
+    pending: Vec<String>,
+    /// Playback has begun since the last rewind
+    started: bool,
+    on_complete: Option<Callback>,
+    on_loop: Option<Callback>,
+}
+
+impl SpriteSheetAnimation {
+    /// Create a stopped clip; fails if there are no frames or a duration isn't positive
+    pub fn new(frames: Vec<SpriteFrame>) -> Result<Self, String> {
+        if frames.is_empty() {
+            return Err("Sprite sheet animation needs at least one frame".to_string());
+        }
+        if let Some(index) = frames.iter().position(|frame| frame.duration <= 0.0) {
+            return Err(format!("Frame {} must have a positive duration", index));
+        }
+        Ok(Self {
+            frames,
+            loop_mode: LoopMode::default(),
+            speed: 1.0,
+            current: 0,
+            frame_time: 0.0,
+            reversed: false,
+            playing: false,
+            finished: false,
+            markers: Vec::new(),
+            pending: Vec::new(),
+            started: false,
+            on_complete: None,
+            on_loop: None,
+        })
+    }
+
+    /// Frames from the first `count` cells of a grid of `frame_size` cells, row by row,
+    /// each shown for `duration` seconds
+    pub fn from_grid(
+        columns: u32,
+        rows: u32,
+        count: usize,
+        frame_size: (f32, f32),
+        duration: f32,
+    ) -> Result<Self, String> {
+        if columns == 0 || rows == 0 {
+            return Err("Sprite sheet grid must have at least one row and column".to_string());
+        }
+        if count > (columns * rows) as usize {
+            return Err(format!(
+                "{} frames don't fit in a {}x{} grid",
+                count, columns, rows
+            ));
+        }
+        let (width, height) = frame_size;
+        let frames = (0..count as u32)
+            .map(|index| {
+                let (column, row) = (index % columns, index / columns);
+                let region = (column as f32 * width, row as f32 * height, width, height);
+                SpriteFrame::new(region, duration)
+            })
+            .collect();
+        Self::new(frames)
+    }
+
+    /// Load a clip from a JSON definition file; see `parse` for the format
+    pub fn load(path: &str) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read sprite sheet '{}': {}", path, e))?;
+        Self::parse(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Parse a JSON clip definition with either a frame list or a grid
+    ///
+    /// ```text
+    /// {
+    ///     "frames": [{ "region": [0, 0, 32, 32], "duration": 0.1 }, ...],
+    ///     "grid": { "columns": 4, "rows": 2, "count": 8, "frame_width": 32,
+    ///               "frame_height": 32, "duration": 0.1 },
+    ///     "loop": "loop" | "once" | "ping_pong" | "reverse",
+    ///     "speed": 1.0,
+    ///     "markers": [{ "frame": 2, "name": "footstep" }]
+    /// }
+    /// ```
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let definition: ClipDefinition = serde_json::from_str(source)
+            .map_err(|e| format!("Invalid sprite sheet JSON: {}", e))?;
+        let mut clip = if let Some(frames) = definition.frames {
+            let frames = frames
+                .into_iter()
+                .map(|frame| {
+                    let [x, y, width, height] = frame.region;
+                    SpriteFrame::new((x, y, width, height), frame.duration)
+                })
+                .collect();
+            Self::new(frames)?
+        } else if let Some(grid) = definition.grid {
+            Self::from_grid(
+                grid.columns,
+                grid.rows,
+                grid.count.unwrap_or((grid.columns * grid.rows) as usize),
+                (grid.frame_width, grid.frame_height),
+                grid.duration,
+            )?
+        } else {
+            return Err("Sprite sheet needs 'frames' or 'grid'".to_string());
+        };
+
+        if let Some(mode) = definition.loop_mode {
+            clip.set_loop_mode(mode);
+        }
+        clip.set_speed(definition.speed);
+        for marker in definition.markers {
+            clip.add_marker(marker.frame, &marker.name)?;
+        }
+        Ok(clip)
+    }
+
+    /// Take the frames, loop mode, speed and markers of `definition`, keeping playback
+    /// state and callbacks; the current frame and its elapsed time are clamped to fit
+    pub fn reload_from(&mut self, definition: &SpriteSheetAnimation) {
+        self.frames = definition.frames.clone();
+        self.loop_mode = definition.loop_mode;
+        self.speed = definition.speed;
+        self.markers = definition.markers.clone();
+        let last = self.frames.len() - 1;
+        if self.current > last {
+            self.current = last;
+            self.reversed = false;
+        }
+        self.frame_time = self.frame_time.min(self.frames[self.current].duration);
+        if !self.started {
+            self.rewind();
+        }
+    }
+
+    pub fn with_loop_mode(mut self, loop_mode: LoopMode) -> Self {
+        self.set_loop_mode(loop_mode);
+        self
+    }
+
+    /// Playback rate multiplier; 2.0 plays twice as fast
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed.max(0.0);
+        self
+    }
+
+    /// Name an event that fires whenever playback enters `frame`, e.g. a footstep
+    pub fn add_marker(&mut self, frame: usize, name: &str) -> Result<(), String> {
+        if frame >= self.frames.len() {
+            return Err(format!(
+                "Marker '{}' is on frame {} of a {} frame clip",
+                name,
+                frame,
+                self.frames.len()
+            ));
+        }
+        self.markers.push((frame, name.to_string()));
+        Ok(())
+    }
+
+    pub fn markers(&self) -> &[(usize, String)] {
+        &self.markers
+    }
+
+    /// Change the loop mode; a clip that hasn't started moves to the mode's first frame
+    pub fn set_loop_mode(&mut self, loop_mode: LoopMode) {
+        self.loop_mode = loop_mode;
+        if !self.started {
+            self.rewind();
+        }
+    }
+
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// Called when a `LoopMode::Once` clip finishes
+    pub fn on_complete(mut self, callback: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_complete = Some(Callback(Arc::new(callback)));
+        self
+    }
+
+    /// Called each time a looping clip starts another cycle: when `Loop` and `Reverse`
+    /// wrap around, and when `PingPong` gets back to the first frame
+    pub fn on_loop(mut self, callback: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_loop = Some(Callback(Arc::new(callback)));
+        self
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Start or resume playback; a finished clip restarts from the first frame
+    pub fn play(&mut self) {
+        if self.finished {
+            self.rewind();
+        }
+        self.playing = true;
+        if !self.started {
+            self.started = true;
+            self.queue_markers();
+        }
+    }
+
+    /// Hold the current frame
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Stop and go back to the first frame
+    pub fn stop(&mut self) {
+        self.playing = false;
+        self.rewind();
+    }
+
+    fn rewind(&mut self) {
+        self.current = match self.loop_mode {
+            LoopMode::Reverse => self.frames.len() - 1,
+            _ => 0,
+        };
+        self.frame_time = 0.0;
+        self.reversed = false;
+        self.finished = false;
+        self.started = false;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// A `LoopMode::Once` clip that reached its last frame
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn frames(&self) -> &[SpriteFrame] {
+        &self.frames
+    }
+
+    /// Index of the frame being shown
+    pub fn current_frame(&self) -> usize {
+        self.current
+    }
+
+    /// Jump to a frame, clamped to the clip
+    pub fn set_frame(&mut self, index: usize) {
+        self.current = index.min(self.frames.len() - 1);
+        self.frame_time = 0.0;
+        self.finished = false;
+    }
+
+    /// Texture region of the frame being shown
+    pub fn current_region(&self) -> FrameRegion {
+        self.frames[self.current].region
+    }
+
+    /// Total length of one pass through the frames, in seconds
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Advance playback; returns true if the frame changed
+    pub fn update(&mut self, delta_time: f32) -> bool {
+        if !self.playing {
+            return false;
+        }
+        let start = self.current;
+        self.frame_time += delta_time * self.speed;
+        while self.playing && self.frame_time >= self.frames[self.current].duration {
+            self.frame_time -= self.frames[self.current].duration;
+            if self.advance() {
+                self.queue_markers();
+            }
+        }
+        self.current != start
+    }
+
+    /// Step to the next frame according to the loop mode; returns false if no frame was
+    /// entered
+    fn advance(&mut self) -> bool {
+        let last = self.frames.len() - 1;
+        match self.loop_mode {
+            LoopMode::Once if self.current == last => {
+                self.playing = false;
+                self.finished = true;
+                self.frame_time = 0.0;
+                Self::call(&self.on_complete);
+                return false;
+            }
+            LoopMode::Once => self.current += 1,
+            LoopMode::Loop => {
+                self.current = (self.current + 1) % self.frames.len();
+                if self.current == 0 {
+                    Self::call(&self.on_loop);
+                }
+            }
+            LoopMode::Reverse => {
+                self.current = self.current.checked_sub(1).unwrap_or(last);
+                if self.current == last {
+                    Self::call(&self.on_loop);
+                }
+            }
+            LoopMode::PingPong if last == 0 => return false,
+            LoopMode::PingPong => {
+                if self.reversed && self.current == 0 {
+                    self.reversed = false;
+                } else if !self.reversed && self.current == last {
+                    self.reversed = true;
+                }
+                if self.reversed {
+                    self.current -= 1;
+                } else {
+                    self.current += 1;
+                }
+                if self.current == 0 {
+                    Self::call(&self.on_loop);
+                }
+            }
+        }
+        true
+    }
+
+    fn call(callback: &Option<Callback>) {
+        if let Some(Callback(callback)) = callback {
+            callback();
+        }
+    }
+
+    fn queue_markers(&mut self) {
+        for (frame, name) in &self.markers {
+            if *frame == self.current {
+                self.pending.push(name.clone());
+            }
+        }
+    }
+
+    /// Names of the markers reached since the last call, in order
+    pub fn take_events(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Send the markers reached since the last call as `AnimationEvent`s
+    pub fn dispatch_events(&mut self, entity: Option<Entity>, sender: &Sender<AnimationEvent>) {
+        for name in self.take_events() {
+            let _ = sender.send(AnimationEvent::marker(&name, entity));
+        }
+    }
+
+    /// Show the current frame on a sprite
+    #[cfg(feature = "opengl")]
+    pub fn apply(&self, sprite: &mut Sprite) {
+        sprite.set_region(Some(self.current_region()));
+    }
+
+    /// Advance playback and show the resulting frame on a sprite
+    #[cfg(feature = "opengl")]
+    pub fn update_sprite(&mut self, delta_time: f32, sprite: &mut Sprite) {
+        self.update(delta_time);
+        self.apply(sprite);
+    }
+}
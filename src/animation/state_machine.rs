@@ -0,0 +1,390 @@
+use super::sprite_sheet::{FrameRegion, SpriteSheetAnimation};
+use crate::ecs::Entity;
+use crate::events::AnimationEvent;
+#[cfg(feature = "opengl")]
+use crate::render::sprite::Sprite;
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+
+/// A test on the controller's parameters or current clip
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// The bool parameter has this value (unset bools are false)
+    Bool(String, bool),
+    /// The float parameter is above the threshold (unset floats are 0)
+    Greater(String, f32),
+    /// The float parameter is below the threshold
+    Less(String, f32),
+    /// The trigger is set; taking the transition resets it
+    Trigger(String),
+    /// The current clip has finished (`LoopMode::Once` clips only)
+    ClipFinished,
+}
+
+impl Condition {
+    pub fn is_true(name: &str) -> Self {
+        Condition::Bool(name.to_string(), true)
+    }
+
+    pub fn is_false(name: &str) -> Self {
+        Condition::Bool(name.to_string(), false)
+    }
+
+    pub fn greater(name: &str, threshold: f32) -> Self {
+        Condition::Greater(name.to_string(), threshold)
+    }
+
+    pub fn less(name: &str, threshold: f32) -> Self {
+        Condition::Less(name.to_string(), threshold)
+    }
+
+    pub fn trigger(name: &str) -> Self {
+        Condition::Trigger(name.to_string())
+    }
+}
+
+/// A move between states once every condition holds
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    /// None for a transition from any state
+    from: Option<String>,
+    to: String,
+    conditions: Vec<Condition>,
+    /// Minimum time in the source state, in seconds
+    exit_time: f32,
+    /// Seconds over which `blend_weight` rises from 0 to 1 after the transition
+    blend_duration: f32,
+    /// Start the target clip from its first frame
+    restart: bool,
+}
+
+impl Transition {
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: Some(from.to_string()),
+            ..Self::from_any(to)
+        }
+    }
+
+    /// A transition taken from whichever state is current, except `to` itself
+    pub fn from_any(to: &str) -> Self {
+        Self {
+            from: None,
+            to: to.to_string(),
+            conditions: Vec::new(),
+            exit_time: 0.0,
+            blend_duration: 0.0,
+            restart: true,
+        }
+    }
+
+    /// Add a condition; all of them must hold
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Only leave the source state after this many seconds in it
+    pub fn with_exit_time(mut self, seconds: f32) -> Self {
+        self.exit_time = seconds.max(0.0);
+        self
+    }
+
+    pub fn with_blend(mut self, seconds: f32) -> Self {
+        self.blend_duration = seconds.max(0.0);
+        self
+    }
+
+    /// Resume the target clip where it was left instead of restarting it
+    pub fn without_restart(mut self) -> Self {
+        self.restart = false;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Param {
+    Bool(bool),
+    Float(f32),
+    Trigger,
+}
+
+/// Moves between named animation clips when parameter conditions are met
+///
+/// ```
+/// # use engine_2d::animation::{AnimationController, Condition, LoopMode, SpriteSheetAnimation, Transition};
+/// # use glam::Vec2;
+/// # let clip = SpriteSheetAnimation::from_grid(4, 1, 4, (32.0, 32.0), 0.1)?;
+/// # let (idle_clip, run_clip, jump_clip) = (clip.clone(), clip.clone(), clip);
+/// # let (velocity, delta_time) = (Vec2::new(3.0, 0.0), 0.016);
+/// let mut controller = AnimationController::new();
+/// controller.add_state("idle", idle_clip);
+/// controller.add_state("run", run_clip);
+/// controller.add_state("jump", jump_clip.with_loop_mode(LoopMode::Once));
+/// let run = Transition::new("idle", "run").when(Condition::greater("speed", 0.1));
+/// controller.add_transition(run.with_blend(0.1))?;
+/// controller.add_transition(Transition::new("run", "idle").when(Condition::less("speed", 0.1)))?;
+/// controller.add_transition(Transition::from_any("jump").when(Condition::trigger("jump")))?;
+/// controller.add_transition(Transition::new("jump", "idle").when(Condition::ClipFinished))?;
+///
+/// controller.set_float("speed", velocity.length());
+/// // each frame; `update_sprite` also shows the frame on a sprite
+/// controller.update(delta_time);
+/// # assert_eq!(controller.current_state(), "run");
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnimationController {
+    states: HashMap<String, SpriteSheetAnimation>,
+    /// Checked in the order added; the first transition that passes is taken
+    transitions: Vec<Transition>,
+    params: HashMap<String, Param>,
+    current: Option<String>,
+    previous: Option<String>,
+    time_in_state: f32,
+    blend_duration: f32,
+    /// Playback rate of the whole controller, on top of each clip's own speed
+    speed: f32,
+}
+
+impl Default for AnimationController {
+    fn default() -> Self {
+        Self {
+            states: HashMap::new(),
+            transitions: Vec::new(),
+            params: HashMap::new(),
+            current: None,
+            previous: None,
+            time_in_state: 0.0,
+            blend_duration: 0.0,
+            speed: 1.0,
+        }
+    }
+}
+
+impl AnimationController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Playback rate multiplier for every state, including exit and blend times
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.set_speed(speed);
+        self
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Add a state playing `clip`; the first state added is the initial one
+    pub fn add_state(&mut self, name: &str, mut clip: SpriteSheetAnimation) {
+        if self.current.is_none() {
+            clip.play();
+            self.current = Some(name.to_string());
+        }
+        self.states.insert(name.to_string(), clip);
+    }
+
+    /// Update a state's clip from an edited definition without interrupting its playback;
+    /// returns false if there is no such state
+    pub fn reload_state(&mut self, name: &str, definition: &SpriteSheetAnimation) -> bool {
+        match self.states.get_mut(name) {
+            Some(clip) => {
+                clip.reload_from(definition);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Add a transition between states that were already added
+    pub fn add_transition(&mut self, transition: Transition) -> Result<(), String> {
+        for state in transition.from.iter().chain([&transition.to]) {
+            if !self.states.contains_key(state) {
+                return Err(format!("Unknown animation state '{}'", state));
+            }
+        }
+        self.transitions.push(transition);
+        Ok(())
+    }
+
+    pub fn set_bool(&mut self, name: &str, value: bool) {
+        self.params.insert(name.to_string(), Param::Bool(value));
+    }
+
+    pub fn set_float(&mut self, name: &str, value: f32) {
+        self.params.insert(name.to_string(), Param::Float(value));
+    }
+
+    /// Set a trigger, which stays set until a transition uses it
+    pub fn set_trigger(&mut self, name: &str) {
+        self.params.insert(name.to_string(), Param::Trigger);
+    }
+
+    pub fn reset_trigger(&mut self, name: &str) {
+        if self.params.get(name) == Some(&Param::Trigger) {
+            self.params.remove(name);
+        }
+    }
+
+    pub fn bool(&self, name: &str) -> bool {
+        matches!(self.params.get(name), Some(Param::Bool(true)))
+    }
+
+    pub fn float(&self, name: &str) -> f32 {
+        match self.params.get(name) {
+            Some(Param::Float(value)) => *value,
+            _ => 0.0,
+        }
+    }
+
+    pub fn is_triggered(&self, name: &str) -> bool {
+        self.params.get(name) == Some(&Param::Trigger)
+    }
+
+    /// Name of the current state; empty until a state is added
+    pub fn current_state(&self) -> &str {
+        self.current.as_deref().unwrap_or("")
+    }
+
+    /// State left by the last transition
+    pub fn previous_state(&self) -> Option<&str> {
+        self.previous.as_deref()
+    }
+
+    /// Seconds since entering the current state
+    pub fn time_in_state(&self) -> f32 {
+        self.time_in_state
+    }
+
+    /// Progress of the last transition's blend, from 0 (all previous state) to 1
+    pub fn blend_weight(&self) -> f32 {
+        if self.blend_duration <= 0.0 {
+            1.0
+        } else {
+            (self.time_in_state / self.blend_duration).min(1.0)
+        }
+    }
+
+    pub fn clip(&self, state: &str) -> Option<&SpriteSheetAnimation> {
+        self.states.get(state)
+    }
+
+    pub fn current_clip(&self) -> Option<&SpriteSheetAnimation> {
+        self.states.get(self.current.as_ref()?)
+    }
+
+    fn current_clip_mut(&mut self) -> Option<&mut SpriteSheetAnimation> {
+        self.states.get_mut(self.current.as_ref()?)
+    }
+
+    /// Texture region of the current clip's frame
+    pub fn current_region(&self) -> Option<FrameRegion> {
+        self.current_clip()
+            .map(SpriteSheetAnimation::current_region)
+    }
+
+    /// Switch to a state immediately, ignoring transitions
+    pub fn force_state(&mut self, name: &str) -> Result<(), String> {
+        if !self.states.contains_key(name) {
+            return Err(format!("Unknown animation state '{}'", name));
+        }
+        self.enter(name.to_string(), 0.0, true);
+        Ok(())
+    }
+
+    /// Take at most one transition, then advance the current clip; returns true if the
+    /// state changed
+    pub fn update(&mut self, delta_time: f32) -> bool {
+        let delta_time = delta_time * self.speed;
+        self.time_in_state += delta_time;
+        let transition = self
+            .transitions
+            .iter()
+            .position(|transition| self.passes(transition));
+        if let Some(index) = transition {
+            let transition = self.transitions[index].clone();
+            for condition in &transition.conditions {
+                if let Condition::Trigger(name) = condition {
+                    self.params.remove(name);
+                }
+            }
+            self.enter(transition.to, transition.blend_duration, transition.restart);
+        }
+        if let Some(clip) = self.current_clip_mut() {
+            clip.update(delta_time);
+        }
+        transition.is_some()
+    }
+
+    fn passes(&self, transition: &Transition) -> bool {
+        let Some(current) = &self.current else {
+            return false;
+        };
+        let from_current = match &transition.from {
+            Some(from) => from == current,
+            None => &transition.to != current,
+        };
+        from_current
+            && self.time_in_state >= transition.exit_time
+            && transition
+                .conditions
+                .iter()
+                .all(|condition| match condition {
+                    Condition::Bool(name, value) => self.bool(name) == *value,
+                    Condition::Greater(name, threshold) => self.float(name) > *threshold,
+                    Condition::Less(name, threshold) => self.float(name) < *threshold,
+                    Condition::Trigger(name) => self.is_triggered(name),
+                    Condition::ClipFinished => self
+                        .current_clip()
+                        .is_some_and(SpriteSheetAnimation::is_finished),
+                })
+    }
+
+    fn enter(&mut self, state: String, blend_duration: f32, restart: bool) {
+        self.previous = self.current.replace(state);
+        self.time_in_state = 0.0;
+        self.blend_duration = blend_duration;
+        if let Some(clip) = self.current_clip_mut() {
+            if restart {
+                clip.stop();
+            }
+            clip.play();
+        }
+    }
+
+    /// Names of the clip markers reached since the last call
+    pub fn take_events(&mut self) -> Vec<String> {
+        self.states
+            .values_mut()
+            .flat_map(SpriteSheetAnimation::take_events)
+            .collect()
+    }
+
+    /// Send the clip markers reached since the last call as `AnimationEvent`s
+    pub fn dispatch_events(&mut self, entity: Option<Entity>, sender: &Sender<AnimationEvent>) {
+        for name in self.take_events() {
+            let _ = sender.send(AnimationEvent::marker(&name, entity));
+        }
+    }
+
+    /// Show the current clip's frame on a sprite
+    #[cfg(feature = "opengl")]
+    pub fn apply(&self, sprite: &mut Sprite) {
+        if let Some(clip) = self.current_clip() {
+            clip.apply(sprite);
+        }
+    }
+
+    /// Update the state machine and show the resulting frame on a sprite
+    #[cfg(feature = "opengl")]
+    pub fn update_sprite(&mut self, delta_time: f32, sprite: &mut Sprite) {
+        self.update(delta_time);
+        self.apply(sprite);
+    }
+}
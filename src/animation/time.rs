@@ -0,0 +1,83 @@
+use crate::ecs::World;
+
+/// Global speed of animations, for slow motion and pausing
+///
+/// `Engine` keeps one as a world resource and scales the time given to the `Animation`
+/// and to animation systems such as `timeline_system` by it.
+///
+/// ```
+/// # use engine_2d::engine::{Engine, EngineConfig, Game, UpdateContext};
+/// # struct Idle;
+/// # impl Game for Idle {
+/// #     fn update(&mut self, _ctx: &mut UpdateContext<'_>) {}
+/// #     fn name(&self) -> &str {
+/// #         "Idle"
+/// #     }
+/// # }
+/// # let mut engine = Engine::new_headless(EngineConfig::default(), Box::new(Idle)).unwrap();
+/// engine.animation_time_mut().set_scale(0.25); // bullet time
+/// engine.animation_time_mut().pause();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationTime {
+    scale: f32,
+    paused: bool,
+}
+
+impl Default for AnimationTime {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            paused: false,
+        }
+    }
+}
+
+impl AnimationTime {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Multiplier for animation time; 0.5 is half speed
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.set_scale(scale);
+        self
+    }
+
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.max(0.0);
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Freeze animations without losing the time scale
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Animation time passed during `delta_time` seconds of game time
+    pub fn delta(&self, delta_time: f32) -> f32 {
+        if self.paused {
+            0.0
+        } else {
+            delta_time * self.scale
+        }
+    }
+}
+
+/// `delta_time` scaled by the world's `AnimationTime`, or unchanged if it has none
+pub fn animation_delta(world: &World, delta_time: f32) -> f32 {
+    world
+        .resource::<AnimationTime>()
+        .map_or(delta_time, |time| time.delta(delta_time))
+}
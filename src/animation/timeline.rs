@@ -0,0 +1,395 @@
+use super::time::animation_delta;
+use super::tween::{Easing, Tweenable};
+#[cfg(feature = "opengl")]
+use crate::ecs::SpriteComponent;
+use crate::ecs::{Entity, Transform, World};
+use crate::events::AnimationEvent;
+use glam::Vec2;
+use serde::{Deserialize, Deserializer};
+use std::sync::mpsc::Sender;
+
+/// A value at a point in time, eased in from the previous key
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Keyframe<T> {
+    pub time: f32,
+    pub value: T,
+    /// Easing of the segment arriving at this key
+    #[serde(default)]
+    pub ease: Easing,
+}
+
+/// Keyframes of one property, sorted by time
+#[derive(Debug, Clone)]
+pub struct Track<T> {
+    keys: Vec<Keyframe<T>>,
+}
+
+impl<T> Default for Track<T> {
+    fn default() -> Self {
+        Self { keys: Vec::new() }
+    }
+}
+
+/// Tracks are read as a list of keys in any order
+impl<'de, T: Tweenable + Deserialize<'de>> Deserialize<'de> for Track<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut track = Track::new();
+        for key in Vec::<Keyframe<T>>::deserialize(deserializer)? {
+            track.add_key(key.time, key.value, key.ease);
+        }
+        Ok(track)
+    }
+}
+
+impl<T: Tweenable> Track<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a key, keeping the track sorted; a key at the same time is replaced
+    pub fn add_key(&mut self, time: f32, value: T, ease: impl Into<Easing>) {
+        let ease = ease.into();
+        let key = Keyframe { time, value, ease };
+        match self.keys.iter().position(|k| k.time >= time) {
+            Some(index) if self.keys[index].time == time => self.keys[index] = key,
+            Some(index) => self.keys.insert(index, key),
+            None => self.keys.push(key),
+        }
+    }
+
+    pub fn with_key(mut self, time: f32, value: T, ease: impl Into<Easing>) -> Self {
+        self.add_key(time, value, ease);
+        self
+    }
+
+    pub fn keys(&self) -> &[Keyframe<T>] {
+        &self.keys
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Time of the last key
+    pub fn duration(&self) -> f32 {
+        self.keys.last().map_or(0.0, |key| key.time)
+    }
+
+    /// Value at `time`, holding the first and last keys outside their range; None for
+    /// an empty track
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let first = self.keys.first()?;
+        if time <= first.time {
+            return Some(first.value);
+        }
+        for pair in self.keys.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            if time < to.time {
+                let t = (time - from.time) / (to.time - from.time);
+                return Some(T::lerp(from.value, to.value, to.ease.apply(t)));
+            }
+        }
+        self.keys.last().map(|key| key.value)
+    }
+}
+
+/// Values of every non-empty track at one time
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TimelineSample {
+    pub position: Option<Vec2>,
+    /// Radians
+    pub rotation: Option<f32>,
+    pub scale: Option<Vec2>,
+    /// RGBA tint
+    pub color: Option<(f32, f32, f32, f32)>,
+}
+
+impl TimelineSample {
+    /// Write the sampled position, rotation and scale into a transform
+    pub fn apply_to_transform(&self, transform: &mut Transform) {
+        if let Some(position) = self.position {
+            transform.position = position;
+        }
+        if let Some(rotation) = self.rotation {
+            transform.rotation = rotation;
+        }
+        if let Some(scale) = self.scale {
+            transform.scale = scale;
+        }
+    }
+}
+
+/// Multi-track keyframe animation for cutscenes and UI transitions
+///
+/// Timelines can be authored in JSON files with a list of keys per track, in any order:
+///
+/// ```text
+/// {
+///     "position": [
+///         { "time": 0.0, "value": [0, 0] },
+///         { "time": 1.5, "value": [120, 40], "ease": "ease_out" }
+///     ],
+///     "rotation": [{ "time": 1.5, "value": 3.1416, "ease": "ease_in_out" }],
+///     "scale": [{ "time": 0.0, "value": [1, 1] }],
+///     "color": [{ "time": 2.0, "value": [1, 1, 1, 0] }],
+///     "events": [{ "time": 1.5, "name": "land" }],
+///     "looping": true
+/// }
+/// ```
+///
+/// Every field is optional. Easing names are those of `easing_by_name`; keys without one
+/// are linear. `events` name markers that `TimelinePlayer` reports when playback reaches
+/// their time.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Timeline {
+    pub position: Track<Vec2>,
+    pub rotation: Track<f32>,
+    pub scale: Track<Vec2>,
+    pub color: Track<(f32, f32, f32, f32)>,
+    /// Named markers, sorted by time
+    #[serde(deserialize_with = "deserialize_events")]
+    events: Vec<(f32, String)>,
+    /// Start again from the beginning after the last key
+    pub looping: bool,
+}
+
+/// An event marker as written in timeline files
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EventKey {
+    time: f32,
+    name: String,
+}
+
+fn deserialize_events<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(f32, String)>, D::Error> {
+    let mut timeline = Timeline::new();
+    for event in Vec::<EventKey>::deserialize(deserializer)? {
+        timeline.add_event(event.time, &event.name);
+    }
+    Ok(timeline.events)
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Name an event that fires when playback reaches `time`
+    pub fn add_event(&mut self, time: f32, name: &str) {
+        let index = self.events.partition_point(|(t, _)| *t <= time);
+        self.events.insert(index, (time, name.to_string()));
+    }
+
+    pub fn with_event(mut self, time: f32, name: &str) -> Self {
+        self.add_event(time, name);
+        self
+    }
+
+    pub fn events(&self) -> &[(f32, String)] {
+        &self.events
+    }
+
+    /// Time of the last key or event on any track
+    pub fn duration(&self) -> f32 {
+        let last_event = self.events.last().map_or(0.0, |(time, _)| *time);
+        self.position
+            .duration()
+            .max(self.rotation.duration())
+            .max(self.scale.duration())
+            .max(self.color.duration())
+            .max(last_event)
+    }
+
+    pub fn sample(&self, time: f32) -> TimelineSample {
+        TimelineSample {
+            position: self.position.sample(time),
+            rotation: self.rotation.sample(time),
+            scale: self.scale.sample(time),
+            color: self.color.sample(time),
+        }
+    }
+
+    /// Load a timeline from a JSON file (see `Timeline` for the format)
+    pub fn load(path: &str) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read timeline file '{}': {}", path, e))?;
+        Self::parse(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Parse the JSON form of a timeline
+    pub fn parse(source: &str) -> Result<Self, String> {
+        serde_json::from_str(source).map_err(|e| format!("Invalid timeline JSON: {}", e))
+    }
+}
+
+/// Plays a `Timeline`; attach it to an entity and run `timeline_system` to drive the
+/// entity's `Transform` (and `SpriteComponent` tint)
+#[derive(Debug, Clone)]
+pub struct TimelinePlayer {
+    timeline: Timeline,
+    time: f32,
+    speed: f32,
+    playing: bool,
+    /// Playback has begun since the last stop, so events at the start time already fired
+    started: bool,
+    /// Events reached but not yet taken
+    pending: Vec<String>,
+}
+
+impl TimelinePlayer {
+    /// A player that starts playing immediately
+    pub fn new(timeline: Timeline) -> Self {
+        Self {
+            timeline,
+            time: 0.0,
+            speed: 1.0,
+            playing: true,
+            started: false,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Playback rate multiplier; 2.0 plays twice as fast
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.set_speed(speed);
+        self
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn timeline(&self) -> &Timeline {
+        &self.timeline
+    }
+
+    /// Swap in an edited timeline, keeping the playback time (clamped to its length),
+    /// speed and play state
+    pub fn set_timeline(&mut self, timeline: Timeline) {
+        self.timeline = timeline;
+        self.time = self.time.min(self.timeline.duration());
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Pause and go back to the start
+    pub fn stop(&mut self) {
+        self.playing = false;
+        self.time = 0.0;
+        self.started = false;
+    }
+
+    /// Jump to a time without firing the events in between
+    pub fn seek(&mut self, time: f32) {
+        self.time = time.clamp(0.0, self.timeline.duration());
+        self.started = true;
+    }
+
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// A non-looping timeline that reached its end
+    pub fn is_finished(&self) -> bool {
+        !self.timeline.looping && self.time >= self.timeline.duration()
+    }
+
+    /// Advance time and return the values at the new time
+    pub fn update(&mut self, delta_time: f32) -> TimelineSample {
+        if self.playing {
+            let duration = self.timeline.duration();
+            let start = self.time;
+            let from_start = !self.started;
+            self.started = true;
+            self.time += delta_time * self.speed;
+            if self.time >= duration {
+                self.queue_events(start, duration, from_start);
+                if self.timeline.looping && duration > 0.0 {
+                    self.time %= duration;
+                    self.queue_events(0.0, self.time, true);
+                } else {
+                    self.time = duration;
+                    self.playing = false;
+                }
+            } else {
+                self.queue_events(start, self.time, from_start);
+            }
+        }
+        self.timeline.sample(self.time)
+    }
+
+    /// Queue the events after `from` (or at it, if `inclusive`) up to and including `to`
+    fn queue_events(&mut self, from: f32, to: f32, inclusive: bool) {
+        for (time, name) in &self.timeline.events {
+            if (*time > from || inclusive && *time == from) && *time <= to {
+                self.pending.push(name.clone());
+            }
+        }
+    }
+
+    /// Names of the events reached since the last call, in order
+    pub fn take_events(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Send the events reached since the last call as `AnimationEvent`s
+    pub fn dispatch_events(&mut self, entity: Option<Entity>, sender: &Sender<AnimationEvent>) {
+        for name in self.take_events() {
+            let _ = sender.send(AnimationEvent::marker(&name, entity));
+        }
+    }
+}
+
+/// Advance every `TimelinePlayer` and apply it to the entity's `Transform` (and
+/// `SpriteComponent` tint when rendering)
+///
+/// Time is scaled by the world's `AnimationTime`, if it has one. If the world has a
+/// `Sender<AnimationEvent>` resource (the engine adds
+/// `EventSystem::get_animation_sender()`), reached events are sent through it; otherwise
+/// they stay on the player until `take_events`.
+///
+/// ```
+/// # use engine_2d::animation::timeline_system;
+/// # use engine_2d::ecs::{Scheduler, Stage};
+/// # let mut scheduler = Scheduler::new();
+/// scheduler.add_system(Stage::Update, "timelines", timeline_system);
+/// ```
+pub fn timeline_system(world: &mut World, delta_time: f32) {
+    let delta_time = animation_delta(world, delta_time);
+    let sender = world.resource::<Sender<AnimationEvent>>().cloned();
+    for (entity, (player, transform)) in world.query::<(&mut TimelinePlayer, &mut Transform)>() {
+        player.update(delta_time).apply_to_transform(transform);
+        if let Some(sender) = &sender {
+            player.dispatch_events(Some(entity), sender);
+        }
+    }
+    #[cfg(feature = "opengl")]
+    for (_, (player, sprite)) in world.query::<(&TimelinePlayer, &mut SpriteComponent)>() {
+        if let Some(color) = player.timeline.sample(player.time).color {
+            sprite.0.set_tint_rgba(color);
+        }
+    }
+}
@@ -0,0 +1,491 @@
+use super::curve::AnimationCurve;
+use crate::render::layer::ColorTint;
+use crate::utils::math::interpolation;
+use glam::{Vec2, Vec3, Vec4};
+use serde::{Deserialize, Deserializer, de};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Maps linear progress in 0..1 to eased progress
+///
+/// Made from any `Fn(f32) -> f32`, such as the functions in `utils::math::interpolation`,
+/// or from an `AnimationCurve`.
+#[derive(Clone)]
+pub struct Easing(Arc<dyn Fn(f32) -> f32 + Send + Sync>);
+
+impl Easing {
+    pub fn new(ease: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        Self(Arc::new(ease))
+    }
+
+    pub fn apply(&self, t: f32) -> f32 {
+        (self.0)(t)
+    }
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Self::new(linear)
+    }
+}
+
+impl fmt::Debug for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Easing")
+    }
+}
+
+/// Easings are deserialized by name (see `easing_by_name`)
+impl<'de> Deserialize<'de> for Easing {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        easing_by_name(&name).ok_or_else(|| de::Error::custom(format!("unknown easing '{}'", name)))
+    }
+}
+
+impl<F: Fn(f32) -> f32 + Send + Sync + 'static> From<F> for Easing {
+    fn from(ease: F) -> Self {
+        Self::new(ease)
+    }
+}
+
+impl From<AnimationCurve> for Easing {
+    fn from(curve: AnimationCurve) -> Self {
+        Self::new(move |t| curve.evaluate(t))
+    }
+}
+
+/// Constant-speed easing, the default
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+/// Look up an easing by name, for animation data files: `linear`, `ease_in`, `ease_out`,
+/// `ease_in_out`, `bounce` or `elastic`
+pub fn easing_by_name(name: &str) -> Option<Easing> {
+    let ease: fn(f32) -> f32 = match name {
+        "linear" => linear,
+        "ease_in" => interpolation::ease_in,
+        "ease_out" => interpolation::ease_out,
+        "ease_in_out" => interpolation::ease_in_out,
+        "bounce" => interpolation::bounce,
+        "elastic" => interpolation::elastic,
+        _ => return None,
+    };
+    Some(Easing::new(ease))
+}
+
+/// A value that can be interpolated
+pub trait Tweenable: Copy + 'static {
+    fn lerp(a: Self, b: Self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        a + (b - a) * t
+    }
+}
+
+impl Tweenable for Vec2 {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        a.lerp(b, t)
+    }
+}
+
+impl Tweenable for Vec3 {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        a.lerp(b, t)
+    }
+}
+
+impl Tweenable for Vec4 {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        a.lerp(b, t)
+    }
+}
+
+/// An RGB color, as used for sprite tints
+impl Tweenable for (f32, f32, f32) {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        (
+            f32::lerp(a.0, b.0, t),
+            f32::lerp(a.1, b.1, t),
+            f32::lerp(a.2, b.2, t),
+        )
+    }
+}
+
+/// An RGBA color
+impl Tweenable for (f32, f32, f32, f32) {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        (
+            f32::lerp(a.0, b.0, t),
+            f32::lerp(a.1, b.1, t),
+            f32::lerp(a.2, b.2, t),
+            f32::lerp(a.3, b.3, t),
+        )
+    }
+}
+
+impl Tweenable for ColorTint {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        a.lerp(b, t)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Segment<T> {
+    target: T,
+    duration: f32,
+    ease: Easing,
+}
+
+/// How many extra times a tween plays after the first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    Times(u32),
+    Forever,
+}
+
+/// Interpolates a value through one or more targets over time
+///
+/// ```
+/// # use engine_2d::animation::tween;
+/// # use engine_2d::utils::math::interpolation;
+/// # use glam::Vec2;
+/// # let delta_time = 0.016;
+/// let mut slide = tween(Vec2::ZERO)
+///     .to(Vec2::new(100.0, 0.0)).over(0.5).ease(interpolation::ease_out)
+///     .to(Vec2::new(100.0, 50.0)).over(0.25)
+///     .delay(1.0)
+///     .on_complete(|| println!("done"));
+/// // each frame
+/// let position = slide.update(delta_time);
+/// ```
+pub struct Tween<T: Tweenable> {
+    start: T,
+    /// Chained targets, played in order
+    segments: Vec<Segment<T>>,
+    delay: f32,
+    repeat: Repeat,
+    /// Play every other repeat backwards
+    yoyo: bool,
+    on_complete: Option<Box<dyn FnMut()>>,
+    /// Time since the delay ended
+    elapsed: f32,
+    delay_left: f32,
+    /// Repeats played so far
+    cycle: u32,
+    value: T,
+    finished: bool,
+}
+
+impl<T: Tweenable + fmt::Debug> fmt::Debug for Tween<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tween")
+            .field("value", &self.value)
+            .field("segments", &self.segments.len())
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+/// Start a tween at `start`; add targets with `to`
+pub fn tween<T: Tweenable>(start: T) -> Tween<T> {
+    Tween::new(start)
+}
+
+impl<T: Tweenable> Tween<T> {
+    pub fn new(start: T) -> Self {
+        Self {
+            start,
+            segments: Vec::new(),
+            delay: 0.0,
+            repeat: Repeat::Times(0),
+            yoyo: false,
+            on_complete: None,
+            elapsed: 0.0,
+            delay_left: 0.0,
+            cycle: 0,
+            value: start,
+            finished: false,
+        }
+    }
+
+    /// Chain a target, reached over one second linearly unless `over`/`ease` follow
+    pub fn to(mut self, target: T) -> Self {
+        self.segments.push(Segment {
+            target,
+            duration: 1.0,
+            ease: Easing::default(),
+        });
+        self
+    }
+
+    /// Seconds to reach the last target added
+    pub fn over(mut self, duration: f32) -> Self {
+        if let Some(segment) = self.segments.last_mut() {
+            segment.duration = duration.max(0.0);
+        }
+        self
+    }
+
+    /// Easing for the last target added: a function or an `AnimationCurve`
+    pub fn ease(mut self, ease: impl Into<Easing>) -> Self {
+        if let Some(segment) = self.segments.last_mut() {
+            segment.ease = ease.into();
+        }
+        self
+    }
+
+    /// Wait before starting, once
+    pub fn delay(mut self, seconds: f32) -> Self {
+        self.delay = seconds.max(0.0);
+        self.delay_left = self.delay;
+        self
+    }
+
+    /// Play `times` more times after the first
+    pub fn repeat(mut self, times: u32) -> Self {
+        self.repeat = Repeat::Times(times);
+        self
+    }
+
+    pub fn repeat_forever(mut self) -> Self {
+        self.repeat = Repeat::Forever;
+        self
+    }
+
+    /// Play every other repeat backwards, so the value swings back and forth
+    pub fn yoyo(mut self, yoyo: bool) -> Self {
+        self.yoyo = yoyo;
+        self
+    }
+
+    /// Called once when the last repeat ends
+    pub fn on_complete(mut self, callback: impl FnMut() + 'static) -> Self {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Length of one play through every target, in seconds
+    pub fn cycle_duration(&self) -> f32 {
+        self.segments.iter().map(|segment| segment.duration).sum()
+    }
+
+    /// Go back to the start, including the delay
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+        self.delay_left = self.delay;
+        self.cycle = 0;
+        self.value = self.start;
+        self.finished = false;
+    }
+
+    /// Advance time and return the new value
+    pub fn update(&mut self, delta_time: f32) -> T {
+        if self.finished {
+            return self.value;
+        }
+        let mut delta_time = delta_time;
+        if self.delay_left > 0.0 {
+            let waited = delta_time.min(self.delay_left);
+            self.delay_left -= waited;
+            delta_time -= waited;
+            if self.delay_left > 0.0 {
+                return self.value;
+            }
+        }
+
+        self.elapsed += delta_time;
+        let cycle_duration = self.cycle_duration();
+        loop {
+            if self.elapsed < cycle_duration {
+                self.value = self.sample(self.elapsed);
+                return self.value;
+            }
+            let more = match self.repeat {
+                Repeat::Forever => cycle_duration > 0.0,
+                Repeat::Times(times) => self.cycle < times,
+            };
+            if !more {
+                self.value = self.sample(cycle_duration);
+                self.finished = true;
+                if let Some(callback) = &mut self.on_complete {
+                    callback();
+                }
+                return self.value;
+            }
+            self.elapsed -= cycle_duration;
+            self.cycle += 1;
+        }
+    }
+
+    /// Value `time` seconds into the current cycle
+    fn sample(&self, time: f32) -> T {
+        let cycle_duration = self.cycle_duration();
+        let backwards = self.yoyo && self.cycle % 2 == 1;
+        let time = if backwards {
+            cycle_duration - time
+        } else {
+            time
+        };
+
+        let mut from = self.start;
+        let mut segment_start = 0.0;
+        for segment in &self.segments {
+            let segment_end = segment_start + segment.duration;
+            if time < segment_end {
+                let t = (time - segment_start) / segment.duration;
+                return T::lerp(from, segment.target, segment.ease.apply(t));
+            }
+            from = segment.target;
+            segment_start = segment_end;
+        }
+        from
+    }
+}
+
+/// Handle to a tween in a `TweenManager`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TweenId(u64);
+
+trait AnyTween {
+    fn update(&mut self, delta_time: f32);
+    fn is_finished(&self) -> bool;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Tweenable> AnyTween for Tween<T> {
+    fn update(&mut self, delta_time: f32) {
+        Tween::update(self, delta_time);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Updates many tweens of any value type, read back by handle
+///
+/// A finished tween keeps its final value until the next `update`, then is removed.
+///
+/// ```
+/// # use engine_2d::animation::{TweenManager, tween};
+/// # use glam::Vec2;
+/// let mut tweens = TweenManager::new();
+/// let fade = tweens.add(tween(1.0).to(0.0).over(0.5));
+/// let slide = tweens.add(tween(Vec2::ZERO).to(Vec2::new(0.0, 40.0)).over(0.25));
+/// // each frame
+/// tweens.update(0.25);
+/// let alpha: f32 = tweens.value(fade).unwrap();
+/// let offset: Vec2 = tweens.value(slide).unwrap();
+/// # assert_eq!((alpha, offset), (0.5, Vec2::new(0.0, 40.0)));
+/// ```
+pub struct TweenManager {
+    tweens: HashMap<TweenId, Box<dyn AnyTween>>,
+    next_id: u64,
+    time_scale: f32,
+}
+
+impl Default for TweenManager {
+    fn default() -> Self {
+        Self {
+            tweens: HashMap::new(),
+            next_id: 0,
+            time_scale: 1.0,
+        }
+    }
+}
+
+impl fmt::Debug for TweenManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TweenManager")
+            .field("tweens", &self.tweens.len())
+            .finish()
+    }
+}
+
+impl TweenManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Playback rate multiplier for every tween; 0.5 is half speed
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale.max(0.0);
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    pub fn add<T: Tweenable>(&mut self, tween: Tween<T>) -> TweenId {
+        let id = TweenId(self.next_id);
+        self.next_id += 1;
+        self.tweens.insert(id, Box::new(tween));
+        id
+    }
+
+    /// Current value of a tween, or None if it is gone or not a `Tween<T>`
+    pub fn value<T: Tweenable>(&self, id: TweenId) -> Option<T> {
+        self.get::<T>(id).map(Tween::value)
+    }
+
+    pub fn get<T: Tweenable>(&self, id: TweenId) -> Option<&Tween<T>> {
+        self.tweens.get(&id)?.as_any().downcast_ref()
+    }
+
+    pub fn get_mut<T: Tweenable>(&mut self, id: TweenId) -> Option<&mut Tween<T>> {
+        self.tweens.get_mut(&id)?.as_any_mut().downcast_mut()
+    }
+
+    pub fn contains(&self, id: TweenId) -> bool {
+        self.tweens.contains_key(&id)
+    }
+
+    /// Stop a tween without calling its completion callback
+    pub fn remove(&mut self, id: TweenId) -> bool {
+        self.tweens.remove(&id).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tweens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tweens.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.tweens.clear();
+    }
+
+    /// Drop tweens that finished last update, then advance the rest
+    pub fn update(&mut self, delta_time: f32) {
+        self.tweens.retain(|_, tween| !tween.is_finished());
+        let delta_time = delta_time * self.time_scale;
+        for tween in self.tweens.values_mut() {
+            tween.update(delta_time);
+        }
+    }
+}
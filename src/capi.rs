@@ -0,0 +1,645 @@
+//! C ABI for embedding the engine in other languages and tools
+//!
+//! The host owns the loop: it creates an engine, reports input, queues draws and calls
+//! `e2d_engine_frame` once per frame, which runs `Engine::tick`. Handles are opaque and
+//! every entry point accepts null. Functions that can fail return `false` (or null) and
+//! leave a message for `e2d_last_error`. The matching header is `include/engine_2d.h`;
+//! building with `capi` generates it into `OUT_DIR` with cbindgen (see `cbindgen.toml`),
+//! and a test fails when the checked-in copy is out of date.
+//!
+//! Draws are queued and submitted in the frame's render phase, so they must be queued
+//! before `e2d_engine_frame`; a headless engine discards them.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::time::Duration;
+
+use glam::Vec2;
+
+use crate::ecs::Stage;
+#[cfg(feature = "opengl")]
+use crate::engine::RenderContext;
+use crate::engine::{Engine, EngineConfig, Game, TickResult, UpdateContext};
+use crate::input::manager::InputManager;
+use crate::input::types::{
+    ActionCategory, ActionMetadata, GameAction, InputBinding, InputType, PhysicalInput,
+};
+#[cfg(feature = "opengl")]
+use crate::render::sprite::Sprite;
+#[cfg(feature = "opengl")]
+use crate::render::texture::TextureId;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = CString::new(message.into().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Read a C string argument, recording an error if it is null or not UTF-8
+///
+/// # Safety
+/// `ptr` must be null or point to a NUL-terminated string that outlives `'a`.
+unsafe fn c_str<'a>(ptr: *const c_char, what: &str) -> Option<&'a str> {
+    if ptr.is_null() {
+        set_last_error(format!("{} is null", what));
+        return None;
+    }
+    // SAFETY: non-null and NUL-terminated per the caller's contract
+    match unsafe { CStr::from_ptr(ptr) }.to_str() {
+        Ok(text) => Some(text),
+        Err(_) => {
+            set_last_error(format!("{} is not valid UTF-8", what));
+            None
+        }
+    }
+}
+
+fn engine_or_error<T>(engine: Option<T>) -> Option<T> {
+    if engine.is_none() {
+        set_last_error("engine is null");
+    }
+    engine
+}
+
+/// Window settings for `e2d_engine_create`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct E2dConfig {
+    pub width: u32,
+    pub height: u32,
+    /// Frame rate cap; 0 for uncapped
+    pub target_fps: u32,
+    pub vsync: bool,
+    pub fullscreen: bool,
+}
+
+impl From<&EngineConfig> for E2dConfig {
+    fn from(config: &EngineConfig) -> Self {
+        Self {
+            width: config.window_width,
+            height: config.window_height,
+            target_fps: config.target_fps.unwrap_or(0),
+            vsync: config.vsync,
+            fullscreen: config.fullscreen,
+        }
+    }
+}
+
+/// Straight (not premultiplied) RGBA, each 0 to 1
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct E2dColor {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+/// What `e2d_engine_frame` did, mirroring `TickResult`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum E2dFrameResult {
+    Rendered = 0,
+    /// No frame was presented (idle mode or headless)
+    Skipped = 1,
+    /// The engine was asked to close; stop calling `e2d_engine_frame`
+    Exit = 2,
+}
+
+impl From<TickResult> for E2dFrameResult {
+    fn from(result: TickResult) -> Self {
+        match result {
+            TickResult::Rendered => E2dFrameResult::Rendered,
+            TickResult::Skipped => E2dFrameResult::Skipped,
+            TickResult::Exit => E2dFrameResult::Exit,
+        }
+    }
+}
+
+/// A draw queued by the host for the next frame
+#[derive(Debug, Clone, PartialEq)]
+enum DrawCommand {
+    Sprite {
+        texture: u32,
+        position: Vec2,
+        size: Vec2,
+        color: E2dColor,
+    },
+    Text {
+        text: String,
+        position: Vec2,
+        font: String,
+        color: E2dColor,
+    },
+}
+
+/// World resource holding the frame's queued draws while it ticks
+#[cfg(feature = "opengl")]
+#[derive(Debug, Default)]
+struct DrawList(Vec<DrawCommand>);
+
+/// The game the C host drives: it keeps input mapped to the view and submits queued draws
+struct HostGame;
+
+impl Game for HostGame {
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        #[cfg(feature = "opengl")]
+        if let Some(view) = ctx.view
+            && let Some(input) = ctx.world.resource_mut::<InputManager>()
+        {
+            input.set_view_mapping(view);
+        }
+        #[cfg(not(feature = "opengl"))]
+        let _ = ctx;
+    }
+
+    #[cfg(feature = "opengl")]
+    fn render(&mut self, ctx: &mut RenderContext<'_>) {
+        let Some(draws) = ctx.world.resource::<DrawList>() else {
+            return;
+        };
+        for draw in &draws.0 {
+            let result = match draw {
+                DrawCommand::Sprite {
+                    texture,
+                    position,
+                    size,
+                    color,
+                } => ctx.sprite_renderer.render_sprite(
+                    &Sprite::new(TextureId(*texture), *position, *size)
+                        .with_tint((color.r, color.g, color.b))
+                        .with_alpha(color.a),
+                ),
+                DrawCommand::Text {
+                    text,
+                    position,
+                    font,
+                    color,
+                } => ctx.text_renderer.draw_text_with_alpha(
+                    text, position.x, position.y, font, color.r, color.g, color.b, color.a,
+                ),
+            };
+            if let Err(e) = result {
+                eprintln!("C API draw failed: {}", e);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "C API Host"
+    }
+}
+
+/// Opaque engine handle owned by the C host
+pub struct E2dEngine {
+    engine: Engine,
+    draws: Vec<DrawCommand>,
+}
+
+impl E2dEngine {
+    fn new(mut engine: Engine) -> Box<Self> {
+        engine.world_mut().insert_resource(InputManager::new());
+        engine
+            .scheduler_mut()
+            .add_system(Stage::Input, "capi_input", |world, delta_time| {
+                if let Some(input) = world.resource_mut::<InputManager>() {
+                    input.update(delta_time);
+                }
+            });
+        Box::new(Self {
+            engine,
+            draws: Vec::new(),
+        })
+    }
+
+    fn input(&self) -> Option<&InputManager> {
+        self.engine.world().resource()
+    }
+
+    fn input_mut(&mut self) -> Option<&mut InputManager> {
+        self.engine.world_mut().resource_mut()
+    }
+}
+
+/// Build an engine config from the C settings and title
+///
+/// # Safety
+/// `title` must be null or a NUL-terminated string.
+unsafe fn engine_config(config: Option<&E2dConfig>, title: *const c_char) -> Option<EngineConfig> {
+    let mut engine_config = EngineConfig::default();
+    if let Some(config) = config {
+        engine_config.window_width = config.width;
+        engine_config.window_height = config.height;
+        engine_config.target_fps = (config.target_fps > 0).then_some(config.target_fps);
+        engine_config.vsync = config.vsync;
+        engine_config.fullscreen = config.fullscreen;
+    }
+    if !title.is_null() {
+        // SAFETY: forwarded from the caller's contract
+        engine_config.window_title = unsafe { c_str(title, "title") }?.to_string();
+    }
+    Some(engine_config)
+}
+
+/// The default window settings
+#[unsafe(no_mangle)]
+pub extern "C" fn e2d_config_default() -> E2dConfig {
+    E2dConfig::from(&EngineConfig::default())
+}
+
+/// The last error on this thread, or null; valid until the next failing call
+#[unsafe(no_mangle)]
+pub extern "C" fn e2d_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Create an engine with a window (headless without the `opengl` feature); null on failure
+///
+/// # Safety
+/// `title` must be null or a NUL-terminated string. A null `config` uses the defaults.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_engine_create(
+    config: Option<&E2dConfig>,
+    title: *const c_char,
+) -> *mut E2dEngine {
+    // SAFETY: forwarded from the caller's contract
+    let Some(config) = (unsafe { engine_config(config, title) }) else {
+        return std::ptr::null_mut();
+    };
+    match Engine::new_with_config_and_game(config, Box::new(HostGame)) {
+        Ok(engine) => Box::into_raw(E2dEngine::new(engine)),
+        Err(e) => {
+            set_last_error(format!("Failed to create engine: {}", e));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Create an engine with no window or GPU; null on failure
+///
+/// # Safety
+/// `title` must be null or a NUL-terminated string. A null `config` uses the defaults.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_engine_create_headless(
+    config: Option<&E2dConfig>,
+    title: *const c_char,
+) -> *mut E2dEngine {
+    // SAFETY: forwarded from the caller's contract
+    let Some(config) = (unsafe { engine_config(config, title) }) else {
+        return std::ptr::null_mut();
+    };
+    match Engine::new_headless(config, Box::new(HostGame)) {
+        Ok(engine) => Box::into_raw(E2dEngine::new(engine)),
+        Err(e) => {
+            set_last_error(format!("Failed to create engine: {}", e));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Shut the engine down and free it
+#[unsafe(no_mangle)]
+pub extern "C" fn e2d_engine_destroy(engine: Option<Box<E2dEngine>>) {
+    if let Some(mut engine) = engine {
+        engine.engine.quit();
+    }
+}
+
+/// Run one frame, submitting the queued draws; `delta_time` in seconds, or 0 to measure it
+#[unsafe(no_mangle)]
+pub extern "C" fn e2d_engine_frame(
+    engine: Option<&mut E2dEngine>,
+    delta_time: f32,
+) -> E2dFrameResult {
+    let Some(engine) = engine_or_error(engine) else {
+        return E2dFrameResult::Exit;
+    };
+    let dt_override = (delta_time > 0.0).then(|| Duration::from_secs_f32(delta_time));
+    #[cfg(feature = "opengl")]
+    {
+        let draws = DrawList(std::mem::take(&mut engine.draws));
+        engine.engine.world_mut().insert_resource(draws);
+    }
+    let result = engine.engine.tick(dt_override);
+    #[cfg(feature = "opengl")]
+    engine.engine.world_mut().remove_resource::<DrawList>();
+    // Headless, nothing submits the draws
+    engine.draws.clear();
+    result.into()
+}
+
+/// Ask the engine to stop; the next frame returns `E2D_FRAME_RESULT_EXIT`
+#[unsafe(no_mangle)]
+pub extern "C" fn e2d_engine_quit(engine: Option<&mut E2dEngine>) {
+    if let Some(engine) = engine {
+        engine.engine.quit();
+    }
+}
+
+/// Seconds of frame time since the engine started
+#[unsafe(no_mangle)]
+pub extern "C" fn e2d_engine_elapsed_time(engine: Option<&E2dEngine>) -> f32 {
+    engine.map_or(0.0, |engine| engine.engine.elapsed_time())
+}
+
+/// Load a texture for `e2d_draw_sprite`; needs a windowed engine
+///
+/// # Safety
+/// `path` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_texture_load(
+    engine: Option<&mut E2dEngine>,
+    path: *const c_char,
+    out_texture: Option<&mut u32>,
+) -> bool {
+    let Some(engine) = engine_or_error(engine) else {
+        return false;
+    };
+    // SAFETY: forwarded from the caller's contract
+    let Some(path) = (unsafe { c_str(path, "path") }) else {
+        return false;
+    };
+    #[cfg(feature = "opengl")]
+    if let Some(sprite_renderer) = engine.engine.try_get_sprite_renderer() {
+        return match sprite_renderer.texture_manager().load_texture(path) {
+            Ok(texture) => {
+                if let Some(out_texture) = out_texture {
+                    *out_texture = texture.0;
+                }
+                true
+            }
+            Err(e) => {
+                set_last_error(e);
+                false
+            }
+        };
+    }
+    let _ = (engine, out_texture);
+    set_last_error(format!("Can't load '{}': the engine has no renderer", path));
+    false
+}
+
+/// Load a font under `name` for `e2d_draw_text`; needs a windowed engine
+///
+/// # Safety
+/// `name` and `path` must be null or NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_font_load(
+    engine: Option<&mut E2dEngine>,
+    name: *const c_char,
+    path: *const c_char,
+    size: u32,
+) -> bool {
+    let Some(engine) = engine_or_error(engine) else {
+        return false;
+    };
+    // SAFETY: forwarded from the caller's contract
+    let (Some(name), Some(path)) = (unsafe { c_str(name, "name") }, unsafe {
+        c_str(path, "path")
+    }) else {
+        return false;
+    };
+    #[cfg(feature = "opengl")]
+    if let Some(text_renderer) = engine.engine.try_text_renderer_mut() {
+        return match text_renderer.load_font(name, path, size) {
+            Ok(()) => true,
+            Err(e) => {
+                set_last_error(e);
+                false
+            }
+        };
+    }
+    let _ = (engine, name, size);
+    set_last_error(format!("Can't load '{}': the engine has no renderer", path));
+    false
+}
+
+/// Queue a textured sprite for the next frame, in world units
+#[unsafe(no_mangle)]
+pub extern "C" fn e2d_draw_sprite(
+    engine: Option<&mut E2dEngine>,
+    texture: u32,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    color: E2dColor,
+) {
+    if let Some(engine) = engine {
+        engine.draws.push(DrawCommand::Sprite {
+            texture,
+            position: Vec2::new(x, y),
+            size: Vec2::new(width, height),
+            color,
+        });
+    }
+}
+
+/// Queue text in a loaded font for the next frame
+///
+/// # Safety
+/// `text` and `font` must be null or NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_draw_text(
+    engine: Option<&mut E2dEngine>,
+    text: *const c_char,
+    x: f32,
+    y: f32,
+    font: *const c_char,
+    color: E2dColor,
+) -> bool {
+    let Some(engine) = engine_or_error(engine) else {
+        return false;
+    };
+    // SAFETY: forwarded from the caller's contract
+    let (Some(text), Some(font)) = (unsafe { c_str(text, "text") }, unsafe {
+        c_str(font, "font")
+    }) else {
+        return false;
+    };
+    engine.draws.push(DrawCommand::Text {
+        text: text.to_string(),
+        position: Vec2::new(x, y),
+        font: font.to_string(),
+        color,
+    });
+    true
+}
+
+/// Register an action bound to one input, written like `Keyboard:Space` or
+/// `GamepadAxis:LeftStickX`; axes make the action analog
+///
+/// # Safety
+/// `action_id` and `input` must be null or NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_input_bind_action(
+    engine: Option<&mut E2dEngine>,
+    action_id: *const c_char,
+    input: *const c_char,
+) -> bool {
+    let Some(engine) = engine_or_error(engine) else {
+        return false;
+    };
+    // SAFETY: forwarded from the caller's contract
+    let (Some(action_id), Some(input)) = (unsafe { c_str(action_id, "action_id") }, unsafe {
+        c_str(input, "input")
+    }) else {
+        return false;
+    };
+    let input = match PhysicalInput::parse(input) {
+        Ok(input) => input,
+        Err(e) => {
+            set_last_error(e);
+            return false;
+        }
+    };
+    let input_type = match input {
+        PhysicalInput::MouseAxis(_) | PhysicalInput::GamepadAxis(_) => InputType::Analog,
+        _ => InputType::Digital,
+    };
+    let Some(manager) = engine.input_mut() else {
+        set_last_error("engine has no input manager");
+        return false;
+    };
+    manager.register_action(GameAction {
+        id: action_id.to_string(),
+        display_name: action_id.to_string(),
+        category: ActionCategory::Custom("Host".to_string()),
+        input_type,
+        default_bindings: vec![InputBinding::Single(input)],
+        metadata: ActionMetadata::default(),
+    });
+    true
+}
+
+/// Report a button or key going down or up; applied on the next frame
+///
+/// # Safety
+/// `input` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_input_set(
+    engine: Option<&mut E2dEngine>,
+    input: *const c_char,
+    pressed: bool,
+) -> bool {
+    // SAFETY: forwarded from the caller's contract
+    unsafe {
+        set_input(engine, input, |manager, input| {
+            manager.set_raw_input(input, pressed)
+        })
+    }
+}
+
+/// Report an analog value (axis position or delta); applied on the next frame
+///
+/// # Safety
+/// `input` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_input_set_value(
+    engine: Option<&mut E2dEngine>,
+    input: *const c_char,
+    value: f32,
+) -> bool {
+    // SAFETY: forwarded from the caller's contract
+    unsafe {
+        set_input(engine, input, |manager, input| {
+            manager.set_raw_value(input, value)
+        })
+    }
+}
+
+/// # Safety
+/// `input` must be null or a NUL-terminated string.
+unsafe fn set_input(
+    engine: Option<&mut E2dEngine>,
+    input: *const c_char,
+    apply: impl FnOnce(&mut InputManager, PhysicalInput),
+) -> bool {
+    let Some(engine) = engine_or_error(engine) else {
+        return false;
+    };
+    // SAFETY: forwarded from the caller's contract
+    let Some(input) = (unsafe { c_str(input, "input") }) else {
+        return false;
+    };
+    match PhysicalInput::parse(input) {
+        Ok(input) => {
+            if let Some(manager) = engine.input_mut() {
+                apply(manager, input);
+            }
+            true
+        }
+        Err(e) => {
+            set_last_error(e);
+            false
+        }
+    }
+}
+
+/// Read an action from the last frame's input
+///
+/// # Safety
+/// `action_id` must be null or a NUL-terminated string.
+unsafe fn query_action<T: Default>(
+    engine: Option<&E2dEngine>,
+    action_id: *const c_char,
+    query: impl FnOnce(&InputManager, &str) -> T,
+) -> T {
+    let Some(engine) = engine_or_error(engine) else {
+        return T::default();
+    };
+    // SAFETY: forwarded from the caller's contract
+    let Some(action_id) = (unsafe { c_str(action_id, "action_id") }) else {
+        return T::default();
+    };
+    engine
+        .input()
+        .map(|manager| query(manager, action_id))
+        .unwrap_or_default()
+}
+
+/// Check if an action went down on the last frame
+///
+/// # Safety
+/// `action_id` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_action_pressed(
+    engine: Option<&E2dEngine>,
+    action_id: *const c_char,
+) -> bool {
+    // SAFETY: forwarded from the caller's contract
+    unsafe { query_action(engine, action_id, InputManager::is_action_pressed) }
+}
+
+/// Check if an action is held down
+///
+/// # Safety
+/// `action_id` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_action_held(
+    engine: Option<&E2dEngine>,
+    action_id: *const c_char,
+) -> bool {
+    // SAFETY: forwarded from the caller's contract
+    unsafe { query_action(engine, action_id, InputManager::is_action_held) }
+}
+
+/// An analog action's value, or 1/0 for a digital one
+///
+/// # Safety
+/// `action_id` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn e2d_action_value(
+    engine: Option<&E2dEngine>,
+    action_id: *const c_char,
+) -> f32 {
+    // SAFETY: forwarded from the caller's contract
+    unsafe { query_action(engine, action_id, InputManager::get_action_value) }
+}
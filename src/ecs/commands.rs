@@ -0,0 +1,104 @@
+use super::component::Component;
+use super::entity::Entity;
+use super::world::{Bundle, World};
+use std::fmt;
+
+type Command = Box<dyn FnOnce(&mut World) + Send>;
+
+/// World changes recorded while the world is borrowed, applied later in order
+///
+/// ```
+/// # use engine_2d::ecs::{Commands, Transform, World};
+/// # struct Health(f32);
+/// # struct Explosion;
+/// # let mut world = World::new();
+/// # world.spawn((Health(0.0),));
+/// let mut commands = Commands::new();
+/// for (entity, health) in world.query::<&Health>() {
+///     if health.0 <= 0.0 {
+///         commands.despawn(entity);
+///         commands.spawn((Transform::default(), Explosion));
+///     }
+/// }
+/// commands.apply(&mut world);
+/// # assert_eq!(world.query::<&Explosion>().count(), 1);
+/// ```
+///
+/// Systems run by a `Scheduler` can hand their buffer to `World::defer` instead; deferred
+/// commands are applied at the end of each stage.
+#[derive(Default)]
+pub struct Commands {
+    commands: Vec<Command>,
+}
+
+impl fmt::Debug for Commands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Commands")
+            .field("len", &self.commands.len())
+            .finish()
+    }
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn an entity with a bundle of components
+    pub fn spawn(&mut self, bundle: impl Bundle + Send + 'static) {
+        self.add(move |world| {
+            world.spawn(bundle);
+        });
+    }
+
+    pub fn despawn(&mut self, entity: Entity) {
+        self.add(move |world| {
+            world.despawn(entity);
+        });
+    }
+
+    /// Attach a component; skipped if the entity is gone by the time it applies
+    pub fn insert<T: Component + Send>(&mut self, entity: Entity, component: T) {
+        self.add(move |world| {
+            let _ = world.insert(entity, component);
+        });
+    }
+
+    pub fn remove<T: Component>(&mut self, entity: Entity) {
+        self.add(move |world| {
+            world.remove::<T>(entity);
+        });
+    }
+
+    pub fn insert_resource<R: Component + Send>(&mut self, resource: R) {
+        self.add(move |world| {
+            world.insert_resource(resource);
+        });
+    }
+
+    /// Record any other change
+    pub fn add(&mut self, command: impl FnOnce(&mut World) + Send + 'static) {
+        self.commands.push(Box::new(command));
+    }
+
+    /// Move every command from `other` to the end of this buffer
+    pub fn append(&mut self, other: &mut Commands) {
+        self.commands.append(&mut other.commands);
+    }
+
+    /// Number of recorded commands
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Apply every command in the order it was recorded
+    pub fn apply(self, world: &mut World) {
+        for command in self.commands {
+            command(world);
+        }
+    }
+}
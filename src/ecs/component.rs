@@ -1 +1,245 @@
-// Component traits implementation will go here
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use std::any::{Any, type_name};
+
+/// Data that can be attached to entities
+///
+/// Implemented for every `'static` type, so plain structs work as components.
+pub trait Component: 'static {}
+
+impl<T: 'static> Component for T {}
+
+/// Position, rotation and scale of an entity in world units
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Transform {
+    pub position: Vec2,
+    /// Rotation in radians
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            rotation: 0.0,
+            scale: Vec2::ONE,
+        }
+    }
+}
+
+impl Transform {
+    pub fn from_position(position: Vec2) -> Self {
+        Self {
+            position,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: Vec2) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+/// Linear velocity in world units per second
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Velocity(pub Vec2);
+
+/// World change ticks at which a component was added and last changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ComponentTicks {
+    pub added: u32,
+    pub changed: u32,
+}
+
+impl ComponentTicks {
+    /// Added after `last_run`, the tick a system last ran at
+    pub fn is_added(&self, last_run: u32) -> bool {
+        self.added > last_run
+    }
+
+    /// Added or mutably accessed after `last_run`
+    pub fn is_changed(&self, last_run: u32) -> bool {
+        self.changed > last_run
+    }
+}
+
+/// Components of one type, indexed by entity slot
+#[derive(Debug)]
+pub struct ComponentStorage<T> {
+    slots: Vec<Option<T>>,
+    ticks: Vec<ComponentTicks>,
+    len: usize,
+}
+
+impl<T> Default for ComponentStorage<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            ticks: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T: Component> ComponentStorage<T> {
+    /// Store a component at change tick `tick`, returning the one it replaced
+    pub fn insert(&mut self, index: u32, component: T, tick: u32) -> Option<T> {
+        let index = index as usize;
+        if index >= self.slots.len() {
+            self.slots.resize_with(index + 1, || None);
+            self.ticks.resize(index + 1, ComponentTicks::default());
+        }
+        let previous = self.slots[index].replace(component);
+        if previous.is_none() {
+            self.len += 1;
+            self.ticks[index].added = tick;
+        }
+        self.ticks[index].changed = tick;
+        previous
+    }
+
+    pub fn remove(&mut self, index: u32) -> Option<T> {
+        let removed = self.slots.get_mut(index as usize)?.take();
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    pub fn get(&self, index: u32) -> Option<&T> {
+        self.slots.get(index as usize)?.as_ref()
+    }
+
+    /// Get a component mutably, marking it changed at `tick`
+    pub fn get_mut(&mut self, index: u32, tick: u32) -> Option<&mut T> {
+        let component = self.slots.get_mut(index as usize)?.as_mut()?;
+        self.ticks[index as usize].changed = tick;
+        Some(component)
+    }
+
+    /// When the component in a slot was added and last changed
+    pub fn ticks(&self, index: u32) -> Option<ComponentTicks> {
+        self.get(index)?;
+        self.ticks.get(index as usize).copied()
+    }
+
+    /// Number of stored components
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Bytes allocated for slots and ticks, not counting heap data the components own
+    pub fn memory_bytes(&self) -> usize {
+        self.slots.capacity() * std::mem::size_of::<Option<T>>()
+            + self.ticks.capacity() * std::mem::size_of::<ComponentTicks>()
+    }
+
+    /// Raw slots for queries that hand out several borrows at once
+    pub(crate) fn column(&mut self) -> Column<T> {
+        Column {
+            slots: self.slots.as_mut_ptr(),
+            ticks: self.ticks.as_mut_ptr(),
+            len: self.slots.len(),
+        }
+    }
+}
+
+/// Base pointers into a storage, held by query state while the world is borrowed
+#[derive(Debug)]
+pub struct Column<T> {
+    pub(crate) slots: *mut Option<T>,
+    pub(crate) ticks: *mut ComponentTicks,
+    pub(crate) len: usize,
+}
+
+impl<T> Clone for Column<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Column<T> {}
+
+// Safety: a column only hands out `&T`/`&mut T`, and the scheduler only shares columns
+// across threads when no two systems access the same type with one of them writing
+unsafe impl<T: Send + Sync> Send for Column<T> {}
+unsafe impl<T: Send + Sync> Sync for Column<T> {}
+
+impl<T> Column<T> {
+    /// A column with no slots, for component types that were never inserted
+    pub(crate) fn empty() -> Self {
+        Self {
+            slots: std::ptr::null_mut(),
+            ticks: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    /// Ticks of an occupied slot
+    ///
+    /// # Safety
+    /// The column's storage must still be alive and unmoved.
+    pub(crate) unsafe fn ticks(&self, index: u32) -> Option<ComponentTicks> {
+        let index = index as usize;
+        if index >= self.len {
+            return None;
+        }
+        unsafe {
+            (*self.slots.add(index))
+                .as_ref()
+                .map(|_| *self.ticks.add(index))
+        }
+    }
+}
+
+/// Type-erased storage so the world can hold one of each component type
+pub(crate) trait AnyStorage: Any {
+    fn remove_index(&mut self, index: u32);
+    fn contains_index(&self, index: u32) -> bool;
+    fn component_name(&self) -> &'static str;
+    fn component_count(&self) -> usize;
+    fn memory_bytes(&self) -> usize;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Component> AnyStorage for ComponentStorage<T> {
+    fn remove_index(&mut self, index: u32) {
+        self.remove(index);
+    }
+
+    fn contains_index(&self, index: u32) -> bool {
+        self.get(index).is_some()
+    }
+
+    fn component_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn component_count(&self) -> usize {
+        self.len()
+    }
+
+    fn memory_bytes(&self) -> usize {
+        ComponentStorage::memory_bytes(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -1 +1,122 @@
-// Entity management implementation will go here
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A handle to an entity in a `World`
+///
+/// Indices are reused after despawn; the generation tells a stale handle apart from the
+/// entity that took its slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    /// Slot index, stable for the entity's lifetime
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
+
+/// Hands out entity handles and recycles the slots of despawned ones
+#[derive(Debug, Clone, Default)]
+pub struct EntityAllocator {
+    /// Current generation of each slot
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl EntityAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocate a handle, reusing a free slot if there is one
+    pub fn allocate(&mut self) -> Entity {
+        self.len += 1;
+        if let Some(index) = self.free.pop() {
+            self.alive[index as usize] = true;
+            return Entity {
+                index,
+                generation: self.generations[index as usize],
+            };
+        }
+
+        let index = self.generations.len() as u32;
+        self.generations.push(0);
+        self.alive.push(true);
+        Entity {
+            index,
+            generation: 0,
+        }
+    }
+
+    /// Free an entity's slot; returns false if the handle was already stale
+    pub fn free(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        let index = entity.index as usize;
+        self.alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free.push(entity.index);
+        self.len -= 1;
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        let index = entity.index as usize;
+        self.alive.get(index).copied().unwrap_or(false)
+            && self.generations[index] == entity.generation
+    }
+
+    /// The live entity in a slot, if any
+    pub fn get(&self, index: u32) -> Option<Entity> {
+        let slot = index as usize;
+        if !self.alive.get(slot).copied().unwrap_or(false) {
+            return None;
+        }
+        Some(Entity {
+            index,
+            generation: self.generations[slot],
+        })
+    }
+
+    /// Number of live entities
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of slots ever allocated, live or free
+    pub fn capacity(&self) -> usize {
+        self.generations.len()
+    }
+
+    /// Iterate over live entities in slot order
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        (0..self.capacity() as u32).filter_map(|index| self.get(index))
+    }
+
+    /// Free every entity, invalidating all handles
+    pub fn clear(&mut self) {
+        for entity in self.iter().collect::<Vec<_>>() {
+            self.free(entity);
+        }
+    }
+}
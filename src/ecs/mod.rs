@@ -1,4 +1,23 @@
+pub mod commands;
 pub mod component;
 pub mod entity;
+pub mod names;
+pub mod query;
+pub mod serialize;
+#[cfg(feature = "opengl")]
+pub mod sprite;
+pub mod stats;
 pub mod system;
 pub mod world;
+
+pub use commands::Commands;
+pub use component::{Component, ComponentStorage, ComponentTicks, Transform, Velocity};
+pub use entity::Entity;
+pub use names::NameRegistry;
+pub use query::{Added, Changed, Or, Query, QueryFilter, QueryIter, Res, With, Without};
+pub use serialize::{ComponentSet, EntitySnapshot, WorldSnapshot};
+#[cfg(feature = "opengl")]
+pub use sprite::{SpriteComponent, collect_sprites, submit_sprites};
+pub use stats::{ArchetypeStats, ComponentStats, SystemStats, WorldStats};
+pub use system::{Scheduler, Stage, SystemConfig};
+pub use world::{Bundle, World};
@@ -0,0 +1,121 @@
+use super::entity::Entity;
+use std::collections::{BTreeSet, HashMap};
+
+/// Unique entity names and shared tags, indexed both ways for fast lookup
+#[derive(Debug, Clone, Default)]
+pub struct NameRegistry {
+    by_name: HashMap<String, Entity>,
+    names: HashMap<Entity, String>,
+    /// Entities per tag, kept sorted so iteration order is stable
+    by_tag: HashMap<String, BTreeSet<Entity>>,
+    tags: HashMap<Entity, Vec<String>>,
+}
+
+impl NameRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Name an entity, replacing its previous name; fails if another entity has the name
+    pub fn set_name(&mut self, entity: Entity, name: &str) -> Result<(), String> {
+        match self.by_name.get(name) {
+            Some(&owner) if owner == entity => return Ok(()),
+            Some(&owner) => {
+                return Err(format!(
+                    "Name '{}' is already used by entity {}",
+                    name, owner
+                ));
+            }
+            None => {}
+        }
+        self.remove_name(entity);
+        self.by_name.insert(name.to_string(), entity);
+        self.names.insert(entity, name.to_string());
+        Ok(())
+    }
+
+    /// Remove an entity's name, returning it
+    pub fn remove_name(&mut self, entity: Entity) -> Option<String> {
+        let name = self.names.remove(&entity)?;
+        self.by_name.remove(&name);
+        Some(name)
+    }
+
+    pub fn name(&self, entity: Entity) -> Option<&str> {
+        self.names.get(&entity).map(String::as_str)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<Entity> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Tag an entity; returns false if it already had the tag
+    pub fn add_tag(&mut self, entity: Entity, tag: &str) -> bool {
+        let entities = self.by_tag.entry(tag.to_string()).or_default();
+        if !entities.insert(entity) {
+            return false;
+        }
+        self.tags.entry(entity).or_default().push(tag.to_string());
+        true
+    }
+
+    /// Untag an entity; returns false if it didn't have the tag
+    pub fn remove_tag(&mut self, entity: Entity, tag: &str) -> bool {
+        let Some(entities) = self.by_tag.get_mut(tag) else {
+            return false;
+        };
+        if !entities.remove(&entity) {
+            return false;
+        }
+        if entities.is_empty() {
+            self.by_tag.remove(tag);
+        }
+        if let Some(tags) = self.tags.get_mut(&entity) {
+            tags.retain(|t| t != tag);
+            if tags.is_empty() {
+                self.tags.remove(&entity);
+            }
+        }
+        true
+    }
+
+    pub fn has_tag(&self, entity: Entity, tag: &str) -> bool {
+        self.by_tag
+            .get(tag)
+            .is_some_and(|entities| entities.contains(&entity))
+    }
+
+    /// Entities with a tag, in entity order
+    pub fn tagged(&self, tag: &str) -> impl Iterator<Item = Entity> + '_ {
+        self.by_tag.get(tag).into_iter().flatten().copied()
+    }
+
+    /// An entity's tags, in the order they were added
+    pub fn tags(&self, entity: Entity) -> impl Iterator<Item = &str> + '_ {
+        self.tags
+            .get(&entity)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Forget an entity's name and tags
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.remove_name(entity);
+        for tag in self.tags.remove(&entity).unwrap_or_default() {
+            if let Some(entities) = self.by_tag.get_mut(&tag) {
+                entities.remove(&entity);
+                if entities.is_empty() {
+                    self.by_tag.remove(&tag);
+                }
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.by_name.clear();
+        self.names.clear();
+        self.by_tag.clear();
+        self.tags.clear();
+    }
+}
@@ -0,0 +1,499 @@
+use super::component::{Column, Component};
+use super::entity::{Entity, EntityAllocator};
+use super::world::World;
+use std::any::{TypeId, type_name};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Component access requested by a query: `&T`, `&mut T`, or a tuple of them
+///
+/// ```
+/// # use engine_2d::ecs::{Transform, Velocity, World};
+/// # let mut world = World::new();
+/// # let delta_time = 0.016;
+/// for (entity, (transform, velocity)) in world.query::<(&mut Transform, &Velocity)>() {
+///     transform.position += velocity.0 * delta_time;
+/// }
+/// ```
+pub trait Query {
+    type Item<'w>;
+    /// Raw pointers to the storages being read, captured when the query starts
+    type State: Copy;
+
+    /// Record each component type accessed and whether it is written
+    fn access(access: &mut Vec<(TypeId, &'static str, bool)>);
+
+    /// Capture the storages, or None when a component type has never been inserted
+    fn state(world: &mut World) -> Option<Self::State>;
+
+    /// Check whether the entity in a slot has every component, without fetching
+    ///
+    /// # Safety
+    /// `state` must point at live storages.
+    unsafe fn contains(state: Self::State, index: u32) -> bool;
+
+    /// Fetch the item for one entity slot, once `contains` has passed
+    ///
+    /// # Safety
+    /// `state` must point at live storages that nothing else is borrowing, and each slot
+    /// may only be fetched once while its items are alive.
+    unsafe fn fetch<'w>(state: Self::State, index: u32) -> Option<Self::Item<'w>>;
+}
+
+impl<T: Component> Query for &T {
+    type Item<'w> = &'w T;
+    type State = Column<T>;
+
+    fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+        access.push((TypeId::of::<T>(), type_name::<T>(), false));
+    }
+
+    fn state(world: &mut World) -> Option<Self::State> {
+        Some(world.storage_mut::<T>()?.column())
+    }
+
+    unsafe fn contains(state: Self::State, index: u32) -> bool {
+        unsafe { state.ticks(index) }.is_some()
+    }
+
+    unsafe fn fetch<'w>(state: Self::State, index: u32) -> Option<Self::Item<'w>> {
+        if index as usize >= state.len {
+            return None;
+        }
+        unsafe { (*state.slots.add(index as usize)).as_ref() }
+    }
+}
+
+/// Fetching `&mut T` marks the component changed
+impl<T: Component> Query for &mut T {
+    type Item<'w> = &'w mut T;
+    /// The column and the world's current change tick
+    type State = (Column<T>, u32);
+
+    fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+        access.push((TypeId::of::<T>(), type_name::<T>(), true));
+    }
+
+    fn state(world: &mut World) -> Option<Self::State> {
+        let tick = world.change_tick();
+        Some((world.storage_mut::<T>()?.column(), tick))
+    }
+
+    unsafe fn contains(state: Self::State, index: u32) -> bool {
+        unsafe { state.0.ticks(index) }.is_some()
+    }
+
+    unsafe fn fetch<'w>(state: Self::State, index: u32) -> Option<Self::Item<'w>> {
+        let (column, tick) = state;
+        let index = index as usize;
+        if index >= column.len {
+            return None;
+        }
+        unsafe {
+            let component = (*column.slots.add(index)).as_mut()?;
+            (*column.ticks.add(index)).changed = tick;
+            Some(component)
+        }
+    }
+}
+
+/// Read-only access to a world resource from a query, the same for every entity
+///
+/// A query containing `Res<R>` matches nothing while the world has no `R`.
+///
+/// ```
+/// # use engine_2d::ecs::{Res, Scheduler, Stage, Velocity};
+/// # use glam::Vec2;
+/// # struct Gravity(Vec2);
+/// # let mut scheduler = Scheduler::new();
+/// scheduler.add_query_system::<(&mut Velocity, Res<Gravity>), _>(
+///     Stage::Physics,
+///     "gravity",
+///     |_, (velocity, gravity), dt| velocity.0 += gravity.0 * dt,
+/// );
+/// ```
+pub struct Res<'w, R>(&'w R);
+
+impl<R> Deref for Res<'_, R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        self.0
+    }
+}
+
+/// Pointer to a boxed resource, held by query state while the world is borrowed
+pub struct ResourcePtr<R>(*const R);
+
+impl<R> Clone for ResourcePtr<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for ResourcePtr<R> {}
+
+// Safety: only shared references are handed out, and the scheduler never runs a query
+// system alongside an exclusive one that could write the resource
+unsafe impl<R: Sync> Send for ResourcePtr<R> {}
+
+impl<R: Component> Query for Res<'_, R> {
+    type Item<'w> = Res<'w, R>;
+    type State = ResourcePtr<R>;
+
+    fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+        // Keyed apart from `R` used as a component
+        access.push((TypeId::of::<ResourcePtr<R>>(), type_name::<R>(), false));
+    }
+
+    fn state(world: &mut World) -> Option<Self::State> {
+        Some(ResourcePtr(world.resource::<R>()?))
+    }
+
+    unsafe fn contains(_state: Self::State, _index: u32) -> bool {
+        true
+    }
+
+    unsafe fn fetch<'w>(state: Self::State, _index: u32) -> Option<Self::Item<'w>> {
+        Some(Res(unsafe { &*state.0 }))
+    }
+}
+
+macro_rules! impl_query_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Query),+> Query for ($($name,)+) {
+            type Item<'w> = ($($name::Item<'w>,)+);
+            type State = ($($name::State,)+);
+
+            fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+                $($name::access(access);)+
+            }
+
+            fn state(world: &mut World) -> Option<Self::State> {
+                Some(($($name::state(world)?,)+))
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn contains(state: Self::State, index: u32) -> bool {
+                let ($($name,)+) = state;
+                unsafe { $($name::contains($name, index))&&+ }
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn fetch<'w>(state: Self::State, index: u32) -> Option<Self::Item<'w>> {
+                let ($($name,)+) = state;
+                unsafe { Some(($($name::fetch($name, index)?,)+)) }
+            }
+        }
+    };
+}
+
+impl_query_tuple!(A);
+impl_query_tuple!(A, B);
+impl_query_tuple!(A, B, C);
+impl_query_tuple!(A, B, C, D);
+impl_query_tuple!(A, B, C, D, E);
+impl_query_tuple!(A, B, C, D, E, F);
+impl_query_tuple!(A, B, C, D, E, F, G);
+impl_query_tuple!(A, B, C, D, E, F, G, H);
+
+/// Narrows a query by entity without fetching data: `With<T>`, `Without<T>`,
+/// `Added<T>`, `Changed<T>`, a tuple of filters that must all match, or `Or` of a tuple
+/// where any may match
+///
+/// ```
+/// # use engine_2d::ecs::{Transform, Velocity, With, Without, World};
+/// # struct Frozen;
+/// # let mut world = World::new();
+/// # let (spin, delta_time) = (3.0, 0.016);
+/// type Moving = (With<Velocity>, Without<Frozen>);
+/// for (_, transform) in world.query_filtered::<&mut Transform, Moving>() {
+///     transform.rotation += spin * delta_time;
+/// }
+/// ```
+pub trait QueryFilter {
+    type State: Copy;
+
+    /// Record each component type the filter reads
+    fn access(access: &mut Vec<(TypeId, &'static str, bool)>);
+
+    fn state(world: &mut World) -> Self::State;
+
+    /// Check whether the entity in a slot passes
+    ///
+    /// # Safety
+    /// `state` must point at live storages.
+    unsafe fn matches(state: Self::State, index: u32) -> bool;
+}
+
+impl QueryFilter for () {
+    type State = ();
+
+    fn access(_access: &mut Vec<(TypeId, &'static str, bool)>) {}
+
+    fn state(_world: &mut World) -> Self::State {}
+
+    unsafe fn matches(_state: Self::State, _index: u32) -> bool {
+        true
+    }
+}
+
+/// Matches entities that have a `T`
+pub struct With<T>(PhantomData<T>);
+
+/// Matches entities that don't have a `T`
+pub struct Without<T>(PhantomData<T>);
+
+/// Matches entities passing any filter in a tuple: `Or<(With<A>, Changed<B>)>`
+pub struct Or<T>(PhantomData<T>);
+
+impl<T: Component> QueryFilter for With<T> {
+    type State = Column<T>;
+
+    fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+        access.push((TypeId::of::<T>(), type_name::<T>(), false));
+    }
+
+    fn state(world: &mut World) -> Self::State {
+        world
+            .storage_mut::<T>()
+            .map_or(Column::empty(), |storage| storage.column())
+    }
+
+    unsafe fn matches(state: Self::State, index: u32) -> bool {
+        unsafe { state.ticks(index) }.is_some()
+    }
+}
+
+impl<T: Component> QueryFilter for Without<T> {
+    type State = Column<T>;
+
+    fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+        access.push((TypeId::of::<T>(), type_name::<T>(), false));
+    }
+
+    fn state(world: &mut World) -> Self::State {
+        world
+            .storage_mut::<T>()
+            .map_or(Column::empty(), |storage| storage.column())
+    }
+
+    unsafe fn matches(state: Self::State, index: u32) -> bool {
+        unsafe { state.ticks(index) }.is_none()
+    }
+}
+
+/// Matches entities whose `T` was added since the running system last ran
+///
+/// Outside the scheduler, "last ran" is the last `World::clear_trackers` call.
+pub struct Added<T>(PhantomData<T>);
+
+/// Matches entities whose `T` was added or mutably accessed since the running system
+/// last ran
+///
+/// Outside the scheduler, "last ran" is the last `World::clear_trackers` call.
+pub struct Changed<T>(PhantomData<T>);
+
+impl<T: Component> QueryFilter for Added<T> {
+    type State = (Column<T>, u32);
+
+    fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+        access.push((TypeId::of::<T>(), type_name::<T>(), false));
+    }
+
+    fn state(world: &mut World) -> Self::State {
+        let last_run = world.last_change_tick();
+        let column = world
+            .storage_mut::<T>()
+            .map_or(Column::empty(), |storage| storage.column());
+        (column, last_run)
+    }
+
+    unsafe fn matches(state: Self::State, index: u32) -> bool {
+        let (column, last_run) = state;
+        unsafe { column.ticks(index) }.is_some_and(|ticks| ticks.is_added(last_run))
+    }
+}
+
+impl<T: Component> QueryFilter for Changed<T> {
+    type State = (Column<T>, u32);
+
+    fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+        access.push((TypeId::of::<T>(), type_name::<T>(), false));
+    }
+
+    fn state(world: &mut World) -> Self::State {
+        let last_run = world.last_change_tick();
+        let column = world
+            .storage_mut::<T>()
+            .map_or(Column::empty(), |storage| storage.column());
+        (column, last_run)
+    }
+
+    unsafe fn matches(state: Self::State, index: u32) -> bool {
+        let (column, last_run) = state;
+        unsafe { column.ticks(index) }.is_some_and(|ticks| ticks.is_changed(last_run))
+    }
+}
+
+macro_rules! impl_filter_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: QueryFilter),+> QueryFilter for ($($name,)+) {
+            type State = ($($name::State,)+);
+
+            fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+                $($name::access(access);)+
+            }
+
+            fn state(world: &mut World) -> Self::State {
+                ($($name::state(world),)+)
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn matches(state: Self::State, index: u32) -> bool {
+                let ($($name,)+) = state;
+                unsafe { $($name::matches($name, index))&&+ }
+            }
+        }
+    };
+}
+
+impl_filter_tuple!(A);
+impl_filter_tuple!(A, B);
+impl_filter_tuple!(A, B, C);
+impl_filter_tuple!(A, B, C, D);
+
+macro_rules! impl_or_filter {
+    ($($name:ident),+) => {
+        impl<$($name: QueryFilter),+> QueryFilter for Or<($($name,)+)> {
+            type State = ($($name::State,)+);
+
+            fn access(access: &mut Vec<(TypeId, &'static str, bool)>) {
+                $($name::access(access);)+
+            }
+
+            fn state(world: &mut World) -> Self::State {
+                ($($name::state(world),)+)
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn matches(state: Self::State, index: u32) -> bool {
+                let ($($name,)+) = state;
+                unsafe { $($name::matches($name, index))||+ }
+            }
+        }
+    };
+}
+
+impl_or_filter!(A, B);
+impl_or_filter!(A, B, C);
+impl_or_filter!(A, B, C, D);
+
+/// Panic if a query writes a component type it also accesses elsewhere
+pub(crate) fn validate_access<Q: Query>() {
+    let mut access = Vec::new();
+    Q::access(&mut access);
+    for (i, (id, name, write)) in access.iter().enumerate() {
+        let conflict = access[i + 1..]
+            .iter()
+            .any(|(other, _, other_write)| other == id && (*write || *other_write));
+        if conflict {
+            panic!("Query accesses `{}` mutably more than once", name);
+        }
+    }
+}
+
+/// Check whether two access sets touch a component type that either of them writes
+pub(crate) fn access_conflicts(
+    a: &[(TypeId, &'static str, bool)],
+    b: &[(TypeId, &'static str, bool)],
+) -> bool {
+    a.iter().any(|(id, _, write)| {
+        b.iter()
+            .any(|(other, _, other_write)| other == id && (*write || *other_write))
+    })
+}
+
+/// Iterator over the entities matching a query, yielding each entity with its components
+pub struct QueryIter<'w, Q: Query, F: QueryFilter = ()> {
+    entities: &'w EntityAllocator,
+    state: Option<Q::State>,
+    filter: F::State,
+    next_index: u32,
+    _world: PhantomData<&'w mut World>,
+}
+
+impl<'w, Q: Query, F: QueryFilter> QueryIter<'w, Q, F> {
+    pub(crate) fn new(
+        entities: &'w EntityAllocator,
+        state: Option<Q::State>,
+        filter: F::State,
+    ) -> Self {
+        Self {
+            entities,
+            state,
+            filter,
+            next_index: 0,
+            _world: PhantomData,
+        }
+    }
+}
+
+impl<'w, Q: Query, F: QueryFilter> Iterator for QueryIter<'w, Q, F> {
+    type Item = (Entity, Q::Item<'w>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = self.state?;
+        while (self.next_index as usize) < self.entities.capacity() {
+            let index = self.next_index;
+            self.next_index += 1;
+            let Some(entity) = self.entities.get(index) else {
+                continue;
+            };
+            // Safety: the iterator holds the world mutably, the access was validated to
+            // not alias, and every slot is visited once
+            if let Some(item) = unsafe { fetch_matching::<Q, F>(state, self.filter, index) } {
+                return Some((entity, item));
+            }
+        }
+        None
+    }
+}
+
+/// Fetch a slot's item if it matches the query and filter
+///
+/// Checking before fetching keeps `&mut` access from marking entities that don't match
+/// as changed.
+///
+/// # Safety
+/// As for `Query::fetch`.
+unsafe fn fetch_matching<'w, Q: Query, F: QueryFilter>(
+    state: Q::State,
+    filter: F::State,
+    index: u32,
+) -> Option<Q::Item<'w>> {
+    unsafe {
+        if !(Q::contains(state, index) && F::matches(filter, index)) {
+            return None;
+        }
+        Q::fetch(state, index)
+    }
+}
+
+/// Call `f` for every live entity matching states captured ahead of time
+///
+/// # Safety
+/// The states must point at live storages, and nothing else may access the components
+/// `Q` writes (or write the ones it reads) until this returns.
+pub(crate) unsafe fn for_each_captured<Q: Query, F: QueryFilter>(
+    entities: &EntityAllocator,
+    state: Q::State,
+    filter: F::State,
+    mut f: impl FnMut(Entity, Q::Item<'_>),
+) {
+    for entity in entities.iter() {
+        if let Some(item) = unsafe { fetch_matching::<Q, F>(state, filter, entity.index()) } {
+            f(entity, item);
+        }
+    }
+}
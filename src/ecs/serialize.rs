@@ -0,0 +1,135 @@
+use super::entity::Entity;
+use super::world::World;
+use serde::{Deserialize, Serialize};
+
+/// The serializable components of a game, usually an enum generated by `component_set!`
+///
+/// Each variant wraps one component type; the snapshot stores an entity's components as
+/// a list of variants, so any serde format (RON, JSON, bincode) can round-trip it.
+pub trait ComponentSet: Sized {
+    /// Copy out the entity's components that belong to the set
+    fn collect(world: &World, entity: Entity) -> Vec<Self>;
+
+    /// Attach the wrapped component to an entity
+    fn insert(self, world: &mut World, entity: Entity);
+}
+
+/// Generate a `ComponentSet` enum with one variant per component type
+///
+/// Each type must be `Clone`, `Serialize` and `Deserialize`; the variant is named after
+/// the type, which keeps save files readable. The derives use the engine's own serde
+/// through `#[serde(crate = "engine_2d::serde")]`, so the game needs no serde dependency,
+/// but the engine must be reachable as `engine_2d`: a dependency renamed in `Cargo.toml`
+/// won't compile.
+///
+/// ```
+/// # use engine_2d::component_set;
+/// # use engine_2d::ecs::{Transform, Velocity, World, WorldSnapshot};
+/// # use serde::{Deserialize, Serialize};
+/// # #[derive(Debug, Clone, Serialize, Deserialize)]
+/// # struct Health(u32);
+/// # let world = World::new();
+/// component_set! {
+///     pub enum SaveComponent { Transform, Velocity, Health }
+/// }
+/// let snapshot = WorldSnapshot::<SaveComponent>::capture(&world);
+/// let save = serde_json::to_string(&snapshot)?;
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[macro_export]
+macro_rules! component_set {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($component:ident),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, $crate::serde::Serialize, $crate::serde::Deserialize)]
+        // serde can't take `$crate` in a string; lib.rs makes this path work in-crate too
+        #[serde(crate = "engine_2d::serde")]
+        $vis enum $name {
+            $($component($component),)+
+        }
+
+        impl $crate::ecs::serialize::ComponentSet for $name {
+            fn collect(
+                world: &$crate::ecs::World,
+                entity: $crate::ecs::Entity,
+            ) -> Vec<Self> {
+                let mut components = Vec::new();
+                $(
+                    if let Some(component) = world.get::<$component>(entity) {
+                        components.push($name::$component(component.clone()));
+                    }
+                )+
+                components
+            }
+
+            fn insert(self, world: &mut $crate::ecs::World, entity: $crate::ecs::Entity) {
+                let result = match self {
+                    $($name::$component(component) => world.insert(entity, component),)+
+                };
+                debug_assert!(result.is_ok(), "inserting into a despawned entity");
+            }
+        }
+    };
+}
+
+/// One saved entity and its components
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntitySnapshot<C> {
+    /// The entity at capture time, for matching up references in saved data
+    pub entity: Entity,
+    pub components: Vec<C>,
+}
+
+/// Saved component data for a whole world or a selection of entities
+///
+/// Components outside the set are skipped. Entity handles stored inside components are
+/// saved as-is; use the mapping returned by `restore` to fix them up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldSnapshot<C> {
+    pub entities: Vec<EntitySnapshot<C>>,
+}
+
+impl<C> Default for WorldSnapshot<C> {
+    fn default() -> Self {
+        Self {
+            entities: Vec::new(),
+        }
+    }
+}
+
+impl<C: ComponentSet> WorldSnapshot<C> {
+    /// Save every live entity
+    pub fn capture(world: &World) -> Self {
+        let entities: Vec<Entity> = world.entities().collect();
+        Self::capture_entities(world, &entities)
+    }
+
+    /// Save the given entities, skipping any that are despawned
+    pub fn capture_entities(world: &World, entities: &[Entity]) -> Self {
+        Self {
+            entities: entities
+                .iter()
+                .filter(|&&entity| world.is_alive(entity))
+                .map(|&entity| EntitySnapshot {
+                    entity,
+                    components: C::collect(world, entity),
+                })
+                .collect(),
+        }
+    }
+
+    /// Spawn the saved entities into a world
+    ///
+    /// Returns (saved entity, new entity) pairs in snapshot order.
+    pub fn restore(self, world: &mut World) -> Vec<(Entity, Entity)> {
+        self.entities
+            .into_iter()
+            .map(|saved| {
+                let entity = world.spawn(());
+                for component in saved.components {
+                    component.insert(world, entity);
+                }
+                (saved.entity, entity)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,47 @@
+use super::component::Transform;
+use super::world::World;
+use crate::render::sprite::{Sprite, SpriteRenderer};
+
+/// A sprite drawn at its entity's `Transform`
+///
+/// The transform's position replaces the sprite's and its scale multiplies the sprite's
+/// size. Sprites don't rotate, so the transform's rotation is ignored.
+#[derive(Debug, Clone)]
+pub struct SpriteComponent(pub Sprite);
+
+impl SpriteComponent {
+    /// The sprite as it should be drawn for `transform`
+    pub fn placed(&self, transform: &Transform) -> Sprite {
+        let mut sprite = self.0.clone();
+        sprite.position = transform.position;
+        sprite.size *= transform.scale;
+        sprite
+    }
+}
+
+impl From<Sprite> for SpriteComponent {
+    fn from(sprite: Sprite) -> Self {
+        Self(sprite)
+    }
+}
+
+/// Sprites of every entity with a `Transform` and a `SpriteComponent`, in entity order
+pub fn collect_sprites(world: &mut World) -> Vec<Sprite> {
+    world
+        .query::<(&Transform, &SpriteComponent)>()
+        .filter(|(_, (_, sprite))| sprite.0.visible)
+        .map(|(_, (transform, sprite))| sprite.placed(transform))
+        .collect()
+}
+
+/// Draw every entity with a `Transform` and a `SpriteComponent`, sorted by layer
+///
+/// `Engine` calls this each rendered frame after the animation has drawn; call it
+/// yourself when driving a `SpriteRenderer` directly.
+pub fn submit_sprites(world: &mut World, renderer: &SpriteRenderer) -> Result<(), String> {
+    let sprites = collect_sprites(world);
+    if sprites.is_empty() {
+        return Ok(());
+    }
+    renderer.render_sprites(&sprites)
+}
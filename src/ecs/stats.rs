@@ -0,0 +1,131 @@
+use super::system::Stage;
+use std::fmt;
+use std::time::Duration;
+
+/// Snapshot of a world's contents and the cost of its systems, for debug overlays
+///
+/// ```
+/// # use engine_2d::ecs::{Scheduler, World};
+/// # let world = World::new();
+/// # let scheduler = Scheduler::new();
+/// let stats = world.stats().with_systems(scheduler.stats());
+/// let overlay_text = stats.to_string();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorldStats {
+    pub entities: usize,
+    /// Groups of entities with the same component types, most populous first
+    pub archetypes: Vec<ArchetypeStats>,
+    /// One entry per component type, by name
+    pub components: Vec<ComponentStats>,
+    pub resources: usize,
+    /// Empty unless added with `with_systems`
+    pub systems: Vec<SystemStats>,
+}
+
+/// Entities sharing one set of component types
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchetypeStats {
+    /// Component type names, sorted
+    pub components: Vec<&'static str>,
+    pub entities: usize,
+}
+
+/// Storage use of one component type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentStats {
+    pub name: &'static str,
+    pub count: usize,
+    /// Bytes allocated for the type's column, not counting heap data the components own
+    pub bytes: usize,
+}
+
+/// How long a scheduled system took the last time it ran
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemStats {
+    pub name: String,
+    pub stage: Stage,
+    /// Zero until the system has run
+    pub last_run: Duration,
+}
+
+impl WorldStats {
+    pub fn with_systems(mut self, systems: Vec<SystemStats>) -> Self {
+        self.systems = systems;
+        self
+    }
+
+    /// Total bytes allocated for component columns
+    pub fn component_bytes(&self) -> usize {
+        self.components
+            .iter()
+            .map(|component| component.bytes)
+            .sum()
+    }
+}
+
+impl fmt::Display for WorldStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} entities, {} archetypes, {} resources",
+            self.entities,
+            self.archetypes.len(),
+            self.resources
+        )?;
+        for archetype in &self.archetypes {
+            let names: Vec<String> = archetype
+                .components
+                .iter()
+                .map(|name| short_type_name(name))
+                .collect();
+            let names = if names.is_empty() {
+                "(no components)".to_string()
+            } else {
+                names.join(", ")
+            };
+            writeln!(f, "  {:>6}  {}", archetype.entities, names)?;
+        }
+        writeln!(f, "components: {} bytes", self.component_bytes())?;
+        for component in &self.components {
+            writeln!(
+                f,
+                "  {:>6}  {} ({} bytes)",
+                component.count,
+                short_type_name(component.name),
+                component.bytes
+            )?;
+        }
+        if !self.systems.is_empty() {
+            writeln!(f, "systems:")?;
+            for system in &self.systems {
+                writeln!(
+                    f,
+                    "  {:>8.3} ms  {} ({:?})",
+                    system.last_run.as_secs_f64() * 1000.0,
+                    system.name,
+                    system.stage
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Strip module paths from a type name: `alloc::vec::Vec<my_game::Enemy>` becomes
+/// `Vec<Enemy>`
+pub fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    let mut path = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            path.push(c);
+        } else {
+            short.push_str(path.rsplit("::").next().unwrap_or(""));
+            path.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(path.rsplit("::").next().unwrap_or(""));
+    short
+}
@@ -1 +1,538 @@
-// System execution implementation will go here
+use super::entity::{Entity, EntityAllocator};
+use super::query::{Query, QueryFilter, access_conflicts, for_each_captured, validate_access};
+use super::stats::SystemStats;
+use super::world::World;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+/// Frame phases systems run in, in this order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Stage {
+    /// Turn input into intents (movement direction, fire requests)
+    Input,
+    /// Gameplay logic
+    Update,
+    /// Movement and collision
+    Physics,
+    /// Hand the frame's state to the renderers
+    RenderSubmit,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 4] = [
+        Stage::Input,
+        Stage::Update,
+        Stage::Physics,
+        Stage::RenderSubmit,
+    ];
+}
+
+/// A system run by the scheduler with the world and the frame's delta time
+pub type SystemFn = dyn FnMut(&mut World, f32);
+
+/// A system that runs once per entity matching a query, without the rest of the world
+///
+/// Query systems with non-conflicting access can run at the same time on the rayon
+/// thread pool.
+trait QuerySystem: Send {
+    /// Components read and written, including by the filter
+    fn access(&self) -> Vec<(TypeId, &'static str, bool)>;
+
+    /// Capture the storages to iterate; called on the scheduling thread
+    fn prepare(&mut self, world: &mut World);
+
+    /// Iterate what `prepare` captured
+    ///
+    /// # Safety
+    /// The world must not have changed since `prepare`, and nothing else may be
+    /// accessing the components this system writes (or writing the ones it reads).
+    unsafe fn run(&mut self, entities: &EntityAllocator, delta_time: f32);
+}
+
+struct QuerySystemFn<Q: Query, F: QueryFilter, S> {
+    system: S,
+    /// States captured by `prepare`; the query's is None if a component type was never
+    /// inserted
+    prepared: Option<(Option<Q::State>, F::State)>,
+    _query: PhantomData<fn() -> (Q, F)>,
+}
+
+impl<Q, F, S> QuerySystem for QuerySystemFn<Q, F, S>
+where
+    Q: Query,
+    F: QueryFilter,
+    Q::State: Send,
+    F::State: Send,
+    S: for<'w> FnMut(Entity, Q::Item<'w>, f32) + Send,
+{
+    fn access(&self) -> Vec<(TypeId, &'static str, bool)> {
+        let mut access = Vec::new();
+        Q::access(&mut access);
+        F::access(&mut access);
+        access
+    }
+
+    fn prepare(&mut self, world: &mut World) {
+        let filter = F::state(world);
+        self.prepared = Some((Q::state(world), filter));
+    }
+
+    unsafe fn run(&mut self, entities: &EntityAllocator, delta_time: f32) {
+        if let Some((Some(state), filter)) = self.prepared.take() {
+            let system = &mut self.system;
+            unsafe {
+                for_each_captured::<Q, F>(entities, state, filter, |entity, item| {
+                    system(entity, item, delta_time)
+                });
+            }
+        }
+    }
+}
+
+enum SystemKind {
+    /// Gets the whole world; always runs alone
+    Exclusive(Box<SystemFn>),
+    Query(Box<dyn QuerySystem>),
+}
+
+struct SystemEntry {
+    name: String,
+    stage: Stage,
+    before: Vec<String>,
+    after: Vec<String>,
+    /// World change tick at the end of the last run, for change detection
+    last_run: u32,
+    /// Time the last run took
+    last_duration: Duration,
+    kind: SystemKind,
+}
+
+impl SystemEntry {
+    /// Check whether either system has an ordering constraint against the other
+    fn is_ordered_with(&self, other: &SystemEntry) -> bool {
+        let names = |entry: &SystemEntry, name: &str| {
+            entry.before.iter().chain(&entry.after).any(|n| n == name)
+        };
+        names(self, &other.name) || names(other, &self.name)
+    }
+}
+
+/// Ordering constraints for a system just added to a `Scheduler`
+pub struct SystemConfig<'a> {
+    entry: &'a mut SystemEntry,
+}
+
+impl SystemConfig<'_> {
+    /// Run before the named system
+    pub fn before(self, name: &str) -> Self {
+        self.entry.before.push(name.to_string());
+        self
+    }
+
+    /// Run after the named system
+    pub fn after(self, name: &str) -> Self {
+        self.entry.after.push(name.to_string());
+        self
+    }
+}
+
+/// Runs systems stage by stage each frame
+///
+/// Within a stage, systems run in the order they were added unless `before`/`after`
+/// constraints say otherwise. Constraints between systems in different stages must
+/// agree with the stage order.
+///
+/// Consecutive query systems in the same stage run in parallel when their component
+/// access doesn't conflict and neither is ordered against the other. Commands passed
+/// to `World::defer` are applied at the end of each stage.
+///
+/// ```
+/// # use engine_2d::ecs::{Scheduler, Stage, Transform, Velocity, World};
+/// # fn ai_system(_world: &mut World, _delta_time: f32) {}
+/// # fn steering_system(_world: &mut World, _delta_time: f32) {}
+/// # let mut scheduler = Scheduler::new();
+/// # let mut world = World::new();
+/// # let delta_time = 0.016;
+/// scheduler.add_system(Stage::Update, "ai", ai_system);
+/// scheduler.add_system(Stage::Update, "steering", steering_system).after("ai");
+/// scheduler.add_query_system::<(&mut Transform, &Velocity), _>(
+///     Stage::Physics,
+///     "movement",
+///     |_, (transform, velocity), dt| transform.position += velocity.0 * dt,
+/// );
+/// scheduler.run(&mut world, delta_time)?;
+/// # Ok::<(), String>(())
+/// ```
+pub struct Scheduler {
+    systems: Vec<SystemEntry>,
+    /// Run order as indices into `systems`, rebuilt after systems change
+    order: Option<Vec<usize>>,
+    parallel: bool,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self {
+            systems: Vec::new(),
+            order: None,
+            parallel: true,
+        }
+    }
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run non-conflicting query systems on the thread pool (on by default)
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    pub fn is_parallel(&self) -> bool {
+        self.parallel
+    }
+
+    /// Add a named system to a stage, replacing any system with the same name
+    pub fn add_system(
+        &mut self,
+        stage: Stage,
+        name: &str,
+        system: impl FnMut(&mut World, f32) + 'static,
+    ) -> SystemConfig<'_> {
+        self.push_system(stage, name, SystemKind::Exclusive(Box::new(system)))
+    }
+
+    /// Add a named system called for each entity matching `Q`
+    ///
+    /// Panics if `Q` asks for the same component mutably more than once.
+    pub fn add_query_system<Q, S>(
+        &mut self,
+        stage: Stage,
+        name: &str,
+        system: S,
+    ) -> SystemConfig<'_>
+    where
+        Q: Query + 'static,
+        Q::State: Send,
+        S: for<'w> FnMut(Entity, Q::Item<'w>, f32) + Send + 'static,
+    {
+        self.add_filtered_query_system::<Q, (), S>(stage, name, system)
+    }
+
+    /// Add a named system called for each entity matching `Q` and the filter `F`
+    pub fn add_filtered_query_system<Q, F, S>(
+        &mut self,
+        stage: Stage,
+        name: &str,
+        system: S,
+    ) -> SystemConfig<'_>
+    where
+        Q: Query + 'static,
+        F: QueryFilter + 'static,
+        Q::State: Send,
+        F::State: Send,
+        S: for<'w> FnMut(Entity, Q::Item<'w>, f32) + Send + 'static,
+    {
+        validate_access::<Q>();
+        let system = QuerySystemFn::<Q, F, S> {
+            system,
+            prepared: None,
+            _query: PhantomData,
+        };
+        self.push_system(stage, name, SystemKind::Query(Box::new(system)))
+    }
+
+    fn push_system(&mut self, stage: Stage, name: &str, kind: SystemKind) -> SystemConfig<'_> {
+        self.remove_system(name);
+        self.order = None;
+        self.systems.push(SystemEntry {
+            name: name.to_string(),
+            stage,
+            before: Vec::new(),
+            after: Vec::new(),
+            last_run: 0,
+            last_duration: Duration::ZERO,
+            kind,
+        });
+        let entry = self.systems.last_mut().expect("system was just added");
+        SystemConfig { entry }
+    }
+
+    /// Remove a system by name; returns false if there was none
+    pub fn remove_system(&mut self, name: &str) -> bool {
+        let count = self.systems.len();
+        self.systems.retain(|entry| entry.name != name);
+        self.order = None;
+        self.systems.len() != count
+    }
+
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    /// System names in the order they run
+    pub fn order(&mut self) -> Result<Vec<&str>, String> {
+        let order = self.resolve_order()?;
+        Ok(order
+            .iter()
+            .map(|&index| self.systems[index].name.as_str())
+            .collect())
+    }
+
+    /// How long each system took the last time it ran, in run order once the order has
+    /// been resolved
+    pub fn stats(&self) -> Vec<SystemStats> {
+        let added: Vec<usize> = (0..self.systems.len()).collect();
+        self.order
+            .as_ref()
+            .unwrap_or(&added)
+            .iter()
+            .map(|&index| {
+                let entry = &self.systems[index];
+                SystemStats {
+                    name: entry.name.clone(),
+                    stage: entry.stage,
+                    last_run: entry.last_duration,
+                }
+            })
+            .collect()
+    }
+
+    /// System names grouped into the batches they run in; systems in a batch run in
+    /// parallel
+    pub fn parallel_batches(&mut self) -> Result<Vec<Vec<&str>>, String> {
+        let order = self.resolve_order()?;
+        Ok(self
+            .batches(&order)
+            .iter()
+            .map(|batch| {
+                batch
+                    .iter()
+                    .map(|&index| self.systems[index].name.as_str())
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Run every stage in order
+    pub fn run(&mut self, world: &mut World, delta_time: f32) -> Result<(), String> {
+        self.run_stages(&Stage::ALL, world, delta_time)
+    }
+
+    /// Run the given stages in frame order, skipping the rest
+    pub fn run_stages(
+        &mut self,
+        stages: &[Stage],
+        world: &mut World,
+        delta_time: f32,
+    ) -> Result<(), String> {
+        let order = self.resolve_order()?;
+        for stage in Stage::ALL
+            .into_iter()
+            .filter(|stage| stages.contains(stage))
+        {
+            let stage_order: Vec<usize> = order
+                .iter()
+                .copied()
+                .filter(|&index| self.systems[index].stage == stage)
+                .collect();
+            self.run_order(&stage_order, world, delta_time);
+        }
+        Ok(())
+    }
+
+    /// Run the systems of one stage
+    pub fn run_stage(
+        &mut self,
+        stage: Stage,
+        world: &mut World,
+        delta_time: f32,
+    ) -> Result<(), String> {
+        let mut order = self.resolve_order()?;
+        order.retain(|&index| self.systems[index].stage == stage);
+        self.run_order(&order, world, delta_time);
+        Ok(())
+    }
+
+    /// Run one stage's systems, then apply the commands they deferred
+    fn run_order(&mut self, order: &[usize], world: &mut World, delta_time: f32) {
+        for batch in self.batches(order) {
+            self.run_batch(&batch, world, delta_time);
+        }
+        world.apply_deferred();
+    }
+
+    /// Split the run order into batches of systems that can run at the same time
+    fn batches(&self, order: &[usize]) -> Vec<Vec<usize>> {
+        let mut batches: Vec<Vec<usize>> = Vec::new();
+        let mut batch_access = Vec::new();
+        for &index in order {
+            let entry = &self.systems[index];
+            let SystemKind::Query(system) = &entry.kind else {
+                batches.push(vec![index]);
+                continue;
+            };
+            let access = system.access();
+            let joins = self.parallel
+                && batches.last().is_some_and(|batch| {
+                    let first = &self.systems[batch[0]];
+                    matches!(first.kind, SystemKind::Query(_))
+                        && first.stage == entry.stage
+                        && !access_conflicts(&access, &batch_access)
+                        && batch
+                            .iter()
+                            .all(|&other| !entry.is_ordered_with(&self.systems[other]))
+                });
+            if joins {
+                batches.last_mut().expect("batch exists").push(index);
+                batch_access.extend(access);
+            } else {
+                batches.push(vec![index]);
+                batch_access = access;
+            }
+        }
+        batches
+    }
+
+    /// Run a batch of systems, showing each only the changes made since its last run
+    fn run_batch(&mut self, batch: &[usize], world: &mut World, delta_time: f32) {
+        if let [index] = *batch {
+            let entry = &mut self.systems[index];
+            if let SystemKind::Exclusive(run) = &mut entry.kind {
+                let start = Instant::now();
+                entry.last_run = world.run_tracked(entry.last_run, |world| run(world, delta_time));
+                entry.last_duration = start.elapsed();
+                return;
+            }
+        }
+
+        // Every system in the batch shares one tick, so none sees the others' changes
+        let tick = world.begin_tracked();
+        let mut systems = Vec::with_capacity(batch.len());
+        for (index, entry) in self.systems.iter_mut().enumerate() {
+            if let SystemKind::Query(system) = &mut entry.kind
+                && batch.contains(&index)
+            {
+                world.with_last_change_tick(entry.last_run, |world| system.prepare(world));
+                entry.last_run = tick;
+                systems.push((system, &mut entry.last_duration));
+            }
+        }
+
+        let entities = world.entity_allocator();
+        let run = |system: &mut Box<dyn QuerySystem>, duration: &mut Duration| {
+            let start = Instant::now();
+            // Safety: batches only group systems whose access doesn't conflict, and the
+            // world stays borrowed until they all finish
+            unsafe { system.run(entities, delta_time) };
+            *duration = start.elapsed();
+        };
+        if let [(system, duration)] = systems.as_mut_slice() {
+            run(system, duration);
+        } else {
+            rayon::scope(|scope| {
+                for (system, duration) in systems {
+                    scope.spawn(move |_| run(system, duration));
+                }
+            });
+        }
+        world.end_tracked();
+    }
+
+    /// Sort systems by stage, then topologically within each stage
+    fn resolve_order(&mut self) -> Result<Vec<usize>, String> {
+        if let Some(order) = &self.order {
+            return Ok(order.clone());
+        }
+
+        let index_of: HashMap<&str, usize> = self
+            .systems
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.name.as_str(), index))
+            .collect();
+        let lookup = |owner: &str, name: &str| {
+            index_of.get(name).copied().ok_or_else(|| {
+                format!(
+                    "System '{}' is ordered against unknown system '{}'",
+                    owner, name
+                )
+            })
+        };
+
+        // Edges run from the earlier system to the later one
+        let mut successors = vec![Vec::new(); self.systems.len()];
+        let mut incoming = vec![0usize; self.systems.len()];
+        for (index, entry) in self.systems.iter().enumerate() {
+            let edges = entry
+                .before
+                .iter()
+                .map(|name| Ok((index, lookup(&entry.name, name)?)))
+                .chain(
+                    entry
+                        .after
+                        .iter()
+                        .map(|name| Ok((lookup(&entry.name, name)?, index))),
+                )
+                .collect::<Result<Vec<_>, String>>()?;
+            for (first, second) in edges {
+                let (first_stage, second_stage) =
+                    (self.systems[first].stage, self.systems[second].stage);
+                if first_stage > second_stage {
+                    return Err(format!(
+                        "System '{}' ({:?}) can't run before '{}' ({:?})",
+                        self.systems[first].name,
+                        first_stage,
+                        self.systems[second].name,
+                        second_stage
+                    ));
+                }
+                if first_stage == second_stage {
+                    successors[first].push(second);
+                    incoming[second] += 1;
+                }
+            }
+        }
+
+        // Kahn's algorithm, always taking the earliest-stage, earliest-added ready system
+        let mut order = Vec::with_capacity(self.systems.len());
+        let mut ready: Vec<usize> = (0..self.systems.len())
+            .filter(|&index| incoming[index] == 0)
+            .collect();
+        while !ready.is_empty() {
+            let position = (0..ready.len())
+                .min_by_key(|&i| (self.systems[ready[i]].stage, ready[i]))
+                .expect("ready is not empty");
+            let index = ready.remove(position);
+            order.push(index);
+            for &next in &successors[index] {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+
+        if order.len() < self.systems.len() {
+            let cycle: Vec<&str> = (0..self.systems.len())
+                .filter(|&index| incoming[index] > 0)
+                .map(|index| self.systems[index].name.as_str())
+                .collect();
+            return Err(format!(
+                "Systems have cyclic ordering constraints: {}",
+                cycle.join(", ")
+            ));
+        }
+
+        self.order = Some(order.clone());
+        Ok(order)
+    }
+}
@@ -1 +1,494 @@
-// ECS world implementation will go here
+use super::commands::Commands;
+use super::component::{AnyStorage, Component, ComponentStorage, ComponentTicks};
+use super::entity::{Entity, EntityAllocator};
+use super::names::NameRegistry;
+use super::query::{Query, QueryFilter, QueryIter, validate_access};
+use super::stats::{ArchetypeStats, ComponentStats, WorldStats};
+use crate::events::EntityEvent;
+use std::any::{Any, TypeId, type_name};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::mpsc::Sender;
+use std::time::Instant;
+
+/// A set of components spawned together, implemented for tuples of components
+pub trait Bundle {
+    fn insert_into(self, world: &mut World, entity: Entity);
+}
+
+impl Bundle for () {
+    fn insert_into(self, _world: &mut World, _entity: Entity) {}
+}
+
+macro_rules! impl_bundle_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Component),+> Bundle for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn insert_into(self, world: &mut World, entity: Entity) {
+                let ($($name,)+) = self;
+                $(world.insert_component(entity, $name);)+
+            }
+        }
+    };
+}
+
+impl_bundle_tuple!(A);
+impl_bundle_tuple!(A, B);
+impl_bundle_tuple!(A, B, C);
+impl_bundle_tuple!(A, B, C, D);
+impl_bundle_tuple!(A, B, C, D, E);
+impl_bundle_tuple!(A, B, C, D, E, F);
+impl_bundle_tuple!(A, B, C, D, E, F, G);
+impl_bundle_tuple!(A, B, C, D, E, F, G, H);
+
+/// Entities and their components
+///
+/// ```
+/// # use engine_2d::ecs::{Transform, Velocity, World};
+/// # use glam::Vec2;
+/// # let delta_time = 0.5;
+/// let mut world = World::new();
+/// let player = world.spawn((Transform::default(), Velocity(Vec2::X)));
+/// for (_, (transform, velocity)) in world.query::<(&mut Transform, &Velocity)>() {
+///     transform.position += velocity.0 * delta_time;
+/// }
+/// # assert_eq!(world.get::<Transform>(player).unwrap().position, Vec2::new(0.5, 0.0));
+/// ```
+pub struct World {
+    entities: EntityAllocator,
+    storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+    names: NameRegistry,
+    /// Singletons shared by systems, one per type
+    resources: HashMap<TypeId, Box<dyn Any>>,
+    /// Commands waiting for the next sync point
+    deferred: Commands,
+    /// Tick stamped on components as they are added or changed
+    change_tick: u32,
+    /// Changes after this tick count as new for `Added`/`Changed` filters
+    last_change_tick: u32,
+    /// Where spawn, despawn and component-added events go, if anywhere
+    event_sender: Option<Sender<EntityEvent>>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self {
+            entities: EntityAllocator::new(),
+            storages: HashMap::new(),
+            names: NameRegistry::new(),
+            resources: HashMap::new(),
+            deferred: Commands::new(),
+            change_tick: 1,
+            last_change_tick: 0,
+            event_sender: None,
+        }
+    }
+}
+
+impl fmt::Debug for World {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("World")
+            .field("entities", &self.entities.len())
+            .field("component_types", &self.storages.len())
+            .field("resources", &self.resources.len())
+            .finish()
+    }
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send `EntityEvent`s for spawns, despawns and added components, e.g. to
+    /// `EventSystem::get_entity_sender()`; `None` stops sending
+    pub fn set_event_sender(&mut self, sender: Option<Sender<EntityEvent>>) {
+        self.event_sender = sender;
+    }
+
+    /// Create an entity with a bundle of components; pass `()` for an empty entity
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        let entity = self.entities.allocate();
+        self.send_event(|timestamp| EntityEvent::EntitySpawned { entity, timestamp });
+        bundle.insert_into(self, entity);
+        entity
+    }
+
+    /// Remove an entity and all of its components; returns false if it was already gone
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.entities.free(entity) {
+            return false;
+        }
+        for storage in self.storages.values_mut() {
+            storage.remove_index(entity.index());
+        }
+        self.names.remove_entity(entity);
+        self.send_event(|timestamp| EntityEvent::EntityDespawned { entity, timestamp });
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.is_alive(entity)
+    }
+
+    /// Number of live entities
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Iterate over live entities
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter()
+    }
+
+    /// Despawn every entity; resources are kept
+    pub fn clear(&mut self) {
+        if self.event_sender.is_some() {
+            for entity in self.entities.iter().collect::<Vec<_>>() {
+                self.send_event(|timestamp| EntityEvent::EntityDespawned { entity, timestamp });
+            }
+        }
+        self.entities.clear();
+        self.storages.clear();
+        self.names.clear();
+    }
+
+    /// Give an entity a unique name, replacing its previous one
+    pub fn set_name(&mut self, entity: Entity, name: &str) -> Result<(), String> {
+        if !self.is_alive(entity) {
+            return Err(format!("Entity {} does not exist", entity));
+        }
+        self.names.set_name(entity, name)
+    }
+
+    /// Remove an entity's name, returning it
+    pub fn remove_name(&mut self, entity: Entity) -> Option<String> {
+        self.names.remove_name(entity)
+    }
+
+    pub fn name(&self, entity: Entity) -> Option<&str> {
+        self.names.name(entity)
+    }
+
+    /// The live entity with a name
+    pub fn find_by_name(&self, name: &str) -> Option<Entity> {
+        self.names.find_by_name(name)
+    }
+
+    /// Tag an entity; returns false if it already had the tag
+    pub fn add_tag(&mut self, entity: Entity, tag: &str) -> Result<bool, String> {
+        if !self.is_alive(entity) {
+            return Err(format!("Entity {} does not exist", entity));
+        }
+        Ok(self.names.add_tag(entity, tag))
+    }
+
+    /// Untag an entity; returns false if it didn't have the tag
+    pub fn remove_tag(&mut self, entity: Entity, tag: &str) -> bool {
+        self.names.remove_tag(entity, tag)
+    }
+
+    pub fn has_tag(&self, entity: Entity, tag: &str) -> bool {
+        self.names.has_tag(entity, tag)
+    }
+
+    /// Live entities with a tag, in entity order
+    pub fn tagged(&self, tag: &str) -> impl Iterator<Item = Entity> + '_ {
+        self.names.tagged(tag)
+    }
+
+    /// Names and tags of every entity
+    pub fn names(&self) -> &NameRegistry {
+        &self.names
+    }
+
+    /// Store a singleton such as the score or settings, returning the one it replaced
+    pub fn insert_resource<R: Component>(&mut self, resource: R) -> Option<R> {
+        let previous = self
+            .resources
+            .insert(TypeId::of::<R>(), Box::new(resource))?;
+        previous.downcast().ok().map(|previous| *previous)
+    }
+
+    pub fn remove_resource<R: Component>(&mut self) -> Option<R> {
+        let resource = self.resources.remove(&TypeId::of::<R>())?;
+        resource.downcast().ok().map(|resource| *resource)
+    }
+
+    pub fn resource<R: Component>(&self) -> Option<&R> {
+        self.resources.get(&TypeId::of::<R>())?.downcast_ref()
+    }
+
+    pub fn resource_mut<R: Component>(&mut self) -> Option<&mut R> {
+        self.resources.get_mut(&TypeId::of::<R>())?.downcast_mut()
+    }
+
+    /// Get a resource, inserting `R::default()` first if there is none
+    pub fn resource_or_default<R: Component + Default>(&mut self) -> &mut R {
+        self.resources
+            .entry(TypeId::of::<R>())
+            .or_insert_with(|| Box::new(R::default()))
+            .downcast_mut()
+            .expect("Resource type mismatch")
+    }
+
+    pub fn has_resource<R: Component>(&self) -> bool {
+        self.resources.contains_key(&TypeId::of::<R>())
+    }
+
+    /// Attach a component, replacing any existing one of the same type
+    pub fn insert<T: Component>(&mut self, entity: Entity, component: T) -> Result<(), String> {
+        if !self.is_alive(entity) {
+            return Err(format!("Entity {} does not exist", entity));
+        }
+        self.insert_component(entity, component);
+        Ok(())
+    }
+
+    /// Attach every component in a bundle
+    pub fn insert_bundle(&mut self, entity: Entity, bundle: impl Bundle) -> Result<(), String> {
+        if !self.is_alive(entity) {
+            return Err(format!("Entity {} does not exist", entity));
+        }
+        bundle.insert_into(self, entity);
+        Ok(())
+    }
+
+    /// Detach and return a component
+    pub fn remove<T: Component>(&mut self, entity: Entity) -> Option<T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.storage_mut::<T>()?.remove(entity.index())
+    }
+
+    pub fn get<T: Component>(&self, entity: Entity) -> Option<&T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.storage::<T>()?.get(entity.index())
+    }
+
+    /// Get a component mutably, marking it changed
+    pub fn get_mut<T: Component>(&mut self, entity: Entity) -> Option<&mut T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        let tick = self.change_tick;
+        self.storage_mut::<T>()?.get_mut(entity.index(), tick)
+    }
+
+    pub fn has<T: Component>(&self, entity: Entity) -> bool {
+        self.get::<T>(entity).is_some()
+    }
+
+    /// When an entity's component was added and last changed
+    pub fn component_ticks<T: Component>(&self, entity: Entity) -> Option<ComponentTicks> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.storage::<T>()?.ticks(entity.index())
+    }
+
+    /// Check if an entity's component was added since the last tracker reset
+    pub fn is_added<T: Component>(&self, entity: Entity) -> bool {
+        self.component_ticks::<T>(entity)
+            .is_some_and(|ticks| ticks.is_added(self.last_change_tick))
+    }
+
+    /// Check if an entity's component was added or changed since the last tracker reset
+    pub fn is_changed<T: Component>(&self, entity: Entity) -> bool {
+        self.component_ticks::<T>(entity)
+            .is_some_and(|ticks| ticks.is_changed(self.last_change_tick))
+    }
+
+    /// Queue commands to apply at the next sync point: the end of the current
+    /// `Scheduler` stage, or the next `apply_deferred` call
+    pub fn defer(&mut self, mut commands: Commands) {
+        self.deferred.append(&mut commands);
+    }
+
+    /// Apply every deferred command, including any they defer in turn
+    pub fn apply_deferred(&mut self) {
+        while !self.deferred.is_empty() {
+            std::mem::take(&mut self.deferred).apply(self);
+        }
+    }
+
+    /// Tick stamped on components added or changed now
+    pub fn change_tick(&self) -> u32 {
+        self.change_tick
+    }
+
+    /// Changes after this tick are reported by `Added`/`Changed`
+    pub fn last_change_tick(&self) -> u32 {
+        self.last_change_tick
+    }
+
+    /// Start a new change-tracking period: everything so far stops counting as added
+    /// or changed
+    ///
+    /// Systems run by the `Scheduler` track their own periods; call this once per frame
+    /// when querying the world directly.
+    pub fn clear_trackers(&mut self) {
+        self.last_change_tick = self.change_tick;
+        self.change_tick += 1;
+    }
+
+    /// Run `system` seeing only changes made after `last_run`
+    ///
+    /// Returns the tick to pass as `last_run` next time, so the system doesn't see its
+    /// own changes.
+    pub fn run_tracked(&mut self, last_run: u32, system: impl FnOnce(&mut World)) -> u32 {
+        let tick = self.begin_tracked();
+        self.with_last_change_tick(last_run, system);
+        self.end_tracked();
+        tick
+    }
+
+    /// Start a tick for one or more systems that run together
+    pub(crate) fn begin_tracked(&mut self) -> u32 {
+        self.change_tick += 1;
+        self.change_tick
+    }
+
+    /// Finish a tracked run so later changes count as new on the systems' next run
+    pub(crate) fn end_tracked(&mut self) {
+        self.change_tick += 1;
+    }
+
+    /// Run `f` with change detection seeing only changes made after `last_run`
+    pub(crate) fn with_last_change_tick<R>(
+        &mut self,
+        last_run: u32,
+        f: impl FnOnce(&mut World) -> R,
+    ) -> R {
+        let previous = self.last_change_tick;
+        self.last_change_tick = last_run;
+        let result = f(self);
+        self.last_change_tick = previous;
+        result
+    }
+
+    /// Iterate over entities that have every component in `Q`
+    ///
+    /// Panics if `Q` asks for the same component mutably more than once.
+    pub fn query<Q: Query>(&mut self) -> QueryIter<'_, Q> {
+        self.query_filtered::<Q, ()>()
+    }
+
+    /// Iterate over entities that have every component in `Q` and pass the filter `F`
+    ///
+    /// ```
+    /// # use engine_2d::ecs::{Changed, Transform, World};
+    /// # use std::collections::HashMap;
+    /// # let mut world = World::new();
+    /// # let mut spatial_index = HashMap::new();
+    /// for (entity, transform) in world.query_filtered::<&Transform, Changed<Transform>>() {
+    ///     spatial_index.insert(entity, transform.position);
+    /// }
+    /// ```
+    pub fn query_filtered<Q: Query, F: QueryFilter>(&mut self) -> QueryIter<'_, Q, F> {
+        validate_access::<Q>();
+        let filter = F::state(self);
+        let state = Q::state(self);
+        QueryIter::new(&self.entities, state, filter)
+    }
+
+    pub(crate) fn entity_allocator(&self) -> &EntityAllocator {
+        &self.entities
+    }
+
+    /// Entity counts per set of component types, and memory used per component type
+    pub fn stats(&self) -> WorldStats {
+        let mut archetypes: BTreeMap<Vec<&'static str>, usize> = BTreeMap::new();
+        for entity in self.entities.iter() {
+            let mut components: Vec<&'static str> = self
+                .storages
+                .values()
+                .filter(|storage| storage.contains_index(entity.index()))
+                .map(|storage| storage.component_name())
+                .collect();
+            components.sort_unstable();
+            *archetypes.entry(components).or_default() += 1;
+        }
+        let mut archetypes: Vec<ArchetypeStats> = archetypes
+            .into_iter()
+            .map(|(components, entities)| ArchetypeStats {
+                components,
+                entities,
+            })
+            .collect();
+        // Stable, so ties stay sorted by component names
+        archetypes.sort_by_key(|archetype| Reverse(archetype.entities));
+
+        let mut components: Vec<ComponentStats> = self
+            .storages
+            .values()
+            .map(|storage| ComponentStats {
+                name: storage.component_name(),
+                count: storage.component_count(),
+                bytes: storage.memory_bytes(),
+            })
+            .collect();
+        components.sort_by_key(|component| component.name);
+
+        WorldStats {
+            entities: self.entities.len(),
+            archetypes,
+            components,
+            resources: self.resources.len(),
+            systems: Vec::new(),
+        }
+    }
+
+    /// All components of one type
+    pub fn storage<T: Component>(&self) -> Option<&ComponentStorage<T>> {
+        self.storages
+            .get(&TypeId::of::<T>())?
+            .as_any()
+            .downcast_ref()
+    }
+
+    pub(crate) fn storage_mut<T: Component>(&mut self) -> Option<&mut ComponentStorage<T>> {
+        self.storages
+            .get_mut(&TypeId::of::<T>())?
+            .as_any_mut()
+            .downcast_mut()
+    }
+
+    fn insert_component<T: Component>(&mut self, entity: Entity, component: T) {
+        let tick = self.change_tick;
+        let previous = self
+            .storage_or_default::<T>()
+            .insert(entity.index(), component, tick);
+        if previous.is_none() {
+            self.send_event(|timestamp| EntityEvent::ComponentAdded {
+                entity,
+                component: type_name::<T>(),
+                timestamp,
+            });
+        }
+    }
+
+    fn send_event(&self, event: impl FnOnce(Instant) -> EntityEvent) {
+        if let Some(sender) = &self.event_sender {
+            // A dropped receiver just means nobody is listening any more
+            let _ = sender.send(event(Instant::now()));
+        }
+    }
+
+    fn storage_or_default<T: Component>(&mut self) -> &mut ComponentStorage<T> {
+        self.storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(ComponentStorage::<T>::default()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("Component storage type mismatch")
+    }
+}
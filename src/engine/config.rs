@@ -1,3 +1,6 @@
+use crate::render::viewport::ScalingPolicy;
+use crate::ui::SafeArea;
+
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
     pub window_title: String,
@@ -11,6 +14,68 @@ pub struct EngineConfig {
     pub viewport: ViewportConfig,
     /// Fallback font path for text rendering when specified fonts are not found
     pub fallback_font_path: String,
+    /// Reduced tick/render rate while minimized or unfocused
+    pub idle: IdleConfig,
+    /// Periodic export of frame timing and resource metrics
+    pub metrics: MetricsConfig,
+    /// Render into an sRGB framebuffer and decode color textures as sRGB so blending and
+    /// tinting happen in linear space
+    pub srgb: bool,
+}
+
+/// Configuration for idle/background mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleConfig {
+    /// Whether the engine may enter idle mode at all
+    pub enabled: bool,
+    /// Seconds without focus before entering idle mode (minimizing is immediate)
+    pub idle_after_secs: f32,
+    /// Frames presented per second while idle (0 stops presenting entirely); only used
+    /// with `update_while_idle`, since a frame that isn't updated isn't presented
+    pub idle_fps: u32,
+    /// Keep calling the animation update while idle
+    pub update_while_idle: bool,
+}
+
+impl Default for IdleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            idle_after_secs: 5.0,
+            idle_fps: 2,
+            update_while_idle: true,
+        }
+    }
+}
+
+/// Where exported metrics go
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricsOutput {
+    /// Append one row per interval to a CSV file, writing a header if the file is new
+    Csv { path: String },
+    /// Serve the latest interval in the Prometheus text format over HTTP at `address`
+    Prometheus { address: String },
+}
+
+/// Configuration for the metrics exporter, for soak tests and long-running servers
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub output: MetricsOutput,
+    /// Seconds of frames summarized in each export
+    pub interval_secs: f32,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            output: MetricsOutput::Csv {
+                path: "metrics.csv".to_string(),
+            },
+            interval_secs: 10.0,
+        }
+    }
 }
 
 /// Configuration for the viewport coordinate system
@@ -24,6 +89,10 @@ pub struct ViewportConfig {
     pub base_font_size: f32,
     /// Whether text size should be viewport-independent (true) or viewport-relative (false)
     pub viewport_independent_text: bool,
+    /// How the view adapts when the window's aspect ratio differs from the configured size
+    pub scaling: ScalingPolicy,
+    /// Insets that anchored UI stays within, for TVs and streaming overlays
+    pub safe_area: SafeArea,
 }
 
 impl ViewportConfig {
@@ -34,6 +103,8 @@ impl ViewportConfig {
             text_height_fraction: 0.002, // 0.2% for college essay title size
             base_font_size: 16.0,
             viewport_independent_text: true,
+            scaling: ScalingPolicy::Stretch,
+            safe_area: SafeArea::default(),
         }
     }
 
@@ -44,6 +115,8 @@ impl ViewportConfig {
             text_height_fraction: 0.05, // 5% of viewport height
             base_font_size: 16.0,
             viewport_independent_text: false, // Use viewport-relative scaling
+            scaling: ScalingPolicy::Stretch,
+            safe_area: SafeArea::default(),
         }
     }
 
@@ -54,6 +127,8 @@ impl ViewportConfig {
             text_height_fraction: 0.02, // 2% of viewport height for reasonable text size
             base_font_size: 16.0,
             viewport_independent_text: false, // Pixel-based should be viewport-relative
+            scaling: ScalingPolicy::Stretch,
+            safe_area: SafeArea::default(),
         }
     }
 
@@ -64,8 +139,22 @@ impl ViewportConfig {
             text_height_fraction: 0.015, // 1.5% for UI - reasonable text size
             base_font_size: 16.0,
             viewport_independent_text: false, // Use viewport-relative scaling for UI coordinates
+            scaling: ScalingPolicy::Stretch,
+            safe_area: SafeArea::default(),
         }
     }
+
+    /// Keep the aspect ratio on resize using `scaling`
+    pub fn with_scaling(mut self, scaling: ScalingPolicy) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Keep anchored UI within `safe_area`
+    pub fn with_safe_area(mut self, safe_area: SafeArea) -> Self {
+        self.safe_area = safe_area;
+        self
+    }
 }
 
 impl Default for ViewportConfig {
@@ -75,6 +164,8 @@ impl Default for ViewportConfig {
             text_height_fraction: 0.002, // 0.2% of viewport height - college essay title size
             base_font_size: 16.0,
             viewport_independent_text: true, // Default to viewport-independent text
+            scaling: ScalingPolicy::Stretch,
+            safe_area: SafeArea::default(),
         }
     }
 }
@@ -90,9 +181,10 @@ impl Default for EngineConfig {
             vsync: true,
             fullscreen: false,
             viewport: ViewportConfig::default(),
-            fallback_font_path: format!(
-                "{}/assets/fonts/default.ttf",
-                env!("CARGO_MANIFEST_DIR")
-            ),
-        }    }
+            fallback_font_path: format!("{}/assets/fonts/default.ttf", env!("CARGO_MANIFEST_DIR")),
+            idle: IdleConfig::default(),
+            metrics: MetricsConfig::default(),
+            srgb: false,
+        }
+    }
 }
@@ -1,51 +1,109 @@
 use super::config::EngineConfig;
 #[cfg(feature = "opengl")]
+use super::game::RenderContext;
+use super::game::{Game, LegacyAnimation, UpdateContext};
+#[cfg(feature = "opengl")]
+use super::idle::IdleTracker;
+use super::metrics::MetricsExporter;
+#[cfg(feature = "opengl")]
 use super::window::WindowManager;
-use crate::animation::Animation;
+use crate::animation::{Animation, AnimationTime};
 #[cfg(feature = "opengl")]
+use crate::ecs::submit_sprites;
+use crate::ecs::{Scheduler, Stage, World};
 use crate::events::event_system::EventSystem;
 #[cfg(feature = "opengl")]
+use crate::events::event_types::Consumable;
+use crate::input::manager::InputManager;
+#[cfg(feature = "opengl")]
 use crate::render::gl_wrapper::GlWrapper;
 #[cfg(feature = "opengl")]
+use crate::render::lighting::LightingRenderer;
+#[cfg(feature = "opengl")]
 use crate::render::renderer::Renderer;
 #[cfg(feature = "opengl")]
 use crate::render::simple_text::SimpleTextRenderer;
 #[cfg(feature = "opengl")]
 use crate::render::sprite::SpriteRenderer;
 #[cfg(feature = "opengl")]
+use crate::render::viewport::{ViewMapping, ViewportRect};
+#[cfg(feature = "opengl")]
 use glfw::{Action, Key};
 #[cfg(feature = "opengl")]
 use std::rc::Rc;
 use std::time::{Duration, Instant};
 
+/// Outcome of a single `Engine::tick()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickResult {
+    /// The frame was updated and presented
+    Rendered,
+    /// No frame was presented (idle mode or headless)
+    Skipped,
+    /// The engine was asked to close; stop ticking
+    Exit,
+}
+
+/// Why a window or renderer getter panicked
+#[cfg(feature = "opengl")]
+const HEADLESS: &str = "a headless engine has no window or renderers";
+
+/// The window and everything that draws to it
+#[cfg(feature = "opengl")]
+struct Graphics {
+    window_manager: WindowManager,
+    renderer: Renderer,
+    sprite_renderer: SpriteRenderer,
+    text_renderer: SimpleTextRenderer,
+    lighting_renderer: LightingRenderer,
+    // Framebuffer region rendered to under the configured scaling policy
+    viewport_rect: ViewportRect,
+    // Idle/background mode state
+    idle_tracker: IdleTracker,
+}
+
+#[cfg(feature = "opengl")]
+impl Graphics {
+    fn view_mapping(&self, logical_bounds: (f32, f32, f32, f32)) -> ViewMapping {
+        let (width, height) = self.window_manager.window.get_size();
+        ViewMapping {
+            window_size: (width.max(0) as u32, height.max(0) as u32),
+            framebuffer_size: self.window_manager.get_size(),
+            viewport_rect: self.viewport_rect,
+            logical_bounds,
+        }
+    }
+}
+
 pub struct Engine {
     // Engine state
     is_running: bool,
-    // Frame timing for device-agnostic animations (used in run() methods)
-    #[allow(dead_code)] // False positive: fields are used in conditional compilation blocks
+    // Frame timing for device-agnostic animations (advanced by tick())
     delta_time: Duration,
-    #[allow(dead_code)] // False positive: fields are used in conditional compilation blocks
     last_frame_time: Instant,
     // Total elapsed time since engine start (accumulated from delta_time)
     elapsed_time: f32,
+    // Elapsed time as seen by the animation, under the world's `AnimationTime`
+    animation_elapsed: f32,
 
-    // OpenGL context is managed by the renderer
-
-    // Window and input systems
-    #[cfg(feature = "opengl")]
-    window_manager: WindowManager,
     config: EngineConfig,
 
-    // Rendering system
+    // Window, renderers and idle state; None for a headless engine
     #[cfg(feature = "opengl")]
-    renderer: Renderer,
-    #[cfg(feature = "opengl")]
-    sprite_renderer: SpriteRenderer,
-    #[cfg(feature = "opengl")]
-    text_renderer: SimpleTextRenderer,
+    graphics: Option<Graphics>,
+
+    // Metrics export, when enabled in the config
+    metrics: Option<MetricsExporter>,
 
-    // Current animation
-    animation: Box<dyn Animation>,
+    // Entity and animation events, dispatched to listeners each tick
+    event_system: EventSystem,
+
+    // ECS entities and the systems run on them each tick
+    world: World,
+    scheduler: Scheduler,
+
+    // Game logic, updated and rendered each tick
+    game: Box<dyn Game>,
 }
 
 impl Engine {
@@ -60,10 +118,18 @@ impl Engine {
         Self::new_with_config_and_animation(config, Box::new(crate::animation::NoAnimation::new()))
     }
 
-    #[cfg(feature = "opengl")]
+    /// Create an engine running an `Animation` through the `LegacyAnimation` shim
     pub fn new_with_config_and_animation(
         config: EngineConfig,
         animation: Box<dyn Animation>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::new_with_config_and_game(config, Box::new(LegacyAnimation::new(animation)))
+    }
+
+    #[cfg(feature = "opengl")]
+    pub fn new_with_config_and_game(
+        config: EngineConfig,
+        game: Box<dyn Game>,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         // Create GlWrapper first
         let mut gl_wrapper = GlWrapper::new();
@@ -71,8 +137,15 @@ impl Engine {
         // Create event system for window manager
         let event_system = EventSystem::new();
 
+        // World changes and animation markers are reported through the same event system
+        let mut world = World::new();
+        world.set_event_sender(Some(event_system.get_entity_sender()));
+        world.insert_resource(event_system.get_animation_sender());
+        world.insert_resource(AnimationTime::default());
+
         // Create window manager with GlWrapper and event system
-        let window_manager = WindowManager::new(&config, &mut gl_wrapper, Some(event_system))?;
+        let window_manager =
+            WindowManager::new(&config, &mut gl_wrapper, Some(event_system.clone()))?;
 
         // Wrap GlWrapper in Rc for shared ownership
         let gl_wrapper_rc = Rc::new(gl_wrapper);
@@ -82,12 +155,18 @@ impl Engine {
         if let Err(e) = renderer.initialize() {
             return Err(format!("Failed to initialize renderer: {}", e).into());
         }
+        let viewport_rect = config.viewport.scaling.viewport_rect(
+            (config.window_width, config.window_height),
+            window_manager.get_size(),
+        );
+        renderer.set_viewport_rect(viewport_rect)?;
 
         // Create sprite renderer with the same shared GlWrapper
         let mut sprite_renderer = SpriteRenderer::new(Rc::clone(&gl_wrapper_rc));
         if let Err(e) = sprite_renderer.initialize() {
             return Err(format!("Failed to initialize sprite renderer: {}", e).into());
         }
+        sprite_renderer.texture_manager().set_srgb(config.srgb);
 
         // Create text renderer with the same shared GlWrapper
         let mut text_renderer =
@@ -96,6 +175,12 @@ impl Engine {
             return Err(format!("Failed to initialize text renderer: {}", e).into());
         }
 
+        // Create lighting renderer (disabled until an animation enables it)
+        let mut lighting_renderer = LightingRenderer::new(Rc::clone(&gl_wrapper_rc));
+        if let Err(e) = lighting_renderer.initialize() {
+            return Err(format!("Failed to initialize lighting renderer: {}", e).into());
+        }
+
         // Configure viewport for text rendering using the config
         let viewport_config = &config.viewport;
         text_renderer.viewport_mut().logical_bounds = viewport_config.logical_bounds;
@@ -118,45 +203,126 @@ impl Engine {
 
         // Set viewport independence from config
         text_renderer.set_viewport_independent_text(viewport_config.viewport_independent_text);
+        text_renderer.set_safe_area(viewport_config.safe_area);
+
+        // Lights share the configured logical coordinate space
+        lighting_renderer.viewport.logical_bounds = viewport_config.logical_bounds;
+
+        let idle_tracker = IdleTracker::new(config.idle.clone());
+        let metrics = Self::create_metrics(&config)?;
 
         Ok(Self {
-            is_running: false,
+            is_running: true,
             delta_time: Duration::ZERO,
             last_frame_time: Instant::now(),
             elapsed_time: 0.0,
-            window_manager,
+            animation_elapsed: 0.0,
             config,
-            renderer,
-            sprite_renderer,
-            text_renderer,
-            animation,
+            graphics: Some(Graphics {
+                window_manager,
+                renderer,
+                sprite_renderer,
+                text_renderer,
+                lighting_renderer,
+                viewport_rect,
+                idle_tracker,
+            }),
+            metrics,
+            event_system,
+            world,
+            scheduler: Scheduler::new(),
+            game,
         })
     }
 
     #[cfg(not(feature = "opengl"))]
-    pub fn new_with_config_and_animation(
+    pub fn new_with_config_and_game(
         config: EngineConfig,
-        animation: Box<dyn Animation>,
+        game: Box<dyn Game>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::new_headless(config, game)
+    }
+
+    /// Create an engine with no window or GPU, e.g. for tests and servers
+    ///
+    /// `tick` runs the systems and `Game::update` with no window, lighting or view in the
+    /// `UpdateContext`, and never renders. With the `opengl` feature, the window and
+    /// renderer getters panic on a headless engine; their `try_*` variants return None.
+    pub fn new_headless(
+        config: EngineConfig,
+        game: Box<dyn Game>,
     ) -> Result<Self, Box<dyn std::error::Error>> {
+        let metrics = Self::create_metrics(&config)?;
+        let event_system = EventSystem::new();
+        let mut world = World::new();
+        world.set_event_sender(Some(event_system.get_entity_sender()));
+        world.insert_resource(event_system.get_animation_sender());
+        world.insert_resource(AnimationTime::default());
         Ok(Self {
-            is_running: false,
+            is_running: true,
             delta_time: Duration::ZERO,
             last_frame_time: Instant::now(),
             elapsed_time: 0.0,
+            animation_elapsed: 0.0,
             config,
-            animation,
+            #[cfg(feature = "opengl")]
+            graphics: None,
+            metrics,
+            event_system,
+            world,
+            scheduler: Scheduler::new(),
+            game,
         })
     }
 
+    /// Check if the engine was created without a window
+    #[cfg(feature = "opengl")]
+    pub fn is_headless(&self) -> bool {
+        self.graphics.is_none()
+    }
+
+    #[cfg(not(feature = "opengl"))]
+    pub fn is_headless(&self) -> bool {
+        true
+    }
+
+    fn create_metrics(config: &EngineConfig) -> Result<Option<MetricsExporter>, String> {
+        if !config.metrics.enabled {
+            return Ok(None);
+        }
+        MetricsExporter::new(&config.metrics)
+            .map(Some)
+            .map_err(|e| format!("Failed to start metrics exporter: {}", e))
+    }
+
     // Getter methods for testing
+    /// # Panics
+    /// Panics on a headless engine; see `try_get_window_manager`.
     #[cfg(feature = "opengl")]
     pub fn get_window_manager(&self) -> &WindowManager {
-        &self.window_manager
+        self.try_get_window_manager().expect(HEADLESS)
+    }
+
+    /// Get the window manager, or None on a headless engine
+    #[cfg(feature = "opengl")]
+    pub fn try_get_window_manager(&self) -> Option<&WindowManager> {
+        self.graphics
+            .as_ref()
+            .map(|graphics| &graphics.window_manager)
     }
 
+    /// # Panics
+    /// Panics on a headless engine; see `try_get_window_manager_mut`.
     #[cfg(feature = "opengl")]
     pub fn get_window_manager_mut(&mut self) -> &mut WindowManager {
-        &mut self.window_manager
+        self.try_get_window_manager_mut().expect(HEADLESS)
+    }
+
+    #[cfg(feature = "opengl")]
+    pub fn try_get_window_manager_mut(&mut self) -> Option<&mut WindowManager> {
+        self.graphics
+            .as_mut()
+            .map(|graphics| &mut graphics.window_manager)
     }
 
     pub fn get_config(&self) -> &EngineConfig {
@@ -164,117 +330,338 @@ impl Engine {
     }
 
     /// Get access to the sprite renderer for creating sprites
+    ///
+    /// # Panics
+    /// Panics on a headless engine; see `try_get_sprite_renderer`.
     #[cfg(feature = "opengl")]
     pub fn get_sprite_renderer(&mut self) -> &mut SpriteRenderer {
-        &mut self.sprite_renderer
+        self.try_get_sprite_renderer().expect(HEADLESS)
+    }
+
+    /// Get the sprite renderer, or None on a headless engine
+    #[cfg(feature = "opengl")]
+    pub fn try_get_sprite_renderer(&mut self) -> Option<&mut SpriteRenderer> {
+        self.graphics
+            .as_mut()
+            .map(|graphics| &mut graphics.sprite_renderer)
+    }
+
+    /// Get access to the lighting renderer for configuring lights
+    ///
+    /// # Panics
+    /// Panics on a headless engine; see `try_get_lighting_renderer`.
+    #[cfg(feature = "opengl")]
+    pub fn get_lighting_renderer(&mut self) -> &mut LightingRenderer {
+        self.try_get_lighting_renderer().expect(HEADLESS)
+    }
+
+    /// Get the lighting renderer, or None on a headless engine
+    #[cfg(feature = "opengl")]
+    pub fn try_get_lighting_renderer(&mut self) -> Option<&mut LightingRenderer> {
+        self.graphics
+            .as_mut()
+            .map(|graphics| &mut graphics.lighting_renderer)
+    }
+
+    /// Framebuffer region the view is drawn to; smaller than the window when letterboxed
+    ///
+    /// # Panics
+    /// Panics on a headless engine; see `try_viewport_rect`.
+    #[cfg(feature = "opengl")]
+    pub fn viewport_rect(&self) -> ViewportRect {
+        self.try_viewport_rect().expect(HEADLESS)
+    }
+
+    /// Framebuffer region the view is drawn to, or None on a headless engine
+    #[cfg(feature = "opengl")]
+    pub fn try_viewport_rect(&self) -> Option<ViewportRect> {
+        self.graphics
+            .as_ref()
+            .map(|graphics| graphics.viewport_rect)
+    }
+
+    /// Where the view sits in the window, for mapping the cursor to logical coordinates
+    ///
+    /// # Panics
+    /// Panics on a headless engine; see `try_view_mapping`.
+    #[cfg(feature = "opengl")]
+    pub fn view_mapping(&self) -> ViewMapping {
+        self.try_view_mapping().expect(HEADLESS)
+    }
+
+    /// Where the view sits in the window, or None on a headless engine
+    #[cfg(feature = "opengl")]
+    pub fn try_view_mapping(&self) -> Option<ViewMapping> {
+        self.graphics
+            .as_ref()
+            .map(|graphics| graphics.view_mapping(self.config.viewport.logical_bounds))
+    }
+
+    /// Get access to the idle-mode tracker
+    ///
+    /// # Panics
+    /// Panics on a headless engine; see `try_get_idle_tracker`.
+    #[cfg(feature = "opengl")]
+    pub fn get_idle_tracker(&mut self) -> &mut IdleTracker {
+        self.try_get_idle_tracker().expect(HEADLESS)
+    }
+
+    /// Get the idle-mode tracker, or None on a headless engine
+    #[cfg(feature = "opengl")]
+    pub fn try_get_idle_tracker(&mut self) -> Option<&mut IdleTracker> {
+        self.graphics
+            .as_mut()
+            .map(|graphics| &mut graphics.idle_tracker)
     }
 
     #[cfg(feature = "opengl")]
     pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(graphics) = &self.graphics else {
+            return self.run_headless();
+        };
         println!("Starting engine...");
         println!(
             "Window: {} ({}x{})",
-            self.window_manager.get_title(),
-            self.window_manager.get_size().0,
-            self.window_manager.get_size().1
+            graphics.window_manager.get_title(),
+            graphics.window_manager.get_size().0,
+            graphics.window_manager.get_size().1
         );
         println!("Press 'Q' or 'ESC' to quit");
 
         // Renderer is already initialized in the constructor
 
         // Main game loop
-        while !self.window_manager.should_close() {
-            // Update timing
-            let current_time = Instant::now();
-            self.delta_time = current_time.duration_since(self.last_frame_time);
-            self.last_frame_time = current_time;
-
-            // Accumulate delta time for animations (total elapsed time since start)
-            self.elapsed_time += self.delta_time.as_secs_f32();
-
-            // Process window events
-            self.window_manager.poll_events();
-
-            // Handle keyboard input for quit and forward other events to animation
-            self.window_manager.process_events(|event| {
-                match event {
-                    super::window::WindowEvent::Glfw(glfw::WindowEvent::Key(
-                        Key::Escape,
-                        _,
-                        Action::Press,
-                        _,
-                    ))
-                    | super::window::WindowEvent::Glfw(glfw::WindowEvent::Key(
-                        Key::Q,
-                        _,
-                        Action::Press,
-                        _,
-                    )) => {
-                        false // Return false to close window
-                    }
-                    _ => {
-                        // Forward all other events to the animation
-                        self.animation.handle_event(event);
-                        true // Continue processing other events
-                    }
-                }
-            });
-
-            // Clear screen with dark background
-            if let Err(e) = self.renderer.clear(0.1, 0.1, 0.1, 1.0) {
-                eprintln!("Renderer clear error: {}", e);
-            }
-
-            // Update animation (animation is responsible for creating and rendering sprites and text)
-            self.animation.update(
-                Some(&mut self.sprite_renderer),
-                self.elapsed_time,
-                self.delta_time.as_secs_f32(),
-                Some(&mut self.window_manager),
-                Some(&mut self.text_renderer),
-            );
-
-            // Print success message once
-            static PRINTED: std::sync::Once = std::sync::Once::new();
-            PRINTED.call_once(|| {
-                println!("Successfully running animation: {}", self.animation.name());
-            });
-
-            // Swap buffers
-            self.window_manager.swap_buffers();
-        }
+        while self.tick(None) != TickResult::Exit {}
 
         println!("Engine shutting down...");
         Ok(())
     }
 
-    #[cfg(not(feature = "opengl"))]
-    pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("Starting headless engine...");
-        println!("Running animation: {}", self.animation.name());
+    /// Get the metrics exporter, if metrics are enabled (e.g. to report entity counts)
+    pub fn metrics_mut(&mut self) -> Option<&mut MetricsExporter> {
+        self.metrics.as_mut()
+    }
 
-        self.is_running = true;
+    /// Add the finished frame to the metrics, if enabled
+    fn record_metrics(&mut self, draw_calls: u32) {
+        if let Some(metrics) = &mut self.metrics
+            && let Err(e) = metrics.record_frame(self.delta_time.as_secs_f32(), draw_calls)
+        {
+            eprintln!("Metrics export error: {}", e);
+        }
+    }
 
-        // Simple headless game loop - just run the animation logic
-        let mut last_frame_time = Instant::now();
-        let mut frame_count = 0;
+    /// Advance the frame clock, using `dt_override` instead of wall time when given
+    fn advance_time(&mut self, dt_override: Option<Duration>) {
+        let current_time = Instant::now();
+        self.delta_time =
+            dt_override.unwrap_or_else(|| current_time.duration_since(self.last_frame_time));
+        self.last_frame_time = current_time;
+
+        // Accumulate delta time for animations (total elapsed time since start)
+        self.elapsed_time += self.delta_time.as_secs_f32();
+        self.animation_elapsed += self.animation_delta();
+    }
 
-        while self.is_running && frame_count < 1000 {
-            // Limit frames for headless mode
-            // Update timing for frame-independent animation
-            let current_time = Instant::now();
-            let delta_time = current_time.duration_since(last_frame_time);
-            last_frame_time = current_time;
+    /// This frame's time under the animation time scale
+    fn animation_delta(&self) -> f32 {
+        self.animation_time().delta(self.delta_time.as_secs_f32())
+    }
+
+    /// Run exactly one frame: events, update, render and present
+    ///
+    /// `dt_override` replaces the measured frame time, for fixed-step tests and embedders.
+    /// A headless engine only runs the systems and `Game::update`.
+    #[cfg(feature = "opengl")]
+    pub fn tick(&mut self, dt_override: Option<Duration>) -> TickResult {
+        // Taken out for the frame so the game and renderers can borrow it beside the engine
+        let Some(mut graphics) = self.graphics.take() else {
+            return self.tick_headless(dt_override);
+        };
+        let result = self.tick_graphics(&mut graphics, dt_override);
+        self.graphics = Some(graphics);
+        result
+    }
+
+    #[cfg(feature = "opengl")]
+    fn tick_graphics(
+        &mut self,
+        graphics: &mut Graphics,
+        dt_override: Option<Duration>,
+    ) -> TickResult {
+        if graphics.window_manager.should_close() {
+            return TickResult::Exit;
+        }
+
+        self.advance_time(dt_override);
+
+        // Process window events, blocking until input or the next idle frame while idle
+        match graphics.idle_tracker.wait_timeout() {
+            Some(timeout) => graphics.window_manager.wait_events_timeout(timeout),
+            None => graphics.window_manager.poll_events(),
+        }
+
+        // Recompile custom shaders edited on disk (no-op unless hot-reload is enabled)
+        graphics.sprite_renderer.shader_manager().poll_hot_reload();
+
+        // Upload glyphs from background font loads within the per-frame budget
+        graphics.text_renderer.process_pending_glyphs();
+
+        // Handle keyboard input for quit and forward other events to the game
+        let mut input_received = false;
+        graphics.window_manager.process_events(|event| {
+            if matches!(
+                event,
+                super::window::WindowEvent::Glfw(
+                    glfw::WindowEvent::Key(..)
+                        | glfw::WindowEvent::Char(..)
+                        | glfw::WindowEvent::MouseButton(..)
+                        | glfw::WindowEvent::Scroll(..)
+                )
+            ) {
+                input_received = true;
+            }
+
+            // The game sees each event first and can consume it before the quit shortcuts
+            let mut event = Consumable::new(event);
+            self.game.handle_consumable_event(&mut event);
+            !matches!(
+                event.unhandled(),
+                Some(super::window::WindowEvent::Glfw(glfw::WindowEvent::Key(
+                    Key::Escape | Key::Q,
+                    _,
+                    Action::Press,
+                    _,
+                )))
+            )
+        });
+
+        // Apply any framebuffer resize once, after all events for this frame
+        if let Some((old_size, new_size)) = graphics.window_manager.take_resize() {
+            self.handle_resize(graphics, old_size, new_size);
+        }
+
+        // Enter or leave idle mode based on focus; input wakes the engine instantly
+        if input_received {
+            graphics.idle_tracker.wake();
+        }
+        graphics.idle_tracker.update(
+            graphics.window_manager.is_focused(),
+            graphics.window_manager.is_minimized(),
+            self.delta_time.as_secs_f32(),
+        );
+        let should_render = graphics.idle_tracker.should_render();
+        let animation_delta = self.animation_delta();
+
+        // Clear screen with dark background
+        if should_render && let Err(e) = graphics.renderer.clear(0.1, 0.1, 0.1, 1.0) {
+            eprintln!("Renderer clear error: {}", e);
+        }
+
+        // Run ECS systems, then the game's simulation (which may also move lights)
+        if graphics.idle_tracker.should_update() {
+            let paused = self.is_paused();
+            self.run_systems(paused);
+            let view = graphics.view_mapping(self.config.viewport.logical_bounds);
+            let mut ctx = UpdateContext {
+                world: &mut self.world,
+                paused,
+                delta_time: animation_delta,
+                real_delta_time: self.delta_time.as_secs_f32(),
+                elapsed_time: self.animation_elapsed,
+                window_manager: Some(&mut graphics.window_manager),
+                lighting: Some(&mut graphics.lighting_renderer),
+                view: Some(view),
+            };
+            self.game.update(&mut ctx);
+        }
+        self.dispatch_events();
+
+        if !should_render {
+            self.record_metrics(graphics.renderer.take_draw_calls());
+            return TickResult::Skipped;
+        }
+
+        // Normal-mapped sprites are shaded with this frame's lights
+        graphics
+            .sprite_renderer
+            .set_lighting(&graphics.lighting_renderer);
+        graphics.sprite_renderer.set_time(self.elapsed_time);
+        let mut ctx = RenderContext {
+            world: &self.world,
+            sprite_renderer: &mut graphics.sprite_renderer,
+            text_renderer: &mut graphics.text_renderer,
+            window_manager: &mut graphics.window_manager,
+            delta_time: animation_delta,
+            real_delta_time: self.delta_time.as_secs_f32(),
+            elapsed_time: self.animation_elapsed,
+        };
+        self.game.render(&mut ctx);
+
+        // Draw entities with a Transform and SpriteComponent over the game's sprites
+        if let Err(e) = submit_sprites(&mut self.world, &graphics.sprite_renderer) {
+            eprintln!("Entity sprite render error: {}", e);
+        }
+
+        // Composite lighting over the sprite pass
+        if let Err(e) = graphics.lighting_renderer.render() {
+            eprintln!("Lighting render error: {}", e);
+        }
+
+        // Print success message once
+        static PRINTED: std::sync::Once = std::sync::Once::new();
+        PRINTED.call_once(|| {
+            println!("Successfully running game: {}", self.game.name());
+        });
+
+        // Swap buffers
+        graphics.window_manager.swap_buffers();
+        self.record_metrics(graphics.renderer.take_draw_calls());
+
+        if graphics.window_manager.should_close() {
+            TickResult::Exit
+        } else {
+            TickResult::Rendered
+        }
+    }
+
+    /// Propagate a framebuffer resize to every subsystem, then emit `ResolutionChanged`
+    #[cfg(feature = "opengl")]
+    fn handle_resize(
+        &mut self,
+        graphics: &mut Graphics,
+        old_size: (u32, u32),
+        new_size: (u32, u32),
+    ) {
+        graphics.viewport_rect = self.config.viewport.scaling.viewport_rect(
+            (self.config.window_width, self.config.window_height),
+            new_size,
+        );
+        if let Err(e) = graphics.renderer.set_viewport_rect(graphics.viewport_rect) {
+            eprintln!("Failed to resize viewport: {}", e);
+        }
+        self.game.on_resize(old_size, new_size);
+        graphics
+            .window_manager
+            .emit_resolution_changed(old_size, new_size);
+        println!(
+            "Resolution changed from {}x{} to {}x{}",
+            old_size.0, old_size.1, new_size.0, new_size.1
+        );
+    }
 
-            // Accumulate delta time for animations (total elapsed time since start)
-            self.elapsed_time += delta_time.as_secs_f32();
+    #[cfg(not(feature = "opengl"))]
+    pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.run_headless()
+    }
 
-            // Update animation (headless mode - no rendering)
-            // Note: In headless mode, animations can still process game logic
-            // but won't render anything
-            self.animation
-                .update(self.elapsed_time, delta_time.as_secs_f32());
+    fn run_headless(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        println!("Starting headless engine...");
+        println!("Running game: {}", self.game.name());
 
+        // Simple headless game loop - limit frames for headless mode
+        let mut frame_count = 0;
+        while frame_count < 1000 && self.tick(None) != TickResult::Exit {
             frame_count += 1;
 
             // Small delay to prevent busy waiting
@@ -285,10 +672,128 @@ impl Engine {
         Ok(())
     }
 
+    /// Run exactly one frame of game logic (headless mode - no rendering)
+    ///
+    /// `dt_override` replaces the measured frame time, for fixed-step tests and embedders.
+    #[cfg(not(feature = "opengl"))]
+    pub fn tick(&mut self, dt_override: Option<Duration>) -> TickResult {
+        self.tick_headless(dt_override)
+    }
+
+    /// Run the systems, then the game's update, without a window
+    fn tick_headless(&mut self, dt_override: Option<Duration>) -> TickResult {
+        if !self.is_running {
+            return TickResult::Exit;
+        }
+
+        self.advance_time(dt_override);
+
+        // Systems and the game can still process game logic but won't render anything
+        let paused = self.is_paused();
+        self.run_systems(paused);
+        let animation_delta = self.animation_delta();
+        let mut ctx = UpdateContext {
+            world: &mut self.world,
+            paused,
+            delta_time: animation_delta,
+            real_delta_time: self.delta_time.as_secs_f32(),
+            elapsed_time: self.animation_elapsed,
+            #[cfg(feature = "opengl")]
+            window_manager: None,
+            #[cfg(feature = "opengl")]
+            lighting: None,
+            #[cfg(feature = "opengl")]
+            view: None,
+        };
+        self.game.update(&mut ctx);
+        self.dispatch_events();
+        self.record_metrics(0);
+
+        TickResult::Skipped
+    }
+
+    /// Run the scheduled system stages on the world; only input and render submission
+    /// while the game is paused
+    fn run_systems(&mut self, paused: bool) {
+        let delta_time = self.delta_time.as_secs_f32();
+        let stages: &[Stage] = if paused {
+            &[Stage::Input, Stage::RenderSubmit]
+        } else {
+            &Stage::ALL
+        };
+        if let Err(e) = self
+            .scheduler
+            .run_stages(stages, &mut self.world, delta_time)
+        {
+            eprintln!("System scheduling error: {}", e);
+        }
+    }
+
+    /// Check if a modal on the world's `InputManager` pauses the game
+    ///
+    /// While paused, the `Update` and `Physics` stages are skipped. `Game::update` still
+    /// runs with `UpdateContext::paused` set, so the game can drive and close its menu.
+    pub fn is_paused(&self) -> bool {
+        self.world
+            .resource::<InputManager>()
+            .is_some_and(InputManager::is_paused)
+    }
+
+    /// Hand the frame's world and animation events to the event system's listeners
+    fn dispatch_events(&self) {
+        self.event_system.dispatch_entity_events();
+        self.event_system.dispatch_animation_events();
+    }
+
+    /// Get the event system, e.g. to add listeners for world changes and animation markers
+    pub fn event_system(&self) -> &EventSystem {
+        &self.event_system
+    }
+
+    /// Get the ECS world
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Get the ECS world for spawning and editing entities
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Get the system scheduler, run automatically every tick
+    pub fn scheduler_mut(&mut self) -> &mut Scheduler {
+        &mut self.scheduler
+    }
+
+    /// Total time accumulated across ticks, in seconds
+    pub fn elapsed_time(&self) -> f32 {
+        self.elapsed_time
+    }
+
+    /// Time accumulated by the animation, which slows and stops with `AnimationTime`
+    pub fn animation_elapsed_time(&self) -> f32 {
+        self.animation_elapsed
+    }
+
+    /// Global animation speed and pause state
+    pub fn animation_time(&self) -> AnimationTime {
+        self.world
+            .resource::<AnimationTime>()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Change the global animation speed, e.g. for slow motion or a pause menu
+    pub fn animation_time_mut(&mut self) -> &mut AnimationTime {
+        self.world.resource_or_default::<AnimationTime>()
+    }
+
     #[cfg(feature = "opengl")]
     pub fn quit(&mut self) {
         self.is_running = false;
-        self.window_manager.request_close();
+        if let Some(graphics) = &mut self.graphics {
+            graphics.window_manager.request_close();
+        }
     }
 
     #[cfg(not(feature = "opengl"))]
@@ -297,15 +802,36 @@ impl Engine {
     }
 
     /// Get a reference to the text renderer
+    ///
+    /// # Panics
+    /// Panics on a headless engine; see `try_text_renderer`.
     #[cfg(feature = "opengl")]
     pub fn text_renderer(&self) -> &SimpleTextRenderer {
-        &self.text_renderer
+        self.try_text_renderer().expect(HEADLESS)
+    }
+
+    /// Get the text renderer, or None on a headless engine
+    #[cfg(feature = "opengl")]
+    pub fn try_text_renderer(&self) -> Option<&SimpleTextRenderer> {
+        self.graphics
+            .as_ref()
+            .map(|graphics| &graphics.text_renderer)
     }
 
     /// Get a mutable reference to the text renderer
+    ///
+    /// # Panics
+    /// Panics on a headless engine; see `try_text_renderer_mut`.
     #[cfg(feature = "opengl")]
     pub fn text_renderer_mut(&mut self) -> &mut SimpleTextRenderer {
-        &mut self.text_renderer
+        self.try_text_renderer_mut().expect(HEADLESS)
+    }
+
+    #[cfg(feature = "opengl")]
+    pub fn try_text_renderer_mut(&mut self) -> Option<&mut SimpleTextRenderer> {
+        self.graphics
+            .as_mut()
+            .map(|graphics| &mut graphics.text_renderer)
     }
 }
 
@@ -0,0 +1,89 @@
+/// What the pointer is doing, so a themed cursor can be picked for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CursorStyle {
+    /// The normal pointer
+    #[default]
+    Default,
+    /// Over something clickable
+    Hover,
+    /// Over something that can be dragged
+    Grab,
+    /// Dragging something
+    Grabbing,
+    /// Over editable text
+    Text,
+    /// Aiming
+    Crosshair,
+}
+
+impl CursorStyle {
+    pub const ALL: [CursorStyle; 6] = [
+        CursorStyle::Default,
+        CursorStyle::Hover,
+        CursorStyle::Grab,
+        CursorStyle::Grabbing,
+        CursorStyle::Text,
+        CursorStyle::Crosshair,
+    ];
+}
+
+/// RGBA pixels for a hardware cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorImage {
+    pub width: u32,
+    pub height: u32,
+    /// Rows from the top, 4 bytes per pixel, not premultiplied
+    pub pixels: Vec<u8>,
+}
+
+impl CursorImage {
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err(format!("Cursor image is empty ({}x{})", width, height));
+        }
+        let expected = width as usize * height as usize * 4;
+        if pixels.len() != expected {
+            return Err(format!(
+                "Cursor image is {}x{} but has {} bytes (expected {})",
+                width,
+                height,
+                pixels.len(),
+                expected
+            ));
+        }
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Load a cursor image from a file (PNG, etc)
+    #[cfg(feature = "opengl")]
+    pub fn load(path: &str) -> Result<Self, String> {
+        let image = image::open(std::path::Path::new(path))
+            .map_err(|e| format!("Failed to load cursor image '{}': {}", path, e))?
+            .to_rgba8();
+        let (width, height) = image.dimensions();
+        Self::new(width, height, image.into_raw())
+    }
+
+    /// Check a hotspot, the pixel from the top-left that sits on the pointer position
+    pub fn check_hotspot(&self, hotspot: (u32, u32)) -> Result<(), String> {
+        if hotspot.0 >= self.width || hotspot.1 >= self.height {
+            return Err(format!(
+                "Cursor hotspot ({}, {}) is outside the {}x{} image",
+                hotspot.0, hotspot.1, self.width, self.height
+            ));
+        }
+        Ok(())
+    }
+
+    /// Pixels packed one per `u32` with the bytes in RGBA memory order, as GLFW takes them
+    pub fn packed_pixels(&self) -> Vec<u32> {
+        self.pixels
+            .chunks_exact(4)
+            .map(|rgba| u32::from_ne_bytes([rgba[0], rgba[1], rgba[2], rgba[3]]))
+            .collect()
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(feature = "opengl")]
+use super::window::{WindowEvent, WindowManager};
+use crate::animation::Animation;
+use crate::ecs::World;
+#[cfg(feature = "opengl")]
+use crate::events::Consumable;
+#[cfg(feature = "opengl")]
+use crate::render::lighting::LightingRenderer;
+#[cfg(feature = "opengl")]
+use crate::render::simple_text::SimpleTextRenderer;
+#[cfg(feature = "opengl")]
+use crate::render::sprite::SpriteRenderer;
+#[cfg(feature = "opengl")]
+use crate::render::viewport::ViewMapping;
+
+/// What a game sees while simulating a frame
+pub struct UpdateContext<'a> {
+    pub world: &'a mut World,
+    /// A modal on the world's `InputManager` pauses the game; the `Update` and `Physics`
+    /// stages were skipped, and gameplay should wait while the menu runs
+    pub paused: bool,
+    /// Seconds since the last frame, scaled by the engine's `AnimationTime`; zero while
+    /// animations are paused
+    pub delta_time: f32,
+    /// Seconds since the last frame, unaffected by `AnimationTime`; use this for gameplay
+    /// that keeps running when animations are slowed or paused
+    pub real_delta_time: f32,
+    /// Seconds since the game started, under the engine's `AnimationTime`
+    pub elapsed_time: f32,
+    /// The window; None on a headless engine
+    #[cfg(feature = "opengl")]
+    pub window_manager: Option<&'a mut WindowManager>,
+    /// Lights and occluders, composited after the frame is drawn; None on a headless engine
+    #[cfg(feature = "opengl")]
+    pub lighting: Option<&'a mut LightingRenderer>,
+    /// Where the view sits in the window, for `InputManager::set_view_mapping`; None on a
+    /// headless engine
+    #[cfg(feature = "opengl")]
+    pub view: Option<ViewMapping>,
+}
+
+/// What a game sees while drawing a frame
+#[cfg(feature = "opengl")]
+pub struct RenderContext<'a> {
+    pub world: &'a World,
+    pub sprite_renderer: &'a mut SpriteRenderer,
+    pub text_renderer: &'a mut SimpleTextRenderer,
+    pub window_manager: &'a mut WindowManager,
+    /// Seconds since the last frame, scaled by the engine's `AnimationTime`
+    pub delta_time: f32,
+    /// Seconds since the last frame, unaffected by `AnimationTime`
+    pub real_delta_time: f32,
+    /// Seconds since the game started, under the engine's `AnimationTime`
+    pub elapsed_time: f32,
+}
+
+/// Game logic driven by the engine, with simulation and drawing kept apart
+///
+/// Each frame the engine runs the ECS systems, then `update`, then `render` (only when a
+/// frame is presented), then draws entity sprites and lighting over what `render` drew.
+/// While idle `update` may be skipped and `render` throttled; see `IdleConfig`.
+/// Gameplay that should keep going while animations are paused steps with
+/// `real_delta_time`; `delta_time` follows the engine's `AnimationTime`.
+///
+/// ```ignore
+/// impl Game for Asteroids {
+///     fn update(&mut self, ctx: &mut UpdateContext) {
+///         self.ship.steer(ctx.real_delta_time);
+///     }
+///     fn render(&mut self, ctx: &mut RenderContext) {
+///         let _ = ctx.sprite_renderer.render_sprite(&self.ship.sprite);
+///     }
+///     fn name(&self) -> &str {
+///         "Asteroids"
+///     }
+/// }
+/// ```
+pub trait Game {
+    /// Advance the simulation by one frame
+    fn update(&mut self, ctx: &mut UpdateContext<'_>);
+
+    /// Draw the frame; not called in headless mode
+    #[cfg(feature = "opengl")]
+    fn render(&mut self, _ctx: &mut RenderContext<'_>) {}
+
+    /// Handle a window event (keyboard, mouse, etc.) before the frame's update
+    #[cfg(feature = "opengl")]
+    fn handle_event(&mut self, _event: &WindowEvent) {}
+
+    /// Handle a window event before the engine's own shortcuts (Escape and Q quit);
+    /// consume it to keep them from reacting, e.g. while a text field has focus
+    #[cfg(feature = "opengl")]
+    fn handle_consumable_event(&mut self, event: &mut Consumable<&WindowEvent>) {
+        self.handle_event(event.event());
+    }
+
+    /// Handle a framebuffer resize (called once per frame after the viewport is updated)
+    fn on_resize(&mut self, _old_size: (u32, u32), _new_size: (u32, u32)) {}
+
+    /// Get the name of the game (for debugging/logging purposes)
+    fn name(&self) -> &str;
+}
+
+/// Runs an `Animation` written against the single-phase trait as a `Game`
+///
+/// Lighting is updated in the update phase. The animation's own `update`, which both
+/// simulates and draws, runs in the render phase of frames that were updated, with the
+/// time of every update since the last render (idle frames can update without
+/// rendering); headless, it runs in the update phase.
+pub struct LegacyAnimation {
+    animation: Box<dyn Animation>,
+    /// The last update hasn't been followed by a render yet
+    #[cfg(feature = "opengl")]
+    updated: bool,
+    /// Delta time of the updates since the last render
+    #[cfg(feature = "opengl")]
+    pending_delta: f32,
+}
+
+impl LegacyAnimation {
+    pub fn new(animation: Box<dyn Animation>) -> Self {
+        Self {
+            animation,
+            #[cfg(feature = "opengl")]
+            updated: false,
+            #[cfg(feature = "opengl")]
+            pending_delta: 0.0,
+        }
+    }
+
+    pub fn animation(&self) -> &dyn Animation {
+        self.animation.as_ref()
+    }
+
+    pub fn animation_mut(&mut self) -> &mut dyn Animation {
+        self.animation.as_mut()
+    }
+}
+
+impl Game for LegacyAnimation {
+    #[cfg(feature = "opengl")]
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        match ctx.lighting.as_deref_mut() {
+            Some(lighting) => {
+                self.animation.update_lighting(lighting);
+                self.updated = true;
+                self.pending_delta += ctx.delta_time;
+            }
+            // Headless, nothing renders, so the animation runs here without renderers
+            None => self
+                .animation
+                .update(None, ctx.elapsed_time, ctx.delta_time, None, None),
+        }
+    }
+
+    #[cfg(not(feature = "opengl"))]
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        self.animation.update(ctx.elapsed_time, ctx.delta_time);
+    }
+
+    #[cfg(feature = "opengl")]
+    fn render(&mut self, ctx: &mut RenderContext<'_>) {
+        if !std::mem::take(&mut self.updated) {
+            return;
+        }
+        self.animation.update(
+            Some(&mut *ctx.sprite_renderer),
+            ctx.elapsed_time,
+            std::mem::take(&mut self.pending_delta),
+            Some(&mut *ctx.window_manager),
+            Some(&mut *ctx.text_renderer),
+        );
+    }
+
+    #[cfg(feature = "opengl")]
+    fn handle_event(&mut self, event: &WindowEvent) {
+        self.animation.handle_event(event);
+    }
+
+    #[cfg(feature = "opengl")]
+    fn on_resize(&mut self, old_size: (u32, u32), new_size: (u32, u32)) {
+        self.animation.on_resize(old_size, new_size);
+    }
+
+    fn name(&self) -> &str {
+        self.animation.name()
+    }
+}
@@ -0,0 +1,85 @@
+use super::config::IdleConfig;
+use std::time::Duration;
+
+/// Tracks window focus to decide when the engine should drop into idle mode
+#[derive(Debug, Clone)]
+pub struct IdleTracker {
+    config: IdleConfig,
+    unfocused_for: f32,
+    minimized: bool,
+    idle: bool,
+}
+
+impl IdleTracker {
+    /// Create a new idle tracker
+    pub fn new(config: IdleConfig) -> Self {
+        Self {
+            config,
+            unfocused_for: 0.0,
+            minimized: false,
+            idle: false,
+        }
+    }
+
+    /// Update with the current window state; returns whether the engine is idle
+    pub fn update(&mut self, focused: bool, minimized: bool, delta_time: f32) -> bool {
+        self.minimized = minimized;
+        if focused && !minimized {
+            self.unfocused_for = 0.0;
+        } else {
+            self.unfocused_for += delta_time;
+        }
+
+        self.idle =
+            self.config.enabled && (minimized || self.unfocused_for >= self.config.idle_after_secs);
+        self.idle
+    }
+
+    /// Leave idle mode immediately (e.g. on user input); focus loss restarts the countdown
+    pub fn wake(&mut self) {
+        self.unfocused_for = 0.0;
+        self.idle = false;
+    }
+
+    /// Check if the engine is currently idle
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Whether the animation should be updated this frame
+    pub fn should_update(&self) -> bool {
+        !self.idle || self.config.update_while_idle
+    }
+
+    /// Whether a frame should be presented this frame
+    ///
+    /// Frames that skip the update aren't presented either, so the window keeps showing
+    /// the last image instead of a freshly cleared one.
+    pub fn should_render(&self) -> bool {
+        self.should_update() && (!self.idle || (!self.minimized && self.config.idle_fps > 0))
+    }
+
+    /// How long to wait for events between idle frames (None when not idle)
+    pub fn wait_timeout(&self) -> Option<Duration> {
+        if !self.idle {
+            return None;
+        }
+        // With rendering suspended, wake periodically to keep updates and close checks running
+        let fps = if self.should_render() {
+            self.config.idle_fps
+        } else {
+            1
+        };
+        Some(Duration::from_secs_f32(1.0 / fps as f32))
+    }
+
+    /// Get the idle configuration
+    pub fn config(&self) -> &IdleConfig {
+        &self.config
+    }
+
+    /// Replace the idle configuration
+    pub fn set_config(&mut self, config: IdleConfig) {
+        self.config = config;
+    }
+}
@@ -0,0 +1,281 @@
+use super::config::{MetricsConfig, MetricsOutput};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How often the Prometheus endpoint checks for connections and shutdown
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Engine statistics summarized over one export interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Seconds since the exporter started, at the end of the interval
+    pub uptime_secs: f64,
+    pub frames: u32,
+    pub fps: f32,
+    pub frame_time_avg_ms: f32,
+    pub frame_time_max_ms: f32,
+    /// Frame time that 99% of the interval's frames stayed under
+    pub frame_time_p99_ms: f32,
+    /// Average draw calls per frame
+    pub draw_calls: f32,
+    /// Entity count last reported by the game
+    pub entities: usize,
+    /// Resident memory in bytes, where the platform reports it
+    pub memory_bytes: Option<u64>,
+}
+
+impl MetricsSnapshot {
+    /// Column names matching `to_csv_row`
+    pub const CSV_HEADER: &'static str = "uptime_secs,frames,fps,frame_time_avg_ms,\
+        frame_time_max_ms,frame_time_p99_ms,draw_calls,entities,memory_bytes";
+
+    /// One CSV row; memory is left empty where unknown
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{:.3},{},{:.2},{:.3},{:.3},{:.3},{:.1},{},{}",
+            self.uptime_secs,
+            self.frames,
+            self.fps,
+            self.frame_time_avg_ms,
+            self.frame_time_max_ms,
+            self.frame_time_p99_ms,
+            self.draw_calls,
+            self.entities,
+            self.memory_bytes
+                .map_or(String::new(), |bytes| bytes.to_string()),
+        )
+    }
+
+    /// The snapshot as gauges in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut gauges = vec![
+            (
+                "engine_uptime_seconds",
+                "Seconds since metrics started",
+                self.uptime_secs,
+            ),
+            ("engine_fps", "Frames per second", f64::from(self.fps)),
+            (
+                "engine_frame_time_avg_ms",
+                "Average frame time in milliseconds",
+                f64::from(self.frame_time_avg_ms),
+            ),
+            (
+                "engine_frame_time_max_ms",
+                "Longest frame time in milliseconds",
+                f64::from(self.frame_time_max_ms),
+            ),
+            (
+                "engine_frame_time_p99_ms",
+                "99th percentile frame time in milliseconds",
+                f64::from(self.frame_time_p99_ms),
+            ),
+            (
+                "engine_draw_calls",
+                "Average draw calls per frame",
+                f64::from(self.draw_calls),
+            ),
+            ("engine_entities", "Live entities", self.entities as f64),
+        ];
+        if let Some(bytes) = self.memory_bytes {
+            gauges.push((
+                "engine_memory_bytes",
+                "Resident memory in bytes",
+                bytes as f64,
+            ));
+        }
+
+        gauges
+            .into_iter()
+            .map(|(name, help, value)| {
+                format!("# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}\n")
+            })
+            .collect()
+    }
+}
+
+enum Sink {
+    Csv(File),
+    /// Latest exposition text, read by the server thread, and its shutdown flag
+    Prometheus(Arc<Mutex<String>>, Arc<AtomicBool>),
+}
+
+/// Summarizes frames over fixed intervals and writes them to CSV or a Prometheus endpoint
+///
+/// The engine records every frame when `EngineConfig::metrics` is enabled; games report
+/// their entity count with `set_entity_count`.
+pub struct MetricsExporter {
+    interval_secs: f32,
+    sink: Sink,
+    /// Frame times in seconds since the last export, and their sum
+    frame_times: Vec<f32>,
+    interval_elapsed: f32,
+    draw_calls: u64,
+    entities: usize,
+    uptime_secs: f64,
+    latest: Option<MetricsSnapshot>,
+}
+
+impl MetricsExporter {
+    /// Open the CSV file or start the HTTP endpoint named by the config
+    pub fn new(config: &MetricsConfig) -> Result<Self, String> {
+        let sink = match &config.output {
+            MetricsOutput::Csv { path } => {
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| format!("Failed to open metrics file '{}': {}", path, e))?;
+                let is_new = file.metadata().map(|meta| meta.len() == 0).unwrap_or(true);
+                if is_new {
+                    writeln!(file, "{}", MetricsSnapshot::CSV_HEADER)
+                        .map_err(|e| format!("Failed to write metrics file '{}': {}", path, e))?;
+                }
+                Sink::Csv(file)
+            }
+            MetricsOutput::Prometheus { address } => {
+                let listener = TcpListener::bind(address)
+                    .map_err(|e| format!("Failed to bind metrics endpoint '{}': {}", address, e))?;
+                listener
+                    .set_nonblocking(true)
+                    .map_err(|e| format!("Failed to configure metrics endpoint: {}", e))?;
+                let body = Arc::new(Mutex::new(String::new()));
+                let shutdown = Arc::new(AtomicBool::new(false));
+                serve_prometheus(listener, Arc::clone(&body), Arc::clone(&shutdown));
+                Sink::Prometheus(body, shutdown)
+            }
+        };
+
+        Ok(Self {
+            interval_secs: config.interval_secs.max(f32::EPSILON),
+            sink,
+            frame_times: Vec::new(),
+            interval_elapsed: 0.0,
+            draw_calls: 0,
+            entities: 0,
+            uptime_secs: 0.0,
+            latest: None,
+        })
+    }
+
+    /// Record a finished frame, exporting once a full interval has been recorded
+    pub fn record_frame(&mut self, frame_time: f32, draw_calls: u32) -> Result<(), String> {
+        self.frame_times.push(frame_time);
+        self.interval_elapsed += frame_time;
+        self.draw_calls += u64::from(draw_calls);
+        self.uptime_secs += f64::from(frame_time);
+
+        if self.interval_elapsed >= self.interval_secs {
+            self.export()?;
+        }
+        Ok(())
+    }
+
+    /// Report the number of live entities, included in the following exports
+    pub fn set_entity_count(&mut self, count: usize) {
+        self.entities = count;
+    }
+
+    /// Export the frames recorded so far without waiting for the interval to end
+    pub fn export(&mut self) -> Result<(), String> {
+        if self.frame_times.is_empty() {
+            return Ok(());
+        }
+
+        let snapshot = self.summarize();
+        self.frame_times.clear();
+        self.interval_elapsed = 0.0;
+        self.draw_calls = 0;
+
+        match &mut self.sink {
+            Sink::Csv(file) => writeln!(file, "{}", snapshot.to_csv_row())
+                .and_then(|_| file.flush())
+                .map_err(|e| format!("Failed to write metrics: {}", e))?,
+            Sink::Prometheus(body, _) => {
+                *body.lock().map_err(|_| "Metrics endpoint poisoned")? = snapshot.to_prometheus()
+            }
+        }
+        self.latest = Some(snapshot);
+        Ok(())
+    }
+
+    /// The most recently exported snapshot
+    pub fn latest(&self) -> Option<&MetricsSnapshot> {
+        self.latest.as_ref()
+    }
+
+    fn summarize(&self) -> MetricsSnapshot {
+        let frames = self.frame_times.len();
+        let total = self.interval_elapsed;
+        let mut sorted = self.frame_times.clone();
+        sorted.sort_by(f32::total_cmp);
+        let p99_index = ((frames as f32 * 0.99).ceil() as usize).clamp(1, frames) - 1;
+
+        MetricsSnapshot {
+            uptime_secs: self.uptime_secs,
+            frames: frames as u32,
+            fps: if total > 0.0 {
+                frames as f32 / total
+            } else {
+                0.0
+            },
+            frame_time_avg_ms: total / frames as f32 * 1000.0,
+            frame_time_max_ms: sorted[frames - 1] * 1000.0,
+            frame_time_p99_ms: sorted[p99_index] * 1000.0,
+            draw_calls: self.draw_calls as f32 / frames as f32,
+            entities: self.entities,
+            memory_bytes: resident_memory_bytes(),
+        }
+    }
+}
+
+impl Drop for MetricsExporter {
+    fn drop(&mut self) {
+        if let Sink::Prometheus(_, shutdown) = &self.sink {
+            shutdown.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Answer every HTTP request with the latest exposition text until shut down
+fn serve_prometheus(listener: TcpListener, body: Arc<Mutex<String>>, shutdown: Arc<AtomicBool>) {
+    thread::spawn(move || {
+        while !shutdown.load(Ordering::Relaxed) {
+            let Ok((mut stream, _)) = listener.accept() else {
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+                continue;
+            };
+            // Any path is served; the request itself only needs draining
+            let _ = stream.set_nonblocking(false);
+            let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+            let _ = stream.read(&mut [0; 1024]);
+            let body = body.lock().map(|body| body.clone()).unwrap_or_default();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+}
+
+/// Resident set size of this process
+#[cfg(target_os = "linux")]
+fn resident_memory_bytes() -> Option<u64> {
+    // statm reports pages; 4 KiB pages are the norm on Linux
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * 4096)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_memory_bytes() -> Option<u64> {
+    None
+}
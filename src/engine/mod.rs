@@ -1,10 +1,24 @@
 pub mod config;
 pub mod core;
+pub mod cursor;
+pub mod game;
+pub mod idle;
+pub mod metrics;
+pub mod streaming;
+pub mod testing;
 #[cfg(feature = "opengl")]
 pub mod window;
 
-pub use config::{EngineConfig, ViewportConfig};
-pub use core::Engine;
+pub use config::{EngineConfig, IdleConfig, MetricsConfig, MetricsOutput, ViewportConfig};
+pub use core::{Engine, TickResult};
+pub use cursor::{CursorImage, CursorStyle};
+#[cfg(feature = "opengl")]
+pub use game::RenderContext;
+pub use game::{Game, LegacyAnimation, UpdateContext};
+pub use idle::IdleTracker;
+pub use metrics::{MetricsExporter, MetricsSnapshot};
+pub use streaming::{StreamingEvent, WorldStreamer};
+pub use testing::{TestEngine, TestEvents};
 
 #[cfg(test)]
 mod tests {
@@ -38,6 +52,9 @@ mod tests {
             fullscreen: true,
             viewport: ViewportConfig::ndc(), // Use NDC coordinates
             fallback_font_path: "assets/fonts/default.ttf".to_string(),
+            idle: IdleConfig::default(),
+            metrics: MetricsConfig::default(),
+            srgb: false,
         };
 
         assert_eq!(config.window_title, "Test Game");
@@ -152,4 +169,62 @@ mod tests {
         assert_eq!(viewport.text_height_fraction, 0.02);
         assert_eq!(viewport.viewport_independent_text, true);
     }
+
+    #[test]
+    fn test_idle_tracker_disabled_by_default() {
+        let mut tracker = IdleTracker::new(IdleConfig::default());
+        assert!(!tracker.update(false, true, 10.0));
+        assert!(tracker.should_render());
+        assert_eq!(tracker.wait_timeout(), None);
+    }
+
+    #[test]
+    fn test_idle_tracker_enters_after_unfocused_delay() {
+        let config = IdleConfig {
+            enabled: true,
+            idle_after_secs: 1.0,
+            ..Default::default()
+        };
+        let mut tracker = IdleTracker::new(config);
+        assert!(!tracker.update(false, false, 0.6));
+        assert!(tracker.update(false, false, 0.6));
+        assert!(tracker.should_render());
+        assert!(tracker.wait_timeout().is_some());
+
+        // Regaining focus leaves idle mode immediately
+        assert!(!tracker.update(true, false, 0.1));
+    }
+
+    #[test]
+    fn test_idle_tracker_minimized_suspends_rendering() {
+        let config = IdleConfig {
+            enabled: true,
+            update_while_idle: false,
+            ..Default::default()
+        };
+        let mut tracker = IdleTracker::new(config);
+        assert!(tracker.update(false, true, 0.0));
+        assert!(!tracker.should_render());
+        assert!(!tracker.should_update());
+
+        tracker.wake();
+        assert!(!tracker.is_idle());
+        assert!(tracker.should_render());
+    }
+
+    #[test]
+    fn test_idle_tracker_keeps_the_last_frame_without_updates() {
+        let config = IdleConfig {
+            enabled: true,
+            idle_after_secs: 0.5,
+            idle_fps: 4,
+            update_while_idle: false,
+        };
+        let mut tracker = IdleTracker::new(config);
+        assert!(tracker.update(false, false, 1.0));
+        assert!(!tracker.should_update());
+        // Presenting would clear the screen without drawing anything new
+        assert!(!tracker.should_render());
+        assert!(tracker.wait_timeout().is_some());
+    }
 }
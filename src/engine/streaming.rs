@@ -0,0 +1,230 @@
+use crate::utils::math::geometry::Rectangle;
+use glam::{IVec2, Vec2};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Builds a section's contents (tiles, entity spawns, ...) on a worker thread
+pub type SectionLoader<T> = dyn Fn(IVec2) -> Result<T, String> + Send + Sync;
+
+/// Worker threads per streamer; loaders usually wait on disk, so a few are enough
+const LOADER_THREADS: usize = 2;
+
+/// Change in the set of loaded sections, reported by `WorldStreamer::update`
+#[derive(Debug)]
+pub enum StreamingEvent<T> {
+    /// The section finished loading and its data is available
+    Activated(IVec2),
+    /// The section left the unload radius; its data is handed back for cleanup
+    Deactivated(IVec2, T),
+    /// The loader returned an error; the section is retried once it comes back in range
+    LoadFailed(IVec2, String),
+}
+
+/// Streams a world split into a grid of sections in and out around the camera
+///
+/// Sections within `load_radius` of the camera start loading in the background;
+/// loaded sections only unload beyond `unload_radius`, so a camera hovering near a
+/// section border doesn't load and unload it every frame. Distances are measured from
+/// the camera to the nearest edge of the section. Loads run on the streamer's own small
+/// thread pool, so slow loaders queue up there instead of blocking the rayon pool.
+///
+/// ```
+/// # use engine_2d::engine::{StreamingEvent, WorldStreamer};
+/// # use glam::{IVec2, Vec2};
+/// # let camera = Vec2::new(50.0, 50.0);
+/// let mut streamer = WorldStreamer::new(Vec2::splat(100.0), 20.0, 60.0, |coord: IVec2| {
+///     Ok(format!("level/section_{}_{}.tmx", coord.x, coord.y))
+/// });
+/// // each frame
+/// for event in streamer.update(camera) {
+///     match event {
+///         StreamingEvent::Activated(coord) => println!("spawn {}", streamer.section(coord).unwrap()),
+///         StreamingEvent::Deactivated(coord, data) => println!("despawn {:?}: {}", coord, data),
+///         StreamingEvent::LoadFailed(coord, error) => eprintln!("{:?}: {}", coord, error),
+///     }
+/// }
+/// ```
+pub struct WorldStreamer<T: Send + 'static> {
+    section_size: Vec2,
+    load_radius: f32,
+    unload_radius: f32,
+    loader: Arc<SectionLoader<T>>,
+    workers: rayon::ThreadPool,
+    sender: Sender<(IVec2, u64, Result<T, String>)>,
+    receiver: Receiver<(IVec2, u64, Result<T, String>)>,
+    /// Sections being loaded, with the request id their result must carry
+    loading: HashMap<IVec2, u64>,
+    active: HashMap<IVec2, T>,
+    /// Sections whose load failed, skipped until they leave the unload radius
+    failed: Vec<IVec2>,
+    next_request: u64,
+}
+
+impl<T: Send + 'static> WorldStreamer<T> {
+    /// Create a streamer for sections of `section_size` world units
+    ///
+    /// `unload_radius` is raised to `load_radius` if smaller.
+    pub fn new(
+        section_size: Vec2,
+        load_radius: f32,
+        unload_radius: f32,
+        loader: impl Fn(IVec2) -> Result<T, String> + Send + Sync + 'static,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let workers = rayon::ThreadPoolBuilder::new()
+            .num_threads(LOADER_THREADS)
+            .thread_name(|index| format!("section-loader-{}", index))
+            .build()
+            .expect("failed to start the section loader threads");
+        Self {
+            section_size,
+            load_radius,
+            unload_radius: unload_radius.max(load_radius),
+            loader: Arc::new(loader),
+            workers,
+            sender,
+            receiver,
+            loading: HashMap::new(),
+            active: HashMap::new(),
+            failed: Vec::new(),
+            next_request: 0,
+        }
+    }
+
+    /// Change the streaming distances; takes effect on the next update
+    pub fn set_radii(&mut self, load_radius: f32, unload_radius: f32) {
+        self.load_radius = load_radius;
+        self.unload_radius = unload_radius.max(load_radius);
+    }
+
+    /// Section containing a world position
+    pub fn section_at(&self, position: Vec2) -> IVec2 {
+        (position / self.section_size).floor().as_ivec2()
+    }
+
+    /// World-space area covered by a section
+    pub fn section_bounds(&self, coord: IVec2) -> Rectangle {
+        Rectangle::new(coord.as_vec2() * self.section_size, self.section_size)
+    }
+
+    /// Collect finished loads, unload distant sections and request nearby ones
+    pub fn update(&mut self, camera: Vec2) -> Vec<StreamingEvent<T>> {
+        let mut events = Vec::new();
+
+        // Results for sections that were cancelled or re-requested since are stale
+        while let Ok((coord, request, result)) = self.receiver.try_recv() {
+            if self.loading.get(&coord) != Some(&request) {
+                continue;
+            }
+            self.loading.remove(&coord);
+            match result {
+                Ok(data) => {
+                    self.active.insert(coord, data);
+                    events.push(StreamingEvent::Activated(coord));
+                }
+                Err(error) => {
+                    self.failed.push(coord);
+                    events.push(StreamingEvent::LoadFailed(coord, error));
+                }
+            }
+        }
+
+        let out_of_range: Vec<IVec2> = self
+            .active
+            .keys()
+            .copied()
+            .filter(|coord| self.distance_to(*coord, camera) > self.unload_radius)
+            .collect();
+        for coord in out_of_range {
+            if let Some(data) = self.active.remove(&coord) {
+                events.push(StreamingEvent::Deactivated(coord, data));
+            }
+        }
+        let unload_radius = self.unload_radius;
+        let section_size = self.section_size;
+        let in_range =
+            |coord: &IVec2| section_distance(*coord, section_size, camera) <= unload_radius;
+        self.loading.retain(|coord, _| in_range(coord));
+        self.failed.retain(in_range);
+
+        let reach = (Vec2::splat(self.load_radius) / self.section_size)
+            .ceil()
+            .as_ivec2();
+        let center = self.section_at(camera);
+        for y in center.y - reach.y..=center.y + reach.y {
+            for x in center.x - reach.x..=center.x + reach.x {
+                let coord = IVec2::new(x, y);
+                if self.distance_to(coord, camera) <= self.load_radius
+                    && !self.active.contains_key(&coord)
+                    && !self.loading.contains_key(&coord)
+                    && !self.failed.contains(&coord)
+                {
+                    self.request(coord);
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Unload every section, handing back their data; pending loads are dropped
+    pub fn unload_all(&mut self) -> Vec<StreamingEvent<T>> {
+        self.loading.clear();
+        self.failed.clear();
+        self.active
+            .drain()
+            .map(|(coord, data)| StreamingEvent::Deactivated(coord, data))
+            .collect()
+    }
+
+    /// Data of a loaded section
+    pub fn section(&self, coord: IVec2) -> Option<&T> {
+        self.active.get(&coord)
+    }
+
+    pub fn section_mut(&mut self, coord: IVec2) -> Option<&mut T> {
+        self.active.get_mut(&coord)
+    }
+
+    /// Loaded sections and their data
+    pub fn active_sections(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.active.iter().map(|(coord, data)| (*coord, data))
+    }
+
+    pub fn is_active(&self, coord: IVec2) -> bool {
+        self.active.contains_key(&coord)
+    }
+
+    pub fn is_loading(&self, coord: IVec2) -> bool {
+        self.loading.contains_key(&coord)
+    }
+
+    /// Check if any section is still being loaded
+    pub fn has_pending(&self) -> bool {
+        !self.loading.is_empty()
+    }
+
+    fn distance_to(&self, coord: IVec2, camera: Vec2) -> f32 {
+        section_distance(coord, self.section_size, camera)
+    }
+
+    fn request(&mut self, coord: IVec2) {
+        let request = self.next_request;
+        self.next_request += 1;
+        self.loading.insert(coord, request);
+
+        let sender = self.sender.clone();
+        let loader = Arc::clone(&self.loader);
+        self.workers.spawn(move || {
+            // The streamer is gone if it was dropped mid-load
+            let _ = sender.send((coord, request, loader(coord)));
+        });
+    }
+}
+
+/// Distance from a point to the nearest edge of a section, zero inside it
+fn section_distance(coord: IVec2, section_size: Vec2, point: Vec2) -> f32 {
+    let min = coord.as_vec2() * section_size;
+    point.clamp(min, min + section_size).distance(point)
+}
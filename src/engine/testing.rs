@@ -0,0 +1,281 @@
+use std::time::Duration;
+
+use super::config::EngineConfig;
+use super::core::Engine;
+use super::game::Game;
+use crate::ecs::{Scheduler, World};
+use crate::events::event_types::Event;
+use crate::input::manager::InputManager;
+use crate::input::types::PhysicalInput;
+
+/// Raw input change applied at the start of a frame
+#[derive(Debug, Clone, PartialEq)]
+enum ScheduledInput {
+    Set(PhysicalInput, bool),
+    Value(PhysicalInput, f32),
+}
+
+/// World resource carrying events between a test and the game under test
+#[derive(Default)]
+pub struct TestEvents {
+    /// Events sent with `TestEngine::send_event` before this frame
+    received: Vec<Box<dyn Event>>,
+    emitted: Vec<Box<dyn Event>>,
+}
+
+impl TestEvents {
+    /// Record an event for the test to assert on
+    pub fn emit(&mut self, event: impl Event) {
+        self.emitted.push(Box::new(event));
+    }
+
+    /// Events of one type sent before this frame
+    pub fn received<T: Event>(&self) -> impl Iterator<Item = &T> {
+        self.received
+            .iter()
+            .filter_map(|event| event.as_any().downcast_ref())
+    }
+}
+
+/// Headless harness for gameplay integration tests
+///
+/// Runs a `Game` on a headless `Engine` with a fixed timestep, so it works with or
+/// without the `opengl` feature. Each frame is a real `Engine::tick`: the scheduler's
+/// systems run, then `Game::update`. The `InputManager` and `TestEvents` live in the
+/// world as resources; inputs and events can be scheduled for specific frames, and
+/// events the game emits through `TestEvents` are collected for assertions.
+///
+/// ```
+/// # use engine_2d::engine::{Game, TestEngine, TestEvents, UpdateContext};
+/// # use engine_2d::events::event_types::LogicEvent;
+/// # use engine_2d::input::*;
+/// # use std::time::Instant;
+/// # struct MyGame;
+/// # impl MyGame {
+/// #     fn new() -> Self {
+/// #         MyGame
+/// #     }
+/// # }
+/// # impl Game for MyGame {
+/// #     fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+/// #         let jumped = ctx.world.resource::<InputManager>().unwrap().is_action_pressed("JUMP");
+/// #         if jumped {
+/// #             ctx.world.resource_mut::<TestEvents>().unwrap().emit(LogicEvent::GameStateChanged {
+/// #                 new_state: "jumping".to_string(),
+/// #                 timestamp: Instant::now(),
+/// #             });
+/// #         }
+/// #     }
+/// #     fn name(&self) -> &str {
+/// #         "My Game"
+/// #     }
+/// # }
+/// # let jump_action = GameAction {
+/// #     id: "JUMP".to_string(),
+/// #     display_name: "Jump".to_string(),
+/// #     category: ActionCategory::Movement,
+/// #     input_type: InputType::Digital,
+/// #     default_bindings: vec![InputBinding::Single(PhysicalInput::Keyboard(KeyCode::Space))],
+/// #     metadata: ActionMetadata::default(),
+/// # };
+/// let mut engine = TestEngine::new(Box::new(MyGame::new()));
+/// engine.input_mut().register_action(jump_action);
+/// engine.tap(PhysicalInput::Keyboard(KeyCode::Space), 2);
+/// engine.step_frames(10);
+/// assert_eq!(engine.emitted::<LogicEvent>().len(), 1);
+/// ```
+pub struct TestEngine {
+    engine: Engine,
+    delta_time: f32,
+    frame: u64,
+    /// Input changes keyed by the frame they apply on, in scheduling order
+    scheduled: Vec<(u64, ScheduledInput)>,
+    pending_events: Vec<Box<dyn Event>>,
+}
+
+impl TestEngine {
+    /// Create a harness for `game` stepping at 60 frames per second
+    pub fn new(game: Box<dyn Game>) -> Self {
+        Self::new_with_config(EngineConfig::default(), game)
+            .expect("the default config creates a headless engine")
+    }
+
+    /// Create a harness for `game` with a custom engine config
+    pub fn new_with_config(config: EngineConfig, game: Box<dyn Game>) -> Result<Self, String> {
+        let mut engine = Engine::new_headless(config, game).map_err(|e| e.to_string())?;
+        engine.world_mut().insert_resource(InputManager::new());
+        engine.world_mut().insert_resource(TestEvents::default());
+        Ok(Self {
+            engine,
+            delta_time: 1.0 / 60.0,
+            frame: 0,
+            scheduled: Vec::new(),
+            pending_events: Vec::new(),
+        })
+    }
+
+    /// Builder: set the fixed frame time in seconds
+    pub fn with_delta_time(mut self, delta_time: f32) -> Self {
+        self.delta_time = delta_time;
+        self
+    }
+
+    /// Get the engine being stepped
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    pub fn engine_mut(&mut self) -> &mut Engine {
+        &mut self.engine
+    }
+
+    /// Get the ECS scheduler, so games can test their real systems
+    pub fn scheduler_mut(&mut self) -> &mut Scheduler {
+        self.engine.scheduler_mut()
+    }
+
+    pub fn world(&self) -> &World {
+        self.engine.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.engine.world_mut()
+    }
+
+    pub fn input(&self) -> &InputManager {
+        self.world()
+            .resource()
+            .expect("TestEngine keeps an InputManager resource")
+    }
+
+    /// Get the input manager, e.g. to register actions
+    pub fn input_mut(&mut self) -> &mut InputManager {
+        self.world_mut()
+            .resource_mut()
+            .expect("TestEngine keeps an InputManager resource")
+    }
+
+    /// Index of the next frame to run
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn elapsed_time(&self) -> f32 {
+        self.engine.elapsed_time()
+    }
+
+    /// Press an input starting with the next frame
+    pub fn press(&mut self, input: PhysicalInput) {
+        self.press_at(self.frame, input);
+    }
+
+    /// Release an input starting with the next frame
+    pub fn release(&mut self, input: PhysicalInput) {
+        self.release_at(self.frame, input);
+    }
+
+    /// Press an input at the start of `frame`
+    pub fn press_at(&mut self, frame: u64, input: PhysicalInput) {
+        self.scheduled
+            .push((frame, ScheduledInput::Set(input, true)));
+    }
+
+    /// Release an input at the start of `frame`
+    pub fn release_at(&mut self, frame: u64, input: PhysicalInput) {
+        self.scheduled
+            .push((frame, ScheduledInput::Set(input, false)));
+    }
+
+    /// Set an analog input value at the start of `frame`
+    pub fn set_value_at(&mut self, frame: u64, input: PhysicalInput, value: f32) {
+        self.scheduled
+            .push((frame, ScheduledInput::Value(input, value)));
+    }
+
+    /// Press an input on the next frame and release it `frames` frames later
+    pub fn tap(&mut self, input: PhysicalInput, frames: u64) {
+        let release = self.frame + frames.max(1);
+        self.press(input.clone());
+        self.release_at(release, input);
+    }
+
+    /// Deliver an event to the systems and game on the next frame
+    pub fn send_event(&mut self, event: impl Event) {
+        self.pending_events.push(Box::new(event));
+    }
+
+    /// Run one frame: apply scheduled input and update input state, then tick the
+    /// engine, which runs the scheduler and `Game::update`
+    pub fn step(&mut self) {
+        let frame = self.frame;
+        let (due, later) = std::mem::take(&mut self.scheduled)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(at, _)| *at <= frame);
+        self.scheduled = later;
+        let delta_time = self.delta_time;
+        let input = self.input_mut();
+        for (_, change) in due {
+            match change {
+                ScheduledInput::Set(physical, pressed) => input.set_raw_input(physical, pressed),
+                ScheduledInput::Value(physical, value) => input.set_raw_value(physical, value),
+            }
+        }
+        input.update(delta_time);
+
+        let received = std::mem::take(&mut self.pending_events);
+        self.world_mut()
+            .resource_or_default::<TestEvents>()
+            .received = received;
+        self.engine
+            .tick(Some(Duration::from_secs_f32(self.delta_time)));
+        self.world_mut()
+            .resource_or_default::<TestEvents>()
+            .received
+            .clear();
+        self.frame += 1;
+    }
+
+    /// Run `frames` frames
+    pub fn step_frames(&mut self, frames: u64) {
+        for _ in 0..frames {
+            self.step();
+        }
+    }
+
+    /// Run frames until `condition` holds, up to `max_frames`; returns whether it held
+    pub fn step_until(
+        &mut self,
+        max_frames: u64,
+        mut condition: impl FnMut(&World) -> bool,
+    ) -> bool {
+        for _ in 0..max_frames {
+            if condition(self.world()) {
+                return true;
+            }
+            self.step();
+        }
+        condition(self.world())
+    }
+
+    /// Events of one type emitted through `TestEvents` so far
+    pub fn emitted<T: Event>(&self) -> Vec<&T> {
+        self.world()
+            .resource::<TestEvents>()
+            .map(|events| {
+                events
+                    .emitted
+                    .iter()
+                    .filter_map(|event| event.as_any().downcast_ref())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Discard the emitted events collected so far
+    pub fn clear_emitted(&mut self) {
+        self.world_mut()
+            .resource_or_default::<TestEvents>()
+            .emitted
+            .clear();
+    }
+}
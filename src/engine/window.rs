@@ -44,11 +44,14 @@
 // - Plan for WebAssembly support in future
 
 use super::config::EngineConfig;
+use super::cursor::{CursorImage, CursorStyle};
 use crate::events::event_system::EventSystem;
 use crate::events::event_types::RenderEvent;
 use crate::render::gl_wrapper::GlWrapper;
+use crate::ui::Clipboard;
 use glfw::{Context, Glfw, WindowHint, WindowMode};
-use std::time::Instant;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 /// Window display modes
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -87,6 +90,15 @@ pub struct WindowManager {
     pub cursor_hidden: bool,
     pub mouse_captured: bool,
     pub vsync_enabled: bool,
+    /// Framebuffer size last reported to the engine
+    pub framebuffer_size: (u32, u32),
+    /// Latest framebuffer size seen this frame, if it changed
+    pending_framebuffer_size: Option<(u32, u32)>,
+    /// Cursor images and hotspots registered per style
+    cursor_images: HashMap<CursorStyle, (CursorImage, (u32, u32))>,
+    /// Cursors already created, reused when switching back to their style
+    cursor_cache: HashMap<CursorStyle, glfw::Cursor>,
+    cursor_style: CursorStyle,
 }
 
 impl WindowManager {
@@ -108,6 +120,7 @@ impl WindowManager {
         glfw.window_hint(WindowHint::ContextVersion(3, 3));
         glfw.window_hint(WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
         glfw.window_hint(WindowHint::OpenGlForwardCompat(true));
+        glfw.window_hint(WindowHint::SRgbCapable(config.srgb));
 
         // Determine initial window mode
         let initial_mode = if config.fullscreen {
@@ -138,6 +151,7 @@ impl WindowManager {
 
         // Set up event callbacks
         window.set_key_polling(true);
+        window.set_char_polling(true);
         window.set_framebuffer_size_polling(true);
         window.set_close_polling(true);
 
@@ -147,6 +161,10 @@ impl WindowManager {
         }
         println!("OpenGL context initialized successfully!");
 
+        if config.srgb {
+            gl_wrapper.set_srgb_framebuffer(true)?;
+        }
+
         // Configure VSync based on config
         if config.vsync {
             glfw.set_swap_interval(glfw::SwapInterval::Sync(1));
@@ -159,6 +177,7 @@ impl WindowManager {
         // Get initial window position and size for restoration
         let (pos_x, pos_y) = window.get_pos();
         let (width, height) = window.get_size();
+        let (fb_width, fb_height) = window.get_framebuffer_size();
 
         // Detect available monitors at startup
         let available_monitors = Self::detect_monitors(&mut glfw);
@@ -181,6 +200,11 @@ impl WindowManager {
             cursor_hidden: false,
             mouse_captured: false,
             vsync_enabled: config.vsync,
+            framebuffer_size: (fb_width as u32, fb_height as u32),
+            pending_framebuffer_size: None,
+            cursor_images: HashMap::new(),
+            cursor_cache: HashMap::new(),
+            cursor_style: CursorStyle::Default,
         })
     }
 
@@ -265,6 +289,11 @@ impl WindowManager {
         self.glfw.poll_events();
     }
 
+    /// Block until an event arrives or the timeout elapses
+    pub fn wait_events_timeout(&mut self, timeout: Duration) {
+        self.glfw.wait_events_timeout(timeout.as_secs_f64());
+    }
+
     pub fn swap_buffers(&mut self) {
         self.window.swap_buffers();
     }
@@ -482,6 +511,85 @@ impl WindowManager {
         self.window.set_cursor_mode(mode);
     }
 
+    /// Replace the OS arrow with a themed cursor image
+    pub fn set_cursor_image(
+        &mut self,
+        image: CursorImage,
+        hotspot: (u32, u32),
+    ) -> Result<(), String> {
+        self.set_style_cursor_image(CursorStyle::Default, image, hotspot)
+    }
+
+    /// Set the cursor image shown for a style, e.g. a pointing hand for `Hover`
+    pub fn set_style_cursor_image(
+        &mut self,
+        style: CursorStyle,
+        image: CursorImage,
+        hotspot: (u32, u32),
+    ) -> Result<(), String> {
+        image.check_hotspot(hotspot)?;
+        self.cursor_images.insert(style, (image, hotspot));
+        self.refresh_cursor(style);
+        Ok(())
+    }
+
+    /// Go back to the system cursor for a style
+    pub fn clear_cursor_image(&mut self, style: CursorStyle) {
+        if self.cursor_images.remove(&style).is_some() {
+            self.refresh_cursor(style);
+        }
+    }
+
+    /// Switch cursor for what the pointer is doing (call whenever hover or drag state changes)
+    ///
+    /// Styles without an image use the closest system cursor.
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        if style == self.cursor_style {
+            return;
+        }
+        let cursor = self
+            .cursor_cache
+            .remove(&style)
+            .or_else(|| self.create_cursor(style));
+        if let Some(previous) = self.window.set_cursor(cursor) {
+            self.cursor_cache.insert(self.cursor_style, previous);
+        }
+        self.cursor_style = style;
+    }
+
+    pub fn cursor_style(&self) -> CursorStyle {
+        self.cursor_style
+    }
+
+    /// Drop a style's cached cursor, re-creating it if it's showing
+    fn refresh_cursor(&mut self, style: CursorStyle) {
+        self.cursor_cache.remove(&style);
+        if style == self.cursor_style {
+            let cursor = self.create_cursor(style);
+            self.window.set_cursor(cursor);
+        }
+    }
+
+    fn create_cursor(&self, style: CursorStyle) -> Option<glfw::Cursor> {
+        if let Some((image, (x, y))) = self.cursor_images.get(&style) {
+            let pixels = glfw::PixelImage {
+                width: image.width,
+                height: image.height,
+                pixels: image.packed_pixels(),
+            };
+            return Some(glfw::Cursor::create_from_pixels(pixels, *x, *y));
+        }
+        let standard = match style {
+            CursorStyle::Default => return None,
+            CursorStyle::Hover | CursorStyle::Grab | CursorStyle::Grabbing => {
+                glfw::StandardCursor::Hand
+            }
+            CursorStyle::Text => glfw::StandardCursor::IBeam,
+            CursorStyle::Crosshair => glfw::StandardCursor::Crosshair,
+        };
+        Some(glfw::Cursor::standard(standard))
+    }
+
     /// Capture mouse (confine cursor to window)
     pub fn set_capture_mouse(&mut self, capture: bool) {
         if capture {
@@ -524,6 +632,16 @@ impl WindowManager {
         self.window.set_floating(always_on_top);
     }
 
+    /// Check if window has input focus
+    pub fn is_focused(&self) -> bool {
+        self.window.is_focused()
+    }
+
+    /// Check if window is minimized
+    pub fn is_minimized(&self) -> bool {
+        self.window.is_iconified()
+    }
+
     /// Check if window is visible
     pub fn is_visible(&self) -> bool {
         self.window.is_visible()
@@ -539,6 +657,35 @@ impl WindowManager {
         self.available_monitors.first()
     }
 
+    /// Take the framebuffer resize recorded since the last call as (old, new) sizes
+    ///
+    /// Returns None when the size did not change, even if resize events arrived.
+    pub fn take_resize(&mut self) -> Option<((u32, u32), (u32, u32))> {
+        let new_size = self.pending_framebuffer_size.take()?;
+        let old_size = self.framebuffer_size;
+        if new_size == old_size {
+            return None;
+        }
+        self.framebuffer_size = new_size;
+        Some((old_size, new_size))
+    }
+
+    /// Send a single `ResolutionChanged` event to the event system
+    pub fn emit_resolution_changed(&self, old_size: (u32, u32), new_size: (u32, u32)) {
+        if let Some(ref event_system) = self.event_system {
+            let event = RenderEvent::ResolutionChanged {
+                old_width: old_size.0,
+                old_height: old_size.1,
+                new_width: new_size.0,
+                new_height: new_size.1,
+                timestamp: Instant::now(),
+            };
+            if let Err(e) = event_system.send_render_event(event) {
+                eprintln!("Failed to send resolution changed event: {}", e);
+            }
+        }
+    }
+
     pub fn process_events<F>(&mut self, mut callback: F)
     where
         F: FnMut(&WindowEvent) -> bool,
@@ -549,18 +696,9 @@ impl WindowManager {
                     self.should_close = true;
                 }
                 glfw::WindowEvent::FramebufferSize(width, height) => {
-                    // Handle window resize - send viewport update event to render system
-                    if let Some(ref event_system) = self.event_system {
-                        let viewport_event = RenderEvent::ViewportUpdated {
-                            width,
-                            height,
-                            timestamp: Instant::now(),
-                        };
-                        if let Err(e) = event_system.send_render_event(viewport_event) {
-                            eprintln!("Failed to send viewport update event: {}", e);
-                        }
-                    }
-                    println!("Window resized to {}x{}", width, height);
+                    // Coalesce resizes; the engine handles the final size once per frame
+                    self.pending_framebuffer_size =
+                        Some((width.max(0) as u32, height.max(0) as u32));
                 }
                 glfw::WindowEvent::Size(width, height) => {
                     // Handle window size change
@@ -575,3 +713,13 @@ impl WindowManager {
         }
     }
 }
+
+impl Clipboard for WindowManager {
+    fn get_text(&mut self) -> Option<String> {
+        self.window.get_clipboard_string()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.window.set_clipboard_string(text);
+    }
+}
@@ -2,21 +2,38 @@ use crate::events::event_types::*;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 
+/// Callbacks a channel's events are dispatched to
+type Listeners<E> = Arc<Mutex<Vec<Box<dyn FnMut(&E) + Send>>>>;
+
 /// Simplified event system for basic rendering
 #[derive(Clone)]
 pub struct EventSystem {
     render_sender: Sender<RenderEvent>,
     render_receiver: Arc<Mutex<Receiver<RenderEvent>>>,
+    entity_sender: Sender<EntityEvent>,
+    entity_receiver: Arc<Mutex<Receiver<EntityEvent>>>,
+    entity_listeners: Listeners<EntityEvent>,
+    animation_sender: Sender<AnimationEvent>,
+    animation_receiver: Arc<Mutex<Receiver<AnimationEvent>>>,
+    animation_listeners: Listeners<AnimationEvent>,
 }
 
 impl EventSystem {
     /// Create a new event system
     pub fn new() -> Self {
         let (render_sender, render_receiver) = mpsc::channel();
+        let (entity_sender, entity_receiver) = mpsc::channel();
+        let (animation_sender, animation_receiver) = mpsc::channel();
 
         Self {
             render_sender,
             render_receiver: Arc::new(Mutex::new(render_receiver)),
+            entity_sender,
+            entity_receiver: Arc::new(Mutex::new(entity_receiver)),
+            entity_listeners: Arc::default(),
+            animation_sender,
+            animation_receiver: Arc::new(Mutex::new(animation_receiver)),
+            animation_listeners: Arc::default(),
         }
     }
 
@@ -36,6 +53,101 @@ impl EventSystem {
     pub fn get_render_receiver(&self) -> Arc<Mutex<Receiver<RenderEvent>>> {
         Arc::clone(&self.render_receiver)
     }
+
+    /// Get the entity event sender (for `World::set_event_sender`)
+    pub fn get_entity_sender(&self) -> Sender<EntityEvent> {
+        self.entity_sender.clone()
+    }
+
+    /// Get the entity event receiver (for gameplay systems reacting to world changes)
+    ///
+    /// It stays empty on an `Engine` with entity listeners, which take every event.
+    pub fn get_entity_receiver(&self) -> Arc<Mutex<Receiver<EntityEvent>>> {
+        Arc::clone(&self.entity_receiver)
+    }
+
+    /// Take every entity event sent since the last call
+    ///
+    /// Once a listener is added, an `Engine` dispatches the events every tick and they no
+    /// longer reach the receiver.
+    pub fn drain_entity_events(&self) -> Vec<EntityEvent> {
+        match self.entity_receiver.lock() {
+            Ok(receiver) => receiver.try_iter().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Call `listener` with each entity event when they are dispatched
+    ///
+    /// Don't add listeners from inside a listener; dispatch holds the list.
+    pub fn add_entity_listener(&self, listener: impl FnMut(&EntityEvent) + Send + 'static) {
+        if let Ok(mut listeners) = self.entity_listeners.lock() {
+            listeners.push(Box::new(listener));
+        }
+    }
+
+    /// Hand the entity events to the listeners; the engine calls this once per tick, after
+    /// `Game::update`
+    ///
+    /// Without listeners the events are left for `drain_entity_events` and the receiver.
+    pub fn dispatch_entity_events(&self) {
+        dispatch_from(&self.entity_listeners, &self.entity_receiver);
+    }
+
+    /// Get the animation event sender (for animations dispatching their markers)
+    pub fn get_animation_sender(&self) -> Sender<AnimationEvent> {
+        self.animation_sender.clone()
+    }
+
+    /// Get the animation event receiver (for sound and gameplay synced to animations)
+    ///
+    /// It stays empty on an `Engine` with animation listeners, which take every event.
+    pub fn get_animation_receiver(&self) -> Arc<Mutex<Receiver<AnimationEvent>>> {
+        Arc::clone(&self.animation_receiver)
+    }
+
+    /// Take every animation event sent since the last call
+    pub fn drain_animation_events(&self) -> Vec<AnimationEvent> {
+        match self.animation_receiver.lock() {
+            Ok(receiver) => receiver.try_iter().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Call `listener` with each animation marker when they are dispatched
+    ///
+    /// Don't add listeners from inside a listener; dispatch holds the list.
+    pub fn add_animation_listener(&self, listener: impl FnMut(&AnimationEvent) + Send + 'static) {
+        if let Ok(mut listeners) = self.animation_listeners.lock() {
+            listeners.push(Box::new(listener));
+        }
+    }
+
+    /// Hand the animation events to the listeners; the engine calls this once per tick,
+    /// after `Game::update`
+    ///
+    /// Without listeners the events are left for `drain_animation_events` and the receiver.
+    pub fn dispatch_animation_events(&self) {
+        dispatch_from(&self.animation_listeners, &self.animation_receiver);
+    }
+}
+
+/// Drain `receiver` into the listeners, leaving its events queued if there are none
+fn dispatch_from<E>(listeners: &Listeners<E>, receiver: &Mutex<Receiver<E>>) {
+    let Ok(mut listeners) = listeners.lock() else {
+        return;
+    };
+    if listeners.is_empty() {
+        return;
+    }
+    let Ok(receiver) = receiver.lock() else {
+        return;
+    };
+    for event in receiver.try_iter() {
+        for listener in listeners.iter_mut() {
+            listener(&event);
+        }
+    }
 }
 
 impl Default for EventSystem {
@@ -1,3 +1,4 @@
+use crate::ecs::Entity;
 use std::time::Instant;
 
 /// Base event trait that all events must implement
@@ -23,6 +24,41 @@ pub enum EventPriority {
     Critical = 3,
 }
 
+/// An event offered to listeners from the highest priority down, e.g. UI before gameplay;
+/// the one that handles it consumes it so lower layers skip it
+#[derive(Debug, Clone)]
+pub struct Consumable<E> {
+    event: E,
+    handled: bool,
+}
+
+impl<E> Consumable<E> {
+    pub fn new(event: E) -> Self {
+        Self {
+            event,
+            handled: false,
+        }
+    }
+
+    pub fn event(&self) -> &E {
+        &self.event
+    }
+
+    /// Mark the event handled so listeners after this one leave it alone
+    pub fn consume(&mut self) {
+        self.handled = true;
+    }
+
+    pub fn is_handled(&self) -> bool {
+        self.handled
+    }
+
+    /// The event, if no listener has handled it yet
+    pub fn unhandled(&self) -> Option<&E> {
+        (!self.handled).then_some(&self.event)
+    }
+}
+
 /// Input events from keyboard, mouse, gamepad, etc.
 #[derive(Debug, Clone)]
 pub enum InputEvent {
@@ -100,9 +136,12 @@ pub enum RenderEvent {
     PresentFrame {
         timestamp: Instant,
     },
-    ViewportUpdated {
-        width: i32,
-        height: i32,
+    /// Emitted once per frame after all subsystems have handled a framebuffer resize
+    ResolutionChanged {
+        old_width: u32,
+        old_height: u32,
+        new_width: u32,
+        new_height: u32,
         timestamp: Instant,
     },
 }
@@ -114,7 +153,7 @@ impl Event for RenderEvent {
             RenderEvent::DrawRectangle { timestamp, .. } => *timestamp,
             RenderEvent::DrawSprite { timestamp, .. } => *timestamp,
             RenderEvent::PresentFrame { timestamp, .. } => *timestamp,
-            RenderEvent::ViewportUpdated { timestamp, .. } => *timestamp,
+            RenderEvent::ResolutionChanged { timestamp, .. } => *timestamp,
         }
     }
 
@@ -170,6 +209,82 @@ impl Event for LogicEvent {
     }
 }
 
+/// ECS world changes, sent by a `World` with an event sender attached
+#[derive(Debug, Clone)]
+pub enum EntityEvent {
+    EntitySpawned {
+        entity: Entity,
+        timestamp: Instant,
+    },
+    EntityDespawned {
+        entity: Entity,
+        timestamp: Instant,
+    },
+    /// A component type was attached to an entity that didn't have one; replacing a
+    /// component doesn't count
+    ComponentAdded {
+        entity: Entity,
+        /// Type name of the component, e.g. `engine_2d::ecs::component::Transform`
+        component: &'static str,
+        timestamp: Instant,
+    },
+}
+
+impl Event for EntityEvent {
+    fn timestamp(&self) -> Instant {
+        match self {
+            EntityEvent::EntitySpawned { timestamp, .. } => *timestamp,
+            EntityEvent::EntityDespawned { timestamp, .. } => *timestamp,
+            EntityEvent::ComponentAdded { timestamp, .. } => *timestamp,
+        }
+    }
+
+    fn priority(&self) -> EventPriority {
+        EventPriority::Normal
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Named markers reached during animation playback, for syncing sounds and gameplay
+#[derive(Debug, Clone)]
+pub enum AnimationEvent {
+    Marker {
+        name: String,
+        /// The entity whose animation reached the marker, if played through the ECS
+        entity: Option<Entity>,
+        timestamp: Instant,
+    },
+}
+
+impl AnimationEvent {
+    pub fn marker(name: &str, entity: Option<Entity>) -> Self {
+        AnimationEvent::Marker {
+            name: name.to_string(),
+            entity,
+            timestamp: Instant::now(),
+        }
+    }
+}
+
+impl Event for AnimationEvent {
+    fn timestamp(&self) -> Instant {
+        match self {
+            AnimationEvent::Marker { timestamp, .. } => *timestamp,
+        }
+    }
+
+    fn priority(&self) -> EventPriority {
+        EventPriority::Normal
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
 /// Audio events for sound and music
 #[derive(Debug, Clone)]
 pub enum AudioEvent {
@@ -3,7 +3,12 @@ use crate::define_actions;
 ///
 /// This file demonstrates how to define game actions using the macro system.
 /// In a real game, you would define your own actions here.
+use crate::input::manager::InputManager;
 use crate::input::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 // Define common game actions
 define_actions! {
@@ -66,11 +71,7 @@ define_actions! {
         category: Movement,
         input_type: Analog,
         bindings: [
-            InputBinding::Analog {
-                input: PhysicalInput::MouseAxis(MouseAxis::X),
-                threshold: 0.1,
-                deadzone: 0.05,
-            }
+            InputBinding::Single(PhysicalInput::MouseAxis(MouseAxis::X))
         ],
         description: "Horizontal mouse look",
         tags: ["camera", "analog"],
@@ -82,17 +83,25 @@ define_actions! {
         category: Movement,
         input_type: Analog,
         bindings: [
-            InputBinding::Analog {
-                input: PhysicalInput::MouseAxis(MouseAxis::Y),
-                threshold: 0.1,
-                deadzone: 0.05,
-            }
+            InputBinding::Single(PhysicalInput::MouseAxis(MouseAxis::Y))
         ],
         description: "Vertical mouse look",
         tags: ["camera", "analog"],
         priority: 2,
     };
 
+    CAMERA_ZOOM: {
+        name: "Camera Zoom",
+        category: Movement,
+        input_type: Analog,
+        bindings: [
+            InputBinding::Single(PhysicalInput::MouseAxis(MouseAxis::ScrollY))
+        ],
+        description: "Zoom the camera with the scroll wheel",
+        tags: ["camera", "analog"],
+        priority: 2,
+    };
+
     // Combat actions
     FIRE_WEAPON: {
         name: "Fire Weapon",
@@ -248,3 +257,157 @@ pub fn get_debug_actions() -> Vec<GameAction> {
 pub fn get_interaction_actions() -> Vec<GameAction> {
     get_actions_by_category(ActionCategory::Interaction)
 }
+
+/// A player's rebound controls, saved between sessions
+///
+/// Only rebound actions are stored; the others keep their `default_bindings`, so actions
+/// added in a later version of the game start out bound. Profiles are saved as TOML with
+/// an array per action, using the binding text form of `InputBinding::parse`:
+///
+/// ```toml
+/// name = "Left-handed"
+///
+/// [bindings]
+/// MOVE_FORWARD = ["Keyboard:Up", "Gamepad:DPadUp"]
+/// QUICK_SAVE = ["Keyboard:RightCtrl+Keyboard:S"]
+/// RELOAD = []
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BindingProfile {
+    pub name: String,
+    /// Bindings by action ID
+    pub bindings: BTreeMap<String, Vec<InputBinding>>,
+}
+
+impl BindingProfile {
+    /// An empty profile, which leaves every action on its defaults
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            bindings: BTreeMap::new(),
+        }
+    }
+
+    /// Capture the actions rebound in an input manager
+    pub fn from_manager(name: &str, input_manager: &InputManager) -> Self {
+        Self {
+            name: name.to_string(),
+            bindings: input_manager
+                .binding_overrides()
+                .iter()
+                .map(|(action_id, bindings)| (action_id.clone(), bindings.clone()))
+                .collect(),
+        }
+    }
+
+    /// Make this profile's bindings the input manager's, resetting other rebound actions
+    ///
+    /// Actions the manager doesn't have, e.g. ones removed from the game, are skipped.
+    pub fn apply(&self, input_manager: &mut InputManager) {
+        input_manager.reset_all_bindings();
+        for (action_id, bindings) in &self.bindings {
+            if let Err(e) = input_manager.set_bindings(action_id, bindings.clone()) {
+                log::warn!("Skipping binding in profile '{}': {}", self.name, e);
+            }
+        }
+    }
+
+    /// Serialize the profile as TOML
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("binding profiles serialize to TOML")
+    }
+
+    /// Parse a profile saved by `to_toml`
+    pub fn parse(source: &str) -> Result<Self, String> {
+        toml::from_str(source).map_err(|e| e.to_string())
+    }
+
+    /// Save the profile to a TOML file
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_toml())
+            .map_err(|e| format!("Failed to write binding profile '{}': {}", path, e))
+    }
+
+    /// Load a profile from a TOML file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read binding profile '{}': {}", path, e))?;
+        Self::parse(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+}
+
+/// Binding profiles stored as `<name>.toml` files in one directory, e.g. one per player
+pub struct BindingProfiles {
+    dir: PathBuf,
+}
+
+impl BindingProfiles {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Names of the saved profiles, sorted; empty if the directory doesn't exist yet
+    pub fn names(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "toml" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().into_owned())
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Save a profile under its name, creating the directory if needed
+    pub fn save(&self, profile: &BindingProfile) -> Result<(), String> {
+        let path = self.path(&profile.name)?;
+        fs::create_dir_all(&self.dir).map_err(|e| {
+            format!(
+                "Failed to create profile directory '{}': {}",
+                self.dir.display(),
+                e
+            )
+        })?;
+        profile.save(&path.to_string_lossy())
+    }
+
+    /// Load the profile saved under `name`
+    pub fn load(&self, name: &str) -> Result<BindingProfile, String> {
+        let mut profile = BindingProfile::load(&self.path(name)?.to_string_lossy())?;
+        profile.name = name.to_string();
+        Ok(profile)
+    }
+
+    /// Delete the profile saved under `name`
+    pub fn delete(&self, name: &str) -> Result<(), String> {
+        let path = self.path(name)?;
+        fs::remove_file(&path).map_err(|e| {
+            format!(
+                "Failed to delete binding profile '{}': {}",
+                path.display(),
+                e
+            )
+        })
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf, String> {
+        let valid = !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\', ':']);
+        if !valid {
+            return Err(format!("Invalid binding profile name '{}'", name));
+        }
+        Ok(self.dir.join(format!("{}.toml", name)))
+    }
+}
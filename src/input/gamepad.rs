@@ -1,5 +1,156 @@
 use crate::input::types::*;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+/// Calibration for a single gamepad axis
+///
+/// Raw values are re-centered, values within `deadzone` of the center read as 0, and
+/// values at or past `saturation` read as full deflection. Distances go up to 2.0 so
+/// axes resting at one end, like triggers centered at -1.0, can be calibrated too.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxisCalibration {
+    /// Raw value the axis reports at rest
+    pub center: f32,
+    /// Distance from center that still reads as 0
+    pub deadzone: f32,
+    /// Distance from center that reads as full deflection
+    pub saturation: f32,
+    /// Ramp output up from 0 at the deadzone edge instead of jumping to the raw value
+    pub rescale: bool,
+}
+
+impl Default for AxisCalibration {
+    fn default() -> Self {
+        Self {
+            center: 0.0,
+            deadzone: 0.1,
+            saturation: 1.0,
+            rescale: false,
+        }
+    }
+}
+
+impl AxisCalibration {
+    /// Set the rest position
+    pub fn with_center(mut self, center: f32) -> Self {
+        self.center = center.clamp(-1.0, 1.0);
+        self
+    }
+
+    /// Set the deadzone
+    pub fn with_deadzone(mut self, deadzone: f32) -> Self {
+        self.deadzone = deadzone.clamp(0.0, 2.0);
+        self
+    }
+
+    /// Set the saturation point
+    pub fn with_saturation(mut self, saturation: f32) -> Self {
+        self.saturation = saturation.clamp(0.01, 2.0);
+        self
+    }
+
+    /// Rescale the range between deadzone and saturation to 0..1
+    pub fn with_rescale(mut self, rescale: bool) -> Self {
+        self.rescale = rescale;
+        self
+    }
+
+    /// Process a raw axis value
+    pub fn apply(&self, raw: f32) -> f32 {
+        let value = raw - self.center;
+        self.scale_magnitude(value.abs()) * value.signum()
+    }
+
+    /// Map a distance from center to a processed magnitude in 0..1
+    fn scale_magnitude(&self, magnitude: f32) -> f32 {
+        if magnitude < self.deadzone {
+            return 0.0;
+        }
+        let scaled = if self.rescale {
+            (magnitude - self.deadzone) / (self.saturation - self.deadzone).max(f32::EPSILON)
+        } else {
+            magnitude / self.saturation
+        };
+        scaled.min(1.0)
+    }
+}
+
+/// Calibration for every axis of one gamepad
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GamepadCalibration {
+    /// Used for axes without an override
+    pub default_axis: AxisCalibration,
+    /// Per-axis overrides
+    #[serde(serialize_with = "serialize_sorted")]
+    pub axes: HashMap<GamepadAxis, AxisCalibration>,
+    /// Apply stick deadzones to the X/Y pair's combined deflection instead of per axis
+    ///
+    /// Grouped sticks use the X axis calibration for the pair's deadzone and saturation,
+    /// which avoids the "sticky cardinal directions" of per-axis deadzones.
+    pub radial_sticks: bool,
+}
+
+impl GamepadCalibration {
+    /// Get the calibration applied to an axis
+    pub fn axis(&self, axis: GamepadAxis) -> AxisCalibration {
+        self.axes.get(&axis).copied().unwrap_or(self.default_axis)
+    }
+
+    /// Override the calibration of one axis
+    pub fn set_axis(&mut self, axis: GamepadAxis, calibration: AxisCalibration) {
+        self.axes.insert(axis, calibration);
+    }
+
+    /// Set the deadzone of every axis
+    pub fn set_deadzone(&mut self, deadzone: f32) {
+        self.default_axis = self.default_axis.with_deadzone(deadzone);
+        for calibration in self.axes.values_mut() {
+            *calibration = calibration.with_deadzone(deadzone);
+        }
+    }
+
+    /// Process a stick's raw X/Y values together, using the X axis calibration
+    pub fn apply_radial(
+        &self,
+        x_axis: GamepadAxis,
+        y_axis: GamepadAxis,
+        raw_x: f32,
+        raw_y: f32,
+    ) -> (f32, f32) {
+        let calibration = self.axis(x_axis);
+        let offset = (raw_x - calibration.center, raw_y - self.axis(y_axis).center);
+        let magnitude = (offset.0 * offset.0 + offset.1 * offset.1).sqrt();
+        if magnitude == 0.0 {
+            return (0.0, 0.0);
+        }
+        let scale = calibration.scale_magnitude(magnitude) / magnitude;
+        (offset.0 * scale, offset.1 * scale)
+    }
+}
+
+/// Write per-axis overrides in `GamepadAxis::ALL` order so saved files are stable
+fn serialize_sorted<S: Serializer>(
+    axes: &HashMap<GamepadAxis, AxisCalibration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    axes.iter()
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
+/// Live values of one axis for calibration screens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisReading {
+    pub axis: GamepadAxis,
+    /// Value reported by the hardware
+    pub raw: f32,
+    /// Value after calibration, as seen by the game
+    pub processed: f32,
+    pub calibration: AxisCalibration,
+}
 
 /// Gamepad input handler for the game engine
 ///
@@ -11,6 +162,29 @@ use std::collections::HashMap;
 pub struct GamepadInput {
     /// Connected gamepads by ID
     gamepads: HashMap<u32, GamepadState>,
+
+    /// Saved calibrations by device name, applied when a matching gamepad connects
+    calibrations: HashMap<String, GamepadCalibration>,
+
+    /// Rumble playing on each gamepad
+    rumble: HashMap<u32, Rumble>,
+
+    /// Gamepads whose rumble changed since the backend last applied it
+    rumble_changed: Vec<u32>,
+
+    /// Whether the game window has focus; rumble is stopped and ignored while it doesn't
+    focused: bool,
+}
+
+/// Force feedback playing on a gamepad
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rumble {
+    /// Strength of the low-frequency (heavy) motor, 0..1
+    pub low: f32,
+    /// Strength of the high-frequency (light) motor, 0..1
+    pub high: f32,
+    /// Seconds left to play
+    pub remaining: f32,
 }
 
 /// State of a single gamepad
@@ -28,12 +202,18 @@ pub struct GamepadState {
     /// Previous button states for detecting press/release
     pub previous_button_states: HashMap<GamepadButton, bool>,
 
-    /// Analog stick and trigger values (-1.0 to 1.0)
+    /// Analog stick and trigger values (-1.0 to 1.0) after calibration
     pub axis_values: HashMap<GamepadAxis, f32>,
 
+    /// Axis values as reported by the hardware
+    pub raw_axis_values: HashMap<GamepadAxis, f32>,
+
     /// Deadzone for analog sticks
     pub deadzone: f32,
 
+    /// Per-axis deadzone, saturation and centering
+    pub calibration: GamepadCalibration,
+
     /// Gamepad name/type
     pub name: String,
 }
@@ -47,7 +227,9 @@ impl GamepadState {
             button_states: HashMap::new(),
             previous_button_states: HashMap::new(),
             axis_values: HashMap::new(),
+            raw_axis_values: HashMap::new(),
             deadzone: 0.1,
+            calibration: GamepadCalibration::default(),
             name,
         }
     }
@@ -63,15 +245,55 @@ impl GamepadState {
         self.button_states.insert(button, pressed);
     }
 
-    /// Set axis value
+    /// Set a raw axis value and update its calibrated value
     pub fn set_axis(&mut self, axis: GamepadAxis, value: f32) {
-        // Apply deadzone
-        let deadzone_value = if value.abs() < self.deadzone {
-            0.0
-        } else {
-            value
-        };
-        self.axis_values.insert(axis, deadzone_value);
+        self.raw_axis_values.insert(axis, value);
+        self.process_axis(axis);
+    }
+
+    /// Recompute the calibrated value of an axis (and its stick partner when grouped)
+    fn process_axis(&mut self, axis: GamepadAxis) {
+        let raw = |axis| self.raw_axis_values.get(&axis).copied().unwrap_or(0.0);
+        match axis.stick_pair() {
+            Some((x_axis, y_axis)) if self.calibration.radial_sticks => {
+                let (x, y) =
+                    self.calibration
+                        .apply_radial(x_axis, y_axis, raw(x_axis), raw(y_axis));
+                self.axis_values.insert(x_axis, x);
+                self.axis_values.insert(y_axis, y);
+            }
+            _ => {
+                let value = self.calibration.axis(axis).apply(raw(axis));
+                self.axis_values.insert(axis, value);
+            }
+        }
+    }
+
+    /// Get the value the hardware reported for an axis, before calibration
+    pub fn raw_axis(&self, axis: GamepadAxis) -> f32 {
+        self.raw_axis_values.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    /// Replace the calibration and reprocess current axis values
+    pub fn set_calibration(&mut self, calibration: GamepadCalibration) {
+        self.calibration = calibration;
+        let axes: Vec<GamepadAxis> = self.raw_axis_values.keys().cloned().collect();
+        for axis in axes {
+            self.process_axis(axis);
+        }
+    }
+
+    /// Raw and processed values of every axis, for visualizing calibration live
+    pub fn axis_readings(&self) -> Vec<AxisReading> {
+        GamepadAxis::ALL
+            .iter()
+            .map(|axis| AxisReading {
+                axis: *axis,
+                raw: self.raw_axis(*axis),
+                processed: self.get_axis(*axis),
+                calibration: self.calibration.axis(*axis),
+            })
+            .collect()
     }
 
     /// Check if button is pressed
@@ -109,6 +331,9 @@ impl GamepadState {
     /// Set deadzone
     pub fn set_deadzone(&mut self, deadzone: f32) {
         self.deadzone = deadzone.clamp(0.0, 1.0);
+        let mut calibration = self.calibration.clone();
+        calibration.set_deadzone(self.deadzone);
+        self.set_calibration(calibration);
     }
 }
 
@@ -117,6 +342,10 @@ impl GamepadInput {
     pub fn new() -> Self {
         Self {
             gamepads: HashMap::new(),
+            calibrations: HashMap::new(),
+            rumble: HashMap::new(),
+            rumble_changed: Vec::new(),
+            focused: true,
         }
     }
 
@@ -129,13 +358,17 @@ impl GamepadInput {
 
     /// Add a connected gamepad
     pub fn add_gamepad(&mut self, id: u32, name: String) {
-        let gamepad = GamepadState::new(id, name.clone());
+        let mut gamepad = GamepadState::new(id, name.clone());
+        if let Some(calibration) = self.calibrations.get(&name) {
+            gamepad.set_calibration(calibration.clone());
+        }
         self.gamepads.insert(id, gamepad);
         println!("🎮 Gamepad {id} connected: {name}");
     }
 
     /// Remove a disconnected gamepad
     pub fn remove_gamepad(&mut self, id: u32) {
+        self.rumble.remove(&id);
         if let Some(gamepad) = self.gamepads.remove(&id) {
             println!("🎮 Gamepad {} disconnected: {}", id, gamepad.name);
         }
@@ -207,6 +440,61 @@ impl GamepadInput {
             gamepad.set_deadzone(deadzone);
         }
     }
+
+    /// Calibrate a gamepad and remember it for every device with the same name
+    pub fn set_calibration(&mut self, gamepad_id: u32, calibration: GamepadCalibration) {
+        if let Some(gamepad) = self.gamepads.get_mut(&gamepad_id) {
+            self.calibrations
+                .insert(gamepad.name.clone(), calibration.clone());
+            gamepad.set_calibration(calibration);
+        }
+    }
+
+    /// Get the saved calibration for a device name
+    pub fn calibration_for(&self, device_name: &str) -> Option<&GamepadCalibration> {
+        self.calibrations.get(device_name)
+    }
+
+    /// Serialize saved calibrations in the settings file format
+    ///
+    /// A TOML table per device, keyed by device name, holding `radial_sticks`,
+    /// `default_axis` and the per-axis overrides under `axes`. Missing values take their
+    /// defaults when loaded.
+    pub fn calibrations_to_settings(&self) -> String {
+        let calibrations: BTreeMap<_, _> = self.calibrations.iter().collect();
+        toml::to_string(&calibrations).expect("calibrations serialize to TOML")
+    }
+
+    /// Load calibrations from settings text, applying them to connected gamepads
+    ///
+    /// Each device in the text replaces its saved calibration; nothing changes if the text
+    /// fails to parse.
+    pub fn load_calibrations_from_settings(&mut self, settings: &str) -> Result<(), String> {
+        let loaded: HashMap<String, GamepadCalibration> =
+            toml::from_str(settings).map_err(|e| e.to_string())?;
+        self.calibrations.extend(loaded);
+
+        for gamepad in self.gamepads.values_mut() {
+            if let Some(calibration) = self.calibrations.get(&gamepad.name) {
+                gamepad.set_calibration(calibration.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Save calibrations to a settings file
+    pub fn save_calibrations(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.calibrations_to_settings())
+            .map_err(|e| format!("Failed to write calibration file '{}': {}", path, e))
+    }
+
+    /// Load calibrations from a settings file
+    pub fn load_calibrations(&mut self, path: &str) -> Result<(), String> {
+        let settings = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read calibration file '{}': {}", path, e))?;
+        self.load_calibrations_from_settings(&settings)
+            .map_err(|e| format!("{}: {}", path, e))
+    }
 }
 
 impl Default for GamepadInput {
@@ -215,6 +503,91 @@ impl Default for GamepadInput {
     }
 }
 
+impl GamepadInput {
+    /// Shake a gamepad's low-frequency (heavy) and high-frequency (light) motors, each 0..1,
+    /// for `duration` seconds, replacing any rumble already playing on it
+    ///
+    /// Ignored for unknown gamepads and while the window is unfocused. The backend plays it
+    /// (see `take_rumble_changes`); `update_rumble` counts the duration down.
+    pub fn set_rumble(&mut self, gamepad_id: u32, low: f32, high: f32, duration: f32) {
+        if !self.focused || !self.gamepads.contains_key(&gamepad_id) {
+            return;
+        }
+        if duration <= 0.0 || (low <= 0.0 && high <= 0.0) {
+            self.stop_rumble(gamepad_id);
+            return;
+        }
+        let rumble = Rumble {
+            low: low.clamp(0.0, 1.0),
+            high: high.clamp(0.0, 1.0),
+            remaining: duration,
+        };
+        self.rumble.insert(gamepad_id, rumble);
+        self.mark_rumble_changed(gamepad_id);
+    }
+
+    /// Stop a gamepad's rumble
+    pub fn stop_rumble(&mut self, gamepad_id: u32) {
+        if self.rumble.remove(&gamepad_id).is_some() {
+            self.mark_rumble_changed(gamepad_id);
+        }
+    }
+
+    /// Stop rumble on every gamepad
+    pub fn stop_all_rumble(&mut self) {
+        let ids: Vec<u32> = self.rumble.keys().copied().collect();
+        for id in ids {
+            self.stop_rumble(id);
+        }
+    }
+
+    /// The rumble playing on a gamepad, if any
+    pub fn rumble(&self, gamepad_id: u32) -> Option<Rumble> {
+        self.rumble.get(&gamepad_id).copied()
+    }
+
+    /// Count rumble durations down, stopping the ones that ran out (call each frame)
+    pub fn update_rumble(&mut self, delta_time: f32) {
+        let mut expired = Vec::new();
+        for (&id, rumble) in self.rumble.iter_mut() {
+            rumble.remaining -= delta_time;
+            if rumble.remaining <= 0.0 {
+                expired.push(id);
+            }
+        }
+        for id in expired {
+            self.stop_rumble(id);
+        }
+    }
+
+    /// Tell the gamepads whether the window has focus; losing it stops all rumble, so
+    /// controllers don't keep shaking while the player is in another window
+    pub fn set_focused(&mut self, focused: bool) {
+        if self.focused && !focused {
+            self.stop_all_rumble();
+        }
+        self.focused = focused;
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Rumble changes for the backend to apply: the new rumble, or None to stop
+    pub fn take_rumble_changes(&mut self) -> Vec<(u32, Option<Rumble>)> {
+        std::mem::take(&mut self.rumble_changed)
+            .into_iter()
+            .map(|id| (id, self.rumble.get(&id).copied()))
+            .collect()
+    }
+
+    fn mark_rumble_changed(&mut self, gamepad_id: u32) {
+        if !self.rumble_changed.contains(&gamepad_id) {
+            self.rumble_changed.push(gamepad_id);
+        }
+    }
+}
+
 /// Gamepad input event types for integration with input libraries
 #[derive(Debug, Clone)]
 pub enum GamepadEvent {
@@ -0,0 +1,204 @@
+use crate::input::gamepad::{GamepadEvent, GamepadInput, Rumble};
+use crate::input::types::{GamepadAxis, GamepadButton};
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks};
+use gilrs::{Axis, Button, EventType, Gilrs};
+use std::collections::HashMap;
+
+/// Reads real controllers through gilrs and feeds them into a `GamepadInput`
+///
+/// Gamepads already plugged in are reported as connected on the first `update`; later
+/// plugs and unplugs arrive as `GamepadEvent::Connected` and `Disconnected`. Face buttons
+/// are reported under both their position (`South`) and Xbox name (`A`), so bindings
+/// written either way work. Rumble requested with `GamepadInput::set_rumble` is played on
+/// controllers that support force feedback.
+///
+/// ```ignore
+/// let mut backend = GilrsBackend::new()?;
+/// let mut gamepads = GamepadInput::new();
+/// // each frame, before updating the InputManager
+/// gamepads.update();
+/// backend.update(&mut gamepads);
+/// gamepads.update_input_manager(&mut input_manager);
+/// ```
+pub struct GilrsBackend {
+    gilrs: Gilrs,
+    /// The gamepads present at startup have been reported
+    announced: bool,
+    /// Rumble effects playing, by gamepad
+    effects: HashMap<u32, Effect>,
+}
+
+impl GilrsBackend {
+    /// Open the platform's gamepad API
+    pub fn new() -> Result<Self, String> {
+        let gilrs = Gilrs::new().map_err(|e| format!("Failed to initialize gamepads: {}", e))?;
+        Ok(Self {
+            gilrs,
+            announced: false,
+            effects: HashMap::new(),
+        })
+    }
+
+    /// Drain pending controller events as `GamepadEvent`s
+    pub fn poll_events(&mut self) -> Vec<GamepadEvent> {
+        let mut events = Vec::new();
+        if !self.announced {
+            self.announced = true;
+            for (id, gamepad) in self.gilrs.gamepads() {
+                events.push(GamepadEvent::Connected {
+                    id: usize::from(id) as u32,
+                    name: gamepad.name().to_string(),
+                });
+            }
+        }
+        while let Some(event) = self.gilrs.next_event() {
+            let id = usize::from(event.id) as u32;
+            if event.event == EventType::Connected {
+                let name = self.gilrs.gamepad(event.id).name().to_string();
+                events.push(GamepadEvent::Connected { id, name });
+            } else {
+                events.extend(translate_event(id, event.event));
+            }
+        }
+        events
+    }
+
+    /// Apply pending controller events to `gamepads` and play its rumble changes
+    pub fn update(&mut self, gamepads: &mut GamepadInput) {
+        for event in self.poll_events() {
+            gamepads.handle_event(event);
+        }
+        for (id, rumble) in gamepads.take_rumble_changes() {
+            if let Some(effect) = self.effects.remove(&id) {
+                let _ = effect.stop();
+            }
+            if let Some(rumble) = rumble
+                && let Err(e) = self.play_rumble(id, rumble)
+            {
+                log::warn!("{}", e);
+            }
+        }
+        self.effects
+            .retain(|id, _| gamepads.get_gamepad(*id).is_some());
+    }
+
+    fn play_rumble(&mut self, id: u32, rumble: Rumble) -> Result<(), String> {
+        let Some((gamepad_id, gamepad)) = self
+            .gilrs
+            .gamepads()
+            .find(|(gamepad_id, _)| usize::from(*gamepad_id) as u32 == id)
+        else {
+            return Err(format!("Gamepad {} is not connected", id));
+        };
+        if !gamepad.is_ff_supported() {
+            return Ok(());
+        }
+
+        let duration = Ticks::from_ms((rumble.remaining * 1000.0).ceil() as u32);
+        let motor = |kind| BaseEffect {
+            kind,
+            scheduling: Replay {
+                play_for: duration,
+                ..Default::default()
+            },
+            envelope: Default::default(),
+        };
+        let magnitude = |strength: f32| (strength * u16::MAX as f32) as u16;
+        let effect = EffectBuilder::new()
+            .add_effect(motor(BaseEffectType::Strong {
+                magnitude: magnitude(rumble.low),
+            }))
+            .add_effect(motor(BaseEffectType::Weak {
+                magnitude: magnitude(rumble.high),
+            }))
+            .gamepads(&[gamepad_id])
+            .repeat(Repeat::For(duration))
+            .finish(&mut self.gilrs)
+            .map_err(|e| format!("Failed to create rumble for gamepad {}: {}", id, e))?;
+        effect
+            .play()
+            .map_err(|e| format!("Failed to play rumble on gamepad {}: {}", id, e))?;
+        self.effects.insert(id, effect);
+        Ok(())
+    }
+
+    /// The underlying gilrs context, e.g. for force feedback or mappings
+    pub fn gilrs(&self) -> &Gilrs {
+        &self.gilrs
+    }
+
+    pub fn gilrs_mut(&mut self) -> &mut Gilrs {
+        &mut self.gilrs
+    }
+}
+
+/// Engine buttons for a gilrs button; face buttons map to both naming styles
+pub fn map_button(button: Button) -> &'static [GamepadButton] {
+    use GamepadButton as B;
+    match button {
+        Button::South => &[B::South, B::A],
+        Button::East => &[B::East, B::B],
+        Button::West => &[B::West, B::X],
+        Button::North => &[B::North, B::Y],
+        Button::LeftTrigger => &[B::LeftShoulder],
+        Button::RightTrigger => &[B::RightShoulder],
+        Button::LeftTrigger2 => &[B::LeftTrigger],
+        Button::RightTrigger2 => &[B::RightTrigger],
+        Button::Select => &[B::Select],
+        Button::Start => &[B::Start],
+        Button::Mode => &[B::Guide],
+        Button::LeftThumb => &[B::LeftStick],
+        Button::RightThumb => &[B::RightStick],
+        Button::DPadUp => &[B::DPadUp],
+        Button::DPadDown => &[B::DPadDown],
+        Button::DPadLeft => &[B::DPadLeft],
+        Button::DPadRight => &[B::DPadRight],
+        Button::C | Button::Z | Button::Unknown => &[],
+    }
+}
+
+/// Engine axis for a gilrs axis; the D-pad axes are reported as buttons instead
+pub fn map_axis(axis: Axis) -> Option<GamepadAxis> {
+    match axis {
+        Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        Axis::LeftZ => Some(GamepadAxis::LeftTrigger),
+        Axis::RightZ => Some(GamepadAxis::RightTrigger),
+        Axis::DPadX | Axis::DPadY | Axis::Unknown => None,
+    }
+}
+
+/// Translate a gilrs event for gamepad `id`; analog trigger presses also update the
+/// trigger axes. `Connected` needs the device name, so it's handled by `GilrsBackend`.
+pub fn translate_event(id: u32, event: EventType) -> Vec<GamepadEvent> {
+    let buttons = |button, pressed| {
+        map_button(button)
+            .iter()
+            .map(|&button| GamepadEvent::Button {
+                id,
+                button,
+                pressed,
+            })
+            .collect()
+    };
+    match event {
+        EventType::ButtonPressed(button, _) => buttons(button, true),
+        EventType::ButtonReleased(button, _) => buttons(button, false),
+        EventType::ButtonChanged(button, value, _) => {
+            let axis = match button {
+                Button::LeftTrigger2 => GamepadAxis::LeftTrigger,
+                Button::RightTrigger2 => GamepadAxis::RightTrigger,
+                _ => return Vec::new(),
+            };
+            vec![GamepadEvent::Axis { id, axis, value }]
+        }
+        EventType::AxisChanged(axis, value, _) => map_axis(axis)
+            .map(|axis| GamepadEvent::Axis { id, axis, value })
+            .into_iter()
+            .collect(),
+        EventType::Disconnected => vec![GamepadEvent::Disconnected { id }],
+        EventType::Connected | EventType::ButtonRepeated(..) | EventType::Dropped => Vec::new(),
+    }
+}
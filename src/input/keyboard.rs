@@ -1,3 +1,4 @@
+use crate::input::text::TextInput;
 use crate::input::types::*;
 use std::collections::HashMap;
 
@@ -24,6 +25,9 @@ pub struct KeyboardInput {
 
     /// Time since key was first pressed (for repeat)
     key_times: HashMap<KeyCode, f32>,
+
+    /// Characters typed, for text fields
+    text_input: TextInput,
 }
 
 impl KeyboardInput {
@@ -37,6 +41,7 @@ impl KeyboardInput {
             repeat_delay: 0.5, // 500ms delay before repeat starts
             repeat_rate: 0.05, // 50ms between repeats
             key_times: HashMap::new(),
+            text_input: TextInput::new(),
         }
     }
 
@@ -141,6 +146,15 @@ impl KeyboardInput {
         self.repeat_rate = rate;
     }
 
+    /// Characters typed, as opposed to keys pressed
+    pub fn text_input(&self) -> &TextInput {
+        &self.text_input
+    }
+
+    pub fn text_input_mut(&mut self) -> &mut TextInput {
+        &mut self.text_input
+    }
+
     /// Update the InputManager with current keyboard state
     pub fn update_input_manager(&self, input_manager: &mut crate::input::manager::InputManager) {
         // Update all key states
@@ -160,9 +174,20 @@ impl Default for KeyboardInput {
 /// Keyboard input event types for integration with window systems
 #[derive(Debug, Clone)]
 pub enum KeyboardEvent {
-    KeyPress { key: KeyCode },
-    KeyRelease { key: KeyCode },
-    TextInput { text: String },
+    KeyPress {
+        key: KeyCode,
+    },
+    KeyRelease {
+        key: KeyCode,
+    },
+    TextInput {
+        text: String,
+    },
+    /// IME composition in progress; empty text ends it
+    Composition {
+        text: String,
+        cursor: usize,
+    },
 }
 
 impl KeyboardInput {
@@ -176,9 +201,10 @@ impl KeyboardInput {
                 self.handle_key_release(key);
             }
             KeyboardEvent::TextInput { text } => {
-                // Handle text input (for text fields, etc.)
-                // This could be used for chat, console input, etc.
-                println!("Text input: {text}");
+                self.text_input.handle_text(&text);
+            }
+            KeyboardEvent::Composition { text, cursor } => {
+                self.text_input.handle_composition(&text, cursor);
             }
         }
     }
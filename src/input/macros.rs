@@ -137,6 +137,11 @@ macro_rules! input_bindings {
         }
     };
 
+    // Raw mouse movement or scroll delta, scaled by the action's sensitivity
+    (mouse_delta($axis:ident)) => {
+        InputBinding::Single(PhysicalInput::MouseAxis(MouseAxis::$axis))
+    };
+
     // Gamepad axis with threshold and deadzone
     (gamepad_axis($axis:ident, $threshold:expr, $deadzone:expr)) => {
         InputBinding::Analog {
@@ -146,6 +151,17 @@ macro_rules! input_bindings {
         }
     };
 
+    // Gamepad stick axis with a radial deadzone, rescaled from its edge
+    (gamepad_stick($axis:ident, $threshold:expr, $deadzone:expr)) => {
+        InputBinding::AnalogStick {
+            input: PhysicalInput::GamepadAxis(GamepadAxis::$axis),
+            threshold: $threshold,
+            deadzone: $deadzone,
+            shape: DeadzoneShape::Radial,
+            curve: ResponseCurve::Linear,
+        }
+    };
+
     // Modifier + key combination
     (modifier($mod:ident, $key:ident)) => {
         InputBinding::Modified {
@@ -158,6 +174,14 @@ macro_rules! input_bindings {
     (combo($($input:expr),+)) => {
         InputBinding::Combo(vec![$($input),+])
     };
+
+    // Chords entered in order, each within the window (in seconds) of the previous one
+    (sequence($window:expr; $([$($input:expr),+]),+)) => {
+        InputBinding::Sequence {
+            steps: vec![$(vec![$($input),+]),+],
+            window: $window,
+        }
+    };
 }
 
 /// Macro for creating common action categories
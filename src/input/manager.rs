@@ -1,7 +1,18 @@
-use std::collections::HashMap;
+use glam::Vec2;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
 use crate::input::types::*;
+use crate::render::viewport::ViewMapping;
+
+/// Priority of the contexts pushed for UI modals, above any gameplay context
+pub const MODAL_CONTEXT_PRIORITY: u32 = u32::MAX;
+
+/// Longest gap in seconds between presses that still counts as a multi-tap
+pub const DEFAULT_TAP_WINDOW: f32 = 0.25;
+
+/// Seconds an action must be held before it counts as a long hold instead of a tap
+pub const DEFAULT_HOLD_THRESHOLD: f32 = 0.3;
 
 /// Main input manager for handling game actions and input state
 ///
@@ -23,16 +34,138 @@ pub struct InputManager {
     /// Raw input values for analog inputs
     raw_values: HashMap<PhysicalInput, f32>,
 
+    /// Curve, sensitivity and inversion for analog actions
+    analog_settings: HashMap<String, AnalogSettings>,
+
+    /// Player-chosen bindings used instead of an action's `default_bindings`
+    binding_overrides: HashMap<String, Vec<InputBinding>>,
+
     /// Active input contexts (stack-based)
     active_contexts: Vec<InputContext>,
 
+    /// Open UI modals, innermost last, with whether each pauses the game
+    modals: Vec<(String, bool)>,
+
+    /// Actions consumed by another layer or held when a modal closed, ignored until their
+    /// inputs are released
+    suppressed_actions: HashSet<String>,
+
+    /// Progress through each sequence binding in use
+    sequence_progress: HashMap<InputBinding, SequenceProgress>,
+
+    /// Seconds of updates so far, for sequence and tap timing windows
+    time: f64,
+
+    /// Presses in each action's current tap streak, and when the last one was
+    tap_counts: HashMap<String, (u32, f64)>,
+
+    /// Tap windows of actions that don't use `DEFAULT_TAP_WINDOW`
+    tap_windows: HashMap<String, f32>,
+
+    /// When each action was last pressed and, once let go, released
+    press_times: HashMap<String, (f64, Option<f64>)>,
+
+    /// Last frame's and this frame's tap/hold state of each action
+    tap_hold_states: HashMap<String, (TapHoldState, TapHoldState)>,
+
+    /// Hold thresholds of actions that don't use `DEFAULT_HOLD_THRESHOLD`
+    hold_thresholds: HashMap<String, f32>,
+
+    /// Action waiting for the player to press its new input
+    rebind: Option<Rebind>,
+
+    /// How the last rebind turned out, until taken
+    rebind_result: Option<RebindResult>,
+
+    /// Cursor position in window screen coordinates, top-left origin; None outside the window
+    cursor_position: Option<Vec2>,
+
+    /// Where the view sits in the window
+    view_mapping: Option<ViewMapping>,
+
+    /// Camera position the world is drawn against
+    camera_position: Vec2,
+
     /// Input event history for debugging
     input_history: Vec<InputEvent>,
 
+    /// Timestamp of the events the last `update` generated
+    last_event_time: Option<Instant>,
+
     /// Maximum history size
     max_history_size: usize,
 }
 
+/// How far the player is through a `Sequence` binding
+#[derive(Debug, Default)]
+struct SequenceProgress {
+    /// Steps entered so far
+    step: usize,
+    /// When the last step was entered
+    step_time: f64,
+    /// Which of the sequence's inputs were held last frame
+    last_active: Vec<bool>,
+    /// The final step was entered and its chord is still held
+    completed: bool,
+}
+
+impl SequenceProgress {
+    fn advance(
+        &mut self,
+        steps: &[Vec<PhysicalInput>],
+        window: f32,
+        inputs: &[&PhysicalInput],
+        active: Vec<bool>,
+        time: f64,
+    ) {
+        // A step matches when exactly its inputs are held, and counts on the frame it starts
+        let matches = |step: &[PhysicalInput], active: &[bool]| {
+            inputs
+                .iter()
+                .zip(active)
+                .all(|(input, &held)| held == step.contains(input))
+        };
+        let mut last = std::mem::replace(&mut self.last_active, active);
+        last.resize(inputs.len(), false);
+        let active = &self.last_active;
+        let entered = |step: &[PhysicalInput]| matches(step, active) && !matches(step, &last);
+
+        if self.completed {
+            self.completed = steps.last().is_some_and(|step| matches(step, active));
+            if self.completed {
+                return;
+            }
+        }
+        if self.step > 0 && time - self.step_time > window as f64 {
+            self.step = 0;
+        }
+        let Some(step) = steps.get(self.step) else {
+            return;
+        };
+        if entered(step) {
+            self.step += 1;
+        } else if self.step > 0 && entered(&steps[0]) {
+            // Starting over partway through
+            self.step = 1;
+        } else {
+            return;
+        }
+        self.step_time = time;
+        if self.step == steps.len() {
+            self.step = 0;
+            self.completed = true;
+        }
+    }
+}
+
+/// A rebind in progress
+struct Rebind {
+    action_id: String,
+    options: RebindOptions,
+    /// Inputs held last frame; these are only captured once pressed again
+    held: HashSet<PhysicalInput>,
+}
+
 impl InputManager {
     /// Create a new InputManager
     pub fn new() -> Self {
@@ -41,8 +174,25 @@ impl InputManager {
             action_states: HashMap::new(),
             raw_inputs: HashMap::new(),
             raw_values: HashMap::new(),
+            analog_settings: HashMap::new(),
+            binding_overrides: HashMap::new(),
             active_contexts: Vec::new(),
+            modals: Vec::new(),
+            suppressed_actions: HashSet::new(),
+            sequence_progress: HashMap::new(),
+            time: 0.0,
+            tap_counts: HashMap::new(),
+            tap_windows: HashMap::new(),
+            press_times: HashMap::new(),
+            tap_hold_states: HashMap::new(),
+            hold_thresholds: HashMap::new(),
+            rebind: None,
+            rebind_result: None,
+            cursor_position: None,
+            view_mapping: None,
+            camera_position: Vec2::ZERO,
             input_history: Vec::new(),
+            last_event_time: None,
             max_history_size: 1000,
         }
     }
@@ -72,7 +222,11 @@ impl InputManager {
     }
 
     /// Update the input manager (call each frame)
-    pub fn update(&mut self, _delta_time: f32) {
+    pub fn update(&mut self, delta_time: f32) {
+        self.time += delta_time as f64;
+        self.update_sequences();
+        let rebind_input = self.update_rebind();
+
         // Update action states based on current raw inputs
         let action_ids: Vec<_> = self.actions.keys().cloned().collect();
         for action_id in action_ids {
@@ -86,11 +240,39 @@ impl InputManager {
 
                 // Update state if it changed
                 if current_state != new_state {
-                    self.action_states.insert(action_id, new_state);
+                    match new_state {
+                        InputState::Pressed => {
+                            self.count_tap(&action_id);
+                            self.press_times
+                                .insert(action_id.clone(), (self.time, None));
+                        }
+                        InputState::Released => {
+                            if let Some((_, released)) = self.press_times.get_mut(&action_id) {
+                                *released = Some(self.time);
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.action_states.insert(action_id.clone(), new_state);
                 }
+                self.classify_hold(&action_id);
             }
         }
 
+        // The input a rebind captured or was cancelled with doesn't also trigger actions
+        if let Some(input) = rebind_input {
+            self.consume_input(&input);
+        }
+
+        // Inputs held through a modal stay ignored until they are let go
+        let action_states = &self.action_states;
+        self.suppressed_actions.retain(|action_id| {
+            matches!(
+                action_states.get(action_id),
+                Some(InputState::Pressed | InputState::Held)
+            )
+        });
+
         // Generate events for state changes
         self.generate_action_events();
 
@@ -104,8 +286,8 @@ impl InputManager {
     /// Calculate the new state for an action based on its bindings
     fn calculate_action_state(&self, action: &GameAction) -> InputState {
         // Check if any binding for this action is active
-        let any_binding_active = action
-            .default_bindings
+        let any_binding_active = self
+            .bindings(&action.id)
             .iter()
             .any(|binding| self.is_binding_active(binding));
 
@@ -140,7 +322,49 @@ impl InputManager {
             InputBinding::Analog {
                 input, threshold, ..
             } => self.get_physical_input_value(input).abs() > *threshold,
+
+            InputBinding::AnalogStick { threshold, .. } => self
+                .get_binding_value(binding)
+                .is_some_and(|value| value.abs() > *threshold),
+
+            InputBinding::Sequence { .. } => self
+                .sequence_progress
+                .get(binding)
+                .is_some_and(|progress| progress.completed),
+        }
+    }
+
+    /// Advance every sequence binding of the registered actions by this frame's inputs
+    fn update_sequences(&mut self) {
+        let sequences: HashSet<InputBinding> = self
+            .actions
+            .keys()
+            .flat_map(|action_id| self.bindings(action_id))
+            .filter(|binding| matches!(binding, InputBinding::Sequence { .. }))
+            .cloned()
+            .collect();
+        for binding in &sequences {
+            let InputBinding::Sequence { steps, window } = binding else {
+                continue;
+            };
+            let mut inputs: Vec<&PhysicalInput> = Vec::new();
+            for input in steps.iter().flatten() {
+                if !inputs.contains(&input) {
+                    inputs.push(input);
+                }
+            }
+            let active: Vec<bool> = inputs
+                .iter()
+                .map(|input| self.is_physical_input_active(input))
+                .collect();
+            let time = self.time;
+            self.sequence_progress
+                .entry(binding.clone())
+                .or_default()
+                .advance(steps, *window, &inputs, active, time);
         }
+        self.sequence_progress
+            .retain(|binding, _| sequences.contains(binding));
     }
 
     /// Check if a physical input is currently active
@@ -222,27 +446,13 @@ impl InputManager {
                         0.0
                     }
                 }
-                InputType::Analog => {
-                    // Get analog value from bindings
-                    for binding in &action.default_bindings {
-                        if let Some(value) = self.get_binding_value(binding) {
-                            return value;
-                        }
-                    }
-                    0.0
-                }
+                InputType::Analog => self.analog_action_value(action_id),
                 InputType::Hybrid => {
                     // Can be both digital and analog
                     if self.is_action_pressed(action_id) || self.is_action_held(action_id) {
                         1.0
                     } else {
-                        // Check for analog value
-                        for binding in &action.default_bindings {
-                            if let Some(value) = self.get_binding_value(binding) {
-                                return value;
-                            }
-                        }
-                        0.0
+                        self.analog_action_value(action_id)
                     }
                 }
             }
@@ -251,9 +461,413 @@ impl InputManager {
         }
     }
 
+    /// The shaped value of the action's analog bindings, taking whichever is pushed furthest
+    /// so an idle mouse doesn't hide a stick bound to the same action
+    fn analog_action_value(&self, action_id: &str) -> f32 {
+        let settings = self.analog_settings.get(action_id);
+        self.bindings(action_id)
+            .iter()
+            .filter_map(|binding| {
+                let value = self.get_binding_value(binding)?;
+                Some(match (settings, binding) {
+                    (None, _) => value,
+                    (Some(settings), InputBinding::Single(PhysicalInput::MouseAxis(_))) => {
+                        settings.apply_delta(value)
+                    }
+                    (Some(settings), _) => settings.apply(value),
+                })
+            })
+            .fold(0.0, |furthest: f32, value| {
+                if value.abs() > furthest.abs() {
+                    value
+                } else {
+                    furthest
+                }
+            })
+    }
+
+    /// Add a press to the action's tap streak, starting a new one if the window has passed
+    fn count_tap(&mut self, action_id: &str) {
+        let window = self.tap_window(action_id) as f64;
+        let time = self.time;
+        let (count, last_time) = self
+            .tap_counts
+            .entry(action_id.to_string())
+            .or_insert((0, f64::NEG_INFINITY));
+        *count = if time - *last_time <= window {
+            *count + 1
+        } else {
+            1
+        };
+        *last_time = time;
+    }
+
+    /// Set the longest gap between presses of an action that counts as a multi-tap
+    pub fn set_tap_window(&mut self, action_id: &str, seconds: f32) {
+        self.tap_windows.insert(action_id.to_string(), seconds);
+    }
+
+    /// The action's multi-tap window in seconds
+    pub fn tap_window(&self, action_id: &str) -> f32 {
+        self.tap_windows
+            .get(action_id)
+            .copied()
+            .unwrap_or(DEFAULT_TAP_WINDOW)
+    }
+
+    /// Presses in the action's current tap streak: 1 for a single press, 2 after a double tap,
+    /// and 0 once the window after the last press has passed
+    pub fn tap_count(&self, action_id: &str) -> u32 {
+        match self.tap_counts.get(action_id) {
+            Some(&(count, last_time))
+                if self.time - last_time <= self.tap_window(action_id) as f64 =>
+            {
+                count
+            }
+            _ => 0,
+        }
+    }
+
+    /// Check if an action was pressed for the `taps`th time in a row this frame, each press
+    /// within its tap window of the previous one
+    pub fn is_action_multi_tapped(&self, action_id: &str, taps: u32) -> bool {
+        self.is_action_pressed(action_id) && self.tap_count(action_id) == taps
+    }
+
+    /// Check if an action was pressed for the second time in quick succession this frame,
+    /// e.g. to dash on a double-tapped direction
+    pub fn is_action_double_tapped(&self, action_id: &str) -> bool {
+        self.is_action_multi_tapped(action_id, 2)
+    }
+
+    /// Seconds the action has been held for, including this frame's press or release;
+    /// 0 while it's up, so on release this is how long a charge attack was charged
+    pub fn action_held_duration(&self, action_id: &str) -> f32 {
+        let Some(&(pressed, released)) = self.press_times.get(action_id) else {
+            return 0.0;
+        };
+        match (self.action_states.get(action_id), released) {
+            (Some(InputState::Pressed | InputState::Held), _) => (self.time - pressed) as f32,
+            (Some(InputState::Released), Some(released)) if released == self.time => {
+                (released - pressed) as f32
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Move the action's tap/hold state on by this frame's state and held duration
+    fn classify_hold(&mut self, action_id: &str) {
+        let previous = self.tap_hold_state_unchecked(action_id);
+        let held_long = self.action_held_duration(action_id) >= self.hold_threshold(action_id);
+        let next = match self.action_states.get(action_id) {
+            Some(InputState::Pressed | InputState::Held) if held_long => TapHoldState::HeldLong,
+            Some(InputState::Pressed | InputState::Held) => TapHoldState::Holding,
+            Some(InputState::Released) if previous == TapHoldState::Holding => TapHoldState::Tapped,
+            _ => TapHoldState::Idle,
+        };
+        self.tap_hold_states
+            .insert(action_id.to_string(), (previous, next));
+    }
+
+    fn tap_hold_state_unchecked(&self, action_id: &str) -> TapHoldState {
+        self.tap_hold_states
+            .get(action_id)
+            .map_or(TapHoldState::Idle, |&(_, state)| state)
+    }
+
+    /// Set how long an action must be held before it's a long hold rather than a tap
+    pub fn set_hold_threshold(&mut self, action_id: &str, seconds: f32) {
+        self.hold_thresholds.insert(action_id.to_string(), seconds);
+    }
+
+    /// The action's hold threshold in seconds
+    pub fn hold_threshold(&self, action_id: &str) -> f32 {
+        self.hold_thresholds
+            .get(action_id)
+            .copied()
+            .unwrap_or(DEFAULT_HOLD_THRESHOLD)
+    }
+
+    /// Whether the action's current press is a tap or a long hold
+    pub fn tap_hold_state(&self, action_id: &str) -> TapHoldState {
+        if !self.is_action_enabled(action_id) {
+            return TapHoldState::Idle;
+        }
+        self.tap_hold_state_unchecked(action_id)
+    }
+
+    /// Check if an action was let go this frame before its hold threshold
+    pub fn is_action_tapped(&self, action_id: &str) -> bool {
+        self.tap_hold_state(action_id) == TapHoldState::Tapped
+    }
+
+    /// Check if an action has been held for at least its hold threshold
+    pub fn is_action_held_long(&self, action_id: &str) -> bool {
+        self.tap_hold_state(action_id) == TapHoldState::HeldLong
+    }
+
+    /// Check if an action reached its hold threshold this frame, e.g. to open a context menu
+    pub fn is_action_held_long_started(&self, action_id: &str) -> bool {
+        self.is_action_held_long(action_id)
+            && self
+                .tap_hold_states
+                .get(action_id)
+                .is_some_and(|&(previous, _)| previous != TapHoldState::HeldLong)
+    }
+
+    /// Set the curve, sensitivity and inversion for an analog action
+    pub fn set_analog_settings(&mut self, action_id: &str, settings: AnalogSettings) {
+        self.analog_settings.insert(action_id.to_string(), settings);
+    }
+
+    /// Get the analog settings for an action (defaults if none were set)
+    pub fn analog_settings(&self, action_id: &str) -> AnalogSettings {
+        self.analog_settings
+            .get(action_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Reset an action's analog settings to the defaults
+    pub fn reset_analog_settings(&mut self, action_id: &str) {
+        self.analog_settings.remove(action_id);
+    }
+
+    /// Set the response curve for an analog action
+    pub fn set_action_curve(&mut self, action_id: &str, curve: ResponseCurve) {
+        self.analog_settings_mut(action_id).curve = curve;
+    }
+
+    /// Set the sensitivity multiplier for an analog action
+    pub fn set_action_sensitivity(&mut self, action_id: &str, sensitivity: f32) {
+        self.analog_settings_mut(action_id).sensitivity = sensitivity;
+    }
+
+    /// Set whether an analog action's value is inverted
+    pub fn set_action_inverted(&mut self, action_id: &str, inverted: bool) {
+        self.analog_settings_mut(action_id).inverted = inverted;
+    }
+
+    fn analog_settings_mut(&mut self, action_id: &str) -> &mut AnalogSettings {
+        self.analog_settings
+            .entry(action_id.to_string())
+            .or_default()
+    }
+
+    /// Bindings in effect for an action: its overrides if rebound, else its defaults
+    pub fn bindings(&self, action_id: &str) -> &[InputBinding] {
+        match self.binding_overrides.get(action_id) {
+            Some(bindings) => bindings,
+            None => self
+                .actions
+                .get(action_id)
+                .map_or(&[], |action| &action.default_bindings),
+        }
+    }
+
+    /// Rebind an action, replacing its default bindings until reset
+    pub fn set_bindings(
+        &mut self,
+        action_id: &str,
+        bindings: Vec<InputBinding>,
+    ) -> Result<(), String> {
+        if !self.actions.contains_key(action_id) {
+            return Err(format!("Unknown action '{}'", action_id));
+        }
+        self.binding_overrides
+            .insert(action_id.to_string(), bindings);
+        Ok(())
+    }
+
+    /// Go back to an action's default bindings
+    pub fn reset_bindings(&mut self, action_id: &str) {
+        self.binding_overrides.remove(action_id);
+    }
+
+    /// Go back to the default bindings of every action
+    pub fn reset_all_bindings(&mut self) {
+        self.binding_overrides.clear();
+    }
+
+    /// Rebound actions and their bindings
+    pub fn binding_overrides(&self) -> &HashMap<String, Vec<InputBinding>> {
+        &self.binding_overrides
+    }
+
+    /// Listen for the next input pressed and bind it to an action, e.g. when the player
+    /// picks an action in the controls menu
+    ///
+    /// Inputs already held when listening starts have to be let go and pressed again, and
+    /// the captured input doesn't also trigger any action. Poll `take_rebind_result` each
+    /// frame after `update` to see how it went.
+    pub fn start_rebind(&mut self, action_id: &str) -> Result<(), String> {
+        self.start_rebind_with(action_id, RebindOptions::default())
+    }
+
+    /// `start_rebind` with filtering, a cancel input, the binding slot and conflict handling
+    pub fn start_rebind_with(
+        &mut self,
+        action_id: &str,
+        options: RebindOptions,
+    ) -> Result<(), String> {
+        if !self.actions.contains_key(action_id) {
+            return Err(format!("Unknown action '{}'", action_id));
+        }
+        let held = self.held_inputs(options.axis_threshold);
+        self.rebind = Some(Rebind {
+            action_id: action_id.to_string(),
+            options,
+            held,
+        });
+        self.rebind_result = None;
+        Ok(())
+    }
+
+    /// Stop listening without changing the action's bindings
+    pub fn cancel_rebind(&mut self) {
+        if let Some(rebind) = self.rebind.take() {
+            self.rebind_result = Some(RebindResult::Cancelled {
+                action_id: rebind.action_id,
+            });
+        }
+    }
+
+    pub fn is_rebinding(&self) -> bool {
+        self.rebind.is_some()
+    }
+
+    /// The action waiting for a new input
+    pub fn rebinding_action(&self) -> Option<&str> {
+        self.rebind.as_ref().map(|rebind| rebind.action_id.as_str())
+    }
+
+    /// Take how the rebind went: bound, cancelled, or a conflict it's still listening past
+    pub fn take_rebind_result(&mut self) -> Option<RebindResult> {
+        self.rebind_result.take()
+    }
+
+    /// Actions in the same category as `action_id` with a binding that uses the input;
+    /// actions in other categories, like UI and gameplay, can share inputs
+    pub fn binding_conflicts(&self, action_id: &str, input: &PhysicalInput) -> Vec<String> {
+        let Some(category) = self.actions.get(action_id).map(|action| &action.category) else {
+            return Vec::new();
+        };
+        let mut conflicts: Vec<String> = self
+            .actions
+            .values()
+            .filter(|action| action.id != action_id && action.category == *category)
+            .filter(|action| {
+                self.bindings(&action.id)
+                    .iter()
+                    .any(|binding| binding.uses(input))
+            })
+            .map(|action| action.id.clone())
+            .collect();
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Buttons held and axes pushed past `axis_threshold`
+    fn held_inputs(&self, axis_threshold: f32) -> HashSet<PhysicalInput> {
+        let buttons = self
+            .raw_inputs
+            .iter()
+            .filter(|&(_, &pressed)| pressed)
+            .map(|(input, _)| input.clone());
+        let axes = self
+            .raw_values
+            .iter()
+            .filter(|&(_, value)| value.abs() >= axis_threshold)
+            .map(|(input, _)| input.clone());
+        buttons.chain(axes).collect()
+    }
+
+    /// Capture this frame's newly pressed input for the rebind in progress; returns the
+    /// input it used up, if any
+    fn update_rebind(&mut self) -> Option<PhysicalInput> {
+        let mut rebind = self.rebind.take()?;
+        let held = self.held_inputs(rebind.options.axis_threshold);
+        let pressed: Vec<PhysicalInput> = held
+            .iter()
+            .filter(|input| !rebind.held.contains(input))
+            .cloned()
+            .collect();
+        rebind.held = held;
+
+        let cancel = rebind.options.cancel.clone();
+        if let Some(cancel) = cancel.filter(|cancel| pressed.contains(cancel)) {
+            self.rebind_result = Some(RebindResult::Cancelled {
+                action_id: rebind.action_id,
+            });
+            return Some(cancel);
+        }
+        let Some(input) = pressed
+            .into_iter()
+            .find(|input| rebind.options.accepts(input))
+        else {
+            self.rebind = Some(rebind);
+            return None;
+        };
+
+        let conflicts = self.binding_conflicts(&rebind.action_id, &input);
+        if !conflicts.is_empty() {
+            match rebind.options.conflict {
+                RebindConflict::Reject => {
+                    self.rebind_result = Some(RebindResult::Conflict {
+                        action_id: rebind.action_id.clone(),
+                        input: input.clone(),
+                        conflicts,
+                    });
+                    self.rebind = Some(rebind);
+                    return Some(input);
+                }
+                RebindConflict::Replace => {
+                    for action_id in &conflicts {
+                        let bindings = self
+                            .bindings(action_id)
+                            .iter()
+                            .filter(|binding| !binding.uses(&input))
+                            .cloned()
+                            .collect();
+                        self.binding_overrides.insert(action_id.clone(), bindings);
+                    }
+                }
+                RebindConflict::Allow => {}
+            }
+        }
+
+        let binding = match &input {
+            // Values from `GamepadInput` already have its deadzone applied
+            PhysicalInput::GamepadAxis(_) => InputBinding::AnalogStick {
+                input: input.clone(),
+                threshold: rebind.options.axis_threshold,
+                deadzone: 0.0,
+                shape: DeadzoneShape::Radial,
+                curve: ResponseCurve::Linear,
+            },
+            _ => InputBinding::Single(input.clone()),
+        };
+        let mut bindings = self.bindings(&rebind.action_id).to_vec();
+        match bindings.get_mut(rebind.options.slot) {
+            Some(slot) => *slot = binding.clone(),
+            None => bindings.push(binding.clone()),
+        }
+        self.binding_overrides
+            .insert(rebind.action_id.clone(), bindings);
+        self.rebind_result = Some(RebindResult::Bound {
+            action_id: rebind.action_id,
+            binding,
+        });
+        Some(input)
+    }
+
     /// Get the value of a binding
     fn get_binding_value(&self, binding: &InputBinding) -> Option<f32> {
         match binding {
+            // Mouse movement and scroll deltas pass through unclamped
+            InputBinding::Single(input @ PhysicalInput::MouseAxis(_)) => {
+                Some(self.get_physical_input_value(input))
+            }
             InputBinding::Analog {
                 input,
                 threshold,
@@ -270,12 +884,101 @@ impl InputManager {
                     Some(raw_value)
                 }
             }
+            InputBinding::AnalogStick {
+                input,
+                deadzone,
+                shape,
+                curve,
+                ..
+            } => Some(self.stick_value(input, *deadzone, *shape, curve)),
             _ => None,
         }
     }
 
+    /// Set the cursor position in window screen coordinates, as reported by the window
+    pub fn set_cursor_position(&mut self, position: Vec2) {
+        self.cursor_position = Some(position);
+    }
+
+    /// Forget the cursor position, e.g. when it leaves the window
+    pub fn clear_cursor_position(&mut self) {
+        self.cursor_position = None;
+    }
+
+    pub fn cursor_position(&self) -> Option<Vec2> {
+        self.cursor_position
+    }
+
+    /// Set where the view sits in the window; refresh it from `UpdateContext::view` each
+    /// frame to follow resizes
+    pub fn set_view_mapping(&mut self, view_mapping: ViewMapping) {
+        self.view_mapping = Some(view_mapping);
+    }
+
+    pub fn view_mapping(&self) -> Option<&ViewMapping> {
+        self.view_mapping.as_ref()
+    }
+
+    /// Set the camera position, as passed to `SpriteRenderer::set_camera_position`
+    pub fn set_camera_position(&mut self, position: Vec2) {
+        self.camera_position = position;
+    }
+
+    pub fn camera_position(&self) -> Vec2 {
+        self.camera_position
+    }
+
+    /// Cursor position as 0..1 across the view with y down, like `SimpleTextRenderer`
+    /// positions; None without a view mapping or over a letterbox bar
+    pub fn mouse_viewport_position(&self) -> Option<Vec2> {
+        self.view_mapping?
+            .cursor_to_normalized(self.cursor_position?)
+    }
+
+    /// Cursor position in the viewport's logical coordinates, y up
+    pub fn mouse_logical_position(&self) -> Option<Vec2> {
+        self.view_mapping?.cursor_to_logical(self.cursor_position?)
+    }
+
+    /// Cursor position in world coordinates under the camera, for clicking on entities
+    pub fn mouse_world_position(&self) -> Option<Vec2> {
+        Some(self.mouse_logical_position()? + self.camera_position)
+    }
+
+    /// An axis with the deadzone rescaled away and the curve applied to its deflection
+    fn stick_value(
+        &self,
+        input: &PhysicalInput,
+        deadzone: f32,
+        shape: DeadzoneShape,
+        curve: &ResponseCurve,
+    ) -> f32 {
+        let raw_value = self.get_physical_input_value(input);
+        let stick = match (shape, input) {
+            (DeadzoneShape::Radial, PhysicalInput::GamepadAxis(axis)) => axis.stick_pair(),
+            _ => None,
+        };
+        let magnitude = match stick {
+            Some((x, y)) => Vec2::new(
+                self.get_physical_input_value(&PhysicalInput::GamepadAxis(x)),
+                self.get_physical_input_value(&PhysicalInput::GamepadAxis(y)),
+            )
+            .length(),
+            None => raw_value.abs(),
+        };
+        if magnitude <= deadzone || raw_value == 0.0 {
+            return 0.0;
+        }
+        let deflection = ((magnitude - deadzone) / (1.0 - deadzone).max(f32::EPSILON)).min(1.0);
+        // This axis's share of the stick's direction, scaled to the curved deflection
+        raw_value / magnitude * curve.evaluate(deflection)
+    }
+
     /// Check if an action is enabled in the current context
     pub fn is_action_enabled(&self, action_id: &str) -> bool {
+        if self.suppressed_actions.contains(action_id) {
+            return false;
+        }
         if let Some(action) = self.actions.get(action_id) {
             // Check if context is required
             if let Some(required_context) = &action.metadata.context_required {
@@ -303,6 +1006,68 @@ impl InputManager {
         true
     }
 
+    /// Mark an action's current press handled, e.g. by the UI, so lower layers checking it
+    /// see nothing until it's released; returns whether it was down to consume
+    ///
+    /// Call it after `update`, before lower layers run. The press's `ActionTriggered` event
+    /// from this frame's `update` is dropped from the history.
+    pub fn consume_action(&mut self, action_id: &str) -> bool {
+        let down = matches!(
+            self.action_states.get(action_id),
+            Some(InputState::Pressed | InputState::Held)
+        );
+        if down {
+            self.suppressed_actions.insert(action_id.to_string());
+            if let Some(frame_time) = self.last_event_time {
+                self.input_history.retain(|event| {
+                    !matches!(
+                        event,
+                        InputEvent::ActionTriggered { action_id: id, timestamp, .. }
+                            if id == action_id && *timestamp == frame_time
+                    )
+                });
+            }
+        }
+        down
+    }
+
+    /// Consume every held action bound to a physical input, so a click or key handled by
+    /// a higher layer doesn't also trigger gameplay actions
+    ///
+    /// ```
+    /// # use engine_2d::input::{InputManager, MouseButton, PhysicalInput};
+    /// # let mut input = InputManager::new();
+    /// # let delta_time = 0.016;
+    /// # let ui_wants_pointer = true;
+    /// input.update(delta_time);
+    /// if ui_wants_pointer {
+    ///     input.consume_input(&PhysicalInput::Mouse(MouseButton::Left));
+    /// }
+    /// if input.is_action_pressed("SHOOT") {
+    ///     // not when the click was on a button
+    /// }
+    /// ```
+    pub fn consume_input(&mut self, input: &PhysicalInput) {
+        let action_ids: Vec<String> = self
+            .actions
+            .keys()
+            .filter(|action_id| {
+                self.bindings(action_id)
+                    .iter()
+                    .any(|binding| binding.uses(input))
+            })
+            .cloned()
+            .collect();
+        for action_id in action_ids {
+            self.consume_action(&action_id);
+        }
+    }
+
+    /// Check if an action's press was consumed, or is still ignored after a modal closed
+    pub fn is_action_consumed(&self, action_id: &str) -> bool {
+        self.suppressed_actions.contains(action_id)
+    }
+
     /// Push a new input context
     pub fn push_context(&mut self, context: InputContext) {
         self.active_contexts.push(context);
@@ -317,6 +1082,73 @@ impl InputManager {
     /// Clear all input contexts
     pub fn clear_contexts(&mut self) {
         self.active_contexts.clear();
+        self.modals.clear();
+    }
+
+    /// Open a UI modal, leaving only UI actions enabled until it closes
+    ///
+    /// Modals nest; `pauses_game` marks menus that should stop the game while open.
+    pub fn open_modal(&mut self, name: &str, pauses_game: bool) {
+        let mut context = InputContext::new(name.to_string(), MODAL_CONTEXT_PRIORITY);
+        context.disabled_actions = self
+            .actions
+            .values()
+            .filter(|action| action.category != ActionCategory::UI)
+            .map(|action| action.id.clone())
+            .collect();
+        self.push_context(context);
+
+        let old_context = self.modals.last().map(|(name, _)| name.clone());
+        self.modals.push((name.to_string(), pauses_game));
+        self.input_history.push(InputEvent::ContextChanged {
+            old_context,
+            new_context: Some(name.to_string()),
+        });
+    }
+
+    /// Close the innermost UI modal, restoring the input state from before it opened
+    ///
+    /// Gameplay actions whose inputs are still held stay disabled until released, so the
+    /// key that confirmed a menu doesn't also trigger an action bound to it in game.
+    pub fn close_modal(&mut self) -> Option<String> {
+        let (name, _) = self.modals.pop()?;
+        let index = self.active_contexts.iter().rposition(|context| {
+            context.name == name && context.priority == MODAL_CONTEXT_PRIORITY
+        });
+        if let Some(context) = index.map(|index| self.active_contexts.remove(index)) {
+            for action_id in context.disabled_actions {
+                if matches!(
+                    self.action_states.get(&action_id),
+                    Some(InputState::Pressed | InputState::Held)
+                ) {
+                    self.suppressed_actions.insert(action_id);
+                }
+            }
+        }
+
+        self.input_history.push(InputEvent::ContextChanged {
+            old_context: Some(name.clone()),
+            new_context: self.modals.last().map(|(name, _)| name.clone()),
+        });
+        Some(name)
+    }
+
+    /// Name of the innermost open modal
+    pub fn current_modal(&self) -> Option<&str> {
+        self.modals.last().map(|(name, _)| name.as_str())
+    }
+
+    /// Check if any UI modal is open
+    pub fn is_modal_open(&self) -> bool {
+        !self.modals.is_empty()
+    }
+
+    /// Check if an open modal pauses the game
+    ///
+    /// While the world's `InputManager` is paused, the engine skips the `Update` and
+    /// `Physics` stages and runs `Game::update` with `UpdateContext::paused` set.
+    pub fn is_paused(&self) -> bool {
+        self.modals.iter().any(|(_, pauses_game)| *pauses_game)
     }
 
     /// Get all registered actions
@@ -340,9 +1172,14 @@ impl InputManager {
     /// Generate input events for state changes
     fn generate_action_events(&mut self) {
         let now = Instant::now();
+        self.last_event_time = Some(now);
 
         let action_ids: Vec<_> = self.action_states.keys().cloned().collect();
         for action_id in action_ids {
+            // Consumed presses were handled by another layer
+            if self.suppressed_actions.contains(&action_id) {
+                continue;
+            }
             if let Some(state) = self.action_states.get(&action_id) {
                 if let Some(action) = self.actions.get(&action_id) {
                     let intensity = match action.input_type {
@@ -1,14 +1,25 @@
 pub mod actions;
 pub mod gamepad;
+#[cfg(feature = "gamepad")]
+pub mod gilrs_backend;
 pub mod keyboard;
 pub mod macros;
 pub mod manager;
 pub mod mouse;
+pub mod players;
+pub mod text;
 pub mod types;
 
 pub use actions::*;
-pub use gamepad::{GamepadEvent, GamepadInput, GamepadState};
+pub use gamepad::{
+    AxisCalibration, AxisReading, GamepadCalibration, GamepadEvent, GamepadInput, GamepadState,
+    Rumble,
+};
+#[cfg(feature = "gamepad")]
+pub use gilrs_backend::GilrsBackend;
 pub use keyboard::{KeyboardEvent, KeyboardInput};
 pub use manager::InputManager;
 pub use mouse::{MouseEvent, MouseInput};
+pub use players::{InputDevice, PlayerInputs};
+pub use text::{TextInput, TextInputEvent};
 pub use types::*;
@@ -152,6 +152,12 @@ impl MouseInput {
 
     /// Update the InputManager with current mouse state
     pub fn update_input_manager(&self, input_manager: &mut crate::input::manager::InputManager) {
+        // Captured positions are accumulated movement, not a place in the window
+        if !self.captured {
+            let (x, y) = self.position;
+            input_manager.set_cursor_position(glam::Vec2::new(x, y));
+        }
+
         // Update mouse button states
         for (button, pressed) in &self.button_states {
             let physical_input = PhysicalInput::Mouse(*button);
@@ -0,0 +1,240 @@
+use crate::input::gamepad::GamepadInput;
+use crate::input::keyboard::KeyboardInput;
+use crate::input::manager::InputManager;
+use crate::input::mouse::MouseInput;
+use crate::input::types::*;
+use std::collections::HashMap;
+
+/// A physical device that can be given to a player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    /// The keyboard and mouse together
+    KeyboardMouse,
+    /// A gamepad by its `GamepadInput` ID
+    Gamepad(u32),
+}
+
+struct PlayerSlot {
+    devices: Vec<InputDevice>,
+    input: InputManager,
+}
+
+/// Local multiplayer input: each player slot owns some devices and has its own
+/// `InputManager`, so actions can be asked about per player
+///
+/// Every player shares the registered actions but can be rebound separately through
+/// `input_mut`. A device belongs to at most one player; a gamepad that disconnects keeps
+/// its slot, so its player gets it back on reconnect if the ID is reused.
+///
+/// ```
+/// # use engine_2d::input::*;
+/// # let (keyboard, mouse, gamepads) = (KeyboardInput::new(), MouseInput::new(), GamepadInput::new());
+/// # let delta_time = 0.016;
+/// let mut players = PlayerInputs::new(2);
+/// players.register_actions(get_all_actions());
+/// players.assign(0, InputDevice::KeyboardMouse)?;
+/// // "press Start to join"
+/// for pad in gamepads.connected_gamepads() {
+///     if pad.is_button_just_pressed(GamepadButton::Start) {
+///         players.assign_to_free_player(InputDevice::Gamepad(pad.id));
+///     }
+/// }
+/// players.update(&keyboard, &mouse, &gamepads, delta_time);
+/// if players.is_action_pressed_for(1, "JUMP") {
+///     // player 2 jumps
+/// }
+/// # Ok::<(), String>(())
+/// ```
+pub struct PlayerInputs {
+    players: Vec<PlayerSlot>,
+}
+
+impl PlayerInputs {
+    /// `player_count` player slots with no devices
+    pub fn new(player_count: usize) -> Self {
+        Self {
+            players: (0..player_count)
+                .map(|_| PlayerSlot {
+                    devices: Vec::new(),
+                    input: InputManager::new(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    /// Register an action for every player
+    pub fn register_action(&mut self, action: GameAction) {
+        for player in &mut self.players {
+            player.input.register_action(action.clone());
+        }
+    }
+
+    /// Register several actions for every player
+    pub fn register_actions(&mut self, actions: Vec<GameAction>) {
+        for action in actions {
+            self.register_action(action);
+        }
+    }
+
+    /// Give a device to a player, taking it from whoever had it
+    pub fn assign(&mut self, player: usize, device: InputDevice) -> Result<(), String> {
+        if player >= self.players.len() {
+            return Err(format!(
+                "No player {} (there are {})",
+                player,
+                self.players.len()
+            ));
+        }
+        self.unassign(device);
+        self.players[player].devices.push(device);
+        Ok(())
+    }
+
+    /// Give a device to the first player without any, e.g. when it presses Start to join;
+    /// returns that player, or the current owner if the device is already assigned
+    pub fn assign_to_free_player(&mut self, device: InputDevice) -> Option<usize> {
+        if let Some(owner) = self.player_for(device) {
+            return Some(owner);
+        }
+        let player = self
+            .players
+            .iter()
+            .position(|player| player.devices.is_empty())?;
+        self.players[player].devices.push(device);
+        Some(player)
+    }
+
+    /// Take a device away from its player
+    pub fn unassign(&mut self, device: InputDevice) {
+        for player in &mut self.players {
+            player.devices.retain(|&owned| owned != device);
+        }
+    }
+
+    /// Take every device away from a player, e.g. when they leave
+    pub fn unassign_player(&mut self, player: usize) {
+        if let Some(player) = self.players.get_mut(player) {
+            player.devices.clear();
+        }
+    }
+
+    /// Devices a player owns
+    pub fn devices(&self, player: usize) -> &[InputDevice] {
+        self.players
+            .get(player)
+            .map_or(&[], |player| player.devices.as_slice())
+    }
+
+    /// The player owning a device
+    pub fn player_for(&self, device: InputDevice) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|player| player.devices.contains(&device))
+    }
+
+    /// A player's input manager, e.g. for contexts or rebinding
+    pub fn input(&self, player: usize) -> Option<&InputManager> {
+        self.players.get(player).map(|player| &player.input)
+    }
+
+    pub fn input_mut(&mut self, player: usize) -> Option<&mut InputManager> {
+        self.players.get_mut(player).map(|player| &mut player.input)
+    }
+
+    /// Feed each player's devices into their input manager and update it (call each frame)
+    ///
+    /// A player with several gamepads gets each button held on any of them, and each axis
+    /// from whichever is pushed furthest.
+    pub fn update(
+        &mut self,
+        keyboard: &KeyboardInput,
+        mouse: &MouseInput,
+        gamepads: &GamepadInput,
+        delta_time: f32,
+    ) {
+        for player in &mut self.players {
+            let mut buttons: HashMap<GamepadButton, bool> = HashMap::new();
+            let mut axes: HashMap<GamepadAxis, f32> = HashMap::new();
+            if !player.devices.contains(&InputDevice::KeyboardMouse) {
+                release_keyboard_mouse(&mut player.input);
+            }
+            for device in &player.devices {
+                match device {
+                    InputDevice::KeyboardMouse => {
+                        keyboard.update_input_manager(&mut player.input);
+                        mouse.update_input_manager(&mut player.input);
+                    }
+                    InputDevice::Gamepad(id) => {
+                        let Some(gamepad) = gamepads.get_gamepad(*id) else {
+                            continue;
+                        };
+                        for button in GamepadButton::ALL {
+                            *buttons.entry(button).or_default() |=
+                                gamepad.is_button_pressed(button);
+                        }
+                        for axis in GamepadAxis::ALL {
+                            let value = gamepad.get_axis(axis);
+                            let furthest = axes.entry(axis).or_default();
+                            if value.abs() > furthest.abs() {
+                                *furthest = value;
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Release the inputs of gamepads that were unplugged or taken away
+            for button in GamepadButton::ALL {
+                let pressed = buttons.get(&button).copied().unwrap_or(false);
+                player
+                    .input
+                    .set_physical_input_state(PhysicalInput::Gamepad(button), pressed);
+            }
+            for axis in GamepadAxis::ALL {
+                let value = axes.get(&axis).copied().unwrap_or(0.0);
+                player
+                    .input
+                    .set_physical_input_value(PhysicalInput::GamepadAxis(axis), value);
+            }
+            player.input.update(delta_time);
+        }
+    }
+
+    /// Check if a player just pressed an action this frame
+    pub fn is_action_pressed_for(&self, player: usize, action_id: &str) -> bool {
+        self.input(player)
+            .is_some_and(|input| input.is_action_pressed(action_id))
+    }
+
+    /// Check if a player is holding an action
+    pub fn is_action_held_for(&self, player: usize, action_id: &str) -> bool {
+        self.input(player)
+            .is_some_and(|input| input.is_action_held(action_id))
+    }
+
+    /// Check if a player just released an action
+    pub fn is_action_released_for(&self, player: usize, action_id: &str) -> bool {
+        self.input(player)
+            .is_some_and(|input| input.is_action_released(action_id))
+    }
+
+    /// A player's value for an analog action
+    pub fn get_action_value_for(&self, player: usize, action_id: &str) -> f32 {
+        self.input(player)
+            .map_or(0.0, |input| input.get_action_value(action_id))
+    }
+}
+
+/// Release every key and mouse button, for players without the keyboard
+fn release_keyboard_mouse(input: &mut InputManager) {
+    for key in KeyCode::ALL {
+        input.set_physical_input_state(PhysicalInput::Keyboard(key), false);
+    }
+    for button in MouseButton::ALL {
+        input.set_physical_input_state(PhysicalInput::Mouse(button), false);
+    }
+}
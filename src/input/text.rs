@@ -0,0 +1,149 @@
+/// Typed text for chat boxes, name entry and consoles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInputEvent {
+    /// Characters as the player typed them, after the keyboard layout, dead keys and any
+    /// input method have composed them
+    Text(String),
+    /// An input method's in-progress composition, to draw at the caret until it's committed
+    /// as `Text`; an empty `text` means the composition ended
+    Composition {
+        text: String,
+        /// Cursor position within the composition, in characters
+        cursor: usize,
+    },
+}
+
+/// The stream of text typed between frames
+///
+/// Unlike key codes, the characters here respect the player's keyboard layout, Shift and
+/// AltGr, dead keys and IMEs. Feed window character events in with `handle_char` (or
+/// `handle_window_event` for GLFW) and drain them where the focused text field is updated.
+///
+/// GLFW reports IME text once it's committed; backends that see the composition as it's
+/// typed can report it through `handle_composition`.
+///
+/// ```ignore
+/// // in Game::handle_event
+/// text_input.handle_window_event(event);
+/// // in update
+/// for event in text_input.drain() {
+///     name_field.handle_text_input(&event);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    events: Vec<TextInputEvent>,
+    /// Current composition and its cursor
+    composition: Option<(String, usize)>,
+    enabled: bool,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            composition: None,
+            enabled: true,
+        }
+    }
+
+    /// Turn text collection on or off, e.g. only while a text field has focus
+    ///
+    /// Disabling drops pending events and any composition.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.events.clear();
+            self.composition = None;
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Add a typed character; control characters and the private-use codes some platforms
+    /// send for function keys are ignored, as editing keys arrive as key events
+    pub fn handle_char(&mut self, ch: char) {
+        if ch.is_control() || ('\u{F700}'..='\u{F8FF}').contains(&ch) {
+            return;
+        }
+        let mut buffer = [0; 4];
+        self.handle_text(ch.encode_utf8(&mut buffer));
+    }
+
+    /// Add committed text, such as a whole IME conversion; this ends any composition
+    pub fn handle_text(&mut self, text: &str) {
+        if !self.enabled || text.is_empty() {
+            return;
+        }
+        self.composition = None;
+        match self.events.last_mut() {
+            Some(TextInputEvent::Text(pending)) => pending.push_str(text),
+            _ => self.events.push(TextInputEvent::Text(text.to_string())),
+        }
+    }
+
+    /// Update the IME composition; empty text ends it
+    pub fn handle_composition(&mut self, text: &str, cursor: usize) {
+        if !self.enabled {
+            return;
+        }
+        self.composition = (!text.is_empty()).then(|| (text.to_string(), cursor));
+        self.events.push(TextInputEvent::Composition {
+            text: text.to_string(),
+            cursor,
+        });
+    }
+
+    /// Collect the characters of a GLFW window event; returns whether it was a text event
+    #[cfg(feature = "opengl")]
+    pub fn handle_window_event(&mut self, event: &crate::engine::window::WindowEvent) -> bool {
+        use crate::engine::window::WindowEvent;
+        match event {
+            WindowEvent::Glfw(glfw::WindowEvent::Char(ch)) => {
+                self.handle_char(*ch);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The composition being typed and its cursor, if any
+    pub fn composition(&self) -> Option<(&str, usize)> {
+        self.composition
+            .as_ref()
+            .map(|(text, cursor)| (text.as_str(), *cursor))
+    }
+
+    pub fn is_composing(&self) -> bool {
+        self.composition.is_some()
+    }
+
+    /// Events received since the last drain
+    pub fn events(&self) -> &[TextInputEvent] {
+        &self.events
+    }
+
+    /// Take the events received since the last drain, in order
+    pub fn drain(&mut self) -> Vec<TextInputEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Take the committed text received since the last drain, dropping composition updates
+    pub fn take_text(&mut self) -> String {
+        self.drain()
+            .into_iter()
+            .filter_map(|event| match event {
+                TextInputEvent::Text(text) => Some(text),
+                TextInputEvent::Composition { .. } => None,
+            })
+            .collect()
+    }
+}
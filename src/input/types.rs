@@ -1,3 +1,5 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::fmt;
 use std::hash::{Hash, Hasher};
 
 /// Core input system types for the game engine
@@ -64,6 +66,30 @@ pub enum InputBinding {
         threshold: f32,
         deadzone: f32,
     },
+
+    /// Analog axis with a shaped deadzone and response curve, for sticks used to aim
+    ///
+    /// Unlike `Analog`, the value rises smoothly from 0 at the deadzone's edge to 1 at full
+    /// deflection and then follows `curve`, so small movements stay precise. The binding
+    /// counts as pressed past `threshold` of that value.
+    AnalogStick {
+        input: PhysicalInput,
+        threshold: f32,
+        deadzone: f32,
+        shape: DeadzoneShape,
+        curve: ResponseCurve,
+    },
+
+    /// Steps entered in order, each within `window` seconds of the previous one, such as
+    /// a fighting game special move (Down, Down+Forward, Forward+Punch)
+    ///
+    /// Each step is a chord of inputs held together; a step counts when exactly its inputs,
+    /// out of all the inputs the sequence uses, become held. The binding is active from the
+    /// final step until that chord is let go.
+    Sequence {
+        steps: Vec<Vec<PhysicalInput>>,
+        window: f32,
+    },
 }
 
 impl Eq for InputBinding {}
@@ -98,10 +124,407 @@ impl Hash for InputBinding {
                 threshold.to_bits().hash(state);
                 deadzone.to_bits().hash(state);
             }
+            InputBinding::AnalogStick {
+                input,
+                threshold,
+                deadzone,
+                shape,
+                curve,
+            } => {
+                5u8.hash(state);
+                input.hash(state);
+                threshold.to_bits().hash(state);
+                deadzone.to_bits().hash(state);
+                shape.hash(state);
+                curve.to_string().hash(state);
+            }
+            InputBinding::Sequence { steps, window } => {
+                4u8.hash(state);
+                steps.hash(state);
+                window.to_bits().hash(state);
+            }
+        }
+    }
+}
+
+impl InputBinding {
+    /// Check if the binding reads a physical input
+    pub fn uses(&self, input: &PhysicalInput) -> bool {
+        match self {
+            InputBinding::Single(single) => single == input,
+            InputBinding::Modified { modifier, key } => modifier == input || key == input,
+            InputBinding::Combo(inputs) => inputs.contains(input),
+            InputBinding::Analog { input: axis, .. }
+            | InputBinding::AnalogStick { input: axis, .. } => axis == input,
+            InputBinding::Sequence { steps, .. } => {
+                steps.iter().flatten().any(|step| step == input)
+            }
+        }
+    }
+
+    /// Parse the text form written by `Display`, as stored in binding profiles
+    ///
+    /// `Keyboard:W` is a single input, `Keyboard:LeftCtrl+Keyboard:S` a modified key,
+    /// `combo(Keyboard:LeftCtrl, Keyboard:LeftShift, Keyboard:C)` a combo and
+    /// `analog(GamepadAxis:LeftStickX, 0.5, 0.1)` an analog input with its threshold and
+    /// deadzone. `stick(GamepadAxis:LeftStickX, 0.5, 0.15, radial, squared)` is an analog stick
+    /// axis with its threshold, deadzone, deadzone shape and response curve (see
+    /// `ResponseCurve::parse`). `sequence(0.25, Keyboard:S, Keyboard:S+Keyboard:D, Keyboard:D+Keyboard:J)` is
+    /// a sequence with its timing window first and one `+`-joined chord per step.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        if let Some(args) = call_args(text, "sequence") {
+            let Some((window, steps)) = args.split_first().filter(|(_, steps)| !steps.is_empty())
+            else {
+                return Err(format!(
+                    "Expected 'sequence(window, steps...)', got '{}'",
+                    text
+                ));
+            };
+            let window = window
+                .parse::<f32>()
+                .map_err(|_| format!("Invalid number '{}' in '{}'", window, text))?;
+            let steps = steps
+                .iter()
+                .map(|step| step.split('+').map(PhysicalInput::parse).collect())
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(InputBinding::Sequence { steps, window });
+        }
+        if let Some(args) = call_args(text, "combo") {
+            let inputs = args
+                .into_iter()
+                .map(PhysicalInput::parse)
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(InputBinding::Combo(inputs));
+        }
+        let number = |value: &str| {
+            value
+                .parse::<f32>()
+                .map_err(|_| format!("Invalid number '{}' in '{}'", value, text))
+        };
+        if let Some(args) = call_args(text, "stick") {
+            let [input, threshold, deadzone, shape, curve] = args.as_slice() else {
+                return Err(format!(
+                    "Expected 'stick(input, threshold, deadzone, shape, curve)', got '{}'",
+                    text
+                ));
+            };
+            let shape = match *shape {
+                "axial" => DeadzoneShape::Axial,
+                "radial" => DeadzoneShape::Radial,
+                _ => return Err(format!("Unknown deadzone shape '{}' in '{}'", shape, text)),
+            };
+            return Ok(InputBinding::AnalogStick {
+                input: PhysicalInput::parse(input)?,
+                threshold: number(threshold)?,
+                deadzone: number(deadzone)?,
+                shape,
+                curve: ResponseCurve::parse(curve)?,
+            });
+        }
+        if let Some(args) = call_args(text, "analog") {
+            let [input, threshold, deadzone] = args.as_slice() else {
+                return Err(format!(
+                    "Expected 'analog(input, threshold, deadzone)', got '{}'",
+                    text
+                ));
+            };
+            return Ok(InputBinding::Analog {
+                input: PhysicalInput::parse(input)?,
+                threshold: number(threshold)?,
+                deadzone: number(deadzone)?,
+            });
+        }
+        match text.split_once('+') {
+            Some((modifier, key)) => Ok(InputBinding::Modified {
+                modifier: PhysicalInput::parse(modifier)?,
+                key: PhysicalInput::parse(key)?,
+            }),
+            None => PhysicalInput::parse(text).map(InputBinding::Single),
+        }
+    }
+}
+
+/// Arguments of `name(a, b, ...)`
+fn call_args<'a>(text: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let inner = text
+        .strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    Some(inner.split(',').map(str::trim).collect())
+}
+
+impl fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputBinding::Single(input) => write!(f, "{}", input),
+            InputBinding::Modified { modifier, key } => write!(f, "{}+{}", modifier, key),
+            InputBinding::Combo(inputs) => {
+                let inputs: Vec<String> = inputs.iter().map(ToString::to_string).collect();
+                write!(f, "combo({})", inputs.join(", "))
+            }
+            InputBinding::Analog {
+                input,
+                threshold,
+                deadzone,
+            } => write!(f, "analog({}, {}, {})", input, threshold, deadzone),
+            InputBinding::AnalogStick {
+                input,
+                threshold,
+                deadzone,
+                shape,
+                curve,
+            } => {
+                let shape = match shape {
+                    DeadzoneShape::Axial => "axial",
+                    DeadzoneShape::Radial => "radial",
+                };
+                write!(
+                    f,
+                    "stick({}, {}, {}, {}, {})",
+                    input, threshold, deadzone, shape, curve
+                )
+            }
+            InputBinding::Sequence { steps, window } => {
+                write!(f, "sequence({}", window)?;
+                for step in steps {
+                    let inputs: Vec<String> = step.iter().map(ToString::to_string).collect();
+                    write!(f, ", {}", inputs.join("+"))?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Bindings are serialized as their text form (see `InputBinding::parse`)
+impl Serialize for InputBinding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for InputBinding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        InputBinding::parse(&text).map_err(de::Error::custom)
+    }
+}
+
+/// How an `AnalogStick` binding measures its deadzone
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum DeadzoneShape {
+    /// The axis on its own; simple, but slight diagonals snap to the cardinal directions
+    Axial,
+    /// The whole stick's deflection, this axis together with its pair, so the deadzone is a
+    /// circle and small movements in any direction register (falls back to axial for
+    /// inputs that aren't stick axes)
+    #[default]
+    Radial,
+}
+
+/// Response curve applied to the magnitude of an analog action value
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum ResponseCurve {
+    /// Output equals input
+    #[default]
+    Linear,
+    /// Output is the input squared, giving finer control near the center
+    Squared,
+    /// Output is the input raised to a custom exponent
+    Power(f32),
+    /// Piecewise-linear curve through (input, output) points in the 0..1 range
+    Custom(Vec<(f32, f32)>),
+}
+
+impl ResponseCurve {
+    /// Create a custom curve from (input, output) points, sorted by input
+    pub fn custom(mut points: Vec<(f32, f32)>) -> Self {
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ResponseCurve::Custom(points)
+    }
+
+    /// Parse a custom curve asset with one `input, output` pair per line
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn parse_points(source: &str) -> Result<Self, String> {
+        let mut points = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse = |value: Option<&str>| -> Option<f32> { value?.trim().parse().ok() };
+            let mut values = line.split(',');
+            match (parse(values.next()), parse(values.next()), values.next()) {
+                (Some(input), Some(output), None) => points.push((input, output)),
+                _ => {
+                    return Err(format!(
+                        "Invalid curve point on line {}: '{}'",
+                        index + 1,
+                        line
+                    ));
+                }
+            }
+        }
+        if points.is_empty() {
+            return Err("Curve has no points".to_string());
+        }
+        Ok(Self::custom(points))
+    }
+
+    /// Parse the text form written by `Display`: `linear`, `squared`, `power(2.5)` or
+    /// `custom(0:0 0.5:0.2 1:1)` with space-separated `input:output` points
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let args = |name: &str| {
+            text.strip_prefix(name)?
+                .strip_prefix('(')?
+                .strip_suffix(')')
+                .map(str::trim)
+        };
+        let number = |value: &str| {
+            value
+                .trim()
+                .parse::<f32>()
+                .map_err(|_| format!("Invalid number '{}' in curve '{}'", value, text))
+        };
+        match text {
+            "linear" => return Ok(ResponseCurve::Linear),
+            "squared" => return Ok(ResponseCurve::Squared),
+            _ => {}
+        }
+        if let Some(exponent) = args("power") {
+            return Ok(ResponseCurve::Power(number(exponent)?));
+        }
+        if let Some(points) = args("custom") {
+            let points = points
+                .split_whitespace()
+                .map(|point| {
+                    let (input, output) = point
+                        .split_once(':')
+                        .ok_or_else(|| format!("Expected 'input:output', got '{}'", point))?;
+                    Ok((number(input)?, number(output)?))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            if points.is_empty() {
+                return Err("Curve has no points".to_string());
+            }
+            return Ok(Self::custom(points));
+        }
+        Err(format!("Unknown response curve '{}'", text))
+    }
+
+    /// Load a custom curve asset from a file (see `parse_points` for the format)
+    pub fn load(path: &str) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read curve file '{}': {}", path, e))?;
+        Self::parse_points(&source)
+    }
+
+    /// Map an input magnitude in 0..1 through the curve
+    pub fn evaluate(&self, input: f32) -> f32 {
+        let input = input.clamp(0.0, 1.0);
+        match self {
+            ResponseCurve::Linear => input,
+            ResponseCurve::Squared => input * input,
+            ResponseCurve::Power(exponent) => input.powf(*exponent),
+            ResponseCurve::Custom(points) => {
+                let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                    return input;
+                };
+                if input <= first.0 {
+                    return first.1;
+                }
+                if input >= last.0 {
+                    return last.1;
+                }
+                let segment = points
+                    .windows(2)
+                    .find(|pair| input <= pair[1].0)
+                    .expect("input lies between the first and last points");
+                let ((x0, y0), (x1, y1)) = (segment[0], segment[1]);
+                if x1 <= x0 {
+                    return y1;
+                }
+                y0 + (y1 - y0) * (input - x0) / (x1 - x0)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ResponseCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponseCurve::Linear => write!(f, "linear"),
+            ResponseCurve::Squared => write!(f, "squared"),
+            ResponseCurve::Power(exponent) => write!(f, "power({})", exponent),
+            ResponseCurve::Custom(points) => {
+                let points: Vec<String> = points
+                    .iter()
+                    .map(|(input, output)| format!("{}:{}", input, output))
+                    .collect();
+                write!(f, "custom({})", points.join(" "))
+            }
         }
     }
 }
 
+/// Per-action tuning applied to analog values: response curve, sensitivity and inversion
+#[derive(Clone, PartialEq, Debug)]
+pub struct AnalogSettings {
+    pub curve: ResponseCurve,
+    pub sensitivity: f32,
+    pub inverted: bool,
+}
+
+impl Default for AnalogSettings {
+    fn default() -> Self {
+        Self {
+            curve: ResponseCurve::Linear,
+            sensitivity: 1.0,
+            inverted: false,
+        }
+    }
+}
+
+impl AnalogSettings {
+    /// Set the response curve (builder style)
+    pub fn with_curve(mut self, curve: ResponseCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Set the sensitivity multiplier (builder style)
+    pub fn with_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    /// Set whether the value is inverted (builder style)
+    pub fn with_inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+
+    /// Shape a signed analog value: curve on its magnitude, then sensitivity and inversion
+    pub fn apply(&self, value: f32) -> f32 {
+        if value == 0.0 {
+            return 0.0;
+        }
+        let shaped = value.signum() * self.curve.evaluate(value.abs()) * self.sensitivity;
+        if self.inverted { -shaped } else { shaped }
+    }
+
+    /// Scale an unbounded delta, such as mouse movement in pixels or scroll steps, by the
+    /// sensitivity and inversion; the curve only covers the 0..1 range so it's skipped
+    pub fn apply_delta(&self, value: f32) -> f32 {
+        let scaled = value * self.sensitivity;
+        if self.inverted { -scaled } else { scaled }
+    }
+}
+
 /// Physical input devices and their specific inputs
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum PhysicalInput {
@@ -112,6 +535,50 @@ pub enum PhysicalInput {
     GamepadAxis(GamepadAxis),
 }
 
+impl PhysicalInput {
+    /// Parse the `Device:Input` form written by `Display`, e.g. `Keyboard:W`,
+    /// `Mouse:Other(6)` or `GamepadAxis:LeftStickX`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (device, name) = text
+            .split_once(':')
+            .ok_or_else(|| format!("Expected 'Device:Input', got '{}'", text))?;
+        let name = name.trim();
+        let input = match device.trim() {
+            "Keyboard" => find_by_name(&KeyCode::ALL, name).map(PhysicalInput::Keyboard),
+            "Mouse" => find_by_name(&MouseButton::ALL, name)
+                .or_else(|| {
+                    let number = name.strip_prefix("Other(")?.strip_suffix(')')?;
+                    number.parse().ok().map(MouseButton::Other)
+                })
+                .map(PhysicalInput::Mouse),
+            "MouseAxis" => find_by_name(&MouseAxis::ALL, name).map(PhysicalInput::MouseAxis),
+            "Gamepad" => find_by_name(&GamepadButton::ALL, name).map(PhysicalInput::Gamepad),
+            "GamepadAxis" => find_by_name(&GamepadAxis::ALL, name).map(PhysicalInput::GamepadAxis),
+            _ => None,
+        };
+        input.ok_or_else(|| format!("Unknown input '{}'", text))
+    }
+}
+
+fn find_by_name<T: Copy + fmt::Debug>(all: &[T], name: &str) -> Option<T> {
+    all.iter()
+        .copied()
+        .find(|item| format!("{:?}", item) == name)
+}
+
+impl fmt::Display for PhysicalInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PhysicalInput::Keyboard(key) => write!(f, "Keyboard:{:?}", key),
+            PhysicalInput::Mouse(button) => write!(f, "Mouse:{:?}", button),
+            PhysicalInput::MouseAxis(axis) => write!(f, "MouseAxis:{:?}", axis),
+            PhysicalInput::Gamepad(button) => write!(f, "Gamepad:{:?}", button),
+            PhysicalInput::GamepadAxis(axis) => write!(f, "GamepadAxis:{:?}", axis),
+        }
+    }
+}
+
 /// Keyboard key codes
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum KeyCode {
@@ -234,6 +701,114 @@ pub enum KeyCode {
     Equals,
 }
 
+impl KeyCode {
+    /// Every key, for looking keys up by name
+    pub const ALL: [KeyCode; 102] = [
+        KeyCode::A,
+        KeyCode::B,
+        KeyCode::C,
+        KeyCode::D,
+        KeyCode::E,
+        KeyCode::F,
+        KeyCode::G,
+        KeyCode::H,
+        KeyCode::I,
+        KeyCode::J,
+        KeyCode::K,
+        KeyCode::L,
+        KeyCode::M,
+        KeyCode::N,
+        KeyCode::O,
+        KeyCode::P,
+        KeyCode::Q,
+        KeyCode::R,
+        KeyCode::S,
+        KeyCode::T,
+        KeyCode::U,
+        KeyCode::V,
+        KeyCode::W,
+        KeyCode::X,
+        KeyCode::Y,
+        KeyCode::Z,
+        KeyCode::Key0,
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+        KeyCode::Key9,
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+        KeyCode::F6,
+        KeyCode::F7,
+        KeyCode::F8,
+        KeyCode::F9,
+        KeyCode::F10,
+        KeyCode::F11,
+        KeyCode::F12,
+        KeyCode::Space,
+        KeyCode::Enter,
+        KeyCode::Escape,
+        KeyCode::Tab,
+        KeyCode::Backspace,
+        KeyCode::Delete,
+        KeyCode::LeftShift,
+        KeyCode::RightShift,
+        KeyCode::LeftCtrl,
+        KeyCode::RightCtrl,
+        KeyCode::LeftAlt,
+        KeyCode::RightAlt,
+        KeyCode::LeftSuper,
+        KeyCode::RightSuper,
+        KeyCode::Up,
+        KeyCode::Down,
+        KeyCode::Left,
+        KeyCode::Right,
+        KeyCode::CapsLock,
+        KeyCode::NumLock,
+        KeyCode::ScrollLock,
+        KeyCode::Insert,
+        KeyCode::Home,
+        KeyCode::End,
+        KeyCode::PageUp,
+        KeyCode::PageDown,
+        KeyCode::PrintScreen,
+        KeyCode::Pause,
+        KeyCode::Numpad0,
+        KeyCode::Numpad1,
+        KeyCode::Numpad2,
+        KeyCode::Numpad3,
+        KeyCode::Numpad4,
+        KeyCode::Numpad5,
+        KeyCode::Numpad6,
+        KeyCode::Numpad7,
+        KeyCode::Numpad8,
+        KeyCode::Numpad9,
+        KeyCode::NumpadAdd,
+        KeyCode::NumpadSubtract,
+        KeyCode::NumpadMultiply,
+        KeyCode::NumpadDivide,
+        KeyCode::NumpadEnter,
+        KeyCode::Semicolon,
+        KeyCode::Apostrophe,
+        KeyCode::Grave,
+        KeyCode::Comma,
+        KeyCode::Period,
+        KeyCode::Slash,
+        KeyCode::Backslash,
+        KeyCode::LeftBracket,
+        KeyCode::RightBracket,
+        KeyCode::Minus,
+        KeyCode::Equals,
+    ];
+}
+
 /// Mouse button types
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum MouseButton {
@@ -245,6 +820,17 @@ pub enum MouseButton {
     Other(u8), // Additional mouse buttons
 }
 
+impl MouseButton {
+    /// The named buttons, for looking buttons up by name
+    pub const ALL: [MouseButton; 5] = [
+        MouseButton::Left,
+        MouseButton::Right,
+        MouseButton::Middle,
+        MouseButton::Forward,
+        MouseButton::Back,
+    ];
+}
+
 /// Mouse axis types
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum MouseAxis {
@@ -254,6 +840,16 @@ pub enum MouseAxis {
     ScrollY,
 }
 
+impl MouseAxis {
+    /// Every axis, for looking axes up by name
+    pub const ALL: [MouseAxis; 4] = [
+        MouseAxis::X,
+        MouseAxis::Y,
+        MouseAxis::ScrollX,
+        MouseAxis::ScrollY,
+    ];
+}
+
 /// Gamepad button types
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum GamepadButton {
@@ -291,8 +887,35 @@ pub enum GamepadButton {
     RightStick,
 }
 
+impl GamepadButton {
+    /// Every button, for looking buttons up by name
+    pub const ALL: [GamepadButton; 21] = [
+        GamepadButton::South,
+        GamepadButton::East,
+        GamepadButton::West,
+        GamepadButton::North,
+        GamepadButton::A,
+        GamepadButton::B,
+        GamepadButton::X,
+        GamepadButton::Y,
+        GamepadButton::LeftTrigger,
+        GamepadButton::RightTrigger,
+        GamepadButton::LeftShoulder,
+        GamepadButton::RightShoulder,
+        GamepadButton::DPadUp,
+        GamepadButton::DPadDown,
+        GamepadButton::DPadLeft,
+        GamepadButton::DPadRight,
+        GamepadButton::Start,
+        GamepadButton::Select,
+        GamepadButton::Guide,
+        GamepadButton::LeftStick,
+        GamepadButton::RightStick,
+    ];
+}
+
 /// Gamepad axis types
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
 pub enum GamepadAxis {
     LeftStickX,
     LeftStickY,
@@ -302,6 +925,31 @@ pub enum GamepadAxis {
     RightTrigger,
 }
 
+impl GamepadAxis {
+    /// Every axis, in settings file order
+    pub const ALL: [GamepadAxis; 6] = [
+        GamepadAxis::LeftStickX,
+        GamepadAxis::LeftStickY,
+        GamepadAxis::RightStickX,
+        GamepadAxis::RightStickY,
+        GamepadAxis::LeftTrigger,
+        GamepadAxis::RightTrigger,
+    ];
+
+    /// The (X, Y) stick the axis belongs to, if any
+    pub fn stick_pair(self) -> Option<(GamepadAxis, GamepadAxis)> {
+        match self {
+            GamepadAxis::LeftStickX | GamepadAxis::LeftStickY => {
+                Some((GamepadAxis::LeftStickX, GamepadAxis::LeftStickY))
+            }
+            GamepadAxis::RightStickX | GamepadAxis::RightStickY => {
+                Some((GamepadAxis::RightStickX, GamepadAxis::RightStickY))
+            }
+            GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => None,
+        }
+    }
+}
+
 /// Input state for actions
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum InputState {
@@ -311,6 +959,20 @@ pub enum InputState {
     Idle,     // Not pressed
 }
 
+/// Whether a press is a quick tap or a long hold, against the action's hold threshold
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum TapHoldState {
+    /// Not pressed
+    #[default]
+    Idle,
+    /// Down for less than the hold threshold so far
+    Holding,
+    /// Let go before the hold threshold (this frame only)
+    Tapped,
+    /// Down for at least the hold threshold
+    HeldLong,
+}
+
 /// Input context for managing different game states
 #[derive(Clone, PartialEq, Debug)]
 pub struct InputContext {
@@ -358,6 +1020,124 @@ impl InputContext {
     }
 }
 
+/// What happens when a rebind captures an input another action already uses
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RebindConflict {
+    /// Report the conflict and keep listening for a different input
+    #[default]
+    Reject,
+    /// Take the input away from the other actions
+    Replace,
+    /// Bind it anyway, leaving both actions on the input
+    Allow,
+}
+
+/// Which inputs a rebind listens for and how it applies the one it captures
+#[derive(Clone, PartialEq, Debug)]
+pub struct RebindOptions {
+    pub keyboard: bool,
+    pub mouse_buttons: bool,
+    pub gamepad_buttons: bool,
+    /// Mouse movement, scroll and gamepad axes; off by default since a nudged mouse
+    /// would be captured
+    pub axes: bool,
+    /// How far an axis must be pushed to be captured
+    pub axis_threshold: f32,
+    /// Input that cancels the rebind instead of being bound
+    pub cancel: Option<PhysicalInput>,
+    /// Inputs that are never captured, e.g. keys reserved for the menu
+    pub excluded: Vec<PhysicalInput>,
+    /// Which of the action's bindings to replace; past the end, the binding is added
+    pub slot: usize,
+    pub conflict: RebindConflict,
+}
+
+impl Default for RebindOptions {
+    fn default() -> Self {
+        Self {
+            keyboard: true,
+            mouse_buttons: true,
+            gamepad_buttons: true,
+            axes: false,
+            axis_threshold: 0.5,
+            cancel: Some(PhysicalInput::Keyboard(KeyCode::Escape)),
+            excluded: Vec::new(),
+            slot: 0,
+            conflict: RebindConflict::Reject,
+        }
+    }
+}
+
+impl RebindOptions {
+    /// Only listen for keys and mouse buttons (builder style)
+    pub fn keyboard_mouse_only(mut self) -> Self {
+        self.gamepad_buttons = false;
+        self.axes = false;
+        self
+    }
+
+    /// Only listen for gamepad buttons and, if `axes`, sticks and triggers (builder style)
+    pub fn gamepad_only(mut self, axes: bool) -> Self {
+        self.keyboard = false;
+        self.mouse_buttons = false;
+        self.axes = axes;
+        self
+    }
+
+    /// Set the input that cancels the rebind, or none (builder style)
+    pub fn with_cancel(mut self, cancel: Option<PhysicalInput>) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Never capture an input (builder style)
+    pub fn with_excluded(mut self, input: PhysicalInput) -> Self {
+        self.excluded.push(input);
+        self
+    }
+
+    /// Set which binding to replace (builder style)
+    pub fn with_slot(mut self, slot: usize) -> Self {
+        self.slot = slot;
+        self
+    }
+
+    /// Set how conflicts with other actions are handled (builder style)
+    pub fn with_conflict(mut self, conflict: RebindConflict) -> Self {
+        self.conflict = conflict;
+        self
+    }
+
+    /// Check if the options let an input be captured
+    pub fn accepts(&self, input: &PhysicalInput) -> bool {
+        let device = match input {
+            PhysicalInput::Keyboard(_) => self.keyboard,
+            PhysicalInput::Mouse(_) => self.mouse_buttons,
+            PhysicalInput::Gamepad(_) => self.gamepad_buttons,
+            PhysicalInput::MouseAxis(_) | PhysicalInput::GamepadAxis(_) => self.axes,
+        };
+        device && !self.excluded.contains(input)
+    }
+}
+
+/// How a rebind started with `InputManager::start_rebind` turned out
+#[derive(Clone, PartialEq, Debug)]
+pub enum RebindResult {
+    /// The captured input was bound to the action
+    Bound {
+        action_id: String,
+        binding: InputBinding,
+    },
+    /// The input is already used by other actions in the same category; still listening
+    Conflict {
+        action_id: String,
+        input: PhysicalInput,
+        conflicts: Vec<String>,
+    },
+    /// The cancel input was pressed, or `cancel_rebind` was called
+    Cancelled { action_id: String },
+}
+
 /// Input event for the event system
 #[derive(Clone, Debug)]
 pub enum InputEvent {
@@ -1,11 +1,23 @@
 pub mod animation;
+#[cfg(feature = "capi")]
+pub mod capi;
 pub mod ecs;
 pub mod engine;
 pub mod events;
 pub mod input;
+pub mod particles;
+pub mod physics;
 pub mod render;
+pub mod ui;
 pub mod utils;
 
+// Used by `component_set!`, so games don't need their own serde dependency
+#[doc(hidden)]
+pub use serde;
+
+// Lets `engine_2d::serde`, which `component_set!` names for serde, resolve in this crate
+extern crate self as engine_2d;
+
 #[cfg(test)]
 mod tests {
 
@@ -26,6 +38,9 @@ mod tests {
             fullscreen: false,
             viewport: crate::engine::ViewportConfig::default(),
             fallback_font_path: "assets/fonts/default.ttf".to_string(),
+            idle: crate::engine::IdleConfig::default(),
+            metrics: crate::engine::MetricsConfig::default(),
+            srgb: false,
         };
 
         // Test that we can create an animation
@@ -0,0 +1,276 @@
+use super::storage::ParticleStorage;
+use glam::Vec2;
+
+/// What an emitter does when it wants to spawn but its storage is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Skip the spawn; existing particles live out their lifetime
+    #[default]
+    DropNew,
+    /// Reuse live particles round-robin so new spawns always appear
+    Recycle,
+}
+
+/// Spawn and simulation parameters for a particle emitter
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmitterConfig {
+    pub position: Vec2,
+    /// Particles spawned per second while the emitter is active
+    pub spawn_rate: f32,
+    pub max_particles: usize,
+    /// Lifetime range in seconds (min, max)
+    pub lifetime: (f32, f32),
+    /// Initial speed range (min, max)
+    pub speed: (f32, f32),
+    /// Emission direction in radians (0 = +X)
+    pub direction: f32,
+    /// Total emission cone angle in radians
+    pub spread: f32,
+    pub gravity: Vec2,
+    /// Fraction of velocity lost per second
+    pub drag: f32,
+    /// Particle size at spawn and at death
+    pub start_size: Vec2,
+    pub end_size: Vec2,
+    /// RGBA color at spawn and at death
+    pub start_color: [f32; 4],
+    pub end_color: [f32; 4],
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for EmitterConfig {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            spawn_rate: 50.0,
+            max_particles: 1000,
+            lifetime: (1.0, 2.0),
+            speed: (0.5, 1.0),
+            direction: std::f32::consts::FRAC_PI_2,
+            spread: std::f32::consts::FRAC_PI_4,
+            gravity: Vec2::ZERO,
+            drag: 0.0,
+            start_size: Vec2::splat(0.05),
+            end_size: Vec2::splat(0.05),
+            start_color: [1.0, 1.0, 1.0, 1.0],
+            end_color: [1.0, 1.0, 1.0, 0.0],
+            overflow: OverflowPolicy::DropNew,
+        }
+    }
+}
+
+impl EmitterConfig {
+    /// Set the emitter position (builder style)
+    pub fn with_position(mut self, position: Vec2) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set the spawn rate in particles per second (builder style)
+    pub fn with_spawn_rate(mut self, spawn_rate: f32) -> Self {
+        self.spawn_rate = spawn_rate;
+        self
+    }
+
+    /// Set the particle cap (builder style)
+    pub fn with_max_particles(mut self, max_particles: usize) -> Self {
+        self.max_particles = max_particles;
+        self
+    }
+
+    /// Set the lifetime range in seconds (builder style)
+    pub fn with_lifetime(mut self, min: f32, max: f32) -> Self {
+        self.lifetime = (min, max);
+        self
+    }
+
+    /// Set the initial speed range (builder style)
+    pub fn with_speed(mut self, min: f32, max: f32) -> Self {
+        self.speed = (min, max);
+        self
+    }
+
+    /// Set the emission direction and cone angle in radians (builder style)
+    pub fn with_direction(mut self, direction: f32, spread: f32) -> Self {
+        self.direction = direction;
+        self.spread = spread;
+        self
+    }
+
+    /// Set gravity and drag (builder style)
+    pub fn with_forces(mut self, gravity: Vec2, drag: f32) -> Self {
+        self.gravity = gravity;
+        self.drag = drag;
+        self
+    }
+
+    /// Set the size at spawn and at death (builder style)
+    pub fn with_size(mut self, start: Vec2, end: Vec2) -> Self {
+        self.start_size = start;
+        self.end_size = end;
+        self
+    }
+
+    /// Set the RGBA color at spawn and at death (builder style)
+    pub fn with_color(mut self, start: [f32; 4], end: [f32; 4]) -> Self {
+        self.start_color = start;
+        self.end_color = end;
+        self
+    }
+
+    /// Set the overflow policy (builder style)
+    pub fn with_overflow(mut self, overflow: OverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+/// A particle emitter owning its particles in structure-of-arrays storage
+#[derive(Debug, Clone)]
+pub struct ParticleEmitter {
+    pub config: EmitterConfig,
+    storage: ParticleStorage,
+    spawn_accumulator: f32,
+    recycle_cursor: usize,
+    rng_state: u64,
+    active: bool,
+}
+
+impl ParticleEmitter {
+    /// Create an emitter with storage preallocated for `config.max_particles`
+    pub fn new(config: EmitterConfig) -> Self {
+        Self {
+            storage: ParticleStorage::with_capacity(config.max_particles),
+            config,
+            spawn_accumulator: 0.0,
+            recycle_cursor: 0,
+            rng_state: 0x9E37_79B9_7F4A_7C15,
+            active: true,
+        }
+    }
+
+    /// Seed the emitter's random number generator for reproducible effects
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        // Xorshift state must be non-zero
+        self.rng_state = seed.max(1);
+        self
+    }
+
+    /// Start or stop continuous spawning (live particles keep simulating)
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    /// Check if the emitter is spawning continuously
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Move the emitter
+    pub fn set_position(&mut self, position: Vec2) {
+        self.config.position = position;
+    }
+
+    /// Spawn particles according to the spawn rate, then simulate all of them
+    pub fn update(&mut self, dt: f32) {
+        self.storage
+            .simulate(dt, self.config.gravity, self.config.drag);
+
+        if self.active {
+            self.spawn_accumulator += self.config.spawn_rate * dt;
+            let count = self.spawn_accumulator.floor();
+            self.spawn_accumulator -= count;
+            self.burst(count as usize);
+        }
+    }
+
+    /// Spawn `count` particles immediately, following the overflow policy
+    pub fn burst(&mut self, count: usize) {
+        for _ in 0..count {
+            let (position, velocity, lifetime) = self.next_particle();
+            if self.storage.push(position, velocity, lifetime) {
+                continue;
+            }
+            match self.config.overflow {
+                OverflowPolicy::DropNew => break,
+                OverflowPolicy::Recycle => {
+                    if self.storage.is_empty() {
+                        break;
+                    }
+                    let index = self.recycle_cursor % self.storage.len();
+                    self.storage.respawn(index, position, velocity, lifetime);
+                    self.recycle_cursor = index + 1;
+                }
+            }
+        }
+    }
+
+    /// Remove all live particles
+    pub fn clear(&mut self) {
+        self.storage.clear();
+        self.spawn_accumulator = 0.0;
+    }
+
+    /// Number of live particles
+    pub fn particle_count(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// The emitter's particle storage
+    pub fn storage(&self) -> &ParticleStorage {
+        &self.storage
+    }
+
+    /// Current size of a particle, interpolated over its life
+    pub fn particle_size(&self, index: usize) -> Vec2 {
+        let t = self.storage.life_fraction(index);
+        self.config.start_size.lerp(self.config.end_size, t)
+    }
+
+    /// Current RGBA color of a particle, interpolated over its life
+    pub fn particle_color(&self, index: usize) -> [f32; 4] {
+        let t = self.storage.life_fraction(index);
+        let (start, end) = (self.config.start_color, self.config.end_color);
+        std::array::from_fn(|i| start[i] + (end[i] - start[i]) * t)
+    }
+
+    /// Append one sprite instance per live particle, for `SpriteRenderer::render_instanced`
+    #[cfg(feature = "opengl")]
+    pub fn write_instances(&self, instances: &mut Vec<crate::render::sprite::SpriteInstance>) {
+        instances.reserve(self.storage.len());
+        for index in 0..self.storage.len() {
+            let [r, g, b, a] = self.particle_color(index);
+            instances.push(crate::render::sprite::SpriteInstance::new_with_tint_alpha(
+                self.storage.position(index),
+                self.particle_size(index),
+                (r, g, b),
+                a,
+            ));
+        }
+    }
+
+    fn next_particle(&mut self) -> (Vec2, Vec2, f32) {
+        let angle = self.config.direction + (self.next_random() - 0.5) * self.config.spread;
+        let speed = lerp_range(self.config.speed, self.next_random());
+        let lifetime = lerp_range(self.config.lifetime, self.next_random());
+        (
+            self.config.position,
+            Vec2::new(angle.cos(), angle.sin()) * speed,
+            lifetime,
+        )
+    }
+
+    /// Xorshift64 in [0, 1)
+    fn next_random(&mut self) -> f32 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng_state = x;
+        (x >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+fn lerp_range(range: (f32, f32), t: f32) -> f32 {
+    range.0 + (range.1 - range.0) * t
+}
@@ -0,0 +1,7 @@
+pub mod emitter;
+pub mod storage;
+pub mod system;
+
+pub use emitter::{EmitterConfig, OverflowPolicy, ParticleEmitter};
+pub use storage::ParticleStorage;
+pub use system::{EmitterId, ParticleSystem};
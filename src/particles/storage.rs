@@ -0,0 +1,186 @@
+use glam::Vec2;
+use rayon::prelude::*;
+
+/// Particles per chunk when a storage is integrated across threads
+const PARALLEL_CHUNK_SIZE: usize = 8192;
+
+/// Structure-of-arrays particle storage
+///
+/// Each attribute lives in its own contiguous array so the update loops touch only the
+/// data they need and compile to straight-line, vectorizable code.
+#[derive(Debug, Clone, Default)]
+pub struct ParticleStorage {
+    pub pos_x: Vec<f32>,
+    pub pos_y: Vec<f32>,
+    pub vel_x: Vec<f32>,
+    pub vel_y: Vec<f32>,
+    /// Seconds since the particle was spawned
+    pub age: Vec<f32>,
+    /// Seconds the particle lives for
+    pub lifetime: Vec<f32>,
+    capacity: usize,
+}
+
+impl ParticleStorage {
+    /// Create storage that holds at most `capacity` particles, allocated up front
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            pos_x: Vec::with_capacity(capacity),
+            pos_y: Vec::with_capacity(capacity),
+            vel_x: Vec::with_capacity(capacity),
+            vel_y: Vec::with_capacity(capacity),
+            age: Vec::with_capacity(capacity),
+            lifetime: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Number of live particles
+    pub fn len(&self) -> usize {
+        self.pos_x.len()
+    }
+
+    /// Check if there are no live particles
+    pub fn is_empty(&self) -> bool {
+        self.pos_x.is_empty()
+    }
+
+    /// Maximum number of live particles
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Check if the storage is at capacity
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.capacity
+    }
+
+    /// Add a particle, returning false if the storage is full
+    pub fn push(&mut self, position: Vec2, velocity: Vec2, lifetime: f32) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.pos_x.push(position.x);
+        self.pos_y.push(position.y);
+        self.vel_x.push(velocity.x);
+        self.vel_y.push(velocity.y);
+        self.age.push(0.0);
+        self.lifetime.push(lifetime);
+        true
+    }
+
+    /// Overwrite the particle at `index` with a freshly spawned one
+    pub fn respawn(&mut self, index: usize, position: Vec2, velocity: Vec2, lifetime: f32) {
+        self.pos_x[index] = position.x;
+        self.pos_y[index] = position.y;
+        self.vel_x[index] = velocity.x;
+        self.vel_y[index] = velocity.y;
+        self.age[index] = 0.0;
+        self.lifetime[index] = lifetime;
+    }
+
+    /// Remove a particle by moving the last one into its slot
+    pub fn swap_remove(&mut self, index: usize) {
+        self.pos_x.swap_remove(index);
+        self.pos_y.swap_remove(index);
+        self.vel_x.swap_remove(index);
+        self.vel_y.swap_remove(index);
+        self.age.swap_remove(index);
+        self.lifetime.swap_remove(index);
+    }
+
+    /// Remove all particles
+    pub fn clear(&mut self) {
+        self.pos_x.clear();
+        self.pos_y.clear();
+        self.vel_x.clear();
+        self.vel_y.clear();
+        self.age.clear();
+        self.lifetime.clear();
+    }
+
+    /// Position of a particle
+    pub fn position(&self, index: usize) -> Vec2 {
+        Vec2::new(self.pos_x[index], self.pos_y[index])
+    }
+
+    /// Normalized age of a particle (0 at spawn, 1 at death)
+    pub fn life_fraction(&self, index: usize) -> f32 {
+        (self.age[index] / self.lifetime[index]).min(1.0)
+    }
+
+    /// Advance every particle by `dt` seconds and remove the ones that expired
+    ///
+    /// Velocity is damped by `drag` (fraction lost per second) and accelerated by
+    /// `gravity`. Large storages are integrated in parallel chunks.
+    pub fn simulate(&mut self, dt: f32, gravity: Vec2, drag: f32) {
+        let damping = (1.0 - drag * dt).max(0.0);
+
+        if self.len() >= PARALLEL_CHUNK_SIZE * 2 {
+            (
+                self.pos_x.par_chunks_mut(PARALLEL_CHUNK_SIZE),
+                self.pos_y.par_chunks_mut(PARALLEL_CHUNK_SIZE),
+                self.vel_x.par_chunks_mut(PARALLEL_CHUNK_SIZE),
+                self.vel_y.par_chunks_mut(PARALLEL_CHUNK_SIZE),
+                self.age.par_chunks_mut(PARALLEL_CHUNK_SIZE),
+            )
+                .into_par_iter()
+                .for_each(|(pos_x, pos_y, vel_x, vel_y, age)| {
+                    integrate(pos_x, pos_y, vel_x, vel_y, age, dt, gravity, damping);
+                });
+        } else {
+            integrate(
+                &mut self.pos_x,
+                &mut self.pos_y,
+                &mut self.vel_x,
+                &mut self.vel_y,
+                &mut self.age,
+                dt,
+                gravity,
+                damping,
+            );
+        }
+
+        self.remove_expired();
+    }
+
+    fn remove_expired(&mut self) {
+        let mut index = 0;
+        while index < self.len() {
+            if self.age[index] >= self.lifetime[index] {
+                self.swap_remove(index);
+            } else {
+                index += 1;
+            }
+        }
+    }
+}
+
+/// Integrate one slice of particles; each pass walks a few arrays linearly
+#[allow(clippy::too_many_arguments)]
+fn integrate(
+    pos_x: &mut [f32],
+    pos_y: &mut [f32],
+    vel_x: &mut [f32],
+    vel_y: &mut [f32],
+    age: &mut [f32],
+    dt: f32,
+    gravity: Vec2,
+    damping: f32,
+) {
+    for vx in vel_x.iter_mut() {
+        *vx = *vx * damping + gravity.x * dt;
+    }
+    for vy in vel_y.iter_mut() {
+        *vy = *vy * damping + gravity.y * dt;
+    }
+    for (px, vx) in pos_x.iter_mut().zip(vel_x.iter()) {
+        *px += vx * dt;
+    }
+    for (py, vy) in pos_y.iter_mut().zip(vel_y.iter()) {
+        *py += vy * dt;
+    }
+    for a in age.iter_mut() {
+        *a += dt;
+    }
+}
@@ -0,0 +1,79 @@
+use super::emitter::{EmitterConfig, ParticleEmitter};
+use rayon::prelude::*;
+
+/// Handle to an emitter registered with a `ParticleSystem`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EmitterId(pub usize);
+
+/// Owns particle emitters and simulates them in parallel on the rayon thread pool
+#[derive(Debug, Default)]
+pub struct ParticleSystem {
+    emitters: Vec<Option<ParticleEmitter>>,
+}
+
+impl ParticleSystem {
+    /// Create an empty particle system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an emitter built from a config
+    pub fn add_emitter(&mut self, config: EmitterConfig) -> EmitterId {
+        self.insert_emitter(ParticleEmitter::new(config))
+    }
+
+    /// Add an existing emitter, reusing a free slot if there is one
+    pub fn insert_emitter(&mut self, emitter: ParticleEmitter) -> EmitterId {
+        match self.emitters.iter().position(Option::is_none) {
+            Some(index) => {
+                self.emitters[index] = Some(emitter);
+                EmitterId(index)
+            }
+            None => {
+                self.emitters.push(Some(emitter));
+                EmitterId(self.emitters.len() - 1)
+            }
+        }
+    }
+
+    /// Remove an emitter and its particles
+    pub fn remove_emitter(&mut self, id: EmitterId) -> Option<ParticleEmitter> {
+        self.emitters.get_mut(id.0)?.take()
+    }
+
+    /// Get an emitter
+    pub fn emitter(&self, id: EmitterId) -> Option<&ParticleEmitter> {
+        self.emitters.get(id.0)?.as_ref()
+    }
+
+    /// Get an emitter mutably
+    pub fn emitter_mut(&mut self, id: EmitterId) -> Option<&mut ParticleEmitter> {
+        self.emitters.get_mut(id.0)?.as_mut()
+    }
+
+    /// Iterate over all emitters
+    pub fn emitters(&self) -> impl Iterator<Item = (EmitterId, &ParticleEmitter)> {
+        self.emitters
+            .iter()
+            .enumerate()
+            .filter_map(|(index, emitter)| emitter.as_ref().map(|e| (EmitterId(index), e)))
+    }
+
+    /// Total live particles across all emitters
+    pub fn particle_count(&self) -> usize {
+        self.emitters().map(|(_, e)| e.particle_count()).sum()
+    }
+
+    /// Spawn and simulate every emitter, one emitter per worker
+    pub fn update(&mut self, dt: f32) {
+        self.emitters
+            .par_iter_mut()
+            .flatten()
+            .for_each(|emitter| emitter.update(dt));
+    }
+
+    /// Remove all emitters
+    pub fn clear(&mut self) {
+        self.emitters.clear();
+    }
+}
@@ -1 +1,212 @@
-// Collision detection implementation will go here
+use glam::Vec2;
+
+/// Collision polygon generated from a texture's opaque pixels
+///
+/// Points are in normalized texture coordinates (u to the right, v downward, 0.0-1.0),
+/// so one polygon fits any sprite drawing the texture; use `to_sprite_space` to place it.
+///
+/// ```
+/// # use engine_2d::physics::collision::CollisionPolygon;
+/// # use glam::Vec2;
+/// // A 2x2 texture whose right column is opaque
+/// let rgba = [0, 0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 255, 255, 255, 255];
+/// let hull = CollisionPolygon::from_alpha(&rgba, 2, 2, 128).unwrap();
+/// let shape = hull.to_sprite_space(Vec2::new(100.0, 50.0), Vec2::splat(32.0), false, false);
+/// assert!(shape.contains_point(Vec2::new(108.0, 50.0)));
+/// assert!(!shape.contains_point(Vec2::new(92.0, 50.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionPolygon {
+    points: Vec<Vec2>,
+}
+
+impl CollisionPolygon {
+    /// Create a polygon from points in order (either winding)
+    pub fn new(points: Vec<Vec2>) -> Self {
+        Self { points }
+    }
+
+    /// Build the convex hull of every pixel with alpha at or above the threshold
+    ///
+    /// `rgba` holds `width * height` RGBA pixels in row-major order. Returns `None` if the
+    /// image has no opaque pixels.
+    pub fn from_alpha(rgba: &[u8], width: u32, height: u32, alpha_threshold: u8) -> Option<Self> {
+        if width == 0 || height == 0 || rgba.len() < (width * height * 4) as usize {
+            return None;
+        }
+
+        // Only the outermost opaque pixel of each row can lie on the hull
+        let mut candidates = Vec::new();
+        for y in 0..height {
+            let row = &rgba[(y * width * 4) as usize..((y + 1) * width * 4) as usize];
+            let mut opaque = row
+                .chunks_exact(4)
+                .enumerate()
+                .filter(|(_, pixel)| pixel[3] >= alpha_threshold)
+                .map(|(x, _)| x as f32);
+            let Some(left) = opaque.next() else {
+                continue;
+            };
+            let right = opaque.next_back().unwrap_or(left) + 1.0;
+            let (top, bottom) = (y as f32, y as f32 + 1.0);
+            candidates.extend([
+                Vec2::new(left, top),
+                Vec2::new(left, bottom),
+                Vec2::new(right, top),
+                Vec2::new(right, bottom),
+            ]);
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let scale = Vec2::new(1.0 / width as f32, 1.0 / height as f32);
+        let points = convex_hull(candidates)
+            .into_iter()
+            .map(|point| point * scale)
+            .collect();
+        Some(Self { points })
+    }
+
+    /// Get the polygon's points
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    /// Remove vertices closer than `tolerance` to the line through their neighbours
+    ///
+    /// Useful to cut hull vertex counts for physics; tolerance is in the polygon's units.
+    pub fn simplified(&self, tolerance: f32) -> Self {
+        let mut points = self.points.clone();
+        let mut index = 0;
+        while points.len() > 3 && index < points.len() {
+            let previous = points[(index + points.len() - 1) % points.len()];
+            let next = points[(index + 1) % points.len()];
+            if distance_to_line(points[index], previous, next) < tolerance {
+                points.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        Self { points }
+    }
+
+    /// Place the polygon on a sprite centered at `position` with `size` (y up)
+    pub fn to_sprite_space(&self, position: Vec2, size: Vec2, flip_x: bool, flip_y: bool) -> Self {
+        let points = self
+            .points
+            .iter()
+            .map(|point| {
+                let u = if flip_x { 1.0 - point.x } else { point.x };
+                let v = if flip_y { 1.0 - point.y } else { point.y };
+                position + Vec2::new((u - 0.5) * size.x, (0.5 - v) * size.y)
+            })
+            .collect();
+        Self { points }
+    }
+
+    /// Check if a point is inside the polygon
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        let mut inside = false;
+        let count = self.points.len();
+        for i in 0..count {
+            let a = self.points[i];
+            let b = self.points[(i + count - 1) % count];
+            if (a.y > point.y) != (b.y > point.y)
+                && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// Check if two convex polygons overlap (separating axis test)
+    pub fn intersects(&self, other: &CollisionPolygon) -> bool {
+        if self.points.len() < 3 || other.points.len() < 3 {
+            return false;
+        }
+        !self.edge_normals().chain(other.edge_normals()).any(|axis| {
+            let (min_a, max_a) = self.project(axis);
+            let (min_b, max_b) = other.project(axis);
+            max_a < min_b || max_b < min_a
+        })
+    }
+
+    /// Axis-aligned bounds as (min, max)
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        let first = *self.points.first()?;
+        Some(
+            self.points
+                .iter()
+                .fold((first, first), |(min, max), point| {
+                    (min.min(*point), max.max(*point))
+                }),
+        )
+    }
+
+    /// Enclosed area
+    pub fn area(&self) -> f32 {
+        let count = self.points.len();
+        let twice_area: f32 = (0..count)
+            .map(|i| self.points[i].perp_dot(self.points[(i + 1) % count]))
+            .sum();
+        twice_area.abs() * 0.5
+    }
+
+    fn edge_normals(&self) -> impl Iterator<Item = Vec2> + '_ {
+        let count = self.points.len();
+        (0..count).map(move |i| (self.points[(i + 1) % count] - self.points[i]).perp())
+    }
+
+    fn project(&self, axis: Vec2) -> (f32, f32) {
+        self.points
+            .iter()
+            .map(|point| point.dot(axis))
+            .fold((f32::MAX, f32::MIN), |(min, max), d| {
+                (min.min(d), max.max(d))
+            })
+    }
+}
+
+/// Convex hull of a point set (monotone chain), counter-clockwise without collinear points
+fn convex_hull(mut points: Vec<Vec2>) -> Vec<Vec2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() * 2);
+    for pass in 0..2 {
+        let start = hull.len();
+        let ordered: Box<dyn Iterator<Item = &Vec2>> = if pass == 0 {
+            Box::new(points.iter())
+        } else {
+            Box::new(points.iter().rev())
+        };
+        for &point in ordered {
+            while hull.len() >= start + 2 {
+                let a = hull[hull.len() - 2];
+                let b = hull[hull.len() - 1];
+                if (b - a).perp_dot(point - a) > 0.0 {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // The last point of each chain starts the next one
+        hull.pop();
+    }
+    hull
+}
+
+fn distance_to_line(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let direction = b - a;
+    let length = direction.length();
+    if length == 0.0 {
+        return point.distance(a);
+    }
+    direction.perp_dot(point - a).abs() / length
+}
@@ -0,0 +1,118 @@
+use super::collision::CollisionPolygon;
+use crate::utils::math::geometry::{LineSegment, Rectangle};
+use glam::Vec2;
+
+/// Angle offset of the extra rays cast past each occluder corner
+const CORNER_RAY_OFFSET: f32 = 1e-4;
+
+/// Region visible from a point, bounded by occluder segments
+///
+/// Points are ordered counter-clockwise around the origin, so the origin followed by the
+/// points forms a triangle fan, usable for light shadow shapes or line-of-sight queries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisibilityPolygon {
+    origin: Vec2,
+    points: Vec<Vec2>,
+}
+
+impl VisibilityPolygon {
+    /// Compute what `origin` can see among `occluders`, clipped to `bounds`
+    ///
+    /// Rays are cast toward every occluder endpoint and slightly to either side of it, so
+    /// the result follows each shadow edge exactly. Cost is O(endpoints * occluders).
+    pub fn compute(origin: Vec2, occluders: &[LineSegment], bounds: Rectangle) -> Self {
+        let mut segments = occluders.to_vec();
+        segments.extend(rectangle_segments(&bounds));
+
+        let mut angles: Vec<f32> = segments
+            .iter()
+            .flat_map(|segment| [segment.start, segment.end])
+            .map(|corner| (corner - origin).to_angle())
+            .flat_map(|angle| [angle - CORNER_RAY_OFFSET, angle, angle + CORNER_RAY_OFFSET])
+            .collect();
+        angles.sort_by(f32::total_cmp);
+        angles.dedup();
+
+        let points = angles
+            .into_iter()
+            .filter_map(|angle| cast_ray(origin, Vec2::from_angle(angle), &segments))
+            .collect();
+        Self { origin, points }
+    }
+
+    /// Point the polygon was computed from
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// Boundary points, counter-clockwise around the origin
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    /// Triangle fan vertices: the origin, then the boundary, closed back to its first point
+    pub fn triangle_fan(&self) -> Vec<Vec2> {
+        let mut fan = Vec::with_capacity(self.points.len() + 2);
+        fan.push(self.origin);
+        fan.extend_from_slice(&self.points);
+        if let Some(first) = self.points.first() {
+            fan.push(*first);
+        }
+        fan
+    }
+
+    /// Check if a point lies in the visible region
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        CollisionPolygon::new(self.points.clone()).contains_point(point)
+    }
+}
+
+/// Check if nothing in `occluders` blocks the straight line between two points
+pub fn has_line_of_sight(from: Vec2, to: Vec2, occluders: &[LineSegment]) -> bool {
+    let sight = LineSegment::new(from, to);
+    !occluders.iter().any(|occluder| sight.intersects(occluder))
+}
+
+/// Check if `target` is within `half_angle` radians of the `facing` direction from `origin`
+pub fn within_view_cone(origin: Vec2, facing: Vec2, half_angle: f32, target: Vec2) -> bool {
+    let to_target = target - origin;
+    if to_target == Vec2::ZERO {
+        return true;
+    }
+    facing.angle_between(to_target).abs() <= half_angle
+}
+
+/// Occluder segments along the edges of a collision polygon
+pub fn polygon_occluders(polygon: &CollisionPolygon) -> Vec<LineSegment> {
+    let points = polygon.points();
+    (0..points.len())
+        .map(|i| LineSegment::new(points[i], points[(i + 1) % points.len()]))
+        .collect()
+}
+
+/// Occluder segments along the edges of a rectangle, such as a solid tile
+pub fn rectangle_segments(rect: &Rectangle) -> [LineSegment; 4] {
+    let min = rect.top_left();
+    let max = rect.bottom_right();
+    let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+    [0, 1, 2, 3].map(|i| LineSegment::new(corners[i], corners[(i + 1) % 4]))
+}
+
+/// Nearest point where a ray from `origin` along `direction` hits a segment
+fn cast_ray(origin: Vec2, direction: Vec2, segments: &[LineSegment]) -> Option<Vec2> {
+    segments
+        .iter()
+        .filter_map(|segment| {
+            let edge = segment.direction();
+            let denom = direction.perp_dot(edge);
+            if denom.abs() < 1e-9 {
+                return None;
+            }
+            let offset = segment.start - origin;
+            let distance = offset.perp_dot(edge) / denom;
+            let along = offset.perp_dot(direction) / denom;
+            (distance >= 0.0 && (0.0..=1.0).contains(&along)).then_some(distance)
+        })
+        .min_by(f32::total_cmp)
+        .map(|distance| origin + direction * distance)
+}
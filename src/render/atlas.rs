@@ -0,0 +1,113 @@
+/// Normalized (u, v, width, height) region of an atlas texture
+pub type UvRect = (f32, f32, f32, f32);
+
+/// Shelf (row-based) rectangle packer for texture atlases
+///
+/// Rectangles are placed left to right along the current shelf; when one doesn't fit,
+/// a new shelf starts below the tallest rectangle of the previous one. Works well for
+/// glyphs and other sprites of similar height.
+#[derive(Debug, Clone)]
+pub struct AtlasPacker {
+    width: u32,
+    height: u32,
+    padding: u32,
+    cursor_x: u32,
+    cursor_y: u32,
+    shelf_height: u32,
+}
+
+impl AtlasPacker {
+    /// Create a packer for an atlas of the given size
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            padding: 0,
+            cursor_x: 0,
+            cursor_y: 0,
+            shelf_height: 0,
+        }
+    }
+
+    /// Leave empty pixels between packed rectangles to avoid filtering bleed (builder style)
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Reserve space for a rectangle, returning its top-left corner or `None` if full
+    pub fn pack(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        let padded_width = width + self.padding * 2;
+        let padded_height = height + self.padding * 2;
+        if padded_width > self.width || padded_height > self.height {
+            return None;
+        }
+
+        if self.cursor_x + padded_width > self.width {
+            self.cursor_y += self.shelf_height;
+            self.cursor_x = 0;
+            self.shelf_height = 0;
+        }
+        if self.cursor_y + padded_height > self.height {
+            return None;
+        }
+
+        let position = (self.cursor_x + self.padding, self.cursor_y + self.padding);
+        self.cursor_x += padded_width;
+        self.shelf_height = self.shelf_height.max(padded_height);
+        Some(position)
+    }
+
+    /// Forget all packed rectangles
+    pub fn clear(&mut self) {
+        self.cursor_x = 0;
+        self.cursor_y = 0;
+        self.shelf_height = 0;
+    }
+
+    /// Atlas width in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Atlas height in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Convert a packed pixel rectangle into normalized (u, v, width, height) coordinates
+    pub fn uv_rect(&self, x: u32, y: u32, width: u32, height: u32) -> UvRect {
+        (
+            x as f32 / self.width as f32,
+            y as f32 / self.height as f32,
+            width as f32 / self.width as f32,
+            height as f32 / self.height as f32,
+        )
+    }
+}
+
+/// Regions of the first `count` cells of a `columns` x `rows` grid atlas, row by row
+pub fn grid_regions(columns: u32, rows: u32, count: usize) -> Result<Vec<UvRect>, String> {
+    if columns == 0 || rows == 0 {
+        return Err("Atlas grid must have at least one row and column".to_string());
+    }
+    if count > (columns * rows) as usize {
+        return Err(format!(
+            "{} cells don't fit in a {}x{} grid",
+            count, columns, rows
+        ));
+    }
+
+    let (cell_width, cell_height) = (1.0 / columns as f32, 1.0 / rows as f32);
+    Ok((0..count as u32)
+        .map(|index| {
+            let (column, row) = (index % columns, index / columns);
+            (
+                column as f32 * cell_width,
+                row as f32 * cell_height,
+                cell_width,
+                cell_height,
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,328 @@
+use std::borrow::Cow;
+
+/// Base reading direction of a text
+///
+/// Alignment is relative to the reading direction: `TextAlign::Left` means "start", so it
+/// aligns right-to-left paragraphs to the right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextDirection {
+    /// Taken from the first strong (letter) character, left-to-right if there is none
+    #[default]
+    Auto,
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Resolve `Auto` against a text, leaving explicit directions as they are
+    pub fn resolve(self, text: &str) -> TextDirection {
+        match self {
+            TextDirection::Auto => text
+                .chars()
+                .find_map(|ch| match class(ch) {
+                    Class::Left => Some(TextDirection::LeftToRight),
+                    Class::Right => Some(TextDirection::RightToLeft),
+                    _ => None,
+                })
+                .unwrap_or(TextDirection::LeftToRight),
+            direction => direction,
+        }
+    }
+
+    /// Check if the direction resolves to right-to-left for a text
+    pub fn is_rtl(self, text: &str) -> bool {
+        self.resolve(text) == TextDirection::RightToLeft
+    }
+}
+
+/// Bidirectional character class, reduced from the Unicode bidi classes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Left,
+    Right,
+    Number,
+    /// Separator inside numbers like "1,000" or "3.5"
+    NumberSeparator,
+    Whitespace,
+    Neutral,
+}
+
+fn class(ch: char) -> Class {
+    match ch {
+        '0'..='9' | '\u{0660}'..='\u{0669}' | '\u{06F0}'..='\u{06F9}' => Class::Number,
+        '\u{0590}'..='\u{08FF}' | '\u{FB1D}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFF}' => Class::Right,
+        ',' | '.' | ':' | '/' => Class::NumberSeparator,
+        ch if ch.is_whitespace() => Class::Whitespace,
+        ch if ch.is_alphabetic() => Class::Left,
+        _ => Class::Neutral,
+    }
+}
+
+/// Reorder one line of text from logical (typed) order into the order it is drawn in,
+/// left to right
+///
+/// Call this per line after wrapping. Right-to-left runs are reversed with their brackets
+/// mirrored, numbers stay left-to-right, and Arabic letters are replaced by their joined
+/// presentation forms, which most Arabic fonts include. `direction` is the paragraph
+/// direction; `Auto` resolves it from this line alone. This is a simplified form of the
+/// Unicode bidirectional algorithm, without explicit embedding controls or bracket pairs.
+pub fn visual_order(line: &str, direction: TextDirection) -> Cow<'_, str> {
+    let rtl = direction.is_rtl(line);
+    if !rtl && !line.chars().any(|ch| class(ch) == Class::Right) {
+        return Cow::Borrowed(line);
+    }
+
+    let chars: Vec<char> = shape_arabic(line).chars().collect();
+    let levels = resolve_levels(&chars, rtl);
+
+    // Reverse every run at or above each odd level, highest level first
+    let mut visual: Vec<(char, u8)> = chars.into_iter().zip(levels).collect();
+    let highest = visual.iter().map(|(_, level)| *level).max().unwrap_or(0);
+    for level in (1..=highest).rev() {
+        let mut start = 0;
+        while start < visual.len() {
+            if visual[start].1 < level {
+                start += 1;
+                continue;
+            }
+            let end = visual[start..]
+                .iter()
+                .position(|(_, run_level)| *run_level < level)
+                .map_or(visual.len(), |offset| start + offset);
+            visual[start..end].reverse();
+            start = end;
+        }
+    }
+
+    Cow::Owned(
+        visual
+            .into_iter()
+            .map(|(ch, level)| if level % 2 == 1 { mirror(ch) } else { ch })
+            .collect(),
+    )
+}
+
+/// Embedding level of each character: even levels run left-to-right, odd right-to-left
+fn resolve_levels(chars: &[char], rtl: bool) -> Vec<u8> {
+    let paragraph = u8::from(rtl);
+    let mut classes: Vec<Class> = chars.iter().map(|ch| class(*ch)).collect();
+
+    // A single separator between two digits belongs to the number
+    for i in 1..classes.len().saturating_sub(1) {
+        if classes[i] == Class::NumberSeparator
+            && classes[i - 1] == Class::Number
+            && classes[i + 1] == Class::Number
+        {
+            classes[i] = Class::Number;
+        }
+    }
+
+    // Numbers following left-to-right text are part of it
+    let mut last_strong = if rtl { Class::Right } else { Class::Left };
+    for class in &mut classes {
+        match *class {
+            Class::Left | Class::Right => last_strong = *class,
+            Class::Number if last_strong == Class::Left => *class = Class::Left,
+            _ => {}
+        }
+    }
+
+    // Neutrals take the direction around them when both sides agree (numbers count as
+    // right-to-left here), otherwise the paragraph's
+    let strong = |class: Class| match class {
+        Class::Left => Some(false),
+        Class::Right | Class::Number => Some(true),
+        _ => None,
+    };
+    let mut levels = vec![paragraph; classes.len()];
+    let mut previous = rtl;
+    for i in 0..classes.len() {
+        levels[i] = match classes[i] {
+            Class::Left => paragraph + (paragraph % 2),
+            Class::Right => 1,
+            Class::Number => paragraph + 1 + (1 - paragraph % 2),
+            _ => {
+                let next = classes[i + 1..]
+                    .iter()
+                    .find_map(|class| strong(*class))
+                    .unwrap_or(rtl);
+                match (previous, next) {
+                    (true, true) => 1,
+                    (false, false) => paragraph + (paragraph % 2),
+                    _ => paragraph,
+                }
+            }
+        };
+        if let Some(is_rtl) = strong(classes[i]) {
+            previous = is_rtl;
+        }
+    }
+
+    // Trailing whitespace sits at the paragraph's end
+    for i in (0..classes.len()).rev() {
+        if classes[i] != Class::Whitespace {
+            break;
+        }
+        levels[i] = paragraph;
+    }
+    levels
+}
+
+fn mirror(ch: char) -> char {
+    match ch {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        ch => ch,
+    }
+}
+
+/// How an Arabic letter connects to its neighbours
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Joining {
+    /// Connects on both sides (isolated, final, initial and medial forms)
+    Dual,
+    /// Connects only to the preceding letter (isolated and final forms)
+    Right,
+    /// Tatweel: connects on both sides but has no forms of its own
+    Causing,
+    /// Diacritics, skipped when looking for neighbours
+    Transparent,
+    None,
+}
+
+/// First presentation form (isolated) of each letter from U+0621 to U+064A, 0 if none
+const ARABIC_FORMS: [(u16, Joining); 42] = {
+    use Joining::{Causing, Dual as D, None as U, Right as R};
+    [
+        (0xFE80, U),
+        (0xFE81, R),
+        (0xFE83, R),
+        (0xFE85, R),
+        (0xFE87, R),
+        (0xFE89, D),
+        (0xFE8D, R),
+        (0xFE8F, D),
+        (0xFE93, R),
+        (0xFE95, D),
+        (0xFE99, D),
+        (0xFE9D, D),
+        (0xFEA1, D),
+        (0xFEA5, D),
+        (0xFEA9, R),
+        (0xFEAB, R),
+        (0xFEAD, R),
+        (0xFEAF, R),
+        (0xFEB1, D),
+        (0xFEB5, D),
+        (0xFEB9, D),
+        (0xFEBD, D),
+        (0xFEC1, D),
+        (0xFEC5, D),
+        (0xFEC9, D),
+        (0xFECD, D),
+        (0, U),
+        (0, U),
+        (0, U),
+        (0, U),
+        (0, U),
+        (0, Causing),
+        (0xFED1, D),
+        (0xFED5, D),
+        (0xFED9, D),
+        (0xFEDD, D),
+        (0xFEE1, D),
+        (0xFEE5, D),
+        (0xFEE9, D),
+        (0xFEED, R),
+        (0xFEEF, R),
+        (0xFEF1, D),
+    ]
+};
+
+const LAM: char = '\u{0644}';
+
+fn joining(ch: char) -> Joining {
+    match ch {
+        '\u{0621}'..='\u{064A}' => ARABIC_FORMS[ch as usize - 0x0621].1,
+        '\u{064B}'..='\u{065F}' | '\u{0670}' => Joining::Transparent,
+        _ => Joining::None,
+    }
+}
+
+/// Isolated form of the lam-alef ligature for an alef variant
+fn lam_alef(ch: char) -> Option<u32> {
+    match ch {
+        '\u{0622}' => Some(0xFEF5),
+        '\u{0623}' => Some(0xFEF7),
+        '\u{0625}' => Some(0xFEF9),
+        '\u{0627}' => Some(0xFEFB),
+        _ => None,
+    }
+}
+
+/// Replace Arabic letters with the presentation form matching their neighbours
+fn shape_arabic(line: &str) -> Cow<'_, str> {
+    if !line.chars().any(|ch| joining(ch) != Joining::None) {
+        return Cow::Borrowed(line);
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    let neighbour = |range: &mut dyn Iterator<Item = usize>| {
+        range
+            .map(|i| joining(chars[i]))
+            .find(|joining| *joining != Joining::Transparent)
+            .unwrap_or(Joining::None)
+    };
+
+    let mut shaped = String::with_capacity(line.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        let kind = joining(ch);
+        let joins_previous = matches!(kind, Joining::Dual | Joining::Right)
+            && matches!(
+                neighbour(&mut (0..i).rev()),
+                Joining::Dual | Joining::Causing
+            );
+
+        // Lam followed by alef is always written as one ligature
+        if ch == LAM
+            && let Some(ligature) = chars.get(i + 1).and_then(|next| lam_alef(*next))
+        {
+            let form = ligature + u32::from(joins_previous);
+            shaped.extend(char::from_u32(form));
+            i += 2;
+            continue;
+        }
+
+        let joins_next = kind == Joining::Dual
+            && matches!(
+                neighbour(&mut (i + 1..chars.len())),
+                Joining::Dual | Joining::Right | Joining::Causing
+            );
+        let isolated = match kind {
+            Joining::Dual | Joining::Right => u32::from(ARABIC_FORMS[ch as usize - 0x0621].0),
+            _ => 0,
+        };
+        let offset = match (joins_previous, joins_next) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (false, true) => 2,
+            (true, true) => 3,
+        };
+        match char::from_u32(isolated + offset) {
+            Some(form) if isolated != 0 => shaped.push(form),
+            _ => shaped.push(ch),
+        }
+        i += 1;
+    }
+    Cow::Owned(shaped)
+}
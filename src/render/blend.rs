@@ -0,0 +1,34 @@
+use super::gl_wrapper::GlWrapper;
+
+/// How a draw is combined with what is already in the framebuffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum BlendMode {
+    /// Standard alpha blending
+    #[default]
+    Alpha,
+    /// Adds color on top of the destination (glows, fire, sparks)
+    Additive,
+    /// Multiplies with the destination (shadows, darkening)
+    Multiply,
+    /// Alpha blending for textures whose color is already multiplied by alpha
+    Premultiplied,
+}
+
+impl BlendMode {
+    /// Source and destination factors passed to `glBlendFunc`
+    pub fn factors(self) -> (u32, u32) {
+        match self {
+            BlendMode::Alpha => (gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA),
+            BlendMode::Additive => (gl::SRC_ALPHA, gl::ONE),
+            BlendMode::Multiply => (gl::DST_COLOR, gl::ONE_MINUS_SRC_ALPHA),
+            BlendMode::Premultiplied => (gl::ONE, gl::ONE_MINUS_SRC_ALPHA),
+        }
+    }
+
+    /// Enable blending and set the blend function for this mode
+    pub fn apply(self, gl: &GlWrapper) -> Result<(), String> {
+        let (src, dst) = self.factors();
+        gl.enable_blending()?;
+        gl.set_blend_func(src, dst)
+    }
+}
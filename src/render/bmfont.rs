@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A character entry of a BMFont file, in page pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BmFontChar {
+    pub ch: char,
+    /// Source rectangle within the page texture
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Offset from the cursor to the glyph's top-left (y measured down from the line top)
+    pub xoffset: i32,
+    pub yoffset: i32,
+    pub xadvance: i32,
+    /// Index of the page texture holding the glyph
+    pub page: usize,
+}
+
+/// A pre-baked bitmap font in the AngelCode BMFont text format (.fnt)
+#[derive(Debug, Clone, Default)]
+pub struct BmFont {
+    /// Size the font was baked at
+    pub size: u32,
+    /// Distance between lines
+    pub line_height: u32,
+    /// Distance from the top of a line to the baseline
+    pub base: u32,
+    /// Page texture dimensions
+    pub scale_w: u32,
+    pub scale_h: u32,
+    /// Page texture file names, relative to the .fnt file, indexed by page id
+    pub pages: Vec<String>,
+    pub chars: Vec<BmFontChar>,
+    /// Kerning adjustments in pixels per (first, second) character pair
+    pub kernings: HashMap<(char, char), i32>,
+}
+
+impl BmFont {
+    /// Load and parse a .fnt file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read font file '{}': {}", path.display(), e))?;
+        Self::parse(&source).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Parse the text form of a .fnt file
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut font = BmFont::default();
+        for (index, line) in source.lines().enumerate() {
+            let mut tokens = tokenize(line).into_iter();
+            let Some(tag) = tokens.next() else {
+                continue;
+            };
+            let attributes: HashMap<String, String> = tokens
+                .filter_map(|token| {
+                    let (key, value) = token.split_once('=')?;
+                    Some((key.to_string(), value.trim_matches('"').to_string()))
+                })
+                .collect();
+            let get = |key: &str| -> Result<i64, String> {
+                let value = attributes
+                    .get(key)
+                    .ok_or_else(|| format!("line {}: '{}' is missing '{}'", index + 1, tag, key))?;
+                value
+                    .parse::<i64>()
+                    .map_err(|_| format!("line {}: invalid {} '{}'", index + 1, key, value))
+            };
+            let to_char = |id: i64| {
+                u32::try_from(id)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("line {}: invalid character id {}", index + 1, id))
+            };
+
+            match tag.as_str() {
+                // A negative size means the font was baked to match character height
+                "info" => font.size = get("size")?.unsigned_abs() as u32,
+                "common" => {
+                    font.line_height = get("lineHeight")? as u32;
+                    font.base = get("base")? as u32;
+                    font.scale_w = get("scaleW")? as u32;
+                    font.scale_h = get("scaleH")? as u32;
+                }
+                "page" => {
+                    let id = get("id")? as usize;
+                    let file = attributes
+                        .get("file")
+                        .ok_or_else(|| format!("line {}: 'page' is missing 'file'", index + 1))?;
+                    if font.pages.len() <= id {
+                        font.pages.resize(id + 1, String::new());
+                    }
+                    font.pages[id] = file.clone();
+                }
+                "char" => font.chars.push(BmFontChar {
+                    ch: to_char(get("id")?)?,
+                    x: get("x")? as u32,
+                    y: get("y")? as u32,
+                    width: get("width")? as u32,
+                    height: get("height")? as u32,
+                    xoffset: get("xoffset")? as i32,
+                    yoffset: get("yoffset")? as i32,
+                    xadvance: get("xadvance")? as i32,
+                    page: get("page")? as usize,
+                }),
+                "kerning" => {
+                    let pair = (to_char(get("first")?)?, to_char(get("second")?)?);
+                    font.kernings.insert(pair, get("amount")? as i32);
+                }
+                // "chars" and "kernings" only carry counts
+                _ => {}
+            }
+        }
+
+        if font.scale_w == 0 || font.scale_h == 0 {
+            return Err("missing 'common' line with page dimensions".to_string());
+        }
+        if let Some(ch) = font.chars.iter().find(|ch| ch.page >= font.pages.len()) {
+            return Err(format!(
+                "character '{}' refers to missing page {}",
+                ch.ch, ch.page
+            ));
+        }
+        Ok(font)
+    }
+
+    /// Paths of the page textures, resolved next to the .fnt file
+    pub fn page_paths(&self, fnt_path: &Path) -> Vec<PathBuf> {
+        let dir = fnt_path.parent().unwrap_or(Path::new(""));
+        self.pages.iter().map(|page| dir.join(page)).collect()
+    }
+}
+
+/// Split a line on whitespace, keeping quoted values (`face="Open Sans"`) whole
+fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in line.chars() {
+        match ch {
+            '"' => {
+                quoted = !quoted;
+                current.push(ch);
+            }
+            ch if ch.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            ch => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
@@ -0,0 +1,225 @@
+use glam::Vec2;
+
+/// How the camera chases its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraFollow {
+    /// Half-size of the box around the view center the target can move in freely
+    pub deadzone: Vec2,
+    /// How quickly the camera catches up, per second; 0 snaps to the target
+    pub smoothing: f32,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            deadzone: Vec2::ZERO,
+            smoothing: 8.0,
+        }
+    }
+}
+
+impl CameraFollow {
+    pub fn with_deadzone(mut self, half_size: Vec2) -> Self {
+        self.deadzone = half_size;
+        self
+    }
+
+    pub fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+}
+
+/// Trauma-based screen shake
+///
+/// Hits add trauma, which decays over time; the shake strength is trauma squared, so
+/// small hits barely move the view while big ones stack into a strong shake.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraShake {
+    /// Current trauma, from 0 to 1
+    pub trauma: f32,
+    /// Trauma lost per second
+    pub decay: f32,
+    /// Offset at full trauma, in logical units
+    pub max_offset: Vec2,
+    /// Shake speed in noise samples per second
+    pub frequency: f32,
+    time: f32,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.5,
+            max_offset: Vec2::splat(0.05),
+            frequency: 25.0,
+            time: 0.0,
+        }
+    }
+}
+
+impl CameraShake {
+    pub fn with_max_offset(mut self, max_offset: Vec2) -> Self {
+        self.max_offset = max_offset;
+        self
+    }
+
+    pub fn with_decay(mut self, decay: f32) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    /// Add trauma from a hit or explosion, capped at 1
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Advance the shake and decay its trauma
+    pub fn update(&mut self, delta_time: f32) {
+        self.time += delta_time;
+        self.trauma = (self.trauma - self.decay * delta_time).max(0.0);
+    }
+
+    /// Current shake offset
+    pub fn offset(&self) -> Vec2 {
+        let strength = self.trauma * self.trauma;
+        let t = self.time * self.frequency;
+        Vec2::new(smooth_noise(t, 0), smooth_noise(t, 1)) * self.max_offset * strength
+    }
+}
+
+/// A camera that follows a target within level bounds and shakes on demand
+///
+/// The position is what gets passed to `SpriteRenderer::set_camera_position`: a world
+/// point at the camera position lands on the logical origin. Pass `view_position` there
+/// each frame to include the shake.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Camera {
+    position: Vec2,
+    /// Visible area relative to the camera position
+    view_min: Vec2,
+    view_max: Vec2,
+    /// Level extents the view is kept inside
+    bounds: Option<(Vec2, Vec2)>,
+    pub follow: CameraFollow,
+    pub shake: CameraShake,
+}
+
+impl Camera {
+    /// Create a camera for a viewport's logical bounds (x_min, x_max, y_min, y_max)
+    pub fn new(logical_bounds: (f32, f32, f32, f32)) -> Self {
+        let (x_min, x_max, y_min, y_max) = logical_bounds;
+        Self {
+            position: Vec2::ZERO,
+            view_min: Vec2::new(x_min, y_min),
+            view_max: Vec2::new(x_max, y_max),
+            bounds: None,
+            follow: CameraFollow::default(),
+            shake: CameraShake::default(),
+        }
+    }
+
+    pub fn with_follow(mut self, follow: CameraFollow) -> Self {
+        self.follow = follow;
+        self
+    }
+
+    pub fn with_shake(mut self, shake: CameraShake) -> Self {
+        self.shake = shake;
+        self
+    }
+
+    /// Keep the view inside a level's extents; a level smaller than the view is centered
+    pub fn with_level_bounds(mut self, min: Vec2, max: Vec2) -> Self {
+        self.bounds = Some((min, max));
+        self.position = self.clamped(self.position);
+        self
+    }
+
+    /// Camera position without shake
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Jump to a position immediately, e.g. on level load
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = self.clamped(position);
+    }
+
+    /// Center the view on a world point immediately
+    pub fn center_on(&mut self, point: Vec2) {
+        self.set_position(point - self.view_center());
+    }
+
+    /// World point at the center of the view
+    pub fn center(&self) -> Vec2 {
+        self.position + self.view_center()
+    }
+
+    /// Camera position including shake, for rendering
+    pub fn view_position(&self) -> Vec2 {
+        self.position + self.shake.offset()
+    }
+
+    /// Add screen shake trauma
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.shake.add_trauma(amount);
+    }
+
+    /// Move toward `target` and advance the shake; call once per frame
+    pub fn update(&mut self, target: Vec2, delta_time: f32) {
+        let deadzone = self.follow.deadzone;
+        let delta = target - self.center();
+        let desired = self.position + (delta - delta.clamp(-deadzone, deadzone));
+
+        let t = if self.follow.smoothing <= 0.0 {
+            1.0
+        } else {
+            1.0 - (-self.follow.smoothing * delta_time).exp()
+        };
+        self.position = self.clamped(self.position.lerp(desired, t));
+        self.shake.update(delta_time);
+    }
+
+    fn view_center(&self) -> Vec2 {
+        (self.view_min + self.view_max) / 2.0
+    }
+
+    fn clamped(&self, position: Vec2) -> Vec2 {
+        let Some((min, max)) = self.bounds else {
+            return position;
+        };
+        let low = min - self.view_min;
+        let high = max - self.view_max;
+        let axis = |value: f32, low: f32, high: f32| {
+            if low > high {
+                (low + high) / 2.0
+            } else {
+                value.clamp(low, high)
+            }
+        };
+        Vec2::new(
+            axis(position.x, low.x, high.x),
+            axis(position.y, low.y, high.y),
+        )
+    }
+}
+
+/// Smooth value noise in -1..1, with independent channels
+fn smooth_noise(t: f32, channel: u32) -> f32 {
+    let index = t.floor();
+    let fraction = t - index;
+    let blend = fraction * fraction * (3.0 - 2.0 * fraction);
+    let a = lattice(index as i32, channel);
+    let b = lattice(index as i32 + 1, channel);
+    a + (b - a) * blend
+}
+
+fn lattice(index: i32, channel: u32) -> f32 {
+    let mut hash = (index as u32).wrapping_mul(0x9E37_79B9) ^ channel.wrapping_mul(0x85EB_CA6B);
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x7FEB_352D);
+    hash ^= hash >> 15;
+    (hash as f32 / u32::MAX as f32) * 2.0 - 1.0
+}
@@ -0,0 +1,190 @@
+use fontdue::{Font, FontSettings};
+use glam::Vec2;
+use std::collections::HashSet;
+use std::fs;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// Characters rasterized up front for every loaded font (printable ASCII)
+pub const PRELOADED_CHARS: std::ops::RangeInclusive<u8> = 32..=126;
+
+/// A glyph bitmap rasterized on the CPU, ready to be uploaded to a texture
+#[derive(Debug, Clone)]
+pub struct RasterizedGlyph {
+    pub ch: char,
+    /// Bitmap dimensions in pixels (at the oversampled render scale)
+    pub width: u32,
+    pub height: u32,
+    /// Coverage values, one byte per pixel
+    pub bitmap: Vec<u8>,
+    /// Metrics scaled to the requested font size
+    pub size: Vec2,
+    pub bearing: Vec2,
+    pub advance: f32,
+}
+
+/// Progress reported by a background font load
+#[derive(Debug)]
+pub enum FontLoadEvent {
+    /// The font file was parsed; glyphs follow
+    Parsed {
+        name: String,
+        size: u32,
+        font: Box<Font>,
+        line_height: f32,
+    },
+    /// A glyph finished rasterizing
+    Glyph {
+        name: String,
+        glyph: RasterizedGlyph,
+    },
+    /// All preloaded glyphs have been sent
+    Finished { name: String },
+    /// The font could not be read or parsed
+    Failed { name: String, error: String },
+}
+
+impl FontLoadEvent {
+    /// Name of the font this event belongs to
+    pub fn font_name(&self) -> &str {
+        match self {
+            FontLoadEvent::Parsed { name, .. }
+            | FontLoadEvent::Glyph { name, .. }
+            | FontLoadEvent::Finished { name }
+            | FontLoadEvent::Failed { name, .. } => name,
+        }
+    }
+}
+
+/// Parse font file data with the engine's font settings
+pub fn parse_font(data: Vec<u8>, source: &str) -> Result<Font, String> {
+    let font_settings = FontSettings {
+        scale: 40.0, // Higher scale for better quality
+        collection_index: 0,
+    };
+    Font::from_bytes(data, font_settings)
+        .map_err(|e| format!("Failed to parse font '{}': {:?}", source, e))
+}
+
+/// Line height used for a font at the given pixel size
+pub fn font_line_height(font: &Font, size: u32) -> f32 {
+    font.metrics('A', size as f32).height as f32
+}
+
+/// Rasterize a character at 2x the requested size and scale its metrics back down
+pub fn rasterize_glyph(font: &Font, ch: char, size: u32) -> RasterizedGlyph {
+    let render_scale = (size as f32 * 2.0).max(32.0); // Render at 2x resolution for better quality
+    let (metrics, bitmap) = font.rasterize(ch, render_scale);
+
+    let scale_factor = size as f32 / render_scale;
+    RasterizedGlyph {
+        ch,
+        width: metrics.width as u32,
+        height: metrics.height as u32,
+        bitmap,
+        size: Vec2::new(
+            metrics.width as f32 * scale_factor,
+            metrics.height as f32 * scale_factor,
+        ),
+        bearing: Vec2::new(
+            metrics.xmin as f32 * scale_factor,
+            metrics.ymin as f32 * scale_factor,
+        ),
+        advance: metrics.advance_width * scale_factor,
+    }
+}
+
+/// Loads and rasterizes fonts on worker threads
+///
+/// Results are delivered as `FontLoadEvent`s which the owner drains on the main thread,
+/// where the GL context lives, so texture uploads can be spread across frames.
+pub struct FontLoader {
+    sender: Sender<FontLoadEvent>,
+    receiver: Receiver<FontLoadEvent>,
+    loading: HashSet<String>,
+}
+
+impl Default for FontLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FontLoader {
+    /// Create a new font loader
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver,
+            loading: HashSet::new(),
+        }
+    }
+
+    /// Start loading a font in the background
+    pub fn load(&mut self, name: &str, font_path: &str, size: u32) {
+        self.loading.insert(name.to_string());
+
+        let sender = self.sender.clone();
+        let name = name.to_string();
+        let font_path = font_path.to_string();
+        thread::spawn(move || {
+            let font = fs::read(&font_path)
+                .map_err(|e| format!("Failed to read font file '{}': {}", font_path, e))
+                .and_then(|data| parse_font(data, &font_path));
+            let font = match font {
+                Ok(font) => font,
+                Err(error) => {
+                    let _ = sender.send(FontLoadEvent::Failed { name, error });
+                    return;
+                }
+            };
+
+            let parsed = FontLoadEvent::Parsed {
+                name: name.clone(),
+                size,
+                line_height: font_line_height(&font, size),
+                font: Box::new(font.clone()),
+            };
+            if sender.send(parsed).is_err() {
+                return;
+            }
+
+            for ch in PRELOADED_CHARS {
+                let glyph = rasterize_glyph(&font, ch as char, size);
+                let event = FontLoadEvent::Glyph {
+                    name: name.clone(),
+                    glyph,
+                };
+                // The receiver is gone if the renderer was dropped mid-load
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+
+            let _ = sender.send(FontLoadEvent::Finished { name });
+        });
+    }
+
+    /// Take the next finished event without blocking
+    pub fn try_next(&mut self) -> Option<FontLoadEvent> {
+        let event = self.receiver.try_recv().ok()?;
+        if matches!(
+            event,
+            FontLoadEvent::Finished { .. } | FontLoadEvent::Failed { .. }
+        ) {
+            self.loading.remove(event.font_name());
+        }
+        Some(event)
+    }
+
+    /// Check if a font is still being loaded
+    pub fn is_loading(&self, name: &str) -> bool {
+        self.loading.contains(name)
+    }
+
+    /// Check if any font is still being loaded
+    pub fn has_pending(&self) -> bool {
+        !self.loading.is_empty()
+    }
+}
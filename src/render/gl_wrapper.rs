@@ -1,5 +1,7 @@
+use super::viewport::ViewportRect;
 use gl;
 use glfw::{Glfw, Window as GlfwWindow};
+use std::cell::{Cell, RefCell};
 use std::ffi::CString;
 
 /// Safe wrapper around OpenGL functionality
@@ -9,6 +11,12 @@ pub struct GlWrapper {
     glfw: Option<Glfw>,
     #[allow(dead_code)]
     window: Option<GlfwWindow>,
+    // Draw calls issued since the count was last taken
+    draw_calls: Cell<u32>,
+    // Last rect passed to set_viewport, so clip rects can be given relative to it
+    viewport: Cell<ViewportRect>,
+    // Nested scissor boxes, each already intersected with the one below it
+    clip_stack: RefCell<Vec<ViewportRect>>,
 }
 
 impl GlWrapper {
@@ -17,6 +25,14 @@ impl GlWrapper {
             initialized: false,
             glfw: None,
             window: None,
+            draw_calls: Cell::new(0),
+            viewport: Cell::new(ViewportRect {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+            }),
+            clip_stack: RefCell::new(Vec::new()),
         }
     }
 
@@ -50,9 +66,64 @@ impl GlWrapper {
         unsafe {
             gl::Viewport(x, y, width, height);
         }
+        self.viewport.set(ViewportRect {
+            x,
+            y,
+            width,
+            height,
+        });
+        Ok(())
+    }
+
+    /// The rect last passed to `set_viewport`
+    pub fn viewport_rect(&self) -> ViewportRect {
+        self.viewport.get()
+    }
+
+    /// Enable the scissor test, limiting drawing to `rect` (pixels from the bottom-left)
+    pub fn set_scissor(&self, rect: ViewportRect) -> Result<(), String> {
+        self.check_initialized()?;
+        unsafe {
+            gl::Enable(gl::SCISSOR_TEST);
+            gl::Scissor(rect.x, rect.y, rect.width, rect.height);
+        }
+        Ok(())
+    }
+
+    /// Disable the scissor test
+    pub fn disable_scissor(&self) -> Result<(), String> {
+        self.check_initialized()?;
+        unsafe {
+            gl::Disable(gl::SCISSOR_TEST);
+        }
         Ok(())
     }
 
+    /// Clip drawing to `rect` within the current clip rect, until the matching pop
+    pub fn push_clip_rect(&self, rect: ViewportRect) -> Result<(), String> {
+        let clipped = match self.clip_rect() {
+            Some(current) => current.intersect(&rect),
+            None => rect,
+        };
+        self.clip_stack.borrow_mut().push(clipped);
+        self.set_scissor(clipped)
+    }
+
+    /// Restore the clip rect that was active before the last push
+    pub fn pop_clip_rect(&self) -> Result<(), String> {
+        let mut clip_stack = self.clip_stack.borrow_mut();
+        clip_stack.pop().ok_or("No clip rect to pop")?;
+        match clip_stack.last().copied() {
+            Some(rect) => self.set_scissor(rect),
+            None => self.disable_scissor(),
+        }
+    }
+
+    /// The clip rect drawing is currently limited to, if any
+    pub fn clip_rect(&self) -> Option<ViewportRect> {
+        self.clip_stack.borrow().last().copied()
+    }
+
     /// Set the clear color
     pub fn set_clear_color(&self, r: f32, g: f32, b: f32, a: f32) -> Result<(), String> {
         self.check_initialized()?;
@@ -72,6 +143,19 @@ impl GlWrapper {
     }
 
     /// Enable blending
+    /// Encode fragment output to sRGB when writing to an sRGB-capable framebuffer
+    pub fn set_srgb_framebuffer(&self, enabled: bool) -> Result<(), String> {
+        self.check_initialized()?;
+        unsafe {
+            if enabled {
+                gl::Enable(gl::FRAMEBUFFER_SRGB);
+            } else {
+                gl::Disable(gl::FRAMEBUFFER_SRGB);
+            }
+        }
+        Ok(())
+    }
+
     pub fn enable_blending(&self) -> Result<(), String> {
         self.check_initialized()?;
         unsafe {
@@ -99,6 +183,37 @@ impl GlWrapper {
         Ok(())
     }
 
+    /// Set a 4D float uniform
+    pub fn set_uniform_4f(
+        &self,
+        location: i32,
+        x: f32,
+        y: f32,
+        z: f32,
+        w: f32,
+    ) -> Result<(), String> {
+        self.check_initialized()?;
+        unsafe {
+            gl::Uniform4f(location, x, y, z, w);
+        }
+        Ok(())
+    }
+
+    /// Set an array of 4D float uniforms from tightly packed values
+    pub fn set_uniform_4fv(&self, location: i32, values: &[f32]) -> Result<(), String> {
+        self.check_initialized()?;
+        if !values.len().is_multiple_of(4) {
+            return Err("Uniform vec4 array length must be a multiple of 4".to_string());
+        }
+        if values.is_empty() {
+            return Ok(());
+        }
+        unsafe {
+            gl::Uniform4fv(location, (values.len() / 4) as i32, values.as_ptr());
+        }
+        Ok(())
+    }
+
     /// Set a 3D float uniform
     pub fn set_uniform_3f(&self, location: i32, x: f32, y: f32, z: f32) -> Result<(), String> {
         self.check_initialized()?;
@@ -197,6 +312,28 @@ impl GlWrapper {
         unsafe {
             gl::DrawArrays(mode, first, count);
         }
+        self.draw_calls.set(self.draw_calls.get() + 1);
+        Ok(())
+    }
+
+    /// Number of draw calls issued since the last call, resetting the count
+    pub fn take_draw_calls(&self) -> u32 {
+        self.draw_calls.replace(0)
+    }
+
+    /// Draw multiple instances of the same geometry
+    pub fn draw_arrays_instanced(
+        &self,
+        mode: u32,
+        first: i32,
+        count: i32,
+        instance_count: i32,
+    ) -> Result<(), String> {
+        self.check_initialized()?;
+        unsafe {
+            gl::DrawArraysInstanced(mode, first, count, instance_count);
+        }
+        self.draw_calls.set(self.draw_calls.get() + 1);
         Ok(())
     }
 
@@ -393,6 +530,15 @@ impl GlWrapper {
         Ok(())
     }
 
+    /// Set how often a vertex attribute advances (0 = per vertex, 1 = per instance)
+    pub fn vertex_attrib_divisor(&self, index: u32, divisor: u32) -> Result<(), String> {
+        self.check_initialized()?;
+        unsafe {
+            gl::VertexAttribDivisor(index, divisor);
+        }
+        Ok(())
+    }
+
     /// Enable vertex attribute array
     pub fn enable_vertex_attrib_array(&self, index: u32) -> Result<(), String> {
         self.check_initialized()?;
@@ -459,6 +605,25 @@ impl GlWrapper {
         Ok(())
     }
 
+    /// Set a float texture parameter
+    pub fn tex_parameter_f(&self, target: u32, pname: u32, param: f32) -> Result<(), String> {
+        self.check_initialized()?;
+        unsafe {
+            gl::TexParameterf(target, pname, param);
+        }
+        Ok(())
+    }
+
+    /// Query a float state value
+    pub fn get_float(&self, pname: u32) -> Result<f32, String> {
+        self.check_initialized()?;
+        let mut value = 0.0;
+        unsafe {
+            gl::GetFloatv(pname, &mut value);
+        }
+        Ok(value)
+    }
+
     /// Upload texture image data
     pub fn tex_image_2d(
         &self,
@@ -490,6 +655,37 @@ impl GlWrapper {
         Ok(())
     }
 
+    /// Upload image data into a region of the currently bound texture
+    #[allow(clippy::too_many_arguments)]
+    pub fn tex_sub_image_2d(
+        &self,
+        target: u32,
+        level: i32,
+        x_offset: i32,
+        y_offset: i32,
+        width: i32,
+        height: i32,
+        format: u32,
+        data_type: u32,
+        data: &[u8],
+    ) -> Result<(), String> {
+        self.check_initialized()?;
+        unsafe {
+            gl::TexSubImage2D(
+                target,
+                level,
+                x_offset,
+                y_offset,
+                width,
+                height,
+                format,
+                data_type,
+                data.as_ptr() as *const std::ffi::c_void,
+            );
+        }
+        Ok(())
+    }
+
     /// Delete texture
     pub fn delete_texture(&self, texture: u32) -> Result<(), String> {
         self.check_initialized()?;
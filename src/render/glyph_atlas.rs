@@ -0,0 +1,114 @@
+use super::atlas::{AtlasPacker, UvRect};
+use super::texture::{TextureId, TextureManager};
+
+/// Default side length of a glyph atlas page in pixels
+pub const DEFAULT_ATLAS_PAGE_SIZE: u32 = 1024;
+
+/// Empty pixels kept around each glyph so linear filtering doesn't sample neighbours
+const GLYPH_PADDING: u32 = 1;
+
+struct AtlasPage {
+    texture_id: TextureId,
+    packer: AtlasPacker,
+}
+
+/// Glyph bitmaps packed into shared textures so a string can be drawn with one texture bind
+///
+/// Glyphs are added to the newest page; a new page is allocated only once it fills up.
+pub struct GlyphAtlas {
+    pages: Vec<AtlasPage>,
+    page_size: u32,
+}
+
+impl Default for GlyphAtlas {
+    fn default() -> Self {
+        Self::new(DEFAULT_ATLAS_PAGE_SIZE)
+    }
+}
+
+impl GlyphAtlas {
+    /// Create an atlas whose pages are `page_size` pixels square
+    pub fn new(page_size: u32) -> Self {
+        Self {
+            pages: Vec::new(),
+            page_size,
+        }
+    }
+
+    /// Add a glyph coverage bitmap (one byte per pixel)
+    ///
+    /// Returns the page texture and the glyph's normalized (u, v, width, height) rect.
+    pub fn insert(
+        &mut self,
+        texture_manager: &mut TextureManager,
+        bitmap: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<(TextureId, UvRect), String> {
+        if self.pages.is_empty() {
+            self.add_page(texture_manager)?;
+        }
+
+        // Whitespace glyphs have no pixels; they only need an advance
+        if width == 0 || height == 0 {
+            let page = self.pages.last().unwrap();
+            return Ok((page.texture_id, (0.0, 0.0, 0.0, 0.0)));
+        }
+
+        let mut position = self.pages.last_mut().unwrap().packer.pack(width, height);
+        if position.is_none() {
+            self.add_page(texture_manager)?;
+            position = self.pages.last_mut().unwrap().packer.pack(width, height);
+        }
+        let (x, y) = position.ok_or_else(|| {
+            format!(
+                "Glyph of {}x{} pixels does not fit in a {}x{} atlas page",
+                width, height, self.page_size, self.page_size
+            )
+        })?;
+
+        let page = self.pages.last().unwrap();
+        texture_manager.update_texture_region(
+            page.texture_id,
+            x,
+            y,
+            width,
+            height,
+            &coverage_to_rgba(bitmap),
+        )?;
+
+        Ok((page.texture_id, page.packer.uv_rect(x, y, width, height)))
+    }
+
+    fn add_page(&mut self, texture_manager: &mut TextureManager) -> Result<(), String> {
+        let texture_id = texture_manager.create_blank_texture(self.page_size, self.page_size)?;
+        self.pages.push(AtlasPage {
+            texture_id,
+            packer: AtlasPacker::new(self.page_size, self.page_size).with_padding(GLYPH_PADDING),
+        });
+        Ok(())
+    }
+
+    /// Number of atlas page textures allocated
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Side length of each atlas page in pixels
+    pub fn page_size(&self) -> u32 {
+        self.page_size
+    }
+
+    /// Textures backing the atlas pages
+    pub fn page_textures(&self) -> Vec<TextureId> {
+        self.pages.iter().map(|page| page.texture_id).collect()
+    }
+}
+
+/// Expand coverage values into white RGBA pixels so the text color can be applied in the shader
+fn coverage_to_rgba(bitmap: &[u8]) -> Vec<u8> {
+    bitmap
+        .iter()
+        .flat_map(|&alpha| [255, 255, 255, alpha])
+        .collect()
+}
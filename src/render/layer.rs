@@ -0,0 +1,204 @@
+use glam::Vec2;
+
+/// Per-layer view settings relative to the camera
+///
+/// A scroll factor of 0 pins the layer to the screen (HUD), 1 moves it with the
+/// world, and values in between give parallax (e.g. 0.5 for a distant background).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderLayer {
+    /// How much of the camera movement is applied to the layer, per axis
+    pub scroll_factor: Vec2,
+    /// Constant offset added after scrolling
+    pub offset: Vec2,
+}
+
+impl RenderLayer {
+    /// A layer that moves with the world
+    pub fn world() -> Self {
+        Self {
+            scroll_factor: Vec2::ONE,
+            offset: Vec2::ZERO,
+        }
+    }
+
+    /// A layer pinned to the screen, unaffected by the camera
+    pub fn screen_fixed() -> Self {
+        Self {
+            scroll_factor: Vec2::ZERO,
+            offset: Vec2::ZERO,
+        }
+    }
+
+    /// A parallax layer scrolling at `factor` times the camera speed on both axes
+    pub fn parallax(factor: f32) -> Self {
+        Self {
+            scroll_factor: Vec2::splat(factor),
+            offset: Vec2::ZERO,
+        }
+    }
+
+    /// Builder: set the constant offset
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Translation applied to everything on this layer for the given camera position
+    pub fn view_offset(&self, camera_position: Vec2) -> Vec2 {
+        self.offset - camera_position * self.scroll_factor
+    }
+}
+
+/// Whether positions are in the world, moving with the camera, or fixed to the screen
+///
+/// Both use the viewport's logical coordinates; screen space just ignores the camera.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DrawSpace {
+    /// Scrolled by the camera and the layer's scroll factor
+    #[default]
+    World,
+    /// Fixed on screen, like a HUD; only the layer's constant offset applies
+    Screen,
+}
+
+impl DrawSpace {
+    /// The camera position as seen by things drawn in this space
+    pub fn camera_position(self, camera_position: Vec2) -> Vec2 {
+        match self {
+            DrawSpace::World => camera_position,
+            DrawSpace::Screen => Vec2::ZERO,
+        }
+    }
+}
+
+impl Default for RenderLayer {
+    fn default() -> Self {
+        Self::world()
+    }
+}
+
+/// Color modulation for a whole layer or the whole screen: `color * multiply + add`
+///
+/// Cheap enough for day-night cycles and full-screen damage flashes without the
+/// lighting system. The multiply alpha scales opacity, for fades.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorTint {
+    pub multiply: (f32, f32, f32, f32),
+    pub add: (f32, f32, f32),
+}
+
+impl Default for ColorTint {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ColorTint {
+    /// Leaves colors unchanged
+    pub const IDENTITY: Self = Self {
+        multiply: (1.0, 1.0, 1.0, 1.0),
+        add: (0.0, 0.0, 0.0),
+    };
+
+    /// Multiply colors, e.g. a dark blue for night
+    pub fn multiply(r: f32, g: f32, b: f32) -> Self {
+        Self {
+            multiply: (r, g, b, 1.0),
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Add a color on top, e.g. red for a damage flash
+    pub fn add(r: f32, g: f32, b: f32) -> Self {
+        Self {
+            add: (r, g, b),
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Builder: scale opacity
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.multiply.3 = alpha;
+        self
+    }
+
+    /// Apply to an RGBA color
+    pub fn apply(&self, color: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+        let (m, a) = (self.multiply, self.add);
+        (
+            color.0 * m.0 + a.0,
+            color.1 * m.1 + a.1,
+            color.2 * m.2 + a.2,
+            color.3 * m.3,
+        )
+    }
+
+    /// The tint that applies `self` and then `outer`
+    pub fn then(&self, outer: ColorTint) -> ColorTint {
+        let (m, a) = (outer.multiply, outer.add);
+        ColorTint {
+            multiply: (
+                self.multiply.0 * m.0,
+                self.multiply.1 * m.1,
+                self.multiply.2 * m.2,
+                self.multiply.3 * m.3,
+            ),
+            add: (
+                self.add.0 * m.0 + a.0,
+                self.add.1 * m.1 + a.1,
+                self.add.2 * m.2 + a.2,
+            ),
+        }
+    }
+
+    /// Blend toward `other` by `t` (0 = self, 1 = other)
+    pub fn lerp(&self, other: ColorTint, t: f32) -> ColorTint {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        let (m, n) = (self.multiply, other.multiply);
+        ColorTint {
+            multiply: (mix(m.0, n.0), mix(m.1, n.1), mix(m.2, n.2), mix(m.3, n.3)),
+            add: (
+                mix(self.add.0, other.add.0),
+                mix(self.add.1, other.add.1),
+                mix(self.add.2, other.add.2),
+            ),
+        }
+    }
+}
+
+/// A tint moving linearly from one value to another over a time span
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TintFade {
+    pub from: ColorTint,
+    pub to: ColorTint,
+    /// Time the fade starts, in the same clock passed to `value_at`
+    pub start: f32,
+    /// Fade length in seconds; 0 switches immediately
+    pub duration: f32,
+}
+
+impl TintFade {
+    /// A constant tint
+    pub fn constant(tint: ColorTint) -> Self {
+        Self {
+            from: tint,
+            to: tint,
+            start: 0.0,
+            duration: 0.0,
+        }
+    }
+
+    /// The tint at `time`
+    pub fn value_at(&self, time: f32) -> ColorTint {
+        if self.duration <= 0.0 {
+            return self.to;
+        }
+        let t = ((time - self.start) / self.duration).clamp(0.0, 1.0);
+        self.from.lerp(self.to, t)
+    }
+
+    /// Check if the fade has reached its target at `time`
+    pub fn is_finished(&self, time: f32) -> bool {
+        time >= self.start + self.duration
+    }
+}
@@ -0,0 +1,351 @@
+use super::blend::BlendMode;
+use super::gl_wrapper::GlWrapper;
+use super::shader;
+use super::viewport::Viewport;
+use glam::Vec2;
+use std::rc::Rc;
+
+/// Maximum number of lights drawn per frame (matches the lighting shader)
+pub const MAX_LIGHTS: usize = 32;
+/// Maximum number of shadow-casting occluders (matches the lighting shader)
+pub const MAX_OCCLUDERS: usize = 32;
+
+/// Shape of a light's emission
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LightKind {
+    /// Emits in all directions
+    Point,
+    /// Emits in a cone around `direction` with the given half angle in radians
+    Cone { direction: Vec2, half_angle: f32 },
+}
+
+/// A 2D light positioned in viewport logical coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Light {
+    pub position: Vec2,
+    pub color: (f32, f32, f32),
+    pub intensity: f32,
+    /// Distance at which the light fades to zero, in logical units
+    pub radius: f32,
+    pub kind: LightKind,
+    /// Whether occluders block this light
+    pub casts_shadows: bool,
+}
+
+impl Light {
+    /// Create a point light
+    pub fn point(position: Vec2, radius: f32, color: (f32, f32, f32), intensity: f32) -> Self {
+        Self {
+            position,
+            color,
+            intensity,
+            radius,
+            kind: LightKind::Point,
+            casts_shadows: false,
+        }
+    }
+
+    /// Create a cone light pointing along `direction`
+    pub fn cone(
+        position: Vec2,
+        radius: f32,
+        direction: Vec2,
+        half_angle: f32,
+        color: (f32, f32, f32),
+        intensity: f32,
+    ) -> Self {
+        Self {
+            position,
+            color,
+            intensity,
+            radius,
+            kind: LightKind::Cone {
+                direction,
+                half_angle,
+            },
+            casts_shadows: false,
+        }
+    }
+
+    /// Enable or disable shadow casting from occluders
+    pub fn with_shadows(mut self, casts_shadows: bool) -> Self {
+        self.casts_shadows = casts_shadows;
+        self
+    }
+}
+
+/// An axis-aligned rectangle that blocks shadow-casting lights
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Occluder {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Occluder {
+    /// Create an occluder from two corners
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self {
+            min: min.min(max),
+            max: min.max(max),
+        }
+    }
+
+    /// Create an occluder from its center and size (matches sprite placement)
+    pub fn from_center_size(center: Vec2, size: Vec2) -> Self {
+        let half = size.abs() * 0.5;
+        Self::new(center - half, center + half)
+    }
+}
+
+/// Lights packed into the vec4 uniform arrays shared by the lighting and sprite shaders
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LightUniforms {
+    count: usize,
+    logical_bounds: (f32, f32, f32, f32),
+    pos_radius: Vec<f32>,
+    color_cone: Vec<f32>,
+    dir_shadow: Vec<f32>,
+}
+
+impl LightUniforms {
+    fn pack(lights: &[Light], logical_bounds: (f32, f32, f32, f32)) -> Self {
+        let mut uniforms = Self {
+            count: lights.len(),
+            logical_bounds,
+            pos_radius: Vec::with_capacity(lights.len() * 4),
+            color_cone: Vec::with_capacity(lights.len() * 4),
+            dir_shadow: Vec::with_capacity(lights.len() * 4),
+        };
+        for light in lights {
+            let (direction, cone) = match light.kind {
+                LightKind::Point => (Vec2::ZERO, -2.0),
+                LightKind::Cone {
+                    direction,
+                    half_angle,
+                } => (direction.normalize_or_zero(), half_angle.cos()),
+            };
+            uniforms.pos_radius.extend_from_slice(&[
+                light.position.x,
+                light.position.y,
+                light.radius.max(f32::EPSILON),
+                light.intensity,
+            ]);
+            uniforms.color_cone.extend_from_slice(&[
+                light.color.0,
+                light.color.1,
+                light.color.2,
+                cone,
+            ]);
+            uniforms.dir_shadow.extend_from_slice(&[
+                direction.x,
+                direction.y,
+                if light.casts_shadows { 1.0 } else { 0.0 },
+                0.0,
+            ]);
+        }
+        uniforms
+    }
+
+    /// Number of packed lights
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Upload the light arrays and logical bounds to the currently bound program
+    pub fn upload(&self, gl: &GlWrapper, shader: u32) -> Result<(), String> {
+        let (x_min, x_max, y_min, y_max) = self.logical_bounds;
+        let bounds_loc = gl.get_uniform_location(shader, "logical_bounds")?;
+        gl.set_uniform_4f(bounds_loc, x_min, x_max, y_min, y_max)?;
+
+        let light_count_loc = gl.get_uniform_location(shader, "light_count")?;
+        gl.set_uniform_1i(light_count_loc, self.count as i32)?;
+        let loc = gl.get_uniform_location(shader, "light_pos_radius")?;
+        gl.set_uniform_4fv(loc, &self.pos_radius)?;
+        let loc = gl.get_uniform_location(shader, "light_color_cone")?;
+        gl.set_uniform_4fv(loc, &self.color_cone)?;
+        let loc = gl.get_uniform_location(shader, "light_dir_shadow")?;
+        gl.set_uniform_4fv(loc, &self.dir_shadow)?;
+        Ok(())
+    }
+}
+
+/// Lighting pass composited over the frame by multiplying it with the light buffer
+pub struct LightingRenderer {
+    gl: Rc<GlWrapper>,
+    shader: Option<u32>,
+    quad_vao: Option<u32>,
+    quad_vbo: Option<u32>,
+    enabled: bool,
+    pub ambient_color: (f32, f32, f32),
+    /// Logical coordinate space that lights and occluders are specified in
+    pub viewport: Viewport,
+    pub lights: Vec<Light>,
+    pub occluders: Vec<Occluder>,
+    initialized: bool,
+}
+
+impl LightingRenderer {
+    /// Create a new lighting renderer (disabled until `set_enabled(true)`)
+    pub fn new(gl: Rc<GlWrapper>) -> Self {
+        Self {
+            gl,
+            shader: None,
+            quad_vao: None,
+            quad_vbo: None,
+            enabled: false,
+            ambient_color: (1.0, 1.0, 1.0),
+            viewport: Viewport::new(),
+            lights: Vec::new(),
+            occluders: Vec::new(),
+            initialized: false,
+        }
+    }
+
+    /// Initialize the lighting renderer
+    pub fn initialize(&mut self) -> Result<(), String> {
+        if self.initialized {
+            return Ok(());
+        }
+
+        let shader = shader::compile_program(
+            &self.gl,
+            include_str!("shaders/lighting.vert"),
+            include_str!("shaders/lighting.frag"),
+        )?;
+
+        // Fullscreen quad in NDC
+        let vertices: [f32; 8] = [-1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0];
+        let vao = self.gl.gen_vertex_array()?;
+        let vbo = self.gl.gen_buffer()?;
+        self.gl.bind_vertex_array(vao)?;
+        self.gl.bind_buffer(gl::ARRAY_BUFFER, vbo)?;
+        self.gl
+            .set_buffer_data(gl::ARRAY_BUFFER, &vertices, gl::STATIC_DRAW)?;
+        self.gl.set_vertex_attrib_pointer(
+            0,
+            2,
+            gl::FLOAT,
+            false,
+            2 * std::mem::size_of::<f32>() as i32,
+            0,
+        )?;
+        self.gl.enable_vertex_attrib_array(0)?;
+        self.gl.bind_buffer(gl::ARRAY_BUFFER, 0)?;
+        self.gl.bind_vertex_array(0)?;
+
+        self.shader = Some(shader);
+        self.quad_vao = Some(vao);
+        self.quad_vbo = Some(vbo);
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Enable or disable the lighting pass
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Check if the lighting pass is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Set the ambient light color applied everywhere
+    pub fn set_ambient_color(&mut self, color: (f32, f32, f32)) {
+        self.ambient_color = color;
+    }
+
+    /// Add a light; returns its index
+    pub fn add_light(&mut self, light: Light) -> usize {
+        self.lights.push(light);
+        self.lights.len() - 1
+    }
+
+    /// Add a shadow-casting occluder; returns its index
+    pub fn add_occluder(&mut self, occluder: Occluder) -> usize {
+        self.occluders.push(occluder);
+        self.occluders.len() - 1
+    }
+
+    /// Remove all lights and occluders
+    pub fn clear(&mut self) {
+        self.lights.clear();
+        self.occluders.clear();
+    }
+
+    /// Pack the current lights for upload to a shader (used by normal-mapped sprites)
+    pub fn light_uniforms(&self) -> LightUniforms {
+        LightUniforms::pack(
+            &self.lights[..self.lights.len().min(MAX_LIGHTS)],
+            self.viewport.get_logical_bounds(),
+        )
+    }
+
+    /// Composite the lighting over everything drawn so far this frame
+    ///
+    /// Lights beyond `MAX_LIGHTS` and occluders beyond `MAX_OCCLUDERS` are ignored.
+    pub fn render(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !self.initialized {
+            return Err("Lighting renderer not initialized".to_string());
+        }
+
+        let shader = self.shader.ok_or("Lighting shader not available")?;
+        let vao = self.quad_vao.ok_or("Lighting VAO not available")?;
+
+        let occluders = &self.occluders[..self.occluders.len().min(MAX_OCCLUDERS)];
+        let occluder_data: Vec<f32> = occluders
+            .iter()
+            .flat_map(|o| [o.min.x, o.min.y, o.max.x, o.max.y])
+            .collect();
+
+        self.gl.use_program(shader)?;
+
+        let ambient_loc = self.gl.get_uniform_location(shader, "ambient_color")?;
+        self.gl.set_uniform_3f(
+            ambient_loc,
+            self.ambient_color.0,
+            self.ambient_color.1,
+            self.ambient_color.2,
+        )?;
+        self.light_uniforms().upload(&self.gl, shader)?;
+
+        let occluder_count_loc = self.gl.get_uniform_location(shader, "occluder_count")?;
+        self.gl
+            .set_uniform_1i(occluder_count_loc, occluders.len() as i32)?;
+        let loc = self.gl.get_uniform_location(shader, "occluders")?;
+        self.gl.set_uniform_4fv(loc, &occluder_data)?;
+
+        // Multiply the frame by the light buffer, then restore standard alpha blending
+        self.gl.enable_blending()?;
+        self.gl.set_blend_func(gl::DST_COLOR, gl::ZERO)?;
+        self.gl.bind_vertex_array(vao)?;
+        self.gl.draw_arrays(gl::TRIANGLE_STRIP, 0, 4)?;
+        self.gl.bind_vertex_array(0)?;
+        BlendMode::Alpha.apply(&self.gl)?;
+
+        Ok(())
+    }
+
+    /// Cleanup resources
+    pub fn cleanup(&mut self) {
+        if let Some(shader) = self.shader.take() {
+            let _ = self.gl.delete_program(shader);
+        }
+        if let Some(vao) = self.quad_vao.take() {
+            let _ = self.gl.delete_vertex_array(vao);
+        }
+        if let Some(vbo) = self.quad_vbo.take() {
+            let _ = self.gl.delete_buffer(vbo);
+        }
+        self.initialized = false;
+    }
+}
+
+impl Drop for LightingRenderer {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
@@ -1,14 +1,32 @@
+pub mod atlas;
+pub mod bidi;
+#[cfg(feature = "opengl")]
+pub mod blend;
+pub mod bmfont;
+pub mod camera;
+#[cfg(feature = "opengl")]
+pub mod font_loader;
 #[cfg(feature = "opengl")]
 pub mod gl_wrapper;
 #[cfg(feature = "opengl")]
+pub mod glyph_atlas;
+pub mod layer;
+#[cfg(feature = "opengl")]
+pub mod lighting;
+pub mod popup_text;
+pub mod pseudo_locale;
+#[cfg(feature = "opengl")]
 pub mod renderer;
 #[cfg(feature = "opengl")]
 pub mod shader;
+pub mod shader_preprocessor;
 #[cfg(feature = "opengl")]
 pub mod simple_text;
 #[cfg(feature = "opengl")]
 pub mod sprite;
 #[cfg(feature = "opengl")]
+pub mod sprite_store;
+#[cfg(feature = "opengl")]
 pub mod text;
 #[cfg(feature = "opengl")]
 pub mod text_utils;
@@ -0,0 +1,277 @@
+use glam::Vec2;
+
+/// How a popup moves away from where it was spawned
+///
+/// Distances are in font pixels, like text outline and shadow offsets, so motion scales
+/// with the text rather than with the world's units. Y points up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PopupMotion {
+    /// Stay in place
+    Fixed,
+    /// Float up by `distance`, fast at first and settling at the end
+    Rise { distance: f32 },
+    /// Get tossed with `velocity` and fall back under `gravity`
+    Arc { velocity: Vec2, gravity: f32 },
+}
+
+impl PopupMotion {
+    /// Offset from the spawn point after `age` of a `lifetime` in seconds
+    pub fn offset(&self, age: f32, lifetime: f32) -> Vec2 {
+        match *self {
+            PopupMotion::Fixed => Vec2::ZERO,
+            PopupMotion::Rise { distance } => {
+                let t = 1.0 - (age / lifetime).clamp(0.0, 1.0);
+                Vec2::new(0.0, distance * (1.0 - t * t * t))
+            }
+            PopupMotion::Arc { velocity, gravity } => {
+                velocity * age - Vec2::new(0.0, 0.5 * gravity * age * age)
+            }
+        }
+    }
+}
+
+/// Motion, timing and look of a popup
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PopupStyle {
+    pub motion: PopupMotion,
+    /// Seconds the popup lives for
+    pub lifetime: f32,
+    pub color: (f32, f32, f32),
+    /// Size relative to the font
+    pub scale: f32,
+    /// Scale multiplier at spawn, shrinking back to 1 over `pop_duration` seconds
+    pub pop_scale: f32,
+    pub pop_duration: f32,
+    /// Fraction of the lifetime, at its end, spent fading out
+    pub fade_out: f32,
+}
+
+impl Default for PopupStyle {
+    fn default() -> Self {
+        Self {
+            motion: PopupMotion::Rise { distance: 40.0 },
+            lifetime: 1.0,
+            color: (1.0, 1.0, 1.0),
+            scale: 1.0,
+            pop_scale: 1.0,
+            pop_duration: 0.0,
+            fade_out: 0.4,
+        }
+    }
+}
+
+impl PopupStyle {
+    /// Quick rise for regular damage numbers
+    pub fn damage() -> Self {
+        Self {
+            motion: PopupMotion::Rise { distance: 30.0 },
+            lifetime: 0.8,
+            color: (1.0, 0.3, 0.25),
+            ..Self::default()
+        }
+    }
+
+    /// Larger, punchier numbers for critical hits
+    pub fn critical() -> Self {
+        Self {
+            motion: PopupMotion::Arc {
+                velocity: Vec2::new(20.0, 90.0),
+                gravity: 180.0,
+            },
+            lifetime: 1.0,
+            color: (1.0, 0.85, 0.1),
+            scale: 1.4,
+            pop_scale: 1.8,
+            pop_duration: 0.15,
+            ..Self::default()
+        }
+    }
+
+    pub fn heal() -> Self {
+        Self {
+            color: (0.3, 1.0, 0.4),
+            ..Self::damage()
+        }
+    }
+
+    /// Slow drift for pickups like "+10 gold"
+    pub fn pickup() -> Self {
+        Self {
+            motion: PopupMotion::Rise { distance: 50.0 },
+            lifetime: 1.5,
+            color: (1.0, 0.8, 0.2),
+            fade_out: 0.3,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_color(mut self, r: f32, g: f32, b: f32) -> Self {
+        self.color = (r, g, b);
+        self
+    }
+
+    pub fn with_motion(mut self, motion: PopupMotion) -> Self {
+        self.motion = motion;
+        self
+    }
+
+    pub fn with_lifetime(mut self, lifetime: f32) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Opacity after `age` seconds
+    pub fn alpha(&self, age: f32) -> f32 {
+        let fade_time = self.lifetime * self.fade_out;
+        if fade_time <= 0.0 {
+            return 1.0;
+        }
+        ((self.lifetime - age) / fade_time).clamp(0.0, 1.0)
+    }
+
+    /// Size relative to the font after `age` seconds
+    pub fn scale_at(&self, age: f32) -> f32 {
+        if age >= self.pop_duration {
+            return self.scale;
+        }
+        let t = age / self.pop_duration;
+        self.scale * (self.pop_scale + (1.0 - self.pop_scale) * t * t)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Popup {
+    text: String,
+    position: Vec2,
+    age: f32,
+    style: PopupStyle,
+}
+
+/// A popup as it should be drawn this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PopupInstance<'a> {
+    pub text: &'a str,
+    /// Spawn point in world (logical) coordinates; the text is centered on it
+    pub position: Vec2,
+    /// Offset from `position` in font pixels
+    pub offset: Vec2,
+    pub color: (f32, f32, f32),
+    pub alpha: f32,
+    pub scale: f32,
+}
+
+/// Pooled short-lived floating texts, such as damage numbers and "+10 gold"
+///
+/// Slots are allocated up front and reused, strings included, so spawning in bursts
+/// doesn't allocate once the pool has warmed up. When the pool is full the oldest popup
+/// is replaced. Draw every popup in one batch with `TextRenderer::render_popups`.
+#[derive(Debug, Clone)]
+pub struct PopupTexts {
+    /// Live popups come first, followed by dead slots kept for their string buffers
+    popups: Vec<Popup>,
+    live: usize,
+    capacity: usize,
+}
+
+impl PopupTexts {
+    /// Create a pool holding at most `capacity` popups at once
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            popups: Vec::with_capacity(capacity),
+            live: 0,
+            capacity,
+        }
+    }
+
+    /// Number of live popups
+    pub fn len(&self) -> usize {
+        self.live
+    }
+
+    /// Check if there are no live popups
+    pub fn is_empty(&self) -> bool {
+        self.live == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Spawn a popup at a world position; returns false only if the capacity is zero
+    pub fn spawn(&mut self, text: &str, position: Vec2, style: PopupStyle) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+        let index = if self.live < self.capacity {
+            self.live += 1;
+            self.live - 1
+        } else {
+            self.oldest()
+        };
+        if index == self.popups.len() {
+            self.popups.push(Popup {
+                text: String::new(),
+                position,
+                age: 0.0,
+                style,
+            });
+        }
+
+        let popup = &mut self.popups[index];
+        popup.text.clear();
+        popup.text.push_str(text);
+        popup.position = position;
+        popup.age = 0.0;
+        popup.style = style;
+        true
+    }
+
+    /// Age every popup, removing the ones that outlived their style's lifetime
+    pub fn update(&mut self, delta_time: f32) {
+        let mut i = 0;
+        while i < self.live {
+            let popup = &mut self.popups[i];
+            popup.age += delta_time;
+            if popup.age >= popup.style.lifetime {
+                // Keep the dead slot past the live ones so its string is reused
+                self.live -= 1;
+                self.popups.swap(i, self.live);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    /// Remove every popup
+    pub fn clear(&mut self) {
+        self.live = 0;
+    }
+
+    /// Live popups with their motion, fade and scale applied
+    pub fn iter(&self) -> impl Iterator<Item = PopupInstance<'_>> {
+        self.popups[..self.live].iter().map(|popup| {
+            let style = &popup.style;
+            PopupInstance {
+                text: &popup.text,
+                position: popup.position,
+                offset: style.motion.offset(popup.age, style.lifetime),
+                color: style.color,
+                alpha: style.alpha(popup.age),
+                scale: style.scale_at(popup.age),
+            }
+        })
+    }
+
+    fn oldest(&self) -> usize {
+        self.popups[..self.live]
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.age.total_cmp(&b.age))
+            .map_or(0, |(index, _)| index)
+    }
+}
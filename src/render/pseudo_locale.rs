@@ -0,0 +1,140 @@
+/// Pseudo-localization: fake translations that expose layout problems before real ones exist
+///
+/// Letters are swapped for accented look-alikes (so untranslated, hard-coded strings stand
+/// out and glyph coverage is exercised), the text is padded to simulate longer languages,
+/// and brackets mark where each string starts and ends so truncation is easy to spot.
+/// Format placeholders such as `{name}` and `%s` are left untouched.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PseudoLocale {
+    /// Extra length as a fraction of the original (0.3 adds 30%)
+    pub expansion: f32,
+    pub accents: bool,
+    pub brackets: bool,
+}
+
+impl Default for PseudoLocale {
+    fn default() -> Self {
+        Self {
+            expansion: 0.3,
+            accents: true,
+            brackets: true,
+        }
+    }
+}
+
+impl PseudoLocale {
+    pub fn with_expansion(mut self, expansion: f32) -> Self {
+        self.expansion = expansion.max(0.0);
+        self
+    }
+
+    pub fn with_accents(mut self, accents: bool) -> Self {
+        self.accents = accents;
+        self
+    }
+
+    pub fn with_brackets(mut self, brackets: bool) -> Self {
+        self.brackets = brackets;
+        self
+    }
+
+    /// The pseudo-localized form of `text`, applied line by line
+    pub fn apply(&self, text: &str) -> String {
+        text.split('\n')
+            .map(|line| self.apply_line(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn apply_line(&self, line: &str) -> String {
+        let mut result = String::with_capacity(line.len() * 2);
+        if self.brackets {
+            result.push('[');
+        }
+
+        let mut letters = 0;
+        let mut chars = line.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' => {
+                    // Copy `{...}` placeholders verbatim
+                    result.push(ch);
+                    for inner in chars.by_ref() {
+                        result.push(inner);
+                        if inner == '}' {
+                            break;
+                        }
+                    }
+                }
+                '%' if chars.peek().is_some_and(|next| next.is_ascii_alphabetic()) => {
+                    result.push(ch);
+                    result.extend(chars.next());
+                }
+                _ => {
+                    if ch.is_alphanumeric() {
+                        letters += 1;
+                    }
+                    result.push(if self.accents { accented(ch) } else { ch });
+                }
+            }
+        }
+
+        // Pad based on visible text so placeholders don't inflate short strings
+        let padding = (letters as f32 * self.expansion).ceil() as usize;
+        if padding > 0 {
+            result.push(' ');
+            result.extend(std::iter::repeat_n('~', padding));
+        }
+        if self.brackets {
+            result.push(']');
+        }
+        result
+    }
+}
+
+/// An accented look-alike for ASCII letters; other characters are returned as-is
+fn accented(ch: char) -> char {
+    match ch {
+        'a' => 'á',
+        'b' => 'ƀ',
+        'c' => 'ç',
+        'd' => 'ð',
+        'e' => 'é',
+        'f' => 'ƒ',
+        'g' => 'ĝ',
+        'h' => 'ĥ',
+        'i' => 'í',
+        'j' => 'ĵ',
+        'k' => 'ķ',
+        'l' => 'ļ',
+        'n' => 'ñ',
+        'o' => 'ö',
+        'r' => 'ŕ',
+        's' => 'š',
+        't' => 'ţ',
+        'u' => 'ü',
+        'w' => 'ŵ',
+        'y' => 'ý',
+        'z' => 'ž',
+        'A' => 'Å',
+        'C' => 'Ç',
+        'D' => 'Ð',
+        'E' => 'É',
+        'G' => 'Ĝ',
+        'H' => 'Ĥ',
+        'I' => 'Î',
+        'J' => 'Ĵ',
+        'K' => 'Ķ',
+        'L' => 'Ļ',
+        'N' => 'Ñ',
+        'O' => 'Ö',
+        'R' => 'Ŕ',
+        'S' => 'Š',
+        'T' => 'Ţ',
+        'U' => 'Û',
+        'W' => 'Ŵ',
+        'Y' => 'Ý',
+        'Z' => 'Ž',
+        _ => ch,
+    }
+}
@@ -1,4 +1,5 @@
 use super::gl_wrapper::GlWrapper;
+use super::viewport::ViewportRect;
 use gl;
 use glam::Vec2;
 use std::rc::Rc;
@@ -75,6 +76,34 @@ impl Renderer {
         Ok(())
     }
 
+    /// Update the GL viewport to cover a framebuffer of the given size
+    /// Number of draw calls made by every renderer sharing this GL context since the last call
+    pub fn take_draw_calls(&self) -> u32 {
+        self.gl.take_draw_calls()
+    }
+
+    pub fn resize(&self, width: u32, height: u32) -> Result<(), String> {
+        self.gl.set_viewport(0, 0, width as i32, height as i32)
+    }
+
+    /// Render to part of the framebuffer, e.g. inside letterbox bars
+    pub fn set_viewport_rect(&self, rect: ViewportRect) -> Result<(), String> {
+        self.gl
+            .set_viewport(rect.x, rect.y, rect.width, rect.height)
+    }
+
+    /// Clip drawing to a rect in 0..1 across the view with y down (min is the top-left),
+    /// intersected with any enclosing clip rect; undo with `pop_clip_rect`
+    pub fn push_clip_rect(&self, min: Vec2, size: Vec2) -> Result<(), String> {
+        let rect = self.gl.viewport_rect().sub_rect(min, size);
+        self.gl.push_clip_rect(rect)
+    }
+
+    /// Restore the clip rect from before the last `push_clip_rect`
+    pub fn pop_clip_rect(&self) -> Result<(), String> {
+        self.gl.pop_clip_rect()
+    }
+
     pub fn clear(&self, r: f32, g: f32, b: f32, a: f32) -> Result<(), String> {
         self.gl.set_clear_color(r, g, b, a)?;
         self.gl.clear_color_buffer()
@@ -1 +1,619 @@
-// Shader management implementation will go here
+use super::gl_wrapper::GlWrapper;
+use super::shader_preprocessor::{PreprocessedShader, ShaderDefines, ShaderPreprocessor};
+use super::texture::TextureId;
+use glam::{Vec2, Vec3, Vec4};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Handle to a shader program registered with a `ShaderManager`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShaderId(pub u32);
+
+/// A value that can be assigned to a named shader uniform
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UniformValue {
+    Float(f32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+    /// Texture bound to its own texture unit before the draw
+    Texture(TextureId),
+}
+
+/// Where a shader program's GLSL came from, kept so it can be rebuilt
+#[derive(Debug, Clone)]
+enum ShaderSources {
+    Inline {
+        name: String,
+        vertex: String,
+        fragment: String,
+    },
+    Files {
+        vertex_path: PathBuf,
+        fragment_path: PathBuf,
+    },
+}
+
+/// Files a shader program was built from (including `#include`s), tracked for hot-reload
+#[derive(Debug, Clone)]
+struct WatchedFiles {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl WatchedFiles {
+    fn new(paths: &[PathBuf]) -> Self {
+        Self {
+            files: paths
+                .iter()
+                .map(|path| (path.clone(), modified_time(path)))
+                .collect(),
+        }
+    }
+
+    /// Refresh the stored modification times, returning true if any file changed
+    fn refresh(&mut self) -> bool {
+        let mut changed = false;
+        for (path, modified) in self.files.iter_mut() {
+            let current = modified_time(path);
+            changed |= current != *modified;
+            *modified = current;
+        }
+        changed
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// A compiled and linked shader program with cached uniform locations
+pub struct ShaderProgram {
+    pub name: String,
+    pub program: u32,
+    uniform_locations: RefCell<HashMap<String, i32>>,
+    sources: ShaderSources,
+    defines: ShaderDefines,
+    watched_files: Option<WatchedFiles>,
+}
+
+impl ShaderProgram {
+    /// Get the location of a uniform, caching the lookup
+    pub fn uniform_location(&self, gl: &GlWrapper, name: &str) -> Result<i32, String> {
+        if let Some(location) = self.uniform_locations.borrow().get(name) {
+            return Ok(*location);
+        }
+
+        let location = gl.get_uniform_location(self.program, name)?;
+        self.uniform_locations
+            .borrow_mut()
+            .insert(name.to_string(), location);
+        Ok(location)
+    }
+}
+
+/// Material describing which shader a sprite is drawn with and the uniform values to set
+///
+/// Custom sprite shaders receive the same vertex layout as the built-in sprite shader:
+/// `layout (location = 0) in vec2 position` and `layout (location = 1) in vec2 tex_coords`.
+/// The built-in uniforms (`sprite_position`, `sprite_size`, `tint_color`, `alpha`,
+/// `texture_sampler`, and the layer tint `layer_multiply`/`layer_add`) are set when the
+/// shader declares them.
+///
+/// Materials with `defines` draw with a variant of the shader compiled with those
+/// `#define`s; build it once with `ShaderManager::prepare_material`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Material {
+    pub shader: ShaderId,
+    pub uniforms: HashMap<String, UniformValue>,
+    pub defines: ShaderDefines,
+}
+
+impl Material {
+    /// Create a new material using the given shader
+    pub fn new(shader: ShaderId) -> Self {
+        Self {
+            shader,
+            uniforms: HashMap::new(),
+            defines: ShaderDefines::new(),
+        }
+    }
+
+    /// Set a shader define (builder style)
+    pub fn with_define(mut self, name: &str, value: &str) -> Self {
+        self.set_define(name, value);
+        self
+    }
+
+    /// Set a shader define used to select the compiled shader variant
+    pub fn set_define(&mut self, name: &str, value: &str) {
+        self.defines.insert(name.to_string(), value.to_string());
+    }
+
+    /// Set a uniform value (builder style)
+    pub fn with_uniform(mut self, name: &str, value: UniformValue) -> Self {
+        self.set_uniform(name, value);
+        self
+    }
+
+    /// Set a uniform value
+    pub fn set_uniform(&mut self, name: &str, value: UniformValue) {
+        self.uniforms.insert(name.to_string(), value);
+    }
+
+    /// Set a float uniform
+    pub fn set_float(&mut self, name: &str, value: f32) {
+        self.set_uniform(name, UniformValue::Float(value));
+    }
+
+    /// Set a vec2 uniform
+    pub fn set_vec2(&mut self, name: &str, value: Vec2) {
+        self.set_uniform(name, UniformValue::Vec2(value));
+    }
+
+    /// Set a vec3 uniform
+    pub fn set_vec3(&mut self, name: &str, value: Vec3) {
+        self.set_uniform(name, UniformValue::Vec3(value));
+    }
+
+    /// Set a vec4 uniform
+    pub fn set_vec4(&mut self, name: &str, value: Vec4) {
+        self.set_uniform(name, UniformValue::Vec4(value));
+    }
+
+    /// Set a texture uniform
+    pub fn set_texture(&mut self, name: &str, texture_id: TextureId) {
+        self.set_uniform(name, UniformValue::Texture(texture_id));
+    }
+
+    /// Get a uniform value by name
+    pub fn get_uniform(&self, name: &str) -> Option<&UniformValue> {
+        self.uniforms.get(name)
+    }
+
+    /// Remove a uniform value
+    pub fn remove_uniform(&mut self, name: &str) -> Option<UniformValue> {
+        self.uniforms.remove(name)
+    }
+}
+
+/// Shader manager that compiles user shader programs and applies material uniforms
+///
+/// Sources are run through a `ShaderPreprocessor`, so shaders can `#include` shared
+/// chunks and receive injected defines; compile errors are reported against the
+/// original files and lines.
+///
+/// Programs loaded from files can be hot-reloaded during development: when enabled,
+/// `poll_hot_reload` checks the source files (and their includes) for changes and
+/// recompiles them, keeping the previous program if the new sources fail to build.
+pub struct ShaderManager {
+    gl: Rc<GlWrapper>,
+    programs: HashMap<ShaderId, ShaderProgram>,
+    variants: HashMap<ShaderId, Vec<(ShaderDefines, ShaderId)>>,
+    preprocessor: ShaderPreprocessor,
+    next_id: u32,
+    hot_reload_enabled: bool,
+    hot_reload_interval: Duration,
+    last_hot_reload_check: Instant,
+}
+
+impl ShaderManager {
+    /// Create a new shader manager
+    pub fn new(gl: Rc<GlWrapper>) -> Self {
+        Self {
+            gl,
+            programs: HashMap::new(),
+            variants: HashMap::new(),
+            preprocessor: ShaderPreprocessor::new(),
+            next_id: 1,
+            hot_reload_enabled: false,
+            hot_reload_interval: Duration::from_millis(500),
+            last_hot_reload_check: Instant::now(),
+        }
+    }
+
+    /// Get the preprocessor used for shader sources
+    pub fn preprocessor(&self) -> &ShaderPreprocessor {
+        &self.preprocessor
+    }
+
+    /// Get the preprocessor mutably to add include directories, chunks or global defines
+    pub fn preprocessor_mut(&mut self) -> &mut ShaderPreprocessor {
+        &mut self.preprocessor
+    }
+
+    /// Compile and register a shader program from GLSL sources
+    pub fn create_program(
+        &mut self,
+        name: &str,
+        vertex_source: &str,
+        fragment_source: &str,
+    ) -> Result<ShaderId, String> {
+        let sources = ShaderSources::Inline {
+            name: name.to_string(),
+            vertex: vertex_source.to_string(),
+            fragment: fragment_source.to_string(),
+        };
+        self.build_and_insert(name, sources, ShaderDefines::new())
+    }
+
+    /// Compile and register a shader program from GLSL source files
+    ///
+    /// Programs created this way are watched for changes when hot-reload is enabled.
+    pub fn load_program(
+        &mut self,
+        name: &str,
+        vertex_path: &str,
+        fragment_path: &str,
+    ) -> Result<ShaderId, String> {
+        let sources = ShaderSources::Files {
+            vertex_path: PathBuf::from(vertex_path),
+            fragment_path: PathBuf::from(fragment_path),
+        };
+        self.build_and_insert(name, sources, ShaderDefines::new())
+    }
+
+    /// Get (compiling if needed) a variant of a shader built with extra defines
+    pub fn create_variant(
+        &mut self,
+        base: ShaderId,
+        defines: &ShaderDefines,
+    ) -> Result<ShaderId, String> {
+        if defines.is_empty() {
+            return Ok(base);
+        }
+        if let Some(id) = self.find_variant(base, defines) {
+            return Ok(id);
+        }
+
+        let program = self
+            .programs
+            .get(&base)
+            .ok_or_else(|| format!("Shader {:?} not registered", base))?;
+        let name = format!(
+            "{}[{}]",
+            program.name,
+            defines
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let sources = program.sources.clone();
+        let mut variant_defines = program.defines.clone();
+        variant_defines.extend(defines.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        let id = self.build_and_insert(&name, sources, variant_defines)?;
+        self.variants
+            .entry(base)
+            .or_default()
+            .push((defines.clone(), id));
+        Ok(id)
+    }
+
+    /// Compile the shader variant for a material's defines so it can be drawn
+    pub fn prepare_material(&mut self, material: &Material) -> Result<ShaderId, String> {
+        self.create_variant(material.shader, &material.defines)
+    }
+
+    fn find_variant(&self, base: ShaderId, defines: &ShaderDefines) -> Option<ShaderId> {
+        self.variants
+            .get(&base)?
+            .iter()
+            .find(|(variant_defines, _)| variant_defines == defines)
+            .map(|(_, id)| *id)
+    }
+
+    fn build_and_insert(
+        &mut self,
+        name: &str,
+        sources: ShaderSources,
+        defines: ShaderDefines,
+    ) -> Result<ShaderId, String> {
+        let (program, dependencies) =
+            build_program(&self.gl, &self.preprocessor, &sources, &defines)
+                .map_err(|e| format!("Failed to build shader '{}': {}", name, e))?;
+
+        let watched_files = match sources {
+            ShaderSources::Files { .. } => Some(WatchedFiles::new(&dependencies)),
+            ShaderSources::Inline { .. } => None,
+        };
+
+        let id = ShaderId(self.next_id);
+        self.next_id += 1;
+
+        self.programs.insert(
+            id,
+            ShaderProgram {
+                name: name.to_string(),
+                program,
+                uniform_locations: RefCell::new(HashMap::new()),
+                sources,
+                defines,
+                watched_files,
+            },
+        );
+
+        Ok(id)
+    }
+
+    /// Enable or disable shader hot-reload (development mode)
+    pub fn set_hot_reload(&mut self, enabled: bool) {
+        self.hot_reload_enabled = enabled;
+        if enabled {
+            // Don't pick up edits made while hot-reload was off as a burst of reloads
+            for program in self.programs.values_mut() {
+                if let Some(watched_files) = program.watched_files.as_mut() {
+                    watched_files.refresh();
+                }
+            }
+        }
+    }
+
+    /// Check if shader hot-reload is enabled
+    pub fn is_hot_reload_enabled(&self) -> bool {
+        self.hot_reload_enabled
+    }
+
+    /// Set how often `poll_hot_reload` checks the source files on disk
+    pub fn set_hot_reload_interval(&mut self, interval: Duration) {
+        self.hot_reload_interval = interval;
+    }
+
+    /// Reload changed shaders if hot-reload is enabled and the poll interval has elapsed
+    ///
+    /// Intended to be called once per frame. Returns the shaders that were reloaded.
+    pub fn poll_hot_reload(&mut self) -> Vec<ShaderId> {
+        if !self.hot_reload_enabled
+            || self.last_hot_reload_check.elapsed() < self.hot_reload_interval
+        {
+            return Vec::new();
+        }
+        self.last_hot_reload_check = Instant::now();
+        self.reload_changed()
+    }
+
+    /// Recompile every file-backed shader whose sources changed on disk
+    ///
+    /// If the new sources fail to compile or link, the error is logged and the
+    /// previous program stays in use. Returns the shaders that were reloaded.
+    pub fn reload_changed(&mut self) -> Vec<ShaderId> {
+        let mut reloaded = Vec::new();
+
+        for (id, program) in self.programs.iter_mut() {
+            let Some(watched_files) = program.watched_files.as_mut() else {
+                continue;
+            };
+            if !watched_files.refresh() {
+                continue;
+            }
+
+            let result = build_program(
+                &self.gl,
+                &self.preprocessor,
+                &program.sources,
+                &program.defines,
+            );
+
+            match result {
+                Ok((new_program, dependencies)) => {
+                    // Includes may have been added or removed
+                    *watched_files = WatchedFiles::new(&dependencies);
+                    let _ = self.gl.delete_program(program.program);
+                    program.program = new_program;
+                    program.uniform_locations.borrow_mut().clear();
+                    log::info!("Reloaded shader '{}'", program.name);
+                    reloaded.push(*id);
+                }
+                Err(e) => {
+                    log::error!(
+                        "Failed to reload shader '{}', keeping previous program: {}",
+                        program.name,
+                        e
+                    );
+                }
+            }
+        }
+
+        reloaded
+    }
+
+    /// Get a registered shader program
+    pub fn get_program(&self, id: ShaderId) -> Option<&ShaderProgram> {
+        self.programs.get(&id)
+    }
+
+    /// Find a shader program by the name it was registered with
+    pub fn find_by_name(&self, name: &str) -> Option<ShaderId> {
+        self.programs
+            .iter()
+            .find(|(_, program)| program.name == name)
+            .map(|(id, _)| *id)
+    }
+
+    /// Bind the material's shader program and upload its uniforms
+    ///
+    /// Texture uniforms are bound to consecutive texture units starting at `first_texture_unit`.
+    /// Returns the program so callers can set additional uniforms.
+    pub fn apply_material(
+        &self,
+        material: &Material,
+        first_texture_unit: u32,
+    ) -> Result<&ShaderProgram, String> {
+        let shader = if material.defines.is_empty() {
+            material.shader
+        } else {
+            self.find_variant(material.shader, &material.defines)
+                .ok_or_else(|| {
+                    format!(
+                        "Shader {:?} has no variant for the material's defines; call prepare_material first",
+                        material.shader
+                    )
+                })?
+        };
+        let program = self
+            .programs
+            .get(&shader)
+            .ok_or_else(|| format!("Shader {:?} not registered", shader))?;
+
+        self.gl.use_program(program.program)?;
+
+        let mut texture_unit = first_texture_unit;
+        for (name, value) in &material.uniforms {
+            let location = program.uniform_location(&self.gl, name)?;
+            match value {
+                UniformValue::Float(v) => self.gl.set_uniform_1f(location, *v)?,
+                UniformValue::Vec2(v) => self.gl.set_uniform_2f(location, v.x, v.y)?,
+                UniformValue::Vec3(v) => self.gl.set_uniform_3f(location, v.x, v.y, v.z)?,
+                UniformValue::Vec4(v) => self.gl.set_uniform_4f(location, v.x, v.y, v.z, v.w)?,
+                UniformValue::Texture(texture_id) => {
+                    self.gl.active_texture(gl::TEXTURE0 + texture_unit)?;
+                    self.gl.bind_texture(gl::TEXTURE_2D, texture_id.0)?;
+                    self.gl.set_uniform_1i(location, texture_unit as i32)?;
+                    texture_unit += 1;
+                }
+            }
+        }
+
+        // Leave texture unit 0 active for the main sprite texture
+        self.gl.active_texture(gl::TEXTURE0)?;
+
+        Ok(program)
+    }
+
+    /// Delete a shader program
+    pub fn delete_program(&mut self, id: ShaderId) -> Result<(), String> {
+        if let Some(program) = self.programs.remove(&id) {
+            self.gl.delete_program(program.program)?;
+        }
+        for (_, variant) in self.variants.remove(&id).unwrap_or_default() {
+            self.delete_program(variant)?;
+        }
+        for variants in self.variants.values_mut() {
+            variants.retain(|(_, variant)| *variant != id);
+        }
+        Ok(())
+    }
+
+    /// Delete all shader programs
+    pub fn clear_all(&mut self) {
+        self.variants.clear();
+        for (_, program) in self.programs.drain() {
+            let _ = self.gl.delete_program(program.program);
+        }
+    }
+}
+
+impl Drop for ShaderManager {
+    fn drop(&mut self) {
+        self.clear_all();
+    }
+}
+
+/// Preprocess and compile a program's sources, returning it with the files it was built from
+fn build_program(
+    gl: &GlWrapper,
+    preprocessor: &ShaderPreprocessor,
+    sources: &ShaderSources,
+    defines: &ShaderDefines,
+) -> Result<(u32, Vec<PathBuf>), String> {
+    let (vertex, fragment) = match sources {
+        ShaderSources::Inline {
+            name,
+            vertex,
+            fragment,
+        } => (
+            preprocessor.process_with_defines(vertex, &format!("{}.vert", name), defines)?,
+            preprocessor.process_with_defines(fragment, &format!("{}.frag", name), defines)?,
+        ),
+        ShaderSources::Files {
+            vertex_path,
+            fragment_path,
+        } => (
+            preprocessor.process_file_with_defines(vertex_path, defines)?,
+            preprocessor.process_file_with_defines(fragment_path, defines)?,
+        ),
+    };
+
+    let program = compile_preprocessed(gl, &vertex, &fragment)?;
+    let mut dependencies = vertex.dependencies;
+    for path in fragment.dependencies {
+        if !dependencies.contains(&path) {
+            dependencies.push(path);
+        }
+    }
+    Ok((program, dependencies))
+}
+
+/// Compile preprocessed shaders, mapping compile errors back to the original sources
+pub fn compile_preprocessed(
+    gl: &GlWrapper,
+    vertex: &PreprocessedShader,
+    fragment: &PreprocessedShader,
+) -> Result<u32, String> {
+    let vertex_shader = compile_stage(gl, gl::VERTEX_SHADER, &vertex.source).map_err(|e| {
+        format!(
+            "Vertex shader compilation failed: {}",
+            vertex.map_error_log(&e)
+        )
+    })?;
+    let fragment_shader = match compile_stage(gl, gl::FRAGMENT_SHADER, &fragment.source) {
+        Ok(shader) => shader,
+        Err(e) => {
+            gl.delete_shader(vertex_shader)?;
+            return Err(format!(
+                "Fragment shader compilation failed: {}",
+                fragment.map_error_log(&e)
+            ));
+        }
+    };
+    link_stages(gl, vertex_shader, fragment_shader)
+}
+
+/// Compile a vertex and fragment shader and link them into a program
+pub fn compile_program(
+    gl: &GlWrapper,
+    vertex_source: &str,
+    fragment_source: &str,
+) -> Result<u32, String> {
+    let vertex_shader = compile_stage(gl, gl::VERTEX_SHADER, vertex_source)
+        .map_err(|e| format!("Vertex shader compilation failed: {}", e))?;
+    let fragment_shader = match compile_stage(gl, gl::FRAGMENT_SHADER, fragment_source) {
+        Ok(shader) => shader,
+        Err(e) => {
+            gl.delete_shader(vertex_shader)?;
+            return Err(format!("Fragment shader compilation failed: {}", e));
+        }
+    };
+    link_stages(gl, vertex_shader, fragment_shader)
+}
+
+fn compile_stage(gl: &GlWrapper, kind: u32, source: &str) -> Result<u32, String> {
+    let shader = gl.create_shader(kind)?;
+    gl.set_shader_source(shader, source)?;
+    if let Err(e) = gl.compile_shader(shader) {
+        gl.delete_shader(shader)?;
+        return Err(e);
+    }
+    Ok(shader)
+}
+
+fn link_stages(gl: &GlWrapper, vertex_shader: u32, fragment_shader: u32) -> Result<u32, String> {
+    let program = gl.create_program()?;
+    gl.attach_shader(program, vertex_shader)?;
+    gl.attach_shader(program, fragment_shader)?;
+    let link_result = gl.link_program(program);
+
+    gl.delete_shader(vertex_shader)?;
+    gl.delete_shader(fragment_shader)?;
+
+    if let Err(e) = link_result {
+        gl.delete_program(program)?;
+        return Err(e);
+    }
+
+    Ok(program)
+}
@@ -0,0 +1,412 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `#define` values injected into a shader, ordered so identical sets compare equal
+pub type ShaderDefines = BTreeMap<String, String>;
+
+/// Built-in logical-space to NDC helpers matching the engine's viewport convention
+const VIEWPORT_CHUNK: &str = "\
+// Built-in viewport transforms (logical space is centered at the origin, Y up)
+vec2 logical_to_ndc(vec2 logical_pos, vec2 logical_size) {
+    return logical_pos / (logical_size * 0.5);
+}
+
+vec2 ndc_to_logical(vec2 ndc_pos, vec2 logical_size) {
+    return ndc_pos * (logical_size * 0.5);
+}
+";
+
+/// Built-in color space and blending helpers
+const COLOR_CHUNK: &str = "\
+// Built-in color utilities
+vec3 srgb_to_linear(vec3 color) {
+    return pow(color, vec3(2.2));
+}
+
+vec3 linear_to_srgb(vec3 color) {
+    return pow(color, vec3(1.0 / 2.2));
+}
+
+float luminance(vec3 color) {
+    return dot(color, vec3(0.2126, 0.7152, 0.0722));
+}
+
+vec4 premultiply(vec4 color) {
+    return vec4(color.rgb * color.a, color.a);
+}
+";
+
+/// Name used in line mappings for the injected `#define` block
+const DEFINES_SOURCE_NAME: &str = "<defines>";
+
+/// Maximum include nesting depth before the preprocessor gives up
+const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// Output of the preprocessor: expanded GLSL plus a map back to the original sources
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreprocessedShader {
+    /// Expanded GLSL source ready to hand to the driver
+    pub source: String,
+    /// Files on disk the shader was built from, in include order
+    pub dependencies: Vec<PathBuf>,
+    sources: Vec<String>,
+    /// (source index, 1-based line) for each output line
+    line_map: Vec<(usize, usize)>,
+}
+
+impl PreprocessedShader {
+    /// Map a 1-based line in the expanded source back to its original file and line
+    pub fn map_line(&self, line: usize) -> Option<(&str, usize)> {
+        let (source, original_line) = *self.line_map.get(line.checked_sub(1)?)?;
+        Some((self.sources[source].as_str(), original_line))
+    }
+
+    /// Rewrite line references in a driver compile log to point at the original files
+    ///
+    /// Recognizes the common `0:LINE` (Mesa, AMD, Intel) and `0(LINE)` (NVIDIA) forms.
+    pub fn map_error_log(&self, log: &str) -> String {
+        log.lines()
+            .map(|line| match find_line_reference(line) {
+                Some((start, end, number)) => match self.map_line(number) {
+                    Some((file, original_line)) => {
+                        format!(
+                            "{}{}:{}{}",
+                            &line[..start],
+                            file,
+                            original_line,
+                            &line[end..]
+                        )
+                    }
+                    None => line.to_string(),
+                },
+                None => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn push_line(&mut self, line: &str, source: usize, original_line: usize) {
+        self.source.push_str(line);
+        self.source.push('\n');
+        self.line_map.push((source, original_line));
+    }
+
+    fn source_index(&mut self, name: &str) -> usize {
+        match self.sources.iter().position(|s| s == name) {
+            Some(index) => index,
+            None => {
+                self.sources.push(name.to_string());
+                self.sources.len() - 1
+            }
+        }
+    }
+}
+
+/// Find a `0:LINE` or `0(LINE)` reference, returning its byte range and line number
+fn find_line_reference(line: &str) -> Option<(usize, usize, usize)> {
+    let bytes = line.as_bytes();
+    for start in 0..bytes.len() {
+        if bytes[start] != b'0' || (start > 0 && bytes[start - 1].is_ascii_digit()) {
+            continue;
+        }
+        let Some(&separator) = bytes.get(start + 1) else {
+            break;
+        };
+        if separator != b':' && separator != b'(' {
+            continue;
+        }
+
+        let digits_start = start + 2;
+        let digits_end = bytes[digits_start..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |offset| digits_start + offset);
+        if digits_end == digits_start {
+            continue;
+        }
+        if separator == b'(' && bytes.get(digits_end) != Some(&b')') {
+            continue;
+        }
+
+        let end = if separator == b'(' {
+            digits_end + 1
+        } else {
+            digits_end
+        };
+        let number = line[digits_start..digits_end].parse().ok()?;
+        return Some((start, end, number));
+    }
+    None
+}
+
+/// Tiny GLSL preprocessor handling `#include` and `#define` injection
+///
+/// `#include "name"` is resolved first against registered chunks (including the built-in
+/// `engine/viewport.glsl` and `engine/color.glsl`), then relative to the including file,
+/// then through the configured include directories. Each file is included at most once.
+/// Defines are inserted after the `#version` directive, or at the top if there is none.
+#[derive(Debug, Clone)]
+pub struct ShaderPreprocessor {
+    include_dirs: Vec<PathBuf>,
+    chunks: HashMap<String, String>,
+    defines: ShaderDefines,
+}
+
+impl Default for ShaderPreprocessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShaderPreprocessor {
+    /// Create a preprocessor with the engine's built-in chunks registered
+    pub fn new() -> Self {
+        let mut chunks = HashMap::new();
+        chunks.insert(
+            "engine/viewport.glsl".to_string(),
+            VIEWPORT_CHUNK.to_string(),
+        );
+        chunks.insert("engine/color.glsl".to_string(), COLOR_CHUNK.to_string());
+
+        Self {
+            include_dirs: Vec::new(),
+            chunks,
+            defines: ShaderDefines::new(),
+        }
+    }
+
+    /// Add a directory searched for `#include` files (builder style)
+    pub fn with_include_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.add_include_dir(dir);
+        self
+    }
+
+    /// Add a directory searched for `#include` files
+    pub fn add_include_dir<P: AsRef<Path>>(&mut self, dir: P) {
+        self.include_dirs.push(dir.as_ref().to_path_buf());
+    }
+
+    /// Register an in-memory chunk that can be included by name
+    pub fn add_chunk(&mut self, name: &str, source: &str) {
+        self.chunks.insert(name.to_string(), source.to_string());
+    }
+
+    /// Check if a chunk is registered under the given name
+    pub fn has_chunk(&self, name: &str) -> bool {
+        self.chunks.contains_key(name)
+    }
+
+    /// Set a define injected into every processed shader
+    pub fn define(&mut self, name: &str, value: &str) {
+        self.defines.insert(name.to_string(), value.to_string());
+    }
+
+    /// Remove a global define
+    pub fn undefine(&mut self, name: &str) {
+        self.defines.remove(name);
+    }
+
+    /// Get the global defines
+    pub fn defines(&self) -> &ShaderDefines {
+        &self.defines
+    }
+
+    /// Preprocess in-memory source; `source_name` is used in error mappings
+    pub fn process(&self, source: &str, source_name: &str) -> Result<PreprocessedShader, String> {
+        self.process_with_defines(source, source_name, &ShaderDefines::new())
+    }
+
+    /// Preprocess in-memory source with extra defines layered over the global ones
+    pub fn process_with_defines(
+        &self,
+        source: &str,
+        source_name: &str,
+        defines: &ShaderDefines,
+    ) -> Result<PreprocessedShader, String> {
+        self.run(source, source_name, None, defines)
+    }
+
+    /// Read and preprocess a shader file
+    pub fn process_file(&self, path: &Path) -> Result<PreprocessedShader, String> {
+        self.process_file_with_defines(path, &ShaderDefines::new())
+    }
+
+    /// Read and preprocess a shader file with extra defines layered over the global ones
+    pub fn process_file_with_defines(
+        &self,
+        path: &Path,
+        defines: &ShaderDefines,
+    ) -> Result<PreprocessedShader, String> {
+        let source = read_source(path)?;
+        self.run(&source, &path.display().to_string(), Some(path), defines)
+    }
+
+    fn run(
+        &self,
+        source: &str,
+        source_name: &str,
+        path: Option<&Path>,
+        defines: &ShaderDefines,
+    ) -> Result<PreprocessedShader, String> {
+        let mut output = PreprocessedShader {
+            source: String::new(),
+            dependencies: Vec::new(),
+            sources: Vec::new(),
+            line_map: Vec::new(),
+        };
+        let mut defines_all = self.defines.clone();
+        defines_all.extend(defines.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        let has_version = source
+            .lines()
+            .any(|line| line.trim_start().starts_with("#version"));
+        if !has_version {
+            Self::inject_defines(&mut output, &defines_all);
+        }
+
+        if let Some(path) = path {
+            output.dependencies.push(path.to_path_buf());
+        }
+
+        let mut included = HashSet::new();
+        let mut stack = vec![source_name.to_string()];
+        self.expand(
+            source,
+            source_name,
+            path.and_then(Path::parent),
+            &defines_all,
+            &mut output,
+            &mut included,
+            &mut stack,
+        )?;
+
+        Ok(output)
+    }
+
+    fn inject_defines(output: &mut PreprocessedShader, defines: &ShaderDefines) {
+        if defines.is_empty() {
+            return;
+        }
+        let source = output.source_index(DEFINES_SOURCE_NAME);
+        for (index, (name, value)) in defines.iter().enumerate() {
+            output.push_line(&format!("#define {} {}", name, value), source, index + 1);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn expand(
+        &self,
+        source: &str,
+        source_name: &str,
+        dir: Option<&Path>,
+        defines: &ShaderDefines,
+        output: &mut PreprocessedShader,
+        included: &mut HashSet<String>,
+        stack: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let source_index = output.source_index(source_name);
+        let is_root = stack.len() == 1;
+
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let trimmed = line.trim_start();
+
+            if let Some(rest) = trimmed.strip_prefix("#include") {
+                let target = parse_include_target(rest).ok_or_else(|| {
+                    format!(
+                        "{}:{}: malformed #include, expected #include \"file\"",
+                        source_name, line_number
+                    )
+                })?;
+                let (name, include_source, include_path) = self
+                    .resolve_include(target, dir)
+                    .map_err(|e| format!("{}:{}: {}", source_name, line_number, e))?;
+
+                if stack.contains(&name) {
+                    return Err(format!(
+                        "{}:{}: circular #include of '{}'",
+                        source_name, line_number, name
+                    ));
+                }
+                if stack.len() > MAX_INCLUDE_DEPTH {
+                    return Err(format!(
+                        "{}:{}: #include nested too deeply",
+                        source_name, line_number
+                    ));
+                }
+                if !included.insert(name.clone()) {
+                    continue;
+                }
+
+                if let Some(include_path) = include_path.as_deref() {
+                    output.dependencies.push(include_path.to_path_buf());
+                }
+                stack.push(name.clone());
+                self.expand(
+                    &include_source,
+                    &name,
+                    include_path.as_deref().and_then(Path::parent),
+                    defines,
+                    output,
+                    included,
+                    stack,
+                )?;
+                stack.pop();
+            } else {
+                output.push_line(line, source_index, line_number);
+                if is_root && trimmed.starts_with("#version") {
+                    Self::inject_defines(output, defines);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolve an include target to (display name, source, path on disk)
+    fn resolve_include(
+        &self,
+        target: &str,
+        dir: Option<&Path>,
+    ) -> Result<(String, String, Option<PathBuf>), String> {
+        if let Some(chunk) = self.chunks.get(target) {
+            return Ok((target.to_string(), chunk.clone(), None));
+        }
+
+        let candidates = dir
+            .into_iter()
+            .chain(self.include_dirs.iter().map(PathBuf::as_path))
+            .map(|d| d.join(target));
+        for candidate in candidates {
+            if candidate.is_file() {
+                let source = read_source(&candidate)?;
+                return Ok((candidate.display().to_string(), source, Some(candidate)));
+            }
+        }
+
+        Err(format!("cannot resolve #include \"{}\"", target))
+    }
+}
+
+fn parse_include_target(rest: &str) -> Option<&str> {
+    let rest = rest.trim();
+    let (open, close) = match rest.chars().next()? {
+        '"' => ('"', '"'),
+        '<' => ('<', '>'),
+        _ => return None,
+    };
+    let inner = rest.strip_prefix(open)?;
+    let end = inner.find(close)?;
+    let target = &inner[..end];
+    let trailing = inner[end + 1..].trim();
+    if target.is_empty() || !(trailing.is_empty() || trailing.starts_with("//")) {
+        return None;
+    }
+    Some(target)
+}
+
+fn read_source(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read shader file '{}': {}", path.display(), e))
+}
@@ -0,0 +1,92 @@
+#version 330 core
+#define MAX_LIGHTS 32
+#define MAX_OCCLUDERS 32
+
+in vec2 NdcPos;
+out vec4 FragColor;
+
+uniform vec4 logical_bounds; // x_min, x_max, y_min, y_max
+uniform vec3 ambient_color;
+
+uniform int light_count;
+uniform vec4 light_pos_radius[MAX_LIGHTS];  // xy = position, z = radius, w = intensity
+uniform vec4 light_color_cone[MAX_LIGHTS];  // rgb = color, a = cos(half angle), < -1 for point lights
+uniform vec4 light_dir_shadow[MAX_LIGHTS];  // xy = cone direction, z = casts shadows
+
+uniform int occluder_count;
+uniform vec4 occluders[MAX_OCCLUDERS];      // xy = min, zw = max
+
+// Slab test: does the segment from a to b pass through the rectangle?
+bool segment_hits_rect(vec2 a, vec2 b, vec4 rect) {
+    // Fragments inside an occluder are lit so the occluder itself is visible
+    if (a.x >= rect.x && a.x <= rect.z && a.y >= rect.y && a.y <= rect.w) {
+        return false;
+    }
+    vec2 d = b - a;
+    float t_min = 0.0;
+    float t_max = 1.0;
+    for (int axis = 0; axis < 2; axis++) {
+        float lo = axis == 0 ? rect.x : rect.y;
+        float hi = axis == 0 ? rect.z : rect.w;
+        if (abs(d[axis]) < 1e-6) {
+            if (a[axis] < lo || a[axis] > hi) {
+                return false;
+            }
+        } else {
+            float t1 = (lo - a[axis]) / d[axis];
+            float t2 = (hi - a[axis]) / d[axis];
+            t_min = max(t_min, min(t1, t2));
+            t_max = min(t_max, max(t1, t2));
+            if (t_min > t_max) {
+                return false;
+            }
+        }
+    }
+    return true;
+}
+
+void main() {
+    vec2 uv = NdcPos * 0.5 + 0.5;
+    vec2 frag_pos = vec2(
+        mix(logical_bounds.x, logical_bounds.y, uv.x),
+        mix(logical_bounds.z, logical_bounds.w, uv.y)
+    );
+
+    vec3 light = ambient_color;
+    for (int i = 0; i < light_count; i++) {
+        vec2 light_pos = light_pos_radius[i].xy;
+        float radius = light_pos_radius[i].z;
+        vec2 to_frag = frag_pos - light_pos;
+        float dist = length(to_frag);
+        if (dist >= radius) {
+            continue;
+        }
+
+        float attenuation = 1.0 - dist / radius;
+        attenuation *= attenuation;
+
+        float cone = light_color_cone[i].a;
+        if (cone > -1.5 && dist > 0.0) {
+            float cos_angle = dot(to_frag / dist, light_dir_shadow[i].xy);
+            // Soften the cone edge over the outer 10% of its angular range
+            attenuation *= smoothstep(cone, mix(cone, 1.0, 0.1), cos_angle);
+        }
+
+        if (light_dir_shadow[i].z > 0.5 && attenuation > 0.0) {
+            bool shadowed = false;
+            for (int j = 0; j < occluder_count; j++) {
+                if (segment_hits_rect(frag_pos, light_pos, occluders[j])) {
+                    shadowed = true;
+                    break;
+                }
+            }
+            if (shadowed) {
+                continue;
+            }
+        }
+
+        light += light_color_cone[i].rgb * light_pos_radius[i].w * attenuation;
+    }
+
+    FragColor = vec4(light, 1.0);
+}
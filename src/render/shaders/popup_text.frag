@@ -0,0 +1,13 @@
+#version 330 core
+in vec2 TexCoords;
+in vec4 Color;
+out vec4 FragColor;
+
+uniform sampler2D text_texture;
+uniform float color_glyph; // 1.0 while drawing from a full-color atlas such as emoji
+
+void main() {
+    vec4 texel = texture(text_texture, TexCoords);
+    vec3 color = mix(Color.rgb, texel.rgb, color_glyph);
+    FragColor = vec4(color, texel.a * Color.a);
+}
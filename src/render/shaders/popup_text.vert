@@ -0,0 +1,13 @@
+#version 330 core
+layout (location = 0) in vec2 position;   // NDC, already laid out
+layout (location = 1) in vec2 tex_coords;
+layout (location = 2) in vec4 color;      // rgb tint and alpha of the popup
+
+out vec2 TexCoords;
+out vec4 Color;
+
+void main() {
+    gl_Position = vec4(position, 0.0, 1.0);
+    TexCoords = tex_coords;
+    Color = color;
+}
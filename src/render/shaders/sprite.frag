@@ -1,12 +1,66 @@
 #version 330 core
+#define MAX_LIGHTS 32
+// Height of lights above the sprite plane, as a fraction of the light radius
+#define LIGHT_HEIGHT 0.25
+
 in vec2 TexCoords;
+in vec2 NdcPos;
 out vec4 FragColor;
 
 uniform sampler2D texture_sampler;
+uniform float alpha_cutoff;
 uniform vec3 tint_color;
 uniform float alpha;
+// Layer and global tint: color * layer_multiply.rgb + layer_add
+uniform vec4 layer_multiply;
+uniform vec3 layer_add;
+
+// Normal mapping (texture unit 1), lit by the lights of the lighting pass
+uniform sampler2D normal_sampler;
+uniform int use_normal_map;
+uniform vec2 normal_flip; // -1 on flipped axes
+uniform vec4 logical_bounds; // x_min, x_max, y_min, y_max
+uniform int light_count;
+uniform vec4 light_pos_radius[MAX_LIGHTS]; // xy = position, z = radius, w = intensity
+
+// Directional shading factor: attenuation-weighted average of N.L over nearby lights
+float normal_shading() {
+    vec3 normal = texture(normal_sampler, TexCoords).rgb * 2.0 - 1.0;
+    normal = normalize(vec3(normal.xy * normal_flip, normal.z));
+
+    vec2 uv = NdcPos * 0.5 + 0.5;
+    vec2 frag_pos = vec2(
+        mix(logical_bounds.x, logical_bounds.y, uv.x),
+        mix(logical_bounds.z, logical_bounds.w, uv.y)
+    );
+
+    float shade = 0.0;
+    float total_weight = 0.0;
+    for (int i = 0; i < light_count; i++) {
+        vec2 to_light = light_pos_radius[i].xy - frag_pos;
+        float radius = light_pos_radius[i].z;
+        float dist = length(to_light);
+        if (dist >= radius) {
+            continue;
+        }
+        float weight = (1.0 - dist / radius);
+        weight *= weight * light_pos_radius[i].w;
+        vec3 light_dir = normalize(vec3(to_light, LIGHT_HEIGHT * radius));
+        shade += weight * max(dot(normal, light_dir), 0.0);
+        total_weight += weight;
+    }
+    return total_weight > 0.0 ? shade / total_weight : 1.0;
+}
 
 void main() {
     vec4 tex_color = texture(texture_sampler, TexCoords);
-    FragColor = vec4(tex_color.rgb * tint_color, tex_color.a * alpha);
+    if (tex_color.a < alpha_cutoff) {
+        discard;
+    }
+    vec3 color = tex_color.rgb * tint_color;
+    if (use_normal_map != 0 && light_count > 0) {
+        color *= normal_shading();
+    }
+    color = color * layer_multiply.rgb + layer_add;
+    FragColor = vec4(color, tex_color.a * alpha * layer_multiply.a);
 }
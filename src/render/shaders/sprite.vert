@@ -4,11 +4,18 @@ layout (location = 1) in vec2 tex_coords;
 
 uniform vec2 sprite_position;
 uniform vec2 sprite_size;
+uniform vec4 uv_rect; // xy = offset, zw = scale
+uniform vec2 view_offset; // camera/layer translation
+// Unused here; custom materials build vertex effects on them
+uniform float time;        // seconds, from SpriteRenderer::set_time
+uniform float sprite_seed; // 0..1, different per sprite
 
 out vec2 TexCoords;
+out vec2 NdcPos;
 
 void main() {
-    vec2 world_pos = sprite_position + position * sprite_size;
+    vec2 world_pos = sprite_position + view_offset + position * sprite_size;
     gl_Position = vec4(world_pos, 0.0, 1.0);
-    TexCoords = tex_coords;
+    TexCoords = uv_rect.xy + tex_coords * uv_rect.zw;
+    NdcPos = world_pos;
 }
@@ -0,0 +1,19 @@
+#version 330 core
+in vec2 TexCoords;
+in vec4 InstanceColor;
+out vec4 FragColor;
+
+uniform sampler2D texture_sampler;
+uniform float alpha_cutoff;
+// Layer and global tint: color * layer_multiply.rgb + layer_add
+uniform vec4 layer_multiply;
+uniform vec3 layer_add;
+
+void main() {
+    vec4 tex_color = texture(texture_sampler, TexCoords);
+    if (tex_color.a < alpha_cutoff) {
+        discard;
+    }
+    vec3 color = tex_color.rgb * InstanceColor.rgb * layer_multiply.rgb + layer_add;
+    FragColor = vec4(color, tex_color.a * InstanceColor.a * layer_multiply.a);
+}
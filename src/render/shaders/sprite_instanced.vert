@@ -0,0 +1,21 @@
+#version 330 core
+layout (location = 0) in vec2 position;
+layout (location = 1) in vec2 tex_coords;
+
+// Per-instance attributes
+layout (location = 2) in vec4 instance_transform; // xy = position, zw = size
+layout (location = 3) in vec4 instance_color;     // rgb = tint, a = alpha
+layout (location = 4) in float instance_seed;     // 0..1, different per instance
+
+uniform vec2 view_offset; // camera/layer translation
+uniform float time;       // seconds, from SpriteRenderer::set_time
+
+out vec2 TexCoords;
+out vec4 InstanceColor;
+
+void main() {
+    vec2 world_pos = instance_transform.xy + view_offset + position * instance_transform.zw;
+    gl_Position = vec4(world_pos, 0.0, 1.0);
+    TexCoords = tex_coords;
+    InstanceColor = instance_color;
+}
@@ -5,8 +5,11 @@ out vec4 FragColor;
 uniform sampler2D text_texture;
 uniform vec3 text_color;
 uniform float alpha;
+uniform float color_glyph;     // 1.0 while drawing from a full-color atlas such as emoji
+uniform float use_glyph_color; // 0.0 for shadow and outline passes, which tint every glyph
 
 void main() {
-    vec4 sampled = vec4(1.0, 1.0, 1.0, texture(text_texture, TexCoords).a);
-    FragColor = vec4(text_color, 1.0) * sampled * alpha;
+    vec4 texel = texture(text_texture, TexCoords);
+    vec3 color = mix(text_color, texel.rgb, color_glyph * use_glyph_color);
+    FragColor = vec4(color, 1.0) * vec4(1.0, 1.0, 1.0, texel.a) * alpha;
 }
@@ -4,11 +4,12 @@ layout (location = 1) in vec2 tex_coords;
 
 uniform vec2 glyph_position;
 uniform vec2 glyph_size;
+uniform vec4 uv_rect; // Glyph region in the atlas: xy = offset, zw = scale
 
 out vec2 TexCoords;
 
 void main() {
     vec2 world_pos = glyph_position + position * glyph_size;
     gl_Position = vec4(world_pos, 0.0, 1.0);
-    TexCoords = tex_coords;
+    TexCoords = uv_rect.xy + tex_coords * uv_rect.zw;
 }
@@ -1,6 +1,13 @@
-use crate::render::text::{Text, TextAlign, TextRenderer};
+use crate::render::bidi::TextDirection;
+use crate::render::popup_text::PopupTexts;
+use crate::render::pseudo_locale::PseudoLocale;
+use crate::render::renderer::Renderer;
+use crate::render::text::{
+    PreparedText, Text, TextAlign, TextOutline, TextRenderer, TextReveal, TextShadow,
+};
 use crate::render::text_utils::TextUtils;
 use crate::render::viewport::Viewport;
+use crate::ui::{QuickDraw, QuickUi, SafeArea, TextField};
 use glam::Vec2;
 use std::collections::HashMap;
 use std::fs;
@@ -54,6 +61,12 @@ pub struct TextConfig {
     pub max_width: Option<f32>,
     pub line_spacing: f32,
     pub anchor: TextAnchor,
+    /// Position relative to the renderer's safe area instead of the whole screen
+    pub in_safe_area: bool,
+    pub direction: TextDirection,
+    pub outline: Option<TextOutline>,
+    pub shadow: Option<TextShadow>,
+    pub reveal: Option<TextReveal>,
 }
 
 impl Default for TextConfig {
@@ -67,6 +80,11 @@ impl Default for TextConfig {
             max_width: None,
             line_spacing: 1.2,
             anchor: TextAnchor::TopLeft,
+            in_safe_area: false,
+            direction: TextDirection::Auto,
+            outline: None,
+            shadow: None,
+            reveal: None,
         }
     }
 }
@@ -119,6 +137,54 @@ impl TextConfig {
         self.anchor = anchor;
         self
     }
+
+    /// Treat the position as relative to the safe area, e.g. (1, 0) is its top-right corner
+    pub fn in_safe_area(mut self) -> Self {
+        self.in_safe_area = true;
+        self
+    }
+
+    /// Set the reading direction (detected from the text by default)
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Outline the text, with the width in font pixels
+    pub fn outline(mut self, width: f32, r: f32, g: f32, b: f32) -> Self {
+        self.outline = Some(TextOutline {
+            color: (r, g, b),
+            width,
+        });
+        self
+    }
+
+    /// Draw a shadow behind the text, offset in font pixels (y down, like text positions)
+    pub fn shadow(
+        mut self,
+        offset_x: f32,
+        offset_y: f32,
+        r: f32,
+        g: f32,
+        b: f32,
+        alpha: f32,
+    ) -> Self {
+        self.shadow = Some(TextShadow {
+            offset: Vec2::new(offset_x, -offset_y),
+            color: (r, g, b),
+            alpha,
+        });
+        self
+    }
+
+    /// Show only the characters revealed after `elapsed` seconds at `chars_per_second`
+    pub fn reveal(mut self, chars_per_second: f32, elapsed: f32) -> Self {
+        self.reveal = Some(TextReveal {
+            elapsed,
+            ..TextReveal::new(chars_per_second)
+        });
+        self
+    }
 }
 
 /// Fluent text builder for method chaining
@@ -205,6 +271,44 @@ impl<'a> FluentTextBuilder<'a> {
         self
     }
 
+    /// Treat the position as relative to the safe area, e.g. (1, 0) is its top-right corner
+    pub fn in_safe_area(mut self) -> Self {
+        self.config.in_safe_area = true;
+        self
+    }
+
+    /// Set the reading direction (detected from the text by default)
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.config.direction = direction;
+        self
+    }
+
+    /// Outline the text, with the width in font pixels
+    pub fn outline(mut self, width: f32, r: f32, g: f32, b: f32) -> Self {
+        self.config = self.config.outline(width, r, g, b);
+        self
+    }
+
+    /// Draw a shadow behind the text, offset in font pixels (y down, like text positions)
+    pub fn shadow(
+        mut self,
+        offset_x: f32,
+        offset_y: f32,
+        r: f32,
+        g: f32,
+        b: f32,
+        alpha: f32,
+    ) -> Self {
+        self.config = self.config.shadow(offset_x, offset_y, r, g, b, alpha);
+        self
+    }
+
+    /// Show only the characters revealed after `elapsed` seconds at `chars_per_second`
+    pub fn reveal(mut self, chars_per_second: f32, elapsed: f32) -> Self {
+        self.config = self.config.reveal(chars_per_second, elapsed);
+        self
+    }
+
     /// Draw the text at the specified position
     pub fn draw(self, text: &str, x: f32, y: f32) -> Result<(), String> {
         self.renderer.draw_text_fluent(text, x, y, self.config)
@@ -216,6 +320,7 @@ pub struct SimpleTextRenderer {
     text_renderer: TextRenderer,
     fonts: HashMap<String, Font>, // Registry of available fonts
     fallback_font_path: String,   // Configurable fallback font path
+    safe_area: SafeArea,
 }
 
 impl SimpleTextRenderer {
@@ -229,6 +334,7 @@ impl SimpleTextRenderer {
             text_renderer,
             fonts: HashMap::new(),
             fallback_font_path,
+            safe_area: SafeArea::default(),
         })
     }
 
@@ -289,7 +395,7 @@ impl SimpleTextRenderer {
         let first_char_bearing = text
             .chars()
             .next()
-            .and_then(|ch| font_info.glyphs.get(&ch))
+            .and_then(|ch| font_info.glyph(ch))
             .map(|glyph| glyph.bearing.x * scale_factor)
             .unwrap_or(0.0);
 
@@ -374,7 +480,7 @@ impl SimpleTextRenderer {
     ) -> f32 {
         let mut width = 0.0;
         for ch in text.chars() {
-            if let Some(glyph) = font.glyphs.get(&ch) {
+            if let Some(glyph) = font.glyph(ch) {
                 width += glyph.advance * scale_factor;
             }
         }
@@ -402,10 +508,12 @@ impl SimpleTextRenderer {
         }
 
         // Convert from top-left coordinates to viewport coordinates first
-        let viewport_pos = self
-            .text_renderer
-            .viewport
-            .top_left_to_viewport(Vec2::new(x, y));
+        let position = if config.in_safe_area {
+            self.safe_area.to_screen(Vec2::new(x, y))
+        } else {
+            Vec2::new(x, y)
+        };
+        let viewport_pos = self.text_renderer.viewport.top_left_to_viewport(position);
 
         // Calculate anchor offset in viewport coordinates
         let anchor_offset = self.calculate_anchor_offset_viewport(text, &config, viewport_pos)?;
@@ -421,6 +529,10 @@ impl SimpleTextRenderer {
         text_obj.config.align = config.alignment;
         text_obj.config.max_width = config.max_width;
         text_obj.config.line_spacing = config.line_spacing;
+        text_obj.config.direction = config.direction;
+        text_obj.config.outline = config.outline;
+        text_obj.config.shadow = config.shadow;
+        text_obj.config.reveal = config.reveal;
 
         self.text_renderer.render_text(&text_obj)
     }
@@ -435,6 +547,63 @@ impl SimpleTextRenderer {
         self.text_renderer.load_font(name, font_path, size)
     }
 
+    /// Start loading a font on a background thread
+    pub fn load_font_async(
+        &mut self,
+        name: &str,
+        font_path: &str,
+        size: u32,
+    ) -> Result<(), String> {
+        self.text_renderer.load_font_async(name, font_path, size)
+    }
+
+    /// Check if a font is still being loaded in the background
+    pub fn is_font_loading(&self, name: &str) -> bool {
+        self.text_renderer.is_font_loading(name)
+    }
+
+    /// Rasterize any glyphs `text` needs up front instead of on the next frame
+    pub fn ensure_glyphs(&mut self, font_name: &str, text: &str) -> Result<(), String> {
+        self.text_renderer.ensure_glyphs(font_name, text)
+    }
+
+    /// Load a pre-baked bitmap font (AngelCode BMFont .fnt with its page textures)
+    pub fn load_bmfont(&mut self, name: &str, fnt_path: &str) -> Result<(), String> {
+        self.text_renderer.load_bmfont(name, fnt_path)
+    }
+
+    /// Enable or disable pair kerning (enabled by default)
+    pub fn set_kerning_enabled(&mut self, enabled: bool) {
+        self.text_renderer.set_kerning_enabled(enabled);
+    }
+
+    /// Set the fonts tried, in priority order, for characters missing from `font_name`
+    pub fn set_font_fallbacks(&mut self, font_name: &str, fallbacks: &[&str]) {
+        self.text_renderer.set_font_fallbacks(font_name, fallbacks);
+    }
+
+    /// Load a full-color emoji atlas: a grid image whose cells hold `chars`, row by row
+    pub fn load_emoji_atlas(
+        &mut self,
+        path: &str,
+        columns: u32,
+        rows: u32,
+        chars: &str,
+    ) -> Result<(), String> {
+        self.text_renderer
+            .load_emoji_atlas(path, columns, rows, chars)
+    }
+
+    /// Set the per-frame time budget for uploading glyphs from background font loads
+    pub fn set_glyph_upload_budget(&mut self, budget: std::time::Duration) {
+        self.text_renderer.set_glyph_upload_budget(budget);
+    }
+
+    /// Upload pending glyphs within the upload budget; returns the number uploaded
+    pub fn process_pending_glyphs(&mut self) -> usize {
+        self.text_renderer.process_pending_glyphs()
+    }
+
     /// Load a font with a specific size (creates a unique font name with size suffix)
     pub fn load_font_sized(
         &mut self,
@@ -464,6 +633,232 @@ impl SimpleTextRenderer {
         self.text_renderer.render_text(&text_obj)
     }
 
+    /// Draw cached text, re-laying it out only when it has changed
+    pub fn draw_prepared(&self, prepared: &mut PreparedText) -> Result<(), String> {
+        self.text_renderer.render_prepared(prepared)
+    }
+
+    /// Caret slot positions of single-line text, in the same top-left units as `draw_text`'s x
+    pub fn caret_offsets(&self, text: &str, font_name: &str) -> Result<Vec<f32>, String> {
+        let (x_range, _) = self.text_renderer.viewport.get_logical_ranges();
+        let offsets = self.text_renderer.caret_offsets(text, font_name)?;
+        Ok(offsets.into_iter().map(|x| x / x_range).collect())
+    }
+
+    /// Size of text drawn with `draw_text`, in normalized units (fractions of the viewport)
+    pub fn measure_text(&self, text: &str, font_name: &str) -> Result<Vec2, String> {
+        let text_obj = TextUtils::simple_text(text, Vec2::ZERO, font_name);
+        let size = self
+            .text_renderer
+            .measure(text, font_name, &text_obj.config)?;
+        Ok(size / self.text_renderer.viewport.get_size())
+    }
+
+    /// Draw all live popups in one batch, shifted by `view_offset` in logical units
+    pub fn draw_popups(
+        &self,
+        popups: &PopupTexts,
+        font_name: &str,
+        view_offset: Vec2,
+    ) -> Result<(), String> {
+        self.text_renderer
+            .render_popups(popups, font_name, view_offset)
+    }
+
+    /// Set the insets that text positioned with `in_safe_area` stays within
+    pub fn set_safe_area(&mut self, safe_area: SafeArea) {
+        self.safe_area = safe_area;
+    }
+
+    pub fn safe_area(&self) -> SafeArea {
+        self.safe_area
+    }
+
+    /// Debug overlay: outline the safe area and shade the margins outside it
+    pub fn draw_safe_area_overlay(
+        &self,
+        renderer: &Renderer,
+        color: (f32, f32, f32),
+    ) -> Result<(), String> {
+        // Rects in top-left normalized units, converted to NDC center and size
+        let draw = |min: Vec2, size: Vec2, color: (f32, f32, f32)| {
+            let center = min + size / 2.0;
+            renderer.draw_rect(
+                Vec2::new(center.x * 2.0 - 1.0, 1.0 - center.y * 2.0),
+                size * 2.0,
+                color,
+            )
+        };
+        let area = self.safe_area;
+        let (min, size) = (area.min(), area.size());
+        let max = min + size;
+        let margin_color = (color.0 * 0.25, color.1 * 0.25, color.2 * 0.25);
+        let margins = [
+            (Vec2::ZERO, Vec2::new(1.0, area.top)),
+            (Vec2::new(0.0, max.y), Vec2::new(1.0, area.bottom)),
+            (Vec2::new(0.0, min.y), Vec2::new(area.left, size.y)),
+            (Vec2::new(max.x, min.y), Vec2::new(area.right, size.y)),
+        ];
+        for (corner, extent) in margins {
+            draw(corner, extent, margin_color)?;
+        }
+
+        let line = 0.002;
+        let edges = [
+            (min, Vec2::new(size.x, line)),
+            (Vec2::new(min.x, max.y - line), Vec2::new(size.x, line)),
+            (min, Vec2::new(line, size.y)),
+            (Vec2::new(max.x - line, min.y), Vec2::new(line, size.y)),
+        ];
+        for (corner, extent) in edges {
+            draw(corner, extent, color)?;
+        }
+        Ok(())
+    }
+
+    /// Pseudo-localize all text to catch layouts too tight for longer languages
+    pub fn set_pseudo_locale(&mut self, pseudo_locale: Option<PseudoLocale>) {
+        self.text_renderer.set_pseudo_locale(pseudo_locale);
+    }
+
+    /// Record text boxes whose content doesn't fit, for `draw_overflow_overlay`
+    pub fn set_overflow_tracking(&mut self, enabled: bool) {
+        self.text_renderer.set_overflow_tracking(enabled);
+    }
+
+    /// Debug overlay: outline every text box that overflowed or was ellipsized since the
+    /// last call, drawing ellipsized boxes in a dimmer shade
+    ///
+    /// Call after drawing text each frame, with overflow tracking enabled.
+    pub fn draw_overflow_overlay(
+        &self,
+        renderer: &Renderer,
+        color: (f32, f32, f32),
+    ) -> Result<(), String> {
+        let draw = |min: Vec2, size: Vec2, color: (f32, f32, f32)| {
+            let center = min + size / 2.0;
+            renderer.draw_rect(
+                Vec2::new(center.x * 2.0 - 1.0, 1.0 - center.y * 2.0),
+                size * 2.0,
+                color,
+            )
+        };
+        let line = 0.003;
+        for overflow in self.text_renderer.take_overflows() {
+            let color = if overflow.ellipsized {
+                (color.0 * 0.6, color.1 * 0.6, color.2 * 0.6)
+            } else {
+                color
+            };
+            let (min, size) = (overflow.min, overflow.size);
+            let max = min + size;
+            let edges = [
+                (min, Vec2::new(size.x, line)),
+                (Vec2::new(min.x, max.y - line), Vec2::new(size.x, line)),
+                (min, Vec2::new(line, size.y)),
+                (Vec2::new(max.x - line, min.y), Vec2::new(line, size.y)),
+            ];
+            for (corner, extent) in edges {
+                draw(corner, extent, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw the panels, widgets and labels submitted to a quick UI this frame
+    pub fn draw_quick_ui(
+        &self,
+        renderer: &Renderer,
+        ui: &QuickUi,
+        font_name: &str,
+    ) -> Result<(), String> {
+        let draw = |min: Vec2, size: Vec2, color: (f32, f32, f32)| {
+            let center = min + size / 2.0;
+            renderer.draw_rect(
+                Vec2::new(center.x * 2.0 - 1.0, 1.0 - center.y * 2.0),
+                size * 2.0,
+                color,
+            )
+        };
+        let theme = &ui.theme;
+        for command in ui.draws() {
+            match command {
+                QuickDraw::Rect {
+                    min,
+                    size,
+                    color,
+                    border,
+                } => {
+                    if *border {
+                        let outline = Vec2::splat(theme.border_width);
+                        draw(*min - outline, *size + outline * 2.0, theme.border_color)?;
+                    }
+                    draw(*min, *size, *color)?;
+                }
+                QuickDraw::Text {
+                    text,
+                    position,
+                    color: (r, g, b),
+                } => self.draw_text_colored(text, position.x, position.y, font_name, *r, *g, *b)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw a text field at a position, with its selection highlight and blinking caret
+    ///
+    /// The text is placed as by `draw_text`; the highlight and caret are drawn with `renderer`.
+    pub fn draw_text_field(
+        &self,
+        renderer: &Renderer,
+        field: &TextField,
+        x: f32,
+        y: f32,
+        font_name: &str,
+    ) -> Result<(), String> {
+        let font = self
+            .text_renderer
+            .get_font(font_name)
+            .ok_or_else(|| format!("Font '{}' not found", font_name))?;
+        let (x_range, y_range) = self.text_renderer.viewport.get_logical_ranges();
+        let scale_factor = self
+            .text_renderer
+            .viewport
+            .calculate_scale_factor(font.size as f32);
+
+        // Highlight and caret span the font's ascender to descender around the baseline
+        let top = y - font.ascender * scale_factor / y_range;
+        let height = (font.ascender + font.descender) * scale_factor / y_range;
+        let draw_span = |left: f32, width: f32, color: (f32, f32, f32)| {
+            let center = Vec2::new(x + left + width / 2.0, top + height / 2.0);
+            renderer.draw_rect(
+                Vec2::new(center.x * 2.0 - 1.0, 1.0 - center.y * 2.0),
+                Vec2::new(width * 2.0, height * 2.0),
+                color,
+            )
+        };
+
+        let style = &field.style;
+        let offsets = self.caret_offsets(field.text(), font_name)?;
+        if let Some((start, end)) = field.selection_span(&offsets) {
+            draw_span(start, end - start, style.selection_color)?;
+        }
+
+        let (text, (r, g, b)) = if field.text().is_empty() {
+            (field.placeholder(), style.placeholder_color)
+        } else {
+            (field.text(), style.text_color)
+        };
+        self.draw_text_colored(text, x, y, font_name, r, g, b)?;
+
+        if field.caret_visible() {
+            let caret_width = style.caret_width * scale_factor / x_range;
+            let caret_x = field.caret_x(&offsets) - caret_width / 2.0;
+            draw_span(caret_x, caret_width, style.caret_color)?;
+        }
+        Ok(())
+    }
+
     /// Draw text with custom color
     pub fn draw_text_colored(
         &self,
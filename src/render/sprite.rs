@@ -1,8 +1,98 @@
+use super::blend::BlendMode;
 use super::gl_wrapper::GlWrapper;
+use super::layer::{ColorTint, DrawSpace, RenderLayer, TintFade};
+use super::lighting::{LightUniforms, LightingRenderer};
+use super::shader::{self, Material, ShaderId, ShaderManager};
+use super::sprite_store::SpriteStore;
 use super::texture::{TextureId, TextureManager};
+use crate::physics::collision::CollisionPolygon;
+use crate::utils::math::geometry::Rectangle;
 use glam::Vec2;
+use std::collections::HashMap;
 use std::rc::Rc;
 
+/// Nine-patch configuration: borders keep their size while the edges and center stretch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NineSlice {
+    /// Border insets in texture pixels
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+    /// Sprite units per texture pixel used to size the border quads
+    pub border_scale: Vec2,
+}
+
+impl NineSlice {
+    /// Create a nine-slice with the given texture pixel insets
+    pub fn new(left: f32, right: f32, top: f32, bottom: f32, border_scale: Vec2) -> Self {
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+            border_scale,
+        }
+    }
+
+    /// Create a nine-slice with the same inset on every side
+    pub fn uniform(inset: f32, border_scale: Vec2) -> Self {
+        Self::new(inset, inset, inset, inset, border_scale)
+    }
+
+    /// Compute the nine quads as (center, size, uv_rect) for a sprite and texture size
+    ///
+    /// Borders shrink proportionally when the sprite is smaller than their combined size.
+    pub fn quads(
+        &self,
+        position: Vec2,
+        size: Vec2,
+        texture_width: u32,
+        texture_height: u32,
+    ) -> [(Vec2, Vec2, [f32; 4]); 9] {
+        let tex_w = texture_width.max(1) as f32;
+        let tex_h = texture_height.max(1) as f32;
+
+        // Border sizes in sprite units, clamped to fit inside the sprite
+        let mut left = self.left * self.border_scale.x;
+        let mut right = self.right * self.border_scale.x;
+        let mut top = self.top * self.border_scale.y;
+        let mut bottom = self.bottom * self.border_scale.y;
+        let width = size.x.abs();
+        let height = size.y.abs();
+        if left + right > width && left + right > 0.0 {
+            let shrink = width / (left + right);
+            left *= shrink;
+            right *= shrink;
+        }
+        if top + bottom > height && top + bottom > 0.0 {
+            let shrink = height / (top + bottom);
+            top *= shrink;
+            bottom *= shrink;
+        }
+
+        // Column/row edges in sprite space (y up) and texture space (v down)
+        let min = position - Vec2::new(width, height) * 0.5;
+        let xs = [min.x, min.x + left, min.x + width - right, min.x + width];
+        let ys = [min.y + height, min.y + height - top, min.y + bottom, min.y];
+        let us = [0.0, self.left / tex_w, 1.0 - self.right / tex_w, 1.0];
+        let vs = [0.0, self.top / tex_h, 1.0 - self.bottom / tex_h, 1.0];
+
+        std::array::from_fn(|i| {
+            let (col, row) = (i % 3, i / 3);
+            let quad_min = Vec2::new(xs[col], ys[row + 1]);
+            let quad_max = Vec2::new(xs[col + 1], ys[row]);
+            let uv = [
+                us[col],
+                vs[row],
+                us[col + 1] - us[col],
+                vs[row + 1] - vs[row],
+            ];
+            ((quad_min + quad_max) * 0.5, quad_max - quad_min, uv)
+        })
+    }
+}
+
 /// A sprite that can be rendered with a texture
 #[derive(Debug, Clone)]
 pub struct Sprite {
@@ -11,6 +101,29 @@ pub struct Sprite {
     pub size: Vec2,
     pub tint_color: (f32, f32, f32),
     pub alpha: f32,
+    /// Optional custom material; the built-in sprite shader is used when None
+    pub material: Option<Material>,
+    /// Optional nine-slice borders; the texture is stretched uniformly when None
+    pub nine_slice: Option<NineSlice>,
+    /// Mirror the texture horizontally
+    pub flip_x: bool,
+    /// Mirror the texture vertically
+    pub flip_y: bool,
+    /// How the sprite is blended with the framebuffer
+    pub blend_mode: BlendMode,
+    /// Optional normal map shaded by the lights of the lighting pass
+    pub normal_map: Option<TextureId>,
+    /// Source rectangle in texture pixels (x, y, width, height); the whole texture when None
+    pub region: Option<(f32, f32, f32, f32)>,
+    /// Draw order: lower layers are drawn first
+    pub layer: i32,
+    /// Hidden sprites are skipped when rendering
+    pub visible: bool,
+    /// World sprites move with the camera; screen sprites stay put
+    pub space: DrawSpace,
+    /// Per-sprite value in 0..1 passed to shaders as `sprite_seed`, so vertex effects
+    /// like swaying grass can be out of phase; derived from the initial position
+    pub seed: f32,
 }
 
 impl Sprite {
@@ -22,6 +135,17 @@ impl Sprite {
             size,
             tint_color: (1.0, 1.0, 1.0), // White tint (no color change)
             alpha: 1.0,                  // Fully opaque
+            material: None,
+            nine_slice: None,
+            flip_x: false,
+            flip_y: false,
+            blend_mode: BlendMode::Alpha,
+            normal_map: None,
+            region: None,
+            layer: 0,
+            visible: true,
+            space: DrawSpace::World,
+            seed: position_seed(position),
         }
     }
 
@@ -38,6 +162,17 @@ impl Sprite {
             size,
             tint_color,
             alpha: 1.0,
+            material: None,
+            nine_slice: None,
+            flip_x: false,
+            flip_y: false,
+            blend_mode: BlendMode::Alpha,
+            normal_map: None,
+            region: None,
+            layer: 0,
+            visible: true,
+            space: DrawSpace::World,
+            seed: position_seed(position),
         }
     }
 
@@ -55,6 +190,17 @@ impl Sprite {
             size,
             tint_color,
             alpha,
+            material: None,
+            nine_slice: None,
+            flip_x: false,
+            flip_y: false,
+            blend_mode: BlendMode::Alpha,
+            normal_map: None,
+            region: None,
+            layer: 0,
+            visible: true,
+            space: DrawSpace::World,
+            seed: position_seed(position),
         }
     }
 
@@ -77,15 +223,261 @@ impl Sprite {
     pub fn set_alpha(&mut self, alpha: f32) {
         self.alpha = alpha.clamp(0.0, 1.0);
     }
+
+    /// Set tint color and alpha together as an RGBA color
+    pub fn set_tint_rgba(&mut self, tint: (f32, f32, f32, f32)) {
+        self.tint_color = (tint.0, tint.1, tint.2);
+        self.set_alpha(tint.3);
+    }
+
+    /// Get tint color and alpha as an RGBA color
+    pub fn tint_rgba(&self) -> (f32, f32, f32, f32) {
+        (
+            self.tint_color.0,
+            self.tint_color.1,
+            self.tint_color.2,
+            self.alpha,
+        )
+    }
+
+    /// Set the blend mode used to draw the sprite
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    /// Set the source rectangle in texture pixels (None uses the whole texture)
+    pub fn set_region(&mut self, region: Option<(f32, f32, f32, f32)>) {
+        self.region = region;
+    }
+
+    /// Set the normal map (None disables directional shading)
+    pub fn set_normal_map(&mut self, normal_map: Option<TextureId>) {
+        self.normal_map = normal_map;
+    }
+
+    /// Set the draw layer
+    pub fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
+
+    /// Show or hide the sprite
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// Builder: set the tint color
+    pub fn with_tint(mut self, tint_color: (f32, f32, f32)) -> Self {
+        self.tint_color = tint_color;
+        self
+    }
+
+    /// Builder: set the alpha
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.set_alpha(alpha);
+        self
+    }
+
+    /// Builder: set the source rectangle in texture pixels
+    pub fn with_region(mut self, x: f32, y: f32, width: f32, height: f32) -> Self {
+        self.region = Some((x, y, width, height));
+        self
+    }
+
+    /// Builder: set horizontal and vertical flip flags
+    pub fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.set_flip(flip_x, flip_y);
+        self
+    }
+
+    /// Builder: attach a normal map (sampled with the same UVs as the sprite texture)
+    pub fn with_normal_map(mut self, normal_map: TextureId) -> Self {
+        self.normal_map = Some(normal_map);
+        self
+    }
+
+    /// Builder: set the draw layer
+    pub fn with_layer(mut self, layer: i32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Builder: set visibility
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Builder: set the blend mode
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    /// Builder: set the shader seed
+    pub fn with_seed(mut self, seed: f32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Builder: draw in world or screen space
+    pub fn with_space(mut self, space: DrawSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Builder: set a custom material
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = Some(material);
+        self
+    }
+
+    /// Builder: set nine-slice borders
+    pub fn with_nine_slice(mut self, nine_slice: NineSlice) -> Self {
+        self.nine_slice = Some(nine_slice);
+        self
+    }
+
+    /// Set horizontal and vertical flip flags
+    pub fn set_flip(&mut self, flip_x: bool, flip_y: bool) {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+    }
+
+    /// Set whether the sprite is drawn in world or screen space
+    pub fn set_space(&mut self, space: DrawSpace) {
+        self.space = space;
+    }
+
+    /// Set the seed passed to shaders as `sprite_seed`
+    pub fn set_seed(&mut self, seed: f32) {
+        self.seed = seed;
+    }
+
+    /// Set the material used to draw the sprite (None restores the built-in shader)
+    pub fn set_material(&mut self, material: Option<Material>) {
+        self.material = material;
+    }
+
+    /// Set the nine-slice borders (None stretches the whole texture)
+    pub fn set_nine_slice(&mut self, nine_slice: Option<NineSlice>) {
+        self.nine_slice = nine_slice;
+    }
+
+    /// Get a mutable reference to the sprite's material for per-draw uniform changes
+    pub fn material_mut(&mut self) -> Option<&mut Material> {
+        self.material.as_mut()
+    }
+}
+
+/// Mirror a UV rect (offset, scale) along the requested axes
+fn flip_uv_rect(uv: [f32; 4], flip_x: bool, flip_y: bool) -> [f32; 4] {
+    let mut uv = uv;
+    if flip_x {
+        uv[0] += uv[2];
+        uv[2] = -uv[2];
+    }
+    if flip_y {
+        uv[1] += uv[3];
+        uv[3] = -uv[3];
+    }
+    uv
+}
+
+/// Per-instance data for drawing many copies of the same texture in one draw call
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteInstance {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub tint_color: (f32, f32, f32),
+    pub alpha: f32,
+    /// Passed to the vertex shader as `instance_seed`, like `Sprite::seed`
+    pub seed: f32,
+}
+
+impl SpriteInstance {
+    /// Floats uploaded per instance: position (2) + size (2) + tint (3) + alpha (1) + seed (1)
+    const FLOATS: usize = 9;
+
+    /// Create a new instance with no tint
+    pub fn new(position: Vec2, size: Vec2) -> Self {
+        Self {
+            position,
+            size,
+            tint_color: (1.0, 1.0, 1.0),
+            alpha: 1.0,
+            seed: position_seed(position),
+        }
+    }
+
+    /// Create a new instance with tint color and alpha
+    pub fn new_with_tint_alpha(
+        position: Vec2,
+        size: Vec2,
+        tint_color: (f32, f32, f32),
+        alpha: f32,
+    ) -> Self {
+        Self {
+            position,
+            size,
+            tint_color,
+            alpha: alpha.clamp(0.0, 1.0),
+            seed: position_seed(position),
+        }
+    }
+
+    /// Builder: set the shader seed
+    pub fn with_seed(mut self, seed: f32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn write_to(&self, out: &mut Vec<f32>) {
+        out.extend_from_slice(&[
+            self.position.x,
+            self.position.y,
+            self.size.x,
+            self.size.y,
+            self.tint_color.0,
+            self.tint_color.1,
+            self.tint_color.2,
+            self.alpha,
+            self.seed,
+        ]);
+    }
+}
+
+impl From<&Sprite> for SpriteInstance {
+    fn from(sprite: &Sprite) -> Self {
+        Self {
+            position: sprite.position,
+            size: sprite.size,
+            tint_color: sprite.tint_color,
+            alpha: sprite.alpha,
+            seed: sprite.seed,
+        }
+    }
 }
 
 /// Sprite renderer that handles rendering sprites with textures
 pub struct SpriteRenderer {
     gl: Rc<GlWrapper>,
     texture_manager: Option<TextureManager>,
+    shader_manager: Option<ShaderManager>,
     sprite_shader: Option<u32>,
     sprite_vao: Option<u32>,
     sprite_vbo: Option<u32>,
+    instanced_shader: Option<u32>,
+    instanced_vao: Option<u32>,
+    instance_vbo: Option<u32>,
+    instance_data: Vec<f32>,
+    sprites: SpriteStore,
+    lights: LightUniforms,
+    camera_position: Vec2,
+    /// Seconds passed to shaders as `time`
+    time: f32,
+    layers: HashMap<i32, RenderLayer>,
+    global_tint: TintFade,
+    layer_tints: HashMap<i32, TintFade>,
     initialized: bool,
 }
 
@@ -95,9 +487,21 @@ impl SpriteRenderer {
         Self {
             gl,
             texture_manager: None,
+            shader_manager: None,
             sprite_shader: None,
             sprite_vao: None,
             sprite_vbo: None,
+            instanced_shader: None,
+            instanced_vao: None,
+            instance_vbo: None,
+            instance_data: Vec::new(),
+            sprites: SpriteStore::new(),
+            lights: LightUniforms::default(),
+            camera_position: Vec2::ZERO,
+            time: 0.0,
+            layers: HashMap::new(),
+            global_tint: TintFade::default(),
+            layer_tints: HashMap::new(),
             initialized: false,
         }
     }
@@ -111,6 +515,9 @@ impl SpriteRenderer {
         // Create texture manager
         self.texture_manager = Some(TextureManager::new(Rc::clone(&self.gl)));
 
+        // Create shader manager for custom materials
+        self.shader_manager = Some(ShaderManager::new(Rc::clone(&self.gl)));
+
         // Create sprite shader
         let sprite_shader = Self::create_sprite_shader(&self.gl)?;
         println!("Created sprite shader: {}", sprite_shader);
@@ -122,9 +529,20 @@ impl SpriteRenderer {
             sprite_vao, sprite_vbo
         );
 
+        // Create instanced rendering resources, sharing the quad VBO
+        let instanced_shader = shader::compile_program(
+            &self.gl,
+            include_str!("shaders/sprite_instanced.vert"),
+            include_str!("shaders/sprite_instanced.frag"),
+        )?;
+        let (instanced_vao, instance_vbo) = Self::create_instanced_geometry(&self.gl, sprite_vbo)?;
+
         self.sprite_shader = Some(sprite_shader);
         self.sprite_vao = Some(sprite_vao);
         self.sprite_vbo = Some(sprite_vbo);
+        self.instanced_shader = Some(instanced_shader);
+        self.instanced_vao = Some(instanced_vao);
+        self.instance_vbo = Some(instance_vbo);
         self.initialized = true;
 
         println!("Sprite renderer initialized successfully!");
@@ -138,36 +556,261 @@ impl SpriteRenderer {
             .expect("Sprite renderer not initialized")
     }
 
+    /// Get a reference to the shader manager
+    pub fn shader_manager(&mut self) -> &mut ShaderManager {
+        self.shader_manager
+            .as_mut()
+            .expect("Sprite renderer not initialized")
+    }
+
+    /// Register a custom sprite shader that can be referenced from a `Material`
+    ///
+    /// Besides the built-in sprite uniforms (`sprite_position`, `sprite_size`, `uv_rect`,
+    /// `view_offset`, ...), every draw sets `time` and `sprite_seed`, so vertex shaders
+    /// can sway or stretch sprites around their world position. Shaders for
+    /// `render_instanced_with_material` read the instance attributes instead.
+    pub fn register_shader(
+        &mut self,
+        name: &str,
+        vertex_source: &str,
+        fragment_source: &str,
+    ) -> Result<ShaderId, String> {
+        if !self.initialized {
+            return Err("Sprite renderer not initialized".to_string());
+        }
+        self.shader_manager()
+            .create_program(name, vertex_source, fragment_source)
+    }
+
     /// Render a sprite
     pub fn render_sprite(&self, sprite: &Sprite) -> Result<(), String> {
         if !self.initialized {
             return Err("Sprite renderer not initialized".to_string());
         }
+        if !sprite.visible {
+            return Ok(());
+        }
+        sprite.blend_mode.apply(&self.gl)?;
+        self.draw_sprite(sprite)
+    }
+
+    /// Render a batch of sprites sorted by layer, then grouped by blend mode
+    ///
+    /// Within a layer, sprites are drawn in order within each blend mode, with modes
+    /// drawn in `BlendMode` order, so the blend state changes at most once per group.
+    pub fn render_sprites(&self, sprites: &[Sprite]) -> Result<(), String> {
+        if !self.initialized {
+            return Err("Sprite renderer not initialized".to_string());
+        }
+        self.draw_batch(sprites.iter().collect())
+    }
+
+    /// Render every visible sprite in the retained sprite store
+    pub fn render_retained(&self) -> Result<(), String> {
+        if !self.initialized {
+            return Err("Sprite renderer not initialized".to_string());
+        }
+        self.draw_batch(self.sprites.iter().map(|(_, sprite)| sprite).collect())
+    }
+
+    /// Capture this frame's lights for normal-mapped sprites (no lights when lighting is disabled)
+    pub fn set_lighting(&mut self, lighting: &LightingRenderer) {
+        self.lights = if lighting.is_enabled() {
+            lighting.light_uniforms()
+        } else {
+            LightUniforms::default()
+        };
+    }
+
+    /// Set the camera position that layers scroll against
+    pub fn set_camera_position(&mut self, position: Vec2) {
+        self.camera_position = position;
+    }
+
+    /// Get the camera position
+    pub fn camera_position(&self) -> Vec2 {
+        self.camera_position
+    }
+
+    /// Clip drawing to a rect in 0..1 across the view with y down (min is the top-left),
+    /// intersected with any enclosing clip rect; undo with `pop_clip_rect`
+    pub fn push_clip_rect(&self, min: Vec2, size: Vec2) -> Result<(), String> {
+        let rect = self.gl.viewport_rect().sub_rect(min, size);
+        self.gl.push_clip_rect(rect)
+    }
+
+    /// Restore the clip rect from before the last `push_clip_rect`
+    pub fn pop_clip_rect(&self) -> Result<(), String> {
+        self.gl.pop_clip_rect()
+    }
+
+    /// Set the time in seconds passed to sprite shaders as the `time` uniform
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time;
+    }
+
+    /// Advance the shader time, e.g. by the frame's delta time
+    pub fn advance_time(&mut self, delta_time: f32) {
+        self.time += delta_time;
+    }
+
+    /// Get the shader time
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Configure scrolling for a layer (layers default to `RenderLayer::world()`)
+    pub fn set_render_layer(&mut self, layer: i32, settings: RenderLayer) {
+        self.layers.insert(layer, settings);
+    }
+
+    /// Get the scroll settings of a layer
+    pub fn render_layer(&self, layer: i32) -> RenderLayer {
+        self.layers.get(&layer).copied().unwrap_or_default()
+    }
+
+    /// Tint everything drawn by this renderer, replacing any fade in progress
+    pub fn set_global_tint(&mut self, tint: ColorTint) {
+        self.global_tint = TintFade::constant(tint);
+    }
+
+    /// Fade the global tint from its current value to `tint` over `duration` seconds
+    ///
+    /// For a damage flash, set the flash tint and then fade back to `ColorTint::IDENTITY`.
+    pub fn fade_global_tint(&mut self, tint: ColorTint, duration: f32) {
+        self.global_tint = self.fade_from(self.global_tint(), tint, duration);
+    }
+
+    /// Get the current global tint
+    pub fn global_tint(&self) -> ColorTint {
+        self.global_tint.value_at(self.time)
+    }
+
+    /// Tint one layer, applied before the global tint
+    pub fn set_layer_tint(&mut self, layer: i32, tint: ColorTint) {
+        self.layer_tints.insert(layer, TintFade::constant(tint));
+    }
+
+    /// Fade a layer's tint from its current value to `tint` over `duration` seconds
+    pub fn fade_layer_tint(&mut self, layer: i32, tint: ColorTint, duration: f32) {
+        let fade = self.fade_from(self.layer_tint(layer), tint, duration);
+        self.layer_tints.insert(layer, fade);
+    }
+
+    /// Get the current tint of a layer
+    pub fn layer_tint(&self, layer: i32) -> ColorTint {
+        self.layer_tints
+            .get(&layer)
+            .map_or(ColorTint::IDENTITY, |fade| fade.value_at(self.time))
+    }
+
+    fn fade_from(&self, from: ColorTint, to: ColorTint, duration: f32) -> TintFade {
+        TintFade {
+            from,
+            to,
+            start: self.time,
+            duration,
+        }
+    }
+
+    /// Upload the combined layer and global tint as `layer_multiply` and `layer_add`
+    fn set_tint_uniforms(&self, shader: u32, layer: i32) -> Result<(), String> {
+        let tint = self.layer_tint(layer).then(self.global_tint());
+        let (m, a) = (tint.multiply, tint.add);
+        let multiply_loc = self.gl.get_uniform_location(shader, "layer_multiply")?;
+        self.gl.set_uniform_4f(multiply_loc, m.0, m.1, m.2, m.3)?;
+        let add_loc = self.gl.get_uniform_location(shader, "layer_add")?;
+        self.gl.set_uniform_3f(add_loc, a.0, a.1, a.2)
+    }
+
+    /// Get the retained sprite store
+    pub fn sprites(&self) -> &SpriteStore {
+        &self.sprites
+    }
+
+    /// Get the retained sprite store for creating and mutating sprites
+    pub fn sprites_mut(&mut self) -> &mut SpriteStore {
+        &mut self.sprites
+    }
+
+    /// World-space collider for a sprite from its texture's generated collision shape
+    ///
+    /// Returns `None` if the texture was imported without a collision shape, or if the
+    /// sprite draws a sub-region, since the shape covers the whole texture.
+    pub fn sprite_collider(&self, sprite: &Sprite) -> Option<CollisionPolygon> {
+        if sprite.region.is_some() {
+            return None;
+        }
+        let shape = self
+            .texture_manager
+            .as_ref()?
+            .collision_shape(sprite.texture_id)?;
+        Some(shape.to_sprite_space(sprite.position, sprite.size, sprite.flip_x, sprite.flip_y))
+    }
+
+    /// Check if a world-space point hits a sprite, using its collision shape if it has one
+    pub fn hit_test(&self, sprite: &Sprite, point: Vec2) -> bool {
+        match self.sprite_collider(sprite) {
+            Some(collider) => collider.contains_point(point),
+            None => {
+                Rectangle::from_center(sprite.position, sprite.size.abs()).contains_point(point)
+            }
+        }
+    }
+
+    /// Sort visible sprites by layer and blend mode, then draw them
+    fn draw_batch(&self, mut order: Vec<&Sprite>) -> Result<(), String> {
+        order.retain(|sprite| sprite.visible);
+        order.sort_by_key(|sprite| (sprite.layer, sprite.blend_mode));
+
+        let mut current_blend = None;
+        for sprite in order {
+            if current_blend != Some(sprite.blend_mode) {
+                sprite.blend_mode.apply(&self.gl)?;
+                current_blend = Some(sprite.blend_mode);
+            }
+            self.draw_sprite(sprite)?;
+        }
+        Ok(())
+    }
 
-        let shader = self.sprite_shader.ok_or("Sprite shader not available")?;
+    /// Issue the draw calls for a sprite using the current blend state
+    fn draw_sprite(&self, sprite: &Sprite) -> Result<(), String> {
         let vao = self.sprite_vao.ok_or("Sprite VAO not available")?;
         let texture_manager = self
             .texture_manager
             .as_ref()
             .ok_or("Texture manager not available")?;
 
-        // Use sprite shader
-        self.gl.use_program(shader)?;
+        // Use the material's shader if present, otherwise the built-in sprite shader
+        let shader = match &sprite.material {
+            Some(material) => {
+                let shader_manager = self
+                    .shader_manager
+                    .as_ref()
+                    .ok_or("Shader manager not available")?;
+                // Texture units 0 and 1 are reserved for the sprite texture and normal map
+                shader_manager.apply_material(material, 2)?.program
+            }
+            None => {
+                let shader = self.sprite_shader.ok_or("Sprite shader not available")?;
+                self.gl.use_program(shader)?;
+                shader
+            }
+        };
 
         // Bind texture
         texture_manager.bind_texture(sprite.texture_id)?;
+        let texture_info = texture_manager.get_texture_info(sprite.texture_id);
 
         // Set uniforms
         let pos_loc = self.gl.get_uniform_location(shader, "sprite_position")?;
         let size_loc = self.gl.get_uniform_location(shader, "sprite_size")?;
+        let uv_rect_loc = self.gl.get_uniform_location(shader, "uv_rect")?;
         let tint_loc = self.gl.get_uniform_location(shader, "tint_color")?;
         let alpha_loc = self.gl.get_uniform_location(shader, "alpha")?;
         let texture_loc = self.gl.get_uniform_location(shader, "texture_sampler")?;
 
-        self.gl
-            .set_uniform_2f(pos_loc, sprite.position.x, sprite.position.y)?;
-        self.gl
-            .set_uniform_2f(size_loc, sprite.size.x, sprite.size.y)?;
         self.gl.set_uniform_3f(
             tint_loc,
             sprite.tint_color.0,
@@ -177,13 +820,217 @@ impl SpriteRenderer {
         self.gl.set_uniform_1f(alpha_loc, sprite.alpha)?;
         self.gl.set_uniform_1i(texture_loc, 0)?; // Texture unit 0
 
-        // Draw the sprite
+        let view_offset = self
+            .render_layer(sprite.layer)
+            .view_offset(sprite.space.camera_position(self.camera_position));
+        let view_offset_loc = self.gl.get_uniform_location(shader, "view_offset")?;
+        self.gl
+            .set_uniform_2f(view_offset_loc, view_offset.x, view_offset.y)?;
+        self.set_vertex_effect_uniforms(shader, sprite.seed)?;
+        self.set_tint_uniforms(shader, sprite.layer)?;
+
+        // A material's own alpha_cutoff uniform takes precedence over the texture's
+        let material_cutoff = sprite
+            .material
+            .as_ref()
+            .is_some_and(|material| material.get_uniform("alpha_cutoff").is_some());
+        if !material_cutoff {
+            let cutoff_loc = self.gl.get_uniform_location(shader, "alpha_cutoff")?;
+            let cutoff = texture_info.map_or(0.0, |info| info.alpha_cutoff);
+            self.gl.set_uniform_1f(cutoff_loc, cutoff)?;
+        }
+
+        // Bind the normal map to texture unit 1 and upload lights when it is used
+        let use_normal_map = sprite.normal_map.is_some() && self.lights.count() > 0;
+        let use_normal_loc = self.gl.get_uniform_location(shader, "use_normal_map")?;
+        self.gl
+            .set_uniform_1i(use_normal_loc, use_normal_map as i32)?;
+        if let Some(normal_map) = sprite.normal_map.filter(|_| use_normal_map) {
+            self.gl.active_texture(gl::TEXTURE1)?;
+            texture_manager.bind_texture(normal_map)?;
+            self.gl.active_texture(gl::TEXTURE0)?;
+
+            let normal_loc = self.gl.get_uniform_location(shader, "normal_sampler")?;
+            self.gl.set_uniform_1i(normal_loc, 1)?;
+            let flip_loc = self.gl.get_uniform_location(shader, "normal_flip")?;
+            self.gl.set_uniform_2f(
+                flip_loc,
+                if sprite.flip_x { -1.0 } else { 1.0 },
+                if sprite.flip_y { -1.0 } else { 1.0 },
+            )?;
+            self.lights.upload(&self.gl, shader)?;
+        }
+
+        self.gl.bind_vertex_array(vao)?;
+
+        // Map the sprite's source region (if any) to a base UV rect
+        let (base_uv, source_size) = match (sprite.region, texture_info) {
+            (Some((x, y, width, height)), Some(info)) => {
+                let tex_w = info.width.max(1) as f32;
+                let tex_h = info.height.max(1) as f32;
+                (
+                    [x / tex_w, y / tex_h, width / tex_w, height / tex_h],
+                    Some((width.max(0.0) as u32, height.max(0.0) as u32)),
+                )
+            }
+            (_, info) => (
+                [0.0, 0.0, 1.0, 1.0],
+                info.map(|info| (info.width, info.height)),
+            ),
+        };
+        let to_region = |uv: [f32; 4]| {
+            [
+                base_uv[0] + uv[0] * base_uv[2],
+                base_uv[1] + uv[1] * base_uv[3],
+                uv[2] * base_uv[2],
+                uv[3] * base_uv[3],
+            ]
+        };
+
+        let nine_slice = sprite
+            .nine_slice
+            .and_then(|nine_slice| source_size.map(|size| (nine_slice, size)));
+        match nine_slice {
+            Some((nine_slice, (source_width, source_height))) => {
+                // Draw the nine patches as separate quads with their own UV ranges
+                for (mut center, size, uv) in
+                    nine_slice.quads(sprite.position, sprite.size, source_width, source_height)
+                {
+                    // Mirror each patch around the sprite center as well as its UVs
+                    if sprite.flip_x {
+                        center.x = 2.0 * sprite.position.x - center.x;
+                    }
+                    if sprite.flip_y {
+                        center.y = 2.0 * sprite.position.y - center.y;
+                    }
+                    let uv = flip_uv_rect(to_region(uv), sprite.flip_x, sprite.flip_y);
+                    self.gl.set_uniform_2f(pos_loc, center.x, center.y)?;
+                    self.gl.set_uniform_2f(size_loc, size.x, size.y)?;
+                    self.gl
+                        .set_uniform_4f(uv_rect_loc, uv[0], uv[1], uv[2], uv[3])?;
+                    self.gl.draw_arrays(gl::TRIANGLE_STRIP, 0, 4)?;
+                }
+            }
+            None => {
+                self.gl
+                    .set_uniform_2f(pos_loc, sprite.position.x, sprite.position.y)?;
+                self.gl
+                    .set_uniform_2f(size_loc, sprite.size.x, sprite.size.y)?;
+                let uv = flip_uv_rect(base_uv, sprite.flip_x, sprite.flip_y);
+                self.gl
+                    .set_uniform_4f(uv_rect_loc, uv[0], uv[1], uv[2], uv[3])?;
+                self.gl.draw_arrays(gl::TRIANGLE_STRIP, 0, 4)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Render many copies of one texture with a single instanced draw call
+    ///
+    /// Instance transforms and colors are uploaded in one buffer per call, so this
+    /// suits large batches of identical sprites (bullets, grass). `layer` selects the
+    /// scroll settings for the whole batch.
+    pub fn render_instanced(
+        &mut self,
+        texture_id: TextureId,
+        instances: &[SpriteInstance],
+        blend_mode: BlendMode,
+        layer: i32,
+    ) -> Result<(), String> {
+        self.render_instanced_with_material(texture_id, instances, None, blend_mode, layer)
+    }
+
+    /// Render an instanced batch with a custom material, e.g. for swaying grass
+    ///
+    /// The material's vertex shader receives the quad at locations 0 and 1 and the
+    /// instance data at 2 (`vec4` position and size), 3 (`vec4` tint and alpha) and 4
+    /// (`float` seed), plus the `time` uniform; see `shaders/sprite_instanced.vert`.
+    pub fn render_instanced_with_material(
+        &mut self,
+        texture_id: TextureId,
+        instances: &[SpriteInstance],
+        material: Option<&Material>,
+        blend_mode: BlendMode,
+        layer: i32,
+    ) -> Result<(), String> {
+        if !self.initialized {
+            return Err("Sprite renderer not initialized".to_string());
+        }
+        if instances.is_empty() {
+            return Ok(());
+        }
+
+        let vao = self.instanced_vao.ok_or("Instanced VAO not available")?;
+        let instance_vbo = self.instance_vbo.ok_or("Instance VBO not available")?;
+        let texture_manager = self
+            .texture_manager
+            .as_ref()
+            .ok_or("Texture manager not available")?;
+
+        // Pack instance data into the reusable scratch buffer
+        self.instance_data.clear();
+        self.instance_data
+            .reserve(instances.len() * SpriteInstance::FLOATS);
+        for instance in instances {
+            instance.write_to(&mut self.instance_data);
+        }
+
+        blend_mode.apply(&self.gl)?;
+        let shader = match material {
+            Some(material) => {
+                let shader_manager = self
+                    .shader_manager
+                    .as_ref()
+                    .ok_or("Shader manager not available")?;
+                // Texture unit 0 is reserved for the sprite texture
+                shader_manager.apply_material(material, 1)?.program
+            }
+            None => {
+                let shader = self
+                    .instanced_shader
+                    .ok_or("Instanced sprite shader not available")?;
+                self.gl.use_program(shader)?;
+                shader
+            }
+        };
+        texture_manager.bind_texture(texture_id)?;
+        let texture_loc = self.gl.get_uniform_location(shader, "texture_sampler")?;
+        self.gl.set_uniform_1i(texture_loc, 0)?; // Texture unit 0
+        let cutoff_loc = self.gl.get_uniform_location(shader, "alpha_cutoff")?;
+        let cutoff = texture_manager
+            .get_texture_info(texture_id)
+            .map_or(0.0, |info| info.alpha_cutoff);
+        self.gl.set_uniform_1f(cutoff_loc, cutoff)?;
+        let view_offset = self.render_layer(layer).view_offset(self.camera_position);
+        let view_offset_loc = self.gl.get_uniform_location(shader, "view_offset")?;
+        self.gl
+            .set_uniform_2f(view_offset_loc, view_offset.x, view_offset.y)?;
+        let time_loc = self.gl.get_uniform_location(shader, "time")?;
+        self.gl.set_uniform_1f(time_loc, self.time)?;
+        self.set_tint_uniforms(shader, layer)?;
+
+        self.gl.bind_buffer(gl::ARRAY_BUFFER, instance_vbo)?;
+        self.gl
+            .set_buffer_data(gl::ARRAY_BUFFER, &self.instance_data, gl::STREAM_DRAW)?;
+        self.gl.bind_buffer(gl::ARRAY_BUFFER, 0)?;
+
         self.gl.bind_vertex_array(vao)?;
-        self.gl.draw_arrays(gl::TRIANGLE_STRIP, 0, 4)?;
+        self.gl
+            .draw_arrays_instanced(gl::TRIANGLE_STRIP, 0, 4, instances.len() as i32)?;
+        self.gl.bind_vertex_array(0)?;
 
         Ok(())
     }
 
+    /// Set the uniforms vertex effects in custom materials build on
+    fn set_vertex_effect_uniforms(&self, shader: u32, seed: f32) -> Result<(), String> {
+        let time_loc = self.gl.get_uniform_location(shader, "time")?;
+        self.gl.set_uniform_1f(time_loc, self.time)?;
+        let seed_loc = self.gl.get_uniform_location(shader, "sprite_seed")?;
+        self.gl.set_uniform_1f(seed_loc, seed)
+    }
+
     /// Create sprite shader program
     fn create_sprite_shader(gl: &GlWrapper) -> Result<u32, String> {
         let vertex_shader_source = include_str!("shaders/sprite.vert");
@@ -284,8 +1131,54 @@ impl SpriteRenderer {
         Ok((vao, vbo))
     }
 
+    /// Create the VAO for instanced drawing: the shared quad plus a per-instance buffer
+    fn create_instanced_geometry(gl: &GlWrapper, quad_vbo: u32) -> Result<(u32, u32), String> {
+        let float_size = std::mem::size_of::<f32>();
+        let vertex_stride = 4 * float_size as i32;
+        let instance_stride = (SpriteInstance::FLOATS * float_size) as i32;
+
+        let vao = gl.gen_vertex_array()?;
+        let instance_vbo = gl.gen_buffer()?;
+
+        gl.bind_vertex_array(vao)?;
+
+        // Per-vertex quad attributes (locations 0 and 1)
+        gl.bind_buffer(gl::ARRAY_BUFFER, quad_vbo)?;
+        gl.set_vertex_attrib_pointer(0, 2, gl::FLOAT, false, vertex_stride, 0)?;
+        gl.enable_vertex_attrib_array(0)?;
+        gl.set_vertex_attrib_pointer(1, 2, gl::FLOAT, false, vertex_stride, 2 * float_size)?;
+        gl.enable_vertex_attrib_array(1)?;
+
+        // Per-instance attributes: position + size (location 2), tint + alpha (location 3),
+        // seed (location 4)
+        gl.bind_buffer(gl::ARRAY_BUFFER, instance_vbo)?;
+        gl.set_vertex_attrib_pointer(2, 4, gl::FLOAT, false, instance_stride, 0)?;
+        gl.enable_vertex_attrib_array(2)?;
+        gl.vertex_attrib_divisor(2, 1)?;
+        gl.set_vertex_attrib_pointer(3, 4, gl::FLOAT, false, instance_stride, 4 * float_size)?;
+        gl.enable_vertex_attrib_array(3)?;
+        gl.vertex_attrib_divisor(3, 1)?;
+        gl.set_vertex_attrib_pointer(4, 1, gl::FLOAT, false, instance_stride, 8 * float_size)?;
+        gl.enable_vertex_attrib_array(4)?;
+        gl.vertex_attrib_divisor(4, 1)?;
+
+        gl.bind_buffer(gl::ARRAY_BUFFER, 0)?;
+        gl.bind_vertex_array(0)?;
+
+        Ok((vao, instance_vbo))
+    }
+
     /// Cleanup resources
     pub fn cleanup(&mut self) {
+        if let Some(shader) = self.instanced_shader.take() {
+            let _ = self.gl.delete_program(shader);
+        }
+        if let Some(vao) = self.instanced_vao.take() {
+            let _ = self.gl.delete_vertex_array(vao);
+        }
+        if let Some(vbo) = self.instance_vbo.take() {
+            let _ = self.gl.delete_buffer(vbo);
+        }
         if let Some(shader) = self.sprite_shader.take() {
             let _ = self.gl.delete_program(shader);
         }
@@ -295,6 +1188,9 @@ impl SpriteRenderer {
         if let Some(vbo) = self.sprite_vbo.take() {
             let _ = self.gl.delete_buffer(vbo);
         }
+        if let Some(ref mut shader_manager) = self.shader_manager {
+            shader_manager.clear_all();
+        }
         if let Some(ref mut texture_manager) = self.texture_manager {
             let _ = texture_manager.clear_all();
         }
@@ -306,3 +1202,11 @@ impl Drop for SpriteRenderer {
         self.cleanup();
     }
 }
+
+/// A stable pseudo-random value in 0..1 for a position
+fn position_seed(position: Vec2) -> f32 {
+    let mut hash = position.x.to_bits() ^ position.y.to_bits().rotate_left(16);
+    hash = hash.wrapping_mul(0x9E37_79B9);
+    hash ^= hash >> 15;
+    (hash >> 8) as f32 / (1u32 << 24) as f32
+}
@@ -0,0 +1,144 @@
+use super::sprite::Sprite;
+
+/// Stable handle to a sprite in a `SpriteStore`
+///
+/// Handles stay valid until the sprite is removed; a removed handle never
+/// aliases a sprite created later in the same slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpriteHandle {
+    index: u32,
+    generation: u32,
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    generation: u32,
+    sprite: Option<Sprite>,
+}
+
+/// Retained sprite collection: create sprites once and mutate them through handles
+#[derive(Debug, Clone, Default)]
+pub struct SpriteStore {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl SpriteStore {
+    /// Create an empty sprite store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a sprite and return its handle
+    pub fn insert(&mut self, sprite: Sprite) -> SpriteHandle {
+        self.len += 1;
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.sprite = Some(sprite);
+            return SpriteHandle {
+                index,
+                generation: slot.generation,
+            };
+        }
+
+        let index = self.slots.len() as u32;
+        self.slots.push(Slot {
+            generation: 0,
+            sprite: Some(sprite),
+        });
+        SpriteHandle {
+            index,
+            generation: 0,
+        }
+    }
+
+    /// Remove a sprite, returning it if the handle was still valid
+    pub fn remove(&mut self, handle: SpriteHandle) -> Option<Sprite> {
+        let slot = self.slots.get_mut(handle.index as usize)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        let sprite = slot.sprite.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.index);
+        self.len -= 1;
+        Some(sprite)
+    }
+
+    /// Get a sprite by handle
+    pub fn get(&self, handle: SpriteHandle) -> Option<&Sprite> {
+        self.slots
+            .get(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.sprite.as_ref())
+    }
+
+    /// Get a mutable sprite by handle
+    pub fn get_mut(&mut self, handle: SpriteHandle) -> Option<&mut Sprite> {
+        self.slots
+            .get_mut(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.sprite.as_mut())
+    }
+
+    /// Check if a handle refers to a live sprite
+    pub fn contains(&self, handle: SpriteHandle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    /// Number of live sprites
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the store has no sprites
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all sprites, invalidating every handle
+    pub fn clear(&mut self) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.sprite.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(index as u32);
+            }
+        }
+        self.len = 0;
+    }
+
+    /// Iterate over live sprites in creation-slot order
+    pub fn iter(&self) -> impl Iterator<Item = (SpriteHandle, &Sprite)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.sprite.as_ref().map(|sprite| {
+                (
+                    SpriteHandle {
+                        index: index as u32,
+                        generation: slot.generation,
+                    },
+                    sprite,
+                )
+            })
+        })
+    }
+
+    /// Iterate mutably over live sprites
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (SpriteHandle, &mut Sprite)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let generation = slot.generation;
+                slot.sprite.as_mut().map(|sprite| {
+                    (
+                        SpriteHandle {
+                            index: index as u32,
+                            generation,
+                        },
+                        sprite,
+                    )
+                })
+            })
+    }
+}
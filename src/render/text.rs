@@ -1,21 +1,36 @@
+use super::atlas::{self, UvRect};
+use super::bidi::{self, TextDirection};
+use super::bmfont::BmFont;
+use super::font_loader::{self, FontLoadEvent, FontLoader, RasterizedGlyph};
 use super::gl_wrapper::GlWrapper;
-use super::texture::{TextureId, TextureManager};
+use super::glyph_atlas::GlyphAtlas;
+use super::layer::DrawSpace;
+use super::popup_text::PopupTexts;
+use super::pseudo_locale::PseudoLocale;
+use super::texture::{TextureFilter, TextureId, TextureImportOptions, TextureManager, TextureWrap};
 use super::viewport::Viewport;
 use glam::Vec2;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::path::Path;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "opengl")]
-use fontdue::{Font, FontSettings};
+use fontdue::Font;
+
+/// Side length of the placeholder glyph texture in pixels
+const PLACEHOLDER_TEXTURE_SIZE: u32 = 8;
 
 /// A single character/glyph with its rendering information
 #[derive(Debug, Clone)]
 pub struct Glyph {
-    pub texture_id: TextureId,
-    pub size: Vec2,    // Size of the glyph in pixels
-    pub bearing: Vec2, // Offset from baseline to top-left of glyph
-    pub advance: f32,  // Horizontal advance to next character
+    pub texture_id: TextureId, // Glyph atlas page containing the bitmap
+    pub uv_rect: UvRect,       // Normalized (u, v, width, height) within the page
+    pub size: Vec2,            // Size of the glyph in pixels
+    pub bearing: Vec2,         // Offset from baseline to top-left of glyph
+    pub advance: f32,          // Horizontal advance to next character
 }
 
 /// Font information and glyph cache
@@ -29,6 +44,12 @@ pub struct FontInfo {
     pub descender: f32,
     #[cfg(feature = "opengl")]
     pub fontdue_font: Option<Font>,
+    /// True while glyphs are still arriving from a background load
+    pub loading: bool,
+    /// Glyph drawn in place of characters that haven't been uploaded yet
+    pub placeholder: Option<Glyph>,
+    /// Kerning pairs baked into bitmap fonts, in pixels
+    pub kerning_pairs: HashMap<(char, char), f32>,
 }
 
 impl FontInfo {
@@ -42,7 +63,31 @@ impl FontInfo {
             descender: size as f32 * 0.2,   // Default descender
             #[cfg(feature = "opengl")]
             fontdue_font: None,
+            loading: false,
+            placeholder: None,
+            kerning_pairs: HashMap::new(),
+        }
+    }
+
+    /// Look up a glyph, falling back to the placeholder while the font is loading
+    pub fn glyph(&self, ch: char) -> Option<&Glyph> {
+        match self.glyphs.get(&ch) {
+            Some(glyph) => Some(glyph),
+            None if self.loading => self.placeholder.as_ref(),
+            None => None,
+        }
+    }
+
+    /// Kerning adjustment between two characters at the font's size, in pixels
+    #[cfg(feature = "opengl")]
+    pub fn kerning(&self, left: char, right: char) -> f32 {
+        if let Some(amount) = self.kerning_pairs.get(&(left, right)) {
+            return *amount;
         }
+        self.fontdue_font
+            .as_ref()
+            .and_then(|font| font.horizontal_kern(left, right, self.size as f32))
+            .unwrap_or(0.0)
     }
 }
 
@@ -194,6 +239,122 @@ impl TextBox {
     }
 }
 
+/// A text box whose content didn't fit, recorded while overflow tracking is on
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextOverflow {
+    /// Top-left of the box's content area, normalized with the origin at the top-left
+    pub min: Vec2,
+    /// Size of the content area, normalized
+    pub size: Vec2,
+    /// The text was cut short with "..." rather than spilling out of the box
+    pub ellipsized: bool,
+    pub content: String,
+}
+
+/// Outline drawn around every glyph, for text over busy backgrounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextOutline {
+    pub color: (f32, f32, f32),
+    /// Thickness in font pixels
+    pub width: f32,
+}
+
+/// Copy of the text drawn behind it at an offset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextShadow {
+    /// Offset in font pixels (x right, y up), e.g. (2.0, -2.0) for down-right
+    pub offset: Vec2,
+    pub color: (f32, f32, f32),
+    /// Multiplied by the text's own alpha
+    pub alpha: f32,
+}
+
+/// Per-character adjustment returned by a `CharEffect`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharStyle {
+    /// Offset in font pixels (x right, y up)
+    pub offset: Vec2,
+    /// Replaces the text color (shadows and outlines keep theirs)
+    pub color: Option<(f32, f32, f32)>,
+    /// Multiplied by the text's alpha
+    pub alpha: f32,
+}
+
+impl Default for CharStyle {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            color: None,
+            alpha: 1.0,
+        }
+    }
+}
+
+/// Styles a revealed character from its index and the seconds since it appeared
+pub type CharEffect = fn(index: usize, age: f32) -> CharStyle;
+
+/// Typewriter-style reveal: characters appear one after another at a fixed rate
+///
+/// The whole text is laid out up front, so wrapping doesn't shift as characters appear.
+/// Characters count in drawing order, line breaks excluded.
+#[derive(Debug, Clone, Copy)]
+pub struct TextReveal {
+    pub chars_per_second: f32,
+    /// Seconds since the reveal started
+    pub elapsed: f32,
+    pub effect: Option<CharEffect>,
+}
+
+impl TextReveal {
+    /// Start a reveal with nothing shown yet
+    pub fn new(chars_per_second: f32) -> Self {
+        Self {
+            chars_per_second,
+            elapsed: 0.0,
+            effect: None,
+        }
+    }
+
+    /// Style every character as it appears, e.g. to fade or drop it in
+    pub fn with_effect(mut self, effect: CharEffect) -> Self {
+        self.effect = Some(effect);
+        self
+    }
+
+    /// Number of characters shown so far
+    pub fn visible_chars(&self) -> usize {
+        (self.elapsed * self.chars_per_second).floor() as usize
+    }
+
+    /// Seconds since the character at `index` appeared
+    pub fn char_age(&self, index: usize) -> f32 {
+        self.elapsed - index as f32 / self.chars_per_second
+    }
+
+    fn char_style(&self, index: usize) -> Option<CharStyle> {
+        self.effect
+            .map(|effect| effect(index, self.char_age(index)))
+    }
+}
+
+/// One draw of a text's glyphs: a shadow, an outline copy or the text itself
+#[derive(Clone, Copy)]
+struct EffectPass {
+    /// Offset in logical units
+    offset: Vec2,
+    color: (f32, f32, f32),
+    alpha: f32,
+    /// Whether color glyphs keep their own colors rather than taking the pass color
+    glyph_colors: bool,
+}
+
+/// Full-color glyphs, such as emoji, drawn from a grid image
+#[derive(Debug)]
+struct ColorGlyphAtlas {
+    texture_id: TextureId,
+    cells: HashMap<char, UvRect>,
+}
+
 /// Text rendering configuration
 #[derive(Debug, Clone)]
 pub struct TextConfig {
@@ -205,9 +366,19 @@ pub struct TextConfig {
     pub max_width: Option<f32>,
     pub line_spacing: f32,
     pub wrap: TextWrap,
+    /// Reading direction; right-to-left paragraphs swap left and right alignment
+    pub direction: TextDirection,
     /// Optional bounding box for text. If None, text uses simple position.
     /// If Some, text is constrained within the box bounds.
     pub bounding_box: Option<TextBox>,
+    pub outline: Option<TextOutline>,
+    pub shadow: Option<TextShadow>,
+    /// Reveal the text over time instead of drawing it all at once
+    pub reveal: Option<TextReveal>,
+    /// Screen text (the default) ignores the camera; world text, like name tags, follows it
+    pub space: DrawSpace,
+    /// Clip glyphs to the bounding box's content area instead of letting them overflow
+    pub clip_to_box: bool,
 }
 
 impl Default for TextConfig {
@@ -221,7 +392,13 @@ impl Default for TextConfig {
             max_width: None,
             line_spacing: 1.2,
             wrap: TextWrap::None,
+            direction: TextDirection::Auto,
             bounding_box: None,
+            outline: None,
+            shadow: None,
+            reveal: None,
+            space: DrawSpace::Screen,
+            clip_to_box: false,
         }
     }
 }
@@ -283,10 +460,56 @@ impl Text {
         self.config.wrap = wrap;
     }
 
+    pub fn set_direction(&mut self, direction: TextDirection) {
+        self.config.direction = direction;
+    }
+
     pub fn set_max_width(&mut self, max_width: Option<f32>) {
         self.config.max_width = max_width;
     }
 
+    pub fn set_outline(&mut self, outline: Option<TextOutline>) {
+        self.config.outline = outline;
+    }
+
+    pub fn set_shadow(&mut self, shadow: Option<TextShadow>) {
+        self.config.shadow = shadow;
+    }
+
+    /// Start revealing the text from its first character, or show it all at once with `None`
+    pub fn set_reveal(&mut self, reveal: Option<TextReveal>) {
+        self.config.reveal = reveal;
+    }
+
+    /// Advance the reveal (call each frame)
+    pub fn update_reveal(&mut self, delta_time: f32) {
+        if let Some(reveal) = &mut self.config.reveal {
+            reveal.elapsed += delta_time;
+        }
+    }
+
+    /// Show every remaining character now, e.g. when the player skips ahead
+    pub fn skip_reveal(&mut self) {
+        if let Some(reveal) = &mut self.config.reveal {
+            reveal.elapsed = f32::INFINITY;
+        }
+    }
+
+    /// Check if every character is shown
+    pub fn is_fully_revealed(&self) -> bool {
+        self.config.reveal.is_none_or(|reveal| {
+            reveal.visible_chars() >= self.content.chars().filter(|ch| *ch != '\n').count()
+        })
+    }
+
+    pub fn set_clip_to_box(&mut self, clip_to_box: bool) {
+        self.config.clip_to_box = clip_to_box;
+    }
+
+    pub fn set_space(&mut self, space: DrawSpace) {
+        self.config.space = space;
+    }
+
     pub fn set_vertical_align(&mut self, vertical_align: VerticalAlign) {
         self.config.vertical_align = vertical_align;
     }
@@ -296,6 +519,100 @@ impl Text {
     }
 }
 
+/// Floats per prepared glyph vertex: NDC position and atlas texture coordinates
+const PREPARED_VERTEX_FLOATS: usize = 4;
+
+/// Vertices per prepared glyph quad (two triangles)
+const PREPARED_QUAD_VERTICES: usize = 6;
+
+/// Floats per popup glyph vertex: NDC position, atlas texture coordinates and RGBA color
+const POPUP_VERTEX_FLOATS: usize = 8;
+
+/// A glyph quad ready for a batch: the atlas page it samples and its vertices
+type PopupQuad = (
+    TextureId,
+    [f32; POPUP_VERTEX_FLOATS * PREPARED_QUAD_VERTICES],
+);
+
+/// Inputs a prepared layout was built from: glyph generation, scale factor, logical bounds
+type PreparedLayoutKey = (u64, f32, (f32, f32, f32, f32));
+
+/// Text laid out once into a vertex buffer and redrawn with a single draw call
+///
+/// The layout is rebuilt only when the text is changed through `text_mut`, or when the
+/// viewport or the renderer's glyphs change. Color and alpha are uniforms, so changing
+/// them with `set_color`/`set_alpha` keeps the cached vertices. Glyphs on different
+/// atlas pages need one draw call per page.
+pub struct PreparedText {
+    text: Text,
+    dirty: bool,
+    layout_key: Option<PreparedLayoutKey>,
+    // (atlas page, first vertex, vertex count) per draw call
+    batches: Vec<(TextureId, i32, i32)>,
+    // GL wrapper, VAO and VBO, created on first layout
+    buffers: Option<(Rc<GlWrapper>, u32, u32)>,
+}
+
+impl PreparedText {
+    /// Create prepared text; it is laid out on its first render
+    pub fn new(text: Text) -> Self {
+        Self {
+            text,
+            dirty: true,
+            layout_key: None,
+            batches: Vec::new(),
+            buffers: None,
+        }
+    }
+
+    /// Get the text being drawn
+    pub fn text(&self) -> &Text {
+        &self.text
+    }
+
+    /// Get mutable access to the text, invalidating the cached layout
+    pub fn text_mut(&mut self) -> &mut Text {
+        self.dirty = true;
+        &mut self.text
+    }
+
+    /// Replace the content, invalidating the cached layout only if it changed
+    pub fn set_content(&mut self, content: &str) {
+        if self.text.content != content {
+            self.text_mut().set_content(content.to_string());
+        }
+    }
+
+    /// Set the text color without re-laying out
+    pub fn set_color(&mut self, color: (f32, f32, f32)) {
+        self.text.set_color(color);
+    }
+
+    /// Set the text alpha without re-laying out
+    pub fn set_alpha(&mut self, alpha: f32) {
+        self.text.set_alpha(alpha);
+    }
+
+    /// Check if the layout will be rebuilt on the next render
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Number of draw calls the cached layout needs
+    pub fn draw_call_count(&self) -> usize {
+        self.batches.len()
+    }
+}
+
+impl Drop for PreparedText {
+    fn drop(&mut self) {
+        if let Some((gl, vao, vbo)) = self.buffers.take() {
+            let _ = gl.delete_vertex_array(vao);
+            let _ = gl.delete_buffer(vbo);
+        }
+    }
+}
+
 /// Text renderer that handles font loading and text rendering
 pub struct TextRenderer {
     gl: Rc<GlWrapper>,
@@ -303,7 +620,34 @@ pub struct TextRenderer {
     text_shader: Option<u32>,
     text_vao: Option<u32>,
     text_vbo: Option<u32>,
+    popup_shader: Option<u32>,
+    // VAO and VBO the popup batch is streamed into every frame
+    popup_buffers: Option<(u32, u32)>,
+    // Scratch storage for the popup batch, kept between frames to avoid reallocating
+    popup_quads: RefCell<Vec<PopupQuad>>,
+    popup_vertices: RefCell<Vec<f32>>,
     fonts: HashMap<String, FontInfo>,
+    // Fonts tried in priority order for characters missing from a font
+    font_fallbacks: HashMap<String, Vec<String>>,
+    font_loader: FontLoader,
+    glyph_upload_budget: Duration,
+    glyph_atlas: GlyphAtlas,
+    placeholder_glyph: Option<(TextureId, UvRect)>,
+    emoji_atlas: Option<ColorGlyphAtlas>,
+    // Texture bound on unit 0 by the current render_text call, to skip redundant binds
+    bound_texture: Cell<Option<TextureId>>,
+    // Characters seen while rendering that have no glyph yet, rasterized next frame
+    missing_glyphs: RefCell<HashSet<(String, char)>>,
+    kerning_enabled: bool,
+    // Applied to every text before layout, to test layouts against longer languages
+    pseudo_locale: Option<PseudoLocale>,
+    // Subtracted from the position of world-space text
+    camera_position: Vec2,
+    overflow_tracking: bool,
+    // Boxes that overflowed since the last take_overflows call
+    overflows: RefCell<Vec<TextOverflow>>,
+    // Bumped whenever glyph data or spacing changes, so prepared text knows to re-layout
+    glyph_generation: u64,
     initialized: bool,
     // Viewport configuration - defines the logical coordinate system
     pub viewport: Viewport,
@@ -318,7 +662,25 @@ impl TextRenderer {
             text_shader: None,
             text_vao: None,
             text_vbo: None,
+            popup_shader: None,
+            popup_buffers: None,
+            popup_quads: RefCell::new(Vec::new()),
+            popup_vertices: RefCell::new(Vec::new()),
             fonts: HashMap::new(),
+            font_fallbacks: HashMap::new(),
+            font_loader: FontLoader::new(),
+            glyph_upload_budget: Duration::from_millis(2),
+            glyph_atlas: GlyphAtlas::default(),
+            placeholder_glyph: None,
+            emoji_atlas: None,
+            bound_texture: Cell::new(None),
+            missing_glyphs: RefCell::new(HashSet::new()),
+            kerning_enabled: true,
+            pseudo_locale: None,
+            camera_position: Vec2::ZERO,
+            overflow_tracking: false,
+            overflows: RefCell::new(Vec::new()),
+            glyph_generation: 0,
             initialized: false,
             viewport: Viewport::new(),
         }
@@ -393,6 +755,33 @@ impl TextRenderer {
         self.text_shader = Some(text_shader);
         self.text_vao = Some(text_vao);
         self.text_vbo = Some(text_vbo);
+
+        self.popup_shader = Some(Self::create_program(
+            &self.gl,
+            include_str!("shaders/popup_text.vert"),
+            include_str!("shaders/popup_text.frag"),
+        )?);
+        self.popup_buffers = Some(Self::create_popup_buffers(&self.gl)?);
+
+        // Hollow box drawn for glyphs that are still loading
+        let placeholder_bitmap: Vec<u8> = (0..PLACEHOLDER_TEXTURE_SIZE * PLACEHOLDER_TEXTURE_SIZE)
+            .map(|i| {
+                let (x, y) = (i % PLACEHOLDER_TEXTURE_SIZE, i / PLACEHOLDER_TEXTURE_SIZE);
+                let edge = PLACEHOLDER_TEXTURE_SIZE - 1;
+                if x == 0 || y == 0 || x == edge || y == edge {
+                    160
+                } else {
+                    0
+                }
+            })
+            .collect();
+        self.placeholder_glyph = Some(self.glyph_atlas.insert(
+            self.texture_manager.as_mut().unwrap(),
+            &placeholder_bitmap,
+            PLACEHOLDER_TEXTURE_SIZE,
+            PLACEHOLDER_TEXTURE_SIZE,
+        )?);
+
         self.initialized = true;
 
         println!("Text renderer initialized successfully!");
@@ -419,28 +808,18 @@ impl TextRenderer {
             .map_err(|e| format!("Failed to read font file '{}': {}", font_path, e))?;
 
         // Parse font with fontdue using high-quality settings
-        let font_settings = FontSettings {
-            scale: 40.0, // Higher scale for better quality
-            collection_index: 0,
-        };
-        let fontdue_font = Font::from_bytes(font_data, font_settings)
-            .map_err(|e| format!("Failed to parse font '{}': {:?}", font_path, e))?;
+        let fontdue_font = font_loader::parse_font(font_data, font_path)?;
 
         let mut font_info = FontInfo::new(name.to_string(), size);
+        Self::apply_font_metrics(
+            &mut font_info,
+            font_loader::font_line_height(&fontdue_font, size),
+        );
         font_info.fontdue_font = Some(fontdue_font);
 
-        // Get font metrics
-        let metrics = font_info
-            .fontdue_font
-            .as_ref()
-            .unwrap()
-            .metrics('A', size as f32);
-        font_info.line_height = metrics.height as f32;
-        font_info.ascender = metrics.height as f32 * 0.8; // Approximate ascender
-        font_info.descender = metrics.height as f32 * 0.2; // Approximate descender
-
         // Pre-generate glyphs for common ASCII characters using fontdue
         self.generate_glyphs_with_fontdue(&mut font_info, size)?;
+        self.add_emoji_glyphs(&mut font_info);
 
         self.fonts.insert(name.to_string(), font_info);
         println!(
@@ -452,6 +831,70 @@ impl TextRenderer {
         Ok(())
     }
 
+    /// Load a pre-baked bitmap font (AngelCode BMFont .fnt with its page textures)
+    ///
+    /// Glyphs are drawn from the pages as-is with nearest filtering, so the font stays
+    /// pixel-perfect. Pages must store glyph coverage in their alpha channel.
+    pub fn load_bmfont(&mut self, name: &str, fnt_path: &str) -> Result<(), String> {
+        if !self.initialized {
+            return Err("Text renderer not initialized".to_string());
+        }
+
+        let bmfont = BmFont::load(Path::new(fnt_path))?;
+        let options = TextureImportOptions::default()
+            .with_filter(TextureFilter::Nearest)
+            .with_wrap(TextureWrap::ClampToEdge);
+        let texture_manager = self.texture_manager.as_mut().unwrap();
+        let pages = bmfont
+            .page_paths(Path::new(fnt_path))
+            .iter()
+            .map(|page| texture_manager.load_texture_with_options(&page.to_string_lossy(), options))
+            .collect::<Result<Vec<TextureId>, String>>()?;
+
+        let mut font_info = FontInfo::new(name.to_string(), bmfont.size);
+        font_info.line_height = bmfont.line_height as f32;
+        font_info.ascender = bmfont.base as f32;
+        font_info.descender = bmfont.line_height.saturating_sub(bmfont.base) as f32;
+
+        let (page_width, page_height) = (bmfont.scale_w as f32, bmfont.scale_h as f32);
+        for ch in &bmfont.chars {
+            let (width, height) = (ch.width as f32, ch.height as f32);
+            let glyph = Glyph {
+                texture_id: pages[ch.page],
+                uv_rect: (
+                    ch.x as f32 / page_width,
+                    ch.y as f32 / page_height,
+                    width / page_width,
+                    height / page_height,
+                ),
+                size: Vec2::new(width, height),
+                // BMFont offsets are measured down from the line top; bearings up from the baseline
+                bearing: Vec2::new(
+                    ch.xoffset as f32,
+                    bmfont.base as f32 - ch.yoffset as f32 - height,
+                ),
+                advance: ch.xadvance as f32,
+            };
+            font_info.glyphs.insert(ch.ch, glyph);
+        }
+        font_info.kerning_pairs = bmfont
+            .kernings
+            .iter()
+            .map(|(pair, amount)| (*pair, *amount as f32))
+            .collect();
+
+        self.add_emoji_glyphs(&mut font_info);
+        self.glyph_generation += 1;
+        self.fonts.insert(name.to_string(), font_info);
+        println!(
+            "Bitmap font '{}' loaded successfully with {} glyphs",
+            name,
+            self.fonts[name].glyphs.len()
+        );
+
+        Ok(())
+    }
+
     /// Generate glyphs using fontdue
     fn generate_glyphs_with_fontdue(
         &mut self,
@@ -459,69 +902,435 @@ impl TextRenderer {
         size: u32,
     ) -> Result<(), String> {
         // Generate glyphs for common ASCII characters
-        for ch in 32..=126 {
-            // ASCII printable characters
-            let char_str = ch as u8 as char;
-
-            // Rasterize the character using fontdue with higher resolution
-            let render_scale = (size as f32 * 2.0).max(32.0); // Render at 2x resolution for better quality
-            let (metrics, bitmap) = font_info
-                .fontdue_font
-                .as_ref()
-                .unwrap()
-                .rasterize(char_str, render_scale);
-
-            // Create texture from the bitmap
-            let texture_id = self.create_texture_from_bitmap(
-                &bitmap,
-                metrics.width as u32,
-                metrics.height as u32,
-            )?;
+        for ch in font_loader::PRELOADED_CHARS {
+            let rasterized = font_loader::rasterize_glyph(
+                font_info.fontdue_font.as_ref().unwrap(),
+                ch as char,
+                size,
+            );
+            let glyph = self.upload_glyph(&rasterized)?;
+            font_info.glyphs.insert(rasterized.ch, glyph);
+        }
+
+        Ok(())
+    }
+
+    /// Set line metrics from the font's line height
+    fn apply_font_metrics(font_info: &mut FontInfo, line_height: f32) {
+        font_info.line_height = line_height;
+        font_info.ascender = line_height * 0.8; // Approximate ascender
+        font_info.descender = line_height * 0.2; // Approximate descender
+    }
+
+    /// Pack a rasterized glyph bitmap into the glyph atlas
+    fn upload_glyph(&mut self, rasterized: &RasterizedGlyph) -> Result<Glyph, String> {
+        let (texture_id, uv_rect) = self.glyph_atlas.insert(
+            self.texture_manager.as_mut().unwrap(),
+            &rasterized.bitmap,
+            rasterized.width,
+            rasterized.height,
+        )?;
+        self.glyph_generation += 1;
+        Ok(Glyph {
+            texture_id,
+            uv_rect,
+            size: rasterized.size,
+            bearing: rasterized.bearing,
+            advance: rasterized.advance,
+        })
+    }
+
+    /// Start loading a font on a background thread
+    ///
+    /// The font becomes available once it has been parsed; its glyphs are uploaded over
+    /// the following frames by `process_pending_glyphs`, and characters that haven't
+    /// arrived yet are drawn as a placeholder box.
+    pub fn load_font_async(
+        &mut self,
+        name: &str,
+        font_path: &str,
+        size: u32,
+    ) -> Result<(), String> {
+        if !self.initialized {
+            return Err("Text renderer not initialized".to_string());
+        }
 
-            // Scale down metrics to match the requested font size
-            let scale_factor = size as f32 / render_scale;
+        self.font_loader.load(name, font_path, size);
+        Ok(())
+    }
+
+    /// Check if a font is still being loaded in the background
+    pub fn is_font_loading(&self, name: &str) -> bool {
+        self.font_loader.is_loading(name)
+    }
+
+    /// Set the fonts tried, in priority order, for characters missing from `font_name`
+    ///
+    /// Fallback glyphs are rasterized at the primary font's size and cached with it, so a
+    /// symbol, CJK or emoji font can fill in characters instead of them being dropped.
+    pub fn set_font_fallbacks(&mut self, font_name: &str, fallbacks: &[&str]) {
+        self.font_fallbacks.insert(
+            font_name.to_string(),
+            fallbacks.iter().map(|name| name.to_string()).collect(),
+        );
+        self.glyph_generation += 1;
+
+        // Forget missing-glyph boxes already cached so those characters are retried
+        let emoji_texture = self.emoji_atlas.as_ref().map(|atlas| atlas.texture_id);
+        if let Some(font) = self.fonts.get_mut(font_name)
+            && let Some(fontdue_font) = font.fontdue_font.as_ref()
+        {
+            font.glyphs.retain(|ch, glyph| {
+                fontdue_font.lookup_glyph_index(*ch) != 0 || Some(glyph.texture_id) == emoji_texture
+            });
+        }
+    }
+
+    /// Load a full-color emoji atlas: a grid image whose cells hold `chars`, row by row
+    ///
+    /// Every font draws these characters from the atlas in their own colors, sized to the
+    /// font's line, instead of its monochrome outlines or missing-glyph boxes. Only
+    /// single-codepoint emoji are matched; variation selectors and joiners are skipped.
+    pub fn load_emoji_atlas(
+        &mut self,
+        path: &str,
+        columns: u32,
+        rows: u32,
+        chars: &str,
+    ) -> Result<(), String> {
+        if !self.initialized {
+            return Err("Text renderer not initialized".to_string());
+        }
+
+        let regions = atlas::grid_regions(columns, rows, chars.chars().count())?;
+        let options = TextureImportOptions::default().with_wrap(TextureWrap::ClampToEdge);
+        let texture_id = self
+            .texture_manager
+            .as_mut()
+            .unwrap()
+            .load_texture_with_options(path, options)?;
+
+        // Drop glyphs from a previous atlas before adding the new ones
+        let old_texture = self.emoji_atlas.as_ref().map(|atlas| atlas.texture_id);
+        self.emoji_atlas = Some(ColorGlyphAtlas {
+            texture_id,
+            cells: chars.chars().zip(regions).collect(),
+        });
+        let mut fonts = std::mem::take(&mut self.fonts);
+        for font in fonts.values_mut() {
+            font.glyphs
+                .retain(|_, glyph| Some(glyph.texture_id) != old_texture);
+            self.add_emoji_glyphs(font);
+        }
+        self.fonts = fonts;
+        self.glyph_generation += 1;
+        Ok(())
+    }
+
+    /// Give a font the emoji atlas glyphs, sized to its ascender and descender
+    fn add_emoji_glyphs(&self, font: &mut FontInfo) {
+        let Some(atlas) = self.emoji_atlas.as_ref() else {
+            return;
+        };
+        let size = font.ascender + font.descender;
+        for (ch, uv_rect) in &atlas.cells {
             let glyph = Glyph {
-                texture_id,
-                size: Vec2::new(
-                    metrics.width as f32 * scale_factor,
-                    metrics.height as f32 * scale_factor,
-                ),
-                bearing: Vec2::new(
-                    metrics.xmin as f32 * scale_factor,
-                    metrics.ymin as f32 * scale_factor,
+                texture_id: atlas.texture_id,
+                uv_rect: *uv_rect,
+                size: Vec2::splat(size),
+                bearing: Vec2::new(0.0, -font.descender),
+                advance: size,
+            };
+            font.glyphs.insert(*ch, glyph);
+        }
+    }
+
+    /// Tell the text shader whether the bound texture holds full-color glyphs
+    fn set_color_glyph_uniform(&self, shader: u32, texture_id: TextureId) -> Result<(), String> {
+        let color_glyph_loc = self.gl.get_uniform_location(shader, "color_glyph")?;
+        let color_glyph = if self.is_color_glyph_texture(texture_id) {
+            1.0
+        } else {
+            0.0
+        };
+        self.gl.set_uniform_1f(color_glyph_loc, color_glyph)
+    }
+
+    /// Check if glyphs from a texture are drawn in their own colors
+    fn is_color_glyph_texture(&self, texture_id: TextureId) -> bool {
+        self.emoji_atlas
+            .as_ref()
+            .is_some_and(|atlas| atlas.texture_id == texture_id)
+    }
+
+    /// Get the fallback fonts registered for a font, in priority order
+    pub fn font_fallbacks(&self, font_name: &str) -> &[String] {
+        self.font_fallbacks
+            .get(font_name)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Set the time budget for glyph texture uploads per `process_pending_glyphs` call
+    pub fn set_glyph_upload_budget(&mut self, budget: Duration) {
+        self.glyph_upload_budget = budget;
+    }
+
+    /// Get the time budget for glyph texture uploads per frame
+    pub fn glyph_upload_budget(&self) -> Duration {
+        self.glyph_upload_budget
+    }
+
+    /// Upload pending glyphs until the upload budget is spent
+    ///
+    /// Handles characters first seen while rendering and glyphs from background font
+    /// loads. Intended to be called once per frame. Returns the number of glyphs uploaded.
+    pub fn process_pending_glyphs(&mut self) -> usize {
+        if !self.initialized {
+            return 0;
+        }
+
+        let start = Instant::now();
+        let mut uploaded = 0;
+
+        let missing: Vec<(String, char)> = self.missing_glyphs.borrow().iter().cloned().collect();
+        for (font_name, ch) in missing {
+            if start.elapsed() >= self.glyph_upload_budget {
+                return uploaded;
+            }
+            self.missing_glyphs
+                .borrow_mut()
+                .remove(&(font_name.clone(), ch));
+            match self.rasterize_missing_glyph(&font_name, ch) {
+                Ok(true) => uploaded += 1,
+                Ok(false) => {}
+                Err(e) => log::error!(
+                    "Failed to rasterize glyph '{}' for font '{}': {}",
+                    ch,
+                    font_name,
+                    e
                 ),
-                advance: metrics.advance_width * scale_factor,
+            }
+        }
+
+        while self.font_loader.has_pending() && start.elapsed() < self.glyph_upload_budget {
+            let Some(event) = self.font_loader.try_next() else {
+                break;
             };
 
-            font_info.glyphs.insert(char_str, glyph);
+            match event {
+                FontLoadEvent::Parsed {
+                    name,
+                    size,
+                    font,
+                    line_height,
+                } => {
+                    let mut font_info = FontInfo::new(name.clone(), size);
+                    Self::apply_font_metrics(&mut font_info, line_height);
+                    font_info.fontdue_font = Some(*font);
+                    font_info.loading = true;
+                    font_info.placeholder =
+                        self.placeholder_glyph.map(|(texture_id, uv_rect)| Glyph {
+                            texture_id,
+                            uv_rect,
+                            size: Vec2::new(size as f32 * 0.5, size as f32 * 0.7),
+                            bearing: Vec2::new(size as f32 * 0.05, 0.0),
+                            advance: size as f32 * 0.6,
+                        });
+                    self.add_emoji_glyphs(&mut font_info);
+                    self.fonts.insert(name, font_info);
+                }
+                FontLoadEvent::Glyph { name, glyph } => match self.upload_glyph(&glyph) {
+                    Ok(uploaded_glyph) => {
+                        if let Some(font_info) = self.fonts.get_mut(&name) {
+                            font_info.glyphs.insert(glyph.ch, uploaded_glyph);
+                            uploaded += 1;
+                        }
+                    }
+                    Err(e) => log::error!(
+                        "Failed to upload glyph '{}' for font '{}': {}",
+                        glyph.ch,
+                        name,
+                        e
+                    ),
+                },
+                FontLoadEvent::Finished { name } => {
+                    if let Some(font_info) = self.fonts.get_mut(&name) {
+                        font_info.loading = false;
+                        println!(
+                            "Font '{}' loaded successfully with {} glyphs",
+                            name,
+                            font_info.glyphs.len()
+                        );
+                    }
+                }
+                FontLoadEvent::Failed { name, error } => {
+                    log::error!("Failed to load font '{}': {}", name, error);
+                }
+            }
+        }
+
+        uploaded
+    }
+
+    /// Rasterize glyphs for every character of `text` that the font doesn't have yet
+    ///
+    /// Rendering does this lazily (the glyph appears on the following frame); call this
+    /// to have text complete on its first frame.
+    pub fn ensure_glyphs(&mut self, font_name: &str, text: &str) -> Result<(), String> {
+        if !self.initialized {
+            return Err("Text renderer not initialized".to_string());
         }
 
+        for ch in text.chars() {
+            self.rasterize_missing_glyph(font_name, ch)?;
+        }
         Ok(())
     }
 
-    /// Create a texture from fontdue bitmap data
-    fn create_texture_from_bitmap(
-        &mut self,
-        bitmap: &[u8],
-        width: u32,
-        height: u32,
-    ) -> Result<TextureId, String> {
-        let texture_manager = self.texture_manager.as_mut().unwrap();
+    /// Rasterize and upload a glyph if the font lacks it; returns true if one was added
+    fn rasterize_missing_glyph(&mut self, font_name: &str, ch: char) -> Result<bool, String> {
+        if is_invisible(ch) {
+            return Ok(false);
+        }
+        let font = self
+            .fonts
+            .get(font_name)
+            .ok_or_else(|| format!("Font '{}' not found", font_name))?;
+        let Some(source) = self.glyph_source(font, ch) else {
+            return Ok(false);
+        };
+        if font.glyphs.contains_key(&ch) {
+            return Ok(false);
+        }
+
+        let rasterized = font_loader::rasterize_glyph(source, ch, font.size);
+        let glyph = self.upload_glyph(&rasterized)?;
+        if let Some(font) = self.fonts.get_mut(font_name) {
+            font.glyphs.insert(ch, glyph);
+        }
+        Ok(true)
+    }
 
-        // For font textures, we typically use the grayscale data directly as the alpha channel
-        // and set RGB to white (255, 255, 255) so the text color can be applied in the shader
-        let mut pixels = vec![0u8; (width * height * 4) as usize];
+    /// Pick the font that supplies `ch`: the font itself, else its first fallback with it
+    ///
+    /// Characters no font has come from the primary font's missing-glyph box, so they
+    /// stay visible. Returns `None` if the font has no outlines to rasterize.
+    fn glyph_source<'a>(&'a self, font: &'a FontInfo, ch: char) -> Option<&'a Font> {
+        let primary = font.fontdue_font.as_ref()?;
+        if primary.lookup_glyph_index(ch) != 0 {
+            return Some(primary);
+        }
+
+        let fallback = self
+            .font_fallbacks(&font.name)
+            .iter()
+            .filter_map(|name| self.fonts.get(name)?.fontdue_font.as_ref())
+            .find(|fallback| fallback.lookup_glyph_index(ch) != 0);
+        Some(fallback.unwrap_or(primary))
+    }
 
-        for i in 0..(width * height) as usize {
-            let pixel_index = i * 4;
-            let alpha = bitmap[i];
-            pixels[pixel_index] = 255; // R (white)
-            pixels[pixel_index + 1] = 255; // G (white)
-            pixels[pixel_index + 2] = 255; // B (white)
-            pixels[pixel_index + 3] = alpha; // A (glyph shape)
+    /// Look up a glyph for rendering, queuing characters without one for rasterization
+    fn lookup_glyph<'a>(&self, font: &'a FontInfo, ch: char) -> Option<&'a Glyph> {
+        let glyph = font.glyph(ch);
+        if !font.glyphs.contains_key(&ch) && !is_invisible(ch) && font.fontdue_font.is_some() {
+            self.missing_glyphs
+                .borrow_mut()
+                .insert((font.name.clone(), ch));
         }
+        glyph
+    }
 
-        texture_manager.create_texture_from_data(width, height, &pixels)
+    /// Enable or disable pair kerning (enabled by default)
+    pub fn set_kerning_enabled(&mut self, enabled: bool) {
+        self.kerning_enabled = enabled;
+        self.glyph_generation += 1;
+    }
+
+    /// Check if pair kerning is applied
+    pub fn kerning_enabled(&self) -> bool {
+        self.kerning_enabled
+    }
+
+    /// Clip drawing to a rect in 0..1 across the view with y down (min is the top-left),
+    /// intersected with any enclosing clip rect; undo with `pop_clip_rect`
+    pub fn push_clip_rect(&self, min: Vec2, size: Vec2) -> Result<(), String> {
+        let rect = self.gl.viewport_rect().sub_rect(min, size);
+        self.gl.push_clip_rect(rect)
+    }
+
+    /// Restore the clip rect from before the last `push_clip_rect`
+    pub fn pop_clip_rect(&self) -> Result<(), String> {
+        self.gl.pop_clip_rect()
+    }
+
+    /// Content area of the text's box, normalized, if the text is clipped to it
+    fn clip_area(&self, text: &Text) -> Option<(Vec2, Vec2)> {
+        if !text.config.clip_to_box {
+            return None;
+        }
+        let bounding_box = text.config.bounding_box?;
+        Some(self.normalized_content_area(&bounding_box))
+    }
+
+    /// Top-left and size of a box's content area, normalized with y down
+    fn normalized_content_area(&self, bounding_box: &TextBox) -> (Vec2, Vec2) {
+        // Like layout_text_in_box, sizes above 1 are taken as logical units
+        let (min, width, height) = bounding_box.content_area();
+        let (x_range, y_range) = self.viewport.get_logical_ranges();
+        let normalize = |size: f32, range: f32| if size <= 1.0 { size } else { size / range };
+        let size = Vec2::new(normalize(width, x_range), normalize(height, y_range));
+        (min, size)
+    }
+
+    /// Set the camera position that world-space text scrolls against
+    pub fn set_camera_position(&mut self, position: Vec2) {
+        self.camera_position = position;
+    }
+
+    pub fn camera_position(&self) -> Vec2 {
+        self.camera_position
+    }
+
+    /// Pseudo-localize all text as it is laid out and measured (None turns it off)
+    pub fn set_pseudo_locale(&mut self, pseudo_locale: Option<PseudoLocale>) {
+        self.pseudo_locale = pseudo_locale;
+        self.glyph_generation += 1;
+    }
+
+    pub fn pseudo_locale(&self) -> Option<PseudoLocale> {
+        self.pseudo_locale
+    }
+
+    /// Record text boxes whose content overflows or gets ellipsized, for debug overlays
+    ///
+    /// Boxes are recorded each time they are laid out; prepared text only when its layout
+    /// is rebuilt.
+    pub fn set_overflow_tracking(&mut self, enabled: bool) {
+        self.overflow_tracking = enabled;
+        self.overflows.borrow_mut().clear();
+    }
+
+    pub fn overflow_tracking(&self) -> bool {
+        self.overflow_tracking
+    }
+
+    /// Boxes that overflowed since the last call
+    pub fn take_overflows(&self) -> Vec<TextOverflow> {
+        self.overflows.take()
+    }
+
+    /// Kerning between the previous character on the line and `ch`, in pixels
+    fn kerning(&self, font: &FontInfo, previous: Option<char>, ch: char) -> f32 {
+        match previous {
+            Some(previous) if self.kerning_enabled => font.kerning(previous, ch),
+            _ => 0.0,
+        }
+    }
+
+    /// Get the atlas holding every uploaded glyph
+    pub fn glyph_atlas(&self) -> &GlyphAtlas {
+        &self.glyph_atlas
     }
 
     /// Render text
@@ -529,7 +1338,18 @@ impl TextRenderer {
         if !self.initialized {
             return Err("Text renderer not initialized".to_string());
         }
+        let clip = self.clip_area(text);
+        if let Some((min, size)) = clip {
+            self.push_clip_rect(min, size)?;
+        }
+        let result = self.draw_text_glyphs(text);
+        if clip.is_some() {
+            self.pop_clip_rect()?;
+        }
+        result
+    }
 
+    fn draw_text_glyphs(&self, text: &Text) -> Result<(), String> {
         let font = self
             .fonts
             .get(&text.font_name)
@@ -540,44 +1360,405 @@ impl TextRenderer {
 
         self.gl.use_program(shader)?;
 
-        // Set text color and alpha
-        let color_loc = self.gl.get_uniform_location(shader, "text_color")?;
-        self.gl.set_uniform_3f(
-            color_loc,
-            text.config.color.0,
-            text.config.color.1,
-            text.config.color.2,
-        )?;
-
-        let alpha_loc = self.gl.get_uniform_location(shader, "alpha")?;
-        self.gl.set_uniform_1f(alpha_loc, text.config.alpha)?;
-
         // Set texture uniform
         let texture_loc = self.gl.get_uniform_location(shader, "text_texture")?;
         self.gl.set_uniform_1i(texture_loc, 0)?; // Use texture unit 0
+        self.gl.active_texture(0x84C0)?; // GL_TEXTURE0
+        self.bound_texture.set(None);
 
         let scale_factor = self.viewport.calculate_scale_factor(font.size as f32);
 
+        let passes = Self::effect_passes(&text.config, scale_factor);
+        let reveal = text.config.reveal;
+        let view_offset = -text.config.space.camera_position(self.camera_position);
+        if passes.len() == 1 && reveal.is_none() {
+            self.set_pass_color(shader, &passes[0])?;
+            return self.layout_text(text, font, scale_factor, &mut |glyph, position| {
+                let position = position + view_offset;
+                self.render_glyph(glyph, position, shader, vao, font.size, scale_factor)
+            });
+        }
+
+        // Lay out once, then draw the glyphs for every shadow and outline pass
+        let mut placed = Vec::new();
+        self.layout_text(text, font, scale_factor, &mut |glyph, position| {
+            placed.push((glyph.clone(), position));
+            Ok(())
+        })?;
+        if let Some(reveal) = reveal {
+            placed.truncate(reveal.visible_chars());
+        }
+        for pass in &passes {
+            self.set_pass_color(shader, pass)?;
+            for (index, (glyph, position)) in placed.iter().enumerate() {
+                let mut position = *position + pass.offset + view_offset;
+                if let Some(style) = reveal.and_then(|reveal| reveal.char_style(index)) {
+                    position += style.offset * scale_factor;
+                    let color = style.color.filter(|_| pass.glyph_colors);
+                    let styled = EffectPass {
+                        color: color.unwrap_or(pass.color),
+                        alpha: pass.alpha * style.alpha,
+                        ..*pass
+                    };
+                    self.set_pass_color(shader, &styled)?;
+                }
+                self.render_glyph(glyph, position, shader, vao, font.size, scale_factor)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw a `PreparedText`, laying it out again first only if it is stale
+    ///
+    /// Text being revealed changes every frame, so it is drawn like `render_text`.
+    pub fn render_prepared(&self, prepared: &mut PreparedText) -> Result<(), String> {
+        if !self.initialized {
+            return Err("Text renderer not initialized".to_string());
+        }
+        let clip = self.clip_area(&prepared.text);
+        if let Some((min, size)) = clip {
+            self.push_clip_rect(min, size)?;
+        }
+        let result = self.draw_prepared_glyphs(prepared);
+        if clip.is_some() {
+            self.pop_clip_rect()?;
+        }
+        result
+    }
+
+    fn draw_prepared_glyphs(&self, prepared: &mut PreparedText) -> Result<(), String> {
+        if prepared.text.config.reveal.is_some() {
+            return self.draw_text_glyphs(&prepared.text);
+        }
+
+        let text = &prepared.text;
+        let view_offset = -text.config.space.camera_position(self.camera_position);
+        let font = self
+            .fonts
+            .get(&text.font_name)
+            .ok_or_else(|| format!("Font '{}' not found", text.font_name))?;
+        let shader = self.text_shader.ok_or("Text shader not initialized")?;
+
+        let scale_factor = self.viewport.calculate_scale_factor(font.size as f32);
+        let layout_key = (
+            self.glyph_generation,
+            scale_factor,
+            self.viewport.get_logical_bounds(),
+        );
+        if prepared.dirty || prepared.layout_key != Some(layout_key) {
+            self.build_prepared_layout(prepared, font, scale_factor)?;
+            prepared.layout_key = Some(layout_key);
+            prepared.dirty = false;
+        }
+        let Some((_, vao, _)) = prepared.buffers else {
+            return Ok(());
+        };
+
+        self.gl.use_program(shader)?;
+        let texture_loc = self.gl.get_uniform_location(shader, "text_texture")?;
+        self.gl.set_uniform_1i(texture_loc, 0)?;
+        self.gl.active_texture(0x84C0)?; // GL_TEXTURE0
+
+        // Vertices are already in NDC with atlas coordinates, so the glyph transform is
+        // identity apart from the offset of each effect pass
+        let pos_loc = self.gl.get_uniform_location(shader, "glyph_position")?;
+        let size_loc = self.gl.get_uniform_location(shader, "glyph_size")?;
+        let uv_loc = self.gl.get_uniform_location(shader, "uv_rect")?;
+        self.gl.set_uniform_2f(size_loc, 1.0, 1.0)?;
+        self.gl.set_uniform_4f(uv_loc, 0.0, 0.0, 1.0, 1.0)?;
+
+        let (x_range, y_range) = self.viewport.get_logical_ranges();
+        let texture_manager = self.texture_manager.as_ref().unwrap();
+        self.gl.bind_vertex_array(vao)?;
+        for pass in Self::effect_passes(&prepared.text.config, scale_factor) {
+            self.set_pass_color(shader, &pass)?;
+            let offset = pass.offset + view_offset;
+            self.gl.set_uniform_2f(
+                pos_loc,
+                offset.x * (2.0 / x_range),
+                offset.y * (2.0 / y_range),
+            )?;
+            for &(texture_id, first, count) in &prepared.batches {
+                texture_manager.bind_texture(texture_id)?;
+                self.set_color_glyph_uniform(shader, texture_id)?;
+                self.gl.draw_arrays(gl::TRIANGLES, first, count)?;
+            }
+        }
+        self.bound_texture.set(None);
+
+        Ok(())
+    }
+
+    /// Draw every live popup in one batch, shifted by `view_offset` in logical units
+    ///
+    /// Each popup is a single line centered horizontally on its position, with its
+    /// baseline there. Color and alpha are per vertex, so the batch needs only one draw
+    /// call per glyph atlas page however many popups there are.
+    pub fn render_popups(
+        &self,
+        popups: &PopupTexts,
+        font_name: &str,
+        view_offset: Vec2,
+    ) -> Result<(), String> {
+        if !self.initialized {
+            return Err("Text renderer not initialized".to_string());
+        }
+        if popups.is_empty() {
+            return Ok(());
+        }
+
+        let font = self
+            .fonts
+            .get(font_name)
+            .ok_or_else(|| format!("Font '{}' not found", font_name))?;
+        let shader = self.popup_shader.ok_or("Popup shader not initialized")?;
+        let (vao, vbo) = self.popup_buffers.ok_or("Popup buffers not initialized")?;
+        let scale_factor = self.viewport.calculate_scale_factor(font.size as f32);
+
+        let mut quads = self.popup_quads.borrow_mut();
+        quads.clear();
+        for popup in popups.iter() {
+            let scale = scale_factor * popup.scale;
+            let width = self.calculate_text_width(popup.text, font) * popup.scale;
+            let mut pen = popup.position + view_offset + popup.offset * scale_factor;
+            pen.x -= width / 2.0;
+            let color = [popup.color.0, popup.color.1, popup.color.2, popup.alpha];
+
+            let mut previous = None;
+            for ch in popup.text.chars() {
+                pen.x += self.kerning(font, previous, ch) * scale;
+                previous = Some(ch);
+                let Some(glyph) = self.lookup_glyph(font, ch) else {
+                    continue;
+                };
+
+                let (corner, size) = self.glyph_quad(glyph, pen + glyph.bearing * scale, scale);
+                let (u, v, uv_width, uv_height) = glyph.uv_rect;
+                let vertex = |x: f32, y: f32| {
+                    [
+                        corner.x + x * size.x,
+                        corner.y + y * size.y,
+                        u + x * uv_width,
+                        v + (1.0 - y) * uv_height,
+                        color[0],
+                        color[1],
+                        color[2],
+                        color[3],
+                    ]
+                };
+                let corners = [
+                    vertex(0.0, 0.0),
+                    vertex(1.0, 0.0),
+                    vertex(0.0, 1.0),
+                    vertex(0.0, 1.0),
+                    vertex(1.0, 0.0),
+                    vertex(1.0, 1.0),
+                ];
+                quads.push((glyph.texture_id, corners.concat().try_into().unwrap()));
+                pen.x += glyph.advance * scale;
+            }
+        }
+
+        // Group quads by page; the sort is stable, so later popups still draw on top
+        quads.sort_by_key(|(texture_id, _)| texture_id.0);
+        let mut batches: Vec<(TextureId, i32, i32)> = Vec::new();
+        let mut vertices = self.popup_vertices.borrow_mut();
+        vertices.clear();
+        for (texture_id, quad) in quads.iter() {
+            let first = (vertices.len() / POPUP_VERTEX_FLOATS) as i32;
+            match batches.last_mut() {
+                Some((page, _, count)) if page == texture_id => {
+                    *count += PREPARED_QUAD_VERTICES as i32
+                }
+                _ => batches.push((*texture_id, first, PREPARED_QUAD_VERTICES as i32)),
+            }
+            vertices.extend_from_slice(quad);
+        }
+
+        self.gl.use_program(shader)?;
+        let texture_loc = self.gl.get_uniform_location(shader, "text_texture")?;
+        self.gl.set_uniform_1i(texture_loc, 0)?;
+        self.gl.active_texture(0x84C0)?; // GL_TEXTURE0
+
+        self.gl.bind_vertex_array(vao)?;
+        self.gl.bind_buffer(gl::ARRAY_BUFFER, vbo)?;
+        self.gl
+            .set_buffer_data(gl::ARRAY_BUFFER, &vertices, gl::DYNAMIC_DRAW)?;
+        let texture_manager = self.texture_manager.as_ref().unwrap();
+        for (texture_id, first, count) in batches {
+            texture_manager.bind_texture(texture_id)?;
+            self.set_color_glyph_uniform(shader, texture_id)?;
+            self.gl.draw_arrays(gl::TRIANGLES, first, count)?;
+        }
+        self.gl.bind_vertex_array(0)?;
+        self.bound_texture.set(None);
+
+        Ok(())
+    }
+
+    /// Draw passes for a text's effects, back to front: shadow, outline, then the text itself
+    fn effect_passes(config: &TextConfig, scale_factor: f32) -> Vec<EffectPass> {
+        let mut passes = Vec::new();
+        if let Some(shadow) = config.shadow {
+            passes.push(EffectPass {
+                offset: shadow.offset * scale_factor,
+                color: shadow.color,
+                alpha: shadow.alpha * config.alpha,
+                glyph_colors: false,
+            });
+        }
+        if let Some(outline) = config.outline.filter(|outline| outline.width > 0.0) {
+            // Eight offset copies approximate a stroke well for outlines a few pixels wide
+            let width = outline.width * scale_factor;
+            for step in 0..8 {
+                let angle = step as f32 * std::f32::consts::FRAC_PI_4;
+                passes.push(EffectPass {
+                    offset: Vec2::from_angle(angle) * width,
+                    color: outline.color,
+                    alpha: config.alpha,
+                    glyph_colors: false,
+                });
+            }
+        }
+        passes.push(EffectPass {
+            offset: Vec2::ZERO,
+            color: config.color,
+            alpha: config.alpha,
+            glyph_colors: true,
+        });
+        passes
+    }
+
+    fn set_pass_color(&self, shader: u32, pass: &EffectPass) -> Result<(), String> {
+        let color_loc = self.gl.get_uniform_location(shader, "text_color")?;
+        self.gl
+            .set_uniform_3f(color_loc, pass.color.0, pass.color.1, pass.color.2)?;
+        let alpha_loc = self.gl.get_uniform_location(shader, "alpha")?;
+        self.gl.set_uniform_1f(alpha_loc, pass.alpha)?;
+        let glyph_color_loc = self.gl.get_uniform_location(shader, "use_glyph_color")?;
+        self.gl
+            .set_uniform_1f(glyph_color_loc, if pass.glyph_colors { 1.0 } else { 0.0 })
+    }
+
+    /// Lay out prepared text and upload its glyph quads, grouped by atlas page
+    fn build_prepared_layout(
+        &self,
+        prepared: &mut PreparedText,
+        font: &FontInfo,
+        scale_factor: f32,
+    ) -> Result<(), String> {
+        let mut quads: Vec<(
+            TextureId,
+            [f32; PREPARED_VERTEX_FLOATS * PREPARED_QUAD_VERTICES],
+        )> = Vec::new();
+        self.layout_text(
+            &prepared.text,
+            font,
+            scale_factor,
+            &mut |glyph, position| {
+                let (corner, size) = self.glyph_quad(glyph, position, scale_factor);
+                let (u, v, uv_width, uv_height) = glyph.uv_rect;
+                let vertex = |x: f32, y: f32| {
+                    [
+                        corner.x + x * size.x,
+                        corner.y + y * size.y,
+                        u + x * uv_width,
+                        v + (1.0 - y) * uv_height,
+                    ]
+                };
+                let corners = [
+                    vertex(0.0, 0.0),
+                    vertex(1.0, 0.0),
+                    vertex(0.0, 1.0),
+                    vertex(0.0, 1.0),
+                    vertex(1.0, 0.0),
+                    vertex(1.0, 1.0),
+                ];
+                quads.push((glyph.texture_id, corners.concat().try_into().unwrap()));
+                Ok(())
+            },
+        )?;
+
+        // Group quads by page so each page is a single draw call
+        quads.sort_by_key(|(texture_id, _)| texture_id.0);
+        prepared.batches.clear();
+        let mut vertices =
+            Vec::with_capacity(quads.len() * PREPARED_VERTEX_FLOATS * PREPARED_QUAD_VERTICES);
+        for (texture_id, quad) in &quads {
+            let first = (vertices.len() / PREPARED_VERTEX_FLOATS) as i32;
+            match prepared.batches.last_mut() {
+                Some((page, _, count)) if page == texture_id => {
+                    *count += PREPARED_QUAD_VERTICES as i32
+                }
+                _ => prepared
+                    .batches
+                    .push((*texture_id, first, PREPARED_QUAD_VERTICES as i32)),
+            }
+            vertices.extend_from_slice(quad);
+        }
+
+        if prepared.buffers.is_none() {
+            let vao = self.gl.gen_vertex_array()?;
+            let vbo = self.gl.gen_buffer()?;
+            self.gl.bind_vertex_array(vao)?;
+            self.gl.bind_buffer(gl::ARRAY_BUFFER, vbo)?;
+            let stride = (PREPARED_VERTEX_FLOATS * 4) as i32;
+            self.gl
+                .set_vertex_attrib_pointer(0, 2, gl::FLOAT, false, stride, 0)?;
+            self.gl.enable_vertex_attrib_array(0)?;
+            self.gl
+                .set_vertex_attrib_pointer(1, 2, gl::FLOAT, false, stride, 2 * 4)?;
+            self.gl.enable_vertex_attrib_array(1)?;
+            prepared.buffers = Some((Rc::clone(&self.gl), vao, vbo));
+        }
+        if let Some((_, vao, vbo)) = prepared.buffers {
+            self.gl.bind_vertex_array(vao)?;
+            self.gl.bind_buffer(gl::ARRAY_BUFFER, vbo)?;
+            self.gl
+                .set_buffer_data(gl::ARRAY_BUFFER, &vertices, gl::STATIC_DRAW)?;
+            self.gl.bind_vertex_array(0)?;
+        }
+
+        Ok(())
+    }
+
+    /// Position every glyph of `text`, passing each to `emit` with its logical position
+    fn layout_text(
+        &self,
+        text: &Text,
+        font: &FontInfo,
+        scale_factor: f32,
+        emit: &mut dyn FnMut(&Glyph, Vec2) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let pseudo_text;
+        let text = match self.pseudo_locale {
+            Some(pseudo_locale) => {
+                pseudo_text = Text {
+                    content: pseudo_locale.apply(&text.content),
+                    ..text.clone()
+                };
+                &pseudo_text
+            }
+            None => text,
+        };
+
         // Handle bounding box if present
         if let Some(ref bounding_box) = text.config.bounding_box {
-            self.render_text_in_box(text, font, shader, vao, bounding_box, scale_factor)?;
+            self.layout_text_in_box(text, font, bounding_box, scale_factor, emit)
         } else {
-            // Legacy rendering without bounding box
-            self.render_text_legacy(text, font, shader, vao, scale_factor)?;
+            // Legacy layout without bounding box
+            self.layout_text_legacy(text, font, scale_factor, emit)
         }
-
-        Ok(())
     }
 
-    /// Render text within a bounding box (top-left origin coordinate system)
-    fn render_text_in_box(
+    /// Lay out text within a bounding box (top-left origin coordinate system)
+    fn layout_text_in_box(
         &self,
         text: &Text,
         font: &FontInfo,
-        shader: u32,
-        vao: u32,
         bounding_box: &TextBox,
         scale_factor: f32,
+        emit: &mut dyn FnMut(&Glyph, Vec2) -> Result<(), String>,
     ) -> Result<(), String> {
         // Get content area (box minus padding) in top-left origin coordinates
         let (content_pos, content_width, content_height) = bounding_box.content_area();
@@ -615,18 +1796,31 @@ impl TextRenderer {
         };
 
         // Process text with wrapping, using content width
-        let mut text_with_wrap = text.clone();
-        text_with_wrap.config.max_width = Some(viewport_content_width);
-        if text_with_wrap.config.wrap == TextWrap::None {
-            text_with_wrap.config.wrap = TextWrap::Word; // Default to word wrap when box is specified
-        }
-        let wrapped_content = self.process_text_wrapping(&text_with_wrap, font);
+        let wrapped_content = self.wrapped_content(text, font);
 
         // Calculate total text height
         let line_height = font.line_height * text.config.line_spacing * scale_factor;
         let line_count = wrapped_content.lines().count().max(1);
         let total_text_height = line_height * line_count as f32;
 
+        if self.overflow_tracking {
+            let overflowed = total_text_height > viewport_content_height
+                || wrapped_content
+                    .lines()
+                    .any(|line| self.calculate_text_width(line, font) > viewport_content_width);
+            let ellipsized =
+                text.config.wrap == TextWrap::Ellipsis && wrapped_content != text.content;
+            if overflowed || ellipsized {
+                let (min, size) = self.normalized_content_area(bounding_box);
+                self.overflows.borrow_mut().push(TextOverflow {
+                    min,
+                    size,
+                    ellipsized,
+                    content: text.content.clone(),
+                });
+            }
+        }
+
         // Calculate starting Y position based on vertical alignment
         // In viewport logical coordinates (bottom-left origin, y increases upward):
         // - Top of content area is at normalized_content_pos.y (highest y)
@@ -643,6 +1837,8 @@ impl TextRenderer {
         let viewport_start_y = normalized_content_pos.y - start_y_offset;
 
         // Process each line and calculate horizontal alignment
+        let direction = text.config.direction.resolve(&text.content);
+        let align = start_relative_align(text.config.align, direction);
         let lines: Vec<&str> = wrapped_content.lines().collect();
         let mut current_y = viewport_start_y;
 
@@ -652,10 +1848,11 @@ impl TextRenderer {
                 continue;
             }
 
-            let line_width = self.calculate_text_width(line, font);
+            let line = bidi::visual_order(line, direction);
+            let line_width = self.calculate_text_width(&line, font);
             
             // Calculate horizontal start position within content area
-            let start_x = match text.config.align {
+            let start_x = match align {
                 TextAlign::Left => normalized_content_pos.x,
                 TextAlign::Center => normalized_content_pos.x + (viewport_content_width - line_width) / 2.0,
                 TextAlign::Right => normalized_content_pos.x + viewport_content_width - line_width,
@@ -663,8 +1860,11 @@ impl TextRenderer {
 
             // Render each character in the line
             let mut current_x = start_x;
+            let mut previous = None;
             for ch in line.chars() {
-                if let Some(glyph) = font.glyphs.get(&ch) {
+                current_x += self.kerning(font, previous, ch) * scale_factor;
+                previous = Some(ch);
+                if let Some(glyph) = self.lookup_glyph(font, ch) {
                     // Calculate glyph position
                     let glyph_x = current_x + glyph.bearing.x * scale_factor;
                     let glyph_y = current_y + glyph.bearing.y * scale_factor;
@@ -682,14 +1882,7 @@ impl TextRenderer {
                         && glyph_bottom >= content_bottom
                         && glyph_top <= content_top
                     {
-                        self.render_glyph(
-                            glyph,
-                            Vec2::new(glyph_x, glyph_y),
-                            shader,
-                            vao,
-                            font.size,
-                            scale_factor,
-                        )?;
+                        emit(glyph, Vec2::new(glyph_x, glyph_y))?;
                     }
 
                     // Advance to next character
@@ -704,102 +1897,72 @@ impl TextRenderer {
         Ok(())
     }
 
-    /// Legacy rendering without bounding box (for backward compatibility)
-    fn render_text_legacy(
+    /// Legacy layout without bounding box (for backward compatibility)
+    fn layout_text_legacy(
         &self,
         text: &Text,
         font: &FontInfo,
-        shader: u32,
-        vao: u32,
         scale_factor: f32,
+        emit: &mut dyn FnMut(&Glyph, Vec2) -> Result<(), String>,
     ) -> Result<(), String> {
-        // Process text with wrapping
+        // Process text with wrapping, then put each line in drawing order
         let wrapped_content = self.process_text_wrapping(text, font);
+        let direction = text.config.direction.resolve(&text.content);
+        let lines: Vec<_> = wrapped_content
+            .split('\n')
+            .map(|line| bidi::visual_order(line, direction))
+            .collect();
 
         // Calculate text width for alignment (use first line for alignment)
-        let first_line = wrapped_content.lines().next().unwrap_or("");
+        let first_line = lines.first().map(|line| line.as_ref()).unwrap_or("");
         let text_width = self.calculate_text_width(first_line, font);
 
-        let start_x = match text.config.align {
-            TextAlign::Left => {
-                // For left alignment, subtract the first character's bearing to position text exactly at the specified x
-                let first_char_bearing = text
-                    .content
-                    .chars()
-                    .next()
-                    .and_then(|ch| font.glyphs.get(&ch))
-                    .map(|glyph| glyph.bearing.x * scale_factor)
-                    .unwrap_or(0.0);
-                text.position.x - first_char_bearing
-            }
-            TextAlign::Center => {
-                // For center alignment, also account for the first character's bearing
-                let first_char_bearing = text
-                    .content
-                    .chars()
-                    .next()
-                    .and_then(|ch| font.glyphs.get(&ch))
-                    .map(|glyph| glyph.bearing.x * scale_factor)
-                    .unwrap_or(0.0);
-                text.position.x - text_width / 2.0 - first_char_bearing
-            }
-            TextAlign::Right => {
-                // For right alignment, also account for the first character's bearing
-                let first_char_bearing = text
-                    .content
-                    .chars()
-                    .next()
-                    .and_then(|ch| font.glyphs.get(&ch))
-                    .map(|glyph| glyph.bearing.x * scale_factor)
-                    .unwrap_or(0.0);
-                text.position.x - text_width - first_char_bearing
-            }
+        // Account for the first character's bearing to position text exactly at the specified x
+        let first_char_bearing = first_line
+            .chars()
+            .next()
+            .and_then(|ch| self.lookup_glyph(font, ch))
+            .map(|glyph| glyph.bearing.x * scale_factor)
+            .unwrap_or(0.0);
+        let start_x = match start_relative_align(text.config.align, direction) {
+            TextAlign::Left => text.position.x - first_char_bearing,
+            TextAlign::Center => text.position.x - text_width / 2.0 - first_char_bearing,
+            TextAlign::Right => text.position.x - text_width - first_char_bearing,
         };
 
         // Render each character
-        let mut current_x = start_x;
         let mut current_y = text.position.y;
 
-        for ch in wrapped_content.chars() {
-            if ch == '\n' {
-                current_x = start_x;
-                current_y -= font.line_height * text.config.line_spacing * scale_factor; // Scale line height
-                continue;
-            }
+        for line in &lines {
+            let mut current_x = start_x;
+            let mut previous = None;
+
+            for ch in line.chars() {
+                // Pull pairs like "AV" together before placing the glyph
+                current_x += self.kerning(font, previous, ch) * scale_factor;
+                previous = Some(ch);
+
+                if let Some(glyph) = self.lookup_glyph(font, ch) {
+                    // Calculate glyph position (scaled for normalized coordinates)
+                    let glyph_x = current_x + glyph.bearing.x * scale_factor;
+                    let glyph_y = current_y + glyph.bearing.y * scale_factor;
 
-            if let Some(glyph) = font.glyphs.get(&ch) {
-                // Calculate glyph position (scaled for normalized coordinates)
-                let glyph_x = current_x + glyph.bearing.x * scale_factor;
-                let glyph_y = current_y + glyph.bearing.y * scale_factor;
-
-                // Render the glyph
-                self.render_glyph(
-                    glyph,
-                    Vec2::new(glyph_x, glyph_y),
-                    shader,
-                    vao,
-                    font.size,
-                    scale_factor,
-                )?;
-
-                // Advance to next character (scaled for normalized coordinates)
-                current_x += glyph.advance * scale_factor;
+                    // Render the glyph
+                    emit(glyph, Vec2::new(glyph_x, glyph_y))?;
+
+                    // Advance to next character (scaled for normalized coordinates)
+                    current_x += glyph.advance * scale_factor;
+                }
             }
+
+            current_y -= font.line_height * text.config.line_spacing * scale_factor; // Scale line height
         }
 
         Ok(())
     }
 
-    /// Render a single glyph
-    fn render_glyph(
-        &self,
-        glyph: &Glyph,
-        position: Vec2,
-        shader: u32,
-        vao: u32,
-        font_size: u32,
-        scale_factor: f32,
-    ) -> Result<(), String> {
+    /// Corner and size in NDC of a glyph quad at a logical position
+    fn glyph_quad(&self, glyph: &Glyph, position: Vec2, scale_factor: f32) -> (Vec2, Vec2) {
         // Use the scale factor passed from the main render loop (no duplicate calculation)
         let scaled_size = Vec2::new(glyph.size.x * scale_factor, glyph.size.y * scale_factor);
 
@@ -813,18 +1976,39 @@ impl TextRenderer {
             scaled_size.y * (2.0 / y_range), // Scale height for NDC space
         );
 
-        // Set glyph position and size
+        (gl_position, gl_size)
+    }
+
+    /// Render a single glyph
+    fn render_glyph(
+        &self,
+        glyph: &Glyph,
+        position: Vec2,
+        shader: u32,
+        vao: u32,
+        font_size: u32,
+        scale_factor: f32,
+    ) -> Result<(), String> {
+        let (gl_position, gl_size) = self.glyph_quad(glyph, position, scale_factor);
+
+        // Set glyph position, size and atlas region
         let pos_loc = self.gl.get_uniform_location(shader, "glyph_position")?;
         let size_loc = self.gl.get_uniform_location(shader, "glyph_size")?;
+        let uv_loc = self.gl.get_uniform_location(shader, "uv_rect")?;
 
         self.gl
             .set_uniform_2f(pos_loc, gl_position.x, gl_position.y)?;
         self.gl.set_uniform_2f(size_loc, gl_size.x, gl_size.y)?;
-
-        // Bind the glyph texture to texture unit 0
-        let texture_manager = self.texture_manager.as_ref().unwrap();
-        self.gl.active_texture(0x84C0)?; // GL_TEXTURE0
-        texture_manager.bind_texture(glyph.texture_id)?;
+        let (u, v, uv_width, uv_height) = glyph.uv_rect;
+        self.gl.set_uniform_4f(uv_loc, u, v, uv_width, uv_height)?;
+
+        // Glyphs share atlas pages, so usually only the first glyph of a string binds
+        if self.bound_texture.get() != Some(glyph.texture_id) {
+            let texture_manager = self.texture_manager.as_ref().unwrap();
+            texture_manager.bind_texture(glyph.texture_id)?;
+            self.bound_texture.set(Some(glyph.texture_id));
+            self.set_color_glyph_uniform(shader, glyph.texture_id)?;
+        }
 
         // Draw the quad
         self.gl.bind_vertex_array(vao)?;
@@ -839,12 +2023,19 @@ impl TextRenderer {
         let mut max_width: f32 = 0.0;
         let scale_factor = self.viewport.calculate_scale_factor(font.size as f32);
         let (_x_range, _) = self.viewport.get_logical_ranges();
+        let mut previous = None;
 
         for ch in text.chars() {
             if ch == '\n' {
                 max_width = max_width.max(width);
                 width = 0.0;
-            } else if let Some(glyph) = font.glyphs.get(&ch) {
+                previous = None;
+                continue;
+            }
+
+            width += self.kerning(font, previous, ch) * scale_factor;
+            previous = Some(ch);
+            if let Some(glyph) = self.lookup_glyph(font, ch) {
                 width += glyph.advance * scale_factor;
             }
         }
@@ -852,6 +2043,27 @@ impl TextRenderer {
         max_width.max(width)
     }
 
+    /// Wrap text the way layout does, word wrapping to the content width of a bounding box
+    fn wrapped_content(&self, text: &Text, font: &FontInfo) -> String {
+        let Some(bounding_box) = &text.config.bounding_box else {
+            return self.process_text_wrapping(text, font);
+        };
+
+        // Box sizes up to 1.0 are fractions of the viewport, like in box layout
+        let (_, content_width, _) = bounding_box.content_area();
+        let (x_range, _) = self.viewport.get_logical_ranges();
+        let mut text_with_wrap = text.clone();
+        text_with_wrap.config.max_width = Some(if content_width <= 1.0 {
+            content_width * x_range
+        } else {
+            content_width
+        });
+        if text_with_wrap.config.wrap == TextWrap::None {
+            text_with_wrap.config.wrap = TextWrap::Word; // Default to word wrap when box is specified
+        }
+        self.process_text_wrapping(&text_with_wrap, font)
+    }
+
     /// Process text with wrapping based on configuration
     fn process_text_wrapping(&self, text: &Text, font: &FontInfo) -> String {
         match text.config.wrap {
@@ -1000,24 +2212,39 @@ impl TextRenderer {
 
     /// Calculate the width of a single character
     fn calculate_char_width(&self, ch: char, font: &FontInfo, scale_factor: f32) -> f32 {
-        font.glyphs
-            .get(&ch)
+        self.lookup_glyph(font, ch)
             .map(|glyph| glyph.advance * scale_factor)
             .unwrap_or(0.0)
     }
 
     /// Calculate the width of a word
     fn calculate_word_width(&self, word: &str, font: &FontInfo, scale_factor: f32) -> f32 {
+        let kerning: f32 = word
+            .chars()
+            .zip(word.chars().skip(1))
+            .map(|(left, right)| self.kerning(font, Some(left), right) * scale_factor)
+            .sum();
         word.chars()
             .map(|ch| self.calculate_char_width(ch, font, scale_factor))
-            .sum()
+            .sum::<f32>()
+            + kerning
     }
 
     /// Create the text shader
     fn create_text_shader(gl: &GlWrapper) -> Result<u32, String> {
-        let vertex_source = include_str!("shaders/text.vert");
-        let fragment_source = include_str!("shaders/text.frag");
+        Self::create_program(
+            gl,
+            include_str!("shaders/text.vert"),
+            include_str!("shaders/text.frag"),
+        )
+    }
 
+    /// Compile and link a shader program from vertex and fragment sources
+    fn create_program(
+        gl: &GlWrapper,
+        vertex_source: &str,
+        fragment_source: &str,
+    ) -> Result<u32, String> {
         let vertex_shader = gl.create_shader(gl::VERTEX_SHADER)?;
         gl.set_shader_source(vertex_shader, vertex_source)?;
         gl.compile_shader(vertex_shader)?;
@@ -1038,6 +2265,23 @@ impl TextRenderer {
         Ok(program)
     }
 
+    /// Create the VAO and VBO for popup batches, with position, uv and color per vertex
+    fn create_popup_buffers(gl: &GlWrapper) -> Result<(u32, u32), String> {
+        let vao = gl.gen_vertex_array()?;
+        let vbo = gl.gen_buffer()?;
+        gl.bind_vertex_array(vao)?;
+        gl.bind_buffer(gl::ARRAY_BUFFER, vbo)?;
+        let stride = (POPUP_VERTEX_FLOATS * 4) as i32;
+        gl.set_vertex_attrib_pointer(0, 2, gl::FLOAT, false, stride, 0)?;
+        gl.enable_vertex_attrib_array(0)?;
+        gl.set_vertex_attrib_pointer(1, 2, gl::FLOAT, false, stride, 2 * 4)?;
+        gl.enable_vertex_attrib_array(1)?;
+        gl.set_vertex_attrib_pointer(2, 4, gl::FLOAT, false, stride, 4 * 4)?;
+        gl.enable_vertex_attrib_array(2)?;
+        gl.bind_vertex_array(0)?;
+        Ok((vao, vbo))
+    }
+
     /// Create the text geometry (quad with texture coordinates)
     fn create_text_geometry(gl: &GlWrapper) -> Result<(u32, u32), String> {
         // Quad vertices with texture coordinates
@@ -1074,6 +2318,93 @@ impl TextRenderer {
         self.fonts.get(name)
     }
 
+    /// Logical x offset of every caret slot in single-line text, from before the first
+    /// character to after the last
+    pub fn caret_offsets(&self, text: &str, font_name: &str) -> Result<Vec<f32>, String> {
+        let font = self
+            .fonts
+            .get(font_name)
+            .ok_or_else(|| format!("Font '{}' not found", font_name))?;
+        let scale_factor = self.viewport.calculate_scale_factor(font.size as f32);
+
+        let mut offsets = Vec::with_capacity(text.len() + 1);
+        let mut x = 0.0;
+        let mut previous = None;
+        offsets.push(x);
+        for ch in text.chars() {
+            x += self.kerning(font, previous, ch) * scale_factor;
+            previous = Some(ch);
+            if let Some(glyph) = self.lookup_glyph(font, ch) {
+                x += glyph.advance * scale_factor;
+            }
+            offsets.push(x);
+        }
+        Ok(offsets)
+    }
+
+    /// Size of text laid out with `config`, in logical units, without rendering it
+    ///
+    /// Wrapping follows `config` exactly as rendering does. The width is that of the widest
+    /// line and the height is the line count times `line_height`.
+    pub fn measure(
+        &self,
+        content: &str,
+        font_name: &str,
+        config: &TextConfig,
+    ) -> Result<Vec2, String> {
+        let (font, wrapped) = self.wrap_for_measure(content, font_name, config)?;
+        let width = wrapped
+            .split('\n')
+            .map(|line| self.calculate_text_width(line, font))
+            .fold(0.0, f32::max);
+        let line_count = wrapped.split('\n').count();
+        let height = line_count as f32 * self.line_height_of(font, config);
+        Ok(Vec2::new(width, height))
+    }
+
+    /// Number of lines text takes up once wrapped with `config`
+    pub fn line_count(
+        &self,
+        content: &str,
+        font_name: &str,
+        config: &TextConfig,
+    ) -> Result<usize, String> {
+        let (_, wrapped) = self.wrap_for_measure(content, font_name, config)?;
+        Ok(wrapped.split('\n').count())
+    }
+
+    /// Distance between baselines of consecutive lines, in logical units
+    pub fn line_height(&self, font_name: &str, config: &TextConfig) -> Result<f32, String> {
+        let font = self
+            .fonts
+            .get(font_name)
+            .ok_or_else(|| format!("Font '{}' not found", font_name))?;
+        Ok(self.line_height_of(font, config))
+    }
+
+    fn line_height_of(&self, font: &FontInfo, config: &TextConfig) -> f32 {
+        let scale_factor = self.viewport.calculate_scale_factor(font.size as f32);
+        font.line_height * config.line_spacing * scale_factor
+    }
+
+    fn wrap_for_measure(
+        &self,
+        content: &str,
+        font_name: &str,
+        config: &TextConfig,
+    ) -> Result<(&FontInfo, String), String> {
+        let font = self
+            .fonts
+            .get(font_name)
+            .ok_or_else(|| format!("Font '{}' not found", font_name))?;
+        let content = match self.pseudo_locale {
+            Some(pseudo_locale) => pseudo_locale.apply(content),
+            None => content.to_string(),
+        };
+        let text = Text::with_config(content, Vec2::ZERO, font_name.to_string(), config.clone());
+        Ok((font, self.wrapped_content(&text, font)))
+    }
+
     /// Check if a font is loaded
     pub fn has_font(&self, name: &str) -> bool {
         self.fonts.contains_key(name)
@@ -1096,5 +2427,26 @@ impl Drop for TextRenderer {
         if let Some(vbo) = self.text_vbo.take() {
             let _ = self.gl.delete_buffer(vbo);
         }
+        if let Some(shader) = self.popup_shader.take() {
+            let _ = self.gl.delete_program(shader);
+        }
+        if let Some((vao, vbo)) = self.popup_buffers.take() {
+            let _ = self.gl.delete_vertex_array(vao);
+            let _ = self.gl.delete_buffer(vbo);
+        }
     }
 }
+
+/// Alignment as drawn: left and right swap for right-to-left text, whose start is its right
+fn start_relative_align(align: TextAlign, direction: TextDirection) -> TextAlign {
+    match (align, direction) {
+        (TextAlign::Left, TextDirection::RightToLeft) => TextAlign::Right,
+        (TextAlign::Right, TextDirection::RightToLeft) => TextAlign::Left,
+        (align, _) => align,
+    }
+}
+
+/// Characters that never get a glyph: controls, joiners and variation selectors
+fn is_invisible(ch: char) -> bool {
+    ch.is_control() || matches!(ch, '\u{200B}'..='\u{200D}' | '\u{FE00}'..='\u{FE0F}')
+}
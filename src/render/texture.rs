@@ -1,4 +1,5 @@
 use super::gl_wrapper::GlWrapper;
+use crate::physics::collision::CollisionPolygon;
 use image::{ImageBuffer, RgbaImage};
 use std::collections::HashMap;
 use std::path::Path;
@@ -14,12 +15,138 @@ pub struct TextureInfo {
     pub id: TextureId,
     pub width: u32,
     pub height: u32,
+    /// Fragments with alpha below this value are discarded when drawn (0.0 disables)
+    pub alpha_cutoff: f32,
+    pub filter: TextureFilter,
+    pub wrap: TextureWrap,
+    /// Whether the texture has a mipmap chain
+    pub mipmapped: bool,
+    /// Convex hull of the opaque pixels, generated on import when requested
+    pub collision_shape: Option<CollisionPolygon>,
+}
+
+/// Texture filtering used when a texture is scaled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureFilter {
+    /// Crisp nearest-neighbor sampling for pixel art
+    Nearest,
+    /// Smooth bilinear sampling
+    #[default]
+    Linear,
+}
+
+impl TextureFilter {
+    fn gl_value(self) -> i32 {
+        match self {
+            TextureFilter::Nearest => 0x2600, // GL_NEAREST
+            TextureFilter::Linear => 0x2601,  // GL_LINEAR
+        }
+    }
+
+    fn gl_mipmap_value(self) -> i32 {
+        match self {
+            TextureFilter::Nearest => 0x2700, // GL_NEAREST_MIPMAP_NEAREST
+            TextureFilter::Linear => 0x2703,  // GL_LINEAR_MIPMAP_LINEAR
+        }
+    }
+}
+
+/// Texture wrap mode for coordinates outside [0, 1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureWrap {
+    ClampToEdge,
+    #[default]
+    Repeat,
+    MirroredRepeat,
+}
+
+impl TextureWrap {
+    fn gl_value(self) -> i32 {
+        match self {
+            TextureWrap::ClampToEdge => 0x812F,    // GL_CLAMP_TO_EDGE
+            TextureWrap::Repeat => 0x2901,         // GL_REPEAT
+            TextureWrap::MirroredRepeat => 0x8370, // GL_MIRRORED_REPEAT
+        }
+    }
+}
+
+/// Options applied when importing a texture from an image file
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TextureImportOptions {
+    /// RGB color made fully transparent on import (legacy color-key assets)
+    pub color_key: Option<(u8, u8, u8)>,
+    /// Alpha-test cutoff used by the sprite shader (0.0 disables)
+    pub alpha_cutoff: f32,
+    pub filter: TextureFilter,
+    pub wrap: TextureWrap,
+    /// Generate mipmaps on upload for better quality when zoomed out
+    pub generate_mipmaps: bool,
+    /// Alpha (0-255) at which pixels count as solid for the generated collision shape
+    pub collision_alpha: Option<u8>,
+    /// Keep the data linear even when sRGB decoding is on, for normal maps and other
+    /// non-color textures
+    pub linear: bool,
+}
+
+impl TextureImportOptions {
+    /// Treat the given RGB color as transparent
+    pub fn with_color_key(mut self, r: u8, g: u8, b: u8) -> Self {
+        self.color_key = Some((r, g, b));
+        self
+    }
+
+    /// Discard fragments with alpha below the cutoff for crisp pixel-art edges
+    pub fn with_alpha_cutoff(mut self, cutoff: f32) -> Self {
+        self.alpha_cutoff = cutoff.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the filtering mode
+    pub fn with_filter(mut self, filter: TextureFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Set the wrap mode for both axes
+    pub fn with_wrap(mut self, wrap: TextureWrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Generate mipmaps on upload
+    pub fn with_mipmaps(mut self, generate_mipmaps: bool) -> Self {
+        self.generate_mipmaps = generate_mipmaps;
+        self
+    }
+
+    /// Generate a collision shape from pixels with alpha at or above the threshold
+    pub fn with_collision_shape(mut self, alpha_threshold: u8) -> Self {
+        self.collision_alpha = Some(alpha_threshold);
+        self
+    }
+
+    /// Never decode this texture as sRGB
+    pub fn with_linear(mut self, linear: bool) -> Self {
+        self.linear = linear;
+        self
+    }
+}
+
+/// Make every pixel matching the key color fully transparent
+pub fn apply_color_key(img: &mut RgbaImage, key: (u8, u8, u8)) {
+    for pixel in img.pixels_mut() {
+        if pixel[0] == key.0 && pixel[1] == key.1 && pixel[2] == key.2 {
+            pixel[3] = 0;
+        }
+    }
 }
 
 /// Texture manager that handles loading and managing textures
 pub struct TextureManager {
     gl: Rc<GlWrapper>,
     textures: HashMap<String, TextureInfo>,
+    anisotropy: f32,
+    srgb: bool,
 }
 
 impl TextureManager {
@@ -28,11 +155,52 @@ impl TextureManager {
         Self {
             gl,
             textures: HashMap::new(),
+            anisotropy: 1.0,
+            srgb: false,
         }
     }
 
+    /// Set the anisotropic filtering level applied to mipmapped textures created afterwards
+    ///
+    /// 1.0 disables anisotropic filtering; values are clamped to the driver maximum.
+    pub fn set_anisotropy(&mut self, level: f32) {
+        self.anisotropy = level.max(1.0);
+    }
+
+    /// Get the anisotropic filtering level
+    pub fn anisotropy(&self) -> f32 {
+        self.anisotropy
+    }
+
+    /// Decode color textures created afterwards as sRGB, for use with an sRGB framebuffer
+    pub fn set_srgb(&mut self, srgb: bool) {
+        self.srgb = srgb;
+    }
+
+    /// Check if color textures are decoded as sRGB
+    pub fn srgb(&self) -> bool {
+        self.srgb
+    }
+
+    /// Maximum anisotropy supported by the driver (0.0 when unsupported)
+    pub fn max_supported_anisotropy(&self) -> f32 {
+        // GL_MAX_TEXTURE_MAX_ANISOTROPY
+        self.gl.get_float(0x84FF).unwrap_or(0.0)
+    }
+
     /// Load a texture from a file path
     pub fn load_texture(&mut self, path: &str) -> Result<TextureId, String> {
+        self.load_texture_with_options(path, TextureImportOptions::default())
+    }
+
+    /// Load a texture from a file path, applying import options
+    ///
+    /// Options only apply on first load; a path that is already loaded returns the cached texture.
+    pub fn load_texture_with_options(
+        &mut self,
+        path: &str,
+        options: TextureImportOptions,
+    ) -> Result<TextureId, String> {
         // Check if texture is already loaded
         if let Some(texture_info) = self.textures.get(path) {
             return Ok(texture_info.id);
@@ -43,16 +211,41 @@ impl TextureManager {
             .map_err(|e| format!("Failed to load image '{}': {}", path, e))?;
 
         // Convert to RGBA format
-        let rgba_img = img.to_rgba8();
+        let mut rgba_img = img.to_rgba8();
         let (width, height) = rgba_img.dimensions();
 
+        if let Some(key) = options.color_key {
+            apply_color_key(&mut rgba_img, key);
+        }
+
+        // Hull the opaque pixels once here so hit tests never read texture data back
+        let collision_shape = options.collision_alpha.and_then(|alpha_threshold| {
+            CollisionPolygon::from_alpha(rgba_img.as_raw(), width, height, alpha_threshold)
+        });
+
         // Generate OpenGL texture
-        let texture_id = self.create_texture_from_image(&rgba_img)?;
+        let texture_id = self.upload_image(&rgba_img, options.linear)?;
+        if options.generate_mipmaps {
+            self.gl.bind_texture(0x0DE1, texture_id)?; // GL_TEXTURE_2D
+            self.gl.generate_mipmap(0x0DE1)?;
+            self.gl.bind_texture(0x0DE1, 0)?;
+        }
+        self.apply_sampling(
+            texture_id,
+            options.filter,
+            options.wrap,
+            options.generate_mipmaps,
+        )?;
 
         let texture_info = TextureInfo {
             id: TextureId(texture_id),
             width,
             height,
+            alpha_cutoff: options.alpha_cutoff,
+            filter: options.filter,
+            wrap: options.wrap,
+            mipmapped: options.generate_mipmaps,
+            collision_shape,
         };
 
         // Store texture info
@@ -63,7 +256,16 @@ impl TextureManager {
 
     /// Create a texture from image data
     pub fn create_texture_from_image(&mut self, img: &RgbaImage) -> Result<u32, String> {
+        self.upload_image(img, false)
+    }
+
+    fn upload_image(&mut self, img: &RgbaImage, linear: bool) -> Result<u32, String> {
         let (width, height) = img.dimensions();
+        let internal_format = if self.srgb && !linear {
+            0x8C43 // GL_SRGB8_ALPHA8
+        } else {
+            0x1908 // GL_RGBA
+        };
 
         // Generate OpenGL texture ID
         let texture_id = self.gl.gen_texture()?;
@@ -79,7 +281,7 @@ impl TextureManager {
         self.gl.tex_image_2d(
             0x0DE1, // GL_TEXTURE_2D
             0,      // level
-            0x1908, // GL_RGBA
+            internal_format,
             width as i32,
             height as i32,
             0,      // border
@@ -134,6 +336,11 @@ impl TextureManager {
             id: TextureId(texture_id),
             width,
             height,
+            alpha_cutoff: 0.0,
+            filter: TextureFilter::Linear,
+            wrap: TextureWrap::ClampToEdge,
+            mipmapped: true,
+            collision_shape: None,
         };
 
         // Store with a unique name
@@ -143,6 +350,80 @@ impl TextureManager {
         Ok(texture_info.id)
     }
 
+    /// Create a fully transparent RGBA texture to be filled in with `update_texture_region`
+    ///
+    /// Uses linear filtering without mipmaps so packed sub-images don't bleed together.
+    pub fn create_blank_texture(&mut self, width: u32, height: u32) -> Result<TextureId, String> {
+        let texture_id = self.gl.gen_texture()?;
+        self.gl.bind_texture(0x0DE1, texture_id)?; // GL_TEXTURE_2D
+        self.gl.pixel_store_i(0x0CF5, 1)?; // GL_UNPACK_ALIGNMENT, 1
+
+        let pixels = vec![0u8; (width * height * 4) as usize];
+        self.gl.tex_image_2d(
+            0x0DE1,
+            0,
+            0x1908, // GL_TEXTURE_2D, level, GL_RGBA
+            width as i32,
+            height as i32,
+            0,
+            0x1908,
+            0x1401, // GL_RGBA, GL_UNSIGNED_BYTE
+            Some(&pixels),
+        )?;
+
+        let texture_info = TextureInfo {
+            id: TextureId(texture_id),
+            width,
+            height,
+            alpha_cutoff: 0.0,
+            filter: TextureFilter::Linear,
+            wrap: TextureWrap::ClampToEdge,
+            mipmapped: false,
+            collision_shape: None,
+        };
+        self.apply_sampling(texture_id, texture_info.filter, texture_info.wrap, false)?;
+
+        let name = format!("blank_texture_{}", texture_id);
+        self.textures.insert(name, texture_info.clone());
+
+        Ok(texture_info.id)
+    }
+
+    /// Replace a rectangle of an RGBA texture with new pixel data
+    pub fn update_texture_region(
+        &self,
+        texture_id: TextureId,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Result<(), String> {
+        if data.len() != (width * height * 4) as usize {
+            return Err(format!(
+                "Texture region data has {} bytes, expected {} for {}x{} RGBA",
+                data.len(),
+                width * height * 4,
+                width,
+                height
+            ));
+        }
+
+        self.gl.bind_texture(0x0DE1, texture_id.0)?; // GL_TEXTURE_2D
+        self.gl.pixel_store_i(0x0CF5, 1)?; // GL_UNPACK_ALIGNMENT, 1
+        self.gl.tex_sub_image_2d(
+            0x0DE1,
+            0,
+            x as i32,
+            y as i32,
+            width as i32,
+            height as i32,
+            0x1908,
+            0x1401, // GL_RGBA, GL_UNSIGNED_BYTE
+            data,
+        )
+    }
+
     /// Create a solid color texture
     pub fn create_color_texture(
         &mut self,
@@ -170,6 +451,11 @@ impl TextureManager {
             id: TextureId(texture_id),
             width,
             height,
+            alpha_cutoff: 0.0,
+            filter: TextureFilter::Linear,
+            wrap: TextureWrap::Repeat,
+            mipmapped: false,
+            collision_shape: None,
         };
 
         // Store with a unique name
@@ -184,6 +470,73 @@ impl TextureManager {
         self.textures.values().find(|info| info.id == texture_id)
     }
 
+    /// Get the collision shape generated when the texture was imported
+    pub fn collision_shape(&self, texture_id: TextureId) -> Option<&CollisionPolygon> {
+        self.get_texture_info(texture_id)?.collision_shape.as_ref()
+    }
+
+    /// Set the alpha-test cutoff used when drawing a texture (0.0 disables)
+    pub fn set_alpha_cutoff(&mut self, texture_id: TextureId, cutoff: f32) -> Result<(), String> {
+        let info = self
+            .textures
+            .values_mut()
+            .find(|info| info.id == texture_id)
+            .ok_or_else(|| format!("Texture {:?} not found", texture_id))?;
+        info.alpha_cutoff = cutoff.clamp(0.0, 1.0);
+        Ok(())
+    }
+
+    /// Change filtering and wrap modes of a loaded texture
+    pub fn set_sampling(
+        &mut self,
+        texture_id: TextureId,
+        filter: TextureFilter,
+        wrap: TextureWrap,
+    ) -> Result<(), String> {
+        let mipmapped = self
+            .get_texture_info(texture_id)
+            .ok_or_else(|| format!("Texture {:?} not found", texture_id))?
+            .mipmapped;
+        self.apply_sampling(texture_id.0, filter, wrap, mipmapped)?;
+        if let Some(info) = self
+            .textures
+            .values_mut()
+            .find(|info| info.id == texture_id)
+        {
+            info.filter = filter;
+            info.wrap = wrap;
+        }
+        Ok(())
+    }
+
+    /// Set filter, wrap and anisotropy parameters on a GL texture
+    fn apply_sampling(
+        &self,
+        texture_id: u32,
+        filter: TextureFilter,
+        wrap: TextureWrap,
+        mipmapped: bool,
+    ) -> Result<(), String> {
+        let min_filter = if mipmapped {
+            filter.gl_mipmap_value()
+        } else {
+            filter.gl_value()
+        };
+        self.gl.bind_texture(0x0DE1, texture_id)?; // GL_TEXTURE_2D
+        self.gl.tex_parameter_i(0x0DE1, 0x2800, min_filter)?; // GL_TEXTURE_MIN_FILTER
+        self.gl.tex_parameter_i(0x0DE1, 0x2801, filter.gl_value())?; // GL_TEXTURE_MAG_FILTER
+        self.gl.tex_parameter_i(0x0DE1, 0x2802, wrap.gl_value())?; // GL_TEXTURE_WRAP_S
+        self.gl.tex_parameter_i(0x0DE1, 0x2803, wrap.gl_value())?; // GL_TEXTURE_WRAP_T
+        if mipmapped && self.anisotropy > 1.0 {
+            let level = self.anisotropy.min(self.max_supported_anisotropy());
+            if level > 1.0 {
+                self.gl.tex_parameter_f(0x0DE1, 0x84FE, level)?; // GL_TEXTURE_MAX_ANISOTROPY
+            }
+        }
+        self.gl.bind_texture(0x0DE1, 0)?;
+        Ok(())
+    }
+
     /// Get texture information by path
     pub fn get_texture_info_by_path(&self, path: &str) -> Option<&TextureInfo> {
         self.textures.get(path)
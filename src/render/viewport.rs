@@ -22,6 +22,141 @@ impl std::fmt::Display for ValidationError {
 
 impl std::error::Error for ValidationError {}
 
+/// How the view is fitted to a window whose shape differs from the configured size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScalingPolicy {
+    /// Fill the window, stretching the logical coordinates if its aspect ratio changes
+    #[default]
+    Stretch,
+    /// Scale uniformly to fit inside the window, leaving bars on two sides
+    Fit,
+    /// Scale uniformly to cover the window, cropping what overflows it
+    Fill,
+    /// Fit at whole multiples of the configured size, for crisp pixel art
+    IntegerScale,
+}
+
+impl ScalingPolicy {
+    /// Framebuffer region to render to, for a view designed at `design_size` pixels
+    pub fn viewport_rect(
+        self,
+        design_size: (u32, u32),
+        framebuffer_size: (u32, u32),
+    ) -> ViewportRect {
+        let design = Vec2::new(design_size.0.max(1) as f32, design_size.1.max(1) as f32);
+        let framebuffer = Vec2::new(framebuffer_size.0 as f32, framebuffer_size.1 as f32);
+        let ratio = framebuffer / design;
+        let size = match self {
+            ScalingPolicy::Stretch => framebuffer,
+            ScalingPolicy::Fit => design * ratio.min_element(),
+            ScalingPolicy::Fill => design * ratio.max_element(),
+            ScalingPolicy::IntegerScale => design * ratio.min_element().floor().max(1.0),
+        }
+        .round();
+
+        // Center the view; offsets are negative where Fill crops
+        let offset = ((framebuffer - size) / 2.0).floor();
+        ViewportRect {
+            x: offset.x as i32,
+            y: offset.y as i32,
+            width: size.x as i32,
+            height: size.y as i32,
+        }
+    }
+}
+
+/// Region of the framebuffer that is rendered to, in pixels from its bottom-left corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewportRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl ViewportRect {
+    /// Map a framebuffer pixel (top-left origin, like cursor positions) to 0..1 across the
+    /// view, with y down; None if it falls on a letterbox bar
+    pub fn to_normalized(&self, x: f32, y: f32, framebuffer_height: u32) -> Option<Vec2> {
+        let from_bottom = framebuffer_height as f32 - y;
+        let normalized = Vec2::new(
+            (x - self.x as f32) / self.width as f32,
+            1.0 - (from_bottom - self.y as f32) / self.height as f32,
+        );
+        (normalized.cmpge(Vec2::ZERO).all() && normalized.cmple(Vec2::ONE).all())
+            .then_some(normalized)
+    }
+
+    /// The pixels covered by a rect given in 0..1 across the view with y down, like
+    /// `SimpleTextRenderer` positions, e.g. for a scissor box
+    pub fn sub_rect(&self, min: Vec2, size: Vec2) -> ViewportRect {
+        let (width, height) = (self.width as f32, self.height as f32);
+        let left = (self.x as f32 + min.x * width).round() as i32;
+        let right = (self.x as f32 + (min.x + size.x) * width).round() as i32;
+        let bottom = (self.y as f32 + (1.0 - min.y - size.y) * height).round() as i32;
+        let top = (self.y as f32 + (1.0 - min.y) * height).round() as i32;
+        ViewportRect {
+            x: left,
+            y: bottom,
+            width: (right - left).max(0),
+            height: (top - bottom).max(0),
+        }
+    }
+
+    /// The overlap of two rects, empty (zero-sized) if they don't overlap
+    pub fn intersect(&self, other: &ViewportRect) -> ViewportRect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width).min(other.x + other.width);
+        let top = (self.y + self.height).min(other.y + other.height);
+        ViewportRect {
+            x,
+            y,
+            width: (right - x).max(0),
+            height: (top - y).max(0),
+        }
+    }
+}
+
+/// Where the view sits in the window, for mapping cursor positions onto it
+///
+/// The engine rebuilds this each frame, so it follows resizes and fullscreen switches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewMapping {
+    /// Window size in screen coordinates, the units cursor positions are reported in
+    pub window_size: (u32, u32),
+    /// Framebuffer size in pixels, larger than the window on high-DPI displays
+    pub framebuffer_size: (u32, u32),
+    pub viewport_rect: ViewportRect,
+    /// Logical coordinate bounds (x_min, x_max, y_min, y_max)
+    pub logical_bounds: (f32, f32, f32, f32),
+}
+
+impl ViewMapping {
+    /// A cursor position (top-left origin, screen coordinates) as 0..1 across the view with
+    /// y down; None over a letterbox bar or outside the window
+    pub fn cursor_to_normalized(&self, cursor: Vec2) -> Option<Vec2> {
+        let window = Vec2::new(self.window_size.0 as f32, self.window_size.1 as f32);
+        let framebuffer = Vec2::new(
+            self.framebuffer_size.0 as f32,
+            self.framebuffer_size.1 as f32,
+        );
+        let pixel = cursor * framebuffer / window.max(Vec2::ONE);
+        self.viewport_rect
+            .to_normalized(pixel.x, pixel.y, self.framebuffer_size.1)
+    }
+
+    /// A cursor position in logical coordinates, y up
+    pub fn cursor_to_logical(&self, cursor: Vec2) -> Option<Vec2> {
+        let (x_min, x_max, y_min, y_max) = self.logical_bounds;
+        let normalized = self.cursor_to_normalized(cursor)?;
+        Some(Vec2::new(
+            x_min + normalized.x * (x_max - x_min),
+            y_max - normalized.y * (y_max - y_min),
+        ))
+    }
+}
+
 /// Viewport defines the logical coordinate system for rendering
 /// All rendering coordinates are specified in this logical space, and the viewport
 /// handles conversion to OpenGL's NDC space automatically
@@ -0,0 +1,7 @@
+pub mod quick_ui;
+pub mod safe_area;
+pub mod text_field;
+
+pub use quick_ui::{QuickDraw, QuickInput, QuickTheme, QuickUi};
+pub use safe_area::SafeArea;
+pub use text_field::{Clipboard, TextField, TextFieldStyle};
@@ -0,0 +1,273 @@
+use glam::Vec2;
+use std::ops::RangeInclusive;
+
+/// Colors and metrics shared by every quick UI widget
+///
+/// Sizes are normalized screen units with the origin at the top-left, like
+/// `SimpleTextRenderer` positions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuickTheme {
+    pub panel_color: (f32, f32, f32),
+    pub title_color: (f32, f32, f32),
+    pub widget_color: (f32, f32, f32),
+    pub hover_color: (f32, f32, f32),
+    pub active_color: (f32, f32, f32),
+    pub text_color: (f32, f32, f32),
+    /// Color of the filled part of sliders
+    pub accent_color: (f32, f32, f32),
+    /// Border drawn around panels and widgets
+    pub border_color: (f32, f32, f32),
+    pub border_width: f32,
+    pub row_height: f32,
+    pub padding: f32,
+    /// Where the label baseline sits within a row, as a fraction of the row height
+    pub baseline: f32,
+}
+
+impl Default for QuickTheme {
+    fn default() -> Self {
+        Self {
+            panel_color: (0.1, 0.1, 0.12),
+            title_color: (0.2, 0.25, 0.35),
+            widget_color: (0.22, 0.22, 0.26),
+            hover_color: (0.3, 0.3, 0.36),
+            active_color: (0.15, 0.15, 0.18),
+            text_color: (0.95, 0.95, 0.95),
+            accent_color: (0.3, 0.55, 0.9),
+            border_color: (0.4, 0.4, 0.45),
+            border_width: 0.002,
+            row_height: 0.045,
+            padding: 0.01,
+            baseline: 0.7,
+        }
+    }
+}
+
+/// Pointer state for one frame, in normalized top-left coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct QuickInput {
+    pub pointer: Vec2,
+    /// The pointer button is held
+    pub down: bool,
+    /// The pointer button went down this frame
+    pub pressed: bool,
+    /// The pointer button went up this frame
+    pub released: bool,
+}
+
+/// Something for the renderer to draw, in submission order
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuickDraw {
+    /// A bordered rectangle
+    Rect {
+        min: Vec2,
+        size: Vec2,
+        color: (f32, f32, f32),
+        border: bool,
+    },
+    /// Text with its baseline at `position`
+    Text {
+        text: String,
+        position: Vec2,
+        color: (f32, f32, f32),
+    },
+}
+
+/// Immediate-mode buttons, panels and sliders for debug menus and prototypes
+///
+/// Call `begin` with the frame's input, then one method per widget; widgets stack
+/// vertically inside the current panel. Draw the result with
+/// `SimpleTextRenderer::draw_quick_ui`.
+///
+/// ```ignore
+/// ui.begin(input);
+/// ui.panel("Debug", Vec2::new(0.02, 0.02), 0.3);
+/// ui.button("Respawn", || player.respawn());
+/// ui.slider("Speed", &mut speed, 0.0..=10.0);
+/// renderer.draw_quick_ui(&ui, engine.renderer(), "default")?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QuickUi {
+    pub theme: QuickTheme,
+    input: QuickInput,
+    draws: Vec<QuickDraw>,
+    /// Left edge, width and next row of the current panel
+    origin: Vec2,
+    width: f32,
+    cursor: f32,
+    /// Index of the panel's background in `draws`, resized as widgets are added
+    panel: Option<usize>,
+    /// Widgets are identified by their order within the frame
+    next_id: usize,
+    /// Widget the pointer went down on, held across frames until released
+    active: Option<usize>,
+}
+
+impl QuickUi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_theme(mut self, theme: QuickTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Start a frame, discarding the previous frame's widgets
+    pub fn begin(&mut self, input: QuickInput) {
+        // Keep the active widget through the release frame so buttons can see the click
+        if !input.down && !input.released {
+            self.active = None;
+        }
+        self.input = input;
+        self.draws.clear();
+        self.panel = None;
+        self.next_id = 0;
+        self.origin = Vec2::new(self.theme.padding, self.theme.padding);
+        self.width = 0.3;
+        self.cursor = self.origin.y;
+    }
+
+    /// Start a titled panel at a top-left position; following widgets go inside it
+    pub fn panel(&mut self, title: &str, position: Vec2, width: f32) {
+        let theme = self.theme;
+        self.panel = Some(self.draws.len());
+        self.push_rect(
+            position,
+            Vec2::new(width, theme.row_height),
+            theme.panel_color,
+        );
+        self.push_rect(
+            position,
+            Vec2::new(width, theme.row_height),
+            theme.title_color,
+        );
+        self.push_label(title, position.x + theme.padding, position.y);
+
+        self.origin = Vec2::new(position.x + theme.padding, position.y);
+        self.width = width - theme.padding * 2.0;
+        self.cursor = position.y + theme.row_height + theme.padding;
+    }
+
+    /// A button with a label; calls `on_click` and returns true when clicked
+    pub fn button(&mut self, label: &str, on_click: impl FnOnce()) -> bool {
+        let id = self.next_id();
+        let (min, size) = self.row();
+        let hovered = self.hovered(min, size);
+        if hovered && self.input.pressed {
+            self.active = Some(id);
+        }
+        let clicked = hovered && self.input.released && self.active == Some(id);
+
+        let color = match (self.active == Some(id), hovered) {
+            (true, _) => self.theme.active_color,
+            (false, true) => self.theme.hover_color,
+            (false, false) => self.theme.widget_color,
+        };
+        self.push_rect(min, size, color);
+        self.push_label(label, min.x + self.theme.padding, min.y);
+
+        if clicked {
+            on_click();
+        }
+        clicked
+    }
+
+    /// A slider labeled with its name and value; returns true when `value` changed
+    ///
+    /// Dragging sets the value from the pointer's position along the slider.
+    pub fn slider(&mut self, label: &str, value: &mut f32, range: RangeInclusive<f32>) -> bool {
+        let id = self.next_id();
+        let (min, size) = self.row();
+        let (low, high) = (*range.start(), *range.end());
+        if self.hovered(min, size) && self.input.pressed {
+            self.active = Some(id);
+        }
+
+        let previous = *value;
+        if self.active == Some(id) && self.input.down {
+            let t = ((self.input.pointer.x - min.x) / size.x).clamp(0.0, 1.0);
+            *value = low + (high - low) * t;
+        }
+        *value = value.clamp(low.min(high), high.max(low));
+
+        let t = if high != low {
+            (*value - low) / (high - low)
+        } else {
+            0.0
+        };
+        self.push_rect(min, size, self.theme.widget_color);
+        self.draws.push(QuickDraw::Rect {
+            min,
+            size: Vec2::new(size.x * t, size.y),
+            color: self.theme.accent_color,
+            border: false,
+        });
+        self.push_label(
+            &format!("{label}: {value:.2}"),
+            min.x + self.theme.padding,
+            min.y,
+        );
+        *value != previous
+    }
+
+    /// A line of text
+    pub fn label(&mut self, text: &str) {
+        let (min, _) = self.row();
+        self.push_label(text, min.x, min.y);
+    }
+
+    /// Check if the pointer is over any widget or panel drawn this frame
+    pub fn wants_pointer(&self) -> bool {
+        self.active.is_some()
+            || self.draws.iter().any(|draw| match draw {
+                QuickDraw::Rect { min, size, .. } => self.hovered(*min, *size),
+                QuickDraw::Text { .. } => false,
+            })
+    }
+
+    /// What to draw this frame, back to front
+    pub fn draws(&self) -> &[QuickDraw] {
+        &self.draws
+    }
+
+    fn next_id(&mut self) -> usize {
+        self.next_id += 1;
+        self.next_id - 1
+    }
+
+    /// Claim the next row, growing the current panel's background to fit
+    fn row(&mut self) -> (Vec2, Vec2) {
+        let theme = self.theme;
+        let min = Vec2::new(self.origin.x, self.cursor);
+        self.cursor += theme.row_height + theme.padding;
+        if let Some(QuickDraw::Rect { min: top, size, .. }) =
+            self.panel.and_then(|index| self.draws.get_mut(index))
+        {
+            size.y = self.cursor - top.y;
+        }
+        (min, Vec2::new(self.width, theme.row_height))
+    }
+
+    fn hovered(&self, min: Vec2, size: Vec2) -> bool {
+        let pointer = self.input.pointer;
+        pointer.cmpge(min).all() && pointer.cmple(min + size).all()
+    }
+
+    fn push_rect(&mut self, min: Vec2, size: Vec2, color: (f32, f32, f32)) {
+        self.draws.push(QuickDraw::Rect {
+            min,
+            size,
+            color,
+            border: true,
+        });
+    }
+
+    fn push_label(&mut self, text: &str, x: f32, row_top: f32) {
+        self.draws.push(QuickDraw::Text {
+            text: text.to_string(),
+            position: Vec2::new(x, row_top + self.theme.row_height * self.theme.baseline),
+            color: self.theme.text_color,
+        });
+    }
+}
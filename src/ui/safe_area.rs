@@ -0,0 +1,65 @@
+use glam::Vec2;
+
+/// Screen margins that anchored UI stays inside, as fractions of the screen (0.05 = 5%)
+///
+/// TVs may crop the edges of the picture (overscan) and stream overlays cover corners, so
+/// HUD elements are laid out within these insets. Positions are normalized with the
+/// origin at the top-left, like `SimpleTextRenderer` positions.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SafeArea {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl SafeArea {
+    /// Largest inset per side, so the area can never collapse
+    pub const MAX_INSET: f32 = 0.25;
+
+    /// Create a safe area from per-side insets, clamped to `0..=MAX_INSET`
+    pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        let clamp = |inset: f32| inset.clamp(0.0, Self::MAX_INSET);
+        Self {
+            left: clamp(left),
+            top: clamp(top),
+            right: clamp(right),
+            bottom: clamp(bottom),
+        }
+    }
+
+    /// The same inset on every side, in percent (5% is the usual TV title-safe margin)
+    pub fn from_percent(percent: f32) -> Self {
+        let inset = percent / 100.0;
+        Self::new(inset, inset, inset, inset)
+    }
+
+    /// Current inset as a percentage, for settings menus (the largest side if they differ)
+    pub fn percent(&self) -> f32 {
+        self.left.max(self.top).max(self.right).max(self.bottom) * 100.0
+    }
+
+    /// Top-left corner of the safe area
+    pub fn min(&self) -> Vec2 {
+        Vec2::new(self.left, self.top)
+    }
+
+    /// Width and height of the safe area
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(1.0 - self.left - self.right, 1.0 - self.top - self.bottom)
+    }
+
+    /// Map a position relative to the safe area (0..1 on both axes) to the screen
+    ///
+    /// Anchoring to (1.0, 0.0), for example, puts an element in the top-right corner of
+    /// the safe area rather than of the screen.
+    pub fn to_screen(&self, position: Vec2) -> Vec2 {
+        self.min() + position * self.size()
+    }
+
+    /// Check if a screen position is inside the safe area
+    pub fn contains(&self, point: Vec2) -> bool {
+        let max = self.min() + self.size();
+        point.cmpge(self.min()).all() && point.cmple(max).all()
+    }
+}
@@ -0,0 +1,391 @@
+use crate::input::text::TextInputEvent;
+use crate::input::types::KeyCode;
+use std::ops::Range;
+
+/// Seconds the caret stays shown, then hidden, while blinking
+const CARET_BLINK_INTERVAL: f32 = 0.5;
+
+/// System clipboard access for cut, copy and paste
+pub trait Clipboard {
+    fn get_text(&mut self) -> Option<String>;
+    fn set_text(&mut self, text: &str);
+}
+
+/// An in-process clipboard, for headless use and tests
+impl Clipboard for String {
+    fn get_text(&mut self) -> Option<String> {
+        (!self.is_empty()).then(|| self.clone())
+    }
+
+    fn set_text(&mut self, text: &str) {
+        *self = text.to_string();
+    }
+}
+
+/// Colors used to draw a text field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextFieldStyle {
+    pub text_color: (f32, f32, f32),
+    pub placeholder_color: (f32, f32, f32),
+    pub selection_color: (f32, f32, f32),
+    pub caret_color: (f32, f32, f32),
+    /// Caret thickness in font pixels
+    pub caret_width: f32,
+}
+
+impl Default for TextFieldStyle {
+    fn default() -> Self {
+        Self {
+            text_color: (1.0, 1.0, 1.0),
+            placeholder_color: (0.5, 0.5, 0.5),
+            selection_color: (0.2, 0.4, 0.8),
+            caret_color: (1.0, 1.0, 1.0),
+            caret_width: 2.0,
+        }
+    }
+}
+
+/// Editable single-line text with a caret and selection
+///
+/// Positions are character indices: the caret sits before the character at its index, so
+/// it ranges from 0 to the character count. Mouse methods take caret offsets, the x
+/// position of every caret slot as measured by the text renderer, which keeps the
+/// editing logic independent of fonts.
+#[derive(Debug, Clone, Default)]
+pub struct TextField {
+    text: String,
+    caret: usize,
+    /// Other end of the selection, if any
+    anchor: Option<usize>,
+    max_length: Option<usize>,
+    placeholder: String,
+    focused: bool,
+    blink_time: f32,
+    pub style: TextFieldStyle,
+}
+
+impl TextField {
+    /// Create an empty field
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with some text, caret at the end
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Limit the number of characters
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        let text = std::mem::take(&mut self.text);
+        self.set_text(&text);
+        self
+    }
+
+    /// Text shown while the field is empty
+    pub fn with_placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = placeholder.to_string();
+        self
+    }
+
+    pub fn with_style(mut self, style: TextFieldStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Replace the text, moving the caret to the end and clearing the selection
+    pub fn set_text(&mut self, text: &str) {
+        self.text.clear();
+        self.caret = 0;
+        self.anchor = None;
+        self.insert_str(text);
+    }
+
+    /// Caret position as a character index
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Selected character range, if anything is selected
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|anchor| *anchor != self.caret)?;
+        Some(anchor.min(self.caret)..anchor.max(self.caret))
+    }
+
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection()
+            .map(|range| &self.text[self.byte_index(range.start)..self.byte_index(range.end)])
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Focus or unfocus the field; unfocusing clears the selection
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if !focused {
+            self.anchor = None;
+        }
+        self.blink_time = 0.0;
+    }
+
+    /// Advance the caret blink (call each frame)
+    pub fn update(&mut self, delta_time: f32) {
+        self.blink_time = (self.blink_time + delta_time) % (CARET_BLINK_INTERVAL * 2.0);
+    }
+
+    /// Check if the caret should be drawn this frame
+    pub fn caret_visible(&self) -> bool {
+        self.focused && self.blink_time < CARET_BLINK_INTERVAL
+    }
+
+    /// Insert text at the caret, replacing the selection
+    ///
+    /// Control characters such as newlines are dropped, and text beyond the maximum
+    /// length is cut off.
+    pub fn insert_str(&mut self, text: &str) {
+        self.delete_selection();
+        let room = self
+            .max_length
+            .map_or(usize::MAX, |max| max.saturating_sub(self.char_count()));
+        let inserted: String = text
+            .chars()
+            .filter(|ch| !ch.is_control())
+            .take(room)
+            .collect();
+        let byte_index = self.byte_index(self.caret);
+        self.text.insert_str(byte_index, &inserted);
+        self.caret += inserted.chars().count();
+        self.blink_time = 0.0;
+    }
+
+    /// Insert a typed character at the caret
+    pub fn insert_char(&mut self, ch: char) {
+        self.insert_str(ch.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Insert committed text from the text input stream; returns whether the field used it
+    ///
+    /// Compositions aren't inserted until the input method commits them.
+    pub fn handle_text_input(&mut self, event: &TextInputEvent) -> bool {
+        match event {
+            TextInputEvent::Text(text) => {
+                self.insert_str(text);
+                true
+            }
+            TextInputEvent::Composition { .. } => false,
+        }
+    }
+
+    /// Delete the selection, or the character before the caret
+    pub fn backspace(&mut self) {
+        if !self.delete_selection() && self.caret > 0 {
+            self.remove_range(self.caret - 1..self.caret);
+        }
+    }
+
+    /// Delete the selection, or the character after the caret
+    pub fn delete(&mut self) {
+        if !self.delete_selection() && self.caret < self.char_count() {
+            self.remove_range(self.caret..self.caret + 1);
+        }
+    }
+
+    /// Move the caret one character left; `extend` grows the selection instead of clearing it
+    pub fn move_left(&mut self, extend: bool) {
+        let target = match self.selection() {
+            Some(range) if !extend => range.start,
+            _ => self.caret.saturating_sub(1),
+        };
+        self.move_caret(target, extend);
+    }
+
+    /// Move the caret one character right
+    pub fn move_right(&mut self, extend: bool) {
+        let target = match self.selection() {
+            Some(range) if !extend => range.end,
+            _ => (self.caret + 1).min(self.char_count()),
+        };
+        self.move_caret(target, extend);
+    }
+
+    /// Move the caret to the start of the previous word
+    pub fn move_word_left(&mut self, extend: bool) {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut target = self.caret;
+        while target > 0 && chars[target - 1].is_whitespace() {
+            target -= 1;
+        }
+        while target > 0 && !chars[target - 1].is_whitespace() {
+            target -= 1;
+        }
+        self.move_caret(target, extend);
+    }
+
+    /// Move the caret to the end of the next word
+    pub fn move_word_right(&mut self, extend: bool) {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut target = self.caret;
+        while target < chars.len() && chars[target].is_whitespace() {
+            target += 1;
+        }
+        while target < chars.len() && !chars[target].is_whitespace() {
+            target += 1;
+        }
+        self.move_caret(target, extend);
+    }
+
+    pub fn move_home(&mut self, extend: bool) {
+        self.move_caret(0, extend);
+    }
+
+    pub fn move_end(&mut self, extend: bool) {
+        self.move_caret(self.char_count(), extend);
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.caret = self.char_count();
+    }
+
+    /// Copy the selection to the clipboard
+    pub fn copy(&self, clipboard: &mut dyn Clipboard) {
+        if let Some(selected) = self.selected_text() {
+            clipboard.set_text(selected);
+        }
+    }
+
+    /// Move the selection to the clipboard
+    pub fn cut(&mut self, clipboard: &mut dyn Clipboard) {
+        self.copy(clipboard);
+        self.delete_selection();
+    }
+
+    /// Insert the clipboard's text at the caret
+    pub fn paste(&mut self, clipboard: &mut dyn Clipboard) {
+        if let Some(text) = clipboard.get_text() {
+            self.insert_str(&text);
+        }
+    }
+
+    /// Apply an editing or navigation key; returns whether the field used it
+    ///
+    /// `ctrl` selects the shortcut modifier (Ctrl, or Command on macOS) and word-wise
+    /// movement, `shift` extends the selection.
+    pub fn handle_key(
+        &mut self,
+        key: KeyCode,
+        ctrl: bool,
+        shift: bool,
+        clipboard: &mut dyn Clipboard,
+    ) -> bool {
+        match key {
+            KeyCode::Left if ctrl => self.move_word_left(shift),
+            KeyCode::Left => self.move_left(shift),
+            KeyCode::Right if ctrl => self.move_word_right(shift),
+            KeyCode::Right => self.move_right(shift),
+            KeyCode::Home => self.move_home(shift),
+            KeyCode::End => self.move_end(shift),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::A if ctrl => self.select_all(),
+            KeyCode::C if ctrl => self.copy(clipboard),
+            KeyCode::X if ctrl => self.cut(clipboard),
+            KeyCode::V if ctrl => self.paste(clipboard),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Place the caret at the slot nearest to `x`, selecting from the old caret if `extend`
+    pub fn click(&mut self, x: f32, caret_offsets: &[f32], extend: bool) {
+        self.move_caret(caret_index_at(x, caret_offsets), extend);
+    }
+
+    /// Extend the selection to the slot nearest to `x` while the mouse is dragged
+    pub fn drag_to(&mut self, x: f32, caret_offsets: &[f32]) {
+        self.click(x, caret_offsets, true);
+    }
+
+    /// Horizontal position of the caret, relative to the text start
+    pub fn caret_x(&self, caret_offsets: &[f32]) -> f32 {
+        offset_at(caret_offsets, self.caret)
+    }
+
+    /// Horizontal extent (start, end) of the selection highlight
+    pub fn selection_span(&self, caret_offsets: &[f32]) -> Option<(f32, f32)> {
+        self.selection().map(|range| {
+            (
+                offset_at(caret_offsets, range.start),
+                offset_at(caret_offsets, range.end),
+            )
+        })
+    }
+
+    fn move_caret(&mut self, target: usize, extend: bool) {
+        if extend {
+            self.anchor.get_or_insert(self.caret);
+        } else {
+            self.anchor = None;
+        }
+        self.caret = target.min(self.char_count());
+        self.blink_time = 0.0;
+    }
+
+    /// Remove the selected text; returns whether there was a selection
+    fn delete_selection(&mut self) -> bool {
+        let Some(range) = self.selection() else {
+            self.anchor = None;
+            return false;
+        };
+        self.remove_range(range);
+        true
+    }
+
+    fn remove_range(&mut self, range: Range<usize>) {
+        let bytes = self.byte_index(range.start)..self.byte_index(range.end);
+        self.text.replace_range(bytes, "");
+        self.caret = range.start;
+        self.anchor = None;
+        self.blink_time = 0.0;
+    }
+
+    fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.text.len(), |(index, _)| index)
+    }
+}
+
+/// Caret slot whose offset is nearest to `x`
+fn caret_index_at(x: f32, caret_offsets: &[f32]) -> usize {
+    caret_offsets
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| (*a - x).abs().total_cmp(&(*b - x).abs()))
+        .map_or(0, |(index, _)| index)
+}
+
+fn offset_at(caret_offsets: &[f32], index: usize) -> f32 {
+    caret_offsets
+        .get(index)
+        .or(caret_offsets.last())
+        .copied()
+        .unwrap_or(0.0)
+}
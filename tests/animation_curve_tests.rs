@@ -0,0 +1,90 @@
+use engine_2d::animation::{AnimationCurve, Track, tween};
+use glam::Vec2;
+
+fn approx(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+#[test]
+fn test_hermite_keys() {
+    assert_eq!(AnimationCurve::new().evaluate(0.5), 0.0);
+
+    // Automatic tangents are flat at the ends, like smoothstep
+    let smooth = AnimationCurve::new().with_key(1.0, 1.0).with_key(0.0, 0.0);
+    assert_eq!(smooth.keys().len(), 2);
+    assert!(approx(smooth.evaluate(0.5), 0.5));
+    assert!(approx(smooth.evaluate(0.25), 0.15625));
+    assert_eq!(smooth.evaluate(-1.0), 0.0);
+    assert_eq!(smooth.evaluate(2.0), 1.0);
+
+    // Unit slopes on both keys give a straight line
+    let straight = AnimationCurve::new()
+        .with_key_tangents(0.0, 0.0, None, Some(1.0))
+        .with_key_tangents(2.0, 2.0, Some(1.0), None);
+    assert!(approx(straight.evaluate(0.5), 0.5));
+
+    // An interior key passes through its value
+    let overshoot = AnimationCurve::new()
+        .with_key(0.0, 0.0)
+        .with_key(0.6, 1.2)
+        .with_key(1.0, 1.0);
+    assert!(approx(overshoot.evaluate(0.6), 1.2));
+    assert!(overshoot.evaluate(0.8) > 1.0);
+}
+
+#[test]
+fn test_bezier_timing_curves() {
+    let linear = AnimationCurve::bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0));
+    for t in [0.0, 0.3, 0.7, 1.0] {
+        assert!(approx(linear.evaluate(t), t));
+    }
+
+    let ease = AnimationCurve::bezier(Vec2::new(0.25, 0.1), Vec2::new(0.25, 1.0));
+    assert_eq!(ease.evaluate(0.0), 0.0);
+    assert!(approx(ease.evaluate(1.0), 1.0));
+    // CSS `ease` is about 0.8 of the way through at half time
+    assert!((ease.evaluate(0.5) - 0.8024).abs() < 0.01);
+    assert!(ease.keys().is_empty());
+}
+
+#[test]
+fn test_curves_drive_tweens_and_tracks() {
+    let curve = AnimationCurve::new().with_key(0.0, 0.0).with_key(1.0, 1.0);
+
+    let mut slide = tween(0.0).to(10.0).over(1.0).ease(curve.clone());
+    assert!(approx(slide.update(0.25), 1.5625));
+
+    let track = Track::new()
+        .with_key(0.0, 0.0, |t: f32| t)
+        .with_key(2.0, 10.0, curve);
+    assert!(approx(track.sample(1.0).unwrap(), 5.0));
+    assert!(approx(track.sample(0.5).unwrap(), 1.5625));
+}
+
+#[test]
+fn test_key_replacement_and_degenerate_bezier_controls() {
+    // A key at an existing time replaces it, and keys turn a bezier curve into a key curve
+    let curve = AnimationCurve::bezier(Vec2::new(0.5, 0.0), Vec2::new(0.5, 1.0))
+        .with_key(0.0, 0.0)
+        .with_key(1.0, 4.0)
+        .with_key(1.0, 2.0);
+    assert_eq!(curve.keys().len(), 2);
+    assert_eq!(curve.evaluate(1.0), 2.0);
+
+    // Flat x slope at the start stalls Newton's method; bisection still finds the value
+    let steep = AnimationCurve::bezier(Vec2::new(0.0, 1.0), Vec2::new(1.0, 0.0));
+    let mut previous = steep.evaluate(0.0);
+    for step in 1..=20 {
+        let value = steep.evaluate(step as f32 / 20.0);
+        assert!(value >= previous - 1e-4, "not monotonic at step {}", step);
+        previous = value;
+    }
+    assert!(approx(steep.evaluate(0.5), 0.5));
+
+    // Control point x values outside 0..1 are clamped, keeping the curve a function of t
+    let clamped = AnimationCurve::bezier(Vec2::new(-1.0, 0.0), Vec2::new(2.0, 1.0));
+    let reference = AnimationCurve::bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0));
+    for t in [0.1, 0.5, 0.9] {
+        assert!(approx(clamped.evaluate(t), reference.evaluate(t)));
+    }
+}
@@ -0,0 +1,133 @@
+use engine_2d::animation::{SpriteSheetAnimation, Timeline, TimelinePlayer, timeline_system};
+use engine_2d::ecs::{Stage, Transform, World};
+use engine_2d::engine::{Game, TestEngine, UpdateContext};
+use engine_2d::events::{AnimationEvent, EventSystem};
+use std::sync::{Arc, Mutex};
+
+fn marker_names(events: &[AnimationEvent]) -> Vec<&str> {
+    events
+        .iter()
+        .map(|event| match event {
+            AnimationEvent::Marker { name, .. } => name.as_str(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_clip_markers_fire_when_frames_are_entered() {
+    let mut run = SpriteSheetAnimation::from_grid(4, 1, 4, (16.0, 16.0), 0.1).unwrap();
+    run.add_marker(0, "start").unwrap();
+    run.add_marker(2, "footstep").unwrap();
+    assert!(run.add_marker(4, "missing").is_err());
+
+    run.play();
+    assert_eq!(run.take_events(), vec!["start"]);
+    run.update(0.15);
+    assert!(run.take_events().is_empty());
+    // Crosses frames 2 and 3, then loops back to frame 0
+    run.update(0.3);
+    assert_eq!(run.take_events(), vec!["footstep", "start"]);
+
+    // Pausing and resuming doesn't fire the current frame again
+    run.pause();
+    run.play();
+    assert!(run.take_events().is_empty());
+
+    let events = EventSystem::new();
+    run.update(0.2);
+    run.dispatch_events(None, &events.get_animation_sender());
+    assert_eq!(
+        marker_names(&events.drain_animation_events()),
+        vec!["footstep"]
+    );
+}
+
+#[test]
+fn test_timeline_events_fire_once_per_crossing() {
+    let timeline = Timeline::parse(
+        r#"{
+            "events": [
+                { "time": 1, "name": "land" },
+                { "time": 0, "name": "begin" },
+                { "time": 0.5, "name": "swing" }
+            ],
+            "looping": true
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(timeline.duration(), 1.0);
+    let mut player = TimelinePlayer::new(timeline);
+
+    player.update(0.25);
+    assert_eq!(player.take_events(), vec!["begin"]);
+    player.update(0.25);
+    assert_eq!(player.take_events(), vec!["swing"]);
+    // Wraps around the end of the loop
+    player.update(0.6);
+    assert_eq!(player.take_events(), vec!["land", "begin"]);
+
+    player.seek(0.75);
+    player.update(0.0);
+    assert!(player.take_events().is_empty());
+
+    let error = Timeline::parse("{\"events\": [\n  {\"name\": \"soon\"}\n]}").unwrap_err();
+    assert!(error.contains("missing field `time`"), "{}", error);
+}
+
+#[test]
+fn test_timeline_system_sends_events_with_their_entity() {
+    let events = EventSystem::new();
+    let mut world = World::new();
+    world.insert_resource(events.get_animation_sender());
+    let timeline = Timeline::new().with_event(0.5, "explode");
+    let entity = world.spawn((Transform::default(), TimelinePlayer::new(timeline)));
+
+    timeline_system(&mut world, 0.25);
+    assert!(events.drain_animation_events().is_empty());
+    timeline_system(&mut world, 0.5);
+    let sent = events.drain_animation_events();
+    assert_eq!(marker_names(&sent), vec!["explode"]);
+    assert!(matches!(sent[0], AnimationEvent::Marker { entity: Some(e), .. } if e == entity));
+}
+
+struct Idle;
+
+impl Game for Idle {
+    fn update(&mut self, _ctx: &mut UpdateContext<'_>) {}
+
+    fn name(&self) -> &str {
+        "Idle"
+    }
+}
+
+#[test]
+fn test_engine_dispatches_timeline_markers_to_listeners() {
+    let mut engine = TestEngine::new(Box::new(Idle)).with_delta_time(0.25);
+    engine
+        .scheduler_mut()
+        .add_system(Stage::Update, "timelines", timeline_system);
+    let timeline = Timeline::new().with_event(0.5, "explode");
+    let entity = engine
+        .world_mut()
+        .spawn((Transform::default(), TimelinePlayer::new(timeline)));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let listener_seen = Arc::clone(&seen);
+    engine
+        .engine()
+        .event_system()
+        .add_animation_listener(move |event| listener_seen.lock().unwrap().push(event.clone()));
+
+    engine.step();
+    assert!(seen.lock().unwrap().is_empty());
+    engine.step();
+    let sent = std::mem::take(&mut *seen.lock().unwrap());
+    assert_eq!(marker_names(&sent), vec!["explode"]);
+    assert!(matches!(sent[0], AnimationEvent::Marker { entity: Some(e), .. } if e == entity));
+    assert!(
+        engine
+            .engine()
+            .event_system()
+            .drain_animation_events()
+            .is_empty()
+    );
+}
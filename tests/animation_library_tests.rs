@@ -0,0 +1,139 @@
+use engine_2d::animation::{
+    AnimationAsset, AnimationLibrary, LoopMode, SpriteSheetAnimation, TimelinePlayer,
+    animation_reload_system,
+};
+use engine_2d::ecs::World;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "engine_2d_animation_library_{}_{}",
+        name,
+        std::process::id()
+    ));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Rewrite a file with a modification time that's certainly different from the last one
+fn edit(path: &PathBuf, contents: &str, seconds_later: u64) {
+    fs::write(path, contents).unwrap();
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(seconds_later))
+        .unwrap();
+}
+
+/// A timeline bobbing up by 10 over `duration` seconds
+fn bob(duration: f32) -> String {
+    format!(
+        r#"{{"position": [{{ "time": 0, "value": [0, 0] }}, {{ "time": {}, "value": [0, 10] }}]}}"#,
+        duration
+    )
+}
+
+const RUN: &str = r#"{
+    "frames": [
+        { "region": [0, 0, 16, 16], "duration": 0.1 },
+        { "region": [16, 0, 16, 16], "duration": 0.1 },
+        { "region": [32, 0, 16, 16], "duration": 0.1 }
+    ],
+    "loop": "ping_pong",
+    "speed": 2,
+    "markers": [{ "frame": 1, "name": "step" }]
+}"#;
+
+#[test]
+fn test_parse_sprite_sheet_json() {
+    let clip = SpriteSheetAnimation::parse(RUN).unwrap();
+    assert_eq!(clip.frames().len(), 3);
+    assert_eq!(clip.frames()[1].region, (16.0, 0.0, 16.0, 16.0));
+    assert_eq!(clip.loop_mode(), LoopMode::PingPong);
+    assert_eq!(clip.speed(), 2.0);
+    assert_eq!(clip.markers(), &[(1, "step".to_string())]);
+
+    let grid = SpriteSheetAnimation::parse(
+        r#"{ "grid": { "columns": 2, "rows": 2, "count": 3, "frame_width": 8,
+             "frame_height": 8, "duration": 0.05 }, "loop": "reverse" }"#,
+    )
+    .unwrap();
+    assert_eq!(grid.frames()[2].region, (0.0, 8.0, 8.0, 8.0));
+    assert_eq!(grid.current_frame(), 2);
+
+    assert!(SpriteSheetAnimation::parse("{}").is_err());
+    assert!(SpriteSheetAnimation::parse(r#"{"frames": [{"region": [0, 0]}]}"#).is_err());
+    assert!(SpriteSheetAnimation::parse(r#"{"grid": {"count": 1}, "loop": "sideways"}"#).is_err());
+}
+
+#[test]
+fn test_reload_keeps_playback_state() {
+    let mut clip = SpriteSheetAnimation::parse(RUN).unwrap();
+    clip.play();
+    clip.update(0.06);
+    assert_eq!(clip.current_frame(), 1);
+
+    // Fewer, slower frames: the clip stays playing on the clamped frame
+    let edited = SpriteSheetAnimation::parse(
+        r#"{ "frames": [{ "region": [0, 0, 32, 32], "duration": 0.5 }], "loop": "once" }"#,
+    )
+    .unwrap();
+    clip.reload_from(&edited);
+    assert!(clip.is_playing());
+    assert_eq!(clip.current_frame(), 0);
+    assert_eq!(clip.current_region(), (0.0, 0.0, 32.0, 32.0));
+    assert_eq!(clip.loop_mode(), LoopMode::Once);
+    assert!(clip.markers().is_empty());
+}
+
+#[test]
+fn test_library_reloads_changed_files_into_entities() {
+    let sheet = temp_file("run.json", RUN);
+    let timeline = temp_file("bob.json", &bob(2.0));
+    let mut library = AnimationLibrary::new();
+    library
+        .load_sprite_sheet("run", sheet.to_str().unwrap())
+        .unwrap();
+    library
+        .load_timeline("bob", timeline.to_str().unwrap())
+        .unwrap();
+    assert!(library.timeline("run").is_none());
+    assert!(library.reload_changed().is_empty());
+
+    let mut world = World::new();
+    let mut run = library.sprite_sheet("run").unwrap().clone();
+    run.play();
+    run.update(0.06);
+    let runner = world.spawn((run, AnimationAsset("run".to_string())));
+    let mut player = TimelinePlayer::new(library.timeline("bob").unwrap().clone());
+    player.update(1.5);
+    let bobber = world.spawn((player, AnimationAsset("bob".to_string())));
+
+    library.set_hot_reload(true);
+    library.set_hot_reload_interval(Duration::ZERO);
+    world.insert_resource(library);
+
+    edit(
+        &sheet,
+        &RUN.replace("[16, 0, 16, 16]", "[16, 16, 16, 16]"),
+        10,
+    );
+    edit(&timeline, &bob(1.0), 10);
+    animation_reload_system(&mut world, 0.016);
+
+    let run = world.get::<SpriteSheetAnimation>(runner).unwrap();
+    assert_eq!(run.current_frame(), 1);
+    assert_eq!(run.current_region(), (16.0, 16.0, 16.0, 16.0));
+    let player = world.get::<TimelinePlayer>(bobber).unwrap();
+    assert_eq!(player.timeline().duration(), 1.0);
+    assert_eq!(player.time(), 1.0);
+
+    // A broken edit keeps the previous clip
+    edit(&sheet, "{ broken", 20);
+    let library = world.resource_mut::<AnimationLibrary>().unwrap();
+    assert!(library.poll_hot_reload().is_empty());
+    assert_eq!(library.sprite_sheet("run").unwrap().frames().len(), 3);
+
+    let _ = fs::remove_file(sheet);
+    let _ = fs::remove_file(timeline);
+}
@@ -0,0 +1,164 @@
+use engine_2d::animation::{
+    AnimationController, Condition, LoopMode, SpriteFrame, SpriteSheetAnimation, Transition,
+};
+
+fn clip(row: u32, loop_mode: LoopMode) -> SpriteSheetAnimation {
+    let frames = (0..4)
+        .map(|i| {
+            let region = (i as f32 * 16.0, row as f32 * 16.0, 16.0, 16.0);
+            SpriteFrame::new(region, 0.1)
+        })
+        .collect();
+    SpriteSheetAnimation::new(frames)
+        .unwrap()
+        .with_loop_mode(loop_mode)
+}
+
+fn controller() -> AnimationController {
+    let mut controller = AnimationController::new();
+    controller.add_state("idle", clip(0, LoopMode::Loop));
+    controller.add_state("run", clip(1, LoopMode::Loop));
+    controller.add_state("jump", clip(2, LoopMode::Once));
+    controller
+        .add_transition(
+            Transition::new("idle", "run")
+                .when(Condition::greater("speed", 0.1))
+                .with_blend(0.2),
+        )
+        .unwrap();
+    controller
+        .add_transition(Transition::new("run", "idle").when(Condition::less("speed", 0.1)))
+        .unwrap();
+    controller
+        .add_transition(
+            Transition::from_any("jump")
+                .when(Condition::trigger("jump"))
+                .when(Condition::is_true("grounded")),
+        )
+        .unwrap();
+    controller
+        .add_transition(Transition::new("jump", "idle").when(Condition::ClipFinished))
+        .unwrap();
+    controller
+}
+
+#[test]
+fn test_float_conditions_switch_states() {
+    let mut controller = controller();
+    assert_eq!(controller.current_state(), "idle");
+    assert!(!controller.update(0.05));
+
+    controller.set_float("speed", 3.0);
+    assert!(controller.update(0.05));
+    assert_eq!(controller.current_state(), "run");
+    assert_eq!(controller.previous_state(), Some("idle"));
+    assert_eq!(controller.current_region(), Some((0.0, 16.0, 16.0, 16.0)));
+    controller.update(0.1);
+    assert!((controller.blend_weight() - 0.5).abs() < 1e-5);
+
+    controller.set_float("speed", 0.0);
+    controller.update(0.05);
+    assert_eq!(controller.current_state(), "idle");
+    assert_eq!(controller.blend_weight(), 1.0);
+
+    assert!(
+        controller
+            .add_transition(Transition::new("idle", "swim"))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_triggers_are_consumed_and_clips_finish() {
+    let mut controller = controller();
+    controller.set_trigger("jump");
+    controller.update(0.01);
+    // Not grounded: the trigger waits
+    assert_eq!(controller.current_state(), "idle");
+    assert!(controller.is_triggered("jump"));
+
+    controller.set_bool("grounded", true);
+    controller.update(0.01);
+    assert_eq!(controller.current_state(), "jump");
+    assert!(!controller.is_triggered("jump"));
+
+    // The jump clip plays once, then returns to idle
+    for _ in 0..4 {
+        controller.update(0.1);
+    }
+    assert_eq!(controller.current_state(), "jump");
+    controller.update(0.01);
+    assert_eq!(controller.current_state(), "idle");
+}
+
+#[test]
+fn test_exit_time_and_force_state() {
+    let mut controller = AnimationController::new();
+    controller.add_state("attack", clip(0, LoopMode::Loop));
+    controller.add_state("recover", clip(1, LoopMode::Loop));
+    controller
+        .add_transition(
+            Transition::new("attack", "recover")
+                .with_exit_time(0.3)
+                .without_restart(),
+        )
+        .unwrap();
+
+    controller.update(0.2);
+    assert_eq!(controller.current_state(), "attack");
+    controller.update(0.2);
+    assert_eq!(controller.current_state(), "recover");
+
+    controller.force_state("attack").unwrap();
+    assert_eq!(controller.current_state(), "attack");
+    assert_eq!(controller.time_in_state(), 0.0);
+    assert_eq!(controller.current_clip().unwrap().current_frame(), 0);
+    assert!(controller.force_state("missing").is_err());
+}
+
+#[test]
+fn test_transition_order_speed_and_resume() {
+    let mut controller = AnimationController::new().with_speed(2.0);
+    controller.add_state("a", clip(0, LoopMode::Loop));
+    controller.add_state("b", clip(1, LoopMode::Loop));
+    controller.add_state("c", clip(2, LoopMode::Loop));
+    // Both pass; the one added first wins, and only one transition is taken per update
+    controller
+        .add_transition(Transition::new("a", "b").when(Condition::is_true("go")))
+        .unwrap();
+    controller
+        .add_transition(Transition::new("a", "c").when(Condition::is_true("go")))
+        .unwrap();
+    controller
+        .add_transition(
+            Transition::new("b", "a")
+                .when(Condition::is_true("back"))
+                .without_restart(),
+        )
+        .unwrap();
+    controller
+        .add_transition(Transition::new("b", "c").when(Condition::is_true("go")))
+        .unwrap();
+
+    // Controller speed scales the clip: 0.1 s at double speed is two frames
+    controller.update(0.1);
+    assert_eq!(controller.current_clip().unwrap().current_frame(), 2);
+
+    controller.set_bool("go", true);
+    assert!(controller.update(0.0));
+    assert_eq!(controller.current_state(), "b");
+    controller.set_bool("go", false);
+
+    // Returning without restart resumes "a" on the frame it was left on
+    controller.set_bool("back", true);
+    controller.update(0.0);
+    assert_eq!(controller.current_state(), "a");
+    assert_eq!(controller.current_clip().unwrap().current_frame(), 2);
+
+    // A trigger can be cleared before any transition uses it
+    controller.set_trigger("jump");
+    controller.reset_trigger("jump");
+    assert!(!controller.is_triggered("jump"));
+    controller.set_speed(-1.0);
+    assert_eq!(controller.speed(), 0.0);
+}
@@ -0,0 +1,57 @@
+use engine_2d::animation::{
+    AnimationController, AnimationTime, SpriteSheetAnimation, Timeline, TimelinePlayer,
+    TweenManager, timeline_system, tween,
+};
+use engine_2d::ecs::{Transform, World};
+use glam::Vec2;
+
+#[test]
+fn test_animation_time_scales_and_pauses() {
+    let mut time = AnimationTime::new().with_scale(0.5);
+    assert_eq!(time.delta(0.2), 0.1);
+    time.pause();
+    assert_eq!(time.delta(0.2), 0.0);
+    time.resume();
+    time.set_scale(-1.0);
+    assert_eq!(time.scale(), 0.0);
+}
+
+#[test]
+fn test_per_animation_playback_rates() {
+    let clip = SpriteSheetAnimation::from_grid(4, 1, 4, (8.0, 8.0), 0.1).unwrap();
+    let mut controller = AnimationController::new().with_speed(2.0);
+    controller.add_state("run", clip);
+    controller.update(0.1);
+    assert_eq!(controller.current_clip().unwrap().current_frame(), 2);
+    assert!((controller.time_in_state() - 0.2).abs() < 1e-5);
+
+    let mut tweens = TweenManager::new();
+    tweens.set_time_scale(0.5);
+    let id = tweens.add(tween(0.0).to(10.0).over(1.0));
+    tweens.update(1.0);
+    assert_eq!(tweens.value::<f32>(id), Some(5.0));
+}
+
+#[test]
+fn test_world_animation_time_slows_timelines() {
+    let mut world = World::new();
+    world.insert_resource(AnimationTime::new().with_scale(0.5));
+    let timeline = Timeline::parse(
+        r#"{"position": [{ "time": 0, "value": [0, 0] }, { "time": 1, "value": [10, 0] }]}"#,
+    )
+    .unwrap();
+    let entity = world.spawn((Transform::default(), TimelinePlayer::new(timeline)));
+
+    timeline_system(&mut world, 0.5);
+    assert_eq!(
+        world.get::<Transform>(entity).unwrap().position,
+        Vec2::new(2.5, 0.0)
+    );
+
+    world.resource_mut::<AnimationTime>().unwrap().pause();
+    timeline_system(&mut world, 0.5);
+    assert_eq!(
+        world.get::<Transform>(entity).unwrap().position,
+        Vec2::new(2.5, 0.0)
+    );
+}
@@ -0,0 +1,41 @@
+use engine_2d::render::atlas::{AtlasPacker, grid_regions};
+
+#[test]
+fn test_pack_fills_shelves_left_to_right() {
+    let mut packer = AtlasPacker::new(10, 10);
+
+    assert_eq!(packer.pack(4, 3), Some((0, 0)));
+    assert_eq!(packer.pack(4, 5), Some((4, 0)));
+    // Doesn't fit on the first shelf, so starts a new one below its tallest entry
+    assert_eq!(packer.pack(4, 2), Some((0, 5)));
+}
+
+#[test]
+fn test_pack_with_padding_and_full_atlas() {
+    let mut packer = AtlasPacker::new(8, 8).with_padding(1);
+
+    assert_eq!(packer.pack(2, 2), Some((1, 1)));
+    assert_eq!(packer.pack(2, 2), Some((5, 1)));
+    assert_eq!(packer.pack(2, 2), Some((1, 5)));
+    assert_eq!(packer.pack(2, 2), Some((5, 5)));
+    assert_eq!(packer.pack(2, 2), None);
+    assert_eq!(packer.pack(9, 1), None);
+
+    packer.clear();
+    assert_eq!(packer.pack(2, 2), Some((1, 1)));
+}
+
+#[test]
+fn test_uv_rect_is_normalized() {
+    let packer = AtlasPacker::new(256, 128);
+    assert_eq!(packer.uv_rect(64, 32, 128, 64), (0.25, 0.25, 0.5, 0.5));
+}
+
+#[test]
+fn test_grid_regions_run_row_by_row() {
+    let regions = grid_regions(4, 2, 6).unwrap();
+    assert_eq!(regions.len(), 6);
+    assert_eq!(regions[1], (0.25, 0.0, 0.25, 0.5));
+    assert_eq!(regions[5], (0.25, 0.5, 0.25, 0.5));
+    assert!(grid_regions(4, 2, 9).is_err());
+}
@@ -0,0 +1,49 @@
+use engine_2d::render::bidi::{TextDirection, visual_order};
+
+#[test]
+fn test_direction_resolves_from_first_letter() {
+    assert_eq!(
+        TextDirection::Auto.resolve("123 שלום world"),
+        TextDirection::RightToLeft
+    );
+    assert_eq!(
+        TextDirection::Auto.resolve("score: 10"),
+        TextDirection::LeftToRight
+    );
+    assert_eq!(
+        TextDirection::LeftToRight.resolve("שלום"),
+        TextDirection::LeftToRight
+    );
+}
+
+#[test]
+fn test_visual_order_reverses_rtl_runs() {
+    // Pure left-to-right text is untouched
+    assert_eq!(
+        visual_order("Hello (world)", TextDirection::Auto),
+        "Hello (world)"
+    );
+
+    // Hebrew inside an English line is reversed in place
+    assert_eq!(
+        visual_order("I said שלום!", TextDirection::Auto),
+        "I said םולש!"
+    );
+
+    // In a right-to-left paragraph, numbers keep their order and brackets mirror
+    assert_eq!(
+        visual_order("שלום (עולם) 1,000", TextDirection::Auto),
+        "1,000 (םלוע) םולש"
+    );
+}
+
+#[test]
+fn test_arabic_letters_are_joined() {
+    // Beh, alef, beh: initial beh joins the alef, which can't join forward
+    let shaped = visual_order("\u{0628}\u{0627}\u{0628}", TextDirection::Auto);
+    assert_eq!(shaped, "\u{FE8F}\u{FE8E}\u{FE91}");
+
+    // Lam followed by alef becomes a single ligature
+    let shaped = visual_order("\u{0644}\u{0627}", TextDirection::Auto);
+    assert_eq!(shaped, "\u{FEFB}");
+}
@@ -0,0 +1,44 @@
+use engine_2d::render::bmfont::BmFont;
+use std::path::Path;
+
+const SAMPLE: &str = r#"info face="Pixel Sans" size=-16 bold=0 italic=0 charset="" unicode=1
+common lineHeight=18 base=14 scaleW=128 scaleH=64 pages=2 packed=0
+page id=0 file="pixel_0.png"
+page id=1 file="pixel_1.png"
+chars count=2
+char id=65   x=0     y=0     width=9     height=11    xoffset=0     yoffset=3     xadvance=10    page=0  chnl=15
+char id=86   x=10    y=0     width=9     height=11    xoffset=-1    yoffset=3     xadvance=9     page=1  chnl=15
+kernings count=1
+kerning first=65  second=86  amount=-2
+"#;
+
+#[test]
+fn test_parse_bmfont_text_format() {
+    let font = BmFont::parse(SAMPLE).unwrap();
+
+    assert_eq!(font.size, 16);
+    assert_eq!((font.line_height, font.base), (18, 14));
+    assert_eq!((font.scale_w, font.scale_h), (128, 64));
+    assert_eq!(font.pages, vec!["pixel_0.png", "pixel_1.png"]);
+
+    assert_eq!(font.chars.len(), 2);
+    let v = font.chars[1];
+    assert_eq!(v.ch, 'V');
+    assert_eq!((v.x, v.width, v.xoffset, v.page), (10, 9, -1, 1));
+    assert_eq!(font.kernings.get(&('A', 'V')), Some(&-2));
+
+    let pages = font.page_paths(Path::new("assets/fonts/pixel.fnt"));
+    assert_eq!(pages[1], Path::new("assets/fonts/pixel_1.png"));
+}
+
+#[test]
+fn test_parse_bmfont_errors() {
+    assert!(BmFont::parse("info size=16\n").is_err());
+
+    let missing_page = "common lineHeight=18 base=14 scaleW=128 scaleH=64\n\
+        char id=65 x=0 y=0 width=9 height=11 xoffset=0 yoffset=3 xadvance=10 page=0\n";
+    assert!(BmFont::parse(missing_page).unwrap_err().contains("page 0"));
+
+    let bad_value = "common lineHeight=tall base=14 scaleW=128 scaleH=64\n";
+    assert!(BmFont::parse(bad_value).unwrap_err().contains("line 1"));
+}
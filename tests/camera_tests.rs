@@ -0,0 +1,61 @@
+use engine_2d::render::camera::{Camera, CameraFollow, CameraShake};
+use glam::Vec2;
+
+#[test]
+fn test_follow_respects_deadzone() {
+    let follow = CameraFollow::default()
+        .with_deadzone(Vec2::new(0.2, 0.1))
+        .with_smoothing(0.0);
+    let mut camera = Camera::new((-1.0, 1.0, -1.0, 1.0)).with_follow(follow);
+
+    camera.update(Vec2::new(0.15, 0.05), 0.016);
+    assert_eq!(camera.position(), Vec2::ZERO);
+
+    camera.update(Vec2::new(0.5, -0.3), 0.016);
+    assert!(camera.position().abs_diff_eq(Vec2::new(0.3, -0.2), 1e-6));
+}
+
+#[test]
+fn test_smoothing_and_level_bounds() {
+    let mut camera = Camera::new((0.0, 800.0, 0.0, 600.0))
+        .with_level_bounds(Vec2::ZERO, Vec2::new(2000.0, 1000.0));
+
+    // Smoothing moves part of the way each frame
+    camera.update(Vec2::new(600.0, 300.0), 0.05);
+    assert!(camera.center().x > 400.0 && camera.center().x < 600.0);
+
+    // The view never leaves the level
+    camera.center_on(Vec2::new(1900.0, -50.0));
+    assert_eq!(camera.position(), Vec2::new(1200.0, 0.0));
+
+    // Levels smaller than the view are centered
+    let small = Camera::new((0.0, 800.0, 0.0, 600.0))
+        .with_level_bounds(Vec2::ZERO, Vec2::new(400.0, 600.0));
+    assert_eq!(small.position(), Vec2::new(-200.0, 0.0));
+}
+
+#[test]
+fn test_shake_scales_with_trauma_and_decays() {
+    let shake = CameraShake::default()
+        .with_max_offset(Vec2::splat(10.0))
+        .with_decay(1.0);
+    let mut camera = Camera::new((-1.0, 1.0, -1.0, 1.0)).with_shake(shake);
+    assert_eq!(camera.view_position(), camera.position());
+
+    camera.add_trauma(0.6);
+    camera.add_trauma(0.6);
+    assert_eq!(camera.shake.trauma, 1.0);
+
+    let mut max_offset: f32 = 0.0;
+    for _ in 0..30 {
+        camera.update(Vec2::ZERO, 0.01);
+        let offset = camera.view_position() - camera.position();
+        assert!(offset.abs().cmple(Vec2::splat(10.0)).all());
+        max_offset = max_offset.max(offset.length());
+    }
+    assert!(max_offset > 0.5);
+
+    camera.update(Vec2::ZERO, 1.0);
+    assert_eq!(camera.shake.trauma, 0.0);
+    assert_eq!(camera.view_position(), camera.position());
+}
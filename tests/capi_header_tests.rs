@@ -0,0 +1,14 @@
+#![cfg(feature = "capi")]
+
+/// The header the build script generated from `src/capi.rs`
+const GENERATED: &str = include_str!(concat!(env!("OUT_DIR"), "/engine_2d.h"));
+
+#[test]
+fn test_checked_in_header_matches_the_c_api() {
+    let checked_in = include_str!("../include/engine_2d.h");
+    assert!(
+        checked_in == GENERATED,
+        "include/engine_2d.h is out of date; copy {}/engine_2d.h over it",
+        env!("OUT_DIR")
+    );
+}
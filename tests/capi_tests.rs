@@ -0,0 +1,93 @@
+#![cfg(all(feature = "capi", not(feature = "opengl")))]
+
+use engine_2d::capi::*;
+use std::ffi::{CStr, c_char};
+
+fn c(text: &CStr) -> *const c_char {
+    text.as_ptr()
+}
+
+#[test]
+fn test_capi_engine_runs_frames_until_quit() {
+    let config = E2dConfig {
+        width: 320,
+        height: 240,
+        ..e2d_config_default()
+    };
+    let engine = unsafe { e2d_engine_create(Some(&config), c(c"Embedded")) };
+    assert!(!engine.is_null());
+    let mut engine = unsafe { Box::from_raw(engine) };
+
+    assert_eq!(
+        e2d_engine_frame(Some(&mut engine), 0.25),
+        E2dFrameResult::Skipped
+    );
+    assert_eq!(
+        e2d_engine_frame(Some(&mut engine), 0.25),
+        E2dFrameResult::Skipped
+    );
+    assert!((e2d_engine_elapsed_time(Some(&engine)) - 0.5).abs() < 1e-5);
+
+    e2d_engine_quit(Some(&mut engine));
+    assert_eq!(
+        e2d_engine_frame(Some(&mut engine), 0.25),
+        E2dFrameResult::Exit
+    );
+    e2d_engine_destroy(Some(engine));
+}
+
+#[test]
+fn test_capi_input_reaches_actions_on_the_next_frame() {
+    let mut engine = unsafe { Box::from_raw(e2d_engine_create_headless(None, std::ptr::null())) };
+    unsafe {
+        assert!(e2d_input_bind_action(
+            Some(&mut engine),
+            c(c"JUMP"),
+            c(c"Keyboard:Space")
+        ));
+        assert!(e2d_input_set(Some(&mut engine), c(c"Keyboard:Space"), true));
+        assert!(!e2d_action_pressed(Some(&engine), c(c"JUMP")));
+    }
+
+    e2d_engine_frame(Some(&mut engine), 1.0 / 60.0);
+    unsafe {
+        assert!(e2d_action_pressed(Some(&engine), c(c"JUMP")));
+        assert_eq!(e2d_action_value(Some(&engine), c(c"JUMP")), 1.0);
+    }
+    e2d_engine_destroy(Some(engine));
+}
+
+#[test]
+fn test_capi_reports_errors() {
+    let mut engine = unsafe { Box::from_raw(e2d_engine_create_headless(None, std::ptr::null())) };
+
+    assert!(!unsafe { e2d_input_set(Some(&mut engine), c(c"Keyboard"), true) });
+    let error = unsafe { CStr::from_ptr(e2d_last_error()) };
+    assert!(error.to_str().unwrap().contains("Device:Input"));
+
+    assert!(!unsafe { e2d_texture_load(Some(&mut engine), c(c"missing.png"), None) });
+    assert_eq!(e2d_engine_frame(None, 0.1), E2dFrameResult::Exit);
+    let error = unsafe { CStr::from_ptr(e2d_last_error()) };
+    assert_eq!(error.to_str().unwrap(), "engine is null");
+
+    // Draws on a headless engine are dropped at the end of the frame
+    e2d_draw_sprite(
+        Some(&mut engine),
+        1,
+        0.0,
+        0.0,
+        8.0,
+        8.0,
+        E2dColor {
+            r: 1.0,
+            g: 1.0,
+            b: 1.0,
+            a: 1.0,
+        },
+    );
+    assert_eq!(
+        e2d_engine_frame(Some(&mut engine), 0.1),
+        E2dFrameResult::Skipped
+    );
+    e2d_engine_destroy(Some(engine));
+}
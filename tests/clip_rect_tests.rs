@@ -0,0 +1,45 @@
+use engine_2d::render::viewport::ViewportRect;
+use glam::Vec2;
+
+fn rect(x: i32, y: i32, width: i32, height: i32) -> ViewportRect {
+    ViewportRect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn test_sub_rect_maps_top_left_normalized_to_pixels() {
+    let view = rect(0, 0, 800, 600);
+
+    // The top-left quarter of the screen starts half way up from the bottom
+    assert_eq!(
+        view.sub_rect(Vec2::ZERO, Vec2::splat(0.5)),
+        rect(0, 300, 400, 300)
+    );
+    assert_eq!(view.sub_rect(Vec2::ZERO, Vec2::ONE), view);
+}
+
+#[test]
+fn test_sub_rect_respects_letterboxed_viewport() {
+    let view = rect(100, 50, 400, 200);
+    assert_eq!(
+        view.sub_rect(Vec2::new(0.25, 0.5), Vec2::new(0.5, 0.5)),
+        rect(200, 50, 200, 100)
+    );
+}
+
+#[test]
+fn test_intersect_nested_clip_rects() {
+    let panel = rect(0, 0, 400, 300);
+    assert_eq!(
+        panel.intersect(&rect(300, 200, 200, 200)),
+        rect(300, 200, 100, 100)
+    );
+
+    // Disjoint rects clip everything
+    let empty = panel.intersect(&rect(500, 0, 100, 100));
+    assert_eq!(empty.width, 0);
+}
@@ -0,0 +1,95 @@
+use engine_2d::physics::collision::CollisionPolygon;
+use glam::Vec2;
+
+/// RGBA image with the given pixels opaque
+fn image(width: u32, height: u32, opaque: impl Fn(u32, u32) -> bool) -> Vec<u8> {
+    let mut rgba = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let alpha = if opaque(x, y) { 255 } else { 0 };
+            rgba.extend_from_slice(&[255, 255, 255, alpha]);
+        }
+    }
+    rgba
+}
+
+#[test]
+fn test_hull_from_alpha_covers_opaque_pixels() {
+    // Diamond inside an 8x8 image with transparent corners
+    let rgba = image(8, 8, |x, y| {
+        (x as i32 * 2 - 7).abs() + (y as i32 * 2 - 7).abs() <= 8
+    });
+
+    let hull = CollisionPolygon::from_alpha(&rgba, 8, 8, 128).unwrap();
+
+    assert!(hull.contains_point(Vec2::new(0.5, 0.5)));
+    assert!(!hull.contains_point(Vec2::new(0.05, 0.05)));
+    assert!(hull.area() < 1.0);
+    let (min, max) = hull.bounds().unwrap();
+    assert!(min.cmpge(Vec2::ZERO).all() && max.cmple(Vec2::ONE).all());
+
+    assert!(CollisionPolygon::from_alpha(&image(4, 4, |_, _| false), 4, 4, 1).is_none());
+}
+
+#[test]
+fn test_simplify_drops_collinear_points() {
+    let square = CollisionPolygon::new(vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(0.5, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(0.0, 1.0),
+    ]);
+
+    let simplified = square.simplified(0.01);
+
+    assert_eq!(simplified.points().len(), 4);
+    assert!((simplified.area() - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_sprite_space_and_intersection() {
+    let rgba = image(4, 4, |_, _| true);
+    let shape = CollisionPolygon::from_alpha(&rgba, 4, 4, 1).unwrap();
+
+    // The full-texture hull matches the sprite quad centered on its position
+    let a = shape.to_sprite_space(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0), false, false);
+    let (min, max) = a.bounds().unwrap();
+    assert_eq!((min, max), (Vec2::new(-1.0, -1.0), Vec2::new(1.0, 1.0)));
+
+    let overlapping = shape.to_sprite_space(Vec2::new(1.5, 0.0), Vec2::new(2.0, 2.0), false, false);
+    let apart = shape.to_sprite_space(Vec2::new(3.0, 0.0), Vec2::new(2.0, 2.0), false, false);
+    assert!(a.intersects(&overlapping));
+    assert!(!a.intersects(&apart));
+}
+
+#[test]
+fn test_flips_thresholds_and_degenerate_shapes() {
+    // Opaque left column only, with a half-transparent right column
+    let mut rgba = image(4, 2, |x, _| x == 0);
+    for y in 0..2 {
+        rgba[(y * 4 + 3) * 4 + 3] = 100;
+    }
+    let solid = CollisionPolygon::from_alpha(&rgba, 4, 2, 128).unwrap();
+    assert!((solid.area() - 0.25).abs() < 1e-6);
+    let faint = CollisionPolygon::from_alpha(&rgba, 4, 2, 100).unwrap();
+    assert!((faint.area() - 1.0).abs() < 1e-6);
+
+    // Flipping horizontally moves the column to the sprite's right edge
+    let (min, _) = solid
+        .to_sprite_space(Vec2::ZERO, Vec2::new(4.0, 2.0), true, false)
+        .bounds()
+        .unwrap();
+    assert_eq!(min.x, 1.0);
+
+    // Short or mismatched pixel buffers are rejected rather than read out of bounds
+    assert!(CollisionPolygon::from_alpha(&rgba[..8], 4, 2, 1).is_none());
+    assert!(CollisionPolygon::from_alpha(&rgba, 0, 2, 1).is_none());
+
+    // Fewer than three points never intersect, and simplifying keeps at least a triangle
+    let line = CollisionPolygon::new(vec![Vec2::ZERO, Vec2::ONE]);
+    assert!(!line.intersects(&solid) && !solid.intersects(&line));
+    let flat = CollisionPolygon::new(vec![Vec2::ZERO, Vec2::X, Vec2::new(2.0, 0.0)]);
+    assert_eq!(flat.simplified(1.0).points().len(), 3);
+    assert!(CollisionPolygon::new(Vec::new()).bounds().is_none());
+}
@@ -11,6 +11,7 @@ fn test_engine_config_defaults() {
     assert!(!config.show_fps);
     assert!(config.vsync);
     assert!(!config.fullscreen);
+    assert!(!config.srgb);
 }
 
 #[test]
@@ -25,6 +26,9 @@ fn test_engine_config_custom_values() {
         fullscreen: true,
         viewport: engine_2d::engine::config::ViewportConfig::default(),
         fallback_font_path: "assets/fonts/default.ttf".to_string(),
+        idle: engine_2d::engine::config::IdleConfig::default(),
+        metrics: engine_2d::engine::config::MetricsConfig::default(),
+        srgb: true,
     };
 
     assert_eq!(config.window_title, "My Game");
@@ -34,6 +38,7 @@ fn test_engine_config_custom_values() {
     assert!(config.show_fps);
     assert!(!config.vsync);
     assert!(config.fullscreen);
+    assert!(config.srgb);
 }
 
 #[test]
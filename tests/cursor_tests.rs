@@ -0,0 +1,22 @@
+use engine_2d::engine::{CursorImage, CursorStyle};
+
+#[test]
+fn test_cursor_image_validation() {
+    let image = CursorImage::new(2, 2, vec![255; 16]).unwrap();
+    assert!(image.check_hotspot((1, 1)).is_ok());
+    assert!(image.check_hotspot((2, 0)).is_err());
+
+    assert!(CursorImage::new(0, 2, Vec::new()).is_err());
+    let err = CursorImage::new(2, 2, vec![0; 12]).unwrap_err();
+    assert!(err.contains("expected 16"), "{}", err);
+    assert_eq!(CursorStyle::default(), CursorStyle::Default);
+}
+
+#[test]
+fn test_cursor_image_packs_rgba_in_memory_order() {
+    let image = CursorImage::new(2, 1, vec![1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+    let packed = image.packed_pixels();
+    assert_eq!(packed.len(), 2);
+    assert_eq!(packed[0].to_ne_bytes(), [1, 2, 3, 4]);
+    assert_eq!(packed[1].to_ne_bytes(), [5, 6, 7, 8]);
+}
@@ -0,0 +1,87 @@
+use engine_2d::ecs::{Added, Changed, Scheduler, Stage, Transform, Velocity, World};
+use glam::Vec2;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn test_added_and_changed_since_clear_trackers() {
+    let mut world = World::new();
+    let a = world.spawn((Transform::default(),));
+    let b = world.spawn((Transform::default(),));
+    assert!(world.is_added::<Transform>(a));
+
+    world.clear_trackers();
+    assert!(!world.is_added::<Transform>(a));
+    assert!(!world.is_changed::<Transform>(a));
+    assert_eq!(
+        world
+            .query_filtered::<&Transform, Changed<Transform>>()
+            .count(),
+        0
+    );
+
+    world.get_mut::<Transform>(b).unwrap().position = Vec2::ONE;
+    let c = world.spawn((Transform::default(),));
+
+    let changed: Vec<_> = world
+        .query_filtered::<&Transform, Changed<Transform>>()
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(changed, vec![b, c]);
+    let added: Vec<_> = world
+        .query_filtered::<&Transform, Added<Transform>>()
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(added, vec![c]);
+    assert!(world.is_changed::<Transform>(b) && !world.is_added::<Transform>(b));
+}
+
+#[test]
+fn test_mutable_query_marks_changed() {
+    let mut world = World::new();
+    let moving = world.spawn((Transform::default(), Velocity(Vec2::X)));
+    let still = world.spawn((Transform::default(),));
+    world.clear_trackers();
+
+    for (_, (transform, velocity)) in world.query::<(&mut Transform, &Velocity)>() {
+        transform.position += velocity.0;
+    }
+
+    assert!(world.is_changed::<Transform>(moving));
+    assert!(!world.is_changed::<Transform>(still));
+    assert!(!world.is_changed::<Velocity>(moving));
+}
+
+#[test]
+fn test_scheduled_systems_see_changes_since_their_last_run() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut world = World::new();
+    let body = world.spawn((Transform::default(), Velocity(Vec2::ZERO)));
+    world.spawn((Transform::default(),));
+
+    let mut scheduler = Scheduler::new();
+    let log = Rc::clone(&seen);
+    scheduler.add_system(Stage::Update, "watch", move |world, _| {
+        let count = world
+            .query_filtered::<&mut Transform, Changed<Transform>>()
+            .count();
+        log.borrow_mut().push(count);
+    });
+    scheduler.add_system(Stage::Physics, "move", |world, _| {
+        for (_, (transform, velocity)) in world.query::<(&mut Transform, &Velocity)>() {
+            transform.position += velocity.0;
+        }
+    });
+
+    for _ in 0..3 {
+        scheduler.run(&mut world, 0.016).unwrap();
+    }
+    // Both spawned entities first, then only the one "move" touches; "watch" never
+    // sees its own mutable access
+    assert_eq!(*seen.borrow(), vec![2, 1, 1]);
+
+    world.remove::<Velocity>(body);
+    scheduler.run(&mut world, 0.016).unwrap();
+    scheduler.run(&mut world, 0.016).unwrap();
+    assert_eq!(*seen.borrow(), vec![2, 1, 1, 1, 0]);
+}
@@ -0,0 +1,99 @@
+use engine_2d::ecs::{Commands, Scheduler, Stage, Transform, Velocity, World};
+use glam::Vec2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Health(f32);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Explosion;
+
+#[test]
+fn test_commands_apply_in_order_after_iteration() {
+    let mut world = World::new();
+    let alive = world.spawn((Health(10.0),));
+    let dead = world.spawn((Health(0.0), Transform::default()));
+
+    let mut commands = Commands::new();
+    for (entity, health) in world.query::<&Health>() {
+        if health.0 <= 0.0 {
+            commands.despawn(entity);
+            commands.spawn((Explosion,));
+        } else {
+            commands.insert(entity, Velocity(Vec2::X));
+            commands.remove::<Health>(entity);
+        }
+    }
+    // Inserting into an entity despawned earlier in the buffer is skipped
+    commands.insert(dead, Velocity::default());
+    assert_eq!(commands.len(), 5);
+    commands.apply(&mut world);
+
+    assert!(!world.is_alive(dead));
+    assert_eq!(world.get::<Velocity>(alive), Some(&Velocity(Vec2::X)));
+    assert!(!world.has::<Health>(alive));
+    assert_eq!(world.query::<&Explosion>().count(), 1);
+    assert_eq!(world.len(), 2);
+}
+
+#[test]
+fn test_deferred_commands_apply_at_end_of_stage() {
+    let mut world = World::new();
+    let mut scheduler = Scheduler::new();
+    scheduler.add_system(Stage::Update, "spawner", |world, _| {
+        let mut commands = Commands::new();
+        commands.spawn((Velocity(Vec2::Y),));
+        world.defer(commands);
+    });
+    scheduler.add_system(Stage::Update, "same_stage", |world, _| {
+        let seen = world.query::<&Velocity>().count();
+        world.insert_resource(seen);
+    });
+    scheduler.add_system(Stage::Physics, "next_stage", |world, _| {
+        let seen = world.query::<&Velocity>().count() as u32;
+        world.insert_resource(seen);
+    });
+
+    scheduler.run(&mut world, 0.016).unwrap();
+    assert_eq!(world.resource::<usize>(), Some(&0));
+    assert_eq!(world.resource::<u32>(), Some(&1));
+}
+
+#[test]
+fn test_apply_deferred_runs_nested_commands() {
+    let mut world = World::new();
+    let mut commands = Commands::new();
+    commands.add(|world| {
+        let mut nested = Commands::new();
+        nested.insert_resource(7u8);
+        world.defer(nested);
+    });
+    world.defer(commands);
+    assert!(!world.has_resource::<u8>());
+
+    world.apply_deferred();
+    assert_eq!(world.resource::<u8>(), Some(&7));
+}
+
+#[test]
+fn test_append_keeps_order_and_stale_entities_are_ignored() {
+    let mut world = World::new();
+    let entity = world.spawn((Health(1.0),));
+
+    let mut first = Commands::new();
+    first.insert_resource(1u8);
+    let mut second = Commands::new();
+    second.insert_resource(2u8);
+    second.despawn(entity);
+    first.append(&mut second);
+    assert!(second.is_empty());
+    assert_eq!(first.len(), 3);
+
+    // Removing from or despawning an entity that is already gone does nothing
+    first.remove::<Health>(entity);
+    first.despawn(entity);
+    first.apply(&mut world);
+
+    assert_eq!(world.resource::<u8>(), Some(&2));
+    assert!(!world.is_alive(entity));
+    assert!(world.is_empty());
+}
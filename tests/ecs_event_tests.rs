@@ -0,0 +1,126 @@
+use engine_2d::ecs::{Transform, Velocity, World};
+use engine_2d::engine::{Engine, EngineConfig, Game, UpdateContext};
+use engine_2d::events::{EntityEvent, EventSystem};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn describe(events: Vec<EntityEvent>) -> Vec<String> {
+    events
+        .into_iter()
+        .map(|event| match event {
+            EntityEvent::EntitySpawned { entity, .. } => format!("spawned {}", entity),
+            EntityEvent::EntityDespawned { entity, .. } => format!("despawned {}", entity),
+            EntityEvent::ComponentAdded {
+                entity, component, ..
+            } => format!(
+                "added {} to {}",
+                component.rsplit("::").next().unwrap(),
+                entity
+            ),
+        })
+        .collect()
+}
+
+#[test]
+fn test_spawn_and_despawn_send_events() {
+    let events = EventSystem::new();
+    let mut world = World::new();
+    world.set_event_sender(Some(events.get_entity_sender()));
+
+    let entity = world.spawn((Transform::default(), Velocity::default()));
+    world.despawn(entity);
+    world.despawn(entity);
+
+    assert_eq!(
+        describe(events.drain_entity_events()),
+        vec![
+            "spawned 0v0",
+            "added Transform to 0v0",
+            "added Velocity to 0v0",
+            "despawned 0v0",
+        ]
+    );
+}
+
+#[test]
+fn test_only_new_components_count_as_added() {
+    let events = EventSystem::new();
+    let mut world = World::new();
+    let entity = world.spawn(());
+    world.set_event_sender(Some(events.get_entity_sender()));
+
+    world.insert(entity, Velocity::default()).unwrap();
+    world.insert(entity, Velocity::default()).unwrap();
+    world.remove::<Velocity>(entity);
+    world.insert(entity, Velocity::default()).unwrap();
+
+    assert_eq!(
+        describe(events.drain_entity_events()),
+        vec!["added Velocity to 0v0", "added Velocity to 0v0"]
+    );
+}
+
+#[test]
+fn test_clear_despawns_and_detaching_stops_events() {
+    let events = EventSystem::new();
+    let mut world = World::new();
+    world.set_event_sender(Some(events.get_entity_sender()));
+    world.spawn(());
+    world.spawn(());
+    events.drain_entity_events();
+
+    world.clear();
+    assert_eq!(
+        describe(events.drain_entity_events()),
+        vec!["despawned 0v0", "despawned 1v0"]
+    );
+
+    world.set_event_sender(None);
+    world.spawn(());
+    assert!(events.drain_entity_events().is_empty());
+}
+
+/// Spawns one entity per update
+struct Spawner;
+
+impl Game for Spawner {
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        ctx.world.spawn((Transform::default(),));
+    }
+
+    fn name(&self) -> &str {
+        "Spawner"
+    }
+}
+
+#[test]
+fn test_engine_dispatches_entity_events_each_tick() {
+    let mut engine = Engine::new_headless(EngineConfig::default(), Box::new(Spawner)).unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let listener_seen = Arc::clone(&seen);
+    engine.event_system().add_entity_listener(move |event| {
+        listener_seen.lock().unwrap().push(event.clone());
+    });
+
+    engine.tick(Some(Duration::from_millis(16)));
+    assert_eq!(
+        describe(std::mem::take(&mut *seen.lock().unwrap())),
+        vec!["spawned 0v0", "added Transform to 0v0"]
+    );
+
+    // Dispatched events are gone, so nothing piles up between ticks
+    engine.tick(Some(Duration::from_millis(16)));
+    assert_eq!(seen.lock().unwrap().len(), 2);
+    assert!(engine.event_system().drain_entity_events().is_empty());
+}
+
+#[test]
+fn test_engine_leaves_entity_events_queued_without_listeners() {
+    let mut engine = Engine::new_headless(EngineConfig::default(), Box::new(Spawner)).unwrap();
+    let receiver = engine.event_system().get_entity_receiver();
+
+    engine.tick(Some(Duration::from_millis(16)));
+    engine.tick(Some(Duration::from_millis(16)));
+    let events: Vec<_> = receiver.lock().unwrap().try_iter().collect();
+    assert_eq!(events.len(), 4);
+}
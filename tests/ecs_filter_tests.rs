@@ -0,0 +1,71 @@
+use engine_2d::ecs::{Changed, Or, Scheduler, Stage, Transform, Velocity, With, Without, World};
+use glam::Vec2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Frozen;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Player;
+
+#[test]
+fn test_with_and_without_select_by_marker() {
+    let mut world = World::new();
+    let moving = world.spawn((Transform::default(), Velocity(Vec2::X)));
+    let frozen = world.spawn((Transform::default(), Velocity(Vec2::X), Frozen));
+    let still = world.spawn((Transform::default(),));
+
+    let with: Vec<_> = world
+        .query_filtered::<&Transform, With<Velocity>>()
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(with, vec![moving, frozen]);
+
+    let unfrozen: Vec<_> = world
+        .query_filtered::<&Transform, (With<Velocity>, Without<Frozen>)>()
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(unfrozen, vec![moving]);
+
+    // Filtering on a component type that was never inserted
+    let all: Vec<_> = world
+        .query_filtered::<&Transform, Without<Player>>()
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(all, vec![moving, frozen, still]);
+}
+
+#[test]
+fn test_or_matches_any_filter() {
+    let mut world = World::new();
+    let player = world.spawn((Transform::default(), Player));
+    let frozen = world.spawn((Transform::default(), Frozen));
+    world.spawn((Transform::default(),));
+    world.clear_trackers();
+    let moved = world.spawn((Transform::default(),));
+
+    let matched: Vec<_> = world
+        .query_filtered::<&Transform, Or<(With<Player>, With<Frozen>, Changed<Transform>)>>()
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(matched, vec![player, frozen, moved]);
+}
+
+#[test]
+fn test_filtered_mutable_query_only_changes_matches() {
+    let mut world = World::new();
+    let moving = world.spawn((Transform::default(), Velocity(Vec2::X)));
+    let frozen = world.spawn((Transform::default(), Velocity(Vec2::X), Frozen));
+    world.clear_trackers();
+
+    let mut scheduler = Scheduler::new();
+    scheduler.add_filtered_query_system::<(&mut Transform, &Velocity), Without<Frozen>, _>(
+        Stage::Physics,
+        "movement",
+        |_, (transform, velocity), dt| transform.position += velocity.0 * dt,
+    );
+    scheduler.run(&mut world, 1.0).unwrap();
+
+    assert_eq!(world.get::<Transform>(moving).unwrap().position, Vec2::X);
+    assert_eq!(world.get::<Transform>(frozen).unwrap().position, Vec2::ZERO);
+    assert!(!world.is_changed::<Transform>(frozen));
+}
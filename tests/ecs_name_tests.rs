@@ -0,0 +1,69 @@
+use engine_2d::ecs::{Transform, World};
+
+#[test]
+fn test_find_by_name() {
+    let mut world = World::new();
+    let player = world.spawn((Transform::default(),));
+    let camera = world.spawn(());
+
+    world.set_name(player, "player").unwrap();
+    world.set_name(camera, "camera").unwrap();
+    assert_eq!(world.find_by_name("player"), Some(player));
+    assert_eq!(world.name(camera), Some("camera"));
+    assert!(world.set_name(camera, "player").is_err());
+
+    world.set_name(player, "hero").unwrap();
+    assert_eq!(world.find_by_name("player"), None);
+    assert_eq!(world.find_by_name("hero"), Some(player));
+
+    assert_eq!(world.remove_name(player).as_deref(), Some("hero"));
+    assert_eq!(world.find_by_name("hero"), None);
+}
+
+#[test]
+fn test_tagged_entities() {
+    let mut world = World::new();
+    let a = world.spawn(());
+    let b = world.spawn(());
+    let c = world.spawn(());
+
+    assert!(world.add_tag(c, "enemy").unwrap());
+    assert!(world.add_tag(a, "enemy").unwrap());
+    assert!(!world.add_tag(a, "enemy").unwrap());
+    world.add_tag(a, "flying").unwrap();
+
+    assert_eq!(world.tagged("enemy").collect::<Vec<_>>(), vec![a, c]);
+    assert!(world.has_tag(a, "flying"));
+    assert!(!world.has_tag(b, "enemy"));
+    assert_eq!(
+        world.names().tags(a).collect::<Vec<_>>(),
+        vec!["enemy", "flying"]
+    );
+
+    assert!(world.remove_tag(a, "enemy"));
+    assert!(!world.remove_tag(a, "enemy"));
+    assert_eq!(world.tagged("enemy").collect::<Vec<_>>(), vec![c]);
+    assert_eq!(world.tagged("boss").count(), 0);
+}
+
+#[test]
+fn test_despawn_forgets_names_and_tags() {
+    let mut world = World::new();
+    let boss = world.spawn(());
+    world.set_name(boss, "boss").unwrap();
+    world.add_tag(boss, "enemy").unwrap();
+
+    world.despawn(boss);
+    assert_eq!(world.find_by_name("boss"), None);
+    assert_eq!(world.tagged("enemy").count(), 0);
+    assert!(world.set_name(boss, "ghost").is_err());
+    assert!(world.add_tag(boss, "enemy").is_err());
+
+    // The name is free for the entity reusing the slot
+    let next = world.spawn(());
+    world.set_name(next, "boss").unwrap();
+    world.add_tag(next, "enemy").unwrap();
+    world.clear();
+    assert_eq!(world.find_by_name("boss"), None);
+    assert_eq!(world.tagged("enemy").count(), 0);
+}
@@ -0,0 +1,110 @@
+use engine_2d::ecs::{Changed, Scheduler, Stage, Transform, Velocity, World};
+use glam::Vec2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Health(f32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Regen(f32);
+
+fn add_movement_and_regen(scheduler: &mut Scheduler) {
+    scheduler.add_query_system::<(&mut Transform, &Velocity), _>(
+        Stage::Update,
+        "movement",
+        |_, (transform, velocity), dt| transform.position += velocity.0 * dt,
+    );
+    scheduler.add_query_system::<(&mut Health, &Regen), _>(
+        Stage::Update,
+        "regen",
+        |_, (health, regen), dt| health.0 += regen.0 * dt,
+    );
+}
+
+#[test]
+fn test_non_conflicting_query_systems_share_a_batch() {
+    let mut scheduler = Scheduler::new();
+    add_movement_and_regen(&mut scheduler);
+    scheduler.add_query_system::<&Velocity, _>(Stage::Update, "read_velocity", |_, _, _| {});
+    scheduler.add_system(Stage::Update, "exclusive", |_, _| {});
+    scheduler.add_query_system::<&Health, _>(Stage::Update, "read_health", |_, _, _| {});
+    scheduler.add_query_system::<&Health, _>(Stage::Physics, "physics", |_, _, _| {});
+
+    assert_eq!(
+        scheduler.parallel_batches().unwrap(),
+        vec![
+            vec!["movement", "regen", "read_velocity"],
+            vec!["exclusive"],
+            vec!["read_health"],
+            vec!["physics"],
+        ]
+    );
+
+    scheduler.set_parallel(false);
+    assert_eq!(scheduler.parallel_batches().unwrap().len(), 6);
+}
+
+#[test]
+fn test_conflicting_or_ordered_systems_run_separately() {
+    let mut scheduler = Scheduler::new();
+    add_movement_and_regen(&mut scheduler);
+    scheduler.add_query_system::<&Transform, _>(Stage::Update, "read_transform", |_, _, _| {});
+    scheduler.add_filtered_query_system::<&Regen, Changed<Health>, _>(
+        Stage::Update,
+        "on_health_changed",
+        |_, _, _| {},
+    );
+    scheduler
+        .add_query_system::<&Velocity, _>(Stage::Update, "after_read", |_, _, _| {})
+        .after("read_transform");
+
+    assert_eq!(
+        scheduler.parallel_batches().unwrap(),
+        vec![
+            vec!["movement", "regen"],
+            vec!["read_transform", "on_health_changed"],
+            vec!["after_read"],
+        ]
+    );
+}
+
+#[test]
+fn test_parallel_and_serial_runs_agree() {
+    let results: Vec<(Vec<Vec2>, Vec<f32>, usize)> = [true, false]
+        .into_iter()
+        .map(|parallel| {
+            let mut world = World::new();
+            for i in 0..100 {
+                world.spawn((Transform::default(), Velocity(Vec2::new(i as f32, 1.0))));
+                world.spawn((Health(i as f32), Regen(2.0)));
+            }
+            let mut scheduler = Scheduler::new();
+            scheduler.set_parallel(parallel);
+            add_movement_and_regen(&mut scheduler);
+
+            let (sender, receiver) = std::sync::mpsc::channel();
+            scheduler.add_filtered_query_system::<&Health, Changed<Health>, _>(
+                Stage::Physics,
+                "count_changed",
+                move |_, _, _| sender.send(()).unwrap(),
+            );
+            scheduler.run(&mut world, 0.5).unwrap();
+            scheduler.run(&mut world, 0.5).unwrap();
+
+            let positions = world
+                .query::<&Transform>()
+                .map(|(_, transform)| transform.position)
+                .collect();
+            let health = world
+                .query::<&Health>()
+                .map(|(_, health)| health.0)
+                .collect();
+            (positions, health, receiver.try_iter().count())
+        })
+        .collect();
+
+    assert_eq!(results[0], results[1]);
+    let (positions, health, changed) = &results[0];
+    assert_eq!(positions[3], Vec2::new(3.0, 1.0));
+    assert_eq!(health[5], 7.0);
+    assert_eq!(*changed, 200);
+}
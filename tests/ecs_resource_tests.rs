@@ -0,0 +1,79 @@
+use engine_2d::ecs::{Res, Scheduler, Stage, Velocity, World};
+use glam::Vec2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Score(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Gravity(Vec2);
+
+#[test]
+fn test_insert_get_and_remove_resources() {
+    let mut world = World::new();
+    assert!(!world.has_resource::<Score>());
+    assert_eq!(world.insert_resource(Score(1)), None);
+    assert_eq!(world.insert_resource(Score(2)), Some(Score(1)));
+
+    world.resource_mut::<Score>().unwrap().0 += 3;
+    assert_eq!(world.resource::<Score>(), Some(&Score(5)));
+
+    world.clear();
+    assert_eq!(world.remove_resource::<Score>(), Some(Score(5)));
+    assert_eq!(world.resource::<Score>(), None);
+
+    world.resource_or_default::<Score>().0 += 1;
+    assert_eq!(world.resource::<Score>(), Some(&Score(1)));
+}
+
+#[test]
+fn test_systems_share_resources() {
+    let mut world = World::new();
+    world.insert_resource(Score::default());
+    world.spawn((Velocity::default(),));
+    world.spawn((Velocity::default(),));
+
+    let mut scheduler = Scheduler::new();
+    scheduler.add_system(Stage::Update, "score", |world, _| {
+        let count = world.query::<&Velocity>().count() as u32;
+        world.resource_or_default::<Score>().0 += count;
+    });
+    scheduler.run(&mut world, 0.016).unwrap();
+    scheduler.run(&mut world, 0.016).unwrap();
+
+    assert_eq!(world.resource::<Score>(), Some(&Score(4)));
+}
+
+#[test]
+fn test_res_in_query_systems() {
+    let mut world = World::new();
+    let falling = world.spawn((Velocity::default(),));
+
+    let mut scheduler = Scheduler::new();
+    scheduler.add_query_system::<(&mut Velocity, Res<Gravity>), _>(
+        Stage::Physics,
+        "gravity",
+        |_, (velocity, gravity), dt| velocity.0 += gravity.0 * dt,
+    );
+    scheduler.add_query_system::<(&Velocity, Res<Gravity>), _>(
+        Stage::Physics,
+        "read",
+        |_, _, _| {},
+    );
+
+    // No resource yet: the query matches nothing
+    scheduler.run(&mut world, 1.0).unwrap();
+    assert_eq!(world.get::<Velocity>(falling), Some(&Velocity(Vec2::ZERO)));
+
+    world.insert_resource(Gravity(Vec2::new(0.0, -10.0)));
+    scheduler.run(&mut world, 0.5).unwrap();
+    assert_eq!(
+        world.get::<Velocity>(falling),
+        Some(&Velocity(Vec2::new(0.0, -5.0)))
+    );
+
+    // Reading a resource doesn't conflict, but writing `Velocity` does
+    assert_eq!(
+        scheduler.parallel_batches().unwrap(),
+        vec![vec!["gravity"], vec!["read"]]
+    );
+}
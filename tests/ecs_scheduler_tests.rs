@@ -0,0 +1,99 @@
+use engine_2d::ecs::{Scheduler, Stage, World};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn recorder(
+    log: Rc<RefCell<Vec<&'static str>>>,
+    name: &'static str,
+) -> impl FnMut(&mut World, f32) {
+    move |_, _| log.borrow_mut().push(name)
+}
+
+#[test]
+fn test_systems_run_by_stage_then_constraints() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut scheduler = Scheduler::new();
+    scheduler.add_system(
+        Stage::RenderSubmit,
+        "draw",
+        recorder(Rc::clone(&log), "draw"),
+    );
+    scheduler.add_system(
+        Stage::Physics,
+        "collide",
+        recorder(Rc::clone(&log), "collide"),
+    );
+    scheduler
+        .add_system(Stage::Update, "steer", recorder(Rc::clone(&log), "steer"))
+        .after("ai");
+    scheduler.add_system(Stage::Update, "ai", recorder(Rc::clone(&log), "ai"));
+    scheduler
+        .add_system(Stage::Update, "score", recorder(Rc::clone(&log), "score"))
+        .before("ai");
+    scheduler.add_system(
+        Stage::Input,
+        "controls",
+        recorder(Rc::clone(&log), "controls"),
+    );
+
+    let mut world = World::new();
+    scheduler.run(&mut world, 0.016).unwrap();
+    assert_eq!(
+        *log.borrow(),
+        vec!["controls", "score", "ai", "steer", "collide", "draw"]
+    );
+    assert_eq!(
+        scheduler.order().unwrap(),
+        vec!["controls", "score", "ai", "steer", "collide", "draw"]
+    );
+
+    log.borrow_mut().clear();
+    scheduler
+        .run_stage(Stage::Update, &mut world, 0.016)
+        .unwrap();
+    assert_eq!(*log.borrow(), vec!["score", "ai", "steer"]);
+}
+
+#[test]
+fn test_invalid_constraints_are_reported() {
+    let mut world = World::new();
+
+    let mut cyclic = Scheduler::new();
+    cyclic.add_system(Stage::Update, "a", |_, _| {}).after("b");
+    cyclic.add_system(Stage::Update, "b", |_, _| {}).after("a");
+    assert!(cyclic.run(&mut world, 0.0).unwrap_err().contains("cyclic"));
+
+    let mut cross_stage = Scheduler::new();
+    cross_stage
+        .add_system(Stage::Input, "read", |_, _| {})
+        .after("move");
+    cross_stage.add_system(Stage::Physics, "move", |_, _| {});
+    assert!(cross_stage.run(&mut world, 0.0).is_err());
+
+    let mut unknown = Scheduler::new();
+    unknown
+        .add_system(Stage::Update, "a", |_, _| {})
+        .before("missing");
+    assert!(unknown.order().unwrap_err().contains("missing"));
+    assert!(unknown.remove_system("a"));
+    assert!(unknown.is_empty());
+}
+
+#[test]
+fn test_systems_mutate_the_world() {
+    struct Counter(u32);
+
+    let mut world = World::new();
+    let entity = world.spawn((Counter(0),));
+    let mut scheduler = Scheduler::new();
+    scheduler.add_system(Stage::Update, "count", |world, _| {
+        for (_, counter) in world.query::<&mut Counter>() {
+            counter.0 += 1;
+        }
+    });
+
+    for _ in 0..3 {
+        scheduler.run(&mut world, 0.016).unwrap();
+    }
+    assert_eq!(world.get::<Counter>(entity).unwrap().0, 3);
+}
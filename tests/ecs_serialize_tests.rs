@@ -0,0 +1,80 @@
+use engine_2d::component_set;
+use engine_2d::ecs::{Transform, Velocity, World, WorldSnapshot};
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Health(u32);
+
+/// Not part of the save set
+struct Sprite;
+
+component_set! {
+    enum SaveComponent { Transform, Velocity, Health }
+}
+
+#[test]
+fn test_snapshot_round_trips_into_a_new_world() {
+    let mut world = World::new();
+    let player = world.spawn((
+        Transform::from_position(Vec2::new(3.0, 4.0)),
+        Velocity(Vec2::X),
+        Health(80),
+        Sprite,
+    ));
+    let rock = world.spawn((Transform::default(),));
+
+    let snapshot = WorldSnapshot::<SaveComponent>::capture(&world);
+    assert_eq!(snapshot.entities.len(), 2);
+    assert_eq!(snapshot.entities[0].components.len(), 3);
+
+    let bytes = serde_json::to_vec(&snapshot).unwrap();
+    let saved: WorldSnapshot<SaveComponent> = serde_json::from_slice(&bytes).unwrap();
+
+    let mut loaded = World::new();
+    let mapping = saved.restore(&mut loaded);
+    assert_eq!(mapping.len(), 2);
+    let (saved_player, new_player) = mapping[0];
+    assert_eq!(saved_player, player);
+    assert_eq!(mapping[1].0, rock);
+
+    assert_eq!(loaded.get::<Health>(new_player), Some(&Health(80)));
+    assert_eq!(
+        loaded.get::<Transform>(new_player).unwrap().position,
+        Vec2::new(3.0, 4.0)
+    );
+    assert_eq!(
+        loaded
+            .get::<Velocity>(new_player)
+            .map(|velocity| velocity.0),
+        Some(Vec2::X)
+    );
+    assert!(!loaded.has::<Sprite>(new_player));
+    assert!(!loaded.has::<Velocity>(mapping[1].1));
+
+    // Saving the loaded world again gives the same components
+    let resaved = WorldSnapshot::<SaveComponent>::capture(&loaded);
+    let components = |snapshot: &WorldSnapshot<SaveComponent>| {
+        let components: Vec<_> = snapshot.entities.iter().map(|e| &e.components).collect();
+        serde_json::to_value(components).unwrap()
+    };
+    assert_eq!(components(&resaved), components(&snapshot));
+}
+
+#[test]
+fn test_capture_selected_entities_skips_despawned() {
+    let mut world = World::new();
+    let kept = world.spawn((Health(1),));
+    let gone = world.spawn((Health(2),));
+    world.spawn((Health(3),));
+    world.despawn(gone);
+
+    let snapshot = WorldSnapshot::<SaveComponent>::capture_entities(&world, &[kept, gone]);
+    assert_eq!(snapshot.entities.len(), 1);
+    assert_eq!(snapshot.entities[0].entity, kept);
+
+    let mut loaded = World::new();
+    snapshot.restore(&mut loaded);
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded.query::<&Health>().next().unwrap().1, &Health(1));
+}
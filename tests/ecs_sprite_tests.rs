@@ -0,0 +1,45 @@
+#![cfg(feature = "opengl")]
+
+use engine_2d::ecs::{SpriteComponent, Transform, World, collect_sprites};
+use engine_2d::render::sprite::Sprite;
+use engine_2d::render::texture::TextureId;
+use glam::Vec2;
+
+fn sprite(size: Vec2) -> SpriteComponent {
+    SpriteComponent(Sprite::new(TextureId(1), Vec2::ZERO, size))
+}
+
+#[test]
+fn test_sprites_follow_their_transform() {
+    let mut world = World::new();
+    world.spawn((
+        Transform::from_position(Vec2::new(10.0, 20.0)).with_scale(Vec2::new(2.0, 0.5)),
+        sprite(Vec2::new(16.0, 16.0)),
+    ));
+
+    let sprites = collect_sprites(&mut world);
+    assert_eq!(sprites.len(), 1);
+    assert_eq!(sprites[0].position, Vec2::new(10.0, 20.0));
+    assert_eq!(sprites[0].size, Vec2::new(32.0, 8.0));
+}
+
+#[test]
+fn test_entities_need_both_components() {
+    let mut world = World::new();
+    world.spawn((Transform::default(),));
+    world.spawn((sprite(Vec2::ONE),));
+    let drawn = world.spawn((Transform::default(), sprite(Vec2::ONE)));
+
+    assert_eq!(collect_sprites(&mut world).len(), 1);
+    world.despawn(drawn);
+    assert!(collect_sprites(&mut world).is_empty());
+}
+
+#[test]
+fn test_hidden_sprites_are_skipped() {
+    let mut world = World::new();
+    let entity = world.spawn((Transform::default(), sprite(Vec2::ONE)));
+    world.get_mut::<SpriteComponent>(entity).unwrap().0.visible = false;
+
+    assert!(collect_sprites(&mut world).is_empty());
+}
@@ -0,0 +1,107 @@
+use engine_2d::ecs::stats::short_type_name;
+use engine_2d::ecs::{Scheduler, Stage, Transform, Velocity, World};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Enemy;
+
+fn world() -> World {
+    let mut world = World::new();
+    for _ in 0..3 {
+        world.spawn((Transform::default(), Velocity::default()));
+    }
+    world.spawn((Transform::default(), Velocity::default(), Enemy));
+    world.spawn((Transform::default(),));
+    world.spawn(());
+    world.insert_resource(0u32);
+    world
+}
+
+/// Ties are ordered by full type names, so `ecs_stats_tests::Enemy` sorts before
+/// `engine_2d::ecs::component::Transform`
+#[test]
+fn test_stats_group_entities_by_archetype() {
+    let stats = world().stats();
+    assert_eq!(stats.entities, 6);
+    assert_eq!(stats.resources, 1);
+
+    let archetypes: Vec<(Vec<String>, usize)> = stats
+        .archetypes
+        .iter()
+        .map(|archetype| {
+            let names = archetype
+                .components
+                .iter()
+                .map(|name| short_type_name(name))
+                .collect();
+            (names, archetype.entities)
+        })
+        .collect();
+    assert_eq!(
+        archetypes,
+        vec![
+            (vec!["Transform".to_string(), "Velocity".to_string()], 3),
+            (vec![], 1),
+            (
+                vec![
+                    "Enemy".to_string(),
+                    "Transform".to_string(),
+                    "Velocity".to_string()
+                ],
+                1
+            ),
+            (vec!["Transform".to_string()], 1),
+        ]
+    );
+}
+
+#[test]
+fn test_stats_report_component_memory() {
+    let stats = world().stats();
+    let transforms = stats
+        .components
+        .iter()
+        .find(|component| component.name.ends_with("Transform"))
+        .unwrap();
+    assert_eq!(transforms.count, 5);
+    assert!(transforms.bytes >= 5 * std::mem::size_of::<Option<Transform>>());
+    assert_eq!(
+        stats.component_bytes(),
+        stats.components.iter().map(|c| c.bytes).sum::<usize>()
+    );
+
+    let text = stats.to_string();
+    assert!(text.starts_with("6 entities, 4 archetypes, 1 resources\n"));
+    assert!(text.contains("Enemy, Transform, Velocity"));
+    assert!(!text.contains("systems:"));
+}
+
+#[test]
+fn test_system_timings() {
+    let mut world = world();
+    let mut scheduler = Scheduler::new();
+    scheduler.add_system(Stage::Update, "slow", |_, _| {
+        std::thread::sleep(Duration::from_millis(5))
+    });
+    scheduler.add_query_system::<&Transform, _>(Stage::Input, "fast", |_, _, _| {});
+    assert!(
+        scheduler
+            .stats()
+            .iter()
+            .all(|system| system.last_run.is_zero())
+    );
+
+    scheduler.run(&mut world, 0.016).unwrap();
+    let systems = scheduler.stats();
+    assert_eq!(systems[0].name, "fast");
+    assert_eq!(systems[1].stage, Stage::Update);
+    assert!(systems[1].last_run >= Duration::from_millis(5));
+
+    let text = world.stats().with_systems(systems).to_string();
+    assert!(text.contains("systems:"));
+    assert!(text.contains("slow (Update)"));
+    assert_eq!(
+        short_type_name("alloc::vec::Vec<a::b::Enemy>"),
+        "Vec<Enemy>"
+    );
+}
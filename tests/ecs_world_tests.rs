@@ -0,0 +1,61 @@
+use engine_2d::ecs::{Transform, Velocity, World};
+use glam::Vec2;
+
+#[derive(Debug, PartialEq)]
+struct Health(u32);
+
+#[test]
+fn test_spawn_get_and_despawn() {
+    let mut world = World::new();
+    let player = world.spawn((Transform::default(), Health(10)));
+    assert_eq!(world.len(), 1);
+    assert_eq!(world.get::<Health>(player), Some(&Health(10)));
+    assert!(!world.has::<Velocity>(player));
+
+    world.get_mut::<Health>(player).unwrap().0 = 7;
+    assert_eq!(world.remove::<Health>(player), Some(Health(7)));
+    assert!(!world.has::<Health>(player));
+
+    assert!(world.despawn(player));
+    assert!(!world.despawn(player));
+    assert!(world.is_empty());
+
+    // The slot is reused, but the stale handle doesn't see the new entity
+    let enemy = world.spawn((Health(3),));
+    assert_eq!(enemy.index(), player.index());
+    assert!(!world.is_alive(player));
+    assert_eq!(world.get::<Health>(player), None);
+    assert!(world.insert(player, Velocity::default()).is_err());
+}
+
+#[test]
+fn test_query_updates_matching_entities() {
+    let mut world = World::new();
+    let moving = world.spawn((Transform::default(), Velocity(Vec2::new(2.0, 0.0))));
+    let still = world.spawn((Transform::from_position(Vec2::ONE),));
+    let ghost = world.spawn((Velocity(Vec2::Y),));
+
+    let mut visited = Vec::new();
+    for (entity, (transform, velocity)) in world.query::<(&mut Transform, &Velocity)>() {
+        transform.position += velocity.0 * 0.5;
+        visited.push(entity);
+    }
+
+    assert_eq!(visited, vec![moving]);
+    assert_eq!(
+        world.get::<Transform>(moving).unwrap().position,
+        Vec2::new(1.0, 0.0)
+    );
+    assert_eq!(world.get::<Transform>(still).unwrap().position, Vec2::ONE);
+    assert_eq!(world.query::<&Velocity>().count(), 2);
+    assert!(world.query::<&Health>().next().is_none());
+    assert!(world.is_alive(ghost));
+}
+
+#[test]
+#[should_panic(expected = "mutably more than once")]
+fn test_query_rejects_aliasing_access() {
+    let mut world = World::new();
+    world.spawn((Transform::default(),));
+    let _ = world.query::<(&mut Transform, &Transform)>();
+}
@@ -0,0 +1,110 @@
+#![cfg(not(feature = "opengl"))]
+
+use engine_2d::animation::Animation;
+use engine_2d::ecs::{Entity, Transform};
+use engine_2d::engine::{Engine, EngineConfig, Game, LegacyAnimation, UpdateContext};
+use glam::Vec2;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Spawns a marker entity on the first update and moves it along x afterwards
+struct Mover {
+    entity: Option<Entity>,
+}
+
+impl Game for Mover {
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        let entity = *self
+            .entity
+            .get_or_insert_with(|| ctx.world.spawn((Transform::default(),)));
+        if let Some(transform) = ctx.world.get_mut::<Transform>(entity) {
+            transform.position.x += 10.0 * ctx.delta_time;
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Mover"
+    }
+}
+
+/// Records the times an old-style animation is updated with
+struct Recorder(Arc<Mutex<Vec<(f32, f32)>>>);
+
+impl Animation for Recorder {
+    fn update(&mut self, elapsed_time: f32, delta_time: f32) {
+        self.0.lock().unwrap().push((elapsed_time, delta_time));
+    }
+
+    fn name(&self) -> &str {
+        "Recorder"
+    }
+}
+
+#[test]
+fn test_game_updates_with_the_world() {
+    let mut engine =
+        Engine::new_with_config_and_game(EngineConfig::default(), Box::new(Mover { entity: None }))
+            .unwrap();
+    for _ in 0..2 {
+        engine.tick(Some(Duration::from_millis(500)));
+    }
+
+    let mut positions = engine
+        .world_mut()
+        .query::<&Transform>()
+        .map(|(_, t)| t.position);
+    assert_eq!(positions.next(), Some(Vec2::new(10.0, 0.0)));
+    assert_eq!(positions.next(), None);
+}
+
+/// Records the scaled and real delta times of each update
+struct Clock(Arc<Mutex<Vec<(f32, f32)>>>);
+
+impl Game for Clock {
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((ctx.delta_time, ctx.real_delta_time));
+    }
+
+    fn name(&self) -> &str {
+        "Clock"
+    }
+}
+
+#[test]
+fn test_real_delta_time_ignores_the_animation_time() {
+    let deltas = Arc::new(Mutex::new(Vec::new()));
+    let game = Box::new(Clock(Arc::clone(&deltas)));
+    let mut engine = Engine::new_with_config_and_game(EngineConfig::default(), game).unwrap();
+    engine.animation_time_mut().set_scale(0.5);
+    engine.tick(Some(Duration::from_millis(500)));
+    engine.animation_time_mut().pause();
+    engine.tick(Some(Duration::from_millis(500)));
+
+    assert_eq!(*deltas.lock().unwrap(), vec![(0.25, 0.5), (0.0, 0.5)]);
+}
+
+#[test]
+fn test_animations_run_through_the_legacy_shim() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let animation = Box::new(Recorder(Arc::clone(&calls)));
+    let mut engine =
+        Engine::new_with_config_and_animation(EngineConfig::default(), animation).unwrap();
+    engine.animation_time_mut().set_scale(0.5);
+    engine.tick(Some(Duration::from_millis(500)));
+    engine.tick(Some(Duration::from_millis(500)));
+
+    assert_eq!(*calls.lock().unwrap(), vec![(0.25, 0.25), (0.5, 0.25)]);
+}
+
+#[test]
+fn test_legacy_animation_exposes_the_wrapped_animation() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let mut shim = LegacyAnimation::new(Box::new(Recorder(Arc::clone(&calls))));
+    assert_eq!(shim.name(), "Recorder");
+    shim.animation_mut().update(1.0, 0.5);
+    assert_eq!(shim.animation().name(), "Recorder");
+    assert_eq!(calls.lock().unwrap().len(), 1);
+}
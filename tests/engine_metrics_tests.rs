@@ -0,0 +1,77 @@
+use engine_2d::engine::{MetricsConfig, MetricsExporter, MetricsOutput};
+use std::fs;
+
+fn csv_config(name: &str) -> (MetricsConfig, std::path::PathBuf) {
+    let path = std::env::temp_dir().join(format!("engine_2d_{}_{}.csv", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    let config = MetricsConfig {
+        enabled: true,
+        output: MetricsOutput::Csv {
+            path: path.to_string_lossy().into_owned(),
+        },
+        interval_secs: 1.0,
+    };
+    (config, path)
+}
+
+#[test]
+fn test_exporter_summarizes_each_interval() {
+    let (config, path) = csv_config("summary");
+    let mut metrics = MetricsExporter::new(&config).unwrap();
+    metrics.set_entity_count(42);
+
+    for frame in 0..10 {
+        let frame_time = if frame == 9 { 0.19 } else { 0.09 };
+        metrics.record_frame(frame_time, 3).unwrap();
+    }
+
+    let snapshot = metrics.latest().expect("one interval was recorded");
+    assert_eq!(snapshot.frames, 10);
+    assert!((snapshot.fps - 10.0).abs() < 0.01);
+    assert!((snapshot.frame_time_max_ms - 190.0).abs() < 0.01);
+    assert!((snapshot.frame_time_p99_ms - 190.0).abs() < 0.01);
+    assert_eq!(snapshot.draw_calls, 3.0);
+    assert_eq!(snapshot.entities, 42);
+
+    let contents = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("uptime_secs,frames,fps"));
+    assert!(lines[1].starts_with("1.000,10,10.00,"));
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn test_prometheus_text_lists_gauges() {
+    let (config, path) = csv_config("prometheus");
+    let mut metrics = MetricsExporter::new(&config).unwrap();
+    metrics.record_frame(0.5, 7).unwrap();
+    metrics.export().unwrap();
+
+    let text = metrics.latest().unwrap().to_prometheus();
+    assert!(text.contains("# TYPE engine_fps gauge\nengine_fps 2\n"));
+    assert!(text.contains("engine_draw_calls 7\n"));
+    let _ = fs::remove_file(&path);
+}
+
+#[cfg(not(feature = "opengl"))]
+#[test]
+fn test_headless_engine_records_frames() {
+    use engine_2d::engine::{Engine, EngineConfig};
+    use std::time::Duration;
+
+    let (metrics, path) = csv_config("headless");
+    let mut engine = Engine::new_with_config(EngineConfig {
+        metrics,
+        ..EngineConfig::default()
+    })
+    .unwrap();
+    for _ in 0..4 {
+        engine.tick(Some(Duration::from_millis(250)));
+    }
+
+    let latest = engine.metrics_mut().unwrap().latest().cloned().unwrap();
+    assert_eq!(latest.frames, 4);
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+    let _ = fs::remove_file(&path);
+}
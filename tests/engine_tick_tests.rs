@@ -0,0 +1,65 @@
+#![cfg(not(feature = "opengl"))]
+
+use engine_2d::engine::{Engine, TickResult};
+use std::time::Duration;
+
+#[test]
+fn test_tick_uses_dt_override() {
+    let mut engine = Engine::new().unwrap();
+
+    for _ in 0..4 {
+        assert_eq!(
+            engine.tick(Some(Duration::from_millis(250))),
+            TickResult::Skipped
+        );
+    }
+
+    assert!((engine.elapsed_time() - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_tick_exits_after_quit() {
+    let mut engine = Engine::new().unwrap();
+    engine.quit();
+
+    assert_eq!(engine.tick(None), TickResult::Exit);
+    assert_eq!(engine.elapsed_time(), 0.0);
+}
+
+#[test]
+fn test_tick_runs_scheduled_systems() {
+    use engine_2d::ecs::{Stage, Transform, Velocity};
+    use glam::Vec2;
+
+    let mut engine = Engine::new().unwrap();
+    let body = engine
+        .world_mut()
+        .spawn((Transform::default(), Velocity(Vec2::new(4.0, 0.0))));
+    engine
+        .scheduler_mut()
+        .add_system(Stage::Physics, "integrate", |world, delta_time| {
+            for (_, (transform, velocity)) in world.query::<(&mut Transform, &Velocity)>() {
+                transform.position += velocity.0 * delta_time;
+            }
+        });
+
+    for _ in 0..2 {
+        engine.tick(Some(Duration::from_millis(250)));
+    }
+
+    let position = engine.world().get::<Transform>(body).unwrap().position;
+    assert!((position.x - 2.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_animation_time_scale_slows_the_animation_clock() {
+    let mut engine = Engine::new().unwrap();
+    engine.animation_time_mut().set_scale(0.5);
+    engine.tick(Some(Duration::from_millis(500)));
+    engine.animation_time_mut().pause();
+    engine.tick(Some(Duration::from_millis(500)));
+
+    assert!((engine.elapsed_time() - 1.0).abs() < 1e-5);
+    assert!((engine.animation_elapsed_time() - 0.25).abs() < 1e-5);
+    assert!(engine.animation_time().is_paused());
+}
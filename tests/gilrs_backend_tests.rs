@@ -0,0 +1,45 @@
+#![cfg(feature = "gamepad")]
+
+use engine_2d::input::gilrs_backend::{map_axis, map_button, translate_event};
+use engine_2d::input::{GamepadAxis, GamepadButton, GamepadEvent, GamepadInput};
+use gilrs::{Axis, Button, EventType};
+
+#[test]
+fn test_button_and_axis_mapping() {
+    assert_eq!(
+        map_button(Button::South),
+        &[GamepadButton::South, GamepadButton::A]
+    );
+    assert_eq!(
+        map_button(Button::LeftTrigger),
+        &[GamepadButton::LeftShoulder]
+    );
+    assert_eq!(
+        map_button(Button::RightTrigger2),
+        &[GamepadButton::RightTrigger]
+    );
+    assert_eq!(map_button(Button::Mode), &[GamepadButton::Guide]);
+    assert!(map_button(Button::Unknown).is_empty());
+
+    assert_eq!(map_axis(Axis::LeftStickY), Some(GamepadAxis::LeftStickY));
+    assert_eq!(map_axis(Axis::RightZ), Some(GamepadAxis::RightTrigger));
+    assert_eq!(map_axis(Axis::DPadX), None);
+}
+
+#[test]
+fn test_hotplug_events() {
+    let mut gamepads = GamepadInput::new();
+    gamepads.handle_event(GamepadEvent::Connected {
+        id: 3,
+        name: "Pad".to_string(),
+    });
+
+    // Connections are reported by the backend, which knows the device name
+    assert!(translate_event(3, EventType::Connected).is_empty());
+    assert!(translate_event(3, EventType::Dropped).is_empty());
+
+    for event in translate_event(3, EventType::Disconnected) {
+        gamepads.handle_event(event);
+    }
+    assert!(gamepads.get_gamepad(3).is_none());
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "opengl")]
+
+use engine_2d::engine::{Engine, EngineConfig, Game, TickResult, UpdateContext};
+use std::time::Duration;
+
+struct Idle;
+
+impl Game for Idle {
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        assert!(ctx.window_manager.is_none());
+        assert!(ctx.lighting.is_none());
+        assert!(ctx.view.is_none());
+    }
+
+    fn name(&self) -> &str {
+        "Idle"
+    }
+}
+
+#[test]
+fn test_headless_engine_has_no_window_or_renderers() {
+    let mut engine = Engine::new_headless(EngineConfig::default(), Box::new(Idle)).unwrap();
+    assert!(engine.is_headless());
+    assert_eq!(
+        engine.tick(Some(Duration::from_millis(16))),
+        TickResult::Skipped
+    );
+
+    assert!(engine.try_get_window_manager().is_none());
+    assert!(engine.try_get_window_manager_mut().is_none());
+    assert!(engine.try_get_sprite_renderer().is_none());
+    assert!(engine.try_get_lighting_renderer().is_none());
+    assert!(engine.try_viewport_rect().is_none());
+    assert!(engine.try_view_mapping().is_none());
+    assert!(engine.try_get_idle_tracker().is_none());
+    assert!(engine.try_text_renderer().is_none());
+    assert!(engine.try_text_renderer_mut().is_none());
+}
+
+#[test]
+#[should_panic(expected = "headless engine")]
+fn test_headless_engine_getters_panic() {
+    let engine = Engine::new_headless(EngineConfig::default(), Box::new(Idle)).unwrap();
+    let _ = engine.get_window_manager();
+}
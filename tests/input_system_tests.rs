@@ -2,6 +2,7 @@
 ///
 /// These tests verify that the input system works correctly without requiring
 /// a graphical window or user interaction.
+use engine_2d::events::Consumable;
 use engine_2d::input::*;
 
 #[test]
@@ -182,6 +183,66 @@ fn test_analog_input_values() {
     assert_eq!(value, 0.0); // Should be zero due to deadzone
 }
 
+#[test]
+fn test_mouse_deltas_as_analog_actions() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_actions(get_all_actions());
+    input_manager.register_action(GameAction {
+        id: "LOOK_X".to_string(),
+        display_name: "Look X".to_string(),
+        category: ActionCategory::Movement,
+        input_type: InputType::Analog,
+        default_bindings: vec![
+            InputBinding::Single(PhysicalInput::MouseAxis(MouseAxis::X)),
+            InputBinding::Analog {
+                input: PhysicalInput::GamepadAxis(GamepadAxis::RightStickX),
+                threshold: 1.0,
+                deadzone: 0.1,
+            },
+        ],
+        metadata: ActionMetadata::default(),
+    });
+    input_manager.set_analog_settings(
+        "LOOK_X",
+        AnalogSettings::default()
+            .with_curve(ResponseCurve::Squared)
+            .with_sensitivity(0.01)
+            .with_inverted(true),
+    );
+
+    // Scroll steps and pixel deltas come through unclamped, scaled by sensitivity only
+    let mut mouse = MouseInput::new();
+    mouse.handle_scroll(0.0, 2.0);
+    mouse.handle_mouse_move(150.0, 0.0);
+    mouse.update_input_manager(&mut input_manager);
+    input_manager.update(0.016);
+    assert_eq!(input_manager.get_action_value("CAMERA_ZOOM"), 2.0);
+    assert!((input_manager.get_action_value("LOOK_X") + 1.5).abs() < 1e-6);
+
+    // With the mouse still, the stick bound to the same action still counts
+    mouse.update();
+    mouse.update_input_manager(&mut input_manager);
+    input_manager
+        .set_physical_input_value(PhysicalInput::GamepadAxis(GamepadAxis::RightStickX), 0.5);
+    input_manager.update(0.016);
+    assert_eq!(input_manager.get_action_value("CAMERA_ZOOM"), 0.0);
+    assert!((input_manager.get_action_value("LOOK_X") + 0.0025).abs() < 1e-6);
+}
+
+#[test]
+fn test_default_mouse_look_scales_with_the_mouse_delta() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_actions(get_all_actions());
+    input_manager.set_action_sensitivity("MOUSE_LOOK_X", 0.1);
+
+    let mut mouse = MouseInput::new();
+    mouse.handle_mouse_move(40.0, -3.0);
+    mouse.update_input_manager(&mut input_manager);
+    input_manager.update(0.016);
+    assert!((input_manager.get_action_value("MOUSE_LOOK_X") - 4.0).abs() < 1e-6);
+    assert!((input_manager.get_action_value("MOUSE_LOOK_Y") + 3.0).abs() < 1e-6);
+}
+
 #[test]
 fn test_keyboard_input_handler() {
     let mut keyboard = KeyboardInput::new();
@@ -445,3 +506,746 @@ fn test_simultaneous_input_handling() {
     assert!(input_manager.is_action_held("MOVE_UP"));
     assert!(input_manager.is_action_released("MOVE_LEFT"));
 }
+
+#[test]
+fn test_response_curves() {
+    assert_eq!(ResponseCurve::Linear.evaluate(0.5), 0.5);
+    assert_eq!(ResponseCurve::Squared.evaluate(0.5), 0.25);
+    assert_eq!(ResponseCurve::Power(3.0).evaluate(0.5), 0.125);
+
+    let custom = ResponseCurve::parse_points("# input, output\n1.0, 1.0\n0.0, 0.0\n0.5, 0.2\n")
+        .expect("valid curve");
+    assert!((custom.evaluate(0.25) - 0.1).abs() < 1e-6);
+    assert!((custom.evaluate(0.75) - 0.6).abs() < 1e-6);
+    assert_eq!(custom.evaluate(2.0), 1.0);
+
+    assert!(ResponseCurve::parse_points("0.0; 1.0").is_err());
+    assert!(ResponseCurve::parse_points("").is_err());
+}
+
+#[test]
+fn test_analog_action_sensitivity_and_inversion() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_action(GameAction {
+        id: "LOOK_X".to_string(),
+        display_name: "Look X".to_string(),
+        category: ActionCategory::Movement,
+        input_type: InputType::Analog,
+        default_bindings: vec![InputBinding::Analog {
+            input: PhysicalInput::GamepadAxis(GamepadAxis::RightStickX),
+            threshold: 1.0,
+            deadzone: 0.1,
+        }],
+        metadata: ActionMetadata::default(),
+    });
+    input_manager.set_raw_value(PhysicalInput::GamepadAxis(GamepadAxis::RightStickX), -0.5);
+    input_manager.update(0.016);
+
+    assert_eq!(input_manager.get_action_value("LOOK_X"), -0.5);
+
+    input_manager.set_analog_settings(
+        "LOOK_X",
+        AnalogSettings::default()
+            .with_curve(ResponseCurve::Squared)
+            .with_sensitivity(2.0),
+    );
+    assert_eq!(input_manager.get_action_value("LOOK_X"), -0.5);
+
+    input_manager.set_action_inverted("LOOK_X", true);
+    assert_eq!(input_manager.get_action_value("LOOK_X"), 0.5);
+
+    input_manager.set_action_sensitivity("LOOK_X", 1.0);
+    assert_eq!(input_manager.get_action_value("LOOK_X"), 0.25);
+    assert!(input_manager.analog_settings("LOOK_X").inverted);
+
+    input_manager.reset_analog_settings("LOOK_X");
+    assert_eq!(
+        input_manager.analog_settings("LOOK_X"),
+        AnalogSettings::default()
+    );
+}
+
+#[test]
+fn test_gamepad_axis_calibration() {
+    let mut gamepad = GamepadState::new(0, "Worn Stick".to_string());
+    let mut calibration = GamepadCalibration::default();
+    calibration.set_axis(
+        GamepadAxis::LeftTrigger,
+        AxisCalibration::default()
+            .with_center(-1.0)
+            .with_deadzone(0.2)
+            .with_saturation(1.8)
+            .with_rescale(true),
+    );
+    gamepad.set_calibration(calibration);
+
+    // Triggers rest at -1.0 on this pad and never quite reach 1.0
+    gamepad.set_axis(GamepadAxis::LeftTrigger, -0.9);
+    assert_eq!(gamepad.get_axis(GamepadAxis::LeftTrigger), 0.0);
+    gamepad.set_axis(GamepadAxis::LeftTrigger, 0.9);
+    assert_eq!(gamepad.get_axis(GamepadAxis::LeftTrigger), 1.0);
+    assert_eq!(gamepad.raw_axis(GamepadAxis::LeftTrigger), 0.9);
+
+    // Radial grouping keeps diagonal input that per-axis deadzones would cut
+    gamepad.set_axis(GamepadAxis::LeftStickX, 0.08);
+    gamepad.set_axis(GamepadAxis::LeftStickY, 0.08);
+    assert_eq!(gamepad.get_axis(GamepadAxis::LeftStickX), 0.0);
+    let mut radial = gamepad.calibration.clone();
+    radial.radial_sticks = true;
+    gamepad.set_calibration(radial);
+    assert!(gamepad.get_axis(GamepadAxis::LeftStickX) > 0.0);
+
+    let reading = gamepad
+        .axis_readings()
+        .into_iter()
+        .find(|reading| reading.axis == GamepadAxis::LeftTrigger)
+        .unwrap();
+    assert_eq!((reading.raw, reading.processed), (0.9, 1.0));
+}
+
+#[test]
+fn test_gamepad_calibration_settings_round_trip() {
+    let mut gamepads = GamepadInput::new();
+    gamepads.handle_event(GamepadEvent::Connected {
+        id: 0,
+        name: "Test Controller".to_string(),
+    });
+    let mut calibration = GamepadCalibration {
+        radial_sticks: true,
+        ..Default::default()
+    };
+    calibration.set_axis(
+        GamepadAxis::RightStickY,
+        AxisCalibration::default()
+            .with_center(0.05)
+            .with_deadzone(0.15),
+    );
+    gamepads.set_calibration(0, calibration.clone());
+
+    let settings = gamepads.calibrations_to_settings();
+    assert!(settings.contains("[\"Test Controller\"]"), "{}", settings);
+
+    // A new session applies the saved calibration when the same device connects
+    let mut restored = GamepadInput::new();
+    restored.load_calibrations_from_settings(&settings).unwrap();
+    restored.handle_event(GamepadEvent::Connected {
+        id: 3,
+        name: "Test Controller".to_string(),
+    });
+    assert_eq!(restored.get_gamepad(3).unwrap().calibration, calibration);
+
+    // A bad file leaves the loaded calibrations untouched
+    let error = restored
+        .load_calibrations_from_settings(
+            "[\"Test Controller\"]\nradial_sticks = false\n[\"Test Controller\".axes.LeftStickZ]\n",
+        )
+        .unwrap_err();
+    assert!(error.contains("line 3"), "{}", error);
+    assert_eq!(
+        restored.calibration_for("Test Controller"),
+        Some(&calibration)
+    );
+}
+
+#[test]
+fn test_gamepad_calibration_device_names_round_trip() {
+    let mut gamepads = GamepadInput::new();
+    for (id, name) in [(0, "Pad #2"), (1, "[Pad] = \"Pro\"")] {
+        gamepads.handle_event(GamepadEvent::Connected {
+            id,
+            name: name.to_string(),
+        });
+        let calibration = GamepadCalibration {
+            default_axis: AxisCalibration::default().with_deadzone(0.2 + id as f32 * 0.05),
+            ..Default::default()
+        };
+        gamepads.set_calibration(id, calibration);
+    }
+
+    let mut restored = GamepadInput::new();
+    restored
+        .load_calibrations_from_settings(&gamepads.calibrations_to_settings())
+        .unwrap();
+    for name in ["Pad #2", "[Pad] = \"Pro\""] {
+        assert_eq!(
+            restored.calibration_for(name),
+            gamepads.calibration_for(name),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_modal_blocks_gameplay_until_released() {
+    let mut input_manager = InputManager::new();
+    let key = PhysicalInput::Keyboard(KeyCode::Enter);
+    for (id, category) in [
+        ("JUMP", ActionCategory::Movement),
+        ("CONFIRM", ActionCategory::UI),
+    ] {
+        input_manager.register_action(GameAction {
+            id: id.to_string(),
+            display_name: id.to_string(),
+            category,
+            input_type: InputType::Digital,
+            default_bindings: vec![InputBinding::Single(key.clone())],
+            metadata: ActionMetadata::default(),
+        });
+    }
+
+    input_manager.open_modal("pause_menu", true);
+    assert!(input_manager.is_paused());
+    assert_eq!(input_manager.current_modal(), Some("pause_menu"));
+
+    // Confirming the menu doesn't reach gameplay
+    input_manager.set_physical_input_state(key.clone(), true);
+    input_manager.update(0.016);
+    assert!(input_manager.is_action_pressed("CONFIRM"));
+    assert!(!input_manager.is_action_pressed("JUMP"));
+
+    // Closing while the key is still down doesn't make the character jump
+    assert_eq!(input_manager.close_modal().as_deref(), Some("pause_menu"));
+    assert!(!input_manager.is_modal_open() && !input_manager.is_paused());
+    input_manager.update(0.016);
+    assert!(!input_manager.is_action_held("JUMP"));
+
+    // Once released, the next press works again
+    input_manager.set_physical_input_state(key.clone(), false);
+    input_manager.update(0.016);
+    input_manager.set_physical_input_state(key, true);
+    input_manager.update(0.016);
+    assert!(input_manager.is_action_pressed("JUMP"));
+}
+
+fn jump_action() -> GameAction {
+    GameAction {
+        id: "JUMP".to_string(),
+        display_name: "Jump".to_string(),
+        category: ActionCategory::Movement,
+        input_type: InputType::Digital,
+        default_bindings: vec![InputBinding::Single(PhysicalInput::Keyboard(
+            KeyCode::Space,
+        ))],
+        metadata: ActionMetadata::default(),
+    }
+}
+
+#[test]
+fn test_binding_text_round_trip() {
+    for text in [
+        "Keyboard:W",
+        "Mouse:Other(6)",
+        "Keyboard:LeftCtrl+Keyboard:S",
+        "combo(Gamepad:A, Gamepad:B)",
+        "analog(GamepadAxis:LeftStickX, 0.5, 0.1)",
+        "sequence(0.25, Keyboard:S, Keyboard:S+Keyboard:D, Keyboard:D+Keyboard:J)",
+        "stick(GamepadAxis:LeftStickX, 0.5, 0.15, radial, squared)",
+        "stick(GamepadAxis:RightStickY, 0.5, 0.1, axial, custom(0:0 0.5:0.2 1:1))",
+    ] {
+        let binding = InputBinding::parse(text).unwrap();
+        assert_eq!(binding.to_string(), text);
+    }
+    assert!(InputBinding::parse("Keyboard:NotAKey").is_err());
+    assert!(InputBinding::parse("W").is_err());
+}
+
+#[test]
+fn test_rebinding_overrides_defaults() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_action(jump_action());
+    let enter = PhysicalInput::Keyboard(KeyCode::Enter);
+
+    input_manager
+        .set_bindings("JUMP", vec![InputBinding::Single(enter.clone())])
+        .unwrap();
+    assert!(input_manager.set_bindings("FLY", Vec::new()).is_err());
+    input_manager.set_physical_input_state(enter.clone(), true);
+    input_manager.update(0.016);
+    assert!(input_manager.is_action_pressed("JUMP"));
+
+    input_manager.reset_bindings("JUMP");
+    assert_eq!(
+        input_manager.bindings("JUMP"),
+        &[InputBinding::Single(PhysicalInput::Keyboard(
+            KeyCode::Space
+        ))]
+    );
+    input_manager.update(0.016);
+    assert!(!input_manager.is_action_held("JUMP"));
+}
+
+#[test]
+fn test_binding_profile_save_and_load() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_action(jump_action());
+    input_manager
+        .set_bindings(
+            "JUMP",
+            vec![
+                InputBinding::parse("Keyboard:Up").unwrap(),
+                InputBinding::parse("Gamepad:South").unwrap(),
+            ],
+        )
+        .unwrap();
+
+    let dir = std::env::temp_dir().join(format!("engine_2d_profiles_{}", std::process::id()));
+    let profiles = BindingProfiles::new(&dir);
+    let profile = BindingProfile::from_manager("Player 1", &input_manager);
+    profiles.save(&profile).unwrap();
+    assert_eq!(profiles.names(), vec!["Player 1".to_string()]);
+    assert!(profiles.save(&BindingProfile::new("../escape")).is_err());
+
+    let loaded = profiles.load("Player 1").unwrap();
+    assert_eq!(loaded, profile);
+    input_manager.reset_all_bindings();
+    loaded.apply(&mut input_manager);
+    assert_eq!(input_manager.bindings("JUMP").len(), 2);
+
+    let error = BindingProfile::parse("[bindings]\nJUMP = [\"Keyboard:Up\"\n").unwrap_err();
+    assert!(error.contains("line 2"), "{}", error);
+    let error = BindingProfile::parse("[bindings]\nJUMP = [\"Keyboard:Nope\"]\n").unwrap_err();
+    assert!(error.contains("Nope"), "{}", error);
+
+    profiles.delete("Player 1").unwrap();
+    assert!(profiles.names().is_empty());
+    let _ = std::fs::remove_dir(dir);
+}
+
+#[test]
+fn test_binding_profile_reads_any_valid_toml() {
+    // A name with quotes and a control character survives the round trip
+    let mut profile = BindingProfile::new("Tab\there \"P1\"");
+    profile.bindings.insert(
+        "JUMP".to_string(),
+        vec![InputBinding::parse("Keyboard:LeftCtrl+Keyboard:S").unwrap()],
+    );
+    assert_eq!(BindingProfile::parse(&profile.to_toml()).unwrap(), profile);
+
+    // Hand-edited files may use multi-line arrays, escapes, quoted keys and comments
+    let source = "name = \"P\\u0031\" # player one\n\n[bindings]\n\"JUMP\" = [\n    \"Keyboard:Up\", # arrow\n    'Gamepad:South',\n]\n";
+    let profile = BindingProfile::parse(source).unwrap();
+    assert_eq!(profile.name, "P1");
+    assert_eq!(
+        profile.bindings["JUMP"],
+        vec![
+            InputBinding::parse("Keyboard:Up").unwrap(),
+            InputBinding::parse("Gamepad:South").unwrap(),
+        ]
+    );
+}
+
+fn special_move(input_manager: &mut InputManager) {
+    input_manager.register_action(GameAction {
+        id: "HADOUKEN".to_string(),
+        display_name: "Hadouken".to_string(),
+        category: ActionCategory::Combat,
+        input_type: InputType::Digital,
+        default_bindings: vec![
+            InputBinding::parse(
+                "sequence(0.2, Keyboard:S, Keyboard:S+Keyboard:D, Keyboard:D+Keyboard:J)",
+            )
+            .unwrap(),
+        ],
+        metadata: ActionMetadata::default(),
+    });
+}
+
+/// Hold exactly `keys` for one frame of `delta_time`
+fn hold(input_manager: &mut InputManager, keys: &[KeyCode], delta_time: f32) {
+    for key in [KeyCode::S, KeyCode::D, KeyCode::J] {
+        input_manager.set_physical_input_state(PhysicalInput::Keyboard(key), keys.contains(&key));
+    }
+    input_manager.update(delta_time);
+}
+
+#[test]
+fn test_sequence_binding_fires_on_final_step() {
+    let mut input_manager = InputManager::new();
+    special_move(&mut input_manager);
+
+    hold(&mut input_manager, &[KeyCode::S], 0.016);
+    hold(&mut input_manager, &[KeyCode::S, KeyCode::D], 0.1);
+    assert!(!input_manager.is_action_pressed("HADOUKEN"));
+    hold(&mut input_manager, &[KeyCode::D], 0.1);
+    // Inputs that match no step, like forward alone, don't break the sequence
+    hold(&mut input_manager, &[KeyCode::D, KeyCode::J], 0.1);
+    assert!(input_manager.is_action_pressed("HADOUKEN"));
+    hold(&mut input_manager, &[KeyCode::D, KeyCode::J], 0.016);
+    assert!(input_manager.is_action_held("HADOUKEN"));
+    hold(&mut input_manager, &[], 0.016);
+    assert!(input_manager.is_action_released("HADOUKEN"));
+
+    // The chord alone does nothing
+    hold(&mut input_manager, &[KeyCode::D, KeyCode::J], 0.016);
+    assert!(!input_manager.is_action_pressed("HADOUKEN"));
+}
+
+#[test]
+fn test_sequence_binding_timing_window() {
+    let mut input_manager = InputManager::new();
+    special_move(&mut input_manager);
+
+    hold(&mut input_manager, &[KeyCode::S], 0.016);
+    hold(&mut input_manager, &[KeyCode::S, KeyCode::D], 0.1);
+    hold(&mut input_manager, &[KeyCode::D], 0.1);
+    hold(&mut input_manager, &[KeyCode::D, KeyCode::J], 0.3);
+    assert!(!input_manager.is_action_pressed("HADOUKEN"));
+
+    // Starting over partway through restarts the timing
+    hold(&mut input_manager, &[], 0.016);
+    hold(&mut input_manager, &[KeyCode::S], 0.5);
+    hold(&mut input_manager, &[KeyCode::S, KeyCode::D], 0.1);
+    hold(&mut input_manager, &[KeyCode::S], 0.1);
+    hold(&mut input_manager, &[KeyCode::S, KeyCode::D], 0.1);
+    hold(&mut input_manager, &[KeyCode::D], 0.1);
+    hold(&mut input_manager, &[KeyCode::D, KeyCode::J], 0.1);
+    assert!(input_manager.is_action_pressed("HADOUKEN"));
+}
+
+#[test]
+fn test_double_and_multi_tap() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_action(jump_action());
+    let space = PhysicalInput::Keyboard(KeyCode::Space);
+    let tap = |input_manager: &mut InputManager, gap: f32| {
+        input_manager.set_physical_input_state(space.clone(), false);
+        input_manager.update(gap);
+        input_manager.set_physical_input_state(space.clone(), true);
+        input_manager.update(0.016);
+    };
+
+    tap(&mut input_manager, 1.0);
+    assert_eq!(input_manager.tap_count("JUMP"), 1);
+    assert!(!input_manager.is_action_double_tapped("JUMP"));
+    tap(&mut input_manager, 0.1);
+    assert!(input_manager.is_action_double_tapped("JUMP"));
+    tap(&mut input_manager, 0.1);
+    assert!(input_manager.is_action_multi_tapped("JUMP", 3));
+    assert!(!input_manager.is_action_double_tapped("JUMP"));
+
+    // Too slow starts a new streak; a longer window allows it
+    tap(&mut input_manager, 0.4);
+    assert_eq!(input_manager.tap_count("JUMP"), 1);
+    input_manager.set_tap_window("JUMP", 0.5);
+    tap(&mut input_manager, 0.4);
+    assert!(input_manager.is_action_double_tapped("JUMP"));
+    input_manager.update(1.0);
+    assert_eq!(input_manager.tap_count("JUMP"), 0);
+}
+
+#[test]
+fn test_tap_vs_hold_classification() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_action(jump_action());
+    let space = PhysicalInput::Keyboard(KeyCode::Space);
+
+    // A quick press and release is a tap, for one frame
+    input_manager.set_physical_input_state(space.clone(), true);
+    input_manager.update(0.1);
+    assert_eq!(input_manager.tap_hold_state("JUMP"), TapHoldState::Holding);
+    input_manager.set_physical_input_state(space.clone(), false);
+    input_manager.update(0.1);
+    assert!(input_manager.is_action_tapped("JUMP"));
+    assert!((input_manager.action_held_duration("JUMP") - 0.1).abs() < 1e-6);
+    input_manager.update(0.1);
+    assert_eq!(input_manager.tap_hold_state("JUMP"), TapHoldState::Idle);
+    assert_eq!(input_manager.action_held_duration("JUMP"), 0.0);
+
+    // Holding past the threshold is a long hold, started once, and its release isn't a tap
+    input_manager.set_hold_threshold("JUMP", 0.5);
+    input_manager.set_physical_input_state(space.clone(), true);
+    input_manager.update(0.1);
+    input_manager.update(0.3);
+    assert!(!input_manager.is_action_held_long("JUMP"));
+    input_manager.update(0.3);
+    assert!(input_manager.is_action_held_long_started("JUMP"));
+    input_manager.update(0.1);
+    assert!(input_manager.is_action_held_long("JUMP"));
+    assert!(!input_manager.is_action_held_long_started("JUMP"));
+    assert!((input_manager.action_held_duration("JUMP") - 0.7).abs() < 1e-6);
+    input_manager.set_physical_input_state(space, false);
+    input_manager.update(0.1);
+    assert!(!input_manager.is_action_tapped("JUMP"));
+    assert!((input_manager.action_held_duration("JUMP") - 0.8).abs() < 1e-6);
+}
+
+#[test]
+fn test_ui_consumes_input_before_gameplay() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_action(GameAction {
+        id: "SHOOT".to_string(),
+        display_name: "Shoot".to_string(),
+        category: ActionCategory::Combat,
+        input_type: InputType::Digital,
+        default_bindings: vec![InputBinding::Single(PhysicalInput::Mouse(
+            MouseButton::Left,
+        ))],
+        metadata: ActionMetadata::default(),
+    });
+    let click = PhysicalInput::Mouse(MouseButton::Left);
+
+    // The UI handles the click, so the gun doesn't fire while it's held
+    input_manager.set_physical_input_state(click.clone(), true);
+    input_manager.update(0.016);
+    let mut event = Consumable::new(click.clone());
+    if let Some(input) = event.unhandled() {
+        input_manager.consume_input(&input.clone());
+        event.consume();
+    }
+    assert!(event.unhandled().is_none());
+    assert!(input_manager.is_action_consumed("SHOOT"));
+    assert!(input_manager.get_recent_events(10).is_empty());
+    assert!(!input_manager.is_action_pressed("SHOOT"));
+    input_manager.update(0.016);
+    assert!(!input_manager.is_action_held("SHOOT"));
+
+    // The next click is the game's again
+    input_manager.set_physical_input_state(click.clone(), false);
+    input_manager.update(0.016);
+    input_manager.set_physical_input_state(click, true);
+    input_manager.update(0.016);
+    assert!(input_manager.is_action_pressed("SHOOT"));
+    assert!(!input_manager.consume_action("JUMP"));
+}
+
+fn press(input_manager: &mut InputManager, input: &PhysicalInput, pressed: bool) {
+    input_manager.set_physical_input_state(input.clone(), pressed);
+    input_manager.update(0.016);
+}
+
+#[test]
+fn test_rebind_captures_next_input_or_cancels() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_action(jump_action());
+    let enter = PhysicalInput::Keyboard(KeyCode::Enter);
+    let j = PhysicalInput::Keyboard(KeyCode::J);
+    assert!(input_manager.start_rebind("NOPE").is_err());
+
+    // The key that opened the prompt is still down, so it isn't captured
+    press(&mut input_manager, &enter, true);
+    input_manager.start_rebind("JUMP").unwrap();
+    press(&mut input_manager, &enter, true);
+    assert_eq!(input_manager.rebinding_action(), Some("JUMP"));
+    assert_eq!(input_manager.take_rebind_result(), None);
+
+    // The captured key is bound without also jumping
+    press(&mut input_manager, &j, true);
+    assert_eq!(
+        input_manager.take_rebind_result(),
+        Some(RebindResult::Bound {
+            action_id: "JUMP".to_string(),
+            binding: InputBinding::Single(j.clone()),
+        })
+    );
+    assert!(!input_manager.is_rebinding());
+    assert!(!input_manager.is_action_pressed("JUMP"));
+    press(&mut input_manager, &j, false);
+    press(&mut input_manager, &j, true);
+    assert!(input_manager.is_action_pressed("JUMP"));
+
+    // Escape cancels and leaves the bindings alone
+    input_manager.start_rebind("JUMP").unwrap();
+    press(
+        &mut input_manager,
+        &PhysicalInput::Keyboard(KeyCode::Escape),
+        true,
+    );
+    assert_eq!(
+        input_manager.take_rebind_result(),
+        Some(RebindResult::Cancelled {
+            action_id: "JUMP".to_string()
+        })
+    );
+    assert_eq!(input_manager.bindings("JUMP"), &[InputBinding::Single(j)]);
+}
+
+#[test]
+fn test_rebind_filters_and_conflicts() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_actions(get_all_actions());
+    let a = PhysicalInput::Keyboard(KeyCode::A);
+
+    // A is Move Left's, so the rebind reports it and keeps listening
+    input_manager.start_rebind("MOVE_RIGHT").unwrap();
+    press(&mut input_manager, &a, true);
+    assert_eq!(
+        input_manager.take_rebind_result(),
+        Some(RebindResult::Conflict {
+            action_id: "MOVE_RIGHT".to_string(),
+            input: a.clone(),
+            conflicts: vec!["MOVE_LEFT".to_string()],
+        })
+    );
+    assert!(input_manager.is_rebinding());
+    input_manager.cancel_rebind();
+    press(&mut input_manager, &a, false);
+
+    // Replacing takes A away from Move Left
+    input_manager
+        .start_rebind_with(
+            "MOVE_RIGHT",
+            RebindOptions::default().with_conflict(RebindConflict::Replace),
+        )
+        .unwrap();
+    press(&mut input_manager, &a, true);
+    assert_eq!(
+        input_manager.bindings("MOVE_RIGHT")[0],
+        InputBinding::Single(a.clone())
+    );
+    assert!(input_manager.binding_conflicts("MOVE_RIGHT", &a).is_empty());
+    press(&mut input_manager, &a, false);
+
+    // A gamepad-only rebind ignores keys and fills the second slot
+    input_manager
+        .start_rebind_with(
+            "MOVE_RIGHT",
+            RebindOptions::default().gamepad_only(false).with_slot(1),
+        )
+        .unwrap();
+    press(
+        &mut input_manager,
+        &PhysicalInput::Keyboard(KeyCode::K),
+        true,
+    );
+    assert!(input_manager.is_rebinding());
+    let south = PhysicalInput::Gamepad(GamepadButton::South);
+    press(&mut input_manager, &south, true);
+    assert_eq!(
+        input_manager.bindings("MOVE_RIGHT"),
+        &[InputBinding::Single(a), InputBinding::Single(south)]
+    );
+}
+
+#[test]
+fn test_gamepad_rumble_expires_and_stops_on_focus_loss() {
+    let mut gamepads = GamepadInput::new();
+    gamepads.add_gamepad(0, "Pad".to_string());
+    gamepads.set_rumble(0, 1.5, 0.25, 0.5);
+    gamepads.set_rumble(7, 1.0, 1.0, 1.0);
+    let playing = gamepads.rumble(0).unwrap();
+    assert_eq!((playing.low, playing.high), (1.0, 0.25));
+    assert_eq!(gamepads.take_rumble_changes(), vec![(0, Some(playing))]);
+    assert!(gamepads.take_rumble_changes().is_empty());
+
+    gamepads.update_rumble(0.3);
+    assert!(gamepads.rumble(0).is_some());
+    gamepads.update_rumble(0.3);
+    assert!(gamepads.rumble(0).is_none());
+    assert_eq!(gamepads.take_rumble_changes(), vec![(0, None)]);
+
+    // Losing focus stops rumble, and none starts until focus returns
+    gamepads.set_rumble(0, 0.5, 0.5, 2.0);
+    gamepads.set_focused(false);
+    gamepads.set_rumble(0, 0.5, 0.5, 2.0);
+    assert!(gamepads.rumble(0).is_none());
+    assert_eq!(gamepads.take_rumble_changes(), vec![(0, None)]);
+    gamepads.set_focused(true);
+    gamepads.set_rumble(0, 0.5, 0.5, 2.0);
+    assert!(gamepads.rumble(0).is_some());
+}
+
+#[test]
+fn test_player_slots_read_only_their_devices() {
+    let mut players = PlayerInputs::new(2);
+    let mut jump = jump_action();
+    jump.default_bindings
+        .push(InputBinding::Single(PhysicalInput::Gamepad(
+            GamepadButton::A,
+        )));
+    players.register_action(jump);
+    players.assign(0, InputDevice::KeyboardMouse).unwrap();
+    assert_eq!(
+        players.assign_to_free_player(InputDevice::Gamepad(3)),
+        Some(1)
+    );
+    assert_eq!(
+        players.assign_to_free_player(InputDevice::Gamepad(3)),
+        Some(1)
+    );
+    assert_eq!(players.assign_to_free_player(InputDevice::Gamepad(4)), None);
+    assert!(players.assign(2, InputDevice::Gamepad(4)).is_err());
+
+    let mut keyboard = KeyboardInput::new();
+    let mouse = MouseInput::new();
+    let mut gamepads = GamepadInput::new();
+    gamepads.add_gamepad(3, "Pad".to_string());
+    gamepads.handle_button_event(3, GamepadButton::A, true);
+    players.update(&keyboard, &mouse, &gamepads, 0.016);
+    assert!(!players.is_action_pressed_for(0, "JUMP"));
+    assert!(players.is_action_pressed_for(1, "JUMP"));
+
+    keyboard.handle_key_press(KeyCode::Space);
+    players.update(&keyboard, &mouse, &gamepads, 0.016);
+    assert!(players.is_action_pressed_for(0, "JUMP"));
+    assert!(players.is_action_held_for(1, "JUMP"));
+
+    // Handing the gamepad to player 0 releases it for player 1
+    players.assign(0, InputDevice::Gamepad(3)).unwrap();
+    assert_eq!(players.player_for(InputDevice::Gamepad(3)), Some(0));
+    players.update(&keyboard, &mouse, &gamepads, 0.016);
+    assert!(players.is_action_released_for(1, "JUMP"));
+    assert!(players.is_action_held_for(0, "JUMP"));
+    assert!(!players.is_action_pressed_for(5, "JUMP"));
+}
+
+fn aim_action(shape: DeadzoneShape, curve: ResponseCurve) -> GameAction {
+    GameAction {
+        id: "AIM_X".to_string(),
+        display_name: "Aim X".to_string(),
+        category: ActionCategory::Combat,
+        input_type: InputType::Analog,
+        default_bindings: vec![InputBinding::AnalogStick {
+            input: PhysicalInput::GamepadAxis(GamepadAxis::RightStickX),
+            threshold: 0.5,
+            deadzone: 0.2,
+            shape,
+            curve,
+        }],
+        metadata: ActionMetadata::default(),
+    }
+}
+
+fn set_stick(input_manager: &mut InputManager, x: f32, y: f32) {
+    input_manager.set_physical_input_value(PhysicalInput::GamepadAxis(GamepadAxis::RightStickX), x);
+    input_manager.set_physical_input_value(PhysicalInput::GamepadAxis(GamepadAxis::RightStickY), y);
+    input_manager.update(0.016);
+}
+
+#[test]
+fn test_stick_binding_deadzone_shapes() {
+    let mut axial = InputManager::new();
+    axial.register_action(aim_action(DeadzoneShape::Axial, ResponseCurve::Linear));
+    let mut radial = InputManager::new();
+    radial.register_action(aim_action(DeadzoneShape::Radial, ResponseCurve::Linear));
+
+    // A slight diagonal: each axis is inside the deadzone, the stick as a whole isn't
+    set_stick(&mut axial, 0.18, 0.18);
+    set_stick(&mut radial, 0.18, 0.18);
+    assert_eq!(axial.get_action_value("AIM_X"), 0.0);
+    let value = radial.get_action_value("AIM_X");
+    assert!(value > 0.0 && value < 0.1, "{}", value);
+
+    // Values start from zero at the deadzone edge and reach one at full deflection
+    set_stick(&mut axial, -0.6, 0.0);
+    assert!((axial.get_action_value("AIM_X") + 0.5).abs() < 1e-6);
+    assert!(axial.is_action_pressed("AIM_X"));
+    set_stick(&mut axial, 1.0, 0.0);
+    assert_eq!(axial.get_action_value("AIM_X"), 1.0);
+}
+
+#[test]
+fn test_stick_binding_response_curve() {
+    let mut input_manager = InputManager::new();
+    input_manager.register_action(aim_action(DeadzoneShape::Radial, ResponseCurve::Squared));
+    set_stick(&mut input_manager, 0.6, 0.0);
+    assert!((input_manager.get_action_value("AIM_X") - 0.25).abs() < 1e-6);
+    assert!(!input_manager.is_action_held("AIM_X"));
+
+    assert_eq!(
+        ResponseCurve::parse("power(1.5)").unwrap(),
+        ResponseCurve::Power(1.5)
+    );
+    assert!(ResponseCurve::parse("custom()").is_err());
+    assert!(ResponseCurve::parse("cubic").is_err());
+}
@@ -0,0 +1,170 @@
+use engine_2d::particles::*;
+use glam::Vec2;
+
+/// Enough particles for `simulate` to split the work into parallel chunks, with a partial
+/// last chunk (the storage's chunk size is 8192)
+const PARALLEL_PARTICLES: usize = 8192 * 2 + 100;
+
+#[test]
+fn test_storage_simulate_integrates_and_expires() {
+    let mut storage = ParticleStorage::with_capacity(4);
+    assert!(storage.push(Vec2::ZERO, Vec2::new(1.0, 0.0), 1.0));
+    assert!(storage.push(Vec2::ZERO, Vec2::new(0.0, 2.0), 0.25));
+
+    storage.simulate(0.5, Vec2::new(0.0, -1.0), 0.0);
+
+    // The short-lived particle expired; the other moved and felt gravity
+    assert_eq!(storage.len(), 1);
+    assert_eq!(storage.position(0), Vec2::new(0.5, -0.25));
+    assert_eq!(storage.vel_y[0], -0.5);
+    assert_eq!(storage.life_fraction(0), 0.5);
+}
+
+#[test]
+fn test_emitter_spawn_rate_and_cap() {
+    let config = EmitterConfig::default()
+        .with_spawn_rate(10.0)
+        .with_max_particles(5)
+        .with_lifetime(10.0, 10.0);
+    let mut emitter = ParticleEmitter::new(config);
+
+    emitter.update(0.25);
+    assert_eq!(emitter.particle_count(), 2);
+
+    emitter.update(1.0);
+    assert_eq!(emitter.particle_count(), 5);
+
+    emitter.set_active(false);
+    emitter.update(1.0);
+    assert_eq!(emitter.particle_count(), 5);
+}
+
+#[test]
+fn test_recycle_policy_reuses_particles() {
+    let config = EmitterConfig::default()
+        .with_max_particles(3)
+        .with_lifetime(10.0, 10.0)
+        .with_overflow(OverflowPolicy::Recycle);
+    let mut emitter = ParticleEmitter::new(config);
+    emitter.set_active(false);
+    emitter.burst(3);
+    emitter.update(1.0);
+
+    emitter.burst(1);
+
+    assert_eq!(emitter.particle_count(), 3);
+    assert_eq!(emitter.storage().age[0], 0.0);
+    assert_eq!(emitter.storage().age[1], 1.0);
+}
+
+#[test]
+fn test_recycle_policy_wraps_around_the_storage() {
+    let config = EmitterConfig::default()
+        .with_spawn_rate(0.0)
+        .with_max_particles(3)
+        .with_lifetime(10.0, 10.0)
+        .with_overflow(OverflowPolicy::Recycle);
+    let mut emitter = ParticleEmitter::new(config);
+    emitter.burst(3);
+    emitter.update(1.0);
+
+    // Four more overwrite every slot, then wrap back to the first
+    emitter.burst(4);
+    assert_eq!(emitter.particle_count(), 3);
+    assert_eq!(emitter.storage().age, vec![0.0, 0.0, 0.0]);
+
+    // The next recycle continues after the wrapped slot
+    emitter.update(0.5);
+    emitter.burst(1);
+    assert_eq!(emitter.storage().age, vec![0.5, 0.0, 0.5]);
+    emitter.burst(2);
+    assert_eq!(emitter.storage().age, vec![0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_drop_new_policy_keeps_the_oldest_particles() {
+    let config = EmitterConfig::default()
+        .with_spawn_rate(0.0)
+        .with_max_particles(2)
+        .with_lifetime(10.0, 10.0);
+    let mut emitter = ParticleEmitter::new(config);
+    emitter.burst(2);
+    emitter.update(1.0);
+    emitter.burst(5);
+
+    assert_eq!(emitter.particle_count(), 2);
+    assert_eq!(emitter.storage().age, vec![1.0, 1.0]);
+}
+
+#[test]
+fn test_parallel_simulation_matches_the_serial_path() {
+    let velocity = |i: usize| Vec2::new((i % 7) as f32, (i % 5) as f32 - 2.0);
+    let mut storage = ParticleStorage::with_capacity(PARALLEL_PARTICLES);
+    for i in 0..PARALLEL_PARTICLES {
+        // Every third particle expires this step; the others carry their index
+        let lifetime = if i % 3 == 0 { 0.05 } else { 1.0 + i as f32 };
+        storage.push(Vec2::splat(i as f32), velocity(i), lifetime);
+    }
+
+    let (dt, gravity, drag) = (0.1, Vec2::new(0.5, -9.8), 0.5);
+    storage.simulate(dt, gravity, drag);
+    assert_eq!(
+        storage.len(),
+        PARALLEL_PARTICLES - PARALLEL_PARTICLES.div_ceil(3)
+    );
+
+    for index in 0..storage.len() {
+        let i = (storage.lifetime[index] - 1.0) as usize;
+        let mut single = ParticleStorage::with_capacity(1);
+        single.push(Vec2::splat(i as f32), velocity(i), 10.0);
+        single.simulate(dt, gravity, drag);
+
+        assert_eq!(
+            storage.position(index),
+            single.position(0),
+            "particle {}",
+            i
+        );
+        assert_eq!(storage.vel_x[index], single.vel_x[0]);
+        assert_eq!(storage.vel_y[index], single.vel_y[0]);
+        assert_eq!(storage.age[index], dt);
+    }
+}
+
+#[test]
+fn test_particle_system_parallel_update() {
+    let mut system = ParticleSystem::new();
+    let config = EmitterConfig::default()
+        .with_spawn_rate(0.0)
+        .with_max_particles(50_000)
+        .with_lifetime(5.0, 5.0);
+    let a = system.add_emitter(config.clone());
+    let b = system.add_emitter(config);
+    system.emitter_mut(a).unwrap().burst(50_000);
+    system.emitter_mut(b).unwrap().burst(10);
+
+    system.update(0.1);
+
+    assert_eq!(system.particle_count(), 50_010);
+    let emitter = system.emitter(a).unwrap();
+    assert!(emitter.storage().age.iter().all(|&age| age == 0.1));
+
+    assert!(system.remove_emitter(b).is_some());
+    assert_eq!(system.particle_count(), 50_000);
+    assert_eq!(system.add_emitter(EmitterConfig::default()), b);
+}
+
+#[test]
+fn test_particle_color_and_size_interpolate() {
+    let config = EmitterConfig::default()
+        .with_spawn_rate(0.0)
+        .with_lifetime(2.0, 2.0)
+        .with_size(Vec2::splat(1.0), Vec2::splat(3.0))
+        .with_color([1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 0.0]);
+    let mut emitter = ParticleEmitter::new(config);
+    emitter.burst(1);
+    emitter.update(1.0);
+
+    assert_eq!(emitter.particle_size(0), Vec2::splat(2.0));
+    assert_eq!(emitter.particle_color(0), [0.5, 0.0, 0.5, 0.5]);
+}
@@ -0,0 +1,51 @@
+use engine_2d::render::popup_text::{PopupMotion, PopupStyle, PopupTexts};
+use glam::Vec2;
+
+#[test]
+fn test_popups_expire_after_their_lifetime() {
+    let mut popups = PopupTexts::with_capacity(8);
+    popups.spawn("12", Vec2::ZERO, PopupStyle::damage().with_lifetime(0.5));
+    popups.spawn(
+        "+10 gold",
+        Vec2::ONE,
+        PopupStyle::pickup().with_lifetime(2.0),
+    );
+
+    popups.update(1.0);
+    let texts: Vec<&str> = popups.iter().map(|popup| popup.text).collect();
+    assert_eq!(texts, ["+10 gold"]);
+
+    popups.update(1.0);
+    assert!(popups.is_empty());
+}
+
+#[test]
+fn test_full_pool_replaces_oldest_popup() {
+    let mut popups = PopupTexts::with_capacity(2);
+    popups.spawn("1", Vec2::ZERO, PopupStyle::damage());
+    popups.update(0.1);
+    popups.spawn("2", Vec2::ZERO, PopupStyle::damage());
+    popups.update(0.1);
+    assert!(popups.spawn("3", Vec2::ZERO, PopupStyle::damage()));
+
+    let mut texts: Vec<&str> = popups.iter().map(|popup| popup.text).collect();
+    texts.sort();
+    assert_eq!(texts, ["2", "3"]);
+    assert_eq!(popups.len(), 2);
+}
+
+#[test]
+fn test_popup_motion_and_fade_follow_style() {
+    let style = PopupStyle::default()
+        .with_motion(PopupMotion::Rise { distance: 40.0 })
+        .with_lifetime(1.0);
+    assert_eq!(style.motion.offset(0.0, 1.0), Vec2::ZERO);
+    assert_eq!(style.motion.offset(1.0, 1.0), Vec2::new(0.0, 40.0));
+    assert_eq!(style.alpha(0.5), 1.0);
+    assert!((style.alpha(0.8) - 0.5).abs() < 1e-5);
+    assert_eq!(style.alpha(1.0), 0.0);
+
+    let critical = PopupStyle::critical();
+    assert!(critical.scale_at(0.0) > critical.scale);
+    assert_eq!(critical.scale_at(critical.pop_duration), critical.scale);
+}
@@ -0,0 +1,116 @@
+use engine_2d::animation::{Oscillator, SecondaryMotion, Spring, SpringVec2, Waveform};
+use glam::Vec2;
+
+fn approx(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+#[test]
+fn test_spring_chases_and_settles_on_target() {
+    let mut spring = SpringVec2::new(Vec2::ZERO);
+    spring.set_target(Vec2::new(100.0, 0.0));
+    spring.update(0.05);
+    assert!(spring.value().x > 0.0 && spring.value().x < 100.0);
+    assert!(spring.velocity().x > 0.0);
+
+    // A large step is split up, so the spring doesn't blow up at low frame rates
+    spring.update(3.0);
+    assert!(spring.is_settled(0.01));
+    assert!(spring.value().distance(Vec2::new(100.0, 0.0)) < 0.01);
+
+    // Low damping overshoots the target
+    let mut wobbly = Spring::new(0.0).with_stiffness(200.0).with_damping(4.0);
+    wobbly.set_target(1.0);
+    let peak = (0..60)
+        .map(|_| wobbly.update(1.0 / 60.0))
+        .fold(0.0, f32::max);
+    assert!(peak > 1.2);
+
+    // Impulses move a spring at rest, which then comes back
+    let mut button = Spring::new(1.0);
+    button.add_impulse(5.0);
+    assert!(button.update(0.02) > 1.0);
+    button.update(5.0);
+    assert!(button.is_settled(0.001));
+    button.set_value(3.0);
+    assert_eq!((button.value(), button.velocity()), (3.0, 0.0));
+}
+
+#[test]
+fn test_oscillator_waveforms_and_decay() {
+    for (waveform, quarter, half, three_quarters) in [
+        (Waveform::Sine, 1.0, 0.0, -1.0),
+        (Waveform::Triangle, 1.0, 0.0, -1.0),
+        (Waveform::Sawtooth, -0.5, 0.0, 0.5),
+    ] {
+        assert!(approx(waveform.sample(0.25), quarter), "{:?}", waveform);
+        assert!(approx(waveform.sample(0.5), half), "{:?}", waveform);
+        assert!(
+            approx(waveform.sample(1.75), three_quarters),
+            "{:?}",
+            waveform
+        );
+    }
+    assert_eq!(Waveform::Square.sample(0.1), 1.0);
+    assert_eq!(Waveform::Square.sample(0.6), -1.0);
+
+    let mut bob = Oscillator::new(4.0, 0.5);
+    assert!(approx(bob.update(0.5), 4.0));
+    assert!(approx(bob.update(1.0), -4.0));
+
+    let mut shake = Oscillator::new(0.0, 10.0).with_decay(2.0);
+    assert_eq!(shake.update(0.1), 0.0);
+    shake.kick(8.0);
+    shake.update(0.5);
+    assert!(approx(shake.amplitude(), 8.0 * (-1.0f32).exp()));
+}
+
+#[test]
+fn test_secondary_motion_lags_and_settles() {
+    let mut tail = SecondaryMotion::new(Vec2::new(-10.0, 0.0));
+    assert_eq!(
+        tail.update(Vec2::new(50.0, 0.0), 0.016),
+        Vec2::new(40.0, 0.0)
+    );
+
+    // Moving the anchor leaves the tail behind at first
+    tail.update(Vec2::new(60.0, 0.0), 0.016);
+    assert!(tail.lag().x < 0.0);
+    assert!(tail.position().x < 50.0);
+
+    for _ in 0..300 {
+        tail.update(Vec2::new(60.0, 0.0), 1.0 / 60.0);
+    }
+    assert!(tail.position().distance(Vec2::new(50.0, 0.0)) < 0.01);
+
+    tail.reset(Vec2::ZERO);
+    assert_eq!(tail.position(), Vec2::new(-10.0, 0.0));
+    assert_eq!(tail.lag(), Vec2::ZERO);
+}
+
+#[test]
+fn test_spring_steps_and_oscillator_phase() {
+    // One long frame matches the same time in frames of the inner 1/120 s step
+    let mut long_frame = Spring::new(0.0).with_stiffness(150.0).with_damping(12.0);
+    let mut short_frames = long_frame;
+    long_frame.set_target(10.0);
+    short_frames.set_target(10.0);
+    long_frame.update(0.2);
+    for _ in 0..24 {
+        short_frames.update(1.0 / 120.0);
+    }
+    assert!(approx(long_frame.value(), short_frames.value()));
+
+    // Negative time is ignored
+    assert_eq!(long_frame.update(-1.0), long_frame.value());
+
+    // A starting phase offsets the wave, and a kick restarts it from zero
+    let mut wave = Oscillator::new(2.0, 1.0)
+        .with_waveform(Waveform::Triangle)
+        .with_phase(0.25);
+    assert!(approx(wave.value(), 2.0));
+    assert!(approx(wave.update(0.25), 0.0));
+    wave.kick(3.0);
+    assert!(approx(wave.value(), 0.0));
+    assert!(approx(wave.update(0.25), 3.0));
+}
@@ -0,0 +1,30 @@
+use engine_2d::render::pseudo_locale::PseudoLocale;
+
+#[test]
+fn test_accents_brackets_and_expansion() {
+    let pseudo = PseudoLocale::default();
+    assert_eq!(pseudo.apply("Settings"), "[Šéţţíñĝš ~~~]");
+
+    // Padding is roughly 30% of the letters, rounded up
+    let long = pseudo.apply("Start new game");
+    assert!(long.ends_with(" ~~~~]"));
+}
+
+#[test]
+fn test_placeholders_are_preserved() {
+    let pseudo = PseudoLocale::default().with_expansion(0.0);
+    assert_eq!(
+        pseudo.apply("Hello {player_name}, %s left"),
+        "[Ĥéļļö {player_name}, %s ļéƒţ]"
+    );
+}
+
+#[test]
+fn test_options_and_multiple_lines() {
+    let pseudo = PseudoLocale::default()
+        .with_accents(false)
+        .with_brackets(false)
+        .with_expansion(0.5);
+    assert_eq!(pseudo.apply("Quit\nOK"), "Quit ~~\nOK ~");
+    assert_eq!(pseudo.apply(""), "");
+}
@@ -0,0 +1,124 @@
+use engine_2d::ui::{QuickDraw, QuickInput, QuickUi};
+use glam::Vec2;
+
+fn pointer(x: f32, y: f32, down: bool, pressed: bool, released: bool) -> QuickInput {
+    QuickInput {
+        pointer: Vec2::new(x, y),
+        down,
+        pressed,
+        released,
+    }
+}
+
+/// Build a panel at the origin with one button and one slider
+fn frame(ui: &mut QuickUi, input: QuickInput, clicks: &mut u32, speed: &mut f32) -> bool {
+    ui.begin(input);
+    ui.panel("Debug", Vec2::ZERO, 0.4);
+    let clicked = ui.button("Respawn", || *clicks += 1);
+    ui.slider("Speed", speed, 0.0..=10.0);
+    clicked
+}
+
+#[test]
+fn test_button_clicks_on_release_over_it() {
+    let mut ui = QuickUi::new();
+    let (mut clicks, mut speed) = (0, 5.0);
+    // The button occupies the first row under the title bar
+    let row_y = ui.theme.row_height * 1.5 + ui.theme.padding;
+
+    assert!(!frame(
+        &mut ui,
+        pointer(0.1, row_y, true, true, false),
+        &mut clicks,
+        &mut speed
+    ));
+    assert!(frame(
+        &mut ui,
+        pointer(0.1, row_y, false, false, true),
+        &mut clicks,
+        &mut speed
+    ));
+    assert_eq!(clicks, 1);
+
+    // Releasing elsewhere cancels the click
+    frame(
+        &mut ui,
+        pointer(0.1, row_y, true, true, false),
+        &mut clicks,
+        &mut speed,
+    );
+    frame(
+        &mut ui,
+        pointer(0.9, 0.9, false, false, true),
+        &mut clicks,
+        &mut speed,
+    );
+    assert_eq!(clicks, 1);
+}
+
+#[test]
+fn test_slider_drags_bound_value() {
+    let mut ui = QuickUi::new();
+    let (mut clicks, mut speed) = (0, 5.0);
+    let theme = ui.theme;
+    let row_y = theme.row_height * 2.5 + theme.padding * 2.0;
+    let left = theme.padding;
+    let width = 0.4 - theme.padding * 2.0;
+
+    frame(
+        &mut ui,
+        pointer(left + width * 0.5, row_y, true, true, false),
+        &mut clicks,
+        &mut speed,
+    );
+    assert!((speed - 5.0).abs() < 1e-4);
+
+    // Dragging past the end keeps the value in range even outside the slider
+    frame(
+        &mut ui,
+        pointer(2.0, 0.9, true, false, false),
+        &mut clicks,
+        &mut speed,
+    );
+    assert_eq!(speed, 10.0);
+    assert!(ui.wants_pointer());
+
+    frame(
+        &mut ui,
+        pointer(2.0, 0.9, false, false, true),
+        &mut clicks,
+        &mut speed,
+    );
+    frame(
+        &mut ui,
+        pointer(left, row_y, false, false, false),
+        &mut clicks,
+        &mut speed,
+    );
+    assert_eq!(speed, 10.0);
+    assert_eq!(clicks, 0);
+}
+
+#[test]
+fn test_panel_grows_to_fit_widgets() {
+    let mut ui = QuickUi::new();
+    let (mut clicks, mut speed) = (0, 0.0);
+    frame(&mut ui, QuickInput::default(), &mut clicks, &mut speed);
+
+    let theme = ui.theme;
+    let Some(QuickDraw::Rect { size, .. }) = ui.draws().first() else {
+        panic!("panel background should be drawn first");
+    };
+    let expected = theme.row_height * 3.0 + theme.padding * 3.0;
+    assert!((size.y - expected).abs() < 1e-5);
+
+    let labels: Vec<_> = ui
+        .draws()
+        .iter()
+        .filter_map(|draw| match draw {
+            QuickDraw::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(labels, ["Debug", "Respawn", "Speed: 0.00"]);
+}
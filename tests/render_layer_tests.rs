@@ -0,0 +1,83 @@
+use engine_2d::render::layer::{ColorTint, DrawSpace, RenderLayer, TintFade};
+use glam::Vec2;
+
+#[test]
+fn test_world_layer_follows_camera() {
+    let layer = RenderLayer::world();
+    assert_eq!(
+        layer.view_offset(Vec2::new(2.0, -1.0)),
+        Vec2::new(-2.0, 1.0)
+    );
+}
+
+#[test]
+fn test_screen_fixed_layer_ignores_camera() {
+    let layer = RenderLayer::screen_fixed().with_offset(Vec2::new(0.5, 0.5));
+    assert_eq!(
+        layer.view_offset(Vec2::new(10.0, 10.0)),
+        Vec2::new(0.5, 0.5)
+    );
+}
+
+#[test]
+fn test_parallax_layer_scrolls_partially() {
+    let layer = RenderLayer::parallax(0.5);
+    assert_eq!(
+        layer.view_offset(Vec2::new(4.0, 2.0)),
+        Vec2::new(-2.0, -1.0)
+    );
+}
+
+#[test]
+fn test_default_layer_is_world() {
+    assert_eq!(RenderLayer::default(), RenderLayer::world());
+}
+
+#[test]
+fn test_draw_space_camera_position() {
+    let camera = Vec2::new(3.0, -2.0);
+    assert_eq!(DrawSpace::default(), DrawSpace::World);
+    assert_eq!(DrawSpace::World.camera_position(camera), camera);
+    assert_eq!(DrawSpace::Screen.camera_position(camera), Vec2::ZERO);
+
+    // Screen space on a parallax layer keeps only the layer's constant offset
+    let layer = RenderLayer::parallax(0.5).with_offset(Vec2::new(0.1, 0.0));
+    assert_eq!(
+        layer.view_offset(DrawSpace::Screen.camera_position(camera)),
+        Vec2::new(0.1, 0.0)
+    );
+}
+
+#[test]
+fn test_color_tint_apply_and_compose() {
+    let night = ColorTint::multiply(0.5, 0.5, 1.0);
+    let flash = ColorTint::add(0.25, 0.0, 0.0).with_alpha(0.5);
+
+    assert_eq!(
+        ColorTint::IDENTITY.apply((0.2, 0.4, 0.6, 1.0)),
+        (0.2, 0.4, 0.6, 1.0)
+    );
+    assert_eq!(night.apply((1.0, 1.0, 1.0, 1.0)), (0.5, 0.5, 1.0, 1.0));
+
+    // Layer tint first, then the global tint
+    let combined = night.then(flash);
+    let color = (0.8, 0.4, 0.2, 1.0);
+    assert_eq!(combined.apply(color), flash.apply(night.apply(color)));
+}
+
+#[test]
+fn test_tint_fade_interpolates_over_duration() {
+    let fade = TintFade {
+        from: ColorTint::IDENTITY,
+        to: ColorTint::multiply(0.0, 0.0, 0.0),
+        start: 2.0,
+        duration: 4.0,
+    };
+
+    assert_eq!(fade.value_at(0.0), ColorTint::IDENTITY);
+    assert_eq!(fade.value_at(4.0).multiply, (0.5, 0.5, 0.5, 1.0));
+    assert_eq!(fade.value_at(10.0), fade.to);
+    assert!(!fade.is_finished(5.0));
+    assert!(fade.is_finished(6.0));
+    assert_eq!(TintFade::constant(fade.to).value_at(0.0), fade.to);
+}
@@ -0,0 +1,43 @@
+use engine_2d::engine::config::ViewportConfig;
+use engine_2d::ui::SafeArea;
+use glam::Vec2;
+
+#[test]
+fn test_from_percent_maps_anchors_inside_insets() {
+    let area = SafeArea::from_percent(5.0);
+
+    assert!((area.percent() - 5.0).abs() < 1e-4);
+    assert!(
+        area.to_screen(Vec2::ZERO)
+            .abs_diff_eq(Vec2::splat(0.05), 1e-6)
+    );
+    assert!(
+        area.to_screen(Vec2::new(1.0, 0.0))
+            .abs_diff_eq(Vec2::new(0.95, 0.05), 1e-6)
+    );
+    assert!(
+        area.to_screen(Vec2::splat(0.5))
+            .abs_diff_eq(Vec2::splat(0.5), 1e-6)
+    );
+}
+
+#[test]
+fn test_insets_are_clamped() {
+    let area = SafeArea::new(-0.1, 0.5, 0.1, 0.2);
+
+    assert_eq!(area.left, 0.0);
+    assert_eq!(area.top, SafeArea::MAX_INSET);
+    assert_eq!(area.right, 0.1);
+    assert!(area.size().abs_diff_eq(Vec2::new(0.9, 0.55), 1e-6));
+}
+
+#[test]
+fn test_contains_and_viewport_config() {
+    let area = SafeArea::new(0.1, 0.1, 0.0, 0.0);
+    assert!(area.contains(Vec2::new(0.5, 0.5)));
+    assert!(!area.contains(Vec2::new(0.05, 0.5)));
+
+    let config = ViewportConfig::default().with_safe_area(area);
+    assert_eq!(config.safe_area, area);
+    assert_eq!(ViewportConfig::ndc().safe_area, SafeArea::default());
+}
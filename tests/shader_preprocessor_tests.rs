@@ -0,0 +1,98 @@
+use engine_2d::render::shader_preprocessor::{ShaderDefines, ShaderPreprocessor};
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_shader_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "engine_2d_shader_pp_{}_{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_builtin_chunk_include() {
+    let preprocessor = ShaderPreprocessor::new();
+    let source = "#version 330 core\n#include \"engine/color.glsl\"\nvoid main() {}\n";
+
+    let output = preprocessor.process(source, "test.frag").unwrap();
+
+    assert!(output.source.starts_with("#version 330 core\n"));
+    assert!(output.source.contains("float luminance(vec3 color)"));
+    assert!(!output.source.contains("#include"));
+    assert!(output.dependencies.is_empty());
+}
+
+#[test]
+fn test_defines_injected_after_version() {
+    let mut preprocessor = ShaderPreprocessor::new();
+    preprocessor.define("GLOBAL", "1");
+    let mut defines = ShaderDefines::new();
+    defines.insert("USE_FOG".to_string(), "2".to_string());
+
+    let output = preprocessor
+        .process_with_defines("#version 330 core\nvoid main() {}\n", "test.frag", &defines)
+        .unwrap();
+
+    let lines: Vec<&str> = output.source.lines().collect();
+    assert_eq!(lines[0], "#version 330 core");
+    assert_eq!(lines[1], "#define GLOBAL 1");
+    assert_eq!(lines[2], "#define USE_FOG 2");
+    assert_eq!(output.map_line(4), Some(("test.frag", 2)));
+}
+
+#[test]
+fn test_file_includes_and_line_mapping() {
+    let dir = temp_shader_dir("files");
+    fs::write(
+        dir.join("common.glsl"),
+        "float helper() {\n    return 1.0;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("main.frag"),
+        "#version 330 core\n#include \"common.glsl\"\n#include \"common.glsl\"\nvoid main() {}\n",
+    )
+    .unwrap();
+
+    let output = ShaderPreprocessor::new()
+        .process_file(&dir.join("main.frag"))
+        .unwrap();
+
+    // Included once, with each output line traced to its original file
+    assert_eq!(output.source.matches("float helper()").count(), 1);
+    assert_eq!(output.dependencies.len(), 2);
+    let (file, line) = output.map_line(3).unwrap();
+    assert!(file.ends_with("common.glsl"));
+    assert_eq!(line, 2);
+    let (file, line) = output.map_line(5).unwrap();
+    assert!(file.ends_with("main.frag"));
+    assert_eq!(line, 4);
+
+    let log = output.map_error_log("0:3(5): error: syntax error\nERROR: 0(5) : bad");
+    let lines: Vec<&str> = log.lines().collect();
+    assert!(lines[0].contains("common.glsl:2(5): error"));
+    assert!(lines[1].contains("main.frag:4 : bad"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_include_errors_report_location() {
+    let mut preprocessor = ShaderPreprocessor::new();
+    preprocessor.add_chunk("a.glsl", "#include \"b.glsl\"\n");
+    preprocessor.add_chunk("b.glsl", "#include \"a.glsl\"\n");
+
+    let circular = preprocessor
+        .process("#include \"a.glsl\"\n", "root.vert")
+        .unwrap_err();
+    assert!(circular.contains("circular"));
+
+    let missing = preprocessor
+        .process("void main() {}\n#include \"missing.glsl\"\n", "root.vert")
+        .unwrap_err();
+    assert!(missing.starts_with("root.vert:2:"));
+}
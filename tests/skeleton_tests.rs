@@ -0,0 +1,116 @@
+use engine_2d::animation::{SkeletalClip, Skeleton, SkinnedMesh, SkinnedVertex, linear};
+use engine_2d::ecs::Transform;
+use glam::Vec2;
+use std::f32::consts::FRAC_PI_2;
+
+fn approx(a: Vec2, b: Vec2) -> bool {
+    (a - b).length() < 1e-4
+}
+
+/// A root at (10, 0) with an arm bone 5 units along it
+fn arm() -> Skeleton {
+    let mut skeleton = Skeleton::new();
+    skeleton
+        .add_bone("root", None, Transform::from_position(Vec2::new(10.0, 0.0)))
+        .unwrap();
+    skeleton
+        .add_bone(
+            "arm",
+            Some("root"),
+            Transform::from_position(Vec2::new(5.0, 0.0)),
+        )
+        .unwrap();
+    skeleton
+}
+
+#[test]
+fn test_bones_inherit_parent_transforms() {
+    let mut skeleton = arm();
+    assert!(
+        skeleton
+            .add_bone("arm", None, Transform::default())
+            .is_err()
+    );
+    assert!(
+        skeleton
+            .add_bone("hand", Some("wrist"), Transform::default())
+            .is_err()
+    );
+    skeleton
+        .add_attachment(
+            "arm",
+            "sleeve",
+            Transform::from_position(Vec2::new(2.0, 0.0)),
+            Vec2::ONE,
+        )
+        .unwrap();
+
+    let root = skeleton.pose_mut("root").unwrap();
+    root.rotation = FRAC_PI_2;
+    root.scale = Vec2::splat(2.0);
+
+    let arm = skeleton.world_transform("arm").unwrap();
+    assert!(approx(arm.position, Vec2::new(10.0, 10.0)));
+    assert!((arm.rotation - FRAC_PI_2).abs() < 1e-4);
+    let sleeve = skeleton.attachment_transforms()[0];
+    assert!(approx(sleeve.position, Vec2::new(10.0, 14.0)));
+    assert!(approx(sleeve.scale, Vec2::splat(2.0)));
+
+    skeleton.reset_pose();
+    assert!(approx(
+        skeleton.world_transform("arm").unwrap().position,
+        Vec2::new(15.0, 0.0)
+    ));
+}
+
+#[test]
+fn test_clips_key_bone_poses() {
+    let mut skeleton = arm();
+    let mut wave = SkeletalClip::new().with_looping(true);
+    let track = wave.track_mut("arm");
+    track.rotation.add_key(0.0, 0.0, linear);
+    track.rotation.add_key(1.0, 1.0, linear);
+    wave.track_mut("tail")
+        .scale
+        .add_key(0.0, Vec2::ZERO, linear);
+    assert_eq!(wave.duration(), 1.0);
+
+    wave.apply(&mut skeleton, 0.25);
+    assert!((skeleton.bone("arm").unwrap().pose.rotation - 0.25).abs() < 1e-5);
+    // Looping wraps back into the clip
+    wave.apply(&mut skeleton, 1.5);
+    assert!((skeleton.bone("arm").unwrap().pose.rotation - 0.5).abs() < 1e-5);
+    // Half way from the current pose to the clip's
+    wave.apply_weighted(&mut skeleton, 0.0, 0.5);
+    assert!((skeleton.bone("arm").unwrap().pose.rotation - 0.25).abs() < 1e-5);
+    assert_eq!(
+        skeleton.bone("arm").unwrap().pose.position,
+        Vec2::new(5.0, 0.0)
+    );
+}
+
+#[test]
+fn test_weighted_mesh_deformation() {
+    let mut skeleton = arm();
+    let vertices = vec![
+        SkinnedVertex::new(Vec2::new(10.0, 0.0), Vec2::ZERO).with_weight(0, 1.0),
+        SkinnedVertex::new(Vec2::new(15.0, 0.0), Vec2::X).with_weight(1, 1.0),
+        SkinnedVertex::new(Vec2::new(15.0, 1.0), Vec2::ONE)
+            .with_weight(0, 1.0)
+            .with_weight(1, 1.0),
+    ];
+    assert!(SkinnedMesh::new(vertices.clone(), vec![0, 1], &skeleton).is_err());
+    assert!(SkinnedMesh::new(vertices.clone(), vec![0, 1, 3], &skeleton).is_err());
+    let mesh = SkinnedMesh::new(vertices, vec![0, 1, 2], &skeleton).unwrap();
+
+    // The bind pose leaves the mesh alone
+    let rest = mesh.deform(&skeleton);
+    assert!(approx(rest[1], Vec2::new(15.0, 0.0)));
+
+    skeleton.pose_mut("arm").unwrap().position.y += 4.0;
+    let posed = mesh.deform(&skeleton);
+    assert!(approx(posed[0], Vec2::new(10.0, 0.0)));
+    assert!(approx(posed[1], Vec2::new(15.0, 4.0)));
+    // Split evenly between the still root and the moved arm
+    assert!(approx(posed[2], Vec2::new(15.0, 3.0)));
+}
@@ -0,0 +1,144 @@
+use engine_2d::animation::{SpineAtlas, SpineImport};
+use glam::Vec2;
+use std::f32::consts::FRAC_PI_2;
+
+fn approx(a: Vec2, b: Vec2) -> bool {
+    (a - b).length() < 1e-3
+}
+
+const ATLAS: &str = "
+hero.png
+size: 256, 256
+format: RGBA8888
+filter: Linear, Linear
+repeat: none
+body
+  rotate: false
+  xy: 2, 4
+  size: 32, 64
+  orig: 32, 64
+  offset: 0, 0
+  index: -1
+arm
+  bounds: 40, 2, 16, 48
+  rotate: 90
+";
+
+const SKELETON: &str = r#"{
+    "skeleton": { "spine": "3.8.99" },
+    "bones": [
+        { "name": "root" },
+        { "name": "arm", "parent": "root", "x": 10, "y": 0 }
+    ],
+    "slots": [
+        { "name": "body", "bone": "root", "attachment": "body" },
+        { "name": "arm", "bone": "arm", "attachment": "arm" },
+        { "name": "cape", "bone": "root", "attachment": "cape" }
+    ],
+    "skins": [{
+        "name": "default",
+        "attachments": {
+            "body": { "body": { "y": 32, "width": 32, "height": 64 } },
+            "arm": { "arm": { "x": 8, "rotation": 90, "width": 16, "height": 48 } },
+            "cape": { "cape": {
+                "type": "mesh",
+                "uvs": [0, 0, 1, 0, 0, 1],
+                "triangles": [0, 1, 2],
+                "vertices": [
+                    1, 0, 0, 0, 1,
+                    1, 1, 5, 0, 1,
+                    2, 0, 0, -4, 0.5, 1, 0, -4, 0.5
+                ]
+            } }
+        }
+    }],
+    "animations": {
+        "wave": {
+            "bones": {
+                "arm": {
+                    "rotate": [
+                        { "angle": 0, "curve": "stepped" },
+                        { "time": 1, "angle": 90 }
+                    ],
+                    "translate": [
+                        { "x": 0, "y": 0, "curve": 0.25, "c2": 0, "c3": 0.75, "c4": 1 },
+                        { "time": 1, "x": 0, "y": 10 }
+                    ]
+                }
+            }
+        }
+    }
+}"#;
+
+#[test]
+fn test_invalid_json_reports_its_position() {
+    let error = SpineImport::parse("{\n  \"bones\": [],\n  \"slots\" []\n}", None).unwrap_err();
+    assert!(error.contains("line 3"), "{}", error);
+    assert!(SpineImport::parse(r#"{"bones": []} 2"#, None).is_err());
+    assert!(SpineImport::parse(r#"{"slots": []}"#, None).is_err());
+}
+
+#[test]
+fn test_atlas_regions() {
+    let atlas = SpineAtlas::parse(ATLAS).unwrap();
+    assert_eq!(atlas.pages, vec!["hero.png".to_string()]);
+
+    let body = atlas.region("body").unwrap();
+    assert_eq!(body.page, "hero.png");
+    assert_eq!(body.region, (2.0, 4.0, 32.0, 64.0));
+    assert!(!body.rotated);
+
+    // Spine 4 writes a single bounds line and rotation in degrees
+    let arm = atlas.region("arm").unwrap();
+    assert_eq!(arm.region, (40.0, 2.0, 16.0, 48.0));
+    assert!(arm.rotated);
+    assert!(atlas.region("leg").is_none());
+
+    let error = SpineAtlas::parse("page.png\nbody\n  xy: 1\n").unwrap_err();
+    assert!(error.starts_with("line 3"), "{}", error);
+}
+
+#[test]
+fn test_skeleton_attachments_and_clips() {
+    let atlas = SpineAtlas::parse(ATLAS).unwrap();
+    let mut import = SpineImport::parse(SKELETON, Some(&atlas)).unwrap();
+
+    let skeleton = &import.skeleton;
+    assert_eq!(skeleton.bones().len(), 2);
+    assert_eq!(
+        skeleton.world_transform("arm").unwrap().position,
+        Vec2::new(10.0, 0.0)
+    );
+
+    // Region attachments keep their offset, size, atlas region and slot order
+    let attachments = skeleton.attachments();
+    assert_eq!(attachments.len(), 2);
+    let arm = &attachments[1];
+    assert_eq!(arm.name, "arm");
+    assert_eq!(arm.layer, 1);
+    assert_eq!(arm.size, Vec2::new(16.0, 48.0));
+    assert!((arm.offset.rotation - FRAC_PI_2).abs() < 1e-5);
+    assert_eq!(arm.region, Some((40.0, 2.0, 16.0, 48.0)));
+
+    // Weighted mesh vertices are placed in skeleton space
+    let cape = &import.meshes["cape"];
+    let positions: Vec<Vec2> = cape.vertices().iter().map(|v| v.position).collect();
+    assert!(approx(positions[1], Vec2::new(15.0, 0.0)));
+    assert!(approx(positions[2], Vec2::new(5.0, -4.0)));
+
+    // Keys are relative to the setup pose; the stepped rotation holds until the next key
+    let wave = &import.clips["wave"];
+    assert_eq!(wave.duration(), 1.0);
+    wave.apply(&mut import.skeleton, 0.5);
+    let arm = import.skeleton.bone("arm").unwrap();
+    assert_eq!(arm.pose.rotation, 0.0);
+    assert!(approx(arm.pose.position, Vec2::new(10.0, 5.0)));
+
+    wave.apply(&mut import.skeleton, 1.0);
+    let arm = import.skeleton.bone("arm").unwrap();
+    assert!((arm.pose.rotation - FRAC_PI_2).abs() < 1e-5);
+    assert!(approx(arm.pose.position, Vec2::new(10.0, 10.0)));
+
+    let error = SpineImport::parse(r#"{"bones": [{"name": "a", "parent": "b"}]}"#, None);
+    assert!(error.is_err());
+}
@@ -0,0 +1,109 @@
+use engine_2d::animation::{LoopMode, SpriteFrame, SpriteSheetAnimation};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn clip(loop_mode: LoopMode) -> SpriteSheetAnimation {
+    SpriteSheetAnimation::from_grid(2, 2, 3, (16.0, 8.0), 0.1)
+        .unwrap()
+        .with_loop_mode(loop_mode)
+}
+
+fn frames_over(animation: &mut SpriteSheetAnimation, steps: usize) -> Vec<usize> {
+    (0..steps)
+        .map(|_| {
+            animation.update(0.1);
+            animation.current_frame()
+        })
+        .collect()
+}
+
+#[test]
+fn test_grid_frames_and_validation() {
+    let animation = clip(LoopMode::Loop);
+    let regions: Vec<_> = animation.frames().iter().map(|f| f.region).collect();
+    assert_eq!(
+        regions,
+        vec![
+            (0.0, 0.0, 16.0, 8.0),
+            (16.0, 0.0, 16.0, 8.0),
+            (0.0, 8.0, 16.0, 8.0)
+        ]
+    );
+    assert!((animation.duration() - 0.3).abs() < 1e-6);
+
+    assert!(SpriteSheetAnimation::new(Vec::new()).is_err());
+    assert!(SpriteSheetAnimation::new(vec![SpriteFrame::new((0.0, 0.0, 1.0, 1.0), 0.0)]).is_err());
+    assert!(SpriteSheetAnimation::from_grid(2, 2, 5, (16.0, 16.0), 0.1).is_err());
+}
+
+#[test]
+fn test_loop_modes() {
+    let mut looping = clip(LoopMode::Loop);
+    looping.play();
+    assert_eq!(frames_over(&mut looping, 4), vec![1, 2, 0, 1]);
+
+    let mut ping_pong = clip(LoopMode::PingPong);
+    ping_pong.play();
+    assert_eq!(frames_over(&mut ping_pong, 6), vec![1, 2, 1, 0, 1, 2]);
+
+    let mut once = clip(LoopMode::Once);
+    once.play();
+    assert_eq!(frames_over(&mut once, 4), vec![1, 2, 2, 2]);
+    assert!(once.is_finished());
+    assert!(!once.is_playing());
+    once.play();
+    assert_eq!(once.current_frame(), 0);
+}
+
+#[test]
+fn test_reverse_playback_and_callbacks() {
+    let mut reverse = clip(LoopMode::Reverse);
+    assert_eq!(reverse.current_frame(), 2);
+    reverse.play();
+    assert_eq!(frames_over(&mut reverse, 4), vec![1, 0, 2, 1]);
+
+    let loops = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&loops);
+    let mut ping_pong = clip(LoopMode::PingPong).on_loop(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    ping_pong.play();
+    frames_over(&mut ping_pong, 8);
+    assert_eq!(loops.load(Ordering::SeqCst), 2);
+
+    let completed = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&completed);
+    let mut once = clip(LoopMode::Once).on_complete(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    once.play();
+    frames_over(&mut once, 2);
+    assert_eq!(completed.load(Ordering::SeqCst), 0);
+    frames_over(&mut once, 3);
+    assert_eq!(completed.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_play_pause_stop_and_per_frame_durations() {
+    let mut animation = SpriteSheetAnimation::new(vec![
+        SpriteFrame::new((0.0, 0.0, 8.0, 8.0), 0.5),
+        SpriteFrame::new((8.0, 0.0, 8.0, 8.0), 0.1),
+    ])
+    .unwrap()
+    .with_speed(2.0);
+
+    // Stopped clips don't advance
+    assert!(!animation.update(1.0));
+    animation.play();
+    assert!(!animation.update(0.2));
+    assert!(animation.update(0.06));
+    assert_eq!(animation.current_region(), (8.0, 0.0, 8.0, 8.0));
+
+    animation.pause();
+    assert!(!animation.update(1.0));
+    assert_eq!(animation.current_frame(), 1);
+
+    animation.stop();
+    assert_eq!(animation.current_frame(), 0);
+    assert!(!animation.is_playing());
+}
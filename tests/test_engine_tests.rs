@@ -0,0 +1,216 @@
+use engine_2d::ecs::{Stage, Transform, Velocity, World};
+use engine_2d::engine::{Game, TestEngine, TestEvents, UpdateContext};
+use engine_2d::events::event_types::LogicEvent;
+use engine_2d::input::*;
+use glam::Vec2;
+use std::time::Instant;
+
+fn jump_action() -> GameAction {
+    GameAction {
+        id: "JUMP".to_string(),
+        display_name: "Jump".to_string(),
+        category: ActionCategory::Movement,
+        input_type: InputType::Digital,
+        default_bindings: vec![InputBinding::Single(PhysicalInput::Keyboard(
+            KeyCode::Space,
+        ))],
+        metadata: ActionMetadata::default(),
+    }
+}
+
+/// Emits a jump event on frames where JUMP is pressed, and spawns a marker for each collision
+#[derive(Default)]
+struct JumpGame {
+    frame: u64,
+}
+
+impl Game for JumpGame {
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        let jumping = ctx
+            .world
+            .resource::<InputManager>()
+            .is_some_and(|input| input.is_action_pressed("JUMP"));
+        let hits: Vec<_> = ctx
+            .world
+            .resource::<TestEvents>()
+            .unwrap()
+            .received::<LogicEvent>()
+            .filter_map(|event| match event {
+                LogicEvent::CollisionDetected { entity1, .. } => Some(*entity1),
+                _ => None,
+            })
+            .collect();
+        for entity in hits {
+            ctx.world
+                .spawn((Transform::from_position(Vec2::splat(entity as f32)),));
+        }
+        if jumping {
+            ctx.world
+                .resource_mut::<TestEvents>()
+                .unwrap()
+                .emit(LogicEvent::GameStateChanged {
+                    new_state: format!("jump@{}", self.frame),
+                    timestamp: Instant::now(),
+                });
+        }
+        self.frame += 1;
+    }
+
+    fn name(&self) -> &str {
+        "Jump Game"
+    }
+}
+
+/// Records where the body is when the game updates, after the frame's systems
+struct TrackingGame;
+
+impl Game for TrackingGame {
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        let seen: Vec<_> = ctx
+            .world
+            .query::<&Transform>()
+            .map(|(_, transform)| transform.position.x)
+            .collect();
+        ctx.world.resource_or_default::<Vec<f32>>().extend(seen);
+    }
+
+    fn name(&self) -> &str {
+        "Tracking Game"
+    }
+}
+
+fn integrate(world: &mut World, delta_time: f32) {
+    for (_, (transform, velocity)) in world.query::<(&mut Transform, &Velocity)>() {
+        transform.position += velocity.0 * delta_time;
+    }
+}
+
+#[test]
+fn test_step_frames_runs_systems_before_the_game() {
+    let mut engine = TestEngine::new(Box::new(TrackingGame)).with_delta_time(0.5);
+    let body = engine
+        .world_mut()
+        .spawn((Transform::default(), Velocity(Vec2::new(2.0, 0.0))));
+    engine
+        .scheduler_mut()
+        .add_system(Stage::Physics, "integrate", integrate);
+
+    engine.step_frames(4);
+
+    assert_eq!(engine.frame(), 4);
+    assert_eq!(engine.elapsed_time(), 2.0);
+    let position = engine.world().get::<Transform>(body).unwrap().position;
+    assert_eq!(position, Vec2::new(4.0, 0.0));
+    let seen = engine.world().resource::<Vec<f32>>().unwrap();
+    assert_eq!(seen, &vec![1.0, 2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn test_scheduled_input_drives_the_game_and_emits_events() {
+    let mut engine = TestEngine::new(Box::new(JumpGame::default()));
+    engine.input_mut().register_action(jump_action());
+
+    engine.press_at(3, PhysicalInput::Keyboard(KeyCode::Space));
+    engine.release_at(5, PhysicalInput::Keyboard(KeyCode::Space));
+    engine.step_frames(8);
+
+    let jumps: Vec<_> = engine
+        .emitted::<LogicEvent>()
+        .into_iter()
+        .map(|event| match event {
+            LogicEvent::GameStateChanged { new_state, .. } => new_state.clone(),
+            other => panic!("unexpected event {:?}", other),
+        })
+        .collect();
+    assert_eq!(jumps, vec!["jump@3".to_string()]);
+
+    engine.clear_emitted();
+    assert!(engine.emitted::<LogicEvent>().is_empty());
+}
+
+#[test]
+fn test_sent_events_reach_the_game_on_the_next_frame() {
+    let mut engine = TestEngine::new(Box::new(JumpGame::default()));
+
+    engine.step();
+    engine.send_event(LogicEvent::CollisionDetected {
+        entity1: 7,
+        entity2: 8,
+        timestamp: Instant::now(),
+    });
+    assert!(engine.world().is_empty());
+
+    assert!(engine.step_until(5, |world| world.len() == 1));
+    assert_eq!(engine.frame(), 2);
+    engine.step_frames(3);
+    assert_eq!(engine.world().len(), 1);
+}
+
+/// Tracks the body like `TrackingGame`, and closes its pause menu on MENU_BACK
+struct PausableGame;
+
+impl Game for PausableGame {
+    fn update(&mut self, ctx: &mut UpdateContext<'_>) {
+        if !ctx.paused {
+            TrackingGame.update(ctx);
+            return;
+        }
+        let input = ctx.world.resource_mut::<InputManager>().unwrap();
+        if input.is_action_pressed("MENU_BACK") {
+            input.close_modal();
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Pausable Game"
+    }
+}
+
+#[test]
+fn test_pausing_modal_stops_gameplay_until_the_game_closes_it() {
+    let mut engine = TestEngine::new(Box::new(PausableGame)).with_delta_time(0.5);
+    engine.input_mut().register_action(GameAction {
+        id: "MENU_BACK".to_string(),
+        display_name: "Back".to_string(),
+        category: ActionCategory::UI,
+        input_type: InputType::Digital,
+        default_bindings: vec![InputBinding::Single(PhysicalInput::Keyboard(
+            KeyCode::Escape,
+        ))],
+        metadata: ActionMetadata::default(),
+    });
+    let body = engine
+        .world_mut()
+        .spawn((Transform::default(), Velocity(Vec2::new(2.0, 0.0))));
+    engine
+        .scheduler_mut()
+        .add_system(Stage::Physics, "integrate", integrate);
+
+    engine.step();
+    engine.input_mut().open_modal("pause_menu", true);
+    assert!(engine.engine().is_paused());
+    engine.step_frames(3);
+    let position = engine.world().get::<Transform>(body).unwrap().position;
+    assert_eq!(position, Vec2::new(1.0, 0.0));
+    assert_eq!(engine.world().resource::<Vec<f32>>().unwrap(), &vec![1.0]);
+
+    // The game itself closes the menu, and play resumes on the next frame
+    engine.tap(PhysicalInput::Keyboard(KeyCode::Escape), 1);
+    engine.step();
+    assert!(!engine.engine().is_paused());
+    engine.step();
+    let position = engine.world().get::<Transform>(body).unwrap().position;
+    assert_eq!(position, Vec2::new(2.0, 0.0));
+    assert_eq!(
+        engine.world().resource::<Vec<f32>>().unwrap(),
+        &vec![1.0, 2.0]
+    );
+
+    // A modal that doesn't pause leaves the game running
+    engine.input_mut().open_modal("inventory", false);
+    engine.step();
+    assert_eq!(
+        engine.world().resource::<Vec<f32>>().unwrap(),
+        &vec![1.0, 2.0, 3.0]
+    );
+}
@@ -0,0 +1,97 @@
+use engine_2d::input::{KeyCode, TextInput, TextInputEvent};
+use engine_2d::ui::TextField;
+
+#[test]
+fn test_editing_replaces_selection() {
+    let mut field = TextField::new().with_text("hello world");
+    assert_eq!(field.caret(), 11);
+
+    // Select "world" by word and overwrite it
+    field.move_word_left(true);
+    assert_eq!(field.selected_text(), Some("world"));
+    field.insert_str("there");
+    assert_eq!(field.text(), "hello there");
+
+    field.move_home(false);
+    field.delete();
+    field.backspace();
+    assert_eq!(field.text(), "ello there");
+    assert_eq!(field.caret(), 0);
+
+    // Newlines are dropped and the length limit holds
+    let mut field = TextField::new().with_max_length(5);
+    field.insert_str("ab\ncdefg");
+    assert_eq!(field.text(), "abcde");
+}
+
+#[test]
+fn test_clipboard_shortcuts() {
+    let mut clipboard = String::new();
+    let mut field = TextField::new().with_text("née");
+
+    assert!(field.handle_key(KeyCode::Left, false, true, &mut clipboard));
+    assert!(field.handle_key(KeyCode::Left, false, true, &mut clipboard));
+    assert!(field.handle_key(KeyCode::X, true, false, &mut clipboard));
+    assert_eq!(clipboard, "ée");
+    assert_eq!(field.text(), "n");
+
+    field.handle_key(KeyCode::Home, false, false, &mut clipboard);
+    field.handle_key(KeyCode::V, true, false, &mut clipboard);
+    assert_eq!(field.text(), "éen");
+    assert!(!field.handle_key(KeyCode::F1, false, false, &mut clipboard));
+}
+
+#[test]
+fn test_mouse_places_caret_and_drags_selection() {
+    let mut field = TextField::new().with_text("abcd");
+    let offsets = [0.0, 10.0, 20.0, 30.0, 40.0];
+
+    field.click(12.0, &offsets, false);
+    assert_eq!(field.caret(), 1);
+    field.drag_to(38.0, &offsets);
+    assert_eq!(field.selection(), Some(1..4));
+    assert_eq!(field.selection_span(&offsets), Some((10.0, 40.0)));
+
+    // Clicking without extending clears the selection
+    field.click(-5.0, &offsets, false);
+    assert_eq!(field.selection(), None);
+    assert_eq!(field.caret_x(&offsets), 0.0);
+}
+
+#[test]
+fn test_text_input_stream_feeds_field() {
+    let mut text_input = TextInput::new();
+    text_input.handle_char('h');
+    text_input.handle_char('\u{8}');
+    text_input.handle_char('é');
+    text_input.handle_composition("にほ", 2);
+    assert_eq!(text_input.composition(), Some(("にほ", 2)));
+    text_input.handle_text("日本");
+    assert!(!text_input.is_composing());
+
+    let events = text_input.drain();
+    assert_eq!(
+        events,
+        vec![
+            TextInputEvent::Text("hé".to_string()),
+            TextInputEvent::Composition {
+                text: "にほ".to_string(),
+                cursor: 2
+            },
+            TextInputEvent::Text("日本".to_string()),
+        ]
+    );
+    assert!(text_input.events().is_empty());
+
+    let mut field = TextField::new();
+    let used: Vec<bool> = events
+        .iter()
+        .map(|event| field.handle_text_input(event))
+        .collect();
+    assert_eq!(used, vec![true, false, true]);
+    assert_eq!(field.text(), "hé日本");
+
+    text_input.set_enabled(false);
+    text_input.handle_char('x');
+    assert_eq!(text_input.take_text(), "");
+}
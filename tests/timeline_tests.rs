@@ -0,0 +1,121 @@
+use engine_2d::animation::{Timeline, TimelinePlayer, Track, linear, timeline_system};
+use engine_2d::ecs::{Transform, World};
+use engine_2d::utils::math::interpolation;
+use glam::Vec2;
+
+fn approx(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-4
+}
+
+#[test]
+fn test_tracks_use_the_easing_of_the_key_they_approach() {
+    let track = Track::new()
+        .with_key(1.0, 10.0, interpolation::ease_in)
+        .with_key(0.0, 0.0, linear)
+        .with_key(2.0, 20.0, linear);
+
+    assert_eq!(track.sample(-1.0), Some(0.0));
+    assert!(approx(track.sample(0.5).unwrap(), 10.0 * 0.25));
+    assert!(approx(track.sample(1.5).unwrap(), 15.0));
+    assert_eq!(track.sample(5.0), Some(20.0));
+    assert_eq!(Track::<f32>::new().sample(0.0), None);
+}
+
+#[test]
+fn test_parse_timeline_json() {
+    let timeline = Timeline::parse(
+        r#"{
+            "position": [
+                { "time": 1, "value": [100, 50], "ease": "ease_out" },
+                { "time": 0, "value": [0, 0] }
+            ],
+            "rotation": [{ "time": 1, "value": 3.0 }],
+            "color": [{ "time": 2, "value": [1, 1, 1, 0] }],
+            "looping": true
+        }"#,
+    )
+    .unwrap();
+
+    assert!(timeline.looping);
+    assert_eq!(timeline.duration(), 2.0);
+    assert_eq!(timeline.position.keys().len(), 2);
+    assert_eq!(timeline.position.keys()[0].time, 0.0);
+    assert!(approx(
+        timeline.sample(0.5).position.unwrap().x,
+        100.0 * 0.75
+    ));
+    let sample = timeline.sample(1.0);
+    assert_eq!(sample.position, Some(Vec2::new(100.0, 50.0)));
+    assert_eq!(sample.rotation, Some(3.0));
+    assert_eq!(sample.scale, None);
+    assert_eq!(sample.color, Some((1.0, 1.0, 1.0, 0.0)));
+
+    let error = Timeline::parse(
+        "{\"scale\": [\n  {\"time\": 0, \"value\": [1, 1]},\n  {\"time\": 1, \"value\": [2]}\n]}",
+    )
+    .unwrap_err();
+    assert!(error.contains("line 3"), "{}", error);
+    let error =
+        Timeline::parse(r#"{"position": [{"time": 0, "value": [1, 1], "ease": "wobble"}]}"#)
+            .unwrap_err();
+    assert!(error.contains("unknown easing 'wobble'"), "{}", error);
+    assert!(Timeline::parse(r#"{"opacity": []}"#).is_err());
+}
+
+#[test]
+fn test_timeline_system_drives_transforms() {
+    let timeline = Timeline::parse(
+        r#"{
+            "position": [{ "time": 0, "value": [0, 0] }, { "time": 1, "value": [10, 0] }],
+            "scale": [{ "time": 1, "value": [2, 2] }]
+        }"#,
+    )
+    .unwrap();
+    let mut world = World::new();
+    let entity = world.spawn((Transform::default(), TimelinePlayer::new(timeline)));
+
+    timeline_system(&mut world, 0.5);
+    let transform = world.get::<Transform>(entity).unwrap();
+    assert_eq!(transform.position, Vec2::new(5.0, 0.0));
+    assert_eq!(transform.scale, Vec2::new(2.0, 2.0));
+
+    timeline_system(&mut world, 1.0);
+    assert_eq!(
+        world.get::<Transform>(entity).unwrap().position,
+        Vec2::new(10.0, 0.0)
+    );
+    let player = world.get::<TimelinePlayer>(entity).unwrap();
+    assert!(player.is_finished());
+    assert!(!player.is_playing());
+}
+
+#[test]
+fn test_player_speed_looping_and_seeking() {
+    let mut timeline = Timeline::new().with_looping(true);
+    timeline.rotation = Track::new()
+        .with_key(0.0, 0.0, linear)
+        .with_key(2.0, 4.0, linear);
+
+    // Double speed wraps past the end back into the cycle
+    let mut player = TimelinePlayer::new(timeline.clone()).with_speed(2.0);
+    assert_eq!(player.update(0.5).rotation, Some(2.0));
+    assert_eq!(player.update(0.75).rotation, Some(1.0));
+    assert!(!player.is_finished());
+
+    player.pause();
+    assert_eq!(player.update(1.0).rotation, Some(1.0));
+    player.seek(5.0);
+    assert_eq!(player.time(), 2.0);
+    player.stop();
+    assert_eq!((player.time(), player.is_playing()), (0.0, false));
+
+    // A shorter edit clamps the playback time
+    player.seek(1.5);
+    let mut shorter = Timeline::new();
+    shorter.rotation = Track::new().with_key(1.0, 1.0, linear);
+    player.set_timeline(shorter);
+    assert_eq!(player.time(), 1.0);
+    assert_eq!(player.speed(), 2.0);
+    player.set_speed(-1.0);
+    assert_eq!(player.speed(), 0.0);
+}
@@ -0,0 +1,121 @@
+use engine_2d::animation::{TweenManager, tween};
+use engine_2d::utils::math::interpolation;
+use glam::Vec2;
+use std::cell::Cell;
+use std::rc::Rc;
+
+fn approx(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-4
+}
+
+#[test]
+fn test_chained_targets_with_easing_and_delay() {
+    let mut slide = tween(Vec2::ZERO)
+        .to(Vec2::new(10.0, 0.0))
+        .over(1.0)
+        .ease(interpolation::ease_in)
+        .to(Vec2::new(10.0, 20.0))
+        .over(0.5)
+        .delay(0.5);
+
+    assert_eq!(slide.update(0.25), Vec2::ZERO);
+    // 0.25 s of delay left, then 0.25 s into an ease-in segment
+    assert!(approx(slide.update(0.5).x, 10.0 * 0.25 * 0.25));
+    assert_eq!(slide.update(0.75), Vec2::new(10.0, 0.0));
+    assert_eq!(slide.update(0.25), Vec2::new(10.0, 10.0));
+    assert!(!slide.is_finished());
+    assert_eq!(slide.update(1.0), Vec2::new(10.0, 20.0));
+    assert!(slide.is_finished());
+}
+
+#[test]
+fn test_repeat_yoyo_and_completion_callback() {
+    let completed = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&completed);
+    let mut pulse = tween(0.0f32)
+        .to(1.0)
+        .over(1.0)
+        .repeat(2)
+        .yoyo(true)
+        .on_complete(move || counter.set(counter.get() + 1));
+
+    let values: Vec<f32> = (0..6).map(|_| pulse.update(0.5)).collect();
+    assert_eq!(values, vec![0.5, 1.0, 0.5, 0.0, 0.5, 1.0]);
+    assert!(pulse.is_finished());
+    assert_eq!(completed.get(), 1);
+    pulse.update(1.0);
+    assert_eq!(completed.get(), 1);
+
+    pulse.restart();
+    assert_eq!(pulse.value(), 0.0);
+    assert!(!pulse.is_finished());
+}
+
+#[test]
+fn test_manager_updates_mixed_types() {
+    let mut tweens = TweenManager::new();
+    let alpha = tweens.add(tween(1.0f32).to(0.0).over(0.5));
+    let tint = tweens.add(tween((1.0f32, 1.0, 1.0)).to((1.0, 0.0, 0.0)).over(2.0));
+    let forever = tweens.add(tween(0.0f32).to(1.0).repeat_forever());
+
+    tweens.update(0.5);
+    assert_eq!(tweens.value::<f32>(alpha), Some(0.0));
+    assert_eq!(
+        tweens.value::<(f32, f32, f32)>(tint),
+        Some((1.0, 0.75, 0.75))
+    );
+    assert_eq!(tweens.value::<Vec2>(tint), None);
+
+    // Finished tweens are dropped on the next update
+    tweens.update(0.5);
+    assert!(!tweens.contains(alpha));
+    assert_eq!(tweens.len(), 2);
+    assert!(approx(tweens.value::<f32>(forever).unwrap(), 0.0));
+
+    assert!(tweens.remove(forever));
+    tweens.update(5.0);
+    tweens.update(0.0);
+    assert!(tweens.is_empty());
+}
+
+#[test]
+fn test_long_updates_span_repeats_and_zero_length_segments() {
+    let mut ramp = tween(0.0f32).to(1.0).over(1.0).repeat(3);
+    assert!(approx(ramp.update(2.5), 0.5));
+    assert!(!ramp.is_finished());
+    assert_eq!(ramp.update(10.0), 1.0);
+    assert!(ramp.is_finished());
+
+    // A zero-length segment jumps straight to its target
+    let mut snap = tween(0.0f32).to(5.0).over(0.0).to(10.0).over(1.0);
+    assert_eq!(snap.update(0.0), 5.0);
+    assert_eq!(snap.update(0.5), 7.5);
+
+    // Repeating a zero-length tween forever finishes instead of spinning
+    let mut instant = tween(0.0f32).to(1.0).over(0.0).repeat_forever();
+    assert_eq!(instant.update(0.1), 1.0);
+    assert!(instant.is_finished());
+}
+
+#[test]
+fn test_manager_time_scale_and_one_time_delay() {
+    let mut tweens = TweenManager::new();
+    tweens.set_time_scale(0.5);
+    let id = tweens.add(tween(0.0f32).to(10.0).over(1.0).delay(1.0).repeat(1));
+
+    tweens.update(2.0);
+    assert_eq!(tweens.value::<f32>(id), Some(0.0));
+    tweens.update(1.0);
+    assert_eq!(tweens.value::<f32>(id), Some(5.0));
+
+    // The repeat starts right away; the delay only applies before the first play
+    tweens.update(1.0);
+    assert_eq!(tweens.value::<f32>(id), Some(0.0));
+    tweens.update(1.0);
+    assert_eq!(tweens.value::<f32>(id), Some(5.0));
+
+    // Restarting waits out the delay again
+    tweens.get_mut::<f32>(id).unwrap().restart();
+    tweens.update(1.0);
+    assert_eq!(tweens.value::<f32>(id), Some(0.0));
+}
@@ -0,0 +1,102 @@
+use engine_2d::input::InputManager;
+use engine_2d::render::viewport::{ScalingPolicy, ViewMapping, ViewportRect};
+use glam::Vec2;
+
+fn rect(x: i32, y: i32, width: i32, height: i32) -> ViewportRect {
+    ViewportRect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn test_fit_letterboxes_and_fill_crops() {
+    let design = (800, 600);
+    let wide = (1600, 900);
+
+    assert_eq!(
+        ScalingPolicy::Stretch.viewport_rect(design, wide),
+        rect(0, 0, 1600, 900)
+    );
+    assert_eq!(
+        ScalingPolicy::Fit.viewport_rect(design, wide),
+        rect(200, 0, 1200, 900)
+    );
+    assert_eq!(
+        ScalingPolicy::Fill.viewport_rect(design, wide),
+        rect(0, -150, 1600, 1200)
+    );
+}
+
+#[test]
+fn test_integer_scale_uses_whole_multiples() {
+    let design = (320, 180);
+
+    assert_eq!(
+        ScalingPolicy::IntegerScale.viewport_rect(design, (1920, 1200)),
+        rect(0, 60, 1920, 1080)
+    );
+    assert_eq!(
+        ScalingPolicy::IntegerScale.viewport_rect(design, (1000, 700)),
+        rect(20, 80, 960, 540)
+    );
+    // Never below 1x, even if the window is smaller than the design size
+    assert_eq!(
+        ScalingPolicy::IntegerScale.viewport_rect(design, (200, 100)),
+        rect(-60, -40, 320, 180)
+    );
+}
+
+#[test]
+fn test_cursor_on_letterbox_bar_maps_to_none() {
+    let view = ScalingPolicy::Fit.viewport_rect((800, 600), (1600, 900));
+
+    assert_eq!(view.to_normalized(100.0, 450.0, 900), None);
+    let center = view.to_normalized(800.0, 450.0, 900).unwrap();
+    assert!((center.x - 0.5).abs() < 1e-5 && (center.y - 0.5).abs() < 1e-5);
+    let top_left = view.to_normalized(200.0, 0.0, 900).unwrap();
+    assert!(top_left.x.abs() < 1e-5 && top_left.y.abs() < 1e-5);
+}
+
+#[test]
+fn test_cursor_maps_through_letterbox_on_high_dpi() {
+    // An 800x600 design fitted into a 1000x600 window rendered at 2x
+    let view = ViewMapping {
+        window_size: (1000, 600),
+        framebuffer_size: (2000, 1200),
+        viewport_rect: ScalingPolicy::Fit.viewport_rect((800, 600), (2000, 1200)),
+        logical_bounds: (0.0, 800.0, 0.0, 600.0),
+    };
+    assert_eq!(
+        view.cursor_to_normalized(Vec2::new(500.0, 300.0)),
+        Some(Vec2::new(0.5, 0.5))
+    );
+    assert_eq!(
+        view.cursor_to_logical(Vec2::new(100.0, 0.0)),
+        Some(Vec2::new(0.0, 600.0))
+    );
+    assert_eq!(view.cursor_to_logical(Vec2::new(50.0, 300.0)), None);
+}
+
+#[test]
+fn test_input_manager_mouse_world_position() {
+    let mut input = InputManager::new();
+    input.set_cursor_position(Vec2::new(200.0, 150.0));
+    assert_eq!(input.mouse_world_position(), None);
+
+    input.set_view_mapping(ViewMapping {
+        window_size: (800, 600),
+        framebuffer_size: (800, 600),
+        viewport_rect: rect(0, 0, 800, 600),
+        logical_bounds: (-1.0, 1.0, -1.0, 1.0),
+    });
+    input.set_camera_position(Vec2::new(10.0, 5.0));
+    assert_eq!(input.mouse_viewport_position(), Some(Vec2::new(0.25, 0.25)));
+    assert_eq!(input.mouse_logical_position(), Some(Vec2::new(-0.5, 0.5)));
+    assert_eq!(input.mouse_world_position(), Some(Vec2::new(9.5, 5.5)));
+
+    input.clear_cursor_position();
+    assert_eq!(input.mouse_logical_position(), None);
+}
@@ -0,0 +1,62 @@
+use engine_2d::physics::visibility::{
+    VisibilityPolygon, has_line_of_sight, rectangle_segments, within_view_cone,
+};
+use engine_2d::utils::math::geometry::{LineSegment, Rectangle};
+use glam::Vec2;
+
+fn room() -> Rectangle {
+    Rectangle::new(Vec2::new(-10.0, -10.0), Vec2::new(20.0, 20.0))
+}
+
+#[test]
+fn test_open_room_is_fully_visible() {
+    let visibility = VisibilityPolygon::compute(Vec2::ZERO, &[], room());
+
+    assert!(visibility.contains_point(Vec2::new(9.0, 9.0)));
+    assert!(visibility.contains_point(Vec2::new(-9.0, 5.0)));
+    assert!(!visibility.contains_point(Vec2::new(11.0, 0.0)));
+
+    let fan = visibility.triangle_fan();
+    assert_eq!(fan[0], Vec2::ZERO);
+    assert_eq!(fan[1], *fan.last().unwrap());
+}
+
+#[test]
+fn test_wall_casts_shadow() {
+    let wall = [LineSegment::new(Vec2::new(2.0, -2.0), Vec2::new(2.0, 2.0))];
+    let visibility = VisibilityPolygon::compute(Vec2::ZERO, &wall, room());
+
+    // Directly behind the wall is hidden, around it is not
+    assert!(!visibility.contains_point(Vec2::new(5.0, 0.0)));
+    assert!(visibility.contains_point(Vec2::new(5.0, 8.0)));
+    assert!(visibility.contains_point(Vec2::new(1.0, 0.0)));
+
+    assert!(!has_line_of_sight(Vec2::ZERO, Vec2::new(5.0, 0.0), &wall));
+    assert!(has_line_of_sight(Vec2::ZERO, Vec2::new(5.0, 8.0), &wall));
+}
+
+#[test]
+fn test_tile_occluders_and_view_cone() {
+    let tile = Rectangle::new(Vec2::new(3.0, -1.0), Vec2::new(2.0, 2.0));
+    let occluders = rectangle_segments(&tile);
+    assert!(!has_line_of_sight(
+        Vec2::ZERO,
+        Vec2::new(8.0, 0.0),
+        &occluders
+    ));
+
+    let facing = Vec2::X;
+    let half_angle = 30f32.to_radians();
+    assert!(within_view_cone(
+        Vec2::ZERO,
+        facing,
+        half_angle,
+        Vec2::new(5.0, 1.0)
+    ));
+    assert!(!within_view_cone(
+        Vec2::ZERO,
+        facing,
+        half_angle,
+        Vec2::new(0.0, 5.0)
+    ));
+}
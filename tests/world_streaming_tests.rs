@@ -0,0 +1,100 @@
+use engine_2d::engine::{StreamingEvent, WorldStreamer};
+use glam::{IVec2, Vec2};
+use std::thread;
+use std::time::Duration;
+
+/// Update until no loads are pending, collecting every event
+fn settle(streamer: &mut WorldStreamer<String>, camera: Vec2) -> Vec<StreamingEvent<String>> {
+    let mut events = streamer.update(camera);
+    for _ in 0..200 {
+        if !streamer.has_pending() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+        events.extend(streamer.update(camera));
+    }
+    events
+}
+
+fn streamer() -> WorldStreamer<String> {
+    WorldStreamer::new(Vec2::splat(100.0), 10.0, 60.0, |coord| {
+        if coord == IVec2::new(5, 0) {
+            Err("corrupt section".to_string())
+        } else {
+            Ok(format!("section {} {}", coord.x, coord.y))
+        }
+    })
+}
+
+#[test]
+fn test_sections_load_around_camera() {
+    let mut streamer = streamer();
+    let events = settle(&mut streamer, Vec2::new(95.0, 50.0));
+
+    // The camera's section and the neighbour within the load radius
+    let activated = events
+        .iter()
+        .filter(|event| matches!(event, StreamingEvent::Activated(_)))
+        .count();
+    assert_eq!(activated, 2);
+    assert!(streamer.is_active(IVec2::new(0, 0)));
+    assert_eq!(
+        streamer.section(IVec2::new(1, 0)).map(String::as_str),
+        Some("section 1 0")
+    );
+    assert!(!streamer.is_active(IVec2::new(-1, 0)));
+}
+
+#[test]
+fn test_unload_uses_hysteresis() {
+    let mut streamer = streamer();
+    settle(&mut streamer, Vec2::new(95.0, 50.0));
+
+    // 40 units past the border: beyond the load radius but inside the unload radius
+    settle(&mut streamer, Vec2::new(140.0, 50.0));
+    assert!(streamer.is_active(IVec2::new(0, 0)));
+
+    let events = settle(&mut streamer, Vec2::new(170.0, 50.0));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        StreamingEvent::Deactivated(coord, data) if *coord == IVec2::ZERO && data == "section 0 0"
+    )));
+    assert!(!streamer.is_active(IVec2::ZERO));
+}
+
+#[test]
+fn test_failed_sections_are_reported_once() {
+    let mut streamer = streamer();
+    let events = settle(&mut streamer, Vec2::new(550.0, 50.0));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        StreamingEvent::LoadFailed(coord, _) if *coord == IVec2::new(5, 0)
+    )));
+
+    // Not retried while the camera stays nearby
+    let events = settle(&mut streamer, Vec2::new(550.0, 50.0));
+    assert!(events.is_empty());
+}
+
+#[test]
+fn test_many_sections_share_a_few_loader_threads() {
+    // A large radius requests dozens of sections in one update
+    let mut streamer = WorldStreamer::new(Vec2::splat(10.0), 40.0, 40.0, |_| {
+        Ok(thread::current().name().unwrap_or_default().to_string())
+    });
+    let events = settle(&mut streamer, Vec2::ZERO);
+    assert!(events.len() > 20, "{} sections", events.len());
+
+    let mut threads: Vec<&str> = streamer
+        .active_sections()
+        .map(|(_, thread)| thread.as_str())
+        .collect();
+    threads.sort();
+    threads.dedup();
+    assert!(threads.len() <= 2, "{:?}", threads);
+    assert!(
+        threads
+            .iter()
+            .all(|name| name.starts_with("section-loader-"))
+    );
+}